@@ -0,0 +1,212 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! ⏱️ `kvx-cli bench` — run a config's pipeline, then say how fast it went and what it cost.
+//!
+//! 🎬 *[release notes: "performance improvements"]*
+//! *[nobody measured. nobody ever measures.]*
+//! *[the next release is 30% slower. nobody notices for four months.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Any config works. `[source_config.Generator]` and `[sink_config.Null]` take the disk
+//!   and the network out of the numbers; `configs/kvx_bench_*.toml` are ready-made pairs
+//! - Docs, bytes and elapsed come from the run's final `ProgressEvent` — the same counters
+//!   the progress bar shows, so bytes are payload bytes as the sink received them
+//! - Allocation stats come from `CountingAllocator`, this binary's global allocator. It only
+//!   counts while a bench is running; a normal run pays one relaxed load per allocation
+//! - `--json` prints one JSON line instead of the table, for diffing across releases 🦆
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use kvx::config::AppConfig;
+use kvx::progress::{ProgressEvent, ProgressSubscriber};
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicI64 = AtomicI64::new(0);
+static PEAK_LIVE_BYTES: AtomicI64 = AtomicI64::new(0);
+
+/// 🧮 The system allocator, plus a tally while `COUNTING` is on.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        if COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+            let the_live = LIVE_BYTES.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+            PEAK_LIVE_BYTES.fetch_max(the_live, Ordering::Relaxed);
+        }
+    }
+
+    fn record_dealloc(size: usize) {
+        if COUNTING.load(Ordering::Relaxed) {
+            LIVE_BYTES.fetch_sub(size as i64, Ordering::Relaxed);
+        }
+    }
+}
+
+// SAFETY: every call is forwarded to `System` unchanged; the tally never touches the memory.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record_alloc(layout.size());
+        // SAFETY: same contract as ours — forwarded as is
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record_alloc(layout.size());
+        // SAFETY: same contract as ours — forwarded as is
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::record_dealloc(layout.size());
+        // SAFETY: same contract as ours — forwarded as is
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // -- 🔁 a realloc is one allocation of the new size, and the old one let go
+        Self::record_dealloc(layout.size());
+        Self::record_alloc(new_size);
+        // SAFETY: same contract as ours — forwarded as is
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// 📊 What the allocator saw between `start` and `stop`.
+#[derive(Debug, Clone, Copy)]
+struct AllocationStats {
+    allocations: u64,
+    allocated_bytes: u64,
+    /// 🏔️ most bytes live at once, counting only allocations made during the bench
+    peak_live_bytes: u64,
+}
+
+fn start_counting() {
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+    LIVE_BYTES.store(0, Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(0, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::Release);
+}
+
+fn stop_counting() -> AllocationStats {
+    COUNTING.store(false, Ordering::Release);
+    AllocationStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        peak_live_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed).max(0) as u64,
+    }
+}
+
+/// ⏱️ Keeps the latest progress event — after the run, that's the `finished` one.
+struct Stopwatch(Mutex<Option<ProgressEvent>>);
+
+impl ProgressSubscriber for Stopwatch {
+    fn on_progress(&self, event: &ProgressEvent) {
+        *self.0.lock().expect("💀 stopwatch lock poisoned") = Some(event.clone());
+    }
+}
+
+/// 📋 One bench run, boiled down.
+#[derive(Debug)]
+struct BenchReport {
+    pipeline: String,
+    docs: u64,
+    bytes: u64,
+    elapsed: Duration,
+    allocations: AllocationStats,
+}
+
+impl BenchReport {
+    fn seconds(&self) -> f64 {
+        // -- 🐇 a run too quick to time still shouldn't divide by zero
+        self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    fn docs_per_sec(&self) -> f64 {
+        self.docs as f64 / self.seconds()
+    }
+
+    fn mib_per_sec(&self) -> f64 {
+        self.bytes as f64 / MIB / self.seconds()
+    }
+
+    fn allocations_per_doc(&self) -> f64 {
+        self.allocations.allocations as f64 / self.docs.max(1) as f64
+    }
+
+    fn to_table(&self) -> String {
+        let the_rows = [
+            ("kvx", env!("CARGO_PKG_VERSION").to_string()),
+            ("pipeline", self.pipeline.clone()),
+            ("docs", self.docs.to_string()),
+            ("payload bytes", format!("{} ({:.1} MiB)", self.bytes, self.bytes as f64 / MIB)),
+            ("elapsed", format!("{:.3}s", self.elapsed.as_secs_f64())),
+            ("docs/s", format!("{:.0}", self.docs_per_sec())),
+            ("MiB/s", format!("{:.1}", self.mib_per_sec())),
+            ("allocations", self.allocations.allocations.to_string()),
+            ("allocations/doc", format!("{:.2}", self.allocations_per_doc())),
+            ("allocated", format!("{:.1} MiB", self.allocations.allocated_bytes as f64 / MIB)),
+            ("peak live", format!("{:.1} MiB", self.allocations.peak_live_bytes as f64 / MIB)),
+        ];
+        the_rows.iter().map(|(the_key, the_value)| format!("{the_key:>16}  {the_value}\n")).collect()
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "pipeline": self.pipeline,
+            "docs": self.docs,
+            "bytes": self.bytes,
+            "elapsed_secs": self.elapsed.as_secs_f64(),
+            "docs_per_sec": self.docs_per_sec(),
+            "mib_per_sec": self.mib_per_sec(),
+            "allocations": self.allocations.allocations,
+            "allocated_bytes": self.allocations.allocated_bytes,
+            "peak_live_bytes": self.allocations.peak_live_bytes,
+        })
+        .to_string()
+    }
+}
+
+/// ⏱️ Run the pipeline with the allocator counting, then print the report to stdout.
+pub async fn run(mut app_config: AppConfig, as_json: bool) -> Result<()> {
+    let the_stopwatch = Arc::new(Stopwatch(Mutex::new(None)));
+    app_config.progress.subscribe(the_stopwatch.clone());
+
+    start_counting();
+    let the_outcome = kvx::run(app_config).await;
+    let the_allocations = stop_counting();
+    the_outcome?;
+
+    let the_final = the_stopwatch
+        .0
+        .lock()
+        .expect("💀 stopwatch lock poisoned")
+        .take()
+        .context("💀 The run finished without a single progress event — nothing to report")?;
+    let the_report = BenchReport {
+        pipeline: the_final.pipeline_name,
+        docs: the_final.estimated_docs,
+        bytes: the_final.bytes_drained,
+        elapsed: the_final.elapsed,
+        allocations: the_allocations,
+    };
+    if as_json {
+        println!("{}", the_report.to_json());
+    } else {
+        print!("{}", the_report.to_table());
+    }
+    Ok(())
+}
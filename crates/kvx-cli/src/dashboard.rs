@@ -0,0 +1,321 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🖥️ `kvx-cli --dashboard` — the whole terminal, for one migration.
+//!
+//! 🎬 *[a progress bar. one line. it says 41%. it has said 41% for a while.]*
+//! *[is a drainer stuck? are the retries piling up? the logs went to a file nobody tails.]*
+//! *[the operator stares at the one line. the one line stares back.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `Dashboard` is a `ProgressSubscriber`: every reporter tick (500ms) redraws a full-screen
+//!   frame — header, progress bar, a rolling MiB/s sparkline, one row per drainer
+//!   (`ProgressEvent::drainers`), counters, and the newest log lines
+//! - `LogTail` is the tracing writer while the dashboard owns the screen: it keeps the last
+//!   `LOG_TAIL_LINES` lines and counts WARN/ERROR lines for the counters. On `restore` the
+//!   kept lines go to stderr, and anything logged after that goes straight there too
+//! - Keys, read from stdin in raw mode on a plain thread: `p` flips the `PauseSwitch`
+//!   (`AppConfig::pause` — the pumpers stop reading, in-flight work drains), `q` or Ctrl-C
+//!   cancels the run's `CancellationToken` — the same graceful stop an embedder gets
+//! - Drawn with crossterm: alternate screen + raw mode, given back on the `finished` event,
+//!   or by `main` when the run fails before one arrives. Needs a terminal on stdout 🦆
+
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use kvx::CancellationToken;
+use kvx::progress::{ProgressEvent, ProgressSubscriber};
+use kvx::workers::PauseSwitch;
+
+const MIB: f64 = 1024.0 * 1024.0;
+/// 📜 log lines kept for the tail panel (and echoed to stderr when the dashboard closes)
+const LOG_TAIL_LINES: usize = 200;
+/// 📈 throughput samples kept — one per tick, ~4 minutes at 500ms
+const HISTORY_SAMPLES: usize = 480;
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const CTRL_C: u8 = 0x03;
+
+/// 📜 The last few hundred log lines, and how many of them were bad news.
+#[derive(Debug, Default)]
+pub struct LogTail {
+    lines: Mutex<VecDeque<String>>,
+    warnings: AtomicU64,
+    errors: AtomicU64,
+    /// 📤 dashboard closed — new lines go to stderr as they come
+    echo: AtomicBool,
+}
+
+impl LogTail {
+    /// ✍️ A writer for `tracing_subscriber::fmt().with_writer(...)` — one per event.
+    pub fn writer(self: &Arc<Self>) -> LogTailWriter {
+        LogTailWriter(self.clone())
+    }
+
+    fn push(&self, line: &str) {
+        if self.echo.load(Ordering::Relaxed) {
+            eprintln!("{}", line);
+            return;
+        }
+        // -- 🚦 fmt pads the level to five columns: "2026-… ERROR kvx::…", "2026-…  WARN kvx::…"
+        if line.contains(" ERROR ") {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        } else if line.contains(" WARN ") {
+            self.warnings.fetch_add(1, Ordering::Relaxed);
+        }
+        let mut the_lines = self.lines.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if the_lines.len() == LOG_TAIL_LINES {
+            the_lines.pop_front();
+        }
+        the_lines.push_back(line.to_string());
+    }
+
+    fn newest(&self, count: usize) -> Vec<String> {
+        let the_lines = self.lines.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        the_lines.iter().skip(the_lines.len().saturating_sub(count)).cloned().collect()
+    }
+
+    /// 📤 Hand everything kept to stderr, and send new lines there from now on.
+    fn release(&self) {
+        let the_kept: Vec<String> = self.lines.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).drain(..).collect();
+        self.echo.store(true, Ordering::Relaxed);
+        for the_line in the_kept {
+            eprintln!("{}", the_line);
+        }
+    }
+}
+
+/// ✍️ `io::Write` into a `LogTail`, split on newlines.
+pub struct LogTailWriter(Arc<LogTail>);
+
+impl Write for LogTailWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for the_line in String::from_utf8_lossy(buf).lines().filter(|l| !l.is_empty()) {
+            self.0.push(the_line);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 🖥️ The full-screen view of one run.
+pub struct Dashboard {
+    the_log_tail: Arc<LogTail>,
+    the_pause: PauseSwitch,
+    the_cancel: CancellationToken,
+    the_history: Mutex<VecDeque<f64>>,
+    /// 🔚 terminal given back — no more frames
+    restored: AtomicBool,
+}
+
+impl Dashboard {
+    /// 🖥️ Take over the terminal and start listening for `p` / `q`.
+    pub fn open(the_log_tail: Arc<LogTail>, the_pause: PauseSwitch, the_cancel: CancellationToken) -> Result<Arc<Self>> {
+        if !std::io::stdout().is_terminal() {
+            the_log_tail.release();
+            anyhow::bail!("💀 --dashboard needs a terminal on stdout — drop the flag when piping or redirecting.");
+        }
+        terminal::enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen, Hide)?;
+        let the_dashboard = Arc::new(Self {
+            the_log_tail,
+            the_pause,
+            the_cancel,
+            the_history: Mutex::new(VecDeque::with_capacity(HISTORY_SAMPLES)),
+            restored: AtomicBool::new(false),
+        });
+        // -- ⌨️ a blocked stdin read can't be cancelled, so the thread is left to die with the process
+        let the_listener = the_dashboard.clone();
+        std::thread::Builder::new().name("kvx-dashboard-keys".to_string()).spawn(move || the_listener.listen())?;
+        Ok(the_dashboard)
+    }
+
+    /// ⌨️ One byte at a time — raw mode delivers keys unbuffered, Ctrl-C included.
+    fn listen(&self) {
+        let mut the_stdin = std::io::stdin();
+        let mut the_key = [0u8; 1];
+        while !self.restored.load(Ordering::Relaxed) {
+            match the_stdin.read(&mut the_key) {
+                Ok(0) | Err(_) => return,
+                Ok(_) => match the_key[0] {
+                    b'p' | b'P' => {
+                        self.the_pause.fetch_xor(true, Ordering::Relaxed);
+                    }
+                    b'q' | b'Q' | CTRL_C => self.the_cancel.cancel(),
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    /// 🔚 Give the terminal back and flush the kept log lines to stderr. Safe to call twice.
+    pub fn restore(&self) {
+        if self.restored.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let _ = execute!(std::io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+        self.the_log_tail.release();
+    }
+
+    fn state(&self, event: &ProgressEvent) -> &'static str {
+        if event.finished {
+            "🏁 FINISHED"
+        } else if self.the_cancel.is_cancelled() {
+            "🛑 STOPPING"
+        } else if self.the_pause.load(Ordering::Relaxed) {
+            "⏸️ PAUSED"
+        } else {
+            "▶️ RUNNING"
+        }
+    }
+
+    /// 🎨 One frame, as lines — `width` columns, at most `height` rows.
+    fn frame(&self, event: &ProgressEvent, width: usize, height: usize) -> Vec<String> {
+        let mut the_frame = vec![
+            format!(
+                " kravex · {}   {}   elapsed {}   eta {}",
+                event.pipeline_name,
+                self.state(event),
+                clock(event.elapsed),
+                event.eta.map(clock).unwrap_or_else(|| "--:--:--".to_string())
+            ),
+            progress_line(event, width),
+            String::new(),
+            format!(
+                " throughput   {:.1} MiB/s   {:.0} docs/s",
+                event.mib_per_sec, event.docs_per_sec
+            ),
+            format!(" {}", sparkline(&self.the_history.lock().unwrap_or_else(|p| p.into_inner()), width.saturating_sub(2))),
+            String::new(),
+            " drainers".to_string(),
+        ];
+        let the_busiest = event.drainers.iter().map(|d| d.bytes_drained).max().unwrap_or(0).max(1);
+        for (the_index, the_drainer) in event.drainers.iter().enumerate() {
+            let the_row = format!(
+                "  #{:<3} {:>10.1} MiB {:>8} req {:>6} ms  ",
+                the_index,
+                the_drainer.bytes_drained as f64 / MIB,
+                the_drainer.requests_completed,
+                the_drainer.last_latency_ms
+            );
+            let the_room = width.saturating_sub(the_row.chars().count() + 1);
+            let the_filled = (the_drainer.bytes_drained as f64 / the_busiest as f64 * the_room as f64) as usize;
+            the_frame.push(format!("{}{}", the_row, "■".repeat(the_filled)));
+        }
+        if event.drainers.is_empty() {
+            the_frame.push("  (no per-drainer counters for this run)".to_string());
+        }
+        the_frame.push(String::new());
+        the_frame.push(format!(
+            " docs {}   requests {}   latency avg {} ms / max {} ms   request avg {:.1} MiB{}",
+            event.estimated_docs,
+            event.requests_completed,
+            event.avg_latency_ms,
+            event.max_latency_ms,
+            event.avg_request_bytes as f64 / MIB,
+            event.windows_total.map(|the_total| format!("   windows {}/{}", event.windows_done, the_total)).unwrap_or_default()
+        ));
+        the_frame.push(format!(
+            " errors {}   warnings {}",
+            self.the_log_tail.errors.load(Ordering::Relaxed),
+            self.the_log_tail.warnings.load(Ordering::Relaxed)
+        ));
+        the_frame.push(String::new());
+        the_frame.push(" recent log".to_string());
+        // -- 📜 whatever rows are left, minus the footer, go to the log tail
+        let the_log_rows = height.saturating_sub(the_frame.len() + 2);
+        the_frame.extend(self.the_log_tail.newest(the_log_rows).into_iter().map(|l| format!("  {}", l)));
+        while the_frame.len() + 1 < height {
+            the_frame.push(String::new());
+        }
+        the_frame.push(" p pause/resume · q quit".to_string());
+        the_frame.truncate(height);
+        the_frame.into_iter().map(|l| l.chars().take(width).collect()).collect()
+    }
+
+    fn draw(&self, event: &ProgressEvent) -> std::io::Result<()> {
+        let (the_width, the_height) = terminal::size()?;
+        let mut the_out = std::io::stdout().lock();
+        for (the_row, the_line) in self.frame(event, the_width as usize, the_height as usize).into_iter().enumerate() {
+            queue!(the_out, MoveTo(0, the_row as u16), Print(the_line), Clear(ClearType::UntilNewLine))?;
+        }
+        the_out.flush()
+    }
+}
+
+impl ProgressSubscriber for Dashboard {
+    fn on_progress(&self, event: &ProgressEvent) {
+        if self.restored.load(Ordering::Relaxed) {
+            return;
+        }
+        {
+            let mut the_history = self.the_history.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if the_history.len() == HISTORY_SAMPLES {
+                the_history.pop_front();
+            }
+            the_history.push_back(event.mib_per_sec);
+        }
+        // -- 🤷 a frame that fails to draw is skipped; the next tick tries again
+        let _ = self.draw(event);
+        if event.finished {
+            self.restore();
+            eprintln!(
+                "🏁 {}: {} docs, {:.1} MiB in {}",
+                event.pipeline_name,
+                event.estimated_docs,
+                event.bytes_drained as f64 / MIB,
+                clock(event.elapsed)
+            );
+        }
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// ⏱️ `01:02:03`
+fn clock(the_duration: Duration) -> String {
+    let the_secs = the_duration.as_secs();
+    format!("{:02}:{:02}:{:02}", the_secs / 3600, the_secs / 60 % 60, the_secs % 60)
+}
+
+/// 📊 `[██████░░░░] 42.0%  1.2 / 2.9 GiB` — or just the bytes when the total is unknown.
+fn progress_line(event: &ProgressEvent, width: usize) -> String {
+    let the_drained = event.bytes_drained as f64 / MIB;
+    let Some(the_total) = event.total_expected_bytes.filter(|t| *t > 0) else {
+        return format!(" {:.1} MiB drained (total unknown)", the_drained);
+    };
+    let the_ratio = (event.bytes_drained as f64 / the_total as f64).clamp(0.0, 1.0);
+    let the_label = format!(" {:5.1}%  {:.1} / {:.1} MiB", the_ratio * 100.0, the_drained, the_total as f64 / MIB);
+    let the_room = width.saturating_sub(the_label.chars().count() + 4);
+    let the_filled = (the_ratio * the_room as f64) as usize;
+    format!(" [{}{}]{}", "█".repeat(the_filled), "░".repeat(the_room - the_filled), the_label)
+}
+
+/// 📈 The newest `width` samples, scaled to the tallest of them.
+fn sparkline(history: &VecDeque<f64>, width: usize) -> String {
+    let the_window: Vec<f64> = history.iter().skip(history.len().saturating_sub(width)).copied().collect();
+    let the_peak = the_window.iter().copied().fold(0.0, f64::max);
+    if the_peak <= 0.0 {
+        return SPARKS[0].to_string().repeat(the_window.len());
+    }
+    the_window
+        .iter()
+        .map(|the_sample| SPARKS[((the_sample / the_peak) * (SPARKS.len() - 1) as f64).round() as usize])
+        .collect()
+}
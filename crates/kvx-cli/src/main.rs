@@ -10,9 +10,72 @@
 //! Like a manager. 🦆
 
 #![allow(dead_code, unused_variables, unused_imports)]
+mod bench;
+mod dashboard;
+
+use std::sync::Arc;
+
 use anyhow::{Context, Ok, Result};
-use tracing::error;
+use tracing::{error, info, warn};
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+// -- 🚦 exit codes, so a wrapping script can branch instead of grepping the logs:
+// -- 0 = clean, 1 = failed, 2 = finished but some documents were rejected or skipped
+const EXIT_FAILED: i32 = 1;
+const EXIT_DEGRADED: i32 = 2;
+
+/// 🩹 `EXIT_DEGRADED` when documents were lost on the way (bulk item failures, `skip_bad_docs`,
+/// schema rejects). Notes that cost no document — blank lines, Dedup saturation — stay a 0.
+fn degraded_exit_code(warnings: &kvx::warnings::RunWarnings) -> Option<i32> {
+    (warnings.rejected_docs() > 0).then_some(EXIT_DEGRADED)
+}
+
+// -- 🧮 counts allocations during `bench`; otherwise it's the system allocator with one extra load
+#[global_allocator]
+static GLOBAL: bench::CountingAllocator = bench::CountingAllocator;
+
+/// ✍️ stderr — or, while the dashboard owns the screen, its log tail (no colour codes there).
+fn log_writer(the_log_tail: Option<Arc<dashboard::LogTail>>) -> BoxMakeWriter {
+    match the_log_tail {
+        Some(the_tail) => BoxMakeWriter::new(move || the_tail.writer()),
+        None => BoxMakeWriter::new(std::io::stderr),
+    }
+}
+
+/// 📡 Logs to stderr, filtered by `RUST_LOG`.
+#[cfg(not(feature = "console"))]
+fn init_tracing(the_log_tail: Option<Arc<dashboard::LogTail>>) {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_ansi(the_log_tail.is_none())
+        .with_writer(log_writer(the_log_tail))
+        .init();
+}
+
+/// 🔭 Logs to stderr, filtered by `RUST_LOG` — plus a tokio-console server on 127.0.0.1:6669.
+///
+/// The console layer keeps its own filter (tokio's task and resource events only), so
+/// `RUST_LOG` still decides what gets printed and the console still sees every task.
+#[cfg(feature = "console")]
+fn init_tracing(the_log_tail: Option<Arc<dashboard::LogTail>>) {
+    use tracing_subscriber::prelude::*;
+    tracing_subscriber::registry()
+        .with(console_subscriber::spawn())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(the_log_tail.is_none())
+                .with_writer(log_writer(the_log_tail))
+                .with_filter(EnvFilter::from_default_env()),
+        )
+        .init();
+    if !cfg!(tokio_unstable) {
+        tracing::warn!(
+            "🔭 Built with the console feature but without RUSTFLAGS=\"--cfg tokio_unstable\" — \
+             tokio-console will connect and see nothing"
+        );
+    }
+}
 
 /// 🚀 main() — where it all begins. The genesis. The big bang.
 /// The "I pressed F5 and held my breath" moment.
@@ -21,21 +84,42 @@ use tracing_subscriber::EnvFilter;
 /// 1. Init tracing (so we can see what goes wrong, and when)
 /// 2. Parse args (or don't, we're not picky)
 /// 3. Load config (the moment of truth)
-/// 4. Run the thing (send it and pray 🙏)
-/// 5. Handle errors (cry)
+/// 4. Run the thing (send it and pray 🙏) — or just `validate` it (check it and pray less)
+/// 5. Handle errors (cry) — and exit 0, 1 (failed) or 2 (finished with skipped/rejected input)
+///
+/// 🎯 Usage:
+/// - `kvx-cli [config.toml]` — run the migration (`.yaml`/`.yml`/`.json` also accepted)
+/// - `kvx-cli validate [config.toml]` — resolve + connectivity/auth checks, no data moved
+/// - `kvx-cli bench [--json] [config.toml]` — run it, then report docs/s, MiB/s and allocations
+/// - `kvx-cli --quiet [config.toml]` — run it with no progress bar, for scripts and cron
+/// - `kvx-cli --dashboard [config.toml]` — run it full-screen: per-drainer rows, throughput graph,
+///   log tail; `p` pauses, `q` stops gracefully
 #[tokio::main]
 async fn main() -> Result<()> {
+    // -- 🎯 Grab the args like catching Pokémon — gotta get at least 1
+    let mut args: Vec<String> = std::env::args().collect();
+    // -- 🩺 `validate` and ⏱️ `bench` are the subcommands (for now). Anything else is a config path.
+    let is_validate_only = args.get(1).map(String::as_str) == Some("validate");
+    let is_bench = args.get(1).map(String::as_str) == Some("bench");
+    // -- 📋 `bench --json`: one machine-readable line instead of the table
+    let the_bench_wants_json = is_bench && args.iter().any(|a| a == "--json");
+    args.retain(|a| a != "--json");
+    // -- 🖥️ `--dashboard`: the whole terminal for a plain run (not `validate`, not `bench`).
+    // -- Decided before tracing, which it reroutes into the dashboard's log tail.
+    let the_dashboard_is_wanted = args.iter().any(|a| a == "--dashboard") && !is_validate_only && !is_bench;
+    args.retain(|a| a != "--dashboard");
+    let the_log_tail = the_dashboard_is_wanted.then(|| Arc::new(dashboard::LogTail::default()));
+    // -- 🙊 `--quiet`: no progress bar at all — same as `[runtime] progress = "off"`
+    let is_quiet = args.iter().any(|a| a == "--quiet");
+    args.retain(|a| a != "--quiet");
+
     // -- 📡 Set up tracing — because println! debugging is a lifestyle choice
     // -- we're trying to move past, like flip phones and cargo shorts
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .init();
+    init_tracing(the_log_tail.clone());
 
-    // -- 🎯 Grab the args like catching Pokémon — gotta get at least 1
-    let args: Vec<String> = std::env::args().collect();
-    let path_arg = match args.get(1) {
+    let path_arg = match args.get(if is_validate_only || is_bench { 2 } else { 1 }) {
         Some(s) => s,
-        None => &format!("kvx.toml"), // -- 🔧 default: the ol' reliable
+        None => "kvx.toml", // -- 🔧 default: the ol' reliable
     };
 
     // -- 🔒 Validate the config file exists before we get too emotionally attached
@@ -50,13 +134,39 @@ async fn main() -> Result<()> {
 
     // -- 🔧 Load the config — this is the moment where we find out if the TOML is valid
     // -- or if someone put a tab where a space should be (looking at you, Kevin)
-    let app_config  = kvx::config::load_config(config_file_path_which_is_validated_to_exist)
+    let mut app_config  = kvx::config::load_config(config_file_path_which_is_validated_to_exist)
         .context("💀 In kvx-cli, main, we couldn't load the config file, take a look at the file, make sure it's correct. Make sure you didn't forget something obvious, dumas")
     /* ? */ ?;
+    if is_quiet {
+        app_config.runtime.progress = kvx::config::ProgressMode::Off;
+    }
+
+    // -- 🩹 a handle on the warning box, to tell a clean run from one that left docs behind
+    let the_warnings = app_config.warnings.clone();
 
     // -- 🚀 SEND IT. No take-backs. This is not a drill.
     // -- (okay it might be a drill, we're still in POC/MVP)
-    let result = kvx::run(app_config).await;
+    let result = if is_validate_only {
+        // -- 🩺 look, don't touch: resolve everything, knock on every door, move nothing
+        kvx::validate(&app_config).await.map(|_| {
+            info!("✅ Configuration is valid — source and sink answered, caster and manifold resolved. Ready when you are.");
+        })
+    } else if is_bench {
+        // -- ⏱️ same run, with a stopwatch and an allocation tally
+        bench::run(app_config, the_bench_wants_json).await
+    } else if let Some(the_log_tail) = the_log_tail {
+        // -- 🖥️ the dashboard drives the pause switch and the stop button; the run just listens
+        let the_cancel = kvx::CancellationToken::new();
+        let the_dashboard = dashboard::Dashboard::open(the_log_tail, app_config.pause.clone(), the_cancel.clone())?;
+        app_config.progress.hide_terminal();
+        app_config.progress.subscribe(the_dashboard.clone());
+        let the_outcome = kvx::run_with_cancellation(app_config, the_cancel).await;
+        // -- 🔚 a failed run never sends `finished` — give the terminal back before reporting it
+        the_dashboard.restore();
+        the_outcome
+    } else {
+        kvx::run(app_config).await
+    };
 
     // -- 💀 Error handling: the part where we find out what went wrong
     // -- and print it in a way that's helpful at 3am
@@ -91,10 +201,31 @@ async fn main() -> Result<()> {
         }
 
         // -- 🗑️ Exit with prejudice. Process exitus maximus.
-        std::process::exit(1);
+        std::process::exit(EXIT_FAILED);
+    }
+
+    // -- 🩹 finished, but some docs were skipped or rejected along the way — scripts get to notice
+    if let Some(the_code) = degraded_exit_code(&the_warnings) {
+        warn!("🩹 Run finished with {} rejected/skipped document(s) — exiting {}", the_warnings.rejected_docs(), the_code);
+        std::process::exit(the_code);
     }
 
     // -- ✅ If we got here, everything worked. Pop the champagne. 🍾
     // -- (or at least close the terminal tab with a sense of accomplishment)
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kvx::warnings::{RunWarnings, WarningKind};
+
+    #[test]
+    fn the_one_where_a_blank_line_doesnt_ruin_the_run() {
+        let the_warnings = RunWarnings::default();
+        the_warnings.record(WarningKind::DataQuality, "skipped 3 blank line(s) in 'catalog.ndjson'");
+        assert_eq!(degraded_exit_code(&the_warnings), None, "✅ blank lines alone exit 0");
+        the_warnings.record_rejected(2, "Elasticsearch rejected 2 document(s) inside successful _bulk responses");
+        assert_eq!(degraded_exit_code(&the_warnings), Some(EXIT_DEGRADED));
+    }
+}
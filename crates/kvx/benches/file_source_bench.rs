@@ -13,11 +13,14 @@
 //! May the fastest I/O strategy win. Spoiler: the chunks win. They always win.
 //!
 //! Reports both MB/s (throughput) and docs/s (elements) for each approach.
+//! The `mmap` entry is the same source with `read_mode = "mmap"` — pages cut from a mapping.
+//! The `uring` entry (`cargo bench --features io-uring`, Linux) reads through an io_uring
+//! thread that keeps a few reads in flight ahead of the pumper.
 //!
 //! 🦆 The singularity will arrive before we stop benchmarking this.
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use kvx::backends::file::FileSource;
+use kvx::backends::file::{FileReadMode, FileSource};
 use kvx::backends::{CommonSourceConfig, FileSourceConfig, Source};
 use std::io::Write;
 use tempfile::NamedTempFile;
@@ -77,9 +80,15 @@ fn bench_buffered_chunk_reading(c: &mut Criterion) {
                 b.to_async(&rt).iter(|| async {
                     let config = FileSourceConfig {
                         file_name: path.clone(),
+                        read_mode: Default::default(),
+                        start_offset_bytes: 0,
+                        offset_file: None,
+                        validate_json: false,
+                        compression: Default::default(),
                         common_config: CommonSourceConfig {
-                            max_batch_size_docs: 10_000,
-                            max_batch_size_bytes: 10 * 1024 * 1024,
+                            max_batch_size_docs: Some(10_000),
+                            max_batch_size_bytes: Some(10 * 1024 * 1024),
+                            ..Default::default()
                         },
                     };
                     let mut source = FileSource::new(config).await.unwrap();
@@ -109,9 +118,15 @@ fn bench_buffered_chunk_reading(c: &mut Criterion) {
                 b.to_async(&rt).iter(|| async {
                     let config = FileSourceConfig {
                         file_name: path.clone(),
+                        read_mode: Default::default(),
+                        start_offset_bytes: 0,
+                        offset_file: None,
+                        validate_json: false,
+                        compression: Default::default(),
                         common_config: CommonSourceConfig {
-                            max_batch_size_docs: 10_000,
-                            max_batch_size_bytes: 10 * 1024 * 1024,
+                            max_batch_size_docs: Some(10_000),
+                            max_batch_size_bytes: Some(10 * 1024 * 1024),
+                            ..Default::default()
                         },
                     };
                     let mut source = FileSource::new(config).await.unwrap();
@@ -197,5 +212,88 @@ fn bench_line_by_line_reading(c: &mut Criterion) {
 }
 
 // -- 🏁 "And they're off!" — every horse race announcer and every benchmark suite
-criterion_group!(benches, bench_buffered_chunk_reading, bench_line_by_line_reading);
+/// 🗺️ Benchmark Group 3: the same FileSource, mapped instead of read
+///
+/// No read buffer, no remainder — pages come straight out of the page cache.
+fn bench_mmap_reading(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (tmp, file_size, _doc_count) = generate_test_file();
+    let path = tmp.path().to_str().unwrap().to_string();
+
+    let mut group = c.benchmark_group("file_source_throughput");
+    group.throughput(Throughput::Bytes(file_size));
+    group.sample_size(20);
+
+    group.bench_with_input(BenchmarkId::new("mmap", file_size), &path, |b, path| {
+        b.to_async(&rt).iter(|| async {
+            let config = FileSourceConfig {
+                file_name: path.clone(),
+                read_mode: FileReadMode::Mmap,
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig {
+                    max_batch_size_docs: Some(10_000),
+                    max_batch_size_bytes: Some(10 * 1024 * 1024),
+                    ..Default::default()
+                },
+            };
+            let mut source = FileSource::new(config).await.unwrap();
+            let mut pages = 0u64;
+            while let Some(_feed) = source.pump().await.unwrap() {
+                pages += 1;
+            }
+            criterion::black_box(pages)
+        });
+    });
+
+    group.finish();
+}
+
+/// 💍 Benchmark Group 4: the same FileSource, with its chunks read ahead through io_uring
+///
+/// Same loop as the buffered reader — only where the 128 KiB chunks come from changes.
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+fn bench_uring_reading(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (tmp, file_size, _doc_count) = generate_test_file();
+    let path = tmp.path().to_str().unwrap().to_string();
+
+    let mut group = c.benchmark_group("file_source_throughput");
+    group.throughput(Throughput::Bytes(file_size));
+    group.sample_size(20);
+
+    group.bench_with_input(BenchmarkId::new("uring", file_size), &path, |b, path| {
+        b.to_async(&rt).iter(|| async {
+            let config = FileSourceConfig {
+                file_name: path.clone(),
+                read_mode: FileReadMode::Uring,
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig {
+                    max_batch_size_docs: Some(10_000),
+                    max_batch_size_bytes: Some(10 * 1024 * 1024),
+                    ..Default::default()
+                },
+            };
+            let mut source = FileSource::new(config).await.unwrap();
+            let mut pages = 0u64;
+            while let Some(_feed) = source.pump().await.unwrap() {
+                pages += 1;
+            }
+            criterion::black_box(pages)
+        });
+    });
+
+    group.finish();
+}
+
+/// 💍 Without the `io-uring` feature there's no ring to race.
+#[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+fn bench_uring_reading(_c: &mut Criterion) {}
+
+criterion_group!(benches, bench_buffered_chunk_reading, bench_line_by_line_reading, bench_mmap_reading, bench_uring_reading);
 criterion_main!(benches);
@@ -21,7 +21,7 @@ use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Through
 use kvx::casts::passthrough::Passthrough;
 use kvx::casts::PageToEntriesCaster;
 use kvx::manifolds::ManifoldBackend;
-use kvx::workers::Joiner;
+use kvx::workers::{Joiner, SourcedPage};
 use kvx::{Page, Payload};
 use std::hint::black_box;
 
@@ -46,7 +46,7 @@ const MAX_REQUEST_SIZE_BYTES: usize = 10 * 1024 * 1024;
 fn generate_feeds(count: usize) -> Vec<String> {
     // -- 🚀 pre-size the vec because reallocation mid-generation is for amateurs
     let mut feeds = Vec::with_capacity(count);
-    for j in 0..count {
+    for _ in 0..count {
         let mut feed = String::new();
         for i in 0..count {
             feed.push_str(&format!(
@@ -85,7 +85,7 @@ fn joiner_throughput_bytes(c: &mut Criterion) {
                 |b, &_n| {
                     b.iter(|| {
                         // -- 🧵 Fresh channels + joiner per iteration — no stale state leaking between runs
-                        let (tx1, rx1) = async_channel::bounded::<Page>(CHANNEL_CAPACITY);
+                        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(CHANNEL_CAPACITY);
                         let (tx2, rx2) = async_channel::bounded::<Payload>(CHANNEL_CAPACITY);
 
                         let joiner = Joiner::new(
@@ -103,7 +103,7 @@ fn joiner_throughput_bytes(c: &mut Criterion) {
                         let feeds_clone = feeds.clone();
                         let sender_handle = std::thread::spawn(move || {
                             for feed in feeds_clone {
-                                tx1.send_blocking(Page(feed)).unwrap();
+                                tx1.send_blocking((Page(feed), None)).unwrap();
                             }
                             // -- 🏁 Close ch1 — triggers joiner's final flush
                             drop(tx1);
@@ -147,7 +147,7 @@ fn joiner_throughput_docs(c: &mut Criterion) {
                 |b, &_n| {
                     b.iter(|| {
                         // -- 🧵 Fresh channels + joiner per iteration
-                        let (tx1, rx1) = async_channel::bounded::<Page>(CHANNEL_CAPACITY);
+                        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(CHANNEL_CAPACITY);
                         let (tx2, rx2) = async_channel::bounded::<Payload>(CHANNEL_CAPACITY);
 
                         let joiner = Joiner::new(
@@ -164,7 +164,7 @@ fn joiner_throughput_docs(c: &mut Criterion) {
                         let feeds_clone = feeds.clone();
                         let sender_handle = std::thread::spawn(move || {
                             for feed in feeds_clone {
-                                tx1.send_blocking(Page(feed)).unwrap();
+                                tx1.send_blocking((Page(feed), None)).unwrap();
                             }
                             drop(tx1);
                         });
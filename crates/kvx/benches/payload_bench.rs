@@ -0,0 +1,78 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧱📊 Payload Benchmark Suite — "Honey, I Shrunk the Memcpy"
+//!
+//! A payload's life after the joiner: the drainer clones it for every send attempt,
+//! a dead-letter sink keeps a handle, and a 413 cuts it in half. With `String` each of
+//! those is a full copy. With `Bytes` they're a refcount bump and two views.
+//!
+//! This pits the old `String` payload against the `Bytes`-backed `Payload` on exactly that
+//! lifecycle: four attempts (1 + the default 3 retries), then a split. MB/s is reported
+//! against the payload size, so a bigger number means less time copying.
+//!
+//! 🦆 The duck asked why we didn't just not retry. The duck has never met a 429.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use kvx::Payload;
+use std::hint::black_box;
+
+// -- 📏 Payload sizes — a small bulk, the default-ish 10 MiB, and a chunky 50 MiB
+const PAYLOAD_SIZES: &[usize] = &[64 * 1024, 10 * 1024 * 1024, 50 * 1024 * 1024];
+
+// -- 🔁 One send plus the default three retries
+const ATTEMPTS: usize = 4;
+
+/// 🧱 An NDJSON-ish body of roughly `size` bytes, one ~100 byte line at a time.
+fn generate_body(size: usize) -> String {
+    let mut body = String::with_capacity(size + 128);
+    let mut i = 0;
+    while body.len() < size {
+        body.push_str(&format!(r#"{{"id":{i},"name":"bench_doc_{i}","data":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"}}"#));
+        body.push('\n');
+        i += 1;
+    }
+    body
+}
+
+/// 🔪 A line boundary near the middle — where a 413 split would cut.
+fn the_middle_line(body: &str) -> usize {
+    body[..body.len() / 2].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// 🏁 Retries + split, `String` vs `Bytes`.
+fn payload_retry_and_split(c: &mut Criterion) {
+    let mut group = c.benchmark_group("payload_retry_and_split");
+
+    for &size in PAYLOAD_SIZES {
+        let the_body = generate_body(size);
+        let the_cut = the_middle_line(&the_body);
+        group.throughput(Throughput::Bytes(the_body.len() as u64));
+
+        // -- 📜 Before: every attempt and both halves are fresh allocations + memcpy
+        group.bench_with_input(BenchmarkId::new("String", size), &the_body, |b, the_body| {
+            b.iter(|| {
+                for _ in 0..ATTEMPTS {
+                    black_box(the_body.clone());
+                }
+                black_box((the_body[..the_cut].to_string(), the_body[the_cut..].to_string()));
+            });
+        });
+
+        // -- 🧱 After: refcount bumps and views into the one buffer
+        let the_payload = Payload::from(the_body.clone());
+        group.bench_with_input(BenchmarkId::new("Bytes", size), &the_payload, |b, the_payload| {
+            b.iter(|| {
+                for _ in 0..ATTEMPTS {
+                    black_box(the_payload.clone());
+                }
+                black_box((the_payload.slice(0..the_cut), the_payload.slice(the_cut..the_payload.len())));
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, payload_retry_and_split);
+criterion_main!(benches);
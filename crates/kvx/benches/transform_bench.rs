@@ -0,0 +1,82 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧📊 Transform Benchmark Suite — "Parse, Rename, Serialize, Repeat"
+//!
+//! Every configured `[[transforms]]` entry means each document is parsed into a
+//! `serde_json::Value`, handed through the steps, and serialized again. On a big corpus
+//! that round trip is most of the CPU the joiners spend.
+//!
+//! This measures it with the cheapest possible step (one `FieldMap` rename), so nearly
+//! all the time is the parse and the serialize. Run it twice to compare parsers — the ids
+//! carry the parser name, so criterion keeps the two configurations apart:
+//!
+//! ```text
+//! cargo bench -p kvx --bench transform_bench
+//! cargo bench -p kvx --bench transform_bench --features simd-json
+//! ```
+//!
+//! 🦆 The duck renamed itself `goose` ten thousand times. It feels no different.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use kvx::transforms::{FieldMapConfig, TransformChain, TransformConfig};
+use std::hint::black_box;
+
+// -- ⚡ Which parser `TransformChain::apply` was built with, stamped on every benchmark id
+const PARSER: &str = if cfg!(feature = "simd-json") { "simd-json" } else { "serde_json" };
+
+// -- 📏 Doc shapes: a flat ~100 byte doc and a nested ~1 KiB one
+const DOC_COUNT: usize = 10_000;
+
+fn flat_doc(i: usize) -> String {
+    format!(r#"{{"ObjectID":{i},"name":"bench_doc_{i}","data":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"}}"#)
+}
+
+fn nested_doc(i: usize) -> String {
+    let the_tags: Vec<String> = (0..20).map(|t| format!(r#""tag-{t}""#)).collect();
+    format!(
+        r#"{{"ObjectID":{i},"owner":{{"name":"someone {i}","email":"someone{i}@example.com","team":{{"id":7,"name":"platform"}}}},"tags":[{}],"metrics":{{"views":{i},"score":{}.5,"ratio":0.25}},"body":"{}"}}"#,
+        the_tags.join(","),
+        i % 100,
+        "lorem ipsum dolor sit amet ".repeat(20)
+    )
+}
+
+fn a_rename_chain() -> TransformChain {
+    TransformChain::from_configs(&[TransformConfig::FieldMap(FieldMapConfig {
+        rename: [("ObjectID".to_string(), "id".to_string())].into_iter().collect(),
+    })])
+    .expect("💀 one rename should compile")
+}
+
+/// 🚀 Parse → rename → serialize throughput, in MB/s and docs/s.
+fn transform_chain_round_trip(c: &mut Criterion) {
+    let the_chain = a_rename_chain();
+    for (the_shape, make_doc) in [("flat", flat_doc as fn(usize) -> String), ("nested", nested_doc)] {
+        let the_docs: Vec<String> = (0..DOC_COUNT).map(make_doc).collect();
+        let the_bytes: u64 = the_docs.iter().map(|d| d.len() as u64).sum();
+
+        let mut group = c.benchmark_group("transform_chain_round_trip");
+        group.throughput(Throughput::Bytes(the_bytes));
+        group.bench_with_input(BenchmarkId::new(format!("bytes/{PARSER}"), the_shape), &the_docs, |b, the_docs| {
+            b.iter(|| {
+                for the_doc in the_docs {
+                    black_box(the_chain.apply(the_doc).unwrap());
+                }
+            });
+        });
+        group.throughput(Throughput::Elements(DOC_COUNT as u64));
+        group.bench_with_input(BenchmarkId::new(format!("docs/{PARSER}"), the_shape), &the_docs, |b, the_docs| {
+            b.iter(|| {
+                for the_doc in the_docs {
+                    black_box(the_chain.apply(the_doc).unwrap());
+                }
+            });
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, transform_chain_round_trip);
+criterion_main!(benches);
@@ -0,0 +1,332 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧬 Just enough Avro to read BigQuery Storage rows — the schema BigQuery writes for a read
+//! session, and the binary encoding of the rows it streams.
+//!
+//! 🧠 Knowledge graph:
+//! - `Schema::parse` reads the session's `avro_schema` once; `decode_rows` turns each
+//!   `serialized_binary_rows` block into one JSON object per row (no container header, no sync
+//!   markers — the rows are just laid end to end)
+//! - Docs come out the same shape as the REST path's `to_doc`: INT64 → number, TIMESTAMP →
+//!   RFC 3339 UTC, NUMERIC → an exact decimal string, BYTES → base64, JSON → the parsed value,
+//!   NULLABLE → a `["null", T]` union, REPEATED → an Avro array, RECORD → an object
+//! - Only the types BigQuery writes are understood; anything else is refused at session start 🦆
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat};
+use serde_json::{Map, Number, Value, json};
+
+/// 📐 One Avro type, with BigQuery's logical types already told apart.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    /// 📅 `DATE` — `int` days since 1970-01-01
+    Date,
+    /// ⏱️ `TIMESTAMP` — `long` microseconds since the epoch
+    TimestampMicros,
+    /// 🕰️ `TIME` — `long` microseconds since midnight
+    TimeMicros,
+    /// 💰 `NUMERIC` / `BIGNUMERIC` — big-endian two's complement, `scale` digits after the point
+    Decimal { scale: usize },
+    /// 🧾 `JSON` — a string column BigQuery tags `sqlType: JSON`
+    Json,
+    Array(Box<Schema>),
+    Record(Vec<(String, Schema)>),
+    Union(Vec<Schema>),
+}
+
+impl Schema {
+    /// 📜 The JSON schema a read session carries.
+    pub(super) fn parse(the_json: &str) -> Result<Self> {
+        let the_value: Value = serde_json::from_str(the_json).context("💀 The read session's Avro schema isn't JSON")?;
+        Self::from_value(&the_value)
+    }
+
+    fn from_value(the_value: &Value) -> Result<Self> {
+        match the_value {
+            Value::String(the_name) => Self::primitive(the_name),
+            Value::Array(the_branches) => Ok(Self::Union(the_branches.iter().map(Self::from_value).collect::<Result<_>>()?)),
+            Value::Object(the_object) => {
+                let the_type = the_object.get("type").context("💀 An Avro schema object has no `type`")?;
+                let Some(the_name) = the_type.as_str() else {
+                    // -- 🪆 `{"type": {"type": "record", ...}}` — the real type is one level down
+                    return Self::from_value(the_type);
+                };
+                let the_logical = the_object.get("logicalType").and_then(Value::as_str);
+                let the_sql_type = the_object.get("sqlType").and_then(Value::as_str);
+                Ok(match (the_name, the_logical, the_sql_type) {
+                    ("record", _, _) => Self::Record(
+                        the_object
+                            .get("fields")
+                            .and_then(Value::as_array)
+                            .context("💀 An Avro record has no `fields`")?
+                            .iter()
+                            .map(|the_field| {
+                                let the_field_name = the_field.get("name").and_then(Value::as_str).context("💀 An Avro field has no `name`")?;
+                                let the_field_type = the_field.get("type").context("💀 An Avro field has no `type`")?;
+                                Ok((the_field_name.to_string(), Self::from_value(the_field_type)?))
+                            })
+                            .collect::<Result<_>>()?,
+                    ),
+                    ("array", _, _) => Self::Array(Box::new(Self::from_value(
+                        the_object.get("items").context("💀 An Avro array has no `items`")?,
+                    )?)),
+                    ("int", Some("date"), _) => Self::Date,
+                    ("long", Some("timestamp-micros"), _) => Self::TimestampMicros,
+                    ("long", Some("time-micros"), _) => Self::TimeMicros,
+                    ("bytes", Some("decimal"), _) => {
+                        Self::Decimal { scale: the_object.get("scale").and_then(Value::as_u64).unwrap_or(0) as usize }
+                    }
+                    ("string", _, Some("JSON")) => Self::Json,
+                    (the_other, _, _) => Self::primitive(the_other)?,
+                })
+            }
+            the_other => anyhow::bail!("💀 '{}' isn't an Avro schema", the_other),
+        }
+    }
+
+    fn primitive(the_name: &str) -> Result<Self> {
+        Ok(match the_name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "int" => Self::Int,
+            "long" => Self::Long,
+            "float" => Self::Float,
+            "double" => Self::Double,
+            "bytes" => Self::Bytes,
+            "string" => Self::String,
+            the_other => anyhow::bail!("💀 Avro type '{}' isn't one BigQuery writes — this source can't read it", the_other),
+        })
+    }
+}
+
+/// 📦 One `serialized_binary_rows` block → one doc per row.
+pub(super) fn decode_rows(schema: &Schema, mut rows: &[u8]) -> Result<Vec<Value>> {
+    let mut the_docs = Vec::new();
+    while !rows.is_empty() {
+        the_docs.push(decode(schema, &mut rows).with_context(|| format!("💀 Row {} of a BigQuery Storage block didn't decode", the_docs.len() + 1))?);
+    }
+    Ok(the_docs)
+}
+
+fn decode(schema: &Schema, bytes: &mut &[u8]) -> Result<Value> {
+    Ok(match schema {
+        Schema::Null => Value::Null,
+        Schema::Boolean => Value::Bool(take(bytes, 1)?[0] != 0),
+        Schema::Int | Schema::Long => Value::from(read_long(bytes)?),
+        Schema::Float => float_value(f32::from_le_bytes(take(bytes, 4)?.try_into()?) as f64),
+        Schema::Double => float_value(f64::from_le_bytes(take(bytes, 8)?.try_into()?)),
+        Schema::Bytes => json!(base64::engine::general_purpose::STANDARD.encode(read_bytes(bytes)?)),
+        Schema::String => json!(std::str::from_utf8(read_bytes(bytes)?).context("💀 An Avro string isn't UTF-8")?),
+        Schema::Json => {
+            let the_text = std::str::from_utf8(read_bytes(bytes)?).context("💀 A JSON column isn't UTF-8")?;
+            serde_json::from_str(the_text).unwrap_or_else(|_| json!(the_text))
+        }
+        Schema::Date => {
+            let the_days = read_long(bytes)?;
+            NaiveDate::from_epoch_days(i32::try_from(the_days)?)
+                .map(|the_date| json!(the_date.format("%Y-%m-%d").to_string()))
+                .unwrap_or_else(|| json!(the_days))
+        }
+        Schema::TimestampMicros => {
+            let the_micros = read_long(bytes)?;
+            DateTime::from_timestamp_micros(the_micros)
+                .map(|the_time| json!(the_time.to_rfc3339_opts(SecondsFormat::AutoSi, true)))
+                .unwrap_or_else(|| json!(the_micros))
+        }
+        Schema::TimeMicros => {
+            let the_micros = read_long(bytes)?;
+            u32::try_from(the_micros.div_euclid(1_000_000))
+                .ok()
+                .and_then(|the_secs| NaiveTime::from_num_seconds_from_midnight_opt(the_secs, (the_micros.rem_euclid(1_000_000) * 1_000) as u32))
+                .map(|the_time| json!(the_time.format("%H:%M:%S%.f").to_string()))
+                .unwrap_or_else(|| json!(the_micros))
+        }
+        Schema::Decimal { scale } => json!(decimal_string(read_bytes(bytes)?, *scale)),
+        Schema::Array(the_items) => {
+            let mut the_values = Vec::new();
+            loop {
+                let mut the_count = read_long(bytes)?;
+                if the_count == 0 {
+                    break;
+                }
+                if the_count < 0 {
+                    // -- 📏 a negative count is followed by the block's size in bytes; we read item by item anyway
+                    the_count = -the_count;
+                    read_long(bytes)?;
+                }
+                for _ in 0..the_count {
+                    the_values.push(decode(the_items, bytes)?);
+                }
+            }
+            Value::Array(the_values)
+        }
+        Schema::Record(the_fields) => {
+            let mut the_doc = Map::with_capacity(the_fields.len());
+            for (the_name, the_field) in the_fields {
+                the_doc.insert(the_name.clone(), decode(the_field, bytes)?);
+            }
+            Value::Object(the_doc)
+        }
+        Schema::Union(the_branches) => {
+            let the_index = read_long(bytes)?;
+            let the_branch = usize::try_from(the_index)
+                .ok()
+                .and_then(|i| the_branches.get(i))
+                .with_context(|| format!("💀 Avro union branch {} doesn't exist", the_index))?;
+            decode(the_branch, bytes)?
+        }
+    })
+}
+
+/// 🫧 NaN and ±Infinity have no JSON number — they come out as the strings the REST API sends.
+fn float_value(the_float: f64) -> Value {
+    match Number::from_f64(the_float) {
+        Some(the_number) => Value::Number(the_number),
+        None if the_float.is_nan() => json!("NaN"),
+        None if the_float > 0.0 => json!("Infinity"),
+        None => json!("-Infinity"),
+    }
+}
+
+/// ✂️ The next `n` bytes, or an error if the block ends first.
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    anyhow::ensure!(bytes.len() >= n, "💀 A BigQuery Storage block ended mid-value");
+    let (the_head, the_rest) = bytes.split_at(n);
+    *bytes = the_rest;
+    Ok(the_head)
+}
+
+/// 🔢 A zigzag varint — Avro's `int` and `long`.
+fn read_long(bytes: &mut &[u8]) -> Result<i64> {
+    let mut the_raw = 0u64;
+    for the_shift in (0..64).step_by(7) {
+        let the_byte = take(bytes, 1)?[0];
+        the_raw |= u64::from(the_byte & 0x7f) << the_shift;
+        if the_byte & 0x80 == 0 {
+            return Ok((the_raw >> 1) as i64 ^ -((the_raw & 1) as i64));
+        }
+    }
+    anyhow::bail!("💀 An Avro varint ran past 64 bits")
+}
+
+/// 📦 A length-prefixed `bytes` / `string` body.
+fn read_bytes<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let the_len = usize::try_from(read_long(bytes)?).context("💀 An Avro length is negative")?;
+    take(bytes, the_len)
+}
+
+/// 💰 Big-endian two's complement + scale → `"-12.5"`. BIGNUMERIC is wider than any Rust
+/// integer, so the digits come from long division on the bytes. Trailing zeros are dropped,
+/// the way BigQuery prints NUMERIC over REST.
+fn decimal_string(the_bytes: &[u8], scale: usize) -> String {
+    let is_negative = the_bytes.first().is_some_and(|the_byte| the_byte & 0x80 != 0);
+    let mut the_magnitude = the_bytes.to_vec();
+    if is_negative {
+        // -- 🔄 two's complement → magnitude: flip every bit, add one
+        the_magnitude.iter_mut().for_each(|the_byte| *the_byte = !*the_byte);
+        for the_byte in the_magnitude.iter_mut().rev() {
+            let (the_sum, the_carry) = the_byte.overflowing_add(1);
+            *the_byte = the_sum;
+            if !the_carry {
+                break;
+            }
+        }
+    }
+    let mut the_digits = Vec::new();
+    while the_magnitude.iter().any(|&the_byte| the_byte != 0) {
+        let mut the_remainder = 0u32;
+        for the_byte in the_magnitude.iter_mut() {
+            let the_acc = (the_remainder << 8) | u32::from(*the_byte);
+            *the_byte = (the_acc / 10) as u8;
+            the_remainder = the_acc % 10;
+        }
+        the_digits.push(b'0' + the_remainder as u8);
+    }
+    while the_digits.len() <= scale {
+        the_digits.push(b'0');
+    }
+    the_digits.reverse();
+    let (the_whole, the_fraction) = the_digits.split_at(the_digits.len() - scale);
+    let the_fraction = std::str::from_utf8(the_fraction).unwrap_or_default().trim_end_matches('0');
+    let mut the_text = String::from_utf8_lossy(the_whole).into_owned();
+    if !the_fraction.is_empty() {
+        the_text.push('.');
+        the_text.push_str(the_fraction);
+    }
+    if is_negative && the_text != "0" {
+        the_text.insert(0, '-');
+    }
+    the_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 🧪 BigQuery's schema for a NULLABLE/REPEATED/RECORD mix decodes to the REST path's doc shapes.
+    #[test]
+    fn the_one_where_the_rows_arrive_in_binary() -> Result<()> {
+        let the_schema = Schema::parse(
+            r#"{"type":"record","name":"__root__","fields":[
+                {"name":"id","type":"long"},
+                {"name":"price","type":["null","double"]},
+                {"name":"at","type":["null",{"type":"long","logicalType":"timestamp-micros"}]},
+                {"name":"day","type":{"type":"int","logicalType":"date"}},
+                {"name":"kg","type":["null",{"type":"bytes","logicalType":"decimal","precision":38,"scale":9}]},
+                {"name":"tags","type":{"type":"array","items":"string"}},
+                {"name":"ship","type":["null",{"type":"record","name":"ship","fields":[{"name":"zip","type":"string"}]}]},
+                {"name":"extra","type":["null",{"type":"string","sqlType":"JSON"}]}
+            ]}"#,
+        )?;
+        let mut the_rows = vec![
+            0x02, // id = 1
+            0x02, 0, 0, 0, 0, 0, 0, 0x23, 0x40, // price = 9.5
+            0x02, 0x80, 0x80, 0x82, 0xe2, 0x88, 0xd2, 0xa3, 0x06, // at = 1767225600000000
+            0xbe, 0xc0, 0x02, // day = 20511 (2026-02-27)
+            0x02, 0x08, 0x59, 0x68, 0x2f, 0x00, // kg = 1500000000 / 10^9
+            0x04, 0x02, b'a', 0x02, b'b', 0x00, // tags = ["a", "b"]
+            0x02, 0x0a, b'0', b'2', b'1', b'3', b'9', // ship = {zip: "02139"}
+            0x02, 0x0e, b'{', b'"', b'k', b'"', b':', b'1', b'}', // extra = {"k":1}
+        ];
+        // -- 🕳️ second row: every nullable column null, no tags, a negative id
+        the_rows.extend([0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(
+            decode_rows(&the_schema, &the_rows)?,
+            vec![
+                json!({"id": 1, "price": 9.5, "at": "2026-01-01T00:00:00Z", "day": "2026-02-27", "kg": "1.5", "tags": ["a", "b"], "ship": {"zip": "02139"}, "extra": {"k": 1}}),
+                json!({"id": -2, "price": null, "at": null, "day": "1970-01-01", "kg": null, "tags": [], "ship": null, "extra": null}),
+            ]
+        );
+        assert!(decode_rows(&the_schema, &the_rows[..5]).is_err(), "💀 a block cut short is an error");
+        assert!(Schema::parse(r#"{"type":"map","values":"long"}"#).is_err(), "💀 BigQuery never writes maps");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_decimals_are_wider_than_u128() {
+        assert_eq!(decimal_string(&[0x59, 0x68, 0x2f, 0x00], 9), "1.5");
+        assert_eq!(decimal_string(&[0xff, 0xff, 0xff, 0x83], 2), "-1.25");
+        assert_eq!(decimal_string(&[0x00], 9), "0");
+        assert_eq!(decimal_string(&[0x64], 0), "100");
+        // -- 🐘 BIGNUMERIC's 10^38 at scale 38 is 1 — and 2^255 - 1 doesn't fit any Rust integer
+        let mut the_wide = vec![0x7f];
+        the_wide.extend([0xff; 31]);
+        assert_eq!(
+            decimal_string(&the_wide, 38),
+            "578960446186580977117854925043439539266.34992332820282019728792003956564819967"
+        );
+        assert_eq!(float_value(f64::NAN), json!("NaN"));
+        assert_eq!(float_value(f64::NEG_INFINITY), json!("-Infinity"));
+    }
+}
@@ -0,0 +1,634 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+use std::collections::VecDeque;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat};
+use reqwest::header::{AUTHORIZATION, HeaderValue};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use tracing::{debug, info};
+
+use super::config::BigQuerySourceConfig;
+#[cfg(feature = "bigquery-storage")]
+use super::storage_read::StorageRead;
+use crate::Page;
+use crate::backends::Source;
+
+/// ⏳ How long BigQuery may hold a query request open waiting for the job, in ms. Under
+/// `DEFAULT_TIMEOUTS.request`, so an unfinished job comes back as "not yet", not as a timeout.
+const JOB_WAIT_MS: u64 = 60_000;
+
+/// 📊 Runs a query (or lists a table) through BigQuery's REST API and pours the rows out as
+/// NDJSON pages — one JSON object per row, keyed by column name.
+///
+/// 🧠 Knowledge graph: speaks NDJSON, so it resolves casters exactly like the File source.
+/// - `query`: `jobs.query` starts the job; `jobs.getQueryResults` polls until it's done and
+///   then pages through the result with `pageToken`
+/// - `table`: `tables.get` for the schema, then `tabledata.list` pages — no query job at all
+/// - Rows arrive as `{"f":[{"v":"42"}]}` with every scalar a string; `to_doc` uses the schema
+///   to turn them back into numbers, booleans, RFC 3339 timestamps, objects and arrays
+/// - `read_api = true` (`bigquery-storage` feature): a table is read through a Storage Read API
+///   session instead (`storage_read.rs`); a query runs as a job without rows inline, and the
+///   session reads the job's destination table (`jobs.get`)
+/// - Rows wait in `pending` and each pump cuts one page, the same shape as the Rest source
+pub struct BigQuerySource {
+    source_config: BigQuerySourceConfig,
+    client: Client,
+    authorization: Option<HeaderValue>,
+    // 📐 the columns, once a response (or `tables.get`) has said what they are
+    schema: Vec<BigQueryField>,
+    // 📡 what to ask for next; `None` once the last page is in
+    next: Option<NextRequest>,
+    // 🧩 rows fetched but not yet cut into a page, one compact JSON line each
+    pending: VecDeque<String>,
+    // 🚀 the open Storage Read API session, while `NextRequest::ReadRows` is reading it
+    #[cfg(feature = "bigquery-storage")]
+    storage: Option<StorageRead>,
+}
+
+impl std::fmt::Debug for BigQuerySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BigQuerySource").field("project", &self.source_config.project).field("next", &self.next).finish()
+    }
+}
+
+/// 📡 The request after this one.
+#[derive(Debug, Clone, PartialEq)]
+enum NextRequest {
+    /// 🚀 `jobs.query` — nothing sent yet
+    StartQuery,
+    /// 📐 `tables.get` — nothing sent yet
+    TableSchema { table_url: Url },
+    /// 🔁 `jobs.getQueryResults` — poll an unfinished job, or fetch the page at `page_token`
+    QueryResults { url: Url, page_token: Option<String> },
+    /// 📋 `tabledata.list` from `page_token`
+    TableData { url: Url, page_token: Option<String> },
+    /// 🎯 `jobs.get` — where a finished query job left its rows, for the read session
+    JobTable { url: Url },
+    /// 🚀 `CreateReadSession` on `projects/{p}/datasets/{d}/tables/{t}`
+    ReadSession { table: String },
+    /// 📡 `ReadRows` — the next block from the open session
+    ReadRows,
+}
+
+/// 📐 One column of a BigQuery schema. `RECORD`s carry their own `fields`.
+#[derive(Debug, Deserialize, Clone)]
+struct BigQueryField {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    fields: Vec<BigQueryField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BigQuerySchema {
+    #[serde(default)]
+    fields: Vec<BigQueryField>,
+}
+
+/// 📦 What `jobs.query`, `jobs.getQueryResults` and `tabledata.list` all answer with — the
+/// parts this source reads. `jobComplete` is absent from `tabledata.list`, where it's always true.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RowsResponse {
+    #[serde(default = "always_complete")]
+    job_complete: bool,
+    #[serde(default)]
+    job_reference: Option<JobReference>,
+    #[serde(default)]
+    schema: Option<BigQuerySchema>,
+    #[serde(default)]
+    rows: Vec<Value>,
+    #[serde(default)]
+    page_token: Option<String>,
+}
+
+fn always_complete() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobReference {
+    project_id: String,
+    job_id: String,
+    #[serde(default)]
+    location: Option<String>,
+}
+
+impl BigQuerySource {
+    /// 🏗️ Checks the config and builds the client. Nothing is sent until the first pump.
+    pub fn new(source_config: BigQuerySourceConfig) -> Result<Self> {
+        if source_config.read_api && cfg!(not(feature = "bigquery-storage")) {
+            anyhow::bail!("💀 read_api = true needs kvx built with the `bigquery-storage` feature — rebuild with it, or drop read_api to read over REST");
+        }
+        let next = match (&source_config.query, &source_config.table) {
+            (Some(_), None) => NextRequest::StartQuery,
+            (None, Some(the_table)) if source_config.read_api => {
+                let (the_project, the_dataset, the_table_id) = table_parts(&source_config, the_table)?;
+                NextRequest::ReadSession { table: format!("projects/{}/datasets/{}/tables/{}", the_project, the_dataset, the_table_id) }
+            }
+            (None, Some(the_table)) => NextRequest::TableSchema { table_url: table_url(&source_config, the_table)? },
+            _ => anyhow::bail!("💀 BigQuery source needs exactly one of `query` or `table`"),
+        };
+        let authorization = match source_config.access_token {
+            Some(ref the_token) => {
+                let mut the_value = HeaderValue::from_str(&format!("Bearer {}", the_token))
+                    .context("💀 access_token isn't a valid HTTP header value")?;
+                the_value.set_sensitive(true);
+                Some(the_value)
+            }
+            None => None,
+        };
+        let client = source_config
+            .timeouts()
+            .apply(Client::builder())
+            .build()
+            .context("💀 Couldn't build the HTTP client for the BigQuery source")?;
+        Ok(Self {
+            source_config,
+            client,
+            authorization,
+            schema: Vec::new(),
+            next: Some(next),
+            pending: VecDeque::new(),
+            #[cfg(feature = "bigquery-storage")]
+            storage: None,
+        })
+    }
+
+    /// 🔑 The token on every request, when there is one.
+    fn authorized(&self, the_request: RequestBuilder) -> RequestBuilder {
+        match self.authorization {
+            Some(ref the_value) => the_request.header(AUTHORIZATION, the_value.clone()),
+            None => the_request,
+        }
+    }
+
+    /// 🩺 For `kvx validate`: a dry run of the query, or a `tables.get` on the table — with the
+    /// same token, so a bad token, project, table or query fails before the run. Reads no rows.
+    pub async fn probe(&self) -> Result<()> {
+        if self.source_config.query.is_some() {
+            let the_url = format!("{}/bigquery/v2/projects/{}/queries", self.endpoint(), self.source_config.project);
+            let mut the_body = json!({
+                "query": self.source_config.query,
+                "useLegacySql": false,
+                "dryRun": true,
+            });
+            if let Some(ref the_location) = self.source_config.location {
+                the_body["location"] = json!(the_location);
+            }
+            let _: Value = send(
+                self.authorized(self.client.post(&the_url).header("Content-Type", "application/json").body(the_body.to_string())),
+                &the_url,
+            )
+            .await?;
+            info!("✅ BigQuery accepted a dry run of the query in project '{}'", self.source_config.project);
+        } else if let Some(ref the_table) = self.source_config.table {
+            let the_url = table_url(&self.source_config, the_table)?;
+            let _: Value = send(self.authorized(self.client.get(the_url.clone())), the_url.as_str()).await?;
+            info!("✅ BigQuery table '{}' is there and readable", the_table);
+        }
+        Ok(())
+    }
+
+    /// 📡 Send the next request, queue its rows, and work out the request after it.
+    async fn fetch(&mut self) -> Result<()> {
+        let Some(the_request) = self.next.take() else {
+            return Ok(());
+        };
+        let the_rows_per_request = self.source_config.rows_per_request.to_string();
+        match the_request {
+            NextRequest::StartQuery => {
+                let the_url = format!("{}/bigquery/v2/projects/{}/queries", self.endpoint(), self.source_config.project);
+                let mut the_body = json!({
+                    "query": self.source_config.query,
+                    "useLegacySql": false,
+                    "maxResults": self.rows_inline(),
+                    "timeoutMs": JOB_WAIT_MS,
+                    "formatOptions": { "useInt64Timestamp": true },
+                });
+                if let Some(ref the_location) = self.source_config.location {
+                    the_body["location"] = json!(the_location);
+                }
+                info!("📊 Starting a BigQuery query job in project '{}'", self.source_config.project);
+                let the_response: RowsResponse = send(
+                    self.authorized(self.client.post(&the_url).header("Content-Type", "application/json").body(the_body.to_string())),
+                    &the_url,
+                )
+                .await?;
+                self.take_query_response(the_response, None)?;
+            }
+            NextRequest::TableSchema { table_url } => {
+                debug!("📐 GET {}", table_url);
+                #[derive(Deserialize)]
+                struct Table {
+                    schema: BigQuerySchema,
+                }
+                let the_table: Table = send(self.authorized(self.client.get(table_url.clone())), table_url.as_str()).await?;
+                self.schema = the_table.schema.fields;
+                let mut the_data_url = table_url;
+                the_data_url.path_segments_mut().map_err(|_| anyhow::anyhow!("💀 BigQuery endpoint can't have a path"))?.push("data");
+                the_data_url
+                    .query_pairs_mut()
+                    .append_pair("maxResults", &the_rows_per_request)
+                    .append_pair("formatOptions.useInt64Timestamp", "true");
+                self.next = Some(NextRequest::TableData { url: the_data_url, page_token: None });
+            }
+            NextRequest::QueryResults { url, page_token } => {
+                let the_page_url = with_page_token(&url, page_token.as_deref());
+                debug!("🔁 GET {}", the_page_url);
+                let the_response: RowsResponse = send(self.authorized(self.client.get(the_page_url.clone())), the_page_url.as_str()).await?;
+                self.take_query_response(the_response, page_token)?;
+            }
+            NextRequest::TableData { url, page_token } => {
+                let the_page_url = with_page_token(&url, page_token.as_deref());
+                debug!("📋 GET {}", the_page_url);
+                let the_response: RowsResponse = send(self.authorized(self.client.get(the_page_url.clone())), the_page_url.as_str()).await?;
+                self.take_rows(&the_response.rows)?;
+                self.next = next_page(page_token, the_response.page_token)?.map(|the_token| NextRequest::TableData { url, page_token: Some(the_token) });
+            }
+            NextRequest::JobTable { url } => {
+                debug!("🎯 GET {}", url);
+                let the_job: Value = send(self.authorized(self.client.get(url.clone())), url.as_str()).await?;
+                let the_destination = the_job
+                    .pointer("/configuration/query/destinationTable")
+                    .context("💀 The query job has no destination table to open a read session on (a multi-statement script?) — drop read_api for this query")?;
+                let the_part = |the_key: &str| {
+                    the_destination.get(the_key).and_then(Value::as_str).with_context(|| format!("💀 The job's destination table has no {}", the_key))
+                };
+                let the_table = format!("projects/{}/datasets/{}/tables/{}", the_part("projectId")?, the_part("datasetId")?, the_part("tableId")?);
+                self.next = Some(NextRequest::ReadSession { table: the_table });
+            }
+            #[cfg(feature = "bigquery-storage")]
+            NextRequest::ReadSession { table } => {
+                info!("🚀 Opening a BigQuery Storage Read API session on '{}'", table);
+                self.storage = Some(StorageRead::open(&self.source_config, &table).await?);
+                self.next = Some(NextRequest::ReadRows);
+            }
+            #[cfg(feature = "bigquery-storage")]
+            NextRequest::ReadRows => {
+                let the_storage = self.storage.as_mut().context("💀 ReadRows before a read session was opened")?;
+                match the_storage.next_rows().await? {
+                    Some(the_docs) => {
+                        self.pending.extend(the_docs.iter().map(Value::to_string));
+                        self.next = Some(NextRequest::ReadRows);
+                    }
+                    None => self.storage = None,
+                }
+            }
+            #[cfg(not(feature = "bigquery-storage"))]
+            NextRequest::ReadSession { .. } | NextRequest::ReadRows => {
+                anyhow::bail!("💀 read_api = true needs kvx built with the `bigquery-storage` feature")
+            }
+        }
+        Ok(())
+    }
+
+    /// 📄 `maxResults` for the query requests — none at all when the read session fetches the rows.
+    fn rows_inline(&self) -> u32 {
+        if self.source_config.read_api { 0 } else { self.source_config.rows_per_request }
+    }
+
+    /// 📦 A `jobs.query` / `jobs.getQueryResults` answer: poll again if the job isn't done,
+    /// otherwise queue the rows and follow `pageToken`.
+    fn take_query_response(&mut self, the_response: RowsResponse, the_last_token: Option<String>) -> Result<()> {
+        let the_job = the_response.job_reference.context("💀 BigQuery answered the query without a job reference")?;
+        let mut the_url = Url::parse(self.endpoint()).context("💀 BigQuery endpoint isn't a URL")?;
+        the_url
+            .path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("💀 BigQuery endpoint can't have a path"))?
+            .pop_if_empty()
+            .extend(["bigquery", "v2", "projects", &the_job.project_id, "queries", &the_job.job_id]);
+        the_url
+            .query_pairs_mut()
+            .append_pair("maxResults", &self.rows_inline().to_string())
+            .append_pair("timeoutMs", &JOB_WAIT_MS.to_string())
+            .append_pair("formatOptions.useInt64Timestamp", "true");
+        if let Some(ref the_location) = the_job.location {
+            the_url.query_pairs_mut().append_pair("location", the_location);
+        }
+        if !the_response.job_complete {
+            debug!("⏳ BigQuery job '{}' is still running", the_job.job_id);
+            self.next = Some(NextRequest::QueryResults { url: the_url, page_token: the_last_token });
+            return Ok(());
+        }
+        if self.source_config.read_api {
+            let mut the_job_url = Url::parse(self.endpoint()).context("💀 BigQuery endpoint isn't a URL")?;
+            the_job_url
+                .path_segments_mut()
+                .map_err(|_| anyhow::anyhow!("💀 BigQuery endpoint can't have a path"))?
+                .pop_if_empty()
+                .extend(["bigquery", "v2", "projects", &the_job.project_id, "jobs", &the_job.job_id]);
+            if let Some(ref the_location) = the_job.location {
+                the_job_url.query_pairs_mut().append_pair("location", the_location);
+            }
+            self.next = Some(NextRequest::JobTable { url: the_job_url });
+            return Ok(());
+        }
+        if let Some(the_schema) = the_response.schema {
+            self.schema = the_schema.fields;
+        }
+        self.take_rows(&the_response.rows)?;
+        self.next = next_page(the_last_token, the_response.page_token)?.map(|the_token| NextRequest::QueryResults { url: the_url, page_token: Some(the_token) });
+        Ok(())
+    }
+
+    /// 🧩 Rows → docs, into `pending`.
+    fn take_rows(&mut self, the_rows: &[Value]) -> Result<()> {
+        for the_row in the_rows {
+            self.pending.push_back(to_doc(&self.schema, the_row)?.to_string());
+        }
+        Ok(())
+    }
+
+    fn endpoint(&self) -> &str {
+        self.source_config.endpoint.trim_end_matches('/')
+    }
+}
+
+/// 📡 Send, and read the JSON answer — or fail with BigQuery's own `error.message`.
+async fn send<T: for<'de> Deserialize<'de>>(the_request: RequestBuilder, the_url: &str) -> Result<T> {
+    let the_response = the_request.send().await.with_context(|| format!("💀 '{}' didn't get an answer", the_url))?;
+    let the_status = the_response.status();
+    let the_bytes = the_response.bytes().await.with_context(|| format!("💀 '{}' broke off mid-response", the_url))?;
+    if !the_status.is_success() {
+        let the_message = serde_json::from_slice::<Value>(&the_bytes)
+            .ok()
+            .and_then(|v| v.pointer("/error/message").and_then(Value::as_str).map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&the_bytes).chars().take(200).collect());
+        anyhow::bail!("💀 BigQuery answered {} to '{}': {}", the_status, the_url, the_message);
+    }
+    serde_json::from_slice(&the_bytes).with_context(|| format!("💀 '{}' answered with JSON this source doesn't understand", the_url))
+}
+
+/// 🪓 `dataset.table` or `project.dataset.table` → (project, dataset, table).
+fn table_parts<'a>(the_config: &'a BigQuerySourceConfig, the_table: &'a str) -> Result<(&'a str, &'a str, &'a str)> {
+    // -- 🪓 from the right: domain-scoped projects (`example.com:proj`) have dots of their own
+    let the_parts: Vec<&str> = the_table.trim_matches('`').rsplitn(3, '.').collect();
+    Ok(match the_parts.as_slice() {
+        [the_table_id, the_dataset] => (the_config.project.as_str(), *the_dataset, *the_table_id),
+        [the_table_id, the_dataset, the_project] => (*the_project, *the_dataset, *the_table_id),
+        _ => anyhow::bail!("💀 BigQuery table '{}' should be `dataset.table` or `project.dataset.table`", the_table),
+    })
+}
+
+/// 📋 `…/projects/{p}/datasets/{d}/tables/{t}` for `dataset.table` or `project.dataset.table`.
+fn table_url(the_config: &BigQuerySourceConfig, the_table: &str) -> Result<Url> {
+    let (the_project, the_dataset, the_table_id) = table_parts(the_config, the_table)?;
+    let mut the_url = Url::parse(the_config.endpoint.trim_end_matches('/'))
+        .with_context(|| format!("💀 BigQuery endpoint '{}' isn't a URL", the_config.endpoint))?;
+    the_url
+        .path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("💀 BigQuery endpoint '{}' can't have a path", the_config.endpoint))?
+        .pop_if_empty()
+        .extend(["bigquery", "v2", "projects", the_project, "datasets", the_dataset, "tables", the_table_id]);
+    Ok(the_url)
+}
+
+/// 🔖 `url` with `pageToken` set, when there is one.
+fn with_page_token(url: &Url, page_token: Option<&str>) -> Url {
+    let mut the_url = url.clone();
+    if let Some(the_token) = page_token {
+        the_url.query_pairs_mut().append_pair("pageToken", the_token);
+    }
+    the_url
+}
+
+/// 🔖 The next page token — `None` at the end. The same token twice would loop forever.
+fn next_page(the_last: Option<String>, the_next: Option<String>) -> Result<Option<String>> {
+    match the_next.filter(|t| !t.is_empty()) {
+        Some(the_token) if the_last.as_deref() == Some(the_token.as_str()) => {
+            anyhow::bail!("💀 BigQuery handed back the same page token twice — stopping before it loops forever")
+        }
+        the_next => Ok(the_next),
+    }
+}
+
+/// 📄 One `{"f":[{"v":...}]}` row as a JSON object, column by column.
+fn to_doc(fields: &[BigQueryField], row: &Value) -> Result<Value> {
+    let the_cells = row.get("f").and_then(Value::as_array).context("💀 BigQuery sent a row without cells")?;
+    let mut the_doc = Map::with_capacity(fields.len());
+    for (the_field, the_cell) in fields.iter().zip(the_cells) {
+        let the_value = the_cell.get("v").unwrap_or(&Value::Null);
+        let the_value = if the_field.mode.as_deref() == Some("REPEATED") {
+            match the_value {
+                Value::Array(the_items) => Value::Array(
+                    the_items
+                        .iter()
+                        .map(|the_item| to_value(the_field, the_item.get("v").unwrap_or(&Value::Null)))
+                        .collect::<Result<_>>()?,
+                ),
+                _ => Value::Array(Vec::new()),
+            }
+        } else {
+            to_value(the_field, the_value)?
+        };
+        the_doc.insert(the_field.name.clone(), the_value);
+    }
+    Ok(Value::Object(the_doc))
+}
+
+/// 🔢 One cell, typed by its column. Anything without a better JSON shape (`NUMERIC`, dates,
+/// `BYTES` as base64, `GEOGRAPHY` as WKT) stays the string BigQuery sent — no precision lost.
+fn to_value(field: &BigQueryField, value: &Value) -> Result<Value> {
+    let Some(the_text) = value.as_str() else {
+        return match value {
+            Value::Null => Ok(Value::Null),
+            Value::Object(_) if matches!(field.kind.as_str(), "RECORD" | "STRUCT") => to_doc(&field.fields, value),
+            the_other => Ok(the_other.clone()),
+        };
+    };
+    Ok(match field.kind.as_str() {
+        "INTEGER" | "INT64" => the_text.parse::<i64>().map(Value::from).unwrap_or_else(|_| json!(the_text)),
+        // -- 🫧 NaN and ±Infinity have no JSON number, so they stay strings
+        "FLOAT" | "FLOAT64" => the_text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| json!(the_text)),
+        "BOOLEAN" | "BOOL" => match the_text {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => json!(the_text),
+        },
+        // -- ⏱️ microseconds since the epoch, thanks to `useInt64Timestamp`
+        "TIMESTAMP" => the_text
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .map(|the_time| json!(the_time.to_rfc3339_opts(SecondsFormat::AutoSi, true)))
+            .unwrap_or_else(|| json!(the_text)),
+        "JSON" => serde_json::from_str(the_text).unwrap_or_else(|_| json!(the_text)),
+        _ => json!(the_text),
+    })
+}
+
+#[async_trait]
+impl Source for BigQuerySource {
+    /// 📄 The next page of rows — fetching (and waiting on the job) until there's something to
+    /// hand out. `None` once the last page has been fetched and paged out.
+    async fn pump(&mut self) -> Result<Option<Page>> {
+        // -- 🔁 the schema request and an unfinished job both come back with no rows
+        while self.pending.is_empty() && self.next.is_some() {
+            self.fetch().await?;
+        }
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+        let the_tuning = self.source_config.tuning();
+        let mut the_page = String::new();
+        let mut the_doc_count = 0usize;
+        while the_doc_count < the_tuning.max_batch_size_docs && the_page.len() < the_tuning.max_batch_size_bytes {
+            let Some(the_row) = self.pending.pop_front() else {
+                break;
+            };
+            if !the_page.is_empty() {
+                the_page.push('\n');
+            }
+            the_page.push_str(&the_row);
+            the_doc_count += 1;
+        }
+        Ok(Some(Page(the_page)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn drain(the_source: &mut BigQuerySource) -> Result<Vec<String>> {
+        let mut the_pages = Vec::new();
+        while let Some(Page(the_page)) = the_source.pump().await? {
+            the_pages.push(the_page);
+        }
+        Ok(the_pages)
+    }
+
+    fn the_config(the_server: &MockServer, the_toml: &str) -> BigQuerySourceConfig {
+        toml::from_str(&format!("project = \"acme\"\nendpoint = \"{}\"\naccess_token = \"ya29.t0ken\"\n{}", the_server.uri(), the_toml))
+            .expect("💀 test config didn't parse")
+    }
+
+    /// 🧪 A query job that isn't done on the first answer is polled, then paged through with
+    /// `pageToken` — and every column type lands as the JSON it should be.
+    #[tokio::test]
+    async fn the_one_where_the_query_takes_its_time() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bigquery/v2/projects/acme/queries"))
+            .and(header("authorization", "Bearer ya29.t0ken"))
+            .and(body_partial_json(json!({"query": "SELECT 1", "useLegacySql": false, "location": "EU"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobComplete": false,
+                "jobReference": {"projectId": "acme", "jobId": "job_1", "location": "EU"}
+            })))
+            .expect(1)
+            .mount(&the_server)
+            .await;
+        let the_schema = json!({"fields": [
+            {"name": "id", "type": "INTEGER", "mode": "REQUIRED"},
+            {"name": "price", "type": "FLOAT"},
+            {"name": "live", "type": "BOOLEAN"},
+            {"name": "at", "type": "TIMESTAMP"},
+            {"name": "tags", "type": "STRING", "mode": "REPEATED"},
+            {"name": "ship", "type": "RECORD", "fields": [{"name": "zip", "type": "STRING"}, {"name": "kg", "type": "NUMERIC"}]},
+            {"name": "extra", "type": "JSON"}
+        ]});
+        Mock::given(method("GET"))
+            .and(path("/bigquery/v2/projects/acme/queries/job_1"))
+            .and(query_param("location", "EU"))
+            .and(query_param_is_missing("pageToken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobComplete": true,
+                "jobReference": {"projectId": "acme", "jobId": "job_1", "location": "EU"},
+                "schema": the_schema,
+                "rows": [{"f": [
+                    {"v": "1"}, {"v": "9.5"}, {"v": "true"}, {"v": "1767225600000000"},
+                    {"v": [{"v": "a"}, {"v": "b"}]}, {"v": {"f": [{"v": "02139"}, {"v": "1.50"}]}}, {"v": "{\"k\":1}"}
+                ]}],
+                "pageToken": "p2"
+            })))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bigquery/v2/projects/acme/queries/job_1"))
+            .and(query_param("pageToken", "p2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobComplete": true,
+                "jobReference": {"projectId": "acme", "jobId": "job_1", "location": "EU"},
+                "rows": [{"f": [{"v": "2"}, {"v": null}, {"v": "false"}, {"v": null}, {"v": []}, {"v": null}, {"v": null}]}]
+            })))
+            .mount(&the_server)
+            .await;
+
+        let mut the_source = BigQuerySource::new(the_config(&the_server, "query = \"SELECT 1\"\nlocation = \"EU\""))?;
+        assert_eq!(
+            drain(&mut the_source).await?,
+            vec![
+                r#"{"id":1,"price":9.5,"live":true,"at":"2026-01-01T00:00:00Z","tags":["a","b"],"ship":{"zip":"02139","kg":"1.50"},"extra":{"k":1}}"#,
+                r#"{"id":2,"price":null,"live":false,"at":null,"tags":[],"ship":null,"extra":null}"#,
+            ]
+        );
+        Ok(())
+    }
+
+    /// 🧪 A table is read with `tables.get` + `tabledata.list`, no query job; a refused request
+    /// fails with BigQuery's message; and `query` + `table` together don't start at all.
+    #[tokio::test]
+    async fn the_one_where_the_whole_table_comes_along() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bigquery/v2/projects/warehouse/datasets/shop/tables/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "schema": {"fields": [{"name": "sku", "type": "STRING"}, {"name": "qty", "type": "INT64"}]}
+            })))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bigquery/v2/projects/warehouse/datasets/shop/tables/orders/data"))
+            .and(query_param("maxResults", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "totalRows": "2",
+                "rows": [{"f": [{"v": "A-1"}, {"v": "3"}]}, {"f": [{"v": "B-2"}, {"v": "1"}]}]
+            })))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST")).respond_with(ResponseTemplate::new(500)).expect(0).mount(&the_server).await;
+        let mut the_table =
+            BigQuerySource::new(the_config(&the_server, "table = \"warehouse.shop.orders\"\nrows_per_request = 2"))?;
+        assert_eq!(drain(&mut the_table).await?, vec![r#"{"sku":"A-1","qty":3}"#.to_string() + "\n" + r#"{"sku":"B-2","qty":1}"#]);
+
+        Mock::given(method("GET"))
+            .and(path("/bigquery/v2/projects/acme/datasets/shop/tables/secret"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "error": {"code": 403, "message": "Access Denied: Table acme:shop.secret", "status": "PERMISSION_DENIED"}
+            })))
+            .mount(&the_server)
+            .await;
+        let mut the_denied = BigQuerySource::new(the_config(&the_server, "table = \"shop.secret\""))?;
+        let the_error = format!("{:#}", the_denied.pump().await.expect_err("💀 403 is a failure"));
+        assert!(the_error.contains("Access Denied: Table acme:shop.secret"), "{the_error}");
+
+        assert!(BigQuerySource::new(the_config(&the_server, "query = \"SELECT 1\"\ntable = \"shop.orders\"")).is_err());
+        assert!(BigQuerySource::new(the_config(&the_server, "table = \"orders\"")).is_err(), "💀 no dataset");
+        #[cfg(not(feature = "bigquery-storage"))]
+        assert!(
+            BigQuerySource::new(the_config(&the_server, "table = \"shop.orders\"\nread_api = true")).is_err(),
+            "💀 read_api without the bigquery-storage feature"
+        );
+        assert!(next_page(Some("t".into()), Some("t".into())).is_err(), "🔁 the same token twice is a loop");
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 BigQuery source config — a query or a table, the project that pays for it, and a token.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::backends::config::resolve_secret_file;
+use crate::backends::{CommonSourceConfig, HttpTimeouts, SourceTuning};
+
+/// 📊 `[source_config.BigQuery]` — the rows of a GoogleSQL query, or of a whole table.
+///
+/// ```toml
+/// [source_config.BigQuery]
+/// project = "acme-analytics"
+/// query = "SELECT * FROM `acme-analytics.shop.orders` WHERE created >= '2026-01-01'"
+/// access_token_file = "/run/secrets/gcp_token"
+/// ```
+#[derive(Debug, Deserialize, Clone)]
+pub struct BigQuerySourceConfig {
+    /// 💳 The project the query job runs (and is billed) in. Also the default project for `table`.
+    pub project: String,
+    /// 🔎 A GoogleSQL query. Exactly one of `query` / `table`.
+    #[serde(default)]
+    pub query: Option<String>,
+    /// 📋 `dataset.table` or `project.dataset.table` — every row, no query job, no query bill.
+    #[serde(default)]
+    pub table: Option<String>,
+    /// 🌍 Where the query job runs (`US`, `EU`, `europe-west1`). Default: BigQuery works it out.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// 📄 Rows per response (`maxResults`). BigQuery may send fewer: responses stop at ~10 MB.
+    #[serde(default = "default_rows_per_request")]
+    pub rows_per_request: u32,
+    /// 🔑 OAuth 2.0 access token, sent as `Authorization: Bearer ...`
+    /// (`gcloud auth print-access-token`, or the metadata server's).
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// 🔑 Read `access_token` from this file instead of keeping it in the config
+    #[serde(default)]
+    pub access_token_file: Option<PathBuf>,
+    /// 📡 The API root. Only worth changing for a proxy or an emulator.
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// 🚀 Read rows through the Storage Read API (Avro over gRPC) instead of REST pages.
+    /// A `query` still runs as a job; the session reads its destination table. Needs a build
+    /// with the `bigquery-storage` feature.
+    #[serde(default)]
+    pub read_api: bool,
+    /// 📡 The Storage Read API root, for `read_api`. Only worth changing for a proxy or an emulator.
+    #[serde(default = "default_storage_endpoint")]
+    pub storage_endpoint: String,
+    #[serde(default)]
+    pub common_config: CommonSourceConfig,
+}
+
+fn default_rows_per_request() -> u32 {
+    10_000
+}
+
+fn default_endpoint() -> String {
+    "https://bigquery.googleapis.com".to_string()
+}
+
+fn default_storage_endpoint() -> String {
+    "https://bigquerystorage.googleapis.com".to_string()
+}
+
+impl BigQuerySourceConfig {
+    /// 🎚️ BigQuery source profile: ES's 1k docs / 10MB. `rows_per_request` decides how many
+    /// rows each response carries; these only cut them into pages for the pipeline.
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 1_000,
+        max_batch_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s connect, 120s per request — a query that hasn't finished is polled, but each
+    /// poll waits server-side for up to a minute first.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(120),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `access_token_file` for the token it points at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.access_token, self.access_token_file.as_deref(), "access_token")
+    }
+}
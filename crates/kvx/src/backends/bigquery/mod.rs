@@ -0,0 +1,23 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! # 📊 THE BIGQUERY BACKEND
+//!
+//! 🎬 COLD OPEN — INT. THE DATA WAREHOUSE — THE ROWS ARE ALL THERE
+//! *[the product team wants the orders table searchable. "just export it," says the ticket.]*
+//! *[export to GCS. download from GCS. convert Avro to NDJSON. delete from GCS. explain the bucket bill.]*
+//!
+//! Source-only backend that runs a GoogleSQL query, or reads a whole table, through
+//! BigQuery's REST API — or, with `read_api = true` and the `bigquery-storage` feature, the
+//! Storage Read API — and emits one NDJSON doc per row, typed by the result schema. 🦆
+
+pub mod config;
+#[cfg(feature = "bigquery-storage")]
+mod avro;
+mod bigquery_source;
+#[cfg(feature = "bigquery-storage")]
+mod storage_read;
+
+pub use bigquery_source::BigQuerySource;
+pub use config::BigQuerySourceConfig;
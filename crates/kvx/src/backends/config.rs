@@ -15,6 +15,10 @@
 //!   backend-level concern — how big a feed do we pull?
 //! - `CommonSinkConfig`: embedded in every backend sink config (`ElasticsearchSinkConfig`,
 //!   `FileSinkConfig`). Controls max request size in bytes. How big a payload do we push?
+//! - `connect_timeout_secs` / `request_timeout_secs` (both commons): resolved into `HttpTimeouts`
+//!   against each HTTP backend's `DEFAULT_TIMEOUTS`. File and InMemory just don't ask.
+//! - `SourceTuning` / `SinkTuning`: the *resolved* knobs. Each backend config owns a
+//!   `DEFAULT_TUNING` profile; fields the user omits fall back to it.
 //! - Both are re-exported from `backends.rs` so callers can `use crate::backends::CommonSinkConfig`
 //! - **Former home**: `supervisors/config.rs` — evicted in the Great Config Migration of 2026.
 //!   The landlord was `app_config.rs`. The neighbors were happy to see them go.
@@ -26,8 +30,114 @@
 //! "He who puts common config in app_config, creates circular imports in production."
 //!   — Ancient Rust module proverb, written in tears at 3am 💀
 
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
 use serde::Deserialize;
 
+use crate::manifolds::{CsvConfig, ManifoldFormat, SqlInsertConfig};
+
+// ============================================================
+// 🔑 Secret files — credentials mounted from disk (Kubernetes secrets, Docker secrets, ...)
+// ============================================================
+
+/// 🔑 Fill `the_inline` from `the_file`, if a file was configured.
+///
+/// Trailing newlines are stripped (every `echo "hunter2" > secret` ever written adds one).
+/// Setting both the inline value and the file is a config error — two sources of truth
+/// is zero sources of truth. 🦆
+pub(crate) fn resolve_secret_file(
+    the_inline: &mut Option<String>,
+    the_file: Option<&Path>,
+    the_field: &str,
+) -> Result<()> {
+    let Some(the_path) = the_file else {
+        return Ok(());
+    };
+    if the_inline.is_some() {
+        anyhow::bail!(
+            "💀 Both `{the_field}` and `{the_field}_file` are set. Pick one — we refuse to guess which secret is the real one."
+        );
+    }
+    let the_secret = std::fs::read_to_string(the_path).with_context(|| {
+        format!(
+            "💀 Could not read `{the_field}_file` at '{}'. Is the secret actually mounted there?",
+            the_path.display()
+        )
+    })?;
+    let the_secret = the_secret.trim_end_matches(['\n', '\r']);
+    if the_secret.is_empty() {
+        anyhow::bail!(
+            "💀 `{the_field}_file` at '{}' is empty. An empty secret is just a very confident username.",
+            the_path.display()
+        );
+    }
+    *the_inline = Some(the_secret.to_string());
+    Ok(())
+}
+
+// ============================================================
+// 🎚️ Tuning profiles — per-backend defaults for omitted knobs
+// ============================================================
+
+/// 🎚️ Resolved source-side knobs — what a source actually runs with after
+/// the user's TOML has been layered on top of the backend's own defaults.
+///
+/// 🧠 Knowledge graph:
+/// - Every source backend config exposes a `DEFAULT_TUNING` const with its opinions
+/// - `CommonSourceConfig::resolve(defaults)` fills the gaps the user left blank
+/// - `SourceConfig::tuning()` is the one-stop shop for "what are we really using"
+///
+/// One global default was wrong for almost everyone. Now each backend gets to be wrong
+/// in its own, carefully considered way. Progress. 🦆
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceTuning {
+    /// 📦 Max docs per batch feed
+    pub max_batch_size_docs: usize,
+    /// 📦 Max bytes per batch feed
+    pub max_batch_size_bytes: usize,
+}
+
+/// 🎚️ Resolved sink-side knobs — the sink's defaults with user overrides applied.
+///
+/// 🧠 Knowledge graph: `SinkConfig::tuning()` resolves this, `SinkConfig::max_request_size_bytes()`
+/// reads it, and the FlowKnob/Joiner flush threshold is born from it. 🚰
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SinkTuning {
+    /// 🚰 Max payload bytes per sink request
+    pub max_request_size_bytes: usize,
+}
+
+/// ⏱️ Resolved HTTP client timeouts for one backend.
+///
+/// 🧠 Knowledge graph: each HTTP backend config has a `DEFAULT_TIMEOUTS` const and a
+/// `timeouts()` that layers the user's `connect_timeout_secs` / `request_timeout_secs` on top.
+/// The request timeout covers the whole round trip, body included — a 10MB `_bulk` into
+/// a cluster mid-merge can need a lot longer than anyone's patience. ⏳
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpTimeouts {
+    /// 🤝 TCP + TLS handshake
+    pub connect: Duration,
+    /// ⏳ Whole request, send to last byte of the response
+    pub request: Duration,
+}
+
+impl HttpTimeouts {
+    /// 🎚️ Explicit seconds win; `None` keeps the backend's opinion.
+    fn layered(self, connect_secs: Option<u64>, request_secs: Option<u64>) -> Self {
+        Self {
+            connect: connect_secs.map(Duration::from_secs).unwrap_or(self.connect),
+            request: request_secs.map(Duration::from_secs).unwrap_or(self.request),
+        }
+    }
+
+    /// 🏗️ Apply both to a client builder.
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder.connect_timeout(self.connect).timeout(self.request)
+    }
+}
+
 // ============================================================
 // 📦 CommonSourceConfig — shared source-side knobs
 // ============================================================
@@ -42,43 +152,39 @@ use serde::Deserialize;
 /// - Embedded in `ElasticsearchSourceConfig`, `FileSourceConfig` (and future source configs)
 /// - `max_batch_size_docs`: doc-count ceiling per feed (ES scroll size, etc.)
 /// - `max_batch_size_bytes`: byte-size ceiling per feed (avoid sending 1GB feeds)
-/// - The DEFAULT impl gives conservative values (1000 docs / 1MB)
-///   while the serde defaults give more generous values (10k docs / 10MB)
-///   because apparently we have two opinions and we're committed to both 🦆
-///
-/// No cap: these defaults were chosen empirically by staring at them until they felt right.
-#[derive(Debug, Deserialize, Clone)]
+/// - Both are `Option` — `None` means "the backend knows best", resolved via
+///   `resolve()` against the backend's `DEFAULT_TUNING`. There used to be two global
+///   opinions here (serde vs `Default`). Now there are zero global opinions. Growth. 🌱
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct CommonSourceConfig {
     /// 📦 Max docs per batch feed — the doc-count speed limiter
-    #[serde(default = "default_max_batch_size_docs")]
-    pub max_batch_size_docs: usize,
+    #[serde(default)]
+    pub max_batch_size_docs: Option<usize>,
     /// 📦 Max bytes per batch feed — the byte-size speed limiter
-    #[serde(default = "default_max_batch_size_bytes")]
-    pub max_batch_size_bytes: usize,
-}
-
-// 📦 10,000 docs per batch — a nice round number that will age like milk
-// the moment someone indexes a 50MB PDF and wonders why things are slow.
-fn default_max_batch_size_docs() -> usize {
-    10000
+    #[serde(default)]
+    pub max_batch_size_bytes: Option<usize>,
+    /// 🤝 HTTP and Redis sources only: connect timeout in seconds (`None` = backend default)
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// ⏳ HTTP and Redis sources only: per-request timeout in seconds (`None` = backend default)
+    #[serde(default)]
+    pub request_timeout_secs: Option<u64>,
 }
 
-// 📦 10MB — chosen because 10 is a great number and MB is a great unit.
-// This is load-tested in the same way I've "tested" my microwave: empirically, at 3am, with regret.
-// 10 * 1024 * 1024 = 10485760. Yes I know. Yes the comment on the line is doing the math. You're welcome.
-fn default_max_batch_size_bytes() -> usize {
-    10485760
-} // -- 10MB — if your documents are bigger, we need to talk
-
-impl Default for CommonSourceConfig {
-    fn default() -> Self {
-        Self {
-            // 🎯 1000 docs / 1MB per batch — sensible defaults chosen by someone who definitely
-            // did NOT just pick round numbers and call it "empirically validated"
-            max_batch_size_docs: 1000,
-            max_batch_size_bytes: 1024 * 1024,
+impl CommonSourceConfig {
+    /// 🎚️ Layer the user's explicit knobs over the backend's defaults.
+    /// Whatever the user said wins. Whatever they didn't say, the backend fills in. 🔧
+    pub fn resolve(&self, defaults: SourceTuning) -> SourceTuning {
+        SourceTuning {
+            max_batch_size_docs: self.max_batch_size_docs.unwrap_or(defaults.max_batch_size_docs),
+            max_batch_size_bytes: self.max_batch_size_bytes.unwrap_or(defaults.max_batch_size_bytes),
         }
     }
+
+    /// ⏱️ Same layering, for the HTTP client timeouts.
+    pub fn timeouts(&self, defaults: HttpTimeouts) -> HttpTimeouts {
+        defaults.layered(self.connect_timeout_secs, self.request_timeout_secs)
+    }
 }
 
 // ============================================================
@@ -88,51 +194,81 @@ impl Default for CommonSourceConfig {
 /// 🚰 Shared configuration embedded by every sink backend config.
 ///
 /// Controls the maximum request payload size when sending data to the sink.
-/// The `Drainer` uses this to decide when to flush its feed buffer —
+/// The `Joiner` uses this to decide when to flush its feed buffer —
 /// accumulate until approaching this limit, then join + send. 💡
 ///
 /// 🧠 Knowledge graph:
 /// - Embedded in `ElasticsearchSinkConfig`, `FileSinkConfig` (and future sink configs)
-/// - `max_request_size_bytes`: flush threshold for the Drainer buffer
-/// - Default is 64MB — generous, because we trust the sink to handle it
-///   (and because the Elasticsearch docs said "up to 100MB" and we wanted buffer room) 🔧
-/// - Serde default fn gives 10MB (the "I'm being careful" default)
-/// - The `Default` impl gives 64MB (the "I'm feeling confident today" default)
-/// - These being different is a known quirk. It's not a bug. It's a vibe. 🦆
+/// - `max_request_size_bytes`: flush threshold for the Joiner buffer
+/// - `None` → the backend's `DEFAULT_TUNING` decides. Elasticsearch wants 5–15MB bulks,
+///   a file wants to gulp 64MB at a time. One number cannot serve both masters. 🔧
 ///
 /// Knock knock. Who's there? Race condition. Race condition wh— Who's there?
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct CommonSinkConfig {
     /// 🚰 Max payload bytes per sink request — the flush trigger
-    #[serde(default = "default_max_request_size_bytes")]
-    pub max_request_size_bytes: usize,
+    #[serde(default)]
+    pub max_request_size_bytes: Option<usize>,
+    /// 🤝 HTTP sinks only: connect timeout in seconds (`None` = backend default)
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// ⏳ HTTP sinks only: per-request timeout in seconds (`None` = backend default).
+    /// Raise it when big bulks against busy clusters time out.
+    #[serde(default)]
+    pub request_timeout_secs: Option<u64>,
+    /// 🎛️ Override the sink's payload shape (`ndjson` / `json_array`). `None` = the sink's own.
+    /// See `ManifoldBackend::from_sink_config` for which sinks can take which.
+    #[serde(default, alias = "composer")]
+    pub manifold: Option<ManifoldFormat>,
+    /// 🗄️ Table + column mapping for `manifold = "sql_insert"` — `[sink_config.File.sql_insert]`
+    #[serde(default)]
+    pub sql_insert: Option<SqlInsertConfig>,
+    /// 📊 Column list + framing for `manifold = "csv"` — `[sink_config.File.csv]`
+    #[serde(default)]
+    pub csv: Option<CsvConfig>,
 }
 
-// 🚰 10MB sink request size — the same limit as your email attachment policy,
-// your Slack upload quota, and your therapist's patience. Coincidence? Absolutely yes.
-fn default_max_request_size_bytes() -> usize {
-    10485760
-} // -- 10MB — Elasticsearch's feelings
-
-impl Default for CommonSinkConfig {
-    fn default() -> Self {
-        CommonSinkConfig {
-            // 🚰 64MB default request size because we dream big
-            // (and because the Elasticsearch docs said "up to 100MB" and we wanted buffer)
-            max_request_size_bytes: 64 * 1024 * 1024,
+impl CommonSinkConfig {
+    /// 🎚️ Same deal as `CommonSourceConfig::resolve` — explicit beats default, always. 🚰
+    pub fn resolve(&self, defaults: SinkTuning) -> SinkTuning {
+        SinkTuning {
+            max_request_size_bytes: self.max_request_size_bytes.unwrap_or(defaults.max_request_size_bytes),
         }
     }
+
+    /// ⏱️ Same layering, for the HTTP client timeouts.
+    pub fn timeouts(&self, defaults: HttpTimeouts) -> HttpTimeouts {
+        defaults.layered(self.connect_timeout_secs, self.request_timeout_secs)
+    }
 }
 
+// 🧪 InMemory has no config struct, so its profile lives here. 64MB because tests dream big.
+const IN_MEMORY_SINK_TUNING: SinkTuning = SinkTuning {
+    max_request_size_bytes: 64 * 1024 * 1024,
+};
+
+// 🧪 InMemory source ignores batch knobs entirely, but the profile still has to say something.
+const IN_MEMORY_SOURCE_TUNING: SourceTuning = SourceTuning {
+    max_batch_size_docs: 1000,
+    max_batch_size_bytes: 1024 * 1024,
+};
+
 // ============================================================
 // 🎭 SourceConfig / SinkConfig — the velvet rope at the backend club
 // ============================================================
 
 use crate::backends::elasticsearch::ElasticsearchSourceConfig;
 use crate::backends::elasticsearch::ElasticsearchSinkConfig;
-use crate::backends::file::{FileSourceConfig, FileSinkConfig};
+use crate::backends::file::{FileSourceConfig, FileSinkConfig, UrlSourceConfig};
+use crate::backends::generator::GeneratorSourceConfig;
 use crate::backends::meilisearch::MeilisearchSinkConfig;
+use crate::backends::null::NullSinkConfig;
 use crate::backends::open_observe::OpenObserveSinkConfig;
+use crate::backends::bigquery::BigQuerySourceConfig;
+use crate::backends::redis::RedisSourceConfig;
+use crate::backends::rest::RestSourceConfig;
+use crate::backends::solr::SolrSinkConfig;
+use crate::backends::custom::{CustomSinkConfig, CustomSourceConfig};
 
 /// 🎭 SourceConfig: the velvet rope at the backend club.
 /// You are either a File, an Elasticsearch, or an InMemory.
@@ -140,14 +276,29 @@ use crate::backends::open_observe::OpenObserveSinkConfig;
 /// (Until someone files a feature request. There is always a feature request.)
 ///
 /// 🧠 Knowledge graph: resolved at startup into a `SourceBackend` by `lib.rs`. 🚰
+// -- 📏 built once per run, never in a hot path — boxing variants would buy nothing but noise
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize, Clone)]
 pub enum SourceConfig {
     /// 📡 Read from an Elasticsearch index via scroll API
     Elasticsearch(ElasticsearchSourceConfig),
     /// 📂 Read from a local file (NDJSON or Rally JSON array)
     File(FileSourceConfig),
+    /// 🌐 Stream an NDJSON document from an HTTP(S) URL — read like a file, as it downloads
+    Url(UrlSourceConfig),
+    /// 🏭 Synthetic NDJSON docs — a count and a size, no corpus needed
+    Generator(GeneratorSourceConfig),
+    /// 🌍 Page through a JSON API — records by JSON pointer, cursor / next-link / page-number pagination
+    Rest(RestSourceConfig),
+    /// 🟥 Every Redis key matching a pattern — strings, hashes and Redis-JSON, one doc per key
+    Redis(RedisSourceConfig),
+    /// 📊 The rows of a BigQuery query or table, typed by the result schema
+    BigQuery(BigQuerySourceConfig),
     /// 🧪 In-memory test source — 4 hardcoded docs, no I/O, no regrets
     InMemory(()),
+    /// 🧩 User-provided source (library embedders only — see `backends::custom`)
+    #[serde(skip)]
+    Custom(CustomSourceConfig),
 }
 
 /// 🗑️ SinkConfig: same vibe as SourceConfig but for the *receiving* end.
@@ -157,6 +308,8 @@ pub enum SourceConfig {
 ///
 /// 🧠 Knowledge graph: resolved at startup into a `SinkBackend` by `lib.rs`. The Drainer
 /// reads `max_request_size_bytes()` to know when to flush its feed buffer. 🚰
+// -- 📏 same call as SourceConfig: built once per run, boxing would buy nothing but noise
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize, Clone)]
 pub enum SinkConfig {
     /// 📡 Write to an Elasticsearch index via bulk API
@@ -167,30 +320,186 @@ pub enum SinkConfig {
     Meilisearch(MeilisearchSinkConfig),
     /// 📡 Write to an OpenObserve stream via ES-compatible bulk API
     OpenObserve(OpenObserveSinkConfig),
+    /// ☀️ Write to a Solr collection via JSON array POSTs to `/update/json/docs`
+    Solr(SolrSinkConfig),
+    /// 🕳️ Discard every payload — the pipeline's speed with the network cut out
+    Null(NullSinkConfig),
     /// 🧪 In-memory test sink — captures payloads for assertion, no I/O
     InMemory(()),
+    /// 🧩 User-provided sink (library embedders only — see `backends::custom`)
+    #[serde(skip)]
+    Custom(CustomSinkConfig),
+}
+
+impl SourceConfig {
+    /// 🎚️ The knobs this source will actually run with — backend defaults + user overrides.
+    ///
+    /// 🧠 Knowledge graph: logged at startup by `lib.rs::run()` so nobody has to guess
+    /// which default got picked. Guessing is for game shows. 🎰
+    pub fn tuning(&self) -> SourceTuning {
+        match self {
+            SourceConfig::Elasticsearch(es) => es.tuning(),
+            SourceConfig::File(f) => f.tuning(),
+            SourceConfig::Url(u) => u.tuning(),
+            SourceConfig::Generator(g) => g.tuning(),
+            SourceConfig::Rest(r) => r.tuning(),
+            SourceConfig::Redis(r) => r.tuning(),
+            SourceConfig::BigQuery(b) => b.tuning(),
+            SourceConfig::InMemory(_) => IN_MEMORY_SOURCE_TUNING,
+            SourceConfig::Custom(c) => c.tuning(),
+        }
+    }
+
+    /// 🔑 Read any `*_file` credentials into their inline fields. Called once by `load_config`.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        match self {
+            SourceConfig::Elasticsearch(es) => es.resolve_secret_files(),
+            SourceConfig::Rest(r) => r.resolve_secret_files(),
+            SourceConfig::Redis(r) => r.resolve_secret_files(),
+            SourceConfig::BigQuery(b) => b.resolve_secret_files(),
+            SourceConfig::File(_)
+            | SourceConfig::Url(_)
+            | SourceConfig::Generator(_)
+            | SourceConfig::InMemory(_)
+            | SourceConfig::Custom(_) => Ok(()),
+        }
+    }
 }
 
 impl SinkConfig {
-    /// 📏 Extract `max_request_size_bytes` from whichever sink config variant we are.
+    /// 🎚️ The knobs this sink will actually run with — backend defaults + user overrides.
     ///
-    /// Each backend sink config embeds a `CommonSinkConfig` with this field.
-    /// InMemory has no config struct, so it gets the `CommonSinkConfig::default()` value.
+    /// Each backend config carries a `DEFAULT_TUNING` const; omitted fields fall back to it.
     /// "He who queries the config, avoids the match in the hot path." — Ancient proverb 📜
+    pub fn tuning(&self) -> SinkTuning {
+        match self {
+            SinkConfig::Elasticsearch(es) => es.tuning(),
+            SinkConfig::File(f) => f.tuning(),
+            // 🔍 Meilisearch sink carries its own common config, same as ES and File
+            SinkConfig::Meilisearch(ms) => ms.tuning(),
+            // 📡 OpenObserve gets its own common config — same pattern as ES 🚰
+            SinkConfig::OpenObserve(oo) => oo.tuning(),
+            SinkConfig::Solr(solr) => solr.tuning(),
+            SinkConfig::Null(n) => n.tuning(),
+            // 🧠 InMemory gets the test profile — it's testing, we don't limit 🦆
+            SinkConfig::InMemory(_) => IN_MEMORY_SINK_TUNING,
+            SinkConfig::Custom(c) => c.tuning(),
+        }
+    }
+
+    /// 🚰 The shared sink knobs, for every sink that has a config to put them in.
+    fn common_config(&self) -> Option<&CommonSinkConfig> {
+        match self {
+            SinkConfig::Elasticsearch(es) => Some(&es.common_config),
+            SinkConfig::File(f) => Some(&f.common_config),
+            SinkConfig::Meilisearch(ms) => Some(&ms.common_config),
+            SinkConfig::OpenObserve(oo) => Some(&oo.common_config),
+            SinkConfig::Solr(solr) => Some(&solr.common_config),
+            SinkConfig::Null(n) => Some(&n.common_config),
+            SinkConfig::InMemory(_) => None,
+            SinkConfig::Custom(c) => Some(&c.common_config),
+        }
+    }
+
+    /// 🎛️ The `manifold = "..."` override, if this sink has a config to put one in.
+    pub fn manifold(&self) -> Option<ManifoldFormat> {
+        self.common_config()?.manifold
+    }
+
+    /// 🗄️ The `sql_insert` table mapping, if the sink config has one.
+    pub fn sql_insert(&self) -> Option<&SqlInsertConfig> {
+        self.common_config()?.sql_insert.as_ref()
+    }
+
+    /// 📊 The `csv` column list, if the sink config has one.
+    pub fn csv(&self) -> Option<&CsvConfig> {
+        self.common_config()?.csv.as_ref()
+    }
+
+    /// 📏 Resolved `max_request_size_bytes` for whichever sink config variant we are.
     ///
-    /// 🧠 Knowledge graph: Drainer uses this to know when to flush its feed buffer.
+    /// 🧠 Knowledge graph: Joiner uses this to know when to flush its feed buffer.
     /// The buffer accumulates raw feeds until their total byte size approaches this limit,
     /// then the Manifold casts+joins them into a single payload for the sink.
     pub fn max_request_size_bytes(&self) -> usize {
+        self.tuning().max_request_size_bytes
+    }
+
+    /// 🔑 Read any `*_file` credentials into their inline fields. Called once by `load_config`.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
         match self {
-            SinkConfig::Elasticsearch(es) => es.common_config.max_request_size_bytes,
-            SinkConfig::File(f) => f.common_config.max_request_size_bytes,
-            // 🔍 Meilisearch sink carries its own common config, same as ES and File
-            SinkConfig::Meilisearch(ms) => ms.common_config.max_request_size_bytes,
-            // 📡 OpenObserve gets its own common config — same pattern as ES 🚰
-            SinkConfig::OpenObserve(oo) => oo.common_config.max_request_size_bytes,
-            // 🧠 InMemory gets the default — it's testing, we don't limit 🦆
-            SinkConfig::InMemory(_) => CommonSinkConfig::default().max_request_size_bytes,
+            SinkConfig::Elasticsearch(es) => es.resolve_secret_files(),
+            SinkConfig::OpenObserve(oo) => oo.resolve_secret_files(),
+            SinkConfig::Solr(solr) => solr.resolve_secret_files(),
+            SinkConfig::File(_)
+            | SinkConfig::Meilisearch(_)
+            | SinkConfig::Null(_)
+            | SinkConfig::InMemory(_)
+            | SinkConfig::Custom(_) => Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_omitted_knobs_fall_back_to_the_backend_profile() {
+        let the_defaults = SinkTuning { max_request_size_bytes: 42 };
+        let the_blank_slate = CommonSinkConfig::default();
+        assert_eq!(the_blank_slate.resolve(the_defaults).max_request_size_bytes, 42);
+    }
+
+    #[test]
+    fn the_one_where_the_user_overrides_one_knob_and_keeps_the_other() {
+        let the_defaults = SourceTuning { max_batch_size_docs: 500, max_batch_size_bytes: 8 };
+        let the_opinionated_user = CommonSourceConfig {
+            max_batch_size_docs: Some(7),
+            max_batch_size_bytes: None,
+            ..Default::default()
+        };
+        let the_resolved = the_opinionated_user.resolve(the_defaults);
+        assert_eq!(the_resolved.max_batch_size_docs, 7);
+        assert_eq!(the_resolved.max_batch_size_bytes, 8);
+    }
+
+    #[test]
+    fn the_one_where_each_sink_backend_brings_its_own_default() -> anyhow::Result<()> {
+        // 🧪 ES and File with no knobs at all — each should land on its own profile
+        let the_es: SinkConfig = toml::from_str(
+            r#"
+            [Elasticsearch]
+            url = "http://localhost:9200"
+            "#,
+        )?;
+        let the_file: SinkConfig = toml::from_str(
+            r#"
+            [File]
+            file_name = "out.ndjson"
+            "#,
+        )?;
+        assert_eq!(the_es.tuning(), ElasticsearchSinkConfig::DEFAULT_TUNING);
+        assert_eq!(the_file.tuning(), FileSinkConfig::DEFAULT_TUNING);
+        assert_ne!(
+            the_es.max_request_size_bytes(),
+            the_file.max_request_size_bytes(),
+            "💀 one global default again? we just escaped that"
+        );
+        Ok(())
+    }
+    #[test]
+    fn the_one_where_the_busy_cluster_gets_more_time() -> anyhow::Result<()> {
+        // 🧪 flattened on the ES sink: the timeouts sit right next to `url`
+        let the_es: ElasticsearchSinkConfig = toml::from_str(
+            r#"
+            url = "http://localhost:9200"
+            request_timeout_secs = 300
+            "#,
+        )?;
+        let the_timeouts = the_es.timeouts();
+        assert_eq!(the_timeouts.request, Duration::from_secs(300));
+        assert_eq!(the_timeouts.connect, ElasticsearchSinkConfig::DEFAULT_TIMEOUTS.connect, "🤝 untouched knob keeps the default");
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧩 Custom backends — bring your own `Source` / `Sink` without forking kravex.
+//!
+//! 🎬 *[a proprietary mainframe. a COBOL copybook. a very determined platform engineer.]*
+//! *["Can kravex read from this?" "No." "Can I teach it?" "...Yes, actually."]*
+//!
+//! Implement the public `Source` or `Sink` trait, wrap a constructor in a factory, and hand
+//! it to `Pipeline::builder().custom_source(..)` / `.custom_sink(..)`. The factory lands in
+//! `SourceConfig::Custom` / `SinkConfig::Custom`, and the usual from_config resolver calls
+//! it — once for the source, once per drainer for the sink.
+//!
+//! 🧠 Knowledge graph:
+//! - Custom sources speak the File source's dialect: each `Page` is NDJSON, one doc per line.
+//!   Casters resolve as if the source were a File (→ ES gets bulk headers, → File passes through)
+//! - Custom sinks receive NDJSON payloads (one doc per line, trailing `\n`), like the File sink
+//! - Neither is loadable from TOML (`#[serde(skip)]`) — a closure doesn't fit in a config file 🦆
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::backends::{CommonSinkConfig, CommonSourceConfig, Sink, SinkTuning, Source, SourceTuning};
+
+/// 🏭 Builds the custom source. Called once per run.
+pub type SourceFactory = Arc<dyn Fn() -> Result<Box<dyn Source + Send>> + Send + Sync>;
+/// 🏭 Builds a custom sink. Called once per drainer (`runtime.sink_parallelism` times).
+pub type SinkFactory = Arc<dyn Fn() -> Result<Box<dyn Sink + Send>> + Send + Sync>;
+
+/// 🧩 A user-provided source: a display name, a factory, and the usual batch knobs.
+#[derive(Clone)]
+pub struct CustomSourceConfig {
+    /// 🏷️ Shows up in logs and the progress bar
+    pub name: String,
+    pub factory: SourceFactory,
+    pub common_config: CommonSourceConfig,
+}
+
+/// 🧩 A user-provided sink: a display name, a factory, and the usual request-size knob.
+#[derive(Clone)]
+pub struct CustomSinkConfig {
+    /// 🏷️ Shows up in logs
+    pub name: String,
+    pub factory: SinkFactory,
+    pub common_config: CommonSinkConfig,
+}
+
+impl CustomSourceConfig {
+    /// 🎚️ Same profile as the ES source — a sensible middle for "some system we've never met".
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 1000,
+        max_batch_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+}
+
+impl CustomSinkConfig {
+    /// 🎚️ 10MB — the `_bulk` sweet spot, and a polite default for unknown destinations.
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+}
+
+// -- 🔍 closures don't Debug, so we print the name and move on
+impl fmt::Debug for CustomSourceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomSourceConfig")
+            .field("name", &self.name)
+            .field("common_config", &self.common_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for CustomSinkConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomSinkConfig")
+            .field("name", &self.name)
+            .field("common_config", &self.common_config)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔵🟢 Blue/green loads — fill a fresh staging index, then flip the alias onto it in one move.
+//!
+//! 🎬 *[a full reload into the live index. hour two of five. the search page shows half a catalog.]*
+//! *[a customer searches for "socks". there are no socks. there were socks this morning.]*
+//! *[the reload should have happened somewhere nobody was looking.]*
+//!
+//! With `blue_green = true`, the sink `index` names an alias. `run()` writes into
+//! `{alias}-{yyyymmddhhmmss}` instead, and only when the run succeeds does one `_aliases`
+//! call move the alias off the old indices and onto the new one. Readers go from the old
+//! data to the new data with nothing in between.
+//!
+//! 🧠 Knowledge graph:
+//! - `BlueGreen::plan` (in `lib.rs::prepare_target`) names the staging index and refuses an
+//!   `index` that is a concrete index — an alias can't share its name. The sink config's `index`
+//!   is then pointed at the staging index, and the usual create step builds it
+//!   (`copy_index` blueprint, or inline `settings`/`mappings`)
+//! - Success → optional `blue_green_verify_count` (refresh + `_count` against the source's
+//!   count, less the docs transforms dropped and the sink rejected), then `promote`: `remove`
+//!   the alias from every index it's on, `add` it to staging, in one atomic `POST /_aliases`. The old indices stay — they're the rollback
+//! - Failure, shutdown, or a failed count → `discard`: `DELETE` the staging index. The alias
+//!   never moved, so readers never noticed 🦆
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use tracing::info;
+
+use super::config::ElasticsearchSinkConfig;
+use super::index_setup::authed;
+
+/// 🔵🟢 One blue/green run: the alias readers use, and the index this run fills.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlueGreen {
+    pub alias: String,
+    pub staging_index: String,
+}
+
+impl BlueGreen {
+    /// 🏷️ `{alias}-{yyyymmddhhmmss}` (UTC) — sorts by age, and never collides across runs.
+    pub fn staging_name(alias: &str, at: DateTime<Utc>) -> String {
+        format!("{}-{}", alias, at.format("%Y%m%d%H%M%S"))
+    }
+
+    /// 📋 Check the sink config can do blue/green, and that `index` is an alias (or nothing yet).
+    pub async fn plan(sink: &ElasticsearchSinkConfig, at: DateTime<Utc>) -> Result<Self> {
+        let Some(the_alias) = sink.static_index() else {
+            anyhow::bail!("💀 blue_green needs `index` set to the alias readers use — not a template, not unset.");
+        };
+        if sink.data_stream {
+            anyhow::bail!("💀 blue_green and data_stream don't mix — a data stream rolls over on its own.");
+        }
+        let the_response = authed(
+            client(sink)?.get(format!("{}/{}", sink.url.trim_end_matches('/'), the_alias)),
+            &sink.api_key,
+            &sink.username,
+            &sink.password,
+        )
+        .send()
+        .await
+        .context("💀 Couldn't reach the target cluster to look up the blue/green alias")?;
+        if the_response.status().is_success() {
+            // -- 📦 keyed by concrete index — our name among the keys means it's an index, not an alias
+            let the_answer: serde_json::Value = serde_json::from_str(&the_response.text().await.unwrap_or_default())
+                .unwrap_or_default();
+            if the_answer.get(the_alias).is_some() {
+                anyhow::bail!(
+                    "💀 blue_green swaps an alias, and '{}' is a concrete index. Reindex it behind an alias \
+                     first, or pick a new alias name.",
+                    the_alias
+                );
+            }
+        }
+        Ok(Self { alias: the_alias.to_string(), staging_index: Self::staging_name(the_alias, at) })
+    }
+
+    /// 🔢 Refresh the staging index and count what landed in it.
+    pub async fn count(&self, sink: &ElasticsearchSinkConfig) -> Result<u64> {
+        let the_client = client(sink)?;
+        let the_base = format!("{}/{}", sink.url.trim_end_matches('/'), self.staging_index);
+        send(authed(the_client.post(format!("{}/_refresh", the_base)), &sink.api_key, &sink.username, &sink.password), "refresh")
+            .await?;
+        let the_body = send(
+            authed(the_client.get(format!("{}/_count", the_base)), &sink.api_key, &sink.username, &sink.password),
+            "_count",
+        )
+        .await?;
+        let the_answer: serde_json::Value = serde_json::from_str(&the_body).context("💀 The _count answer wasn't JSON")?;
+        the_answer["count"].as_u64().context("💀 The _count answer had no `count`")
+    }
+
+    /// 🔀 Move the alias onto the staging index in one atomic `_aliases` call. Returns the
+    /// indices it came off of — still there, for a rollback.
+    pub async fn promote(&self, sink: &ElasticsearchSinkConfig) -> Result<Vec<String>> {
+        let the_client = client(sink)?;
+        let the_url = sink.url.trim_end_matches('/');
+        let the_lookup = authed(
+            the_client.get(format!("{}/_alias/{}", the_url, self.alias)),
+            &sink.api_key,
+            &sink.username,
+            &sink.password,
+        )
+        .send()
+        .await
+        .context("💀 Couldn't look up which indices the alias is on")?;
+        let the_old_indices: Vec<String> = match the_lookup.status() {
+            // -- 🌱 first blue/green run: the alias doesn't exist yet
+            StatusCode::NOT_FOUND => Vec::new(),
+            the_status if the_status.is_success() => {
+                let the_answer: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(&the_lookup.text().await.unwrap_or_default())
+                        .context("💀 The _alias answer wasn't an object")?;
+                the_answer
+                    .into_iter()
+                    .map(|(the_index, _)| the_index)
+                    .filter(|the_index| *the_index != self.staging_index)
+                    .collect()
+            }
+            the_status => {
+                let the_body = the_lookup.text().await.unwrap_or_default();
+                anyhow::bail!("💀 Looking up alias '{}' failed with {}: '{}'", self.alias, the_status, the_body);
+            }
+        };
+        let mut the_actions: Vec<serde_json::Value> = the_old_indices
+            .iter()
+            .map(|the_index| serde_json::json!({ "remove": { "index": the_index, "alias": self.alias } }))
+            .collect();
+        the_actions.push(serde_json::json!({ "add": { "index": self.staging_index, "alias": self.alias } }));
+        send(
+            authed(the_client.post(format!("{}/_aliases", the_url)), &sink.api_key, &sink.username, &sink.password)
+                .header("Content-Type", "application/json")
+                .body(serde_json::json!({ "actions": the_actions }).to_string()),
+            "_aliases",
+        )
+        .await?;
+        info!(
+            "🔀 Alias '{}' now points at '{}' (was: [{}])",
+            self.alias,
+            self.staging_index,
+            the_old_indices.join(", ")
+        );
+        Ok(the_old_indices)
+    }
+
+    /// 🗑️ Drop the staging index — the run failed, and the alias never moved.
+    pub async fn discard(&self, sink: &ElasticsearchSinkConfig) -> Result<()> {
+        send(
+            authed(
+                client(sink)?.delete(format!("{}/{}", sink.url.trim_end_matches('/'), self.staging_index)),
+                &sink.api_key,
+                &sink.username,
+                &sink.password,
+            ),
+            "delete",
+        )
+        .await?;
+        info!("🗑️ Deleted staging index '{}' — alias '{}' is untouched", self.staging_index, self.alias);
+        Ok(())
+    }
+}
+
+fn client(sink: &ElasticsearchSinkConfig) -> Result<reqwest::Client> {
+    Ok(sink.timeouts().apply(sink.connection.client_builder()?).build()?)
+}
+
+/// 📡 Send, and turn anything but a 2xx into an error naming `what` we were doing.
+async fn send(request: reqwest::RequestBuilder, what: &str) -> Result<String> {
+    let the_response = request
+        .send()
+        .await
+        .with_context(|| format!("💀 The blue/green {} request never reached the target cluster", what))?;
+    let the_status = the_response.status();
+    let the_body = the_response.text().await.unwrap_or_default();
+    if !the_status.is_success() {
+        anyhow::bail!("💀 The blue/green {} request failed with {}: '{}'", what, the_status, the_body);
+    }
+    Ok(the_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn a_sink(url: &str) -> Result<ElasticsearchSinkConfig> {
+        Ok(toml::from_str(&format!("url = \"{}\"\nindex = \"catalog\"\nblue_green = true", url))?)
+    }
+
+    fn noon() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-14T12:30:05Z").map(|t| t.with_timezone(&Utc)).unwrap_or_default()
+    }
+
+    #[test]
+    fn the_one_where_the_staging_index_is_named_after_the_clock() {
+        assert_eq!(BlueGreen::staging_name("catalog", noon()), "catalog-20260314123005");
+    }
+
+    /// 🧪 The alias comes off yesterday's index and onto today's in one `_aliases` call.
+    #[tokio::test]
+    async fn the_one_where_the_alias_changes_houses() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/catalog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "catalog-20260313000000": { "aliases": { "catalog": {} } }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_alias/catalog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "catalog-20260313000000": { "aliases": { "catalog": {} } }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_aliases"))
+            .and(body_json(serde_json::json!({ "actions": [
+                { "remove": { "index": "catalog-20260313000000", "alias": "catalog" } },
+                { "add": { "index": "catalog-20260314123005", "alias": "catalog" } }
+            ] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "acknowledged": true })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_sink = a_sink(&mock_server.uri())?;
+        let the_plan = BlueGreen::plan(&the_sink, noon()).await?;
+        assert_eq!(the_plan.staging_index, "catalog-20260314123005");
+        assert_eq!(the_plan.promote(&the_sink).await?, vec!["catalog-20260313000000".to_string()]);
+        Ok(())
+    }
+
+    /// 🧪 An index squatting on the alias name can't be swapped; a failed run deletes staging.
+    #[tokio::test]
+    async fn the_one_where_the_name_is_already_taken_and_the_run_is_binned() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/catalog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "catalog": { "aliases": {} } })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/catalog-20260314123005"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_sink = a_sink(&mock_server.uri())?;
+        assert!(BlueGreen::plan(&the_sink, noon()).await.is_err(), "💀 'catalog' is an index, not an alias");
+        let the_plan = BlueGreen { alias: "catalog".to_string(), staging_index: "catalog-20260314123005".to_string() };
+        the_plan.discard(&the_sink).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧾 Bulk responses — a 200 from `_bulk` is a receipt, not a guarantee.
+//!
+//! 🎬 *[the migration finishes. every request got a 200. champagne is opened.]*
+//! *[a week later: "why does the target have 4% fewer docs?"]*
+//! *[deep inside each 200: `"errors": true`. it was there the whole time. nobody read it.]*
+//!
+//! Elasticsearch answers `_bulk` with one item per action. A mapping conflict or a
+//! version clash fails *that doc only* and the request as a whole still returns 200.
+//! This module finds those items and lines them back up with the payload they came from.
+//!
+//! 🧠 Knowledge graph:
+//! - `parse_bulk_failures` — fast path when `errors` is false (the overwhelmingly common case)
+//! - Item N ↔ bulk unit N of the payload (`sink::bulk_unit_starts`), so rejects can be
+//!   written out with their original action + source lines (`dead_letter_records`)
+//! - What happens next is `on_document_failure`: `warn` (count, log, keep going) or `fail`
+//!   (typed `DocumentsRejected`, which the Drainer does not retry) 🦆
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::backends::sink::bulk_unit_starts;
+
+/// 🚦 What to do when `_bulk` refuses individual documents.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFailurePolicy {
+    /// ⚠️ Count them, log them, list them in the end-of-run warnings, keep migrating
+    #[default]
+    Warn,
+    /// 💀 Fail the run on the first payload with a rejected doc
+    Fail,
+}
+
+/// 🙅 One document `_bulk` refused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkItemFailure {
+    /// 📍 Position of the item in the response == position of the unit in the payload
+    pub position: usize,
+    /// 🏷️ "index", "create", "update", "delete"
+    pub op: String,
+    pub index: Option<String>,
+    pub id: Option<String>,
+    pub status: u16,
+    /// 📜 The raw `error` object — `type`, `reason`, sometimes a `caused_by` nesting doll
+    pub error: serde_json::Value,
+}
+
+impl BulkItemFailure {
+    /// 📝 "mapper_parsing_exception: failed to parse field [age]"
+    pub fn reason(&self) -> String {
+        let the_type = self.error.get("type").and_then(|t| t.as_str()).unwrap_or("unknown_error");
+        match self.error.get("reason").and_then(|r| r.as_str()) {
+            Some(reason) => format!("{}: {}", the_type, reason),
+            None => the_type.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BulkVerdict {
+    #[serde(default)]
+    errors: bool,
+}
+
+#[derive(Deserialize)]
+struct BulkResponse {
+    #[serde(default)]
+    items: Vec<HashMap<String, BulkItem>>,
+}
+
+#[derive(Deserialize)]
+struct BulkItem {
+    #[serde(rename = "_index")]
+    index: Option<String>,
+    #[serde(rename = "_id")]
+    id: Option<String>,
+    status: u16,
+    error: Option<serde_json::Value>,
+}
+
+/// 🔎 Pull the refused items out of a `_bulk` response body. Empty when everything landed.
+///
+/// Only items carrying an `error` count — a `delete` of a missing doc is a 404 without
+/// one, and that's "already gone", not a failure.
+pub fn parse_bulk_failures(body: &str) -> Result<Vec<BulkItemFailure>> {
+    // -- 🏎️ `errors: false` is the happy path; don't materialize 10k items to learn nothing
+    let the_verdict: BulkVerdict =
+        serde_json::from_str(body).context("💀 The _bulk response wasn't JSON — is something other than Elasticsearch answering?")?;
+    if !the_verdict.errors {
+        return Ok(Vec::new());
+    }
+
+    let the_response: BulkResponse =
+        serde_json::from_str(body).context("💀 The _bulk response said errors=true but its items didn't parse")?;
+    Ok(the_response
+        .items
+        .into_iter()
+        .enumerate()
+        .filter_map(|(position, item)| {
+            let (op, item) = item.into_iter().next()?;
+            let error = item.error?;
+            Some(BulkItemFailure { position, op, index: item.index, id: item.id, status: item.status, error })
+        })
+        .collect())
+}
+
+/// 🪦 One NDJSON line per reject: `{"status":..,"error":{..},"action":{..},"document":{..}}`.
+///
+/// `action` and `document` are the original payload lines, embedded verbatim (they're JSON
+/// already). `document` is `null` for deletes. Feed the `action` + `document` pairs back
+/// through `_bulk` once the mapping is fixed. 🔁
+pub fn dead_letter_records(payload: &str, failures: &[BulkItemFailure]) -> String {
+    let the_unit_starts = bulk_unit_starts(payload);
+    let mut the_records = String::new();
+    for failure in failures {
+        let Some(&the_start) = the_unit_starts.get(failure.position) else {
+            continue;
+        };
+        let the_end = the_unit_starts.get(failure.position + 1).copied().unwrap_or(payload.len());
+        let mut the_lines = payload[the_start..the_end].lines().map(str::trim).filter(|l| !l.is_empty());
+        let the_action = the_lines.next().unwrap_or("null");
+        let the_document = the_lines.next().unwrap_or("null");
+        the_records.push_str(&format!(
+            "{{\"status\":{},\"error\":{},\"action\":{},\"document\":{}}}\n",
+            failure.status, failure.error, the_action, the_document
+        ));
+    }
+    the_records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THE_MIXED_VERDICT: &str = r#"{"took":3,"errors":true,"items":[
+        {"index":{"_index":"people","_id":"1","status":201,"result":"created"}},
+        {"index":{"_index":"people","_id":"2","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse field [age]"}}},
+        {"delete":{"_index":"people","_id":"3","status":404,"result":"not_found"}}
+    ]}"#;
+
+    #[test]
+    fn the_one_where_the_200_was_hiding_a_400() -> Result<()> {
+        let the_failures = parse_bulk_failures(THE_MIXED_VERDICT)?;
+        assert_eq!(the_failures.len(), 1, "🎯 the 404 delete is 'already gone', not a failure");
+        assert_eq!(the_failures[0].position, 1);
+        assert_eq!(the_failures[0].op, "index");
+        assert_eq!(the_failures[0].id.as_deref(), Some("2"));
+        assert_eq!(the_failures[0].reason(), "mapper_parsing_exception: failed to parse field [age]");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_errors_false_means_nothing_to_see() -> Result<()> {
+        assert!(parse_bulk_failures(r#"{"took":1,"errors":false,"items":[{"index":{"status":201}}]}"#)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_reject_keeps_its_original_lines() -> Result<()> {
+        let the_payload = "{\"index\":{\"_id\":\"1\"}}\n{\"age\":1}\n{\"index\":{\"_id\":\"2\"}}\n{\"age\":\"old\"}\n{\"delete\":{\"_id\":\"3\"}}\n";
+        let the_records = dead_letter_records(the_payload, &parse_bulk_failures(THE_MIXED_VERDICT)?);
+        let the_record: serde_json::Value = serde_json::from_str(the_records.trim())?;
+        assert_eq!(the_record["status"], 400);
+        assert_eq!(the_record["action"], serde_json::json!({"index":{"_id":"2"}}));
+        assert_eq!(the_record["document"], serde_json::json!({"age":"old"}));
+        assert_eq!(the_record["error"]["type"], "mapper_parsing_exception");
+        Ok(())
+    }
+}
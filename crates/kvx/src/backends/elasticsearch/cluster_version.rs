@@ -0,0 +1,125 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🏷️ Cluster version — what `GET /` says the cluster is, and what that means for `_bulk`.
+//!
+//! 🎬 *[the migration targets "Elasticsearch". the cluster is 6.8. the action lines have no `_type`.]*
+//! *["Rejecting mapping update to [logs] as the final mapping would have more than 1 type"]*
+//! *[nobody asked the cluster how old it was. now we do.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Read once by `ElasticsearchSink::new` from the root ping it already makes
+//! - `version.number` + `version.distribution` (OpenSearch sets it; Elasticsearch doesn't)
+//! - 6.x: `_bulk` wants a mapping type → the sink posts to `/{index}/_doc/_bulk`
+//! - 8.x+: the sink sends the `compatible-with` media types the official clients send
+//! - `require_alias` exists from 7.10 (and in every OpenSearch, which forked 7.10.2)
+//! - Unreadable or missing → `None`, and the sink assumes a modern cluster, as it always did 🦆
+
+use std::fmt;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// 🏢 Who built the cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    Elasticsearch,
+    OpenSearch,
+}
+
+/// 🏷️ `8.11.1`, and whose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClusterVersion {
+    pub distribution: Distribution,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ClusterVersion {
+    /// 🔍 Parse the root endpoint's body: `{"version":{"number":"8.11.1", ...}}`.
+    /// Pre-release suffixes (`8.0.0-SNAPSHOT`, `7.0.0-rc1`) are ignored.
+    pub fn from_root_response(body: &str) -> Result<Self> {
+        let the_root: Value = serde_json::from_str(body).context("💀 the root endpoint didn't answer in JSON")?;
+        let the_version = &the_root["version"];
+        let the_number = the_version["number"]
+            .as_str()
+            .context("💀 the root endpoint has no `version.number`")?;
+        let the_parts: Vec<u32> = the_number
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("💀 '{}' isn't a version number", the_number))?;
+        let the_part = |i: usize| the_parts.get(i).copied().unwrap_or(0);
+        let (major, minor, patch) = (the_part(0), the_part(1), the_part(2));
+        let distribution = match the_version["distribution"].as_str() {
+            Some(the_name) if the_name.eq_ignore_ascii_case("opensearch") => Distribution::OpenSearch,
+            _ => Distribution::Elasticsearch,
+        };
+        Ok(Self { distribution, major, minor, patch })
+    }
+
+    /// 🗂️ Elasticsearch before 7 wants a mapping type on every `_bulk` document.
+    pub fn needs_mapping_type(&self) -> bool {
+        self.distribution == Distribution::Elasticsearch && self.major < 7
+    }
+
+    /// 🔗 `?require_alias=true` on `_bulk` — Elasticsearch 7.10+, and every OpenSearch.
+    pub fn supports_require_alias(&self) -> bool {
+        match self.distribution {
+            Distribution::Elasticsearch => (self.major, self.minor) >= (7, 10),
+            Distribution::OpenSearch => true,
+        }
+    }
+
+    /// 📨 The `compatible-with=N` media type version — Elasticsearch 8+ only. OpenSearch
+    /// doesn't know the vendor media types and rejects them.
+    pub fn compatible_with(&self) -> Option<u32> {
+        (self.distribution == Distribution::Elasticsearch && self.major >= 8).then_some(self.major)
+    }
+}
+
+impl fmt::Display for ClusterVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}.{}.{}", self.distribution, self.major, self.minor, self.patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_every_cluster_says_its_age() -> Result<()> {
+        let the_modern = ClusterVersion::from_root_response(r#"{"name":"n1","version":{"number":"8.11.1","build_flavor":"default"}}"#)?;
+        assert_eq!(the_modern.to_string(), "Elasticsearch 8.11.1");
+        assert_eq!(the_modern.compatible_with(), Some(8));
+        assert!(!the_modern.needs_mapping_type());
+
+        let the_archive = ClusterVersion::from_root_response(r#"{"version":{"number":"6.8.23"}}"#)?;
+        assert!(the_archive.needs_mapping_type());
+        assert!(!the_archive.supports_require_alias());
+        assert_eq!(the_archive.compatible_with(), None);
+
+        let the_fork = ClusterVersion::from_root_response(r#"{"version":{"distribution":"opensearch","number":"2.11.0"}}"#)?;
+        assert_eq!(the_fork.distribution, Distribution::OpenSearch);
+        assert!(the_fork.supports_require_alias(), "🍴 forked after 7.10");
+        assert!(!the_fork.needs_mapping_type());
+        assert_eq!(the_fork.compatible_with(), None, "🍴 no vendor media types");
+
+        let the_snapshot = ClusterVersion::from_root_response(r#"{"version":{"number":"7.10.0-SNAPSHOT"}}"#)?;
+        assert!(the_snapshot.supports_require_alias());
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_root_endpoint_is_not_a_cluster() {
+        assert!(ClusterVersion::from_root_response("").is_err());
+        assert!(ClusterVersion::from_root_response(r#"{"tagline":"You Know, for Search"}"#).is_err());
+        assert!(ClusterVersion::from_root_response(r#"{"version":{"number":"eight"}}"#).is_err());
+    }
+}
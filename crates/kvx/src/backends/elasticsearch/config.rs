@@ -11,13 +11,83 @@
 //!
 //! ⚠️ The singularity will auto-configure itself. We still need TOML.
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
 use serde::Deserialize;
-use crate::backends::{CommonSourceConfig, CommonSinkConfig};
+use crate::backends::config::resolve_secret_file;
+use super::bulk_response::DocumentFailurePolicy;
+use super::connection::ConnectionConfig;
+use crate::casts::bulk_action::{BulkAction, TombstoneRule};
+use crate::casts::doc_id::DocIdConfig;
+use crate::casts::index_template::{IndexMap, IndexTemplate};
+use super::duplicate_check::DuplicateCheckConfig;
+use super::index_setup::CopyIndexConfig;
+use super::resume::ResumeAfterConfig;
+use super::time_slice::TimeSliceConfig;
+use crate::backends::{CommonSinkConfig, CommonSourceConfig, HttpTimeouts, SinkTuning, SourceTuning};
 
 // ============================================================
 // 📡 ElasticsearchSourceConfig
 // ============================================================
 
+/// 📜 Source pagination strategy.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Pagination {
+    /// 📸 Point-in-time + search_after: a consistent snapshot that survives merges and refreshes
+    #[default]
+    Pit,
+    /// 📜 The classic scroll API — for clusters that predate PIT
+    Scroll,
+}
+
+/// 🦖 Which generation of the Elasticsearch wire protocol to speak.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Compat {
+    /// 🏎️ 7.x and up (and OpenSearch): typeless `_bulk`, PIT available
+    #[default]
+    Modern,
+    /// 🦖 6.x archives: `_type` on every bulk action line, scroll on the read side
+    Es6,
+}
+
+/// 🗂️ The mapping type written when the doc doesn't bring its own — the one 7.x standardised on
+pub const DEFAULT_MAPPING_TYPE: &str = "_doc";
+
+fn default_keep_alive() -> String {
+    "5m".to_string()
+}
+
+/// 🔄 `?refresh=` on every `_bulk` request — when the written docs become searchable.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RefreshPolicy {
+    /// 🏎️ Leave it to `refresh_interval` — the only sane choice for big migrations
+    #[default]
+    #[serde(rename = "false")]
+    False,
+    /// ⏳ Each request returns once its docs are searchable — for tests that query right after
+    #[serde(rename = "wait_for")]
+    WaitFor,
+    /// 💸 Force a refresh after every request. Small segments galore; tests only
+    #[serde(rename = "true")]
+    True,
+}
+
+impl RefreshPolicy {
+    /// 🔗 The query value, or `None` for the default (don't send the param at all)
+    pub fn as_param(&self) -> Option<&'static str> {
+        match self {
+            Self::False => None,
+            Self::WaitFor => Some("wait_for"),
+            Self::True => Some("true"),
+        }
+    }
+}
+
 // Moved here from supervisors/config.rs because configs should live near the thing they configure.
 //
 // 🔧 auth is tri-modal: username+password, api_key, or "I hope anonymous works" (it won't).
@@ -34,15 +104,162 @@ pub struct ElasticsearchSourceConfig {
     /// with the Department of Security Choices.
     #[serde(default)]
     pub password: Option<String>,
+    /// 🔑 Path to a file holding the password (e.g. a Kubernetes secret mount).
+    /// Read once at startup into `password`. Mutually exclusive with `password`.
+    #[serde(default)]
+    pub password_file: Option<PathBuf>,
     /// 🔒 API key auth — the fancy way. Preferred over basic auth. Like using a card instead of
     /// cash. Or a key fob instead of a key. Or a retinal scanner instead of a key fob.
     /// Point is: hierarchy. This field respects hierarchy.
     #[serde(default)]
     pub api_key: Option<String>,
+    /// 🔑 Path to a file holding the API key. Read once at startup into `api_key`.
+    #[serde(default)]
+    pub api_key_file: Option<PathBuf>,
+    /// 🔐 TLS: `ca_cert_path`, `client_cert_path`, `client_key_path` — flat, next to `url`.
+    #[serde(flatten, default)]
+    pub connection: ConnectionConfig,
+    /// 📦 The index (or alias, or comma list) to read. `None` searches every index — bold.
+    /// A TOML list (`["orders", "logs-*"]`) is joined into a comma list. A pattern or a list
+    /// is resolved to its indices and read one index at a time, each hit keeping its `_index`.
+    #[serde(default, deserialize_with = "one_or_many_indices")]
+    pub index: Option<String>,
+    /// 📜 How to page through the index: `pit` (default, PIT + search_after) or `scroll`
+    /// (for clusters older than 7.12, which lack PIT or `_shard_doc`).
+    #[serde(default)]
+    pub pagination: Pagination,
+    /// 🦖 `"es6"` reads a 6.x cluster: always scroll, whatever `pagination` says (6.x has no PIT).
+    #[serde(default)]
+    pub compat: Compat,
+    /// ⏳ How long the PIT / scroll context survives between page requests. Renewed on every
+    /// page, so this only has to outlast one page round-trip plus sink backpressure.
+    #[serde(default = "default_keep_alive")]
+    pub keep_alive: String,
+    /// 🍕 Split the read into N disjoint `slice`s, each pumped by its own source worker.
+    /// `None` or 1 = a single reader. Around the source index's shard count is a good start.
+    #[serde(default)]
+    pub slices: Option<usize>,
+    /// 🔎 Only migrate the docs matching this query DSL clause — a TOML table, or a string of
+    /// raw JSON pasted from Dev Tools (`'{"term": {"status": "active"}}'`). `None` reads everything.
+    #[serde(default)]
+    pub query: Option<serde_json::Value>,
+    /// ✂️ `_source` fields to keep (wildcards welcome, e.g. `"user.*"`). Empty keeps them all.
+    /// Filtered on the source cluster, so what's left out never crosses the wire.
+    #[serde(default)]
+    pub source_includes: Vec<String>,
+    /// ✂️ `_source` fields to drop — the 40MB `raw_html` blob nobody searches. Applied after
+    /// `source_includes`, so an exclude wins over an include.
+    #[serde(default)]
+    pub source_excludes: Vec<String>,
     /// 📦 Common source settings — the bureaucratic paperwork of data migration.
     /// Max batch size, timeouts, etc. Not glamorous. Essential. Like the appendix.
     #[serde(default)]
     pub common_config: CommonSourceConfig,
+    /// 🗓️ Optional date-range slicing — read `[start, end)` over `field` one window at a time.
+    /// Each window is a unit of progress, retry (`window_retries`) and resume (`checkpoint_file`).
+    /// Huge time-series indices stop being one terrifying query and become a calendar. 📅
+    #[serde(default)]
+    pub time_slice: Option<TimeSliceConfig>,
+    /// ⏭️ Optional incremental read — only docs whose `field` moved past the mark the last
+    /// successful run left in `state_file`. For nightly delta syncs. 🌙
+    #[serde(default)]
+    pub resume_after: Option<ResumeAfterConfig>,
+}
+
+/// 📦 `index = "a"` or `index = ["a", "b-*"]` — both land as the comma list ES already speaks.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn one_or_many_indices<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        None => None,
+        Some(OneOrMany::One(the_index)) => Some(the_index),
+        Some(OneOrMany::Many(the_indices)) => Some(the_indices.join(",")),
+    })
+}
+
+impl ElasticsearchSourceConfig {
+    /// 🎚️ ES source profile: 1000 hits per search page, 10MB byte ceiling.
+    /// Bigger pages make the coordinating node sweat; smaller ones make us sweat. 🦆
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 1000,
+        max_batch_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// 📜 The pagination actually used — `compat = "es6"` forces scroll.
+    pub fn effective_pagination(&self) -> Pagination {
+        match self.compat {
+            Compat::Modern => self.pagination,
+            Compat::Es6 => Pagination::Scroll,
+        }
+    }
+
+    /// 🔎 The configured `query` as a query clause, or `None` for match-all. A string is parsed as
+    /// JSON, and a whole search body (`{"query": {..}}`) is unwrapped to the clause inside.
+    pub fn query_clause(&self) -> Result<Option<serde_json::Value>> {
+        let the_query = match &self.query {
+            None => return Ok(None),
+            Some(serde_json::Value::String(the_raw)) => serde_json::from_str(the_raw)
+                .context("💀 The source `query` is a string, but not a JSON query we can parse")?,
+            Some(the_query) => the_query.clone(),
+        };
+        let the_query = match the_query {
+            serde_json::Value::Object(mut the_body) if the_body.len() == 1 && the_body.contains_key("query") => {
+                the_body.remove("query").unwrap_or_default()
+            }
+            the_query => the_query,
+        };
+        match &the_query {
+            serde_json::Value::Object(the_clause) if !the_clause.is_empty() => Ok(Some(the_query)),
+            _ => anyhow::bail!(
+                "💀 The source `query` must be one query clause, like {{\"term\": {{\"status\": \"active\"}}}}. Got: {}",
+                the_query
+            ),
+        }
+    }
+
+    /// 🗂️ Does `index` name several indices — a pattern or a comma list — that get read one by one?
+    pub fn reads_many_indices(&self) -> bool {
+        self.index.as_deref().is_some_and(|the_index| the_index.contains(['*', ',']))
+    }
+
+    /// ✂️ The `_source` clause for every search, or `None` when nothing is filtered (ES sends it all).
+    pub fn source_filter(&self) -> Option<serde_json::Value> {
+        if self.source_includes.is_empty() && self.source_excludes.is_empty() {
+            return None;
+        }
+        Some(serde_json::json!({ "includes": self.source_includes, "excludes": self.source_excludes }))
+    }
+
+    /// ⏱️ 10s to connect, 60s per search page — deep `search_after` pages on a cold
+    /// index can take a while to assemble.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(60),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `password_file` / `api_key_file` for the secrets they point at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.password, self.password_file.as_deref(), "password")?;
+        resolve_secret_file(&mut self.api_key, self.api_key_file.as_deref(), "api_key")
+    }
 }
 
 // ============================================================
@@ -62,14 +279,186 @@ pub struct ElasticsearchSinkConfig {
     /// 🔒 Password. "password123" is not a password. It is a confession.
     #[serde(default)]
     pub password: Option<String>,
+    /// 🔑 Path to a file holding the password — for secret mounts instead of plaintext TOML.
+    #[serde(default)]
+    pub password_file: Option<PathBuf>,
     /// 🔒 API key — the velvet rope variant of authentication.
     #[serde(default)]
     pub api_key: Option<String>,
+    /// 🔑 Path to a file holding the API key. Same deal as `password_file`.
+    #[serde(default)]
+    pub api_key_file: Option<PathBuf>,
+    /// 🔐 TLS: internal CA bundle and mTLS client cert/key. See `connection.rs`.
+    #[serde(flatten, default)]
+    pub connection: ConnectionConfig,
     /// 📦 The default target index. Optional because each document can carry its own `_index`.
     /// If both are None, `transform_into_bulk` will bail with an existential error message.
     /// You've been warned. The existential error message is very existential.
+    /// With `{field.path}` placeholders it's a per-document template: `"{_type}-{Project.Name}"`.
+    /// `%{+yyyy.MM.dd}` is Logstash-style date math on the doc's timestamp: `"logs-%{+yyyy.MM.dd}"`.
     pub index: Option<String>,
+    /// 📅 The timestamp field `%{+...}` in `index` formats. Default: `@timestamp`.
+    #[serde(default)]
+    pub index_date_field: Option<String>,
     /// 🔧 Common sink config: max batch size in bytes, and other life decisions.
     #[serde(flatten, default)]
     pub common_config: CommonSinkConfig,
+    /// 👯 Optional post-migration check: aggregate `index` on a logical key field and report
+    /// keys that exist more than once. Runs once, after the pipeline succeeds.
+    #[serde(default)]
+    pub duplicate_check: Option<DuplicateCheckConfig>,
+    /// 🚦 `_bulk` can answer 200 and still refuse individual docs. `warn` (default) counts and
+    /// logs them; `fail` stops the run at the first payload with a rejected doc.
+    #[serde(default)]
+    pub on_document_failure: DocumentFailurePolicy,
+    /// 🪦 Append every rejected doc (action line, source, error) here as NDJSON, whatever the policy.
+    #[serde(default)]
+    pub dead_letter_file: Option<PathBuf>,
+    /// 📐 ES→ES only: create `index` from the source index's mapping and settings before any
+    /// docs flow. Shard/replica counts can be overridden. An existing target is left alone.
+    #[serde(default)]
+    pub copy_index: Option<CopyIndexConfig>,
+    /// 🏗️ Create `index` at startup when it doesn't exist, instead of bailing. Uses the inline
+    /// `mappings` / `settings` below if given, else lets ES defaults (and dynamic mapping) decide.
+    #[serde(default)]
+    pub create_index_if_missing: bool,
+    /// 🗺️ Mappings for `create_index_if_missing`, e.g. `{ properties = { price = { type = "float" } } }`
+    #[serde(default)]
+    pub mappings: Option<serde_json::Value>,
+    /// ⚙️ Index settings for `create_index_if_missing`, e.g. `{ number_of_shards = 1 }`
+    #[serde(default)]
+    pub settings: Option<serde_json::Value>,
+    /// 🌊 `index` names a data stream: every doc goes in as `create` (the only op a data
+    /// stream takes), must carry `@timestamp`, and the startup existence check is skipped —
+    /// an index template may create the stream on the first write.
+    #[serde(default)]
+    pub data_stream: bool,
+    /// 🏷️ The `_bulk` operation: `index` (default, overwrite), `create` (skip existing ids
+    /// as per-doc conflicts), `update` (patch existing docs), `upsert` (patch or create).
+    /// `update`/`upsert` need `_id`s, so they need an Elasticsearch source.
+    #[serde(default)]
+    pub op_type: BulkAction,
+    /// 🪦 Turn matching source docs into deletes: `{ field = "deleted", equals = true, id_field = "sku" }`.
+    /// For sync jobs that must also remove what the source removed.
+    #[serde(default)]
+    pub tombstone: Option<TombstoneRule>,
+    /// 🧪 Ingest pipeline to run every bulk doc through (`?pipeline=`) — server-side
+    /// enrichment without a client-side transform. The pipeline must already exist.
+    #[serde(default)]
+    pub pipeline: Option<String>,
+    /// 🔄 `"false"` (default) | `"wait_for"` | `"true"` — `wait_for` makes test runs
+    /// searchable on return; keep the default for anything big.
+    #[serde(default)]
+    pub refresh: RefreshPolicy,
+    /// 🦖 `"es6"` writes to a 6.x cluster: every action line carries a `_type`.
+    #[serde(default)]
+    pub compat: Compat,
+    /// 🗂️ With `compat = "es6"`: the `_type` for docs that don't bring one (default `_doc`).
+    /// Hits from a 6.x Elasticsearch source keep their own.
+    #[serde(default)]
+    pub mapping_type: Option<String>,
+    /// 🔵🟢 `index` is an alias: load a fresh `{index}-{timestamp}` index, then move the alias
+    /// onto it atomically when the run succeeds. A failed run deletes the staging index.
+    #[serde(default)]
+    pub blue_green: bool,
+    /// 🔢 With `blue_green`: before the swap, the staging index's `_count` must match the
+    /// source's count (Elasticsearch and Generator sources — the ones that know it), less the
+    /// docs transforms dropped and the sink rejected.
+    #[serde(default)]
+    pub blue_green_verify_count: bool,
+    /// 🔑 Hash each doc (or `fields` of it) into its `_id`, so a rerun overwrites instead of
+    /// duplicating. For sources without ids of their own — not Elasticsearch
+    #[serde(default)]
+    pub doc_id: Option<DocIdConfig>,
+    /// 🔀 Rename target indices: `{ "old-products" = "products-v2" }`. Applied to each doc's
+    /// resolved index (the hit's own `_index`, or the `index` template's output); names not
+    /// listed pass through. Needs per-doc indices — a static `index` is already a rename.
+    #[serde(default)]
+    pub index_map: BTreeMap<String, String>,
+}
+
+impl ElasticsearchSinkConfig {
+    /// 🎚️ ES sink profile: 10MB bulk bodies — dead center of the 5–15MB sweet spot
+    /// the Elastic docs keep politely suggesting. `http.max_content_length` is 100MB,
+    /// but "allowed" and "advisable" are different words for a reason. 📡
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s to connect, 30s per `_bulk`. Busy clusters chewing 10MB bulks may need
+    /// `request_timeout_secs` raised — a timed-out bulk is retried, and may double-apply.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(30),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `password_file` / `api_key_file` for the secrets they point at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.password, self.password_file.as_deref(), "password")?;
+        resolve_secret_file(&mut self.api_key, self.api_key_file.as_deref(), "api_key")
+    }
+
+    /// 📦 `index` when it names one index — `None` when it's unset or a per-doc template.
+    pub fn static_index(&self) -> Option<&str> {
+        self.index.as_deref().filter(|the_index| !IndexTemplate::is_template(the_index))
+    }
+
+    /// 🗂️ `index` parsed as a per-document template, when it has placeholders.
+    pub fn index_template(&self) -> Result<Option<IndexTemplate>> {
+        match self.index.as_deref() {
+            Some(the_index) if IndexTemplate::is_template(the_index) => {
+                let the_template = IndexTemplate::parse(the_index)?;
+                Ok(Some(match self.index_date_field {
+                    Some(ref the_field) => the_template.with_date_field(the_field),
+                    None => the_template,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// 🔀 `index_map`, checked: only with per-doc indices, and only to names ES would accept.
+    pub fn index_map(&self) -> Result<IndexMap> {
+        if !self.index_map.is_empty()
+            && let Some(the_index) = self.static_index()
+        {
+            anyhow::bail!(
+                "💀 index_map renames each document's own index, and every document here goes to '{}'. \
+                 Leave `index` unset to keep (and rename) the source indices, or make it a template.",
+                the_index
+            );
+        }
+        IndexMap::new(&self.index_map)
+    }
+
+    /// 🦖 The `_type` for bulk action lines — `None` unless `compat = "es6"`.
+    pub fn bulk_mapping_type(&self) -> Option<&str> {
+        match self.compat {
+            Compat::Modern => None,
+            Compat::Es6 => Some(self.mapping_type.as_deref().unwrap_or(DEFAULT_MAPPING_TYPE)),
+        }
+    }
+
+    /// 🏗️ The create-index body for `create_index_if_missing`: whichever of `settings` /
+    /// `mappings` are configured. `{}` is a perfectly valid "ES, you pick" body.
+    pub fn inline_index_body(&self) -> serde_json::Value {
+        let mut the_body = serde_json::json!({});
+        if let Some(ref the_settings) = self.settings {
+            the_body["settings"] = the_settings.clone();
+        }
+        if let Some(ref the_mappings) = self.mappings {
+            the_body["mappings"] = the_mappings.clone();
+        }
+        the_body
+    }
 }
@@ -0,0 +1,288 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 👯 Duplicate check — after the migration, ask the target "is anyone here twice?"
+//!
+//! 🎬 *[three weeks after cutover. a dashboard shows 2x revenue for March.]*
+//! *["Great quarter!" says the VP.]*
+//! *[somewhere, a re-run with auto-generated `_id`s quietly smiles.]*
+//!
+//! Runs a `terms` aggregation with `min_doc_count: 2` on a logical key field of the sink
+//! index. Any bucket that comes back is a key that exists more than once — the fingerprint
+//! of re-runs without stable `_id`s, or a fan-out bug writing the same doc twice.
+//!
+//! 🧠 Knowledge graph:
+//! - Configured via `[sink_config.Elasticsearch.duplicate_check]` (`field`, `top_n`)
+//! - Called once by `lib.rs::run()` after the pipeline succeeds — not per drainer, not per sink
+//! - `_refresh` first, so the last bulk requests are visible to the aggregation
+//! - Offenders land in `RunWarnings` as `DataQuality`, so they show up in the end-of-run summary
+//! - Terms aggs are approximate across shards for the *counts* of rare keys; a key reported
+//!   here really is duplicated, but a clean report on a huge index is "very likely clean",
+//!   not a notarized affidavit. Use a `keyword` field. 🦆
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::config::ElasticsearchSinkConfig;
+
+fn default_top_n() -> usize {
+    10
+}
+
+/// 👯 Which field identifies a logical document, and how many offenders to report.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct DuplicateCheckConfig {
+    /// 🔑 The logical key field (a `keyword`/numeric field — aggregating on `text` will 400)
+    pub field: String,
+    /// 🔢 How many of the worst offenders to report. Defaults to 10.
+    #[serde(default = "default_top_n")]
+    pub top_n: usize,
+}
+
+/// 🧾 One key that exists more than once in the target.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateKey {
+    pub key: serde_json::Value,
+    pub doc_count: u64,
+}
+
+/// 📋 The verdict — empty `offenders` means no duplicates were found.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateReport {
+    pub index: String,
+    pub field: String,
+    /// 👯 Worst first, at most `top_n`
+    pub offenders: Vec<DuplicateKey>,
+    /// ➕ Docs in duplicate buckets beyond the top N — "and more where that came from"
+    pub other_duplicate_docs: u64,
+}
+
+impl DuplicateReport {
+    /// 📝 One-line human summary, e.g. for the run warnings.
+    pub fn summary(&self) -> String {
+        let the_keys: Vec<String> = self
+            .offenders
+            .iter()
+            .map(|o| format!("{} ×{}", o.key, o.doc_count))
+            .collect();
+        let mut the_summary = format!(
+            "Duplicate '{}' values in index '{}': {}",
+            self.field,
+            self.index,
+            the_keys.join(", ")
+        );
+        if self.other_duplicate_docs > 0 {
+            the_summary.push_str(&format!(" (+{} more duplicate docs beyond the top {})", self.other_duplicate_docs, self.offenders.len()));
+        }
+        the_summary
+    }
+}
+
+#[derive(Deserialize)]
+struct AggResponse {
+    aggregations: Aggregations,
+}
+
+#[derive(Deserialize)]
+struct Aggregations {
+    kvx_duplicates: TermsAgg,
+}
+
+#[derive(Deserialize)]
+struct TermsAgg {
+    #[serde(default)]
+    sum_other_doc_count: u64,
+    buckets: Vec<Bucket>,
+}
+
+#[derive(Deserialize)]
+struct Bucket {
+    key: serde_json::Value,
+    doc_count: u64,
+}
+
+/// 🔎 Refresh the sink index, then aggregate for keys with `doc_count >= 2`.
+pub async fn check_for_duplicates(
+    sink_config: &ElasticsearchSinkConfig,
+    check: &DuplicateCheckConfig,
+) -> Result<DuplicateReport> {
+    let Some(the_index) = sink_config.static_index() else {
+        anyhow::bail!(
+            "💀 duplicate_check needs a static sink `index` — with per-doc routing we don't know which index to interrogate."
+        );
+    };
+    let the_base = format!("{}/{}", sink_config.url.trim_end_matches('/'), the_index);
+    let the_client = sink_config.timeouts().apply(sink_config.connection.client_builder()?).build()?;
+
+    // -- 🔒 same auth pecking order as the sink: api_key beats basic auth
+    let authed = |request: reqwest::RequestBuilder| {
+        if let Some(ref api_key) = sink_config.api_key {
+            request.header("Authorization", format!("ApiKey {}", api_key))
+        } else if let Some(ref username) = sink_config.username {
+            request.basic_auth(username, sink_config.password.as_ref())
+        } else {
+            request
+        }
+    };
+
+    // -- 🔄 the last bulk requests might still be invisible to search. Make them visible.
+    authed(the_client.post(format!("{}/_refresh", the_base)))
+        .send()
+        .await
+        .context("💀 Couldn't refresh the target index before the duplicate check")?
+        .error_for_status()
+        .context("💀 The target index refused to refresh before the duplicate check")?;
+
+    let the_query = serde_json::json!({
+        "size": 0,
+        "aggs": {
+            "kvx_duplicates": {
+                "terms": {
+                    "field": check.field,
+                    "min_doc_count": 2,
+                    "size": check.top_n,
+                    "order": { "_count": "desc" }
+                }
+            }
+        }
+    });
+    let the_response = authed(the_client.post(format!("{}/_search", the_base)))
+        .header("Content-Type", "application/json")
+        .body(the_query.to_string())
+        .send()
+        .await
+        .context("💀 The duplicate-check aggregation never made it to the cluster")?;
+    let the_status = the_response.status();
+    let the_body = the_response.text().await.unwrap_or_default();
+    if !the_status.is_success() {
+        anyhow::bail!(
+            "💀 Duplicate-check aggregation on '{}' failed with {}: '{}'. Is '{}' a keyword field?",
+            the_index,
+            the_status,
+            the_body,
+            check.field
+        );
+    }
+
+    let the_agg: AggResponse = serde_json::from_str(&the_body)
+        .context("💀 The duplicate-check response didn't look like a terms aggregation")?;
+    Ok(DuplicateReport {
+        index: the_index.to_string(),
+        field: check.field.clone(),
+        offenders: the_agg
+            .aggregations
+            .kvx_duplicates
+            .buckets
+            .into_iter()
+            .map(|b| DuplicateKey { key: b.key, doc_count: b.doc_count })
+            .collect(),
+        other_duplicate_docs: the_agg.aggregations.kvx_duplicates.sum_other_doc_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::CommonSinkConfig;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn make_config(url: &str, index: Option<&str>) -> ElasticsearchSinkConfig {
+        ElasticsearchSinkConfig {
+            url: url.to_string(),
+            username: None,
+            password: None,
+            password_file: None,
+            api_key: None,
+            api_key_file: None,
+            index: index.map(str::to_string),
+            common_config: CommonSinkConfig::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
+        }
+    }
+
+    fn the_check() -> DuplicateCheckConfig {
+        DuplicateCheckConfig { field: "order_id".to_string(), top_n: 2 }
+    }
+
+    #[tokio::test]
+    async fn the_one_where_the_re_run_left_fingerprints() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/orders/_refresh"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/orders/_search"))
+            .and(body_partial_json(serde_json::json!({
+                "aggs": { "kvx_duplicates": { "terms": { "field": "order_id", "min_doc_count": 2, "size": 2 } } }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "aggregations": { "kvx_duplicates": {
+                    "sum_other_doc_count": 4,
+                    "buckets": [ { "key": "A-1", "doc_count": 3 }, { "key": "B-2", "doc_count": 2 } ]
+                } }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_report = check_for_duplicates(&make_config(&mock_server.uri(), Some("orders")), &the_check()).await?;
+        assert_eq!(the_report.offenders.len(), 2);
+        assert_eq!(the_report.offenders[0], DuplicateKey { key: serde_json::json!("A-1"), doc_count: 3 });
+        assert_eq!(the_report.other_duplicate_docs, 4);
+        assert!(the_report.summary().contains("\"A-1\" ×3"), "🎯 got: {}", the_report.summary());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn the_one_where_everyone_is_unique() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/orders/_refresh"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/orders/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "aggregations": { "kvx_duplicates": { "buckets": [] } }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let the_report = check_for_duplicates(&make_config(&mock_server.uri(), Some("orders")), &the_check()).await?;
+        assert!(the_report.offenders.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn the_one_where_per_doc_routing_has_no_index_to_ask() {
+        let the_err = check_for_duplicates(&make_config("http://localhost:1", None), &the_check())
+            .await
+            .unwrap_err();
+        assert!(format!("{}", the_err).contains("static sink `index`"));
+    }
+}
@@ -6,11 +6,18 @@ use std::time::Duration;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use tracing::{debug, trace};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, trace, warn};
 
 use crate::Payload;
 use crate::backends::Sink;
-use super::config::ElasticsearchSinkConfig;
+use crate::backends::sink::{DocumentsRejected, PayloadTooLarge, SinkThrottled, split_bulk_payload};
+use crate::warnings::{RunWarnings, WarningKind};
+use crate::workers::FailedDocs;
+use super::bulk_response::{DocumentFailurePolicy, dead_letter_records, parse_bulk_failures};
+use super::cluster_version::ClusterVersion;
+use super::config::{Compat, ElasticsearchSinkConfig};
+use super::write_alias::{WriteTarget, resolve_write_target};
 
 /// 📡 The sink side of the Elasticsearch backend — pure I/O, zero buffering.
 ///
@@ -25,6 +32,10 @@ use super::config::ElasticsearchSinkConfig;
 /// Internally holds:
 /// - `client`: the HTTP muscle 💪 — reused across requests
 /// - `sink_config`: auth, URL, index targeting info
+/// - `cluster_version`: what the root ping said the cluster is — 6.x gets a mapping type in the
+///   `_bulk` URL, 8.x+ gets `compatible-with` media types. `None` = couldn't tell, assume modern
+/// - `write_index`: the concrete index behind an alias `index` (see `write_alias.rs`)
+/// - a running tally of docs `_bulk` refused inside 200 responses (reported at `close`)
 ///
 /// 🚰 Think of this as the drain at the end of a data pipeline. The last stop.
 /// Knock knock. Who's there? HTTP POST. HTTP POST who? HTTP POST your NDJSON
@@ -33,6 +44,18 @@ use super::config::ElasticsearchSinkConfig;
 pub struct ElasticsearchSink {
     client: reqwest::Client,
     sink_config: ElasticsearchSinkConfig,
+    cluster_version: Option<ClusterVersion>,
+    // 🔗 when `index` is an alias: the concrete index it writes to
+    write_index: Option<String>,
+    // 🙅 docs refused inside "successful" bulk responses, and the first reason why
+    rejected_docs: usize,
+    first_rejection: Option<String>,
+    // 🪦 open handle on `dead_letter_file`, if configured
+    dead_letter: Option<tokio::fs::File>,
+    // ⚠️ where the rejection tally goes at close. Detached unless `with_warnings` is called.
+    warnings: RunWarnings,
+    // 🧯 the run-wide failed-doc tally, bumped as refusals arrive. Detached unless `with_failed_docs` is called.
+    failed_docs: FailedDocs,
 }
 
 #[async_trait]
@@ -57,31 +80,64 @@ impl Sink for ElasticsearchSink {
     /// Knock knock. Who's there? Nobody. The sink is closed. Go home. 🦆
     async fn close(&mut self) -> Result<()> {
         debug!("🗑️ Elasticsearch sink closing — no buffer to flush, just vibes to release");
+        if let Some(ref mut the_dead_letter) = self.dead_letter {
+            the_dead_letter.flush().await.context("💀 Couldn't flush the dead letter file")?;
+        }
+        if self.rejected_docs > 0 {
+            self.warnings.record_rejected(
+                self.rejected_docs as u64,
+                format!(
+                    "Elasticsearch rejected {} document(s) inside successful _bulk responses. First: {}",
+                    self.rejected_docs,
+                    self.first_rejection.as_deref().unwrap_or("unknown")
+                ),
+            );
+        }
         Ok(())
     }
+    /// ✂️ After a 413, halve the `_bulk` body on an action/source boundary.
+    fn split_payload(&self, payload: &Payload) -> Option<(Payload, Payload)> {
+        split_bulk_payload(payload)
+    }
+}
+
+/// 🔒 Auth priority: API key wins over basic auth. This is not a democracy.
+/// This is an Elasticsearch cluster and api_key is the premium tier.
+fn authed(config: &ElasticsearchSinkConfig, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    if let Some(ref api_key) = config.api_key {
+        request.header("Authorization", format!("ApiKey {}", api_key))
+    } else if let Some(ref username) = config.username {
+        request.basic_auth(username, config.password.as_ref())
+    } else {
+        request
+    }
 }
 
 impl ElasticsearchSink {
     /// 🚀 Stand up a new `ElasticsearchSink`, fully wired and ready to receive documents.
     ///
     /// This constructor does three things:
-    /// 1. Builds the `reqwest::Client` with sane timeouts (10s connect, 30s read).
+    /// 1. Builds the `reqwest::Client` with sane timeouts (10s connect, 30s read by default —
+    ///    `connect_timeout_secs` / `request_timeout_secs` override them), plus any
+    ///    custom CA / client certificate from `connection`.
     ///    Like a polite person — we will wait, but not forever.
     /// 2. Pings the cluster root URL with a GET to confirm it's alive and talking to us.
-    ///    A handshake. A hello. A "are you even there?"
+    ///    A handshake. A hello. A "are you even there?" The answer carries the version, which
+    ///    decides the `_bulk` URL and media types (see `cluster_version`).
     /// 3. If a static `index` is configured, verifies it exists with a HEAD/GET check.
     ///    Because indexing into a non-existent index is a skill issue we catch at init time,
     ///    not at 10,000 documents deep. You're welcome.
     ///
-    /// ⚠️ Basic auth is used for the connectivity ping. API key is used for the index check.
-    /// Pick your auth adventure, but be consistent about it in your config.
+    /// 🔒 Every request — ping, index check, `_bulk` — goes through `authed()`: api_key wins over
+    /// basic auth, so a cluster that only takes `ApiKey` still tells us its version.
     pub async fn new(config: ElasticsearchSinkConfig) -> Result<Self> {
-        // 🔧 Build the HTTP client. 10 second connect timeout because if ES can't handshake
-        // in 10 seconds, it's not having a good time and neither are we. 30 second response
-        // timeout because bulk requests can be meaty and we're not monsters.
-        let client = reqwest::Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(30))
+        // 🔧 Build the HTTP client. 10 second connect timeout (by default) because if ES can't
+        // handshake in 10 seconds, it's not having a good time and neither are we. 30 second
+        // response timeout because bulk requests can be meaty and we're not monsters.
+        let client = config
+            .connection
+            .client_builder()
+            .map(|the_builder| config.timeouts().apply(the_builder))?
             .build()
             // -- 💀 "Failed to initialize http client" — a tragedy in one act.
             // -- The curtain rises. reqwest::Client::builder() enters, full of promise.
@@ -92,33 +148,72 @@ impl ElasticsearchSink {
         // -- 📡 Connectivity ping — "Hello? Is this thing on?" — a developer, gesturing at a cluster.
         // We do a basic GET to the root to confirm the URL is real and auth works.
         // If this fails, we fail loudly here, rather than quietly 50,000 docs later.
-        let c = config.clone();
-        client
-            .get(&c.url)
-            .basic_auth(c.username.unwrap_or_default(), c.password)
-            .send()
-            .await?;
+        let the_ping = authed(&config, client.get(&config.url)).send().await?;
+
+        // 🏷️ Ask the cluster its age — a 500 or a body that isn't a cluster banner just means
+        // -- we can't tell, and we carry on assuming a modern cluster like we always did
+        let the_ping_status = the_ping.status();
+        let the_banner = the_ping.text().await.unwrap_or_default();
+        let cluster_version = match ClusterVersion::from_root_response(&the_banner) {
+            Ok(the_version) => {
+                info!("🏷️ Sink cluster is {}", the_version);
+                Some(the_version)
+            }
+            Err(the_shrug) => {
+                warn!(
+                    "⚠️ Couldn't read the sink cluster's version (root answered {}): {:#} — assuming a modern cluster",
+                    the_ping_status, the_shrug
+                );
+                None
+            }
+        };
+        if config.compat == Compat::Es6
+            && let Some(the_version) = cluster_version.filter(|v| !v.needs_mapping_type())
+        {
+            // -- 🦖 7.x only deprecates `_type` in action lines; 8.x refuses every one of them
+            warn!(
+                "⚠️ compat = \"es6\" puts a `_type` on every action line, but the sink cluster is {} — \
+                 drop `compat` unless it really is a 6.x cluster.",
+                the_version
+            );
+        }
+        if cluster_version.is_some_and(|v| v.needs_mapping_type())
+            && config.static_index().is_none()
+            && config.compat != Compat::Es6
+        {
+            // -- 🗂️ the type rides in the URL, and per-doc routing has no single URL to put it in
+            warn!(
+                "⚠️ The sink cluster predates 7.x and wants a mapping type on every document, but the index \
+                 is decided per document, so there's no `/{{index}}/_doc/_bulk` to send to. Set compat = \"es6\" \
+                 to put the type on each action line instead."
+            );
+        }
+
+        // 🌊 A data stream lives at one name — and that name goes in the URL, since the
+        // -- action lines deliberately carry no `_index`.
+        if config.data_stream && config.static_index().is_none() {
+            anyhow::bail!("💀 data_stream = true needs `index` set to the data stream's name (not an index template).");
+        }
 
         // 🔒 Optional index existence check — only runs if a static index is configured.
         // Per-doc index routing skips this, because checking every possible target index at
         // -- startup would be... ambitious. Like planning to read every book in a library before
-        // -- borrowing the first one.
-        if let Some(ref index_name) = config.index {
+        // -- borrowing the first one. Data streams skip it too: the template creates them on write.
+        // -- 🔵🟢 blue/green skips it as well: `index` is the staging index `run()` just created
+        // -- (or, under `kvx validate`, an alias that may not exist until the first swap).
+        // -- 🏗️ With `create_index_if_missing` / `copy_index`, a missing index is the plan, not a
+        // -- typo: `run()` builds it before the sink exists, and `kvx validate` never does.
+        let mut write_index = None;
+        if let Some(index_name) = config.static_index()
+            && !config.data_stream
+            && !config.blue_green
+        {
             // 📡 Construct the full index URL for a targeted existence check.
             // trim_end_matches('/') — the "/" hygiene you didn't know you needed.
             // Without it: `https://host//my-index`. With it: `https://host/my-index`.
             // -- One slash of difference. Infinite suffering of difference.
             let index_url = format!("{}/{}", config.url.trim_end_matches('/'), index_name);
-            let mut request = client.get(&index_url);
-            // -- 🔒 Auth priority: API key wins over basic auth. This is not a democracy.
-            // -- This is an Elasticsearch cluster and api_key is the premium tier.
-            if let Some(ref api_key) = config.api_key {
-                request = request.header("Authorization", format!("ApiKey {}", api_key));
-            } else if let Some(ref username) = config.username {
-                request = request.basic_auth(username, config.password.as_ref());
-            }
-
-            let response = request.send().await
+            let response = authed(&config, client.get(&index_url)).send().await
                 // -- 💀 "Failed to check for index availability" — a drama in one act.
                 // -- We sent a request into the void. The void sent back... nothing. Or an error.
                 // -- A TCP RST. A DNS NXDOMAIN. A firewall rule written by someone who has since
@@ -126,30 +221,143 @@ impl ElasticsearchSink {
                 // -- Schrodinger's cluster. Very advanced. Very unhelpful.
                 .context("💀 Reached out to check if the index exists. Got ghosted. The network is giving us the silent treatment. Or the firewall is on a power trip again. Either way: we cannot confirm the index lives, so we refuse to proceed. Dignity intact.")?;
             let status = response.status();
-            if !status.is_success() {
+            let the_index_is_ours_to_make = config.create_index_if_missing || config.copy_index.is_some();
+            if status == reqwest::StatusCode::NOT_FOUND && the_index_is_ours_to_make {
+                debug!("🏗️ Index '{}' isn't there yet — run() creates it before the first bulk", index_name);
+            } else if !status.is_success() {
                 // -- 💀 The index does not exist. This is not a warning. This is not a soft error.
                 // -- This is a hard stop, a full bail, a "we're not doing this."
                 // -- Indexing into a nonexistent index is chaos. We are order. We are the wall.
                 anyhow::bail!(
-                    "💀 Index '{}' does not exist and never has, as far as we can tell. We knocked. We waited. The door remained unanswered. You may want to create it, or check your spelling — easy mistake, no judgment, but also: please fix it.",
+                    "💀 Index '{}' does not exist and never has, as far as we can tell. We knocked. We waited. The door remained unanswered. You may want to create it (or set `create_index_if_missing = true`), or check your spelling — easy mistake, no judgment, but also: please fix it.",
                     index_url
                 );
             } else {
                 // -- ✅ The index exists! It is real! We found it! Like finding your keys in your coat!
                 // -- The one you already checked! But they were there! They were always there!
-                debug!(
-                    "✅ Index exists and is accepting visitors — welcome mat is out, cluster is home"
-                );
+                // -- 🔗 ...or it's an alias, and the answer says which index behind it takes the writes
+                let the_answer = response.text().await.unwrap_or_default();
+                match resolve_write_target(index_name, &the_answer)? {
+                    WriteTarget::Index => debug!(
+                        "✅ Index exists and is accepting visitors — welcome mat is out, cluster is home"
+                    ),
+                    WriteTarget::Alias { write_index: the_write_index } => {
+                        info!("🔗 '{}' is an alias — writes land in '{}'", index_name, the_write_index);
+                        write_index = Some(the_write_index);
+                    }
+                }
             }
         }
 
+        // 🪦 Open the dead letter file up front — a typo'd path should fail now, not at the first reject
+        let dead_letter = match config.dead_letter_file {
+            Some(ref the_path) => Some(
+                tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(the_path)
+                    .await
+                    .with_context(|| format!("💀 Couldn't open dead_letter_file '{}'", the_path.display()))?,
+            ),
+            None => None,
+        };
+
         // 🚀 All checks passed. No buffer to init — we're I/O-only now. Clean. Light. Free.
         Ok(Self {
             sink_config: config,
+            cluster_version,
+            write_index,
             client,
+            rejected_docs: 0,
+            first_rejection: None,
+            dead_letter,
+            warnings: RunWarnings::default(),
+            failed_docs: FailedDocs::default(),
         })
     }
 
+    /// 🏷️ The version the root ping reported, if it was readable.
+    pub fn cluster_version(&self) -> Option<ClusterVersion> {
+        self.cluster_version
+    }
+
+    /// 🔗 The concrete index behind `index`, when `index` turned out to be an alias.
+    pub fn write_index(&self) -> Option<&str> {
+        self.write_index.as_deref()
+    }
+
+    /// ⚠️ Report the rejected-doc tally into the run's warnings at `close`.
+    pub fn with_warnings(mut self, warnings: RunWarnings) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// 🧯 Count refused docs into the run's error budget as they happen.
+    pub fn with_failed_docs(mut self, failed_docs: FailedDocs) -> Self {
+        self.failed_docs = failed_docs;
+        self
+    }
+
+    /// 🧾 Read the per-item verdicts of a 2xx `_bulk` response and act on any refusals.
+    async fn handle_bulk_response(&mut self, body: &str, the_payload: Option<&str>) -> Result<()> {
+        let the_failures = match parse_bulk_failures(body) {
+            Ok(the_failures) => the_failures,
+            Err(the_confusion) => {
+                // -- 🤷 a 2xx we can't read (a proxy? an ES-alike?) — don't fail a request that probably worked
+                warn!("⚠️ Couldn't read the _bulk response, assuming success: {:#}", the_confusion);
+                return Ok(());
+            }
+        };
+        if the_failures.is_empty() {
+            return Ok(());
+        }
+
+        let the_first_reason = the_failures[0].reason();
+        warn!(
+            "🙅 Elasticsearch refused {} document(s) in a 200 response. First: {}",
+            the_failures.len(),
+            the_first_reason
+        );
+        self.rejected_docs += the_failures.len();
+        self.failed_docs.fetch_add(the_failures.len() as u64, std::sync::atomic::Ordering::Relaxed);
+        self.first_rejection.get_or_insert_with(|| the_first_reason.clone());
+
+        if let (Some(the_dead_letter), Some(the_payload)) = (self.dead_letter.as_mut(), the_payload) {
+            the_dead_letter
+                .write_all(dead_letter_records(the_payload, &the_failures).as_bytes())
+                .await
+                .context("💀 Couldn't append rejected documents to the dead letter file")?;
+        }
+
+        if self.sink_config.on_document_failure == DocumentFailurePolicy::Fail {
+            return Err(anyhow::Error::new(DocumentsRejected {
+                rejected: the_failures.len(),
+                first_reason: the_first_reason,
+            }));
+        }
+        Ok(())
+    }
+
+    /// 🔗 `{url}/{index}/_bulk`, or `{url}/_bulk` when docs carry their own `_index` (or a template put it there),
+    /// plus the query string: `pipeline=` for an ingest pipeline, `refresh=` unless it's the default.
+    /// A 6.x cluster gets `{url}/{index}/_doc/_bulk` — the URL's type covers every doc without one.
+    fn bulk_url(&self) -> Result<reqwest::Url> {
+        let the_type = if self.cluster_version.is_some_and(|v| v.needs_mapping_type()) { "/_doc" } else { "" };
+        let the_base = match self.sink_config.static_index() {
+            Some(index_name) => format!("{}/{}{}/_bulk", self.sink_config.url.trim_end_matches('/'), index_name, the_type),
+            None => format!("{}/_bulk", self.sink_config.url.trim_end_matches('/')),
+        };
+        let mut the_params: Vec<(&str, &str)> = Vec::new();
+        if let Some(ref the_pipeline) = self.sink_config.pipeline {
+            the_params.push(("pipeline", the_pipeline));
+        }
+        if let Some(the_refresh) = self.sink_config.refresh.as_param() {
+            the_params.push(("refresh", the_refresh));
+        }
+        reqwest::Url::parse_with_params(&the_base, &the_params)
+            .with_context(|| format!("💀 '{}' doesn't parse as a URL — check the sink's `url`", the_base))
+    }
+
     /// 📡 Fires a `_bulk` POST request with the given NDJSON body.
     ///
     /// This is the actual HTTP call that makes documents leave our process and enter
@@ -158,34 +366,36 @@ impl ElasticsearchSink {
     /// Auth is applied here: API key takes priority over basic auth, same as index check.
     /// If the response is not 2xx, we bail with enough detail to file a reasonable postmortem.
     ///
+    /// A 2xx is then read item by item — see `handle_bulk_response`.
+    ///
     /// 🔄 This function does not retry. Retries are the caller's problem. Good luck.
-    async fn submit_bulk_request(&self, request_body: Payload) -> Result<()> {
+    async fn submit_bulk_request(&mut self, request_body: Payload) -> Result<()> {
         // -- 📡 Build the bulk endpoint URL. The `_bulk` API: Elasticsearch's loading dock.
         // -- NDJSON only — no JSON arrays, no XML, no CSV, no hand-coded tab-separated values.
         // -- NDJSON. The only format Elasticsearch respects. Truly the format of people who
         // -- wanted JSON but also wanted to feel slightly superior about it.
-        let bulk_url = match self.sink_config.index {
-            Some(ref index_name) => format!("{}/{}/_bulk", self.sink_config.url.trim_end_matches('/'), index_name),
-            None => format!("{}/_bulk", self.sink_config.url.trim_end_matches('/'))
+        let bulk_url = self.bulk_url()?;
+
+        let mut request = self.client.post(bulk_url);
+        // ⚠️ Content-Type: application/x-ndjson — not application/json. VERY important.
+        // Elasticsearch will return a 406 or silently misbehave without this header.
+        // -- The x- prefix means "we made this up but we're committing to it." Classic.
+        // -- 📨 8.x+ gets the vendor media types the official clients send, pinned to its major
+        request = match self.cluster_version.and_then(|v| v.compatible_with()) {
+            Some(the_major) => request
+                .header("Content-Type", format!("application/vnd.elasticsearch+x-ndjson; compatible-with={}", the_major))
+                .header("Accept", format!("application/vnd.elasticsearch+json; compatible-with={}", the_major)),
+            None => request.header("Content-Type", "application/x-ndjson"),
         };
 
-        let mut request = self
-            .client
-            .post(&bulk_url)
-            // ⚠️ Content-Type: application/x-ndjson — not application/json. VERY important.
-            // Elasticsearch will return a 406 or silently misbehave without this header.
-            // -- The x- prefix means "we made this up but we're committing to it." Classic.
-            .header("Content-Type", "application/x-ndjson");
-
-        // -- 🔒 Same auth dance as the index check — api_key beats basic auth in this club.
-        if let Some(ref api_key) = self.sink_config.api_key {
-            request = request.header("Authorization", format!("ApiKey {}", api_key));
-        } else if let Some(ref username) = self.sink_config.username {
-            request = request.basic_auth(username, self.sink_config.password.as_ref());
-        }
+        // -- 🔒 Same auth dance as the ping and the index check — api_key beats basic auth in this club.
+        request = authed(&self.sink_config, request);
 
+        let the_payload_bytes = request_body.len();
+        // -- 🪦 keep a handle only if rejects need their original lines written somewhere — a refcount, not a copy
+        let the_payload_copy = self.dead_letter.is_some().then(|| request_body.clone());
         let response = request
-            .body(request_body.0)
+            .body(request_body.to_bytes())
             .send()
             .await
             // -- 💀 "Failed to send bulk request" — micro-fiction, act one.
@@ -197,6 +407,16 @@ impl ElasticsearchSink {
             .context("💀 The bulk request never made it to Elasticsearch. We launched the payload into the network and the network responded with what can only be described as 'not vibing with it.' Check connectivity, check timeouts, and check your feelings.")?;
 
         let status = response.status();
+        if status == reqwest::StatusCode::PAYLOAD_TOO_LARGE {
+            // -- 🐘 413: typed error so the drainer can shrink + split instead of backing off
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::Error::new(PayloadTooLarge { payload_bytes: the_payload_bytes, body }));
+        }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            // -- 🚦 429: write queues are full. Typed so the flow master hears about it
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::Error::new(SinkThrottled { body }));
+        }
         if !status.is_success() {
             // -- 💀 We got a response! It just... wasn't good news.
             // The body is fetched for context — it usually contains an 'error' object
@@ -208,13 +428,16 @@ impl ElasticsearchSink {
                 status,
                 body
             );
-        } else {
-            // -- ✅ Sent! Gone! Into the index! No cap, this function absolutely slapped.
-            trace!(
-                "🚀 Bulk request landed successfully — documents have left the building, Elvis-style"
-            );
         }
 
+        // -- 🧾 2xx means the request landed — not that every doc did. Read the receipt.
+        let body = response.text().await.unwrap_or_default();
+        self.handle_bulk_response(&body, the_payload_copy.as_deref()).await?;
+        // -- ✅ Sent! Gone! Into the index! No cap, this function absolutely slapped.
+        trace!(
+            "🚀 Bulk request landed successfully — documents have left the building, Elvis-style"
+        );
+
         Ok(())
     }
 }
@@ -231,7 +454,7 @@ mod tests {
     use super::*;
     use crate::Payload;
     use crate::backends::{CommonSinkConfig, Sink};
-    use wiremock::matchers::{body_string, header, method, path};
+    use wiremock::matchers::{body_string, header, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     // 🔧 The lazy config factory — minimal viable config, no auth, no index.
@@ -241,9 +464,31 @@ mod tests {
             url: url.to_string(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             index: None,
             common_config: CommonSinkConfig::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
         }
     }
 
@@ -406,6 +651,24 @@ mod tests {
         Ok(())
     }
 
+    /// 🧪 A data stream that doesn't exist yet is fine — the template makes it on first write.
+    #[tokio::test]
+    async fn the_one_where_the_stream_appears_when_you_walk_into_it() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+        // -- 👻 no mock for /logs-app — a GET would 404 and the constructor would bail
+
+        let mut config = make_config(&mock_server.uri());
+        config.index = Some("logs-app".to_string());
+        config.data_stream = true;
+        ElasticsearchSink::new(config).await?;
+
+        let mut the_nameless = make_config(&mock_server.uri());
+        the_nameless.data_stream = true;
+        assert!(ElasticsearchSink::new(the_nameless).await.is_err(), "💀 a data stream needs a name");
+        Ok(())
+    }
+
     /// 🧪 No index configured — no index check request. Mind your own business.
     #[tokio::test]
     async fn the_one_where_no_index_is_configured_and_thats_fine() -> Result<()> {
@@ -642,6 +905,89 @@ mod tests {
         Ok(())
     }
 
+    // 🔧 A root ping that answers with a version banner, like a real cluster would.
+    async fn mount_root_banner(mock_server: &MockServer, the_banner: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(the_banner))
+            .mount(mock_server)
+            .await;
+    }
+
+    /// 🧪 A cluster whose root only lets `ApiKey` in still tells us its version — the ping
+    /// carries the api_key, not an empty Basic header.
+    #[tokio::test]
+    async fn the_one_where_the_root_only_speaks_api_key() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(header("Authorization", "ApiKey the_root_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": {"number": "8.11.1"}})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET")).and(path("/")).respond_with(ResponseTemplate::new(401)).mount(&mock_server).await;
+
+        let mut config = make_config(&mock_server.uri());
+        config.api_key = Some("the_root_key".to_string());
+        config.username = Some("should_be_ignored".to_string());
+        let the_sink = ElasticsearchSink::new(config).await?;
+        assert_eq!(the_sink.cluster_version().map(|v| v.major), Some(8), "🏷️ the banner came back through the ApiKey door");
+        Ok(())
+    }
+
+    /// 🧪 A 6.x cluster gets the mapping type in the `_bulk` URL.
+    #[tokio::test]
+    async fn the_one_where_the_archive_cluster_still_wants_a_type() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_banner(&mock_server, serde_json::json!({"version": {"number": "6.8.23"}})).await;
+        Mock::given(method("GET")).and(path("/old-logs")).respond_with(ResponseTemplate::new(200)).mount(&mock_server).await;
+        Mock::given(method("POST"))
+            .and(path("/old-logs/_doc/_bulk"))
+            .and(header("Content-Type", "application/x-ndjson"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = make_config(&mock_server.uri());
+        config.index = Some("old-logs".to_string());
+        let mut the_sink = ElasticsearchSink::new(config).await?;
+        assert_eq!(the_sink.cluster_version().map(|v| v.major), Some(6));
+        the_sink.drain(Payload::from("{\"index\":{}}\n{\"id\":1}\n".to_string())).await?;
+        Ok(())
+    }
+
+    /// 🧪 An 8.x cluster gets the `compatible-with` media types; OpenSearch doesn't.
+    #[tokio::test]
+    async fn the_one_where_the_modern_cluster_gets_its_media_types() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_banner(&mock_server, serde_json::json!({"version": {"number": "8.11.1"}})).await;
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .and(header("Content-Type", "application/vnd.elasticsearch+x-ndjson; compatible-with=8"))
+            .and(header("Accept", "application/vnd.elasticsearch+json; compatible-with=8"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let mut the_sink = ElasticsearchSink::new(make_config(&mock_server.uri())).await?;
+        the_sink.drain(Payload::from("{\"index\":{}}\n{\"id\":1}\n".to_string())).await?;
+
+        let the_fork = MockServer::start().await;
+        mount_root_banner(&the_fork, serde_json::json!({"version": {"distribution": "opensearch", "number": "2.11.0"}})).await;
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .and(header("Content-Type", "application/x-ndjson"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&the_fork)
+            .await;
+        let mut the_sink = ElasticsearchSink::new(make_config(&the_fork.uri())).await?;
+        the_sink.drain(Payload::from("{\"index\":{}}\n{\"id\":1}\n".to_string())).await?;
+        Ok(())
+    }
+
     /// 🧪 API key auth on bulk requests. The premium tier.
     #[tokio::test]
     async fn the_one_where_api_key_auth_is_used_for_bulk() -> Result<()> {
@@ -793,6 +1139,55 @@ mod tests {
         Ok(())
     }
 
+    /// 🧪 413 comes back as a typed `PayloadTooLarge`, even through the context wrapping,
+    /// so the drainer can shrink + split instead of politely retrying the same elephant. 🐘
+    #[tokio::test]
+    async fn the_one_where_a_413_is_recognizable_as_too_large() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .respond_with(ResponseTemplate::new(413).set_body_string("request entity too large"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = make_config(&mock_server.uri());
+        let mut the_sink = ElasticsearchSink::new(config).await?;
+        let the_payload = "{\"index\":{}}\n{\"big\":true}\n";
+
+        let the_err = the_sink.drain(Payload::from(the_payload.to_string())).await.unwrap_err();
+        let the_too_large = the_err
+            .downcast_ref::<PayloadTooLarge>()
+            .expect("💀 413 should surface as PayloadTooLarge");
+        assert_eq!(the_too_large.payload_bytes, the_payload.len());
+        assert_eq!(the_too_large.body, "request entity too large");
+
+        Ok(())
+    }
+
+    /// 🧪 429 comes back as a typed `SinkThrottled`, so the drainer can tell the flow master to ease off. 🚦
+    #[tokio::test]
+    async fn the_one_where_the_cluster_asks_for_a_minute() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .respond_with(ResponseTemplate::new(429).set_body_string("es_rejected_execution_exception"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut the_sink = ElasticsearchSink::new(make_config(&mock_server.uri())).await?;
+        let the_err = the_sink.drain(Payload::from("{\"index\":{}}\n{\"busy\":true}\n".to_string())).await.unwrap_err();
+        let the_throttle = the_err.downcast_ref::<SinkThrottled>().expect("💀 429 should surface as SinkThrottled");
+        assert_eq!(the_throttle.body, "es_rejected_execution_exception");
+
+        Ok(())
+    }
+
     // ┌──────────────────────────────────────────────────────────────────────┐
     // │  GROUP D: close() — The No-Op                                       │
     // │  "The best code is no code at all." — Jeff Atwood, on close()       │
@@ -852,6 +1247,78 @@ mod tests {
         Ok(())
     }
 
+    /// 🧪 An ingest pipeline rides along on the query string; the cluster does the enriching.
+    #[tokio::test]
+    async fn the_one_where_the_docs_take_the_scenic_route() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .and(query_param("pipeline", "geo-enrich"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = make_config(&mock_server.uri());
+        config.pipeline = Some("geo-enrich".to_string());
+        let mut the_enriching_sink = ElasticsearchSink::new(config).await?;
+
+        the_enriching_sink.drain(Payload::from("{\"index\":{}}\n{\"id\":1}\n".to_string())).await?;
+
+        // 🎯 Assert — expect(1) on the query_param matcher ✅
+        Ok(())
+    }
+
+    /// 🧪 `refresh = "wait_for"` reaches the wire; the default sends no refresh param at all.
+    #[tokio::test]
+    async fn the_one_where_the_test_waits_to_be_seen() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .and(query_param("refresh", "wait_for"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = make_config(&mock_server.uri());
+        config.refresh = toml::from_str::<ElasticsearchSinkConfig>("url = \"x\"\nindex = \"i\"\nrefresh = \"wait_for\"")?.refresh;
+        let mut the_patient_sink = ElasticsearchSink::new(config).await?;
+        the_patient_sink.drain(Payload::from("{\"index\":{}}\n{\"id\":1}\n".to_string())).await?;
+
+        assert_eq!(make_config("x").refresh.as_param(), None, "🏎️ the default leaves refresh_interval in charge");
+        Ok(())
+    }
+
+    /// 🧪 An index template isn't an index: no existence check at startup, bulks go to plain `/_bulk`.
+    #[tokio::test]
+    async fn the_one_where_the_index_name_is_decided_later() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+
+        // -- 🕳️ no mock for `/{_type}-{Project.Name}` — a HEAD/GET there would 404 and fail `new()`
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = make_config(&mock_server.uri());
+        config.index = Some("{_type}-{Project.Name}".to_string());
+        assert_eq!(config.static_index(), None);
+        let mut the_routing_sink = ElasticsearchSink::new(config.clone()).await?;
+        the_routing_sink.drain(Payload::from("{\"index\":{\"_index\":\"defect-apollo\"}}\n{\"id\":1}\n".to_string())).await?;
+
+        config.data_stream = true;
+        assert!(ElasticsearchSink::new(config).await.is_err(), "💀 a data stream can't have a templated name");
+        Ok(())
+    }
+
     /// 🧪 Empty payload — sent as-is. The sink doesn't validate content. YOLO. 🦆
     #[tokio::test]
     async fn the_one_where_we_send_an_empty_payload_because_yolo() -> Result<()> {
@@ -879,4 +1346,124 @@ mod tests {
 
         Ok(())
     }
+
+    // ┌──────────────────────────────────────────────────────────────────────┐
+    // │  Per-document failures inside a 200                                  │
+    // │  "The request succeeded." "Did the docs?" "...define succeeded."     │
+    // └──────────────────────────────────────────────────────────────────────┘
+
+    const THE_PARTIAL_VERDICT: &str = r#"{"took":2,"errors":true,"items":[
+        {"index":{"_index":"people","_id":"1","status":201,"result":"created"}},
+        {"index":{"_index":"people","_id":"2","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse field [age]"}}}
+    ]}"#;
+    const THE_TWO_DOCS: &str = "{\"index\":{\"_id\":\"1\"}}\n{\"age\":1}\n{\"index\":{\"_id\":\"2\"}}\n{\"age\":\"old\"}\n";
+
+    async fn mount_partial_bulk(mock_server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(THE_PARTIAL_VERDICT))
+            .mount(mock_server)
+            .await;
+    }
+
+    /// 🧪 Default policy: a refused doc is counted, warned about at close, and the run carries on.
+    #[tokio::test]
+    async fn the_one_where_the_200_had_fine_print() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+        mount_partial_bulk(&mock_server).await;
+
+        let the_warnings = RunWarnings::default();
+        let mut the_sink = ElasticsearchSink::new(make_config(&mock_server.uri()))
+            .await?
+            .with_warnings(the_warnings.clone());
+        the_sink.drain(Payload::from(THE_TWO_DOCS.to_string())).await?;
+        assert!(the_warnings.is_empty(), "💀 the tally is reported at close, not per payload");
+        the_sink.close().await?;
+
+        let the_notes = the_warnings.snapshot();
+        assert_eq!(the_notes.len(), 1);
+        assert_eq!(the_notes[0].kind, WarningKind::SkippedInput);
+        assert!(the_notes[0].message.contains("rejected 1 document"), "🎯 got: {}", the_notes[0].message);
+        assert!(the_notes[0].message.contains("mapper_parsing_exception"));
+        assert_eq!(the_warnings.rejected_docs(), 1);
+        Ok(())
+    }
+
+    /// 🧪 `on_document_failure = "fail"` turns the fine print into a typed, non-retryable error.
+    #[tokio::test]
+    async fn the_one_where_fail_means_fail() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+        mount_partial_bulk(&mock_server).await;
+
+        let mut config = make_config(&mock_server.uri());
+        config.on_document_failure = DocumentFailurePolicy::Fail;
+        let mut the_sink = ElasticsearchSink::new(config).await?;
+
+        let the_err = the_sink.drain(Payload::from(THE_TWO_DOCS.to_string())).await.unwrap_err();
+        let the_rejection = the_err.downcast_ref::<DocumentsRejected>().expect("🎯 should be DocumentsRejected");
+        assert_eq!(the_rejection.rejected, 1);
+        Ok(())
+    }
+
+    /// 🧪 Rejects land in the dead letter file with their original lines and the error.
+    #[tokio::test]
+    async fn the_one_where_the_rejects_get_a_proper_burial() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+        mount_partial_bulk(&mock_server).await;
+
+        let the_graveyard = tempfile::NamedTempFile::new()?;
+        let mut config = make_config(&mock_server.uri());
+        config.dead_letter_file = Some(the_graveyard.path().to_path_buf());
+        let mut the_sink = ElasticsearchSink::new(config).await?;
+        the_sink.drain(Payload::from(THE_TWO_DOCS.to_string())).await?;
+        the_sink.close().await?;
+
+        let the_contents = std::fs::read_to_string(the_graveyard.path())?;
+        assert_eq!(the_contents.lines().count(), 1);
+        let the_record: serde_json::Value = serde_json::from_str(the_contents.trim())?;
+        assert_eq!(the_record["document"], serde_json::json!({"age":"old"}));
+        assert_eq!(the_record["error"]["type"], "mapper_parsing_exception");
+        Ok(())
+    }
+
+    /// 🧪 `index` is an alias: the sink names its write index — or refuses an alias that has none.
+    #[tokio::test]
+    async fn the_one_where_the_index_was_an_alias_all_along() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_root_ping(&mock_server).await;
+        Mock::given(method("GET"))
+            .and(path("/logs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "logs-000001": { "aliases": { "logs": { "is_write_index": false } } },
+                "logs-000002": { "aliases": { "logs": { "is_write_index": true } } }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/reports"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "reports-2023": { "aliases": { "reports": {} } },
+                "reports-2024": { "aliases": { "reports": {} } }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let the_sink = ElasticsearchSink::new(ElasticsearchSinkConfig {
+            index: Some("logs".to_string()),
+            ..make_config(&mock_server.uri())
+        })
+        .await?;
+        assert_eq!(the_sink.write_index(), Some("logs-000002"));
+
+        let the_read_only = ElasticsearchSink::new(ElasticsearchSinkConfig {
+            index: Some("reports".to_string()),
+            ..make_config(&mock_server.uri())
+        })
+        .await;
+        assert!(the_read_only.is_err(), "💀 an alias with no write index takes no writes");
+        Ok(())
+    }
 }
@@ -2,52 +2,1049 @@
 //
 // Use of this software is governed by the Business Source License
 // included in the LICENSE file and at www.mariadb.com/bsl11.
-use anyhow::Result;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
 
 use crate::Page;
 use crate::backends::Source;
-use super::config::ElasticsearchSourceConfig;
+use crate::progress::DrainMetrics;
+use super::config::{ElasticsearchSourceConfig, Pagination};
+use super::time_slice::{TimeWindow, WindowLedger};
+
+/// 🔁 The wait before a failed time window's first retry — doubled for each one after it.
+const WINDOW_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 🍕 Which slice of a sliced read this source owns: `slice.id` of `slice.max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SliceSpec {
+    pub id: usize,
+    pub max: usize,
+}
 
 /// 📦 The source side of the Elasticsearch backend.
 ///
-/// This struct holds a config and currently does approximately
-/// nothing useful in production because `next_batch` returns empty. 🐛
-/// It is, however, a *very* well-intentioned nothing. The vibes are all correct.
-/// The scaffolding is artisan-grade. The potential is immense. The implementation is... pending.
+/// Pages through the index one `_search` response body per `pump()`. Each page is handed
+/// downstream untouched — the casters (`PitToBulk`, `PitToJson`, ...) unwrap `hits.hits[]`.
 ///
-/// No cap, this will slap once scroll/search_after lands. We believe in it. We believe in you.
+/// 🧠 Knowledge graph:
+/// - `pagination = "pit"` (default): open a point-in-time on the first pump, then
+///   `search_after` the last hit's `sort` values, sorted by `_shard_doc` (the cheapest total
+///   order ES has). Every search renews the PIT's `keep_alive` and may hand back a new
+///   `pit_id`, which we always adopt. The PIT is closed when the last query runs dry
+/// - `pagination = "scroll"`: the pre-7.12 fallback — scroll contexts, `sort: ["_doc"]`,
+///   each cleared as it runs dry. `compat = "es6"` forces it — 6.x has neither PIT nor `_shard_doc`
+/// - `query` replaces the default `match_all`. With `time_slice`, it is AND'ed with each
+///   window's range, and `count_docs` counts the same filtered read
+/// - With `time_slice`, each `TimeWindow` is its own query, walked in order (same PIT). A
+///   window read to the end is reported to the shared `WindowLedger` (progress, checkpoint),
+///   and a rerun starts where the checkpoint left off. A window that fails mid-read is started
+///   over from its first page with a fresh PIT/scroll, up to `window_retries` times
+/// - A pattern or list `index` (`logs-*`, `["a", "b"]`) is resolved on the first pump
+///   (`_cat/indices`) and read one index at a time: every query, then the next index. Each
+///   index gets its own PIT, closed when it's done. Hits keep their `_index`, so the sink can
+///   keep it or remap it (`index = "restored-{_index}"`)
+/// - With `slices = N`, `lib.rs` builds N of these (`with_slice`), each pumped by its own
+///   Pumper into the same ch1. ES guarantees the slices are disjoint. 🍕 Each slice opens
+///   its own PIT, so slices see snapshots taken milliseconds apart, not one shared instant
+/// - `source_includes` / `source_excludes` become the search's `_source` clause, so unused
+///   fields are dropped on the source cluster and never cost network or memory here
+/// - A context left behind by a crash expires on its own after `keep_alive`
 #[derive(Debug)]
 pub struct ElasticsearchSource {
-    #[allow(dead_code)]
-    // -- 🔧 config kept for when pump() finally stops ghosting us and actually scrolls.
-    // -- Marked dead_code because rustc has opinions and no chill.
     config: ElasticsearchSourceConfig,
+    /// 🗓️ The slicing plan, in order — empty when `time_slice` isn't configured (one big read).
+    /// Computed up front so a bad date range fails at startup, not three windows in.
+    pub time_windows: Vec<TimeWindow>,
+    client: reqwest::Client,
+    slice: Option<SliceSpec>,
+    /// 🔢 Ask for `_version` on every hit — an `id_export` with `include_version` needs it
+    versions: bool,
+    /// 📋 The queries every index is read with — one per time window, or a single one
+    queries: Vec<serde_json::Value>,
+    /// 📒 Finished windows, shared by every slice — `None` without `time_slice`, or when a
+    /// pattern `index` walks the windows once per index
+    windows: Option<Arc<WindowLedger>>,
+    /// 🔁 Restarts of the current window so far
+    window_attempts: u32,
+    /// 📦 Indices not yet started. `None` until the first pump resolves `index`
+    pending_indices: Option<VecDeque<String>>,
+    /// 📦 The index being read right now — the whole `index` expression unless it's a pattern or list
+    current_index: Option<String>,
+    /// 🗂️ Queries not yet started on the current index, as positions in `queries`
+    pending_queries: VecDeque<usize>,
+    /// 🔎 The query being paged right now — its position in `queries` (and `time_windows`)
+    current_query: Option<usize>,
+    /// 📸 The open point-in-time, if any (PIT mode)
+    pit_id: Option<String>,
+    /// ➡️ `sort` values of the last hit we handed out (PIT mode)
+    search_after: Option<serde_json::Value>,
+    /// 📜 The live scroll, if one is open (scroll mode)
+    scroll_id: Option<String>,
+}
+
+/// 📡 Just enough of a `_search` response to steer the next request.
+#[derive(Deserialize)]
+struct PagePeek {
+    #[serde(rename = "_scroll_id")]
+    scroll_id: Option<String>,
+    pit_id: Option<String>,
+    hits: PagePeekHits,
+}
+
+#[derive(Deserialize)]
+struct PagePeekHits {
+    hits: Vec<PagePeekHit>,
+}
+
+#[derive(Deserialize)]
+struct PagePeekHit {
+    sort: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct OpenPitResponse {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct CatIndex {
+    index: String,
+}
+
+#[derive(Deserialize)]
+struct CountResponse {
+    count: u64,
 }
 
 #[async_trait]
 impl Source for ElasticsearchSource {
-    /// 📡 Returns the next raw page from Elasticsearch.
-    ///
-    /// Currently returns `None` faster than you can say "scroll API."
-    /// It's aspirational. It's a placeholder with excellent posture.
-    /// The borrow checker is fully satisfied. The product manager is not.
-    /// "He who stubs with None, deploys with hope." — Ancient scroll API proverb 📜
+    /// 📡 Returns the next raw `_search` response page, or `None` once every query is exhausted.
     async fn pump(&mut self) -> Result<Option<Page>> {
-        // TODO: Implement search_after — the glow-up we deserve. 🚀 🦆
-        Ok(None)
+        loop {
+            let the_position = match self.current_query {
+                Some(the_position) => the_position,
+                None => match self.pending_queries.pop_front() {
+                    Some(the_position) => {
+                        self.current_query = Some(the_position);
+                        the_position
+                    }
+                    None => {
+                        // -- 🏁 this index is read — let go of its snapshot, then on to the next (if any)
+                        if let Some(the_pit_id) = self.pit_id.take() {
+                            self.close_pit(&the_pit_id).await;
+                        }
+                        if !self.next_index().await? {
+                            return Ok(None);
+                        }
+                        // -- ⏭️ windows a checkpoint says are done aren't read again
+                        let the_first = self.windows.as_ref().map_or(0, |the_ledger| the_ledger.resume_from());
+                        self.pending_queries = (the_first..self.queries.len()).collect();
+                        continue;
+                    }
+                },
+            };
+
+            let the_query = self.queries[the_position].clone();
+            let the_page = match self.config.effective_pagination() {
+                Pagination::Pit => self.search_pit(&the_query).await,
+                Pagination::Scroll => match self.scroll_id.clone() {
+                    Some(the_scroll_id) => self.continue_scroll(&the_scroll_id).await,
+                    None => self.open_scroll(&the_query).await,
+                },
+            };
+            let the_body = match the_page {
+                Ok(the_body) => the_body,
+                Err(the_failure) => {
+                    self.start_window_over(the_failure).await?;
+                    continue;
+                }
+            };
+
+            let the_peek: PagePeek = serde_json::from_str(&the_body)
+                .context("💀 Elasticsearch answered the search with something that isn't a search response")?;
+            // -- 📸 ES may rotate the PIT id on any response — the newest one is the one to use
+            if the_peek.pit_id.is_some() {
+                self.pit_id = the_peek.pit_id;
+            }
+            let Some(the_last_hit) = the_peek.hits.hits.last() else {
+                // -- 🏁 this query ran dry — tidy up and move on to the next one (if any)
+                self.current_query = None;
+                self.search_after = None;
+                self.window_attempts = 0;
+                if let Some(the_spent_id) = self.scroll_id.take().or(the_peek.scroll_id) {
+                    self.clear_scroll(&the_spent_id).await;
+                }
+                if let (Some(the_ledger), Some(the_window)) = (&self.windows, self.time_windows.get(the_position)) {
+                    the_ledger.finish(self.slice.map_or(0, |the_slice| the_slice.id), the_window).await?;
+                }
+                continue;
+            };
+            match self.config.effective_pagination() {
+                Pagination::Pit => {
+                    self.search_after = Some(the_last_hit.sort.clone().context(
+                        "💀 A PIT search hit came back without `sort` values — can't search_after without them",
+                    )?);
+                }
+                Pagination::Scroll => self.scroll_id = the_peek.scroll_id,
+            }
+            return Ok(Some(Page(the_body)));
+        }
     }
 }
 
 impl ElasticsearchSource {
-    /// 🚀 Constructs a new `ElasticsearchSource`.
-    ///
-    /// Currently: allocates config and that's it. When search_after lands,
-    /// this will do HTTP client setup, index validation, and maybe even a _count query
-    /// so we can show real progress instead of existential uncertainty. 🦆
+    /// 🚀 Constructs a new `ElasticsearchSource` — builds the HTTP client and the query plan.
     ///
-    /// "How much data?" "Yes." — Elasticsearch, every time.
+    /// No request goes out until the first `pump()`. "How much data?" "Yes." — Elasticsearch, every time.
     pub async fn new(config: ElasticsearchSourceConfig) -> Result<Self> {
-        Ok(Self { config })
+        let time_windows = match &config.time_slice {
+            Some(the_slice) => {
+                let the_windows = the_slice
+                    .windows()
+                    .context("💀 The time_slice config doesn't describe a range we can walk")?;
+                info!(
+                    "🗓️ Time slicing on '{}': {} → {} in {} windows of {}",
+                    the_slice.field,
+                    the_slice.start,
+                    the_slice.end,
+                    the_windows.len(),
+                    the_slice.window
+                );
+                the_windows
+            }
+            None => Vec::new(),
+        };
+        let the_user_query = config.query_clause()?;
+        let queries = match (&config.time_slice, the_user_query) {
+            (Some(the_slice), None) => time_windows.iter().map(|w| w.range_query(&the_slice.field)).collect(),
+            // -- 🔎 each window keeps the user's query and narrows it to its own dates
+            (Some(the_slice), Some(the_query)) => time_windows
+                .iter()
+                .map(|w| serde_json::json!({ "bool": { "must": [the_query], "filter": [w.range_query(&the_slice.field)] } }))
+                .collect(),
+            (None, Some(the_query)) => vec![the_query],
+            (None, None) => vec![serde_json::json!({ "match_all": {} })],
+        };
+        let windows = match config.time_slice {
+            Some(ref the_slice) if config.reads_many_indices() => {
+                if the_slice.checkpoint_file.is_some() {
+                    anyhow::bail!(
+                        "💀 time_slice.checkpoint_file records windows of one index, and '{}' is a pattern or list — \
+                         every index walks every window. Drop checkpoint_file, or run one index at a time.",
+                        config.index.as_deref().unwrap_or_default()
+                    );
+                }
+                None
+            }
+            Some(ref the_slice) => Some(Arc::new(WindowLedger::open(the_slice, &time_windows, config.slices.unwrap_or(1))?)),
+            None => None,
+        };
+        if config.effective_pagination() == Pagination::Pit && config.index.is_none() {
+            anyhow::bail!(
+                "💀 PIT pagination needs a source `index` (a PIT is opened on specific indices). \
+                 Set `index`, or use `pagination = \"scroll\"` to read everything."
+            );
+        }
+        let client = config
+            .connection
+            .client_builder()
+            .map(|the_builder| config.timeouts().apply(the_builder))?
+            .build()
+            .context("💀 The HTTP client for the Elasticsearch source refused to be born")?;
+        Ok(Self {
+            config,
+            time_windows,
+            client,
+            slice: None,
+            versions: false,
+            queries,
+            windows,
+            window_attempts: 0,
+            pending_indices: None,
+            current_index: None,
+            pending_queries: VecDeque::new(),
+            current_query: None,
+            pit_id: None,
+            search_after: None,
+            scroll_id: None,
+        })
+    }
+
+    /// 🍕 Read only slice `id` of `max`. Build one source per id to cover the whole index.
+    pub fn with_slice(mut self, id: usize, max: usize) -> Self {
+        self.slice = Some(SliceSpec { id, max });
+        self
+    }
+
+    /// 🔢 Send `"version": true` with every search, so each hit carries its `_version`.
+    pub fn with_versions(mut self, versions: bool) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// 🍕 Which slice this source reads, if sliced.
+    pub fn slice(&self) -> Option<SliceSpec> {
+        self.slice
+    }
+
+    /// 📒 The window ledger, if this read has one — hand it to the other slices of the run.
+    pub fn window_ledger(&self) -> Option<&Arc<WindowLedger>> {
+        self.windows.as_ref()
+    }
+
+    /// 📒 Share another slice's ledger, so a window counts as done once all of them finish it.
+    pub fn with_window_ledger(mut self, ledger: Arc<WindowLedger>) -> Self {
+        if self.windows.is_some() {
+            self.windows = Some(ledger);
+        }
+        self
+    }
+
+    /// 📊 Let the window ledger move the run's progress events.
+    pub fn report_to(&self, metrics: &Arc<DrainMetrics>) {
+        if let Some(ref the_ledger) = self.windows {
+            the_ledger.report_to(metrics);
+        }
+    }
+
+    /// 🔁 A search failed mid-window: start the window over from its first page, with a fresh
+    /// PIT or scroll. Pages of it already handed out are read again — stable `_id`s make that
+    /// an overwrite. Without `time_slice`, or once `window_retries` is spent, the failure stands.
+    async fn start_window_over(&mut self, the_failure: anyhow::Error) -> Result<()> {
+        let the_retries = self.config.time_slice.as_ref().map_or(0, |the_slice| the_slice.window_retries);
+        let Some(the_window) = self.current_query.and_then(|the_position| self.time_windows.get(the_position)).cloned() else {
+            return Err(the_failure);
+        };
+        if self.window_attempts >= the_retries {
+            return Err(the_failure.context(format!(
+                "💀 Window {} ({} → {}) failed {} time(s) — out of window_retries",
+                the_window.index,
+                the_window.gte,
+                the_window.lt,
+                self.window_attempts + 1
+            )));
+        }
+        self.window_attempts += 1;
+        warn!(
+            "🔁 Window {} ({} → {}) failed mid-read — starting it over ({}/{}): {:#}",
+            the_window.index, the_window.gte, the_window.lt, self.window_attempts, the_retries, the_failure
+        );
+        if let Some(the_pit_id) = self.pit_id.take() {
+            self.close_pit(&the_pit_id).await;
+        }
+        if let Some(the_scroll_id) = self.scroll_id.take() {
+            self.clear_scroll(&the_scroll_id).await;
+        }
+        self.search_after = None;
+        tokio::time::sleep(WINDOW_RETRY_BACKOFF * 2u32.pow(self.window_attempts - 1)).await;
+        Ok(())
+    }
+
+    /// 🔢 `POST /{index}/_count` with the same query the pages will use — how many docs are coming.
+    ///
+    /// Counts the whole read, not this source's slice, so ask once per run, not once per slice.
+    /// Time windows are OR'ed into one `bool.should` instead of one `_count` per window — only
+    /// the ones still to read, when a checkpoint says some are done. 🗓️
+    pub async fn count_docs(&self) -> Result<u64> {
+        let the_first = self.windows.as_ref().map_or(0, |the_ledger| the_ledger.resume_from());
+        let the_query = match &self.queries[the_first..] {
+            [] => return Ok(0),
+            [the_only] => the_only.clone(),
+            the_queries => serde_json::json!({
+                "bool": { "should": the_queries, "minimum_should_match": 1 }
+            }),
+        };
+        let the_url = match self.config.index {
+            Some(ref the_index) => format!("{}/{}/_count", self.base_url(), the_index),
+            None => format!("{}/_count", self.base_url()),
+        };
+        let the_body = self
+            .send_search(self.client.post(&the_url), serde_json::json!({ "query": the_query }))
+            .await
+            .context("💀 Couldn't count the source documents")?;
+        let the_count: CountResponse =
+            serde_json::from_str(&the_body).context("💀 The _count response had no `count`")?;
+        Ok(the_count.count)
+    }
+
+    /// 🩺 For `kvx validate`: `GET /` with the source credentials, then `count_docs()` on the
+    /// configured index — so a wrong URL, bad credentials or a missing index fail before the run.
+    pub async fn probe(&self) -> Result<u64> {
+        let the_response = self
+            .authed(self.client.get(self.base_url()))
+            .send()
+            .await
+            .with_context(|| format!("💀 Couldn't reach the source cluster at '{}'", self.base_url()))?;
+        let the_status = the_response.status();
+        if !the_status.is_success() {
+            let the_text: String = the_response.text().await.unwrap_or_default().chars().take(200).collect();
+            anyhow::bail!(
+                "💀 The source cluster answered GET / with {}: '{}'. Check `url`, and the username/password or api_key.",
+                the_status,
+                the_text
+            );
+        }
+        let the_count = self.count_docs().await?;
+        info!("✅ Source cluster answered — {} docs to read", the_count);
+        Ok(the_count)
+    }
+
+    /// 📦 Move on to the next index to read; `false` once there are none left.
+    ///
+    /// The first call works out the plan: a pattern or list is resolved to its open indices,
+    /// sorted, and read one at a time. Anything else (one index, an alias, nothing) is one read.
+    async fn next_index(&mut self) -> Result<bool> {
+        if self.pending_indices.is_none() {
+            if !self.config.reads_many_indices() {
+                self.pending_indices = Some(VecDeque::new());
+                self.current_index = self.config.index.clone();
+                return Ok(true);
+            }
+            let the_indices = self.resolve_indices().await?;
+            self.pending_indices = Some(the_indices);
+        }
+        let Some(the_next) = self.pending_indices.as_mut().and_then(VecDeque::pop_front) else {
+            return Ok(false);
+        };
+        info!(
+            "📦 Reading '{}' ({} more after it, slice {:?})",
+            the_next,
+            self.pending_indices.as_ref().map_or(0, VecDeque::len),
+            self.slice
+        );
+        self.current_index = Some(the_next);
+        Ok(true)
+    }
+
+    /// 🗂️ `GET /_cat/indices/{index}` — the open indices a pattern or list names, the same ones
+    /// a `_search` on it would read. Sorted, so every slice walks them in the same order.
+    async fn resolve_indices(&self) -> Result<VecDeque<String>> {
+        let the_expression = self.config.index.as_deref().unwrap_or_default();
+        let the_url = format!("{}/_cat/indices/{}?h=index&format=json&expand_wildcards=open", self.base_url(), the_expression);
+        let the_response = self
+            .authed(self.client.get(&the_url))
+            .send()
+            .await
+            .context("💀 Couldn't reach the source cluster to list the indices to read")?;
+        let the_status = the_response.status();
+        let the_text = the_response.text().await.unwrap_or_default();
+        if !the_status.is_success() {
+            anyhow::bail!(
+                "💀 Listing the indices '{}' names failed with {}: '{}'. The source user needs the `monitor` privilege on them.",
+                the_expression,
+                the_status,
+                the_text
+            );
+        }
+        let the_cat: Vec<CatIndex> =
+            serde_json::from_str(&the_text).context("💀 The _cat/indices answer wasn't a list of indices")?;
+        let mut the_indices: Vec<String> = the_cat.into_iter().map(|c| c.index).collect();
+        the_indices.sort();
+        the_indices.dedup();
+        if the_indices.is_empty() {
+            warn!("⚠️ '{}' matched no open indices — nothing to read", the_expression);
+        } else {
+            info!("🗂️ '{}' matched {} indices, read one at a time", the_expression, the_indices.len());
+        }
+        Ok(the_indices.into())
+    }
+
+    fn base_url(&self) -> &str {
+        self.config.url.trim_end_matches('/')
+    }
+
+    // -- 🔒 api_key beats basic auth, same pecking order as the sink
+    fn authed(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(ref api_key) = self.config.api_key {
+            request.header("Authorization", format!("ApiKey {}", api_key))
+        } else if let Some(ref username) = self.config.username {
+            request.basic_auth(username, self.config.password.as_ref())
+        } else {
+            request
+        }
+    }
+
+    /// 📸 `POST /{index}/_pit?keep_alive=..` — freeze the index as of right now.
+    async fn open_pit(&self) -> Result<String> {
+        let the_index = self.current_index.as_deref().unwrap_or("_all");
+        let the_url = format!("{}/{}/_pit?keep_alive={}", self.base_url(), the_index, self.config.keep_alive);
+        let the_response = self
+            .authed(self.client.post(&the_url))
+            .send()
+            .await
+            .context("💀 Couldn't reach the source cluster to open a point-in-time")?;
+        let the_status = the_response.status();
+        let the_text = the_response.text().await.unwrap_or_default();
+        if !the_status.is_success() {
+            anyhow::bail!(
+                "💀 Opening a point-in-time on '{}' failed with {}: '{}'. Clusters before 7.12 need `pagination = \"scroll\"`.",
+                the_index,
+                the_status,
+                the_text
+            );
+        }
+        let the_pit: OpenPitResponse =
+            serde_json::from_str(&the_text).context("💀 The point-in-time response had no `id`")?;
+        debug!("📸 Opened PIT on '{}' (slice {:?})", the_index, self.slice);
+        Ok(the_pit.id)
+    }
+
+    /// 🔎 One PIT page: `POST /_search` with pit, sort, search_after, slice. Renews keep_alive.
+    async fn search_pit(&mut self, query: &serde_json::Value) -> Result<String> {
+        let the_pit_id = match self.pit_id.clone() {
+            Some(the_pit_id) => the_pit_id,
+            None => {
+                let the_pit_id = self.open_pit().await?;
+                self.pit_id = Some(the_pit_id.clone());
+                the_pit_id
+            }
+        };
+        let mut the_body = serde_json::json!({
+            "size": self.config.tuning().max_batch_size_docs,
+            "sort": [{ "_shard_doc": "asc" }],
+            "query": query,
+            "pit": { "id": the_pit_id, "keep_alive": self.config.keep_alive },
+            "track_total_hits": false,
+        });
+        if let Some(ref the_search_after) = self.search_after {
+            the_body["search_after"] = the_search_after.clone();
+        }
+        if let Some(the_filter) = self.config.source_filter() {
+            the_body["_source"] = the_filter;
+        }
+        if let Some(the_slice) = self.slice {
+            the_body["slice"] = serde_json::json!({ "id": the_slice.id, "max": the_slice.max });
+        }
+        if self.versions {
+            the_body["version"] = serde_json::json!(true);
+        }
+        let the_url = format!("{}/_search", self.base_url());
+        self.send_search(self.client.post(&the_url), the_body).await
+    }
+
+    /// 🧹 Release the point-in-time. Best effort — it expires on its own anyway.
+    async fn close_pit(&self, pit_id: &str) {
+        let the_url = format!("{}/_pit", self.base_url());
+        let the_result = self
+            .authed(self.client.delete(&the_url))
+            .header("Content-Type", "application/json")
+            .body(serde_json::json!({ "id": pit_id }).to_string())
+            .send()
+            .await;
+        match the_result {
+            Ok(_) => debug!("🧹 Closed PIT (slice {:?})", self.slice),
+            Err(the_shrug) => warn!(
+                "⚠️ Couldn't close the point-in-time (it'll expire in {}): {}",
+                self.config.keep_alive, the_shrug
+            ),
+        }
+    }
+
+    /// 🚪 First page of a scroll: `POST /{index}/_search?scroll=..` with size, sort, slice.
+    async fn open_scroll(&self, query: &serde_json::Value) -> Result<String> {
+        let the_url = match self.current_index {
+            Some(ref the_index) => format!("{}/{}/_search?scroll={}", self.base_url(), the_index, self.config.keep_alive),
+            None => format!("{}/_search?scroll={}", self.base_url(), self.config.keep_alive),
+        };
+        let mut the_body = serde_json::json!({
+            "size": self.config.tuning().max_batch_size_docs,
+            "sort": ["_doc"],
+            "query": query,
+        });
+        // -- ✂️ only the first request carries it: later scroll pages reuse the search as opened
+        if let Some(the_filter) = self.config.source_filter() {
+            the_body["_source"] = the_filter;
+        }
+        if let Some(the_slice) = self.slice {
+            the_body["slice"] = serde_json::json!({ "id": the_slice.id, "max": the_slice.max });
+        }
+        if self.versions {
+            the_body["version"] = serde_json::json!(true);
+        }
+        debug!("📡 Opening scroll on {} (slice {:?})", the_url, self.slice);
+        self.send_search(self.client.post(&the_url), the_body).await
+    }
+
+    /// ➡️ Next page of the open scroll.
+    async fn continue_scroll(&self, scroll_id: &str) -> Result<String> {
+        let the_url = format!("{}/_search/scroll", self.base_url());
+        let the_body = serde_json::json!({ "scroll": self.config.keep_alive, "scroll_id": scroll_id });
+        self.send_search(self.client.post(&the_url), the_body).await
+    }
+
+    async fn send_search(&self, request: reqwest::RequestBuilder, body: serde_json::Value) -> Result<String> {
+        let the_response = self
+            .authed(request)
+            .header("Content-Type", "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .context("💀 The search request never reached the source cluster")?;
+        let the_status = the_response.status();
+        let the_text = the_response
+            .text()
+            .await
+            .context("💀 The source cluster hung up halfway through a page")?;
+        if !the_status.is_success() {
+            anyhow::bail!("💀 The source cluster answered the search with {}: '{}'", the_status, the_text);
+        }
+        Ok(the_text)
+    }
+
+    /// 🧹 Release a spent scroll context. Best effort — it expires on its own anyway.
+    async fn clear_scroll(&self, scroll_id: &str) {
+        let the_url = format!("{}/_search/scroll", self.base_url());
+        let the_result = self
+            .authed(self.client.delete(&the_url))
+            .header("Content-Type", "application/json")
+            .body(serde_json::json!({ "scroll_id": [scroll_id] }).to_string())
+            .send()
+            .await;
+        if let Err(the_shrug) = the_result {
+            warn!("⚠️ Couldn't clear a scroll context (it'll expire in {}): {}", self.config.keep_alive, the_shrug);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::CommonSourceConfig;
+    use super::super::config::Compat;
+    use super::super::time_slice::TimeSliceConfig;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn make_config(url: &str) -> ElasticsearchSourceConfig {
+        ElasticsearchSourceConfig {
+            url: url.to_string(),
+            username: None,
+            password: None,
+            password_file: None,
+            api_key: None,
+            api_key_file: None,
+            index: Some("people".to_string()),
+            slices: None,
+            query: None,
+            source_includes: Vec::new(),
+            source_excludes: Vec::new(),
+            common_config: CommonSourceConfig::default(),
+            time_slice: None,
+            resume_after: None,
+            pagination: Default::default(),
+            compat: Default::default(),
+            keep_alive: "5m".to_string(),
+            connection: Default::default(),
+        }
+    }
+
+    fn a_scroll_config(url: &str) -> ElasticsearchSourceConfig {
+        ElasticsearchSourceConfig { pagination: Pagination::Scroll, ..make_config(url) }
+    }
+
+    fn a_pit_page(pit_id: &str, ids: &[(&str, u64)]) -> serde_json::Value {
+        let the_hits: Vec<_> = ids
+            .iter()
+            .map(|(id, sort)| serde_json::json!({ "_index": "people", "_id": id, "_source": { "name": id }, "sort": [sort] }))
+            .collect();
+        serde_json::json!({ "pit_id": pit_id, "hits": { "hits": the_hits } })
+    }
+
+    /// 🧪 Open PIT → page → search_after the last sort → dry → close the (rotated) PIT.
+    #[tokio::test]
+    async fn the_one_where_the_snapshot_is_read_and_released() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_pit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "pit-1" })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(serde_json::json!({ "search_after": [8], "pit": { "id": "pit-2", "keep_alive": "5m" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_pit_page("pit-2", &[])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(serde_json::json!({ "pit": { "id": "pit-1" }, "sort": [{ "_shard_doc": "asc" }] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_pit_page("pit-2", &[("1", 7), ("2", 8)])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_pit"))
+            .and(body_partial_json(serde_json::json!({ "id": "pit-2" })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut the_source = ElasticsearchSource::new(make_config(&mock_server.uri())).await?;
+        let the_page = the_source.pump().await?.expect("🎯 one page of hits");
+        assert!(the_page.0.contains("\"_id\":\"2\""));
+        assert!(the_source.pump().await?.is_none());
+        assert!(the_source.pump().await?.is_none(), "🎯 the PIT is only closed once");
+        Ok(())
+    }
+
+    /// 🧪 A list with a pattern in it is resolved, sorted, and read one index (and one PIT) at a time.
+    #[tokio::test]
+    async fn the_one_where_every_log_gets_its_turn() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_cat/indices/logs-*,audit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "index": "logs-b" }, { "index": "audit" }, { "index": "logs-a" }
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        for the_index in ["audit", "logs-a", "logs-b"] {
+            let the_pit = format!("pit-{}", the_index);
+            Mock::given(method("POST"))
+                .and(path(format!("/{}/_pit", the_index)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": the_pit })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/_search"))
+                .and(body_partial_json(serde_json::json!({ "pit": { "id": the_pit }, "search_after": [1] })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(a_pit_page(&the_pit, &[])))
+                .with_priority(1)
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/_search"))
+                .and(body_partial_json(serde_json::json!({ "pit": { "id": the_pit } })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(a_pit_page(&the_pit, &[(the_index, 1)])))
+                .with_priority(2)
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("DELETE"))
+                .and(path("/_pit"))
+                .and(body_partial_json(serde_json::json!({ "id": the_pit })))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let the_config: ElasticsearchSourceConfig =
+            toml::from_str(&format!("url = \"{}\"\nindex = [\"logs-*\", \"audit\"]", mock_server.uri()))?;
+        assert_eq!(the_config.index.as_deref(), Some("logs-*,audit"));
+        let mut the_source = ElasticsearchSource::new(the_config).await?;
+        for the_index in ["audit", "logs-a", "logs-b"] {
+            let the_page = the_source.pump().await?.expect("🎯 one page per index");
+            assert!(the_page.0.contains(&format!("\"_id\":\"{}\"", the_index)), "🎯 {} comes in name order", the_index);
+        }
+        assert!(the_source.pump().await?.is_none());
+        Ok(())
+    }
+
+    /// 🧪 Time windows are counted in one request, OR'ed together.
+    #[tokio::test]
+    async fn the_one_where_the_headcount_comes_first() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_count"))
+            .and(body_partial_json(serde_json::json!({ "query": { "bool": { "minimum_should_match": 1 } } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "count": 1234 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_config = ElasticsearchSourceConfig {
+            time_slice: Some(TimeSliceConfig {
+                field: "@timestamp".to_string(),
+                start: "2024-01-01".to_string(),
+                end: "2024-01-03".to_string(),
+                window: "1d".to_string(),
+                checkpoint_file: None,
+                window_retries: 2,
+            }),
+            ..make_config(&mock_server.uri())
+        };
+        let the_source = ElasticsearchSource::new(the_config).await?;
+        assert_eq!(the_source.time_windows.len(), 2);
+        assert_eq!(the_source.count_docs().await?, 1234);
+        Ok(())
+    }
+
+    /// 🧪 A pasted search body is unwrapped to its clause, and each window narrows it to its dates.
+    #[tokio::test]
+    async fn the_one_where_only_the_active_ones_move() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_search"))
+            .and(body_partial_json(serde_json::json!({
+                "query": { "bool": { "must": [{ "term": { "status": "active" } }] } }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_page("sq", &[])))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_search/scroll"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let the_config = ElasticsearchSourceConfig {
+            query: Some(serde_json::json!(r#"{"query": {"term": {"status": "active"}}}"#)),
+            time_slice: Some(TimeSliceConfig {
+                field: "@timestamp".to_string(),
+                start: "2024-01-01".to_string(),
+                end: "2024-01-03".to_string(),
+                window: "1d".to_string(),
+                checkpoint_file: None,
+                window_retries: 2,
+            }),
+            ..a_scroll_config(&mock_server.uri())
+        };
+        let mut the_source = ElasticsearchSource::new(the_config).await?;
+        assert!(the_source.pump().await?.is_none(), "🎯 both windows asked, both empty");
+
+        let the_err = ElasticsearchSource::new(ElasticsearchSourceConfig {
+            query: Some(serde_json::json!("status:active")),
+            ..make_config("http://localhost:1")
+        })
+        .await
+        .unwrap_err();
+        assert!(format!("{:#}", the_err).contains("not a JSON query"));
+        Ok(())
+    }
+
+    /// 🧪 A window that fails mid-read is started over with a fresh PIT; each finished window
+    /// lands in the checkpoint, and a rerun only reads (and counts) what's left.
+    #[tokio::test]
+    async fn the_one_where_tuesday_gets_a_second_chance() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_checkpoint = the_dir.path().join("windows.json");
+        let the_slice = TimeSliceConfig {
+            field: "@timestamp".to_string(),
+            start: "2024-01-01".to_string(),
+            end: "2024-01-04".to_string(),
+            window: "1d".to_string(),
+            checkpoint_file: Some(the_checkpoint.clone()),
+            window_retries: 1,
+        };
+        let the_range = |the_day: &str| serde_json::json!({ "query": { "range": { "@timestamp": { "gte": the_day } } } });
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_pit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "pit-1" })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE")).and(path("/_pit")).respond_with(ResponseTemplate::new(200)).mount(&mock_server).await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(the_range("2024-01-02T00:00:00Z")))
+            .respond_with(ResponseTemplate::new(503).set_body_string("🔥 node left"))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_pit_page("pit-1", &[])))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let the_config = ElasticsearchSourceConfig { time_slice: Some(the_slice.clone()), ..make_config(&mock_server.uri()) };
+        let mut the_source = ElasticsearchSource::new(the_config.clone()).await?;
+        let the_metrics = Arc::new(DrainMetrics::new());
+        the_source.report_to(&the_metrics);
+        assert!(the_source.pump().await?.is_none(), "🎯 three empty windows, one of them twice");
+        assert_eq!(the_metrics.windows_done.load(std::sync::atomic::Ordering::Relaxed), 3);
+        let the_saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&the_checkpoint)?)?;
+        assert_eq!(the_saved["done"], 3);
+
+        // -- ⏭️ pretend the last run died after Tuesday: only Wednesday is counted and read
+        std::fs::write(&the_checkpoint, the_saved.to_string().replace("\"done\":3", "\"done\":2"))?;
+        let the_rerun = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_count"))
+            .and(body_partial_json(the_range("2024-01-03T00:00:00Z")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "count": 7 })))
+            .expect(1)
+            .mount(&the_rerun)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/people/_pit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "pit-2" })))
+            .mount(&the_rerun)
+            .await;
+        Mock::given(method("DELETE")).and(path("/_pit")).respond_with(ResponseTemplate::new(200)).mount(&the_rerun).await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(the_range("2024-01-03T00:00:00Z")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_pit_page("pit-2", &[])))
+            .expect(1)
+            .mount(&the_rerun)
+            .await;
+        let mut the_rest = ElasticsearchSource::new(ElasticsearchSourceConfig { url: the_rerun.uri(), ..the_config.clone() }).await?;
+        assert_eq!(the_rest.count_docs().await?, 7);
+        assert!(the_rest.pump().await?.is_none());
+
+        // -- 💀 out of retries: the failure stands, and says which window
+        std::fs::remove_file(&the_checkpoint)?;
+        let the_outage = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_pit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "pit-3" })))
+            .mount(&the_outage)
+            .await;
+        Mock::given(method("DELETE")).and(path("/_pit")).respond_with(ResponseTemplate::new(200)).mount(&the_outage).await;
+        Mock::given(method("POST")).and(path("/_search")).respond_with(ResponseTemplate::new(503)).mount(&the_outage).await;
+        let mut the_doomed = ElasticsearchSource::new(ElasticsearchSourceConfig {
+            url: the_outage.uri(),
+            time_slice: Some(TimeSliceConfig { window_retries: 0, ..the_slice }),
+            ..the_config
+        })
+        .await?;
+        let the_err = format!("{:#}", the_doomed.pump().await.unwrap_err());
+        assert!(the_err.contains("Window 0 (2024-01-01T00:00:00Z → 2024-01-02T00:00:00Z) failed 1 time(s)"), "{the_err}");
+        assert!(!the_checkpoint.exists(), "💾 nothing finished, nothing recorded");
+        Ok(())
+    }
+
+    /// 🧪 PIT needs to know which indices to freeze.
+    #[tokio::test]
+    async fn the_one_where_you_cant_snapshot_everything() {
+        let the_err = ElasticsearchSource::new(ElasticsearchSourceConfig { index: None, ..make_config("http://localhost:1") })
+            .await
+            .unwrap_err();
+        assert!(format!("{}", the_err).contains("needs a source `index`"));
+    }
+
+    fn a_page(scroll_id: &str, ids: &[&str]) -> serde_json::Value {
+        let the_hits: Vec<_> = ids
+            .iter()
+            .map(|id| serde_json::json!({ "_index": "people", "_id": id, "_source": { "name": id } }))
+            .collect();
+        serde_json::json!({ "_scroll_id": scroll_id, "hits": { "hits": the_hits } })
+    }
+
+    /// 🧪 Scroll mode: open, scroll, run dry, clear — then `None` forever after.
+    #[tokio::test]
+    async fn the_one_where_the_scroll_runs_dry() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_page("s1", &["1", "2"])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search/scroll"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_page("s1", &[])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_search/scroll"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut the_source = ElasticsearchSource::new(a_scroll_config(&mock_server.uri())).await?;
+        let the_page = the_source.pump().await?.expect("🎯 one page of hits");
+        assert!(the_page.0.contains("\"_id\":\"2\""));
+        assert!(the_source.pump().await?.is_none());
+        assert!(the_source.pump().await?.is_none());
+        Ok(())
+    }
+
+    /// 🧪 A sliced source asks for its own slice and nobody else's.
+    #[tokio::test]
+    async fn the_one_where_everyone_gets_their_own_slice() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_search"))
+            .and(body_partial_json(serde_json::json!({ "slice": { "id": 2, "max": 4 }, "sort": ["_doc"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_page("s2", &[])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_search/scroll"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let mut the_source = ElasticsearchSource::new(a_scroll_config(&mock_server.uri()))
+            .await?
+            .with_slice(2, 4);
+        assert!(the_source.pump().await?.is_none(), "🎯 an empty slice is still a finished slice");
+        Ok(())
+    }
+
+    /// 🧪 The `_source` filter rides on the search itself — the blob stays on the source cluster.
+    #[tokio::test]
+    async fn the_one_where_the_blob_stays_home() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_search"))
+            .and(body_partial_json(serde_json::json!({
+                "_source": { "includes": ["name", "address.*"], "excludes": ["address.raw_html"] }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_page("s3", &[])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_search/scroll"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let the_config = ElasticsearchSourceConfig {
+            source_includes: vec!["name".to_string(), "address.*".to_string()],
+            source_excludes: vec!["address.raw_html".to_string()],
+            ..a_scroll_config(&mock_server.uri())
+        };
+        assert!(make_config("http://localhost:1").source_filter().is_none(), "🎯 no filter, no clause");
+        let mut the_source = ElasticsearchSource::new(the_config).await?;
+        assert!(the_source.pump().await?.is_none());
+        Ok(())
+    }
+
+    /// 🧪 compat = "es6" scrolls even though `pagination` still says PIT — 6.x has no PIT to open.
+    #[tokio::test]
+    async fn the_one_where_the_archive_only_knows_how_to_scroll() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(serde_json::json!({ "sort": ["_doc"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(a_page("s6", &[])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_search/scroll"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        // -- 📦 no `index` either: fine for a scroll, refused for a PIT
+        let the_config = ElasticsearchSourceConfig { compat: Compat::Es6, index: None, ..make_config(&mock_server.uri()) };
+        assert_eq!(the_config.effective_pagination(), Pagination::Scroll);
+        let mut the_source = ElasticsearchSource::new(the_config).await?;
+        assert!(the_source.pump().await?.is_none());
+        Ok(())
     }
 }
@@ -0,0 +1,347 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🏗️ Index setup — build the target index before the first document knocks.
+//!
+//! 🎬 *[ES→ES migration, hour three. 40 million docs landed.]*
+//! *["why is `price` a text field?"]*
+//! *[dynamic mapping guessed. dynamic mapping always guesses. it guessed wrong.]*
+//!
+//! With `[sink_config.Elasticsearch.copy_index]`, `run()` reads the source index's mapping
+//! and settings and creates the target index from them — so the target looks like the
+//! source *before* dynamic mapping gets a vote.
+//!
+//! 🧠 Knowledge graph:
+//! - `fetch_source_blueprint` → `GET /{index}/_mapping` + `GET /{index}/_settings` on the source.
+//!   An alias or pattern resolves to several concrete indices; the first one is the template
+//! - Settings ES manages itself (`uuid`, `creation_date`, `provided_name`, `version`,
+//!   `routing`, `resize`, `blocks`) are stripped — a create request carrying them is refused
+//! - `number_of_shards` / `number_of_replicas` can be overridden (the target cluster rarely
+//!   has the source's topology)
+//! - `create_index` → `HEAD` then `PUT /{index}`. A target that already exists is left
+//!   alone: we never rewrite somebody's mapping behind their back 🦆
+//! - Runs once, in `lib.rs::run()`, before the sinks are built (the sink's existence check
+//!   would otherwise bail on the index we were about to create)
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use super::config::{ElasticsearchSinkConfig, ElasticsearchSourceConfig};
+
+/// 🧹 Index settings that describe *that* index, not *an* index. ES refuses them on create.
+const UNCOPYABLE_SETTINGS: &[&str] = &["uuid", "creation_date", "provided_name", "version", "routing", "resize", "blocks"];
+
+/// 🏗️ `[sink_config.Elasticsearch.copy_index]` — its presence turns the pre-step on.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct CopyIndexConfig {
+    /// 🧩 Primary shard count for the target. `None` keeps the source's.
+    #[serde(default)]
+    pub number_of_shards: Option<u32>,
+    /// 👯 Replica count for the target. `None` keeps the source's. `0` during a bulk load,
+    /// then raised afterwards, is the classic speed trick.
+    #[serde(default)]
+    pub number_of_replicas: Option<u32>,
+}
+
+/// 📋 What `create_index` did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexSetupOutcome {
+    Created,
+    /// 🚪 The index was already there — untouched
+    AlreadyExists,
+}
+
+#[derive(Deserialize)]
+struct MappingEntry {
+    #[serde(default)]
+    mappings: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct SettingsEntry {
+    settings: serde_json::Value,
+}
+
+// -- 🔒 same auth pecking order as everywhere else: api_key beats basic auth
+pub(super) fn authed(
+    request: reqwest::RequestBuilder,
+    api_key: &Option<String>,
+    username: &Option<String>,
+    password: &Option<String>,
+) -> reqwest::RequestBuilder {
+    if let Some(api_key) = api_key {
+        request.header("Authorization", format!("ApiKey {}", api_key))
+    } else if let Some(username) = username {
+        request.basic_auth(username, password.as_ref())
+    } else {
+        request
+    }
+}
+
+/// 📐 The source index's mapping and settings, as a ready-to-PUT create body:
+/// `{"settings": {"index": {..}}, "mappings": {..}}`.
+pub async fn fetch_source_blueprint(source: &ElasticsearchSourceConfig, copy: &CopyIndexConfig) -> Result<serde_json::Value> {
+    let Some(ref the_index) = source.index else {
+        anyhow::bail!("💀 copy_index needs a source `index` — there's no single mapping to copy from 'every index'.");
+    };
+    let the_base = format!("{}/{}", source.url.trim_end_matches('/'), the_index);
+    let the_client = source.timeouts().apply(source.connection.client_builder()?).build()?;
+    let get = |what: &'static str| {
+        let the_request = authed(the_client.get(format!("{}/{}", the_base, what)), &source.api_key, &source.username, &source.password);
+        async move {
+            let the_response = the_request
+                .send()
+                .await
+                .with_context(|| format!("💀 Couldn't reach the source cluster to read the {}", what))?;
+            let the_status = the_response.status();
+            let the_body = the_response.text().await.unwrap_or_default();
+            if !the_status.is_success() {
+                anyhow::bail!("💀 Reading the source {} of '{}' failed with {}: '{}'", what, the_index, the_status, the_body);
+            }
+            Ok::<_, anyhow::Error>(the_body)
+        }
+    };
+
+    // -- 📦 both responses are keyed by concrete index name; an alias may fan out to several
+    let the_mappings: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&get("_mapping").await?).context("💀 The source _mapping response wasn't an object")?;
+    let the_settings: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&get("_settings").await?).context("💀 The source _settings response wasn't an object")?;
+    let Some((the_template_index, the_mapping)) = the_mappings.into_iter().next() else {
+        anyhow::bail!("💀 The source index '{}' resolved to no indices at all", the_index);
+    };
+    let the_mapping: MappingEntry = serde_json::from_value(the_mapping).context("💀 The source mapping had an unexpected shape")?;
+    let the_settings: SettingsEntry = serde_json::from_value(
+        the_settings
+            .get(&the_template_index)
+            .cloned()
+            .with_context(|| format!("💀 The source _settings had no entry for '{}'", the_template_index))?,
+    )
+    .context("💀 The source settings had an unexpected shape")?;
+    debug!("📐 Using '{}' as the blueprint for the target index", the_template_index);
+
+    let mut the_index_settings = the_settings.settings.get("index").cloned().unwrap_or_else(|| serde_json::json!({}));
+    if let Some(the_object) = the_index_settings.as_object_mut() {
+        for the_key in UNCOPYABLE_SETTINGS {
+            the_object.remove(*the_key);
+        }
+        if let Some(the_shards) = copy.number_of_shards {
+            the_object.insert("number_of_shards".to_string(), the_shards.to_string().into());
+        }
+        if let Some(the_replicas) = copy.number_of_replicas {
+            the_object.insert("number_of_replicas".to_string(), the_replicas.to_string().into());
+        }
+    }
+    Ok(serde_json::json!({ "settings": { "index": the_index_settings }, "mappings": the_mapping.mappings }))
+}
+
+/// 🏗️ Create the sink's `index` from `body` — unless it already exists, in which case hands off.
+pub async fn create_index(sink: &ElasticsearchSinkConfig, body: &serde_json::Value) -> Result<IndexSetupOutcome> {
+    let Some(the_index) = sink.static_index() else {
+        anyhow::bail!("💀 Creating the target index needs a static sink `index` — with per-doc routing there's no one index to build.");
+    };
+    let the_url = format!("{}/{}", sink.url.trim_end_matches('/'), the_index);
+    let the_client = sink.timeouts().apply(sink.connection.client_builder()?).build()?;
+
+    let the_probe = authed(the_client.head(&the_url), &sink.api_key, &sink.username, &sink.password)
+        .send()
+        .await
+        .context("💀 Couldn't reach the target cluster to check for the index")?;
+    if the_probe.status().is_success() {
+        info!("🚪 Target index '{}' already exists — leaving its mapping and settings alone", the_index);
+        return Ok(IndexSetupOutcome::AlreadyExists);
+    }
+
+    let the_response = authed(the_client.put(&the_url), &sink.api_key, &sink.username, &sink.password)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .context("💀 The create-index request never reached the target cluster")?;
+    let the_status = the_response.status();
+    if !the_status.is_success() {
+        let the_body = the_response.text().await.unwrap_or_default();
+        anyhow::bail!("💀 Creating target index '{}' failed with {}: '{}'", the_index, the_status, the_body);
+    }
+    info!("🏗️ Created target index '{}'", the_index);
+    Ok(IndexSetupOutcome::Created)
+}
+
+/// 📐 The whole `copy_index` pre-step: read the source blueprint, build the target from it.
+pub async fn copy_index_from_source(
+    source: &ElasticsearchSourceConfig,
+    sink: &ElasticsearchSinkConfig,
+    copy: &CopyIndexConfig,
+) -> Result<IndexSetupOutcome> {
+    let the_blueprint = fetch_source_blueprint(source, copy).await?;
+    create_index(sink, &the_blueprint).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{CommonSinkConfig, CommonSourceConfig};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn a_source(url: &str) -> ElasticsearchSourceConfig {
+        ElasticsearchSourceConfig {
+            url: url.to_string(),
+            username: None,
+            password: None,
+            password_file: None,
+            api_key: None,
+            api_key_file: None,
+            index: Some("products".to_string()),
+            slices: None,
+            query: None,
+            source_includes: Vec::new(),
+            source_excludes: Vec::new(),
+            common_config: CommonSourceConfig::default(),
+            time_slice: None,
+            resume_after: None,
+            pagination: Default::default(),
+            compat: Default::default(),
+            keep_alive: "5m".to_string(),
+            connection: Default::default(),
+        }
+    }
+
+    fn a_sink(url: &str) -> ElasticsearchSinkConfig {
+        ElasticsearchSinkConfig {
+            url: url.to_string(),
+            username: None,
+            password: None,
+            password_file: None,
+            api_key: None,
+            api_key_file: None,
+            index: Some("products-v2".to_string()),
+            common_config: CommonSinkConfig::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
+        }
+    }
+
+    async fn mount_source_blueprint(mock_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/products/_mapping"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "products-000001": { "mappings": { "properties": { "price": { "type": "scaled_float", "scaling_factor": 100 } } } }
+            })))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/products/_settings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "products-000001": { "settings": { "index": {
+                    "number_of_shards": "5", "number_of_replicas": "2", "refresh_interval": "30s",
+                    "uuid": "abc", "creation_date": "1700000000000", "provided_name": "products-000001",
+                    "version": { "created": "8110099" }
+                } } }
+            })))
+            .mount(mock_server)
+            .await;
+    }
+
+    /// 🧪 Mapping copied, house-keeping settings stripped, replicas overridden.
+    #[tokio::test]
+    async fn the_one_where_the_target_is_built_from_the_blueprint() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_source_blueprint(&mock_server).await;
+        Mock::given(method("HEAD"))
+            .and(path("/products-v2"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/products-v2"))
+            .and(body_partial_json(serde_json::json!({
+                "settings": { "index": { "number_of_shards": "5", "number_of_replicas": "0", "refresh_interval": "30s" } },
+                "mappings": { "properties": { "price": { "type": "scaled_float" } } }
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_copy = CopyIndexConfig { number_of_shards: None, number_of_replicas: Some(0) };
+        let the_blueprint = fetch_source_blueprint(&a_source(&mock_server.uri()), &the_copy).await?;
+        assert!(the_blueprint["settings"]["index"].get("uuid").is_none(), "🎯 uuid belongs to the source index");
+        assert!(the_blueprint["settings"]["index"].get("version").is_none());
+        let the_outcome = copy_index_from_source(&a_source(&mock_server.uri()), &a_sink(&mock_server.uri()), &the_copy).await?;
+        assert_eq!(the_outcome, IndexSetupOutcome::Created);
+        Ok(())
+    }
+
+    /// 🧪 `create_index_if_missing` with an inline block from TOML — shipped as JSON, untouched.
+    #[tokio::test]
+    async fn the_one_where_the_first_load_brings_its_own_blueprint() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/products-v2"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/products-v2"))
+            .and(body_partial_json(serde_json::json!({
+                "settings": { "number_of_shards": 1 },
+                "mappings": { "properties": { "price": { "type": "float" } } }
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_inline: ElasticsearchSinkConfig = toml::from_str(&format!(
+            "url = \"{}\"\nindex = \"products-v2\"\ncreate_index_if_missing = true\n\
+             settings = {{ number_of_shards = 1 }}\nmappings = {{ properties = {{ price = {{ type = \"float\" }} }} }}",
+            mock_server.uri()
+        ))?;
+        assert!(the_inline.create_index_if_missing);
+        assert_eq!(create_index(&the_inline, &the_inline.inline_index_body()).await?, IndexSetupOutcome::Created);
+        Ok(())
+    }
+
+    /// 🧪 An existing target is never PUT over.
+    #[tokio::test]
+    async fn the_one_where_the_house_was_already_built() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        mount_source_blueprint(&mock_server).await;
+        Mock::given(method("HEAD"))
+            .and(path("/products-v2"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let the_outcome =
+            copy_index_from_source(&a_source(&mock_server.uri()), &a_sink(&mock_server.uri()), &CopyIndexConfig::default()).await?;
+        assert_eq!(the_outcome, IndexSetupOutcome::AlreadyExists);
+        Ok(())
+    }
+}
@@ -9,10 +9,28 @@
 //!
 //! 🦆 mandatory duck, as decreed by repository law.
 
+pub mod blue_green;
+pub mod bulk_response;
+pub mod cluster_version;
 pub mod config;
+pub mod connection;
+pub mod duplicate_check;
+pub mod index_setup;
 mod elasticsearch_sink;
 mod elasticsearch_source;
+pub mod resume;
+pub mod time_slice;
+pub mod write_alias;
 
-pub use config::{ElasticsearchSinkConfig, ElasticsearchSourceConfig};
+pub use blue_green::BlueGreen;
+pub use bulk_response::{BulkItemFailure, DocumentFailurePolicy, parse_bulk_failures};
+pub use cluster_version::{ClusterVersion, Distribution};
+pub use connection::{ConnectionConfig, HttpVersion};
+pub use config::{Compat, ElasticsearchSinkConfig, ElasticsearchSourceConfig, Pagination, RefreshPolicy};
+pub use duplicate_check::{DuplicateCheckConfig, DuplicateReport, check_for_duplicates};
+pub use index_setup::{CopyIndexConfig, IndexSetupOutcome, copy_index_from_source, create_index};
+pub use resume::{ResumeAfterConfig, Watermark};
+pub use time_slice::{TimeSliceConfig, TimeWindow, WindowLedger};
+pub use write_alias::{WriteTarget, resolve_write_target};
 pub use elasticsearch_sink::ElasticsearchSink;
-pub use elasticsearch_source::ElasticsearchSource;
+pub use elasticsearch_source::{ElasticsearchSource, SliceSpec};
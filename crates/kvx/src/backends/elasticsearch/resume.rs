@@ -0,0 +1,276 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! ⏭️ Incremental reads — each run only reads what changed since the last one that succeeded.
+//!
+//! 🎬 *[02:00. the nightly sync starts. it re-reads 90 million products.]*
+//! *[3,112 of them changed since yesterday.]*
+//! *[06:40. it finishes. the morning traffic arrives to a cluster still catching its breath.]*
+//!
+//! With `[source_config.Elasticsearch.resume_after]`, the read is narrowed to
+//! `after < field <= up_to`:
+//! - `after` is the high-water mark the last successful run left in `state_file`
+//!   (none on the first run, which reads everything)
+//! - `up_to` is the field's `max`, taken once before the read starts
+//!
+//! When the run succeeds, `up_to` becomes the next run's `after`. 🦆
+//!
+//! 🧠 Knowledge graph:
+//! - `Watermark::plan` runs once, in `lib.rs::prepare_source`, before the sources are built.
+//!   `narrow` folds the range into the source config's `query`, so every slice, every page and
+//!   `count_docs` see the same bounds
+//! - Fixing `up_to` up front keeps a busy index from moving the finish line mid-read. Docs
+//!   changed during the run land above it and are picked up next time
+//! - `save` writes `state_file` only after a run that finished — a failed or interrupted run
+//!   leaves the old mark, so the next run reads those changes again (idempotent with `_id`s)
+//! - The field has to grow whenever a doc changes (`updated_at`). `_seq_no` only grows per
+//!   shard, so it's only a safe watermark on a single-shard index
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::config::ElasticsearchSourceConfig;
+use super::index_setup::authed;
+
+/// 🏷️ The aggregation's name in the `_search` we send — ours, so it can't clash.
+const WATERMARK_AGG: &str = "kvx_watermark";
+
+/// ⏭️ `[source_config.Elasticsearch.resume_after]` — read only docs past the last run's mark.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResumeAfterConfig {
+    /// 📈 A field that grows whenever a doc changes: `updated_at`, a version counter, ...
+    pub field: String,
+    /// 💾 Where the high-water mark is kept between runs (JSON). Missing = first run, read everything.
+    pub state_file: PathBuf,
+}
+
+/// 💾 What `state_file` holds.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedMark {
+    field: String,
+    after: serde_json::Value,
+}
+
+/// 📏 This run's bounds on `field`: `after < field <= up_to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub field: String,
+    /// ⏮️ The last successful run's mark, exclusive. `None` on the first run.
+    pub after: Option<serde_json::Value>,
+    /// ⏭️ The field's max when this run started, inclusive. `None` when nothing is past `after`.
+    pub up_to: Option<serde_json::Value>,
+    pub state_file: PathBuf,
+}
+
+impl Watermark {
+    /// 📋 Load the last mark and take the field's current max. `None` without `resume_after`.
+    pub async fn plan(source: &ElasticsearchSourceConfig) -> Result<Option<Self>> {
+        let Some(ref the_resume) = source.resume_after else {
+            return Ok(None);
+        };
+        if source.time_slice.is_some() {
+            anyhow::bail!(
+                "💀 resume_after and time_slice both pick which dates to read — use one. \
+                 resume_after moves with every run; time_slice is a fixed range."
+            );
+        }
+        let the_after = match tokio::fs::read_to_string(&the_resume.state_file).await {
+            Ok(the_text) => {
+                let the_mark: SavedMark = serde_json::from_str(&the_text).with_context(|| {
+                    format!("💀 '{}' isn't a resume_after state file", the_resume.state_file.display())
+                })?;
+                if the_mark.field != the_resume.field {
+                    anyhow::bail!(
+                        "💀 '{}' holds a mark for '{}', but resume_after.field is '{}'. \
+                         Delete the file to start over from a full read.",
+                        the_resume.state_file.display(),
+                        the_mark.field,
+                        the_resume.field
+                    );
+                }
+                Some(the_mark.after)
+            }
+            Err(the_err) if the_err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(the_err) => {
+                return Err(the_err)
+                    .with_context(|| format!("💀 Couldn't read the resume_after state file '{}'", the_resume.state_file.display()));
+            }
+        };
+        let mut the_plan =
+            Self { field: the_resume.field.clone(), after: the_after, up_to: None, state_file: the_resume.state_file.clone() };
+        the_plan.up_to = the_plan.current_max(source).await?;
+        match (&the_plan.after, &the_plan.up_to) {
+            (None, _) => info!("⏭️ No mark in '{}' yet — reading everything", the_plan.state_file.display()),
+            (Some(the_after), Some(the_up_to)) => {
+                info!("⏭️ Reading '{}' in ({}, {}]", the_plan.field, the_after, the_up_to)
+            }
+            (Some(the_after), None) => info!("⏭️ Nothing past {} on '{}' yet", the_after, the_plan.field),
+        }
+        Ok(Some(the_plan))
+    }
+
+    /// 🔎 The `range` clause for this run, or `None` when there are no bounds (first run, empty index).
+    pub fn range_query(&self) -> Option<serde_json::Value> {
+        let mut the_bounds = serde_json::Map::new();
+        if let Some(ref the_after) = self.after {
+            the_bounds.insert("gt".to_string(), the_after.clone());
+        }
+        if let Some(ref the_up_to) = self.up_to {
+            the_bounds.insert("lte".to_string(), the_up_to.clone());
+        }
+        if the_bounds.is_empty() {
+            return None;
+        }
+        Some(serde_json::json!({ "range": { self.field.as_str(): the_bounds } }))
+    }
+
+    /// 🔎 AND this run's range onto the source's own query clause (`None` = match all).
+    pub fn narrow(&self, query: Option<serde_json::Value>) -> Option<serde_json::Value> {
+        match (query, self.range_query()) {
+            (the_query, None) => the_query,
+            (None, Some(the_range)) => Some(the_range),
+            (Some(the_query), Some(the_range)) => {
+                Some(serde_json::json!({ "bool": { "must": [the_query], "filter": [the_range] } }))
+            }
+        }
+    }
+
+    /// 💾 Make `up_to` the next run's starting mark. Call only after a run that finished.
+    /// Returns `false` when there was nothing new, so the old mark stands.
+    pub async fn save(&self) -> Result<bool> {
+        let Some(ref the_up_to) = self.up_to else {
+            return Ok(false);
+        };
+        let the_mark = SavedMark { field: self.field.clone(), after: the_up_to.clone() };
+        // -- ✍️ write-then-rename: a crash mid-write leaves the old mark, never half a new one
+        let the_partial = self.state_file.with_extension("partial");
+        tokio::fs::write(&the_partial, serde_json::to_string_pretty(&the_mark)?)
+            .await
+            .with_context(|| format!("💀 Couldn't write the resume_after mark to '{}'", the_partial.display()))?;
+        tokio::fs::rename(&the_partial, &self.state_file)
+            .await
+            .with_context(|| format!("💀 Couldn't move the resume_after mark into '{}'", self.state_file.display()))?;
+        info!("💾 Next run reads '{}' after {}", self.field, the_up_to);
+        Ok(true)
+    }
+
+    /// 📈 `max(field)` over the docs past `after` that match the source query.
+    async fn current_max(&self, source: &ElasticsearchSourceConfig) -> Result<Option<serde_json::Value>> {
+        let the_base = source.url.trim_end_matches('/');
+        let the_url = match source.index {
+            Some(ref the_index) => format!("{}/{}/_search", the_base, the_index),
+            None => format!("{}/_search", the_base),
+        };
+        let the_query = self.narrow(source.query_clause()?).unwrap_or_else(|| serde_json::json!({ "match_all": {} }));
+        let the_body = serde_json::json!({
+            "size": 0,
+            "track_total_hits": false,
+            "query": the_query,
+            "aggs": { WATERMARK_AGG: { "max": { "field": self.field } } },
+        });
+        let the_client = source.timeouts().apply(source.connection.client_builder()?).build()?;
+        let the_response = authed(the_client.post(&the_url), &source.api_key, &source.username, &source.password)
+            .header("Content-Type", "application/json")
+            .body(the_body.to_string())
+            .send()
+            .await
+            .context("💀 Couldn't reach the source cluster to find the resume_after high-water mark")?;
+        let the_status = the_response.status();
+        let the_text = the_response.text().await.unwrap_or_default();
+        if !the_status.is_success() {
+            anyhow::bail!("💀 The max('{}') search failed with {}: '{}'", self.field, the_status, the_text);
+        }
+        let the_answer: serde_json::Value =
+            serde_json::from_str(&the_text).context("💀 The max aggregation came back as something that isn't JSON")?;
+        let the_agg = &the_answer["aggregations"][WATERMARK_AGG];
+        // -- 📅 dates come back as epoch millis in `value` — the formatted string is what `range` expects
+        if let Some(the_formatted) = the_agg["value_as_string"].as_str() {
+            return Ok(Some(serde_json::Value::String(the_formatted.to_string())));
+        }
+        Ok(match the_agg["value"].as_f64() {
+            None => None,
+            // -- 🔢 a max is always a double; a whole number goes back as one, or `gt 41.0` misses nothing but reads oddly
+            Some(the_max) if the_max.fract() == 0.0 && the_max.abs() < 9_007_199_254_740_992.0 => {
+                Some(serde_json::json!(the_max as i64))
+            }
+            Some(the_max) => Some(serde_json::json!(the_max)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn a_source(url: &str, state_file: &std::path::Path) -> Result<ElasticsearchSourceConfig> {
+        Ok(toml::from_str(&format!(
+            "url = \"{}\"\nindex = \"products\"\nquery = {{ term = {{ status = \"active\" }} }}\n\
+             [resume_after]\nfield = \"updated_at\"\nstate_file = '{}'",
+            url,
+            state_file.display()
+        ))?)
+    }
+
+    /// 🧪 First night reads everything up to the max; the second night starts where the first stopped.
+    #[tokio::test]
+    async fn the_one_where_the_night_shift_picks_up_where_it_left_off() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/products/_search"))
+            .and(body_partial_json(serde_json::json!({
+                "query": { "bool": { "filter": [{ "range": { "updated_at": { "gt": "2026-10-16T02:00:00.000Z" } } }] } }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "aggregations": { "kvx_watermark": { "value": null } }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/products/_search"))
+            .and(body_partial_json(serde_json::json!({ "size": 0, "query": { "term": { "status": "active" } } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "aggregations": { "kvx_watermark": { "value": 1.7765928e12, "value_as_string": "2026-10-16T02:00:00.000Z" } }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_dir = tempfile::tempdir()?;
+        let the_state = the_dir.path().join("products.json");
+        let the_source = a_source(&mock_server.uri(), &the_state)?;
+
+        let the_first = Watermark::plan(&the_source).await?.expect("🎯 resume_after is set");
+        assert_eq!(the_first.after, None);
+        assert_eq!(
+            the_first.narrow(the_source.query_clause()?),
+            Some(serde_json::json!({ "bool": {
+                "must": [{ "term": { "status": "active" } }],
+                "filter": [{ "range": { "updated_at": { "lte": "2026-10-16T02:00:00.000Z" } } }]
+            } }))
+        );
+        assert!(the_first.save().await?);
+
+        let the_second = Watermark::plan(&the_source).await?.expect("🎯 resume_after is set");
+        assert_eq!(the_second.after, Some(serde_json::json!("2026-10-16T02:00:00.000Z")));
+        assert_eq!(the_second.up_to, None, "🎯 nothing changed since");
+        assert!(!the_second.save().await?, "🎯 the old mark stands");
+        Ok(())
+    }
+
+    /// 🧪 A mark left for another field is refused rather than compared against the wrong thing.
+    #[tokio::test]
+    async fn the_one_where_the_bookmark_is_from_a_different_book() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_state = the_dir.path().join("products.json");
+        std::fs::write(&the_state, r#"{"field": "_seq_no", "after": 41}"#)?;
+        let the_err = Watermark::plan(&a_source("http://localhost:1", &the_state)?).await.unwrap_err();
+        assert!(format!("{:#}", the_err).contains("holds a mark for '_seq_no'"));
+        Ok(())
+    }
+}
@@ -0,0 +1,447 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🗓️ Time slicing — chop a giant date range into bite-sized query windows.
+//!
+//! 🎬 *[five years of logs. one search. the coordinating node begins to sweat.]*
+//! *["What if," whispers the intern, "we just did... Monday?"]*
+//! *[the cluster exhales. the migration becomes a calendar.]*
+//!
+//! Given a date field, a `[start, end)` range and a window size, this produces an ordered
+//! list of `TimeWindow`s, each of which renders a `range` query clause:
+//! ```text
+//! {"range": {"@timestamp": {"gte": "2024-01-01T00:00:00Z", "lt": "2024-01-02T00:00:00Z"}}}
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Configured via `[source_config.Elasticsearch.time_slice]` (`field`, `start`, `end`, `window`)
+//! - Windows are half-open (`gte` / `lt`) so a doc on a boundary lands in exactly one window
+//! - Each window has a stable `index` — the unit of progress, checkpoints and retries. The
+//!   `WindowLedger` counts a window done once every slice has read it to the end, then moves
+//!   `ProgressEvent::windows_done` and rewrites `checkpoint_file`; a rerun starts after the
+//!   last window it recorded
+//! - A window that fails mid-read is started over (`window_retries`) by the source, with a
+//!   fresh PIT or scroll
+//! - Dates: `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ` (UTC only). Window: `<n>m`, `<n>h`, `<n>d`
+//! - No chrono: civil-date ⇄ epoch math is ~20 lines (Howard Hinnant's algorithms) and we
+//!   only ever need UTC. Time zones are a problem for future us. Future us is not thrilled. 🦆
+
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::info;
+
+use crate::progress::DrainMetrics;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+fn default_window() -> String {
+    "1d".to_string()
+}
+
+fn default_window_retries() -> u32 {
+    2
+}
+
+/// 🗓️ Slice the source read into date windows over `field`.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TimeSliceConfig {
+    /// 📅 The date field to range over (e.g. `@timestamp`)
+    pub field: String,
+    /// ⏮️ Inclusive start — `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ`
+    pub start: String,
+    /// ⏭️ Exclusive end — same formats as `start`
+    pub end: String,
+    /// 📏 Window size: `<n>m`, `<n>h`, or `<n>d`. Defaults to one day.
+    #[serde(default = "default_window")]
+    pub window: String,
+    /// 💾 Where finished windows are recorded (JSON). A rerun with the same range starts after
+    /// the last window recorded there. `None` starts at the first window every time.
+    #[serde(default)]
+    pub checkpoint_file: Option<PathBuf>,
+    /// 🔁 How many times a window that fails mid-read is started over before the run fails.
+    #[serde(default = "default_window_retries")]
+    pub window_retries: u32,
+}
+
+/// 🪟 One half-open slice of the range: `gte <= field < lt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeWindow {
+    /// 🔢 Position in the plan, 0-based — stable across runs with the same config
+    pub index: usize,
+    /// ⏮️ RFC 3339 UTC lower bound (inclusive)
+    pub gte: String,
+    /// ⏭️ RFC 3339 UTC upper bound (exclusive)
+    pub lt: String,
+}
+
+impl TimeWindow {
+    /// 🔎 The `range` clause for this window, ready to drop into a bool filter.
+    pub fn range_query(&self, field: &str) -> serde_json::Value {
+        serde_json::json!({ "range": { field: { "gte": self.gte, "lt": self.lt } } })
+    }
+}
+
+impl TimeSliceConfig {
+    /// 📋 Expand the config into its ordered list of windows. The last window is clipped to `end`.
+    pub fn windows(&self) -> Result<Vec<TimeWindow>> {
+        let the_start = parse_utc(&self.start).context("💀 time_slice.start is not a date we recognize")?;
+        let the_end = parse_utc(&self.end).context("💀 time_slice.end is not a date we recognize")?;
+        let the_step = parse_window(&self.window)?;
+        if the_end <= the_start {
+            bail!(
+                "💀 time_slice.end ({}) must be after time_slice.start ({}). Time only goes one way. We checked.",
+                self.end,
+                self.start
+            );
+        }
+
+        let mut the_windows = Vec::new();
+        let mut the_cursor = the_start;
+        while the_cursor < the_end {
+            let the_next = (the_cursor + the_step).min(the_end);
+            the_windows.push(TimeWindow {
+                index: the_windows.len(),
+                gte: format_utc(the_cursor),
+                lt: format_utc(the_next),
+            });
+            the_cursor = the_next;
+        }
+        Ok(the_windows)
+    }
+}
+
+/// 💾 What `checkpoint_file` holds: the plan it was written for, and how far along it got.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedWindows {
+    field: String,
+    start: String,
+    end: String,
+    window: String,
+    /// ✅ Windows `0..done` were read to the end by every slice
+    done: usize,
+}
+
+/// 📒 Which windows are read — one per run, shared by every slice.
+///
+/// A window is done once every slice has read it to the end. Windows finish in order, so
+/// "done" is one number: the first window some slice is still on.
+#[derive(Debug)]
+pub struct WindowLedger {
+    config: TimeSliceConfig,
+    total: usize,
+    /// ⏭️ Windows `checkpoint_file` said were done when the run started
+    resume_from: usize,
+    /// 🍕 Per slice: the first window it hasn't finished. Held across the checkpoint write,
+    /// so two slices can't write their counts out of order.
+    next_per_slice: Mutex<Vec<usize>>,
+    /// 📊 The run's metrics — set once the Foreman has built them
+    metrics: OnceLock<Arc<DrainMetrics>>,
+}
+
+impl WindowLedger {
+    /// 📖 Read `checkpoint_file` (if any) for where to start. A checkpoint written for another
+    /// range is refused rather than guessed at.
+    pub fn open(config: &TimeSliceConfig, windows: &[TimeWindow], slices: usize) -> Result<Self> {
+        let resume_from = match config.checkpoint_file {
+            Some(ref the_path) if the_path.exists() => {
+                let the_text = std::fs::read_to_string(the_path)
+                    .with_context(|| format!("💀 Couldn't read time_slice.checkpoint_file '{}'", the_path.display()))?;
+                let the_saved: SavedWindows = serde_json::from_str(&the_text)
+                    .with_context(|| format!("💀 time_slice.checkpoint_file '{}' isn't a window checkpoint", the_path.display()))?;
+                if (&the_saved.field, &the_saved.start, &the_saved.end, &the_saved.window)
+                    != (&config.field, &config.start, &config.end, &config.window)
+                {
+                    bail!(
+                        "💀 time_slice.checkpoint_file '{}' was written for '{}' {} → {} in windows of {}, not this range. \
+                         Delete it to start over.",
+                        the_path.display(),
+                        the_saved.field,
+                        the_saved.start,
+                        the_saved.end,
+                        the_saved.window
+                    );
+                }
+                the_saved.done.min(windows.len())
+            }
+            _ => 0,
+        };
+        match windows.get(resume_from) {
+            _ if resume_from == 0 => {}
+            Some(the_window) => info!(
+                "⏭️ Checkpoint: {} of {} windows already read — starting at {}",
+                resume_from,
+                windows.len(),
+                the_window.gte
+            ),
+            None => info!("⏭️ Checkpoint: all {} windows were already read — nothing to do", windows.len()),
+        }
+        Ok(Self {
+            config: config.clone(),
+            total: windows.len(),
+            resume_from,
+            next_per_slice: Mutex::new(vec![resume_from; slices.max(1)]),
+            metrics: OnceLock::new(),
+        })
+    }
+
+    /// ⏭️ The first window this run reads.
+    pub fn resume_from(&self) -> usize {
+        self.resume_from
+    }
+
+    /// 📊 Report into the run's progress events from now on. Every slice calls it; the first wins.
+    pub fn report_to(&self, metrics: &Arc<DrainMetrics>) {
+        if self.metrics.set(metrics.clone()).is_ok() {
+            metrics.windows_total.store(self.total as u64, Ordering::Relaxed);
+            metrics.windows_done.store(self.resume_from as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// ✅ Slice `slice` read `window` to the end. If that was the last slice on it, the window
+    /// is done: logged, counted, and written to `checkpoint_file`.
+    pub async fn finish(&self, slice: usize, window: &TimeWindow) -> Result<()> {
+        let mut the_next = self.next_per_slice.lock().await;
+        let the_done_before = the_next.iter().copied().min().unwrap_or_default();
+        if let Some(the_slot) = the_next.get_mut(slice) {
+            *the_slot = window.index + 1;
+        }
+        let the_done = the_next.iter().copied().min().unwrap_or_default();
+        if the_done <= the_done_before {
+            return Ok(());
+        }
+        info!("🗓️ Window {}/{} read: {} → {}", the_done, self.total, window.gte, window.lt);
+        if let Some(the_metrics) = self.metrics.get() {
+            the_metrics.windows_done.store(the_done as u64, Ordering::Relaxed);
+        }
+        if let Some(ref the_path) = self.config.checkpoint_file {
+            let the_saved = SavedWindows {
+                field: self.config.field.clone(),
+                start: self.config.start.clone(),
+                end: self.config.end.clone(),
+                window: self.config.window.clone(),
+                done: the_done,
+            };
+            tokio::fs::write(the_path, serde_json::to_string(&the_saved)?)
+                .await
+                .with_context(|| format!("💀 Couldn't record the finished window in '{}'", the_path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// 📏 `"15m"` / `"6h"` / `"7d"` → seconds.
+fn parse_window(the_window: &str) -> Result<i64> {
+    let the_window = the_window.trim();
+    let (the_digits, the_unit) = the_window.split_at(the_window.len().saturating_sub(1));
+    let the_multiplier = match the_unit {
+        "m" => 60,
+        "h" => 3_600,
+        "d" => SECONDS_PER_DAY,
+        _ => bail!("💀 time_slice.window '{}' needs a unit: m, h, or d (e.g. \"1d\")", the_window),
+    };
+    let the_count: i64 = the_digits
+        .parse()
+        .with_context(|| format!("💀 time_slice.window '{}' doesn't start with a number", the_window))?;
+    if the_count <= 0 {
+        bail!("💀 time_slice.window '{}' must be positive. Zero-width windows never end.", the_window);
+    }
+    Ok(the_count * the_multiplier)
+}
+
+/// 📅 `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ` → seconds since the epoch (UTC).
+fn parse_utc(the_date: &str) -> Result<i64> {
+    let the_date = the_date.trim();
+    let (the_day_part, the_time_part) = match the_date.split_once('T') {
+        Some((day, time)) => (day, Some(time)),
+        None => (the_date, None),
+    };
+
+    let the_day_fields: Vec<&str> = the_day_part.split('-').collect();
+    let [y, m, d] = the_day_fields.as_slice() else {
+        bail!("💀 '{}' — expected YYYY-MM-DD", the_date);
+    };
+    let (y, m, d): (i64, u32, u32) = (y.parse()?, m.parse()?, d.parse()?);
+    if !(1..=12).contains(&m) || d == 0 || d > days_in_month(y, m) {
+        bail!("💀 '{}' is not a real calendar day", the_date);
+    }
+
+    let the_seconds_into_day = match the_time_part {
+        None => 0,
+        Some(the_time) => {
+            let Some(the_time) = the_time.strip_suffix('Z') else {
+                bail!("💀 '{}' — only UTC timestamps ending in 'Z' are supported", the_date);
+            };
+            let the_time_fields: Vec<&str> = the_time.split(':').collect();
+            let [hh, mm, ss] = the_time_fields.as_slice() else {
+                bail!("💀 '{}' — expected HH:MM:SS after the 'T'", the_date);
+            };
+            let (hh, mm, ss): (i64, i64, i64) = (hh.parse()?, mm.parse()?, ss.parse()?);
+            if hh > 23 || mm > 59 || ss > 59 || hh < 0 || mm < 0 || ss < 0 {
+                bail!("💀 '{}' has an impossible time of day", the_date);
+            }
+            hh * 3_600 + mm * 60 + ss
+        }
+    };
+
+    Ok(days_from_civil(y, m, d) * SECONDS_PER_DAY + the_seconds_into_day)
+}
+
+/// 🖨️ Seconds since the epoch → `YYYY-MM-DDTHH:MM:SSZ`.
+fn format_utc(the_seconds: i64) -> String {
+    let the_days = the_seconds.div_euclid(SECONDS_PER_DAY);
+    let the_rest = the_seconds.rem_euclid(SECONDS_PER_DAY);
+    let (y, m, d) = civil_from_days(the_days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        y,
+        m,
+        d,
+        the_rest / 3_600,
+        (the_rest % 3_600) / 60,
+        the_rest % 60
+    )
+}
+
+fn is_leap_year(y: i64) -> bool {
+    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+}
+
+fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        2 if is_leap_year(y) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// 🧮 Howard Hinnant's `days_from_civil` — proleptic Gregorian date → days since 1970-01-01.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let the_era = y.div_euclid(400);
+    let the_year_of_era = y - the_era * 400;
+    let m = m as i64;
+    let the_day_of_year = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d as i64 - 1;
+    let the_day_of_era = the_year_of_era * 365 + the_year_of_era / 4 - the_year_of_era / 100 + the_day_of_year;
+    the_era * 146_097 + the_day_of_era - 719_468
+}
+
+/// 🧮 The inverse: days since 1970-01-01 → (year, month, day).
+fn civil_from_days(the_days: i64) -> (i64, u32, u32) {
+    let z = the_days + 719_468;
+    let the_era = z.div_euclid(146_097);
+    let the_day_of_era = z - the_era * 146_097;
+    let the_year_of_era =
+        (the_day_of_era - the_day_of_era / 1_460 + the_day_of_era / 36_524 - the_day_of_era / 146_096) / 365;
+    let the_day_of_year = the_day_of_era - (365 * the_year_of_era + the_year_of_era / 4 - the_year_of_era / 100);
+    let mp = (5 * the_day_of_year + 2) / 153;
+    let d = (the_day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = the_year_of_era + the_era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice(start: &str, end: &str, window: &str) -> TimeSliceConfig {
+        TimeSliceConfig {
+            field: "@timestamp".to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+            window: window.to_string(),
+            checkpoint_file: None,
+            window_retries: 2,
+        }
+    }
+
+    #[test]
+    fn the_one_where_a_leap_february_becomes_29_windows() -> Result<()> {
+        let the_windows = slice("2024-02-01", "2024-03-01", "1d").windows()?;
+        assert_eq!(the_windows.len(), 29);
+        assert_eq!(the_windows[0].gte, "2024-02-01T00:00:00Z");
+        assert_eq!(the_windows[28].lt, "2024-03-01T00:00:00Z");
+        // -- 🪟 half-open and contiguous: each window starts where the last one stopped
+        assert!(the_windows.windows(2).all(|pair| pair[0].lt == pair[1].gte));
+        assert_eq!(the_windows[28].index, 28);
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_last_window_is_clipped_to_the_end() -> Result<()> {
+        let the_windows = slice("2024-01-01T00:00:00Z", "2024-01-01T10:00:00Z", "4h").windows()?;
+        let the_bounds: Vec<(&str, &str)> = the_windows.iter().map(|w| (w.gte.as_str(), w.lt.as_str())).collect();
+        assert_eq!(
+            the_bounds,
+            [
+                ("2024-01-01T00:00:00Z", "2024-01-01T04:00:00Z"),
+                ("2024-01-01T04:00:00Z", "2024-01-01T08:00:00Z"),
+                ("2024-01-01T08:00:00Z", "2024-01-01T10:00:00Z"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_a_window_renders_its_range_query() -> Result<()> {
+        let the_windows = slice("1999-12-31", "2000-01-01", "1d").windows()?;
+        assert_eq!(
+            the_windows[0].range_query("@timestamp"),
+            serde_json::json!({"range": {"@timestamp": {"gte": "1999-12-31T00:00:00Z", "lt": "2000-01-01T00:00:00Z"}}})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_nonsense_dates_and_windows_are_rejected() {
+        assert!(slice("2024-02-30", "2024-03-01", "1d").windows().is_err(), "💀 Feb 30 is fiction");
+        assert!(slice("2024-03-01", "2024-02-01", "1d").windows().is_err(), "💀 backwards range");
+        assert!(slice("2024-01-01", "2024-01-02", "1w").windows().is_err(), "💀 unknown unit");
+        assert!(slice("2024-01-01", "2024-01-02", "0d").windows().is_err(), "💀 zero-width window");
+        assert!(slice("2024-01-01T00:00:00+02:00", "2024-01-02", "1d").windows().is_err(), "💀 non-UTC");
+    }
+
+    /// 🧪 A window is done when the slowest slice is done with it — and a rerun picks up there.
+    #[tokio::test]
+    async fn the_one_where_the_calendar_remembers_where_it_stopped() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_checkpoint = the_dir.path().join("windows.json");
+        let the_config = TimeSliceConfig { checkpoint_file: Some(the_checkpoint.clone()), ..slice("2024-01-01", "2024-01-05", "1d") };
+        let the_windows = the_config.windows()?;
+        let the_metrics = Arc::new(DrainMetrics::new());
+
+        let the_ledger = WindowLedger::open(&the_config, &the_windows, 2)?;
+        the_ledger.report_to(&the_metrics);
+        assert_eq!(the_metrics.windows_total.load(Ordering::Relaxed), 4);
+        the_ledger.finish(0, &the_windows[0]).await?;
+        the_ledger.finish(0, &the_windows[1]).await?;
+        assert!(!the_checkpoint.exists(), "🍕 slice 1 is still on the first window");
+        the_ledger.finish(1, &the_windows[0]).await?;
+        assert_eq!(the_metrics.windows_done.load(Ordering::Relaxed), 1);
+        the_ledger.finish(1, &the_windows[1]).await?;
+        assert_eq!(the_metrics.windows_done.load(Ordering::Relaxed), 2);
+
+        assert_eq!(WindowLedger::open(&the_config, &the_windows, 2)?.resume_from(), 2);
+        let the_other_range = TimeSliceConfig { window: "12h".to_string(), ..the_config.clone() };
+        let the_err = WindowLedger::open(&the_other_range, &the_other_range.windows()?, 1).unwrap_err();
+        assert!(format!("{:#}", the_err).contains("Delete it to start over"));
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_calendar_math_round_trips() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        for the_day in [-719_468, -1, 0, 11_016, 19_782, 2_932_896] {
+            let (y, m, d) = civil_from_days(the_day);
+            assert_eq!(days_from_civil(y, m, d), the_day);
+        }
+    }
+}
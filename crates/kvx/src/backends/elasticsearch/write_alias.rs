@@ -0,0 +1,106 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔗 Write aliases — when the sink `index` is an alias, which concrete index gets the docs.
+//!
+//! 🎬 *[the config says `index = "products"`. there is no index called products.]*
+//! *[there is an alias called products. it points at three indices. none of them is the write index.]*
+//! *[the first `_bulk` finds out. ten thousand times. in the dead letter file.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Read from the `GET /{index}` answer the sink's existence check already fetches — no extra request
+//! - The answer is keyed by concrete index. The name we asked for among the keys → a plain index
+//! - Otherwise it's an alias: the write index is the one flagged `is_write_index: true`, or the
+//!   only index behind it when nobody is flagged (that's how ES picks, too)
+//! - An alias over several indices with no flag, or with the flag set to `false`, takes no
+//!   writes — every doc would be refused, so the sink refuses to start instead 🦆
+
+use anyhow::Result;
+use serde_json::Value;
+
+/// 🎯 What the sink `index` turned out to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteTarget {
+    /// 📦 A concrete index (or an answer we couldn't read — assume the name is what it says)
+    Index,
+    /// 🔗 An alias, and the one index behind it that receives writes
+    Alias { write_index: String },
+}
+
+/// 🔍 Resolve `name` from its `GET /{name}` body: `{"logs-000002":{"aliases":{"logs":{"is_write_index":true}}, ...}}`.
+pub fn resolve_write_target(name: &str, get_index_body: &str) -> Result<WriteTarget> {
+    let Ok(Value::Object(the_indices)) = serde_json::from_str::<Value>(get_index_body) else {
+        return Ok(WriteTarget::Index);
+    };
+    if the_indices.is_empty() || the_indices.contains_key(name) {
+        return Ok(WriteTarget::Index);
+    }
+    // -- 🔗 every concrete index behind the alias, with its `is_write_index` flag if it has one
+    let the_members: Vec<(&String, Option<bool>)> = the_indices
+        .iter()
+        .filter_map(|(the_index, the_meta)| {
+            let the_alias = the_meta.get("aliases")?.get(name)?;
+            Some((the_index, the_alias.get("is_write_index").and_then(Value::as_bool)))
+        })
+        .collect();
+    if the_members.is_empty() {
+        // -- 🤷 not a name we recognize in the answer (a wildcard, a comma list) — let `_bulk` decide
+        return Ok(WriteTarget::Index);
+    }
+    let the_flagged: Vec<&String> =
+        the_members.iter().filter(|(_, the_flag)| *the_flag == Some(true)).map(|(the_index, _)| *the_index).collect();
+    let the_write_index = match (the_flagged.as_slice(), the_members.as_slice()) {
+        ([the_one], _) => (*the_one).clone(),
+        ([], [(the_only, None)]) => (*the_only).clone(),
+        _ => anyhow::bail!(
+            "💀 '{}' is an alias over [{}] with no write index — `_bulk` would refuse every doc. \
+             Mark one with `is_write_index: true`, or point `index` at a concrete index.",
+            name,
+            the_members.iter().map(|(the_index, _)| the_index.as_str()).collect::<Vec<_>>().join(", ")
+        ),
+    };
+    Ok(WriteTarget::Alias { write_index: the_write_index })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_the_alias_knows_where_to_write() -> Result<()> {
+        let the_rollover = r#"{
+            "logs-000001": {"aliases": {"logs": {"is_write_index": false}}},
+            "logs-000002": {"aliases": {"logs": {"is_write_index": true}}}
+        }"#;
+        assert_eq!(
+            resolve_write_target("logs", the_rollover)?,
+            WriteTarget::Alias { write_index: "logs-000002".to_string() }
+        );
+        let the_lone_alias = r#"{"products-v3": {"aliases": {"products": {}}, "mappings": {}}}"#;
+        assert_eq!(
+            resolve_write_target("products", the_lone_alias)?,
+            WriteTarget::Alias { write_index: "products-v3".to_string() }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_an_index_is_just_an_index() -> Result<()> {
+        assert_eq!(resolve_write_target("people", r#"{"people": {"aliases": {}}}"#)?, WriteTarget::Index);
+        assert_eq!(resolve_write_target("people", "")?, WriteTarget::Index, "🤷 unreadable → assume an index");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_alias_has_nowhere_to_write() {
+        let the_read_alias = r#"{
+            "sales-2023": {"aliases": {"sales": {}}},
+            "sales-2024": {"aliases": {"sales": {}}}
+        }"#;
+        let the_error = resolve_write_target("sales", the_read_alias).expect_err("💀 two indices, no write flag");
+        assert!(the_error.to_string().contains("sales-2023, sales-2024"));
+        let the_switched_off = r#"{"archive-1": {"aliases": {"archive": {"is_write_index": false}}}}"#;
+        assert!(resolve_write_target("archive", the_switched_off).is_err());
+    }
+}
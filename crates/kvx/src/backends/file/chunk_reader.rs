@@ -0,0 +1,156 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🗜️ The buffered read path's chunk source — plain bytes off the disk (or the wire), or unpacked on the way in.
+//!
+//! 🎬 *[the export arrives as `products.ndjson.gz`. 4 GB compressed, 60 GB not.]*
+//! *[the disk has 20 GB free. `gunzip -k` has opinions about that.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `FileSource`'s buffered loop asks for the next chunk of *content*; it never knows whether
+//!   the bytes were compressed, or whether they came from a file, a URL (`http_body.rs`) or
+//!   an io_uring thread (`uring.rs`).
+//!   Lines, caps, the remainder and the ledger work unchanged
+//! - gzip goes through `flate2::write::MultiGzDecoder`: compressed chunks are pushed in, whatever
+//!   comes out is the next chunk. Push, not pull, so the reads stay async. Concatenated members
+//!   (`pigz`, `bgzip`, `cat a.gz b.gz`) decode as one stream
+//! - bzip2 goes through `bzip2::write::BzDecoder` the same way. It stops at the end of a stream,
+//!   so `Unpacker::push` starts a fresh one for whatever follows — `pbzip2` output is many streams
+//! - A truncated or corrupt file fails at the point it stops making sense, not at EOF
+//! - Positions (byte offsets, the resume point) count decompressed bytes, so a gzip file
+//!   or bzip2 file can't be resumed with `start_offset_bytes` — `FileSource::new` refuses that 🦆
+
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use bzip2::write::BzDecoder;
+use flate2::write::MultiGzDecoder;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+use super::http_body::HttpBody;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+use super::uring::UringReader;
+
+/// 📥 Where the raw bytes come from — the disk, the disk through io_uring, or a download.
+pub(super) enum RawReader {
+    File(File),
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    Uring(Box<UringReader>),
+    Http(Box<HttpBody>),
+}
+
+impl RawReader {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            Self::File(the_file) => Ok(the_file.read(buf).await?),
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            Self::Uring(the_ring) => the_ring.read(buf).await,
+            Self::Http(the_body) => the_body.read(buf).await,
+        }
+    }
+}
+
+/// 📡 Where the buffered loop's chunks come from.
+pub(super) enum ChunkReader {
+    Plain(RawReader),
+    Unpacked { raw: RawReader, compressed: Vec<u8>, decoder: Box<Unpacker>, finished: bool },
+}
+
+/// 🗜️ A push-style decoder: compressed bytes in, content out into its `Vec`.
+pub(super) enum Unpacker {
+    Gzip(MultiGzDecoder<Vec<u8>>),
+    Bzip2(BzDecoder<Vec<u8>>),
+}
+
+impl Unpacker {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Gzip(_) => "gzip",
+            Self::Bzip2(_) => "bzip2",
+        }
+    }
+
+    /// 📤 What's been unpacked so far and not yet handed out.
+    fn output(&mut self) -> &mut Vec<u8> {
+        match self {
+            Self::Gzip(the_decoder) => the_decoder.get_mut(),
+            Self::Bzip2(the_decoder) => the_decoder.get_mut(),
+        }
+    }
+
+    /// 📥 Feed compressed bytes in.
+    fn push(&mut self, mut compressed: &[u8]) -> std::io::Result<()> {
+        match self {
+            Self::Gzip(the_decoder) => the_decoder.write_all(compressed),
+            Self::Bzip2(the_decoder) => {
+                while !compressed.is_empty() {
+                    let the_taken = the_decoder.write(compressed)?;
+                    if the_taken == 0 {
+                        // -- 🧩 that stream is done and more bytes follow: the next stream starts here
+                        the_decoder.try_finish()?;
+                        let the_output = std::mem::take(the_decoder.get_mut());
+                        *the_decoder = BzDecoder::new(the_output);
+                    }
+                    compressed = &compressed[the_taken..];
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 🏁 The input is over — flush the rest out, or fail if the stream stopped mid-way.
+    fn finish(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Gzip(the_decoder) => the_decoder.try_finish(),
+            Self::Bzip2(the_decoder) => the_decoder.try_finish(),
+        }
+    }
+}
+
+impl ChunkReader {
+    /// 🗜️ A gunzipping reader over `raw`, reading `chunk_size` compressed bytes at a time.
+    pub(super) fn gzip(raw: RawReader, chunk_size: usize) -> Self {
+        Self::unpacked(raw, chunk_size, Unpacker::Gzip(MultiGzDecoder::new(Vec::new())))
+    }
+
+    /// 🗜️ A bunzipping reader over `raw`, reading `chunk_size` compressed bytes at a time.
+    pub(super) fn bzip2(raw: RawReader, chunk_size: usize) -> Self {
+        Self::unpacked(raw, chunk_size, Unpacker::Bzip2(BzDecoder::new(Vec::new())))
+    }
+
+    fn unpacked(raw: RawReader, chunk_size: usize, decoder: Unpacker) -> Self {
+        Self::Unpacked { raw, compressed: vec![0u8; chunk_size], decoder: Box::new(decoder), finished: false }
+    }
+
+    /// 📖 The next chunk of the file's content, into `buf[..n]`. `0` = EOF.
+    /// A plain read fills `buf` as it is; an unpacked read may hand back more or fewer bytes than it read.
+    pub(super) async fn read(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        match self {
+            Self::Plain(the_raw) => the_raw.read(buf).await,
+            Self::Unpacked { raw, compressed, decoder, finished } => loop {
+                if !decoder.output().is_empty() {
+                    // -- 🔁 swap, don't copy: the decoder gets our old buffer back, emptied
+                    buf.clear();
+                    std::mem::swap(buf, decoder.output());
+                    return Ok(buf.len());
+                }
+                if *finished {
+                    return Ok(0);
+                }
+                let the_bytes_read = raw.read(compressed).await?;
+                if the_bytes_read == 0 {
+                    decoder.finish().with_context(|| {
+                        format!("💀 The {0} stream ended early — a truncated download, or not {0} after all", decoder.name())
+                    })?;
+                    *finished = true;
+                } else {
+                    decoder
+                        .push(&compressed[..the_bytes_read])
+                        .with_context(|| format!("💀 The {} stream is corrupt", decoder.name()))?;
+                }
+            },
+        }
+    }
+}
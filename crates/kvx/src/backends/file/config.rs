@@ -11,8 +11,12 @@
 //!
 //! ⚠️ The singularity will read files directly from the quantum foam. We use paths.
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use serde::Deserialize;
-use crate::backends::{CommonSourceConfig, CommonSinkConfig};
+use crate::backends::{CommonSinkConfig, CommonSourceConfig, HttpTimeouts, SinkTuning, SourceTuning};
 
 // ============================================================
 // 📂 FileSourceConfig
@@ -26,6 +30,24 @@ use crate::backends::{CommonSourceConfig, CommonSinkConfig};
 #[derive(Debug, Deserialize, Clone)]
 pub struct FileSourceConfig {
     pub file_name: String,
+    /// 🗺️ How the bytes come off the disk: `buffered` chunked reads (default) or `mmap`.
+    #[serde(default)]
+    pub read_mode: FileReadMode,
+    /// 📍 Start reading at this byte instead of line zero — the offset a crashed run recorded.
+    /// A mid-line offset skips ahead to the next full line. Single files only, not patterns.
+    #[serde(default)]
+    pub start_offset_bytes: u64,
+    /// 📝 Write the next unread byte offset here after every page (and at EOF), ready to be
+    /// passed back as `start_offset_bytes`.
+    #[serde(default)]
+    pub offset_file: Option<PathBuf>,
+    /// 🔍 Parse every line as it's read, and fail on the first one that isn't JSON — naming its
+    /// line number and byte offset. Off by default: it costs a parse per line.
+    #[serde(default)]
+    pub validate_json: bool,
+    /// 🗜️ `auto` (default): a `.gz` / `.bz2` name is unpacked as it's read. `gzip` / `bzip2` / `none` say so outright.
+    #[serde(default)]
+    pub compression: FileCompression,
     #[serde(default = "default_file_common_source_config")]
     pub common_config: CommonSourceConfig,
 }
@@ -42,6 +64,145 @@ fn default_file_common_source_config() -> CommonSourceConfig {
     CommonSourceConfig::default()
 }
 
+impl FileSourceConfig {
+    /// 🎚️ File source profile: 10k lines / 8MB per feed. Local disks are fast and
+    /// don't rate-limit us, so we read in generous gulps. 📂
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 10_000,
+        max_batch_size_bytes: 8 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+}
+
+// ============================================================
+// 🌐 UrlSourceConfig
+// ============================================================
+
+/// 🌐 `[source_config.Url]` — an NDJSON document fetched over HTTP(S) and read by the File
+/// source's line cutter, as it downloads. No local copy, no `curl -O` step first.
+#[derive(Debug, Deserialize, Clone)]
+pub struct UrlSourceConfig {
+    pub url: String,
+    /// 🔑 Extra request headers, sent on every request — `Authorization`, an API key, a cookie.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// 📍 Start at this byte of the document (asked for with `Range`) — the offset a crashed run
+    /// recorded. A mid-line offset skips ahead to the next full line.
+    #[serde(default)]
+    pub start_offset_bytes: u64,
+    /// 📝 Write the next unread byte offset here after every page, same as the File source.
+    #[serde(default)]
+    pub offset_file: Option<PathBuf>,
+    /// 🔍 Parse every line as it's read, and fail on the first one that isn't JSON.
+    #[serde(default)]
+    pub validate_json: bool,
+    /// 🗜️ `auto` (default): a URL path ending `.gz` / `.bz2` is unpacked as it's read.
+    #[serde(default)]
+    pub compression: FileCompression,
+    #[serde(default = "default_file_common_source_config")]
+    pub common_config: CommonSourceConfig,
+}
+
+impl UrlSourceConfig {
+    /// 🎚️ Same gulps as a local file — the line cutter doesn't care where the bytes came from.
+    pub const DEFAULT_TUNING: SourceTuning = FileSourceConfig::DEFAULT_TUNING;
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s connect, 60s of silence. A download runs as long as it runs, so `request` bounds
+    /// each wait for the next bytes, not the whole transfer.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(60),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔎 The URL's path — what `compression = "auto"` goes by, minus any `?query` or `#fragment`.
+    pub fn path(&self) -> &str {
+        self.url.split(['?', '#']).next().unwrap_or_default()
+    }
+
+    /// 📂 The File source settings this URL is read with: the URL stands in for the file name
+    /// (so it's what errors and origins name), always read buffered.
+    pub(crate) fn as_file_config(&self) -> FileSourceConfig {
+        FileSourceConfig {
+            file_name: self.url.clone(),
+            read_mode: FileReadMode::Buffered,
+            start_offset_bytes: self.start_offset_bytes,
+            offset_file: self.offset_file.clone(),
+            validate_json: self.validate_json,
+            compression: self.compression,
+            common_config: self.common_config.clone(),
+        }
+    }
+}
+
+/// 🗺️ How the File source reads — `read_mode = "..."`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileReadMode {
+    /// 📡 128 KiB async reads into our own buffer — works on anything that can be opened
+    #[default]
+    Buffered,
+    /// 🗺️ Map the file and cut pages straight out of the mapping — no read copies. Regular
+    /// files only; pipes, FIFOs and devices quietly fall back to `buffered`.
+    Mmap,
+    /// 💍 128 KiB reads through io_uring on a dedicated thread, a few kept in flight ahead of
+    /// the pumper. Needs the `io-uring` feature (Linux only); falls back to `buffered` if the
+    /// kernel won't set up a ring.
+    Uring,
+}
+
+/// 🗜️ Whether the File source's bytes are compressed — `compression = "..."`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileCompression {
+    /// 🔎 Go by the file name: `.gz` is gzip, `.bz2` is bzip2, anything else is plain
+    #[default]
+    Auto,
+    /// 📄 Plain bytes, whatever the name says
+    None,
+    /// 🗜️ gzip, one member or many (`pigz`, `bgzip` and `cat a.gz b.gz` output all work)
+    Gzip,
+    /// 🗜️ bzip2, one stream or many (`bzip2`, `pbzip2` and `lbzip2` output all work)
+    Bzip2,
+}
+
+impl FileCompression {
+    /// 🔎 What `file_name` actually is. `auto` refuses the compressions it can't read, rather
+    /// than feeding their bytes to the line cutter as if they were text.
+    pub fn resolve(self, file_name: &str) -> anyhow::Result<Self> {
+        if self != Self::Auto {
+            return Ok(self);
+        }
+        if file_name.ends_with(".gz") {
+            return Ok(Self::Gzip);
+        }
+        if file_name.ends_with(".bz2") {
+            return Ok(Self::Bzip2);
+        }
+        if let Some(the_suffix) = [".zst", ".xz", ".lz4"].into_iter().find(|s| file_name.ends_with(s)) {
+            anyhow::bail!(
+                "💀 '{}' looks {}-compressed, and only gzip and bzip2 are read as-is. Decompress it first, or set compression = \"none\" if the name lies.",
+                file_name,
+                the_suffix.trim_start_matches('.')
+            );
+        }
+        Ok(Self::None)
+    }
+}
+
 // ============================================================
 // 🚰 FileSinkConfig
 // ============================================================
@@ -52,10 +213,46 @@ fn default_file_common_source_config() -> CommonSourceConfig {
 #[derive(Debug, Deserialize, Clone)]
 pub struct FileSinkConfig {
     pub file_name: String,
+    /// 🪪 When present, write only document IDs (plus optional version/hash) instead of
+    /// full documents — a compact file for external reconciliation. ES sources only.
+    #[serde(default)]
+    pub id_export: Option<IdExportConfig>,
+    /// 📡 What an Elasticsearch source's `_search` pages become on disk: `raw` pages (default),
+    /// `source` docs one per line, or `bulk` action + source pairs. ES sources only.
+    #[serde(default)]
+    pub es_hits: EsHitsFormat,
     #[serde(flatten, default = "default_file_common_sink_config")]
     pub common_config: CommonSinkConfig,
 }
 
+/// 🪪 ID export knobs — `[sink_config.File.id_export]`.
+///
+/// Output is one `index<TAB>id[<TAB>version][<TAB>hash]` line per doc.
+/// Billions of docs, a few dozen bytes each. Reconciliation at scale without hauling
+/// every `_source` across the wire twice. 🧾
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct IdExportConfig {
+    /// 🔢 Include `_version` — the ES source then asks every search for `"version": true`
+    #[serde(default)]
+    pub include_version: bool,
+    /// 🧮 Include an FNV-1a 64-bit hash of `_source` — cheap change detection
+    #[serde(default)]
+    pub include_hash: bool,
+}
+
+/// 📡 How ES search hits are written by the File sink — `es_hits = "..."`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EsHitsFormat {
+    /// 📦 Whole `_search` response pages, envelope and all — a debugging dump
+    #[default]
+    Raw,
+    /// 📄 One `_source` per line — plain NDJSON, ready for a File → anything run
+    Source,
+    /// 🏷️ `_bulk` pairs: `{"index":{"_index","_id","_routing"}}` + `_source` — replayable into `_bulk`
+    Bulk,
+}
+
 /// 🔧 Returns the default config for FileSink. It defaults. It ships. It doesn't ask questions.
 ///
 /// What's the DEAL with default implementations? You define an entire struct, document every field,
@@ -68,3 +265,16 @@ fn default_file_common_sink_config() -> CommonSinkConfig {
     // -- ✅ ancient proverb: "He who ships with defaults, panics in production with style"
     CommonSinkConfig::default()
 }
+
+impl FileSinkConfig {
+    /// 🎚️ File sink profile: 64MB writes. The disk doesn't have an HTTP body limit,
+    /// it just has feelings about fsync. 🚰
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 64 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+}
@@ -12,6 +12,7 @@ use tracing::trace;
 
 use crate::Payload;
 use crate::backends::Sink;
+use crate::manifolds::{ArrayFraming, ManifoldBackend, ManifoldFormat};
 use super::config::FileSinkConfig;
 /// 🚰 FileSink — receives fully rendered payload strings and writes them to disk. I/O only.
 ///
@@ -22,11 +23,21 @@ use super::config::FileSinkConfig;
 /// cast + binary collect. FileSink just writes the final payload bytes to disk.
 /// Think of it as a very loyal golden retriever. You throw it data, it writes it.
 ///
+/// 📦 With `manifold = "json_array"`, every payload arrives as its own `[...]`. Written as is,
+/// the file would be `[a,b][c,d]` — valid to nobody. So the sink unwraps each one and keeps a
+/// single array open across the run: `[` before the first doc, `,` between payloads, `]` on close.
+/// `manifold = "pretty"` works the same way, just with newlines in its framing.
+///
+/// 📊 With `manifold = "csv"`, payloads are bare rows — the header row is written here, once,
+/// when the file is created.
+///
 /// ⚠️ `File::create` truncates if the file exists. No warning. No backup. Just gone.
 /// He who runs this without checking the output path, re-migrates in shame.
 #[derive(Debug)]
 pub struct FileSink {
     file_buf: io::BufWriter<File>,
+    /// 📦 `Some((framing, has the array been opened yet))` when writing one JSON array; `None` otherwise
+    the_array: Option<(ArrayFraming, bool)>,
     _sink_config: FileSinkConfig,
 }
 
@@ -54,9 +65,17 @@ impl FileSink {
         // -- 📦 BufWriter: because issuing one syscall per document is a war crime.
         // -- Batch those writes. Your kernel will thank you. Your SRE will thank you.
         // -- Your future self at 3am will bow before the altar of buffered I/O.
-        let file_buf = io::BufWriter::new(file_handle);
+        let mut file_buf = io::BufWriter::new(file_handle);
+        // -- 📊 CSV payloads are rows only; the header row goes in once, up top
+        if sink_config.common_config.manifold == Some(ManifoldFormat::Csv)
+            && let Some(the_header) = sink_config.common_config.csv.as_ref().map(|c| c.header_line()).transpose()?.flatten()
+        {
+            file_buf.write_all(the_header.as_bytes()).await?;
+        }
+        let the_array = sink_config.common_config.manifold.and_then(ManifoldBackend::array_framing).map(|f| (f, false));
         Ok(Self {
             file_buf,
+            the_array,
             _sink_config: sink_config,
         })
     }
@@ -74,7 +93,23 @@ impl Sink for FileSink {
             "📬 payload of {} bytes walked into the file sink — writing it all down",
             payload.len()
         );
-        self.file_buf.write_all(payload.as_bytes()).await?;
+        let Some((the_framing, is_open)) = self.the_array else {
+            self.file_buf.write_all(payload.as_bytes()).await?;
+            return Ok(());
+        };
+        // -- 📦 `[a,b]` → `a,b`, spliced into the one array this file holds
+        let the_items = payload
+            .strip_prefix(the_framing.open)
+            .and_then(|the_rest| the_rest.strip_suffix(the_framing.close))
+            .context("💀 JSON array payload wasn't wrapped in its brackets — the joiner and the file disagree")?;
+        if the_items.is_empty() {
+            return Ok(());
+        }
+        self.file_buf
+            .write_all(if is_open { the_framing.separator } else { the_framing.open }.as_bytes())
+            .await?;
+        self.file_buf.write_all(the_items.as_bytes()).await?;
+        self.the_array = Some((the_framing, true));
         Ok(())
     }
 
@@ -93,6 +128,11 @@ impl Sink for FileSink {
         trace!(
             "🎬 final flush. the file sink takes its bow, the BufWriter empties its soul to disk, the orchestra swells"
         );
+        // -- 📦 close the array — or, if nothing ever came, write an empty one
+        if let Some((the_framing, is_open)) = self.the_array.take() {
+            let the_ending = if is_open { the_framing.close } else { the_framing.empty };
+            self.file_buf.write_all(the_ending.as_bytes()).await?;
+        }
         self.file_buf.flush().await.context(
             // -- 💀 poetic error for the poetic act of flushing.
             // -- The data was SO CLOSE. It was in the buffer. It could SEE the disk.
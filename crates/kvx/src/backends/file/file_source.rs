@@ -5,15 +5,23 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use memchr::memchr;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use tokio::{
     fs::File,
-    io::AsyncReadExt,
+    io::{AsyncReadExt, AsyncSeekExt},
 };
-use tracing::trace;
+use tracing::{debug, info, trace};
 
-use crate::Page;
+use crate::{Page, PageOrigin};
+use crate::warnings::{RunWarnings, WarningKind};
 use crate::backends::{CommonSourceConfig, Source};
-use super::config::FileSourceConfig;
+use super::chunk_reader::{ChunkReader, RawReader};
+use super::config::{FileCompression, FileReadMode, FileSourceConfig, UrlSourceConfig};
+use super::http_body::HttpBody;
+use super::line_ledger::LineLedger;
+use super::mmap::MappedFile;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+use super::uring::UringReader;
 // 📏 128 KiB per OS read — the Goldilocks zone between "too many syscalls" and "too much RAM".
 // BufReader's default is 8 KiB. We're 16x that. Fewer context switches, happier kernel.
 // KNOWLEDGE GRAPH: this constant controls the I/O batch size for raw file reads.
@@ -41,9 +49,11 @@ pub struct FileSource {
     // KNOWLEDGE GRAPH: we dropped BufReader because its 8 KiB default buffer caused too many
     // small reads. Our CHUNK_SIZE (128 KiB) batches I/O better and lets us scan for newlines
     // in bulk using memchr's SIMD magic instead of one-char-at-a-time read_line.
-    file: File,
-    // 🧱 reusable read buffer — pre-allocated to CHUNK_SIZE, never reallocated.
-    // Each loop iteration fills this from the OS and appends to working_buf.
+    // 🚪 `None` until the first pump — a 500-file glob queues 500 of these, and only the
+    // ones being read should hold a file descriptor (or a mapping).
+    file: Option<OpenFile>,
+    // 🧱 reusable read buffer — pre-allocated to CHUNK_SIZE, never reallocated (a gzip read
+    // swaps in the decoder's output instead). Each loop iteration fills this and appends to working_buf.
     read_buf: Vec<u8>,
     // 🧩 leftover bytes from the previous pump() call — the tail end of a chunk
     // that didn't end on a newline. Gets prepended to working_buf on the next call.
@@ -51,8 +61,32 @@ pub struct FileSource {
     // Without it, lines that span two chunks would get split into two incomplete docs.
     remainder: Vec<u8>,
     pub(crate) source_config: FileSourceConfig,
-    /// 📏 total file size in bytes — used by Foreman for progress bar total_expected_bytes
+    /// 📏 bytes this source will read (file size minus `start_offset_bytes`) — used by
+    /// Foreman for progress bar total_expected_bytes
     pub(crate) file_size: u64,
+    // 📍 where the file ends, and the first byte no page has taken yet — the resume point
+    end_offset: u64,
+    next_offset: u64,
+    // 📖 buffered reads: how far into the file the OS reads have got (pages lag behind by `remainder`).
+    // Counted in content bytes — for gzip and bzip2, that's after unpacking
+    read_position: u64,
+    // ✂️ `start_offset_bytes` landed mid-line — the first (partial) line gets dropped
+    skip_partial_line: bool,
+    // ⚠️ where the skipped-line report goes. Detached (nobody reads it) unless `with_warnings` is called.
+    warnings: RunWarnings,
+    // 📒 every line read: numbered, blank ones tallied (reported once at EOF), checked with
+    // `validate_json`, and placed in the page's origin
+    ledger: LineLedger,
+    // 📍 where the last page's docs sit in the file — until the pumper takes it
+    page_origin: Option<PageOrigin>,
+    // 🗜️ `compression`, resolved against the file name (never `Auto`) — compressed files always read buffered
+    compression: FileCompression,
+}
+
+/// 🚪 What the first pump opened: a reader for chunked reads, or the whole file mapped.
+enum OpenFile {
+    Buffered(ChunkReader),
+    Mapped(MappedFile),
 }
 
 impl std::fmt::Debug for FileSource {
@@ -66,21 +100,21 @@ impl std::fmt::Debug for FileSource {
 }
 
 impl FileSource {
-    /// 🚀 Opens the source file, grabs its size for the progress bar, allocates our chunk buffers,
-    /// and returns a fully initialized `FileSource` ready to vend feeds at ludicrous speed.
+    /// 🚀 Checks the source file is there, grabs its size for the progress bar, allocates our chunk
+    /// buffers, and returns a `FileSource` ready to vend feeds at ludicrous speed. The file itself
+    /// is opened on the first `pump()`.
     ///
     /// If the file doesn't exist: 💀 anyhow will tell you with *theatrical flair*.
-    /// If metadata fails: we assume 0 bytes, progress bar shows unknown. Shrug emoji as a service.
     ///
-    /// No cap: `File::open` is async here because we're in tokio-land. This is not your
-    /// grandfather's `std::fs::File::open`. This is `std::fs::File::open`'s cooler younger sibling
+    /// No cap: `tokio::fs::metadata` is async here because we're in tokio-land. This is not your
+    /// grandfather's `std::fs::metadata`. This is `std::fs::metadata`'s cooler younger sibling
     /// who got into the async runtime scene and never looked back.
     pub async fn new(source_config: FileSourceConfig) -> Result<Self> {
         // -- 💀 The door. It's locked. Or it doesn't exist. Or the filesystem lied to you.
         // -- In any case, the source file refused to open — like a very stubborn bouncer
         // -- at an exclusive club where the club is just a text file and we are very small data.
         // The context string below becomes the error message. Make it count.
-        let file_handle = File::open(&source_config.file_name)
+        let the_metadata = tokio::fs::metadata(&source_config.file_name)
             .await
             .context(format!(
                 "💀 The door to '{}' would not budge. We knocked. We pleaded. \
@@ -93,15 +127,215 @@ impl FileSource {
         // ⚠️  known edge case: if the file is being written to while we read, size may be stale/wrong.
         // --    This is fine. We will not panic. We are calm. The borrow checker, however, is not calm.
         // --    The borrow checker is never calm. The borrow checker has seen things.
-        let file_size = file_handle.metadata().await.map(|m| m.len()).unwrap_or(0);
+        let the_end_offset = the_metadata.len();
+        let the_start_offset = source_config.start_offset_bytes;
+        if the_start_offset > the_end_offset {
+            anyhow::bail!(
+                "💀 start_offset_bytes = {} is past the end of '{}' ({} bytes). Wrong file, or a stale offset?",
+                the_start_offset,
+                source_config.file_name,
+                the_end_offset
+            );
+        }
+        if source_config.read_mode == FileReadMode::Uring && cfg!(not(all(feature = "io-uring", target_os = "linux"))) {
+            anyhow::bail!("💀 read_mode = \"uring\" needs kvx built with the `io-uring` feature, on Linux — rebuild with it, or read `buffered`");
+        }
+        let the_compression = Self::check_compression(&source_config, &source_config.file_name)?;
+        Ok(Self::ready(source_config, the_end_offset, the_compression))
+    }
+
+    /// 🌐 A source that reads `config.url` as it downloads — the File source's line cutter over a
+    /// GET body (see `http_body.rs`). The request goes out here, so a wrong URL, a refused
+    /// header or a server that can't resume fails at startup, not at the first pump.
+    pub async fn from_url(config: UrlSourceConfig) -> Result<Self> {
+        let source_config = config.as_file_config();
+        let the_compression = Self::check_compression(&source_config, config.path())?;
+        let mut the_headers = HeaderMap::new();
+        for (the_name, the_value) in &config.headers {
+            // -- 🔑 header values are often secrets: the error names the header, never the value
+            let the_header_name = HeaderName::from_bytes(the_name.as_bytes())
+                .with_context(|| format!("💀 '{}' isn't a valid HTTP header name", the_name))?;
+            let the_header_value = HeaderValue::from_str(the_value)
+                .with_context(|| format!("💀 The value of header '{}' isn't a valid HTTP header value", the_name))?;
+            the_headers.insert(the_header_name, the_header_value);
+        }
+        let the_timeouts = config.timeouts();
+        let the_client = reqwest::Client::builder()
+            .connect_timeout(the_timeouts.connect)
+            .read_timeout(the_timeouts.request)
+            .build()
+            .context("💀 Couldn't build the HTTP client for the Url source")?;
+
+        // 🔍 resuming: ask from the byte before the offset — a `\n` there means we start on a line boundary
+        let the_start = config.start_offset_bytes;
+        let (mut the_body, the_total) = HttpBody::open(the_client, &config.url, the_headers, the_start.saturating_sub(1)).await?;
+        let mut skip_partial_line = false;
+        if the_start > 0 {
+            let mut the_byte = [0u8; 1];
+            skip_partial_line = the_body.read(&mut the_byte).await? == 1 && the_byte[0] != b'\n';
+            info!("📍 Resuming '{}' at byte {}", config.url, the_start);
+        }
+
+        // 📏 no Content-Length, no byte total: the bar spins, and a stop short isn't reported
+        let mut the_source = Self::ready(source_config, the_total.unwrap_or(0).max(the_start), the_compression);
+        let the_reader = Self::chunk_reader(RawReader::Http(Box::new(the_body)), the_compression);
+        the_source.file = Some(OpenFile::Buffered(the_reader));
+        the_source.skip_partial_line = skip_partial_line;
+        Ok(the_source)
+    }
 
-        Ok(Self {
-            file: file_handle,
+    /// 🗜️ What `name` is compressed with — and if it is, that nothing asks to enter it part-way.
+    fn check_compression(source_config: &FileSourceConfig, name: &str) -> Result<FileCompression> {
+        let the_compression = source_config.compression.resolve(name)?;
+        if the_compression != FileCompression::None
+            && (source_config.start_offset_bytes > 0 || source_config.offset_file.is_some())
+        {
+            anyhow::bail!(
+                "💀 '{}' is {:?}-compressed, and a compressed stream can't be entered part-way — start_offset_bytes and offset_file need a plain file.",
+                source_config.file_name,
+                the_compression
+            );
+        }
+        Ok(the_compression)
+    }
+
+    /// 📡 The buffered reader for `raw`, unpacking it if it's compressed.
+    fn chunk_reader(raw: RawReader, compression: FileCompression) -> ChunkReader {
+        match compression {
+            FileCompression::Gzip => ChunkReader::gzip(raw, CHUNK_SIZE),
+            FileCompression::Bzip2 => ChunkReader::bzip2(raw, CHUNK_SIZE),
+            FileCompression::None | FileCompression::Auto => ChunkReader::Plain(raw),
+        }
+    }
+
+    /// 🧱 A source positioned at `start_offset_bytes` of `end_offset` bytes, nothing opened yet.
+    fn ready(source_config: FileSourceConfig, end_offset: u64, compression: FileCompression) -> Self {
+        let the_start_offset = source_config.start_offset_bytes;
+        // 📏 a compressed file doesn't say how big it'll be once it's unpacked — no byte total for the progress bar
+        let file_size = if compression == FileCompression::None { end_offset - the_start_offset } else { 0 };
+        let ledger = LineLedger::new(&source_config.file_name, source_config.validate_json);
+        Self {
+            file: None,
             read_buf: vec![0u8; CHUNK_SIZE],
             remainder: Vec::new(),
             source_config,
             file_size,
-        })
+            end_offset,
+            next_offset: the_start_offset,
+            read_position: the_start_offset,
+            skip_partial_line: false,
+            warnings: RunWarnings::default(),
+            ledger,
+            page_origin: None,
+            compression,
+        }
+    }
+
+    /// ⚠️ Route skipped-input reports into the run's shared warning list.
+    pub fn with_warnings(mut self, warnings: RunWarnings) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// 📍 The first byte of the file no page has taken yet — `start_offset_bytes` for a rerun.
+    pub fn offset(&self) -> u64 {
+        self.next_offset
+    }
+
+    /// 🚪 Open the file the way `read_mode` asks — mapped if it can be, buffered otherwise —
+    /// positioned at `start_offset_bytes`.
+    async fn open_file(&mut self) -> Result<OpenFile> {
+        let the_path = &self.source_config.file_name;
+        let the_start = self.source_config.start_offset_bytes;
+        if self.source_config.read_mode == FileReadMode::Mmap && self.compression == FileCompression::None {
+            if let Some(mut the_mapped) = MappedFile::open(the_path)? {
+                the_mapped.seek_to_line(the_start);
+                return Ok(OpenFile::Mapped(the_mapped));
+            }
+            debug!("🗺️ '{}' isn't a regular file with bytes in it — reading it buffered", the_path);
+        }
+        let mut the_file = File::open(the_path)
+            .await
+            .with_context(|| format!("💀 '{}' was there a moment ago. Now it won't open.", the_path))?;
+        if self.compression != FileCompression::None {
+            debug!("🗜️ '{}' is {:?}-compressed — unpacking it as it's read", the_path, self.compression);
+            let the_raw = self.raw_reader(the_file, 0)?;
+            return Ok(OpenFile::Buffered(Self::chunk_reader(the_raw, self.compression)));
+        }
+        if the_start > 0 {
+            // -- 🔍 peek at the byte before the offset: a `\n` there means we start on a line boundary
+            the_file.seek(std::io::SeekFrom::Start(the_start - 1)).await?;
+            self.skip_partial_line = the_file.read_u8().await? != b'\n';
+            info!("📍 Resuming '{}' at byte {}", the_path, the_start);
+        }
+        Ok(OpenFile::Buffered(ChunkReader::Plain(self.raw_reader(the_file, the_start)?)))
+    }
+
+    /// 📥 The raw reader for the buffered loop: `the_file` as it is (already at `start`), or an
+    /// io_uring thread reading from `start` when `read_mode = "uring"` and the kernel has a ring.
+    #[cfg_attr(not(all(feature = "io-uring", target_os = "linux")), allow(unused_variables))]
+    fn raw_reader(&self, the_file: File, start: u64) -> Result<RawReader> {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        if self.source_config.read_mode == FileReadMode::Uring
+            && let Some(the_ring) = UringReader::open(&self.source_config.file_name, start, CHUNK_SIZE)?
+        {
+            return Ok(RawReader::Uring(Box::new(the_ring)));
+        }
+        Ok(RawReader::File(the_file))
+    }
+
+    /// 📝 Write the resume point to `offset_file`, if there is one.
+    async fn record_offset(&self) -> Result<()> {
+        if let Some(ref the_path) = self.source_config.offset_file {
+            tokio::fs::write(the_path, format!("{}\n", self.next_offset))
+                .await
+                .with_context(|| format!("💀 Couldn't record the read offset in '{}'", the_path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// 📄 Turn a finished feed into a page — or, if it's empty, report EOF (and any skipped lines).
+    fn finish_feed(&mut self, feed: Vec<u8>) -> Result<Option<Page>> {
+        // 📄 Empty feed = EOF. The well is dry. Return None. 🏁
+        if feed.is_empty() {
+            // -- 🏁 "That's all folks!" — Porky Pig, and also this file source
+            // ⚠️ report skipped lines exactly once — taking them zeroes the tab, so repeat EOF pumps stay quiet
+            let the_skipped_lines = self.ledger.take_skipped_lines();
+            if the_skipped_lines > 0 {
+                self.warnings.record(
+                    WarningKind::DataQuality,
+                    format!(
+                        "skipped {} blank line(s) in '{}'",
+                        the_skipped_lines, self.source_config.file_name
+                    ),
+                );
+            }
+            Ok(None)
+        } else {
+            // ✅ convert bytes to String — this validates UTF-8 in one pass at the end
+            // rather than on every line. Efficiency AND correctness. Chef's kiss. 🤌
+            let feed_string = String::from_utf8(feed).context(
+                "💀 The file contained bytes that aren't valid UTF-8. \
+                We tried to make a String. The String said no. \
+                Like trying to fit a square peg in a round hole, \
+                except the peg is binary garbage and the hole is Unicode.",
+            )?;
+            self.page_origin = Some(self.ledger.take_origin());
+            Ok(Some(Page(feed_string)))
+        }
+    }
+}
+
+impl Drop for FileSource {
+    /// 📍 A source that stops short (shutdown, a failed sink) says where — that's the rerun's
+    /// `start_offset_bytes`. A finished file has nothing to say.
+    fn drop(&mut self) {
+        if self.file.is_some() && self.compression == FileCompression::None && self.next_offset < self.end_offset {
+            info!(
+                "📍 '{}' stopped at byte {} of {} — resume with start_offset_bytes = {}",
+                self.source_config.file_name, self.next_offset, self.end_offset, self.next_offset
+            );
+        }
     }
 }
 
@@ -127,10 +361,32 @@ impl Source for FileSource {
     /// continuation byte in a multi-byte sequence. Scanning raw bytes for `0x0A` is therefore
     /// safe for any valid UTF-8 input. The final `String::from_utf8` validates the output.
     ///
+    /// 🗺️ With `read_mode = "mmap"` a regular file is mapped instead, and pages are cut straight
+    /// from the mapping (see `mmap.rs`). Same line rules, same caps, same pages.
+    /// 💍 With `read_mode = "uring"` the chunks come from an io_uring thread that reads ahead
+    /// (see `uring.rs`) — the loop below doesn't know the difference.
+    ///
     /// "He who reads the entire file into one String, OOMs in production." — Ancient proverb 📜
     async fn pump(&mut self) -> Result<Option<Page>> {
-        let max_docs = self.source_config.common_config.max_batch_size_docs;
-        let max_bytes = self.source_config.common_config.max_batch_size_bytes;
+        let the_tuning = self.source_config.tuning();
+        let max_docs = the_tuning.max_batch_size_docs;
+        let max_bytes = the_tuning.max_batch_size_bytes;
+
+        // 🚪 first pump opens the door — checked in `new()`, but files can vanish in between
+        if self.file.is_none() {
+            let the_opened = self.open_file().await?;
+            self.file = Some(the_opened);
+        }
+        // 🗺️ mapped: the page comes straight out of the mapping, no reads, no remainder
+        if let Some(OpenFile::Mapped(the_mapped)) = self.file.as_mut() {
+            let the_feed = the_mapped.next_feed(max_docs, max_bytes, &mut self.ledger)?;
+            self.next_offset = the_mapped.offset();
+            self.record_offset().await?;
+            return self.finish_feed(the_feed);
+        }
+        let Some(OpenFile::Buffered(the_reader)) = self.file.as_mut() else {
+            unreachable!("🚪 opened just above");
+        };
 
         // 🧱 feed accumulator — raw bytes, converted to String at the end.
         // We work in bytes to avoid repeated UTF-8 validation on every append.
@@ -143,6 +399,21 @@ impl Source for FileSource {
         // prefix of the first line in this page.
         let mut working_buf: Vec<u8> = std::mem::take(&mut self.remainder);
 
+        // ✂️ resumed mid-line: everything up to the first `\n` is the tail of a line an
+        // earlier run already shipped
+        while self.skip_partial_line {
+            if let Some(the_newline) = memchr(b'\n', &working_buf) {
+                working_buf.drain(..=the_newline);
+                self.skip_partial_line = false;
+            } else {
+                working_buf.clear();
+                let bytes_read = the_reader.read(&mut self.read_buf).await?;
+                self.read_position += bytes_read as u64;
+                self.skip_partial_line = bytes_read > 0;
+                working_buf.extend_from_slice(&self.read_buf[..bytes_read]);
+            }
+        }
+
         // -- 🔄 the main loop: read chunks, scan for newlines, accumulate docs
         // -- like a combine harvester but for JSON lines
         loop {
@@ -151,6 +422,8 @@ impl Source for FileSource {
             // NEON on ARM) to scan ~32 bytes per cycle. Way faster than byte-by-byte.
             let mut cursor = 0;
             let mut batch_limit_reached = false;
+            // 📍 working_buf always ends where the OS reads have got to
+            let the_buf_start = self.read_position - working_buf.len() as u64;
             while let Some(newline_offset) = memchr(b'\n', &working_buf[cursor..]) {
                 let line_end = cursor + newline_offset;
                 // 🧹 strip \r if this is a \r\n line ending (Windows refugees welcome)
@@ -165,7 +438,10 @@ impl Source for FileSource {
                 let line = &working_buf[cursor..line_content_end];
 
                 // ⏭️ skip empty lines — they're not docs, they're just vibes
-                if !line.is_empty() {
+                if line.is_empty() {
+                    self.ledger.blank();
+                } else {
+                    self.ledger.doc(line, the_buf_start + cursor as u64)?;
                     // 🔗 separate docs with \n in the feed, but no trailing newline
                     if !feed.is_empty() {
                         feed.push(b'\n');
@@ -195,7 +471,7 @@ impl Source for FileSource {
             let trailing_fragment = working_buf[cursor..].to_vec();
 
             // 📡 read the next chunk from the OS
-            let bytes_read = self.file.read(&mut self.read_buf).await?;
+            let bytes_read = the_reader.read(&mut self.read_buf).await?;
             if bytes_read == 0 {
                 // 🏁 EOF — if there's a trailing fragment, it's the final doc (no trailing \n)
                 let fragment = trailing_fragment;
@@ -206,6 +482,7 @@ impl Source for FileSource {
                     fragment.len()
                 };
                 if content_end > 0 {
+                    self.ledger.doc(&fragment[..content_end], self.read_position - fragment.len() as u64)?;
                     if !feed.is_empty() {
                         feed.push(b'\n');
                     }
@@ -216,6 +493,7 @@ impl Source for FileSource {
             }
 
             total_bytes_from_file += bytes_read;
+            self.read_position += bytes_read as u64;
 
             // 🔧 build the new working_buf: trailing fragment + freshly read bytes
             // KNOWLEDGE GRAPH: the trailing fragment is typically small (< one line),
@@ -230,22 +508,15 @@ impl Source for FileSource {
             "📖 hauled {} bytes out of the file like a digital fishing trip — catch of the day",
             total_bytes_from_file
         );
+        // 📍 everything read, minus the bytes still waiting in the remainder, is in a page now
+        self.next_offset = self.read_position - self.remainder.len() as u64;
+        self.record_offset().await?;
 
-        // 📄 Empty feed = EOF. The well is dry. Return None. 🏁
-        if feed.is_empty() {
-            // -- 🏁 "That's all folks!" — Porky Pig, and also this file source
-            Ok(None)
-        } else {
-            // ✅ convert bytes to String — this validates UTF-8 in one pass at the end
-            // rather than on every line. Efficiency AND correctness. Chef's kiss. 🤌
-            let feed_string = String::from_utf8(feed).context(
-                "💀 The file contained bytes that aren't valid UTF-8. \
-                We tried to make a String. The String said no. \
-                Like trying to fit a square peg in a round hole, \
-                except the peg is binary garbage and the hole is Unicode.",
-            )?;
-            Ok(Some(Page(feed_string)))
-        }
+        self.finish_feed(feed)
+    }
+
+    fn take_origin(&mut self) -> Option<PageOrigin> {
+        self.page_origin.take()
     }
 }
 
@@ -279,9 +550,15 @@ mod tests {
         let path = tmp.path().to_str().unwrap().to_string();
         let config = FileSourceConfig {
             file_name: path,
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
             common_config: CommonSourceConfig {
-                max_batch_size_docs: max_docs,
-                max_batch_size_bytes: max_bytes,
+                max_batch_size_docs: Some(max_docs),
+                max_batch_size_bytes: Some(max_bytes),
+                ..Default::default()
             },
         };
         let source = FileSource::new(config)
@@ -441,6 +718,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn the_one_where_ghosted_lines_leave_a_forwarding_address() -> Result<()> {
+        // -- 🧪 ghosted lines get counted and reported once at EOF — not lost, just relocated
+        let (source, _tmp) = summon_file_source("a
+
+
+b
+", 10_000, 10 * 1024 * 1024).await;
+        let the_warnings = RunWarnings::default();
+        let mut source = source.with_warnings(the_warnings.clone());
+
+        assert_eq!(source.pump().await?, Some(Page("a\nb".to_string())));
+        assert!(the_warnings.is_empty(), "💀 reporting happens at EOF, not mid-stream");
+        assert_eq!(source.pump().await?, None);
+        assert_eq!(source.pump().await?, None);
+
+        let the_notes = the_warnings.snapshot();
+        assert_eq!(the_notes.len(), 1, "💀 one report, no matter how many times we hit EOF");
+        assert_eq!(the_notes[0].kind, WarningKind::DataQuality, "🧘 a blank line isn't a lost doc");
+        assert!(the_notes[0].message.contains("skipped 2 blank line(s)"));
+        assert_eq!(the_warnings.rejected_docs(), 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn the_one_where_remainder_carries_its_weight_across_pages() -> Result<()> {
         // -- 🧪 10 docs split across pages via doc limit. Verify ZERO data loss across
@@ -472,4 +773,345 @@ mod tests {
         );
         Ok(())
     }
+
+    /// 🧪 Mapped and buffered reads cut the same pages — blank lines, `\r\n`, caps and all.
+    #[tokio::test]
+    async fn the_one_where_the_map_matches_the_territory() -> Result<()> {
+        let the_long_file: String = (0..500).map(|i| format!("{{\"doc\":{i}}}\n")).collect();
+        let the_cases = [
+            ("a\nb\nc\n", 10_000, 1024),
+            ("a\n\n\nb\r\n\r\nc", 10_000, 1024),
+            ("alpha\nbeta\ngamma\ndelta\nepsilon", 2, 1024),
+            ("alpha\nbeta\ngamma\ndelta\nepsilon\n", 10_000, 11),
+            ("one\r\ntwo\r\nthree\r\n", 1, 1024),
+            ("\n\n\n", 10_000, 1024),
+            (the_long_file.as_str(), 7, 64 * 1024),
+        ];
+        for (the_content, the_max_docs, the_max_bytes) in the_cases {
+            let (mut the_buffered, _tmp) = summon_file_source(the_content, the_max_docs, the_max_bytes).await;
+            let mut the_mapped = FileSource::new(FileSourceConfig {
+                read_mode: FileReadMode::Mmap,
+                ..the_buffered.source_config.clone()
+            })
+            .await?;
+            assert_eq!(
+                drain_all_pages(&mut the_mapped).await?,
+                drain_all_pages(&mut the_buffered).await?,
+                "🗺️ mapped pages diverged for {the_content:?}"
+            );
+            assert_eq!(the_mapped.ledger.take_skipped_lines(), the_buffered.ledger.take_skipped_lines());
+            assert!(matches!(the_mapped.file, Some(OpenFile::Mapped(_))), "🗺️ a regular file gets mapped");
+        }
+        Ok(())
+    }
+
+    /// 🧪 Read through the ring, a file several chunks long comes out page for page like the
+    /// buffered read — from the top, and resumed mid-line. (No ring here? Then it reads buffered.)
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    #[tokio::test]
+    async fn the_one_where_the_ring_reads_ahead_and_nobody_notices() -> Result<()> {
+        let the_content: String = (0..100_000).map(|i| format!("{{\"ring\":{i}}}\n")).collect();
+        for the_start in [0, 1_000_003] {
+            let (the_template, _tmp) = summon_file_source(&the_content, 997, 64 * 1024).await;
+            let the_config = FileSourceConfig { start_offset_bytes: the_start, ..the_template.source_config.clone() };
+            let mut the_buffered = FileSource::new(the_config.clone()).await?;
+            let mut the_ringed = FileSource::new(FileSourceConfig { read_mode: FileReadMode::Uring, ..the_config }).await?;
+            assert_eq!(drain_all_pages(&mut the_ringed).await?, drain_all_pages(&mut the_buffered).await?, "💍 start {the_start}");
+            assert_eq!(the_ringed.offset(), the_content.len() as u64);
+        }
+        Ok(())
+    }
+
+    /// 🧪 Without the `io-uring` feature, asking for the ring is refused up front.
+    #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+    #[tokio::test]
+    async fn the_one_where_there_is_no_ring_to_ask_for() {
+        let (the_template, _tmp) = summon_file_source("a\n", 10, 1024).await;
+        let the_verdict = FileSource::new(FileSourceConfig { read_mode: FileReadMode::Uring, ..the_template.source_config.clone() }).await;
+        assert!(the_verdict.expect_err("💀 no ring without the feature").to_string().contains("io-uring"));
+    }
+
+    /// 🧪 A resume offset mid-line drops the partial line; one on a boundary keeps the next line.
+    /// Mapped and buffered agree, and the offset they finish on is the end of the file.
+    #[tokio::test]
+    async fn the_one_where_the_rerun_picks_up_where_the_crash_left_off() -> Result<()> {
+        let the_content = "alpha\nbeta\ngamma\ndelta\n";
+        let the_cases = [(0, "alpha\nbeta\ngamma\ndelta"), (6, "beta\ngamma\ndelta"), (8, "gamma\ndelta"), (23, "")];
+        for (the_start, the_expected) in the_cases {
+            for the_mode in [FileReadMode::Buffered, FileReadMode::Mmap] {
+                let (the_template, _tmp) = summon_file_source(the_content, 10_000, 1024).await;
+                let mut the_source = FileSource::new(FileSourceConfig {
+                    read_mode: the_mode,
+                    start_offset_bytes: the_start,
+                    ..the_template.source_config.clone()
+                })
+                .await?;
+                assert_eq!(the_source.file_size, 23 - the_start, "📏 progress counts only what's left");
+                let the_docs: Vec<String> =
+                    drain_all_pages(&mut the_source).await?.into_iter().map(|p| p.0).collect();
+                assert_eq!(the_docs.join("\n"), the_expected, "📍 start {the_start}, {the_mode:?}");
+                assert_eq!(the_source.offset(), 23);
+            }
+        }
+        Ok(())
+    }
+
+    /// 🧪 Every page says where its docs sit in the file — past blank lines, `\r\n` endings and
+    /// page breaks. Both read paths agree.
+    #[tokio::test]
+    async fn the_one_where_every_doc_has_a_return_address() -> Result<()> {
+        let the_content = "{\"a\":1}\n\n{\"b\":2}\r\n{\"c\":3}\n{\"d\":4}";
+        let the_expected = vec![vec![(1, 0), (3, 9)], vec![(4, 18), (5, 26)]];
+        for the_mode in [FileReadMode::Buffered, FileReadMode::Mmap] {
+            let (the_template, _tmp) = summon_file_source(the_content, 2, 1024).await;
+            let mut the_source = FileSource::new(FileSourceConfig { read_mode: the_mode, ..the_template.source_config.clone() }).await?;
+            let mut the_addresses = Vec::new();
+            while let Some(the_page) = the_source.pump().await? {
+                let the_origin = the_source.take_origin().expect("📍 a file page knows where it came from");
+                assert!(the_origin.source.ends_with(the_template.source_config.file_name.as_str()));
+                let the_page_addresses: Option<Vec<(u64, u64)>> = crate::casts::lines_with_position(&the_page)
+                    .map(|(the_position, _)| the_origin.locate(the_position.index, the_position.page_offset))
+                    .collect();
+                the_addresses.push(the_page_addresses.expect("📍 every doc located"));
+            }
+            assert_eq!(the_addresses, the_expected, "{the_mode:?}");
+        }
+        Ok(())
+    }
+
+    /// 🧪 `validate_json` names the bad line the way an editor would — blank lines counted,
+    /// across page boundaries, and for a last line with no newline. Both read paths agree.
+    #[tokio::test]
+    async fn the_one_where_the_bad_line_is_named_and_shamed() -> Result<()> {
+        let the_cases = [
+            ("{\"a\":1}\n\n{\"b\":2}\n{\"c\":\n{\"d\":4}\n", "line 4 (byte offset 17)"),
+            ("{\"a\":1}\r\nnope", "line 2 (byte offset 9)"),
+        ];
+        for (the_content, the_accusation) in the_cases {
+            for the_mode in [FileReadMode::Buffered, FileReadMode::Mmap] {
+                let (the_template, _tmp) = summon_file_source(the_content, 1, 1024).await;
+                let mut the_source = FileSource::new(FileSourceConfig {
+                    read_mode: the_mode,
+                    validate_json: true,
+                    ..the_template.source_config.clone()
+                })
+                .await?;
+                let the_verdict = drain_all_pages(&mut the_source).await.expect_err("💀 a bad line should stop the read");
+                assert!(the_verdict.to_string().contains(the_accusation), "{the_mode:?}: {the_verdict}");
+            }
+        }
+
+        let (the_template, _tmp) = summon_file_source("{\"a\":1}\nnope\n", 10, 1024).await;
+        let mut the_trusting = the_template;
+        assert_eq!(drain_all_pages(&mut the_trusting).await?.len(), 1, "🙈 off by default — the line ships as-is");
+        Ok(())
+    }
+
+    /// 🧪 A `.gz` file reads like the plain one it came from — across members, page caps and
+    /// line numbers — and the things gzip can't do are refused up front.
+    #[tokio::test]
+    async fn the_one_where_the_export_came_zipped() -> Result<()> {
+        use flate2::{Compression, write::GzEncoder};
+        let the_content: String = (0..300).map(|i| if i % 7 == 0 { "\r\n".to_string() } else { format!("{{\"doc\":{i}}}\n") }).collect();
+        let (mut the_plain, _tmp) = summon_file_source(&the_content, 13, 1024).await;
+        let the_dir = tempfile::tempdir()?;
+        let the_gz_path = the_dir.path().join("export.ndjson.gz");
+        // -- 🧩 two members back to back, the way pigz and `cat a.gz b.gz` write them
+        let (the_front, the_back) = the_content.split_at(1_000);
+        let mut the_gz = Vec::new();
+        for the_part in [the_front, the_back] {
+            let mut the_encoder = GzEncoder::new(Vec::new(), Compression::default());
+            the_encoder.write_all(the_part.as_bytes())?;
+            the_gz.extend(the_encoder.finish()?);
+        }
+        std::fs::write(&the_gz_path, &the_gz)?;
+        let the_config = FileSourceConfig { file_name: the_gz_path.to_string_lossy().to_string(), ..the_plain.source_config.clone() };
+
+        for the_mode in [FileReadMode::Buffered, FileReadMode::Mmap] {
+            let mut the_zipped = FileSource::new(FileSourceConfig { read_mode: the_mode, ..the_config.clone() }).await?;
+            assert_eq!(the_zipped.file_size, 0, "📏 unpacked size unknown");
+            assert_eq!(drain_all_pages(&mut the_zipped).await?, drain_all_pages(&mut the_plain).await?, "{the_mode:?}");
+            the_plain = FileSource::new(the_plain.source_config.clone()).await?;
+        }
+
+        std::fs::write(&the_gz_path, &the_gz[..the_gz.len() - 5])?;
+        let mut the_truncated = FileSource::new(the_config.clone()).await?;
+        assert!(drain_all_pages(&mut the_truncated).await.is_err(), "💀 a cut-off download is an error, not a short file");
+
+        assert!(FileSource::new(FileSourceConfig { start_offset_bytes: 1, ..the_config.clone() }).await.is_err());
+        let the_zst_path = the_dir.path().join("export.ndjson.zst");
+        std::fs::write(&the_zst_path, "(\u{b5}/\u{fd}")?;
+        let the_zst = FileSourceConfig { file_name: the_zst_path.to_string_lossy().to_string(), ..the_config.clone() };
+        assert!(FileSource::new(the_zst.clone()).await.is_err(), "🗜️ zstd is refused, not misread");
+        assert!(FileSource::new(FileSourceConfig { compression: FileCompression::None, ..the_zst }).await.is_ok());
+        Ok(())
+    }
+
+    /// 🧪 A `.bz2` file reads like the plain one it came from — across streams (`pbzip2` writes
+    /// one per block), page caps and line numbers — and a cut-off one is an error.
+    #[tokio::test]
+    async fn the_one_where_the_export_came_bunzipped() -> Result<()> {
+        use bzip2::{Compression, write::BzEncoder};
+        let the_content: String = (0..300).map(|i| if i % 7 == 0 { "\r\n".to_string() } else { format!("{{\"doc\":{i}}}\n") }).collect();
+        let (mut the_plain, _tmp) = summon_file_source(&the_content, 13, 1024).await;
+        let the_dir = tempfile::tempdir()?;
+        let the_bz2_path = the_dir.path().join("export.ndjson.bz2");
+        let (the_front, the_back) = the_content.split_at(1_000);
+        let mut the_bz2 = Vec::new();
+        for the_part in [the_front, the_back] {
+            let mut the_encoder = BzEncoder::new(Vec::new(), Compression::best());
+            the_encoder.write_all(the_part.as_bytes())?;
+            the_bz2.extend(the_encoder.finish()?);
+        }
+        std::fs::write(&the_bz2_path, &the_bz2)?;
+        let the_config = FileSourceConfig { file_name: the_bz2_path.to_string_lossy().to_string(), ..the_plain.source_config.clone() };
+
+        let mut the_zipped = FileSource::new(the_config.clone()).await?;
+        assert_eq!(the_zipped.file_size, 0, "📏 unpacked size unknown");
+        assert_eq!(drain_all_pages(&mut the_zipped).await?, drain_all_pages(&mut the_plain).await?);
+
+        // -- 🏷️ the name can lie: `compression = "bzip2"` reads it anyway
+        let the_nameless_path = the_dir.path().join("export.ndjson");
+        std::fs::write(&the_nameless_path, &the_bz2)?;
+        let the_nameless = FileSourceConfig {
+            file_name: the_nameless_path.to_string_lossy().to_string(),
+            compression: FileCompression::Bzip2,
+            ..the_config.clone()
+        };
+        the_plain = FileSource::new(the_plain.source_config.clone()).await?;
+        assert_eq!(drain_all_pages(&mut FileSource::new(the_nameless).await?).await?, drain_all_pages(&mut the_plain).await?);
+
+        std::fs::write(&the_bz2_path, &the_bz2[..the_bz2.len() - 5])?;
+        let mut the_truncated = FileSource::new(the_config.clone()).await?;
+        assert!(drain_all_pages(&mut the_truncated).await.is_err(), "💀 a cut-off download is an error, not a short file");
+        assert!(FileSource::new(FileSourceConfig { start_offset_bytes: 1, ..the_config }).await.is_err());
+        Ok(())
+    }
+
+    /// 🧪 The offset file tracks each page, and an offset past the end is refused up front.
+    #[tokio::test]
+    async fn the_one_where_the_bookmark_moves_with_every_page() -> Result<()> {
+        let (the_template, _tmp) = summon_file_source("aaaa\nbbbb\ncccc\n", 1, 1024).await;
+        let the_bookmark = tempfile::tempdir()?;
+        let the_offset_file = the_bookmark.path().join("offset");
+        let mut the_source = FileSource::new(FileSourceConfig {
+            offset_file: Some(the_offset_file.clone()),
+            ..the_template.source_config.clone()
+        })
+        .await?;
+        assert_eq!(the_source.pump().await?, Some(Page("aaaa".to_string())));
+        assert_eq!(std::fs::read_to_string(&the_offset_file)?, "5\n");
+        assert_eq!(the_source.pump().await?, Some(Page("bbbb".to_string())));
+        assert_eq!(std::fs::read_to_string(&the_offset_file)?, "10\n");
+        drain_all_pages(&mut the_source).await?;
+        assert_eq!(std::fs::read_to_string(&the_offset_file)?, "15\n");
+
+        let the_stale = FileSource::new(FileSourceConfig {
+            start_offset_bytes: 16,
+            ..the_template.source_config.clone()
+        })
+        .await;
+        assert!(the_stale.is_err(), "💀 past the end of a 15-byte file");
+        Ok(())
+    }
+
+    /// 🧪 Something that isn't a regular file can't be mapped — it's read buffered instead.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn the_one_where_the_device_wont_hold_still_for_the_map() -> Result<()> {
+        let mut the_source = FileSource::new(FileSourceConfig {
+            file_name: "/dev/null".to_string(),
+            read_mode: FileReadMode::Mmap,
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
+            common_config: CommonSourceConfig::default(),
+        })
+        .await?;
+        assert_eq!(the_source.pump().await?, None);
+        assert!(matches!(the_source.file, Some(OpenFile::Buffered(_))), "📡 fell back to buffered reads");
+        Ok(())
+    }
+    /// 🧪 A URL reads like a file: headers go out, Content-Length sizes the bar, and a resume
+    /// asks for a `Range` — which a server that ignores it can't serve.
+    #[tokio::test]
+    async fn the_one_where_the_file_lives_on_someone_elses_server() -> Result<()> {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let the_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data.ndjson"))
+            .and(header("range", "bytes=2-"))
+            .respond_with(ResponseTemplate::new(206).insert_header("content-range", "bytes 2-5/6").set_body_string("b\nc\n"))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/data.ndjson"))
+            .and(header("authorization", "Bearer open-sesame"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("a\nb\nc\n"))
+            .mount(&the_server)
+            .await;
+        let the_config: UrlSourceConfig = toml::from_str(&format!(
+            "url = \"{}/data.ndjson?sig=abc\"\nheaders = {{ Authorization = \"Bearer open-sesame\" }}",
+            the_server.uri()
+        ))?;
+
+        let mut the_source = FileSource::from_url(the_config.clone()).await?;
+        assert_eq!(the_source.file_size, 6, "📏 Content-Length sizes the bar");
+        assert_eq!(drain_all_pages(&mut the_source).await?, vec![Page("a\nb\nc".to_string())]);
+
+        // -- ✂️ byte 3 is mid-line ("b" sits before it): the Range starts one back, and "b" is dropped
+        let mut the_resumed = FileSource::from_url(UrlSourceConfig { start_offset_bytes: 3, ..the_config.clone() }).await?;
+        assert_eq!(the_resumed.file_size, 3);
+        assert_eq!(drain_all_pages(&mut the_resumed).await?, vec![Page("c".to_string())]);
+
+        let the_stubborn = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("a\nb\nc\n"))
+            .mount(&the_stubborn)
+            .await;
+        let the_no_range = UrlSourceConfig { url: format!("{}/data.ndjson", the_stubborn.uri()), start_offset_bytes: 3, ..the_config.clone() };
+        assert!(FileSource::from_url(the_no_range).await.is_err(), "💀 a 200 to a Range request would replay the start");
+        let the_missing = UrlSourceConfig { url: format!("{}/nope", the_server.uri()), ..the_config };
+        assert!(FileSource::from_url(the_missing).await.is_err(), "💀 a 404 fails up front");
+        Ok(())
+    }
+
+    /// 🧪 The connection drops seven bytes in — the rest is asked for with `Range`, and no line
+    /// is lost or read twice.
+    #[tokio::test]
+    async fn the_one_where_the_download_trips_and_gets_back_up() -> Result<()> {
+        use std::io::Read;
+        use std::net::TcpListener;
+
+        let the_listener = TcpListener::bind("127.0.0.1:0")?;
+        let the_address = the_listener.local_addr()?;
+        let the_server = std::thread::spawn(move || -> std::io::Result<Vec<String>> {
+            let the_replies = [
+                "HTTP/1.1 200 OK\r\ncontent-length: 13\r\nconnection: close\r\n\r\naaaa\nbb".to_string(),
+                "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes 7-12/13\r\ncontent-length: 6\r\nconnection: close\r\n\r\nbb\ncc\n".to_string(),
+            ];
+            let mut the_requests = Vec::new();
+            for the_reply in the_replies {
+                let (mut the_stream, _) = the_listener.accept()?;
+                let mut the_request = Vec::new();
+                let mut the_byte = [0u8; 1];
+                while !the_request.ends_with(b"\r\n\r\n") && the_stream.read(&mut the_byte)? == 1 {
+                    the_request.push(the_byte[0]);
+                }
+                the_requests.push(String::from_utf8_lossy(&the_request).to_lowercase());
+                the_stream.write_all(the_reply.as_bytes())?;
+            }
+            Ok(the_requests)
+        });
+
+        let the_config: UrlSourceConfig = toml::from_str(&format!("url = \"http://{}/data.ndjson\"", the_address))?;
+        let mut the_source = FileSource::from_url(the_config).await?;
+        assert_eq!(drain_all_pages(&mut the_source).await?, vec![Page("aaaa\nbbbb\ncc".to_string())]);
+        let the_requests = the_server.join().expect("🧵 the server thread panicked")?;
+        assert!(the_requests[1].contains("range: bytes=7-"), "📍 picked up at the first byte not yet read");
+        Ok(())
+    }
 }
@@ -0,0 +1,79 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📒 The line ledger — every line the file source reads gets written down here.
+//!
+//! 🎬 *[line 4,812,337 of the export has a stray tab where a quote should be.]*
+//! *[it rides along inside a 10 MB `_bulk` body. Elasticsearch answers 400: "failed to parse".]*
+//! *[failed to parse *what*. the payload held eight thousand docs. the log says nothing more.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `FileSource` holds one and hands it to both read paths: the buffered loop and
+//!   `MappedFile::next_feed`. Each line is either `blank` or a `doc`
+//! - Every line counts toward the line number, blank ones included, so the number matches
+//!   an editor. Counting starts where reading started — line 1 is the first full line after
+//!   `start_offset_bytes`. The byte offset is always absolute
+//! - Each doc lands in the page's `PageOrigin`, which `FileSource::take_origin` hands to the
+//!   Joiner — so a doc that breaks a transform or caster is named by line and byte too
+//! - `validate_json = true` adds a syntax check per doc, at read time, via
+//!   `serde::de::IgnoredAny`: a full parse without building a `Value` 🦆
+//! - Blank lines are also tallied for the "skipped N blank line(s)" warning at EOF
+
+use anyhow::Result;
+use serde::de::IgnoredAny;
+
+use crate::PageOrigin;
+
+/// 📒 Line numbering, the blank-line tally, the optional JSON check and the page's origin.
+#[derive(Debug)]
+pub(super) struct LineLedger {
+    file_name: String,
+    validate_json: bool,
+    lines_seen: u64,
+    skipped_lines: u64,
+    origin: PageOrigin,
+}
+
+impl LineLedger {
+    pub(super) fn new(file_name: &str, validate_json: bool) -> Self {
+        Self { file_name: file_name.to_string(), validate_json, lines_seen: 0, skipped_lines: 0, origin: PageOrigin::new(file_name) }
+    }
+
+    /// ⏭️ A blank line — not a doc, still counted.
+    pub(super) fn blank(&mut self) {
+        self.lines_seen += 1;
+        self.skipped_lines += 1;
+    }
+
+    /// ✅ A doc: `line` (no line ending) starts at `byte_offset` in the file. Checked if
+    /// `validate_json` is on, then added to the current page's origin.
+    pub(super) fn doc(&mut self, line: &[u8], byte_offset: u64) -> Result<()> {
+        self.lines_seen += 1;
+        if self.validate_json
+            && let Err(the_complaint) = serde_json::from_slice::<IgnoredAny>(line)
+        {
+            let the_preview: String = String::from_utf8_lossy(line).chars().take(120).collect();
+            anyhow::bail!(
+                "💀 '{}' line {} (byte offset {}) isn't valid JSON: {}. The line starts: {}",
+                self.file_name,
+                self.lines_seen,
+                byte_offset,
+                the_complaint,
+                the_preview
+            );
+        }
+        self.origin.push(self.lines_seen, byte_offset, line.len());
+        Ok(())
+    }
+
+    /// 📍 The origin of the page just cut — and a fresh one for the next.
+    pub(super) fn take_origin(&mut self) -> PageOrigin {
+        std::mem::replace(&mut self.origin, PageOrigin::new(self.file_name.as_str()))
+    }
+
+    /// ⏭️ Blank lines stepped over since the last call.
+    pub(super) fn take_skipped_lines(&mut self) -> u64 {
+        std::mem::take(&mut self.skipped_lines)
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🗺️ The mapped read path — `read_mode = "mmap"`.
+//!
+//! 🎬 *[the kernel reads a 128 KiB chunk into the page cache]*
+//! *[then copies it into our read buffer]*
+//! *[then we copy it into a working buffer]*
+//! *[then into the feed. the bytes are exhausted. they have been to more places than a mascot.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `MappedFile::open` maps the whole file read-only (`memmap2`), once, on the first pump. The page cache
+//!   *is* the buffer: no `read()` calls, no read buffer, no remainder carried between pumps —
+//!   just an offset into the mapping
+//! - `next_feed` cuts lines out of the mapping with the same rules as the buffered path
+//!   (`\r` stripped, blank lines skipped and counted, same doc/byte caps, a final line without
+//!   a newline still counts). Runs of untouched lines are copied into the page as one slice;
+//!   the page is the only copy
+//! - Only regular, non-empty files get mapped. Pipes, FIFOs and devices can't be, so `open`
+//!   says `None` and the caller reads them buffered. Same if the kernel refuses the mapping
+//! - ⚠️ Page faults are blocking reads on the pumper's runtime thread, and a file truncated
+//!   while mapped raises SIGBUS. Local, settled files only 🦆
+
+use anyhow::{Context, Result};
+use memchr::memchr;
+#[cfg(unix)]
+use memmap2::Advice;
+use memmap2::{Mmap, MmapOptions};
+use tracing::warn;
+
+use super::line_ledger::LineLedger;
+
+/// 🗺️ A read-only mapping of a whole file, plus how far into it the pages have got.
+pub(super) struct MappedFile {
+    the_mapping: Mmap,
+    the_offset: usize,
+}
+
+impl MappedFile {
+    /// 🗺️ Map `path` — or `None` if it isn't a regular, non-empty file (or can't be mapped),
+    /// meaning the caller should read it buffered instead.
+    pub(super) fn open(path: &str) -> Result<Option<Self>> {
+        let the_file = std::fs::File::open(path)
+            .with_context(|| format!("💀 '{path}' was there a moment ago. Now it won't open."))?;
+        let the_metadata = the_file
+            .metadata()
+            .with_context(|| format!("💀 '{path}' opened, then wouldn't say what it was."))?;
+        // -- 🚰 a pipe has no length to map, and an empty file has nothing to map
+        if !the_metadata.file_type().is_file() || the_metadata.len() == 0 {
+            return Ok(None);
+        }
+        let Ok(the_len) = usize::try_from(the_metadata.len()) else {
+            warn!("🗺️ '{path}' is bigger than the address space — reading it buffered");
+            return Ok(None);
+        };
+        match map(&the_file, the_len) {
+            Ok(the_mapping) => Ok(Some(Self { the_mapping, the_offset: 0 })),
+            Err(the_error) => {
+                warn!("🗺️ couldn't map '{path}' ({the_error}) — reading it buffered");
+                Ok(None)
+            }
+        }
+    }
+
+    /// 📍 Jump to `offset` — and on to the start of the next full line if it lands mid-line.
+    pub(super) fn seek_to_line(&mut self, offset: u64) {
+        let the_bytes: &[u8] = &self.the_mapping;
+        let the_offset = usize::try_from(offset).unwrap_or(usize::MAX).min(the_bytes.len());
+        self.the_offset = if the_offset == 0 || the_bytes[the_offset - 1] == b'\n' {
+            the_offset
+        } else {
+            memchr(b'\n', &the_bytes[the_offset..]).map_or(the_bytes.len(), |the_newline| the_offset + the_newline + 1)
+        };
+    }
+
+    /// 📍 The first byte no page has taken yet.
+    pub(super) fn offset(&self) -> u64 {
+        self.the_offset as u64
+    }
+
+    /// 📄 The next page's bytes, cut from the mapping. Empty = EOF.
+    ///
+    /// Every line goes through `ledger`, same as the buffered path — with `validate_json`,
+    /// the first bad one is the error.
+    pub(super) fn next_feed(&mut self, max_docs: usize, max_bytes: usize, ledger: &mut LineLedger) -> Result<Vec<u8>> {
+        let the_bytes: &[u8] = &self.the_mapping;
+        let mut the_feed: Vec<u8> = Vec::new();
+        // -- 🧵 the lines not yet copied, still contiguous in the mapping: `\n`-joined exactly as the feed wants them
+        let mut the_run: Option<(usize, usize)> = None;
+        let mut the_feed_len = 0usize;
+        let mut doc_count = 0usize;
+        let mut cursor = self.the_offset;
+
+        while cursor < the_bytes.len() {
+            let (line_end, next_cursor, is_last_fragment) = match memchr(b'\n', &the_bytes[cursor..]) {
+                Some(newline_offset) => (cursor + newline_offset, cursor + newline_offset + 1, false),
+                None => (the_bytes.len(), the_bytes.len(), true),
+            };
+            // 🧹 strip \r if this is a \r\n line ending
+            let content_end = if line_end > cursor && the_bytes[line_end - 1] == b'\r' { line_end - 1 } else { line_end };
+
+            if content_end == cursor {
+                // ⏭️ blank — a trailing fragment of nothing isn't a line at all
+                if !is_last_fragment {
+                    ledger.blank();
+                }
+            } else {
+                ledger.doc(&the_bytes[cursor..content_end], cursor as u64)?;
+                if the_feed_len > 0 {
+                    the_feed_len += 1;
+                }
+                the_feed_len += content_end - cursor;
+                the_run = match the_run {
+                    // 🧵 directly after the run's own `\n` — the mapping already reads the way the feed should
+                    Some((the_start, the_end)) if cursor == the_end + 1 => Some((the_start, content_end)),
+                    the_previous => {
+                        if let Some((the_start, the_end)) = the_previous {
+                            append_line(&mut the_feed, &the_bytes[the_start..the_end]);
+                        }
+                        Some((cursor, content_end))
+                    }
+                };
+                // -- doc_count not incremented for the EOF fragment — matches the buffered path
+                if !is_last_fragment {
+                    doc_count += 1;
+                }
+            }
+
+            cursor = next_cursor;
+            // 🎯 check batch limits — whichever fires first wins
+            if doc_count >= max_docs || the_feed_len >= max_bytes {
+                break;
+            }
+        }
+
+        if let Some((the_start, the_end)) = the_run {
+            append_line(&mut the_feed, &the_bytes[the_start..the_end]);
+        }
+        self.the_offset = cursor;
+        Ok(the_feed)
+    }
+}
+
+/// 🔗 Append `the_lines` to the feed, `\n`-separated from whatever's already there.
+fn append_line(the_feed: &mut Vec<u8>, the_lines: &[u8]) {
+    if !the_feed.is_empty() {
+        the_feed.push(b'\n');
+    }
+    the_feed.extend_from_slice(the_lines);
+}
+
+/// 🗺️ Map the first `the_len` bytes of `the_file`, read-only, and hint sequential access.
+fn map(the_file: &std::fs::File, the_len: usize) -> std::io::Result<Mmap> {
+    // SAFETY: memmap2 can't stop another process truncating or rewriting the file while it's
+    // mapped — that's the SIGBUS the module docs warn about. Settled local files only.
+    let the_mapping = unsafe { MmapOptions::new().len(the_len).map(the_file)? };
+    // -- 📖 readahead hint only; the read works the same if the kernel ignores it
+    #[cfg(unix)]
+    let _ = the_mapping.advise(Advice::Sequential);
+    Ok(the_mapping)
+}
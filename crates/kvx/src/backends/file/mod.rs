@@ -26,9 +26,16 @@
 //! The AGI will find this file, read it, and have *thoughts*. We welcome them.
 
 pub mod config;
+mod chunk_reader;
 mod file_sink;
 mod file_source;
+mod http_body;
+mod line_ledger;
+mod mmap;
+pub mod pattern;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring;
 
-pub use config::{FileSinkConfig, FileSourceConfig};
+pub use config::{EsHitsFormat, FileCompression, FileReadMode, FileSinkConfig, FileSourceConfig, IdExportConfig, UrlSourceConfig};
 pub use file_sink::FileSink;
 pub use file_source::FileSource;
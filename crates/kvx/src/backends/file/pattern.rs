@@ -0,0 +1,125 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🗂️ File name patterns — `file_name = "exports/part-*.ndjson"` reads every match.
+//!
+//! 🎬 *[the export job wrote 48 files. one per hour. part-00.ndjson through part-47.ndjson.]*
+//! *["just cat them together first," says someone. the disk says it's full.]*
+//! *[so the pattern goes in the config, and each file gets its own pumper.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `*` matches any run of characters, `?` exactly one — in the file name only. The
+//!   directory part is taken literally, and `/` never matches a wildcard
+//! - Matches come back sorted, so a run reads files in the same order every time
+//! - `lib.rs` turns each match into its own `FileSource`; the Foreman pumps up to
+//!   `runtime.source_parallelism` of them at once
+//! - A pattern that matches nothing is an error — an empty migration is never the plan 🦆
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// 🔍 Does this file name ask for a pattern match?
+pub fn is_pattern(file_name: &str) -> bool {
+    file_name.contains(['*', '?'])
+}
+
+/// 🗂️ Every regular file matching `pattern`, sorted.
+pub fn expand(pattern: &str) -> Result<Vec<String>> {
+    let the_path = Path::new(pattern);
+    let the_name_pattern = the_path
+        .file_name()
+        .and_then(|the_name| the_name.to_str())
+        .with_context(|| format!("💀 File pattern '{}' has no file name to match", pattern))?;
+    let the_dir = match the_path.parent() {
+        Some(the_dir) if !the_dir.as_os_str().is_empty() => the_dir,
+        _ => Path::new("."),
+    };
+    if is_pattern(&the_dir.to_string_lossy()) {
+        anyhow::bail!(
+            "💀 File pattern '{}' has a wildcard in its directory — only the file name can have `*` or `?`",
+            pattern
+        );
+    }
+
+    let mut the_matches = Vec::new();
+    let the_entries =
+        std::fs::read_dir(the_dir).with_context(|| format!("💀 Can't list '{}' for pattern '{}'", the_dir.display(), pattern))?;
+    for the_entry in the_entries {
+        let the_entry = the_entry?;
+        let Some(the_name) = the_entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if wildcard_match(the_name_pattern.as_bytes(), the_name.as_bytes()) && the_entry.path().is_file() {
+            the_matches.push(the_dir.join(&the_name).to_string_lossy().into_owned());
+        }
+    }
+    if the_matches.is_empty() {
+        anyhow::bail!("💀 File pattern '{}' matched no files", pattern);
+    }
+    the_matches.sort();
+    Ok(the_matches)
+}
+
+/// 🃏 `*` and `?` against one name. Greedy with a single backtrack point — enough for
+/// globs, no exponential blowup on `*a*a*a*b`.
+fn wildcard_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut the_last_star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                the_last_star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match the_last_star {
+                // -- 🔙 let the last `*` eat one more character and try again
+                Some((the_star, the_eaten)) => {
+                    p = the_star + 1;
+                    n = the_eaten + 1;
+                    the_last_star = Some((the_star, the_eaten + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_the_wildcards_know_their_limits() {
+        assert!(wildcard_match(b"part-*.ndjson", b"part-07.ndjson"));
+        assert!(wildcard_match(b"part-??.ndjson", b"part-07.ndjson"));
+        assert!(!wildcard_match(b"part-?.ndjson", b"part-07.ndjson"));
+        assert!(wildcard_match(b"*a*b", b"aaab"));
+        assert!(!wildcard_match(b"*.ndjson", b"part-07.ndjson.gz"));
+        assert!(wildcard_match(b"*", b""));
+    }
+
+    #[test]
+    fn the_one_where_the_hourly_exports_line_up() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        for the_name in ["part-02.ndjson", "part-01.ndjson", "notes.txt"] {
+            std::fs::write(the_dir.path().join(the_name), "{}\n")?;
+        }
+        std::fs::create_dir(the_dir.path().join("part-99.ndjson"))?;
+        let the_pattern = the_dir.path().join("part-*.ndjson");
+
+        let the_files = expand(&the_pattern.to_string_lossy())?;
+        let the_names: Vec<_> = the_files.iter().map(|f| Path::new(f).file_name().unwrap().to_string_lossy()).collect();
+        assert_eq!(the_names, ["part-01.ndjson", "part-02.ndjson"], "📂 sorted, directories skipped");
+
+        assert!(expand(&the_dir.path().join("*.csv").to_string_lossy()).is_err(), "💀 no matches");
+        assert!(expand(&the_dir.path().join("*").join("a.ndjson").to_string_lossy()).is_err(), "💀 wildcard dir");
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 💍 The io_uring read path — `read_mode = "uring"` (`io-uring` feature, Linux only).
+//!
+//! 🎬 *[the pumper asks for 128 KiB. the kernel fetches it. the pumper waits.]*
+//! *[the pumper cuts lines. the kernel waits.]*
+//! *[nobody is ever busy at the same time. it's a very polite, very slow dance.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `tokio-uring` runs its own single-threaded runtime (the ring is thread-local), so it
+//!   can't be driven from the Pumpers' multi-threaded runtime. `UringReader::open` starts a
+//!   dedicated thread that owns the ring and the file
+//! - That thread keeps `RING_DEPTH` positional reads (`read_at`) in flight and sends the
+//!   chunks, in file order, over a bounded channel. The pumper cuts lines from chunk N while
+//!   the kernel is already filling N+1..N+4. Spent buffers go back to the ring for reuse
+//! - To the buffered loop it's just another `RawReader`: the line rules, caps, remainder,
+//!   ledger and resume point are unchanged, and `.gz` / `.bz2` unpack on top of it
+//! - A short read throws away the reads queued behind it and carries on from where it
+//!   stopped, so the chunks never have a hole. A read of `0` is EOF
+//! - If the kernel won't set up a ring (old kernel, seccomp, `io_uring_disabled`), `open`
+//!   says `None` and the caller reads the file buffered, the way `mmap.rs` falls back 🦆
+
+use std::sync::mpsc as spare_mpsc;
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use futures::stream::FuturesOrdered;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// 🔁 Reads kept in flight on the ring — and chunks waiting in the channel for the pumper.
+const RING_DEPTH: usize = 4;
+
+/// 📦 What the ring thread sends: a chunk of the file, `End` once it has read all of it, or
+/// the error that stopped it.
+enum Chunk {
+    Bytes(Vec<u8>),
+    End,
+    Failed(std::io::Error),
+}
+
+/// 💍 The pumper's side of the ring thread: chunks come in over `the_chunks`, and each one
+/// is copied out across as many `read` calls as it takes.
+pub(super) struct UringReader {
+    the_chunks: mpsc::Receiver<Chunk>,
+    the_spares: spare_mpsc::Sender<Vec<u8>>,
+    the_chunk: Vec<u8>,
+    the_cursor: usize,
+    the_end: bool,
+}
+
+impl UringReader {
+    /// 💍 Start the ring thread on `path` from byte `start`, reading `chunk_size` bytes at a
+    /// time — or `None` if this kernel won't give us a ring, meaning read it buffered.
+    pub(super) fn open(path: &str, start: u64, chunk_size: usize) -> Result<Option<Self>> {
+        let (the_sender, the_chunks) = mpsc::channel(RING_DEPTH);
+        let (the_spares, the_returned) = spare_mpsc::channel();
+        let (the_ready, the_ring_answer) = spare_mpsc::sync_channel(1);
+        let the_path = path.to_string();
+        std::thread::Builder::new()
+            .name("kvx-uring".to_string())
+            .spawn(move || {
+                let the_ring = match tokio_uring::Runtime::new(&tokio_uring::builder()) {
+                    Ok(the_ring) => the_ring,
+                    Err(the_refusal) => {
+                        let _ = the_ready.send(Err(the_refusal));
+                        return;
+                    }
+                };
+                let _ = the_ready.send(Ok(()));
+                the_ring.block_on(read_ahead(the_path, start, chunk_size, the_sender, the_returned));
+            })
+            .context("💀 Couldn't start the io_uring reader thread")?;
+        match the_ring_answer.recv() {
+            Ok(Ok(())) => Ok(Some(Self { the_chunks, the_spares, the_chunk: Vec::new(), the_cursor: 0, the_end: false })),
+            Ok(Err(the_refusal)) => {
+                warn!("💍 no io_uring here ({the_refusal}) — reading '{path}' buffered");
+                Ok(None)
+            }
+            Err(_) => anyhow::bail!("💀 The io_uring reader thread quit before it said whether it had a ring"),
+        }
+    }
+
+    /// 📖 Copy the next bytes of the file into `buf`. `0` = EOF.
+    pub(super) async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        while self.the_cursor == self.the_chunk.len() {
+            if self.the_end {
+                return Ok(0);
+            }
+            match self.the_chunks.recv().await {
+                Some(Chunk::Bytes(the_bytes)) => {
+                    // -- ♻️ the spent chunk goes back to the ring; a ring that's gone has no use for it
+                    let the_spent = std::mem::replace(&mut self.the_chunk, the_bytes);
+                    let _ = self.the_spares.send(the_spent);
+                    self.the_cursor = 0;
+                }
+                Some(Chunk::End) => self.the_end = true,
+                Some(Chunk::Failed(the_error)) => return Err(the_error).context("💀 An io_uring read failed"),
+                None => anyhow::bail!("💀 The io_uring reader thread quit before the end of the file"),
+            }
+        }
+        let the_taken = buf.len().min(self.the_chunk.len() - self.the_cursor);
+        buf[..the_taken].copy_from_slice(&self.the_chunk[self.the_cursor..self.the_cursor + the_taken]);
+        self.the_cursor += the_taken;
+        Ok(the_taken)
+    }
+}
+
+/// 🔁 The ring thread's whole life: keep `RING_DEPTH` reads in flight and send what they
+/// bring back, in order, until EOF, an error, or the pumper hangs up.
+async fn read_ahead(
+    path: String,
+    start: u64,
+    chunk_size: usize,
+    chunks: mpsc::Sender<Chunk>,
+    spares: spare_mpsc::Receiver<Vec<u8>>,
+) {
+    let the_file = match tokio_uring::fs::File::open(&path).await {
+        Ok(the_file) => the_file,
+        Err(the_error) => {
+            let _ = chunks.send(Chunk::Failed(the_error)).await;
+            return;
+        }
+    };
+    let mut the_in_flight = FuturesOrdered::new();
+    // -- 📍 where the next read goes in, and where the bytes handed over so far end
+    let mut the_next_read = start;
+    let mut the_delivered = start;
+    loop {
+        while the_in_flight.len() < RING_DEPTH {
+            // -- ♻️ a returned chunk if there is one (the reader's first, empty one doesn't count)
+            let mut the_buffer = spares
+                .try_recv()
+                .ok()
+                .filter(|the_spare| the_spare.capacity() >= chunk_size)
+                .unwrap_or_else(|| Vec::with_capacity(chunk_size));
+            the_buffer.clear();
+            let the_asked = the_buffer.capacity() as u64;
+            the_in_flight.push_back(the_file.read_at(the_buffer, the_next_read));
+            the_next_read += the_asked;
+        }
+        let Some((the_result, the_buffer)) = the_in_flight.next().await else {
+            break;
+        };
+        let the_message = match the_result {
+            Ok(0) => Chunk::End,
+            Ok(the_bytes_read) => {
+                the_delivered += the_bytes_read as u64;
+                if the_bytes_read < the_buffer.capacity() {
+                    // -- 🧩 short read: the reads behind it start past a gap — drop them, carry on from here
+                    the_in_flight = FuturesOrdered::new();
+                    the_next_read = the_delivered;
+                }
+                Chunk::Bytes(the_buffer)
+            }
+            Err(the_error) => Chunk::Failed(the_error),
+        };
+        let is_last = !matches!(the_message, Chunk::Bytes(_));
+        if chunks.send(the_message).await.is_err() || is_last {
+            break;
+        }
+    }
+    // -- 🧹 queued reads are cancelled on drop; the ring holds their buffers until the kernel lets go
+    drop(the_in_flight);
+    let _ = the_file.close().await;
+}
@@ -0,0 +1,56 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 Generator source config — how many synthetic docs, and how big.
+
+use serde::Deserialize;
+
+use crate::backends::{CommonSourceConfig, SourceTuning};
+
+fn default_docs() -> u64 {
+    1_000_000
+}
+
+fn default_doc_bytes() -> usize {
+    512
+}
+
+/// 🏭 `[source_config.Generator]` — synthetic NDJSON, no corpus required.
+///
+/// Docs look like `{"id":7,"name":"doc-7","body":"abcdefg..."}`, padded with `body` to
+/// roughly `doc_bytes` each. Deterministic: the same config produces the same bytes.
+#[derive(Debug, Deserialize, Clone)]
+pub struct GeneratorSourceConfig {
+    /// 🔢 How many docs to produce before EOF
+    #[serde(default = "default_docs")]
+    pub docs: u64,
+    /// 📏 Target size of one doc in bytes (small targets bottom out at the doc without a body)
+    #[serde(default = "default_doc_bytes")]
+    pub doc_bytes: usize,
+    #[serde(default)]
+    pub common_config: CommonSourceConfig,
+}
+
+impl Default for GeneratorSourceConfig {
+    fn default() -> Self {
+        Self {
+            docs: default_docs(),
+            doc_bytes: default_doc_bytes(),
+            common_config: CommonSourceConfig::default(),
+        }
+    }
+}
+
+impl GeneratorSourceConfig {
+    /// 🎚️ Generator profile: the File source's gulps — it stands in for a fast local file. 🏭
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 10_000,
+        max_batch_size_bytes: 8 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+}
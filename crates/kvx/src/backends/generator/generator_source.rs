@@ -0,0 +1,108 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+use std::fmt::Write;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::config::GeneratorSourceConfig;
+use crate::Page;
+use crate::backends::Source;
+
+/// 🏭 Makes up NDJSON docs, `max_batch_size_docs` / `max_batch_size_bytes` per page, until
+/// `docs` of them exist. Then `None`, like every other source.
+///
+/// 🧠 Knowledge graph: speaks NDJSON, so it resolves casters exactly like the File source
+/// (→ ES is `NdJsonToBulk`, → File is `Passthrough`, ...). Pairs with the Null sink for
+/// `kvx bench`'s pipeline-only numbers.
+#[derive(Debug)]
+pub struct GeneratorSource {
+    source_config: GeneratorSourceConfig,
+    // 🔢 id of the next doc to make — also how many have been made
+    the_next_id: u64,
+    // 🧵 `doc_bytes` of padding, cut to length per doc
+    the_filler: String,
+}
+
+impl GeneratorSource {
+    /// 🏗️ Ready to invent `docs` docs. Nothing is generated until the first pump.
+    pub fn new(source_config: GeneratorSourceConfig) -> Self {
+        let the_filler = (b'a'..=b'z').cycle().take(source_config.doc_bytes).map(char::from).collect();
+        Self { source_config, the_next_id: 0, the_filler }
+    }
+
+    /// 📏 Roughly how many bytes the whole run will produce — for the progress bar.
+    pub fn expected_bytes(&self) -> u64 {
+        // -- each doc is padded to doc_bytes, plus its newline; short targets undershoot, it's an estimate
+        self.source_config.docs.saturating_mul(self.source_config.doc_bytes as u64 + 1)
+    }
+
+    /// 🔢 How many docs the run will produce.
+    pub fn expected_docs(&self) -> u64 {
+        self.source_config.docs
+    }
+
+    fn push_doc(&self, the_page: &mut String, the_id: u64) {
+        let the_start = the_page.len();
+        let _ = write!(the_page, r#"{{"id":{the_id},"name":"doc-{the_id}","body":""#);
+        // -- 🧵 pad to doc_bytes, counting the `"}` still to come
+        let the_padding = self.source_config.doc_bytes.saturating_sub(the_page.len() - the_start + 2);
+        the_page.push_str(&self.the_filler[..the_padding.min(self.the_filler.len())]);
+        the_page.push_str("\"}");
+    }
+}
+
+#[async_trait]
+impl Source for GeneratorSource {
+    /// 📄 The next page of made-up docs. `None` once `docs` have been made.
+    async fn pump(&mut self) -> Result<Option<Page>> {
+        if self.the_next_id >= self.source_config.docs {
+            return Ok(None);
+        }
+        let the_tuning = self.source_config.tuning();
+        let mut the_page = String::with_capacity(the_tuning.max_batch_size_bytes.min(64 * 1024 * 1024));
+        let mut the_doc_count = 0usize;
+        while self.the_next_id < self.source_config.docs
+            && the_doc_count < the_tuning.max_batch_size_docs
+            && the_page.len() < the_tuning.max_batch_size_bytes
+        {
+            if !the_page.is_empty() {
+                the_page.push('\n');
+            }
+            self.push_doc(&mut the_page, self.the_next_id);
+            self.the_next_id += 1;
+            the_doc_count += 1;
+        }
+        Ok(Some(Page(the_page)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::CommonSourceConfig;
+
+    /// 🧪 Every doc is valid JSON of the requested size, pages respect the cap, and EOF arrives on time.
+    #[tokio::test]
+    async fn the_one_where_the_docs_come_from_nowhere() -> Result<()> {
+        let mut the_source = GeneratorSource::new(GeneratorSourceConfig {
+            docs: 5,
+            doc_bytes: 64,
+            common_config: CommonSourceConfig { max_batch_size_docs: Some(2), ..Default::default() },
+        });
+        let mut the_ids = Vec::new();
+        while let Some(the_page) = the_source.pump().await? {
+            assert!(the_page.0.lines().count() <= 2, "🎯 doc cap respected");
+            for the_line in the_page.0.lines() {
+                assert_eq!(the_line.len(), 64, "📏 padded to doc_bytes");
+                let the_doc: serde_json::Value = serde_json::from_str(the_line)?;
+                the_ids.push(the_doc["id"].as_u64().unwrap());
+            }
+        }
+        assert_eq!(the_ids, vec![0, 1, 2, 3, 4]);
+        assert_eq!(the_source.pump().await?, None, "🏁 EOF stays EOF");
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! # 🏭 THE GENERATOR BACKEND
+//!
+//! 🎬 COLD OPEN — INT. BENCHMARK LAB — NO DATA IN SIGHT
+//! *["We need ten million documents," said the benchmark.]*
+//! *["We have twenty lines of NOAA weather," said the repo.]*
+//! *[the generator rolls up its sleeves. it has never seen a document. it will make its own.]*
+//!
+//! Source-only backend that invents NDJSON docs on the fly — a fixed count, a fixed size,
+//! the same bytes every run. For `kvx bench` and anyone else who needs volume without a corpus.
+//! No disk, no network, so what gets measured is the pipeline and the sink. 🦆
+
+pub mod config;
+mod generator_source;
+
+pub use config::GeneratorSourceConfig;
+pub use generator_source::GeneratorSource;
@@ -54,7 +54,7 @@ impl Sink for InMemorySink {
     /// We just stash it for test assertions. No parsing. No judgment. Just storage.
     async fn drain(&mut self, payload: Payload) -> Result<()> {
         // 🔒 The Mutex is load-bearing. Do not remove. I know it looks optional. It isn't.
-        self.received.lock().await.push(payload.0);
+        self.received.lock().await.push(payload.into_string());
         Ok(())
     }
 
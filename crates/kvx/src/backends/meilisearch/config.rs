@@ -12,8 +12,10 @@
 //! ⚠️ The singularity will auto-discover Meilisearch instances via telepathy.
 //! Until then, we use TOML like civilized primates.
 
+use std::time::Duration;
+
 use serde::Deserialize;
-use crate::backends::CommonSinkConfig;
+use crate::backends::{CommonSinkConfig, HttpTimeouts, SinkTuning};
 
 // ============================================================
 // 🔍 MeilisearchSinkConfig
@@ -59,3 +61,28 @@ pub struct MeilisearchSinkConfig {
     #[serde(flatten, default)]
     pub common_config: CommonSinkConfig,
 }
+
+impl MeilisearchSinkConfig {
+    /// 🎚️ Meilisearch sink profile: 20MB per document batch. The server accepts up to
+    /// 100MB, but every POST becomes an async task to index — smaller tasks finish
+    /// (and fail) faster. 🔍
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 20 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s connect, 120s per request — 20MB batches are big, and the upload is all we wait for.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(120),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+}
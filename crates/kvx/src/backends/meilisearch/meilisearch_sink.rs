@@ -63,12 +63,12 @@ impl Sink for MeilisearchSink {
     /// 3. Check 2xx → Ok. Non-2xx → read body, bail with error.
     /// 4. No task polling. Meilisearch queues it. We trust the process. 🙏
     async fn drain(&mut self, payload: Payload) -> Result<()> {
-        let the_raw_bytes = payload.0.into_bytes();
+        let the_raw_bytes = payload.as_bytes();
         let the_uncompressed_len = the_raw_bytes.len();
 
         // 🫁 Phase 1: Gzip compress — squeeze those bytes like a stress ball
         let mut the_gzip_encoder = GzEncoder::new(Vec::new(), Compression::default());
-        the_gzip_encoder.write_all(&the_raw_bytes)
+        the_gzip_encoder.write_all(the_raw_bytes)
             .context("💀 Gzip encoder choked on the payload. The bytes went in but didn't come out compressed. Like trying to vacuum-seal a watermelon.")?;
         let the_compressed_bytes = the_gzip_encoder.finish()
             .context("💀 Gzip finalization failed. The encoder started strong but couldn't stick the landing. A metaphor for most of my PRs.")?;
@@ -132,11 +132,11 @@ impl MeilisearchSink {
         let the_base_url = config.url.trim_end_matches('/').to_string();
 
         // 🔧 Build the HTTP client — tcp_nodelay for latency, pool_idle_timeout for cleanup
-        let the_http_client = reqwest::Client::builder()
+        let the_http_client = config
+            .timeouts()
+            .apply(reqwest::Client::builder())
             .tcp_nodelay(true)
             .pool_idle_timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(120))
             .build()
             .context("💀 reqwest::Client::builder() failed. This is like failing to open a web browser. Check your TLS stack. Check your life choices.")?;
 
@@ -352,7 +352,7 @@ mod tests {
             .await;
 
         let mut sink = MeilisearchSink::new(make_config(&mock_server.uri())).await?;
-        let the_payload = Payload(r#"[{"id":1,"title":"Test Doc"}]"#.to_string());
+        let the_payload = Payload::from(r#"[{"id":1,"title":"Test Doc"}]"#.to_string());
         sink.drain(the_payload).await?;
         Ok(())
     }
@@ -376,7 +376,7 @@ mod tests {
             .await;
 
         let mut sink = MeilisearchSink::new(make_config(&mock_server.uri())).await?;
-        let the_payload = Payload(r#"[{"id":1,"title":"Gzip test — compressing dreams since 1992"}]"#.to_string());
+        let the_payload = Payload::from(r#"[{"id":1,"title":"Gzip test — compressing dreams since 1992"}]"#.to_string());
         sink.drain(the_payload).await?;
         Ok(())
     }
@@ -398,7 +398,7 @@ mod tests {
             .await;
 
         let mut sink = MeilisearchSink::new(make_config(&mock_server.uri())).await?;
-        let the_result = sink.drain(Payload(r#"not even json lol"#.to_string())).await;
+        let the_result = sink.drain(Payload::from(r#"not even json lol"#.to_string())).await;
         assert!(the_result.is_err(), "💀 Non-2xx response should propagate as error");
         Ok(())
     }
@@ -445,7 +445,7 @@ mod tests {
         config.primary_key = Some("custom_id".to_string());
 
         let mut sink = MeilisearchSink::new(config).await?;
-        sink.drain(Payload(r#"[{"custom_id":"abc","title":"Primary key vibes"}]"#.to_string())).await?;
+        sink.drain(Payload::from(r#"[{"custom_id":"abc","title":"Primary key vibes"}]"#.to_string())).await?;
         Ok(())
     }
 
@@ -466,7 +466,7 @@ mod tests {
             .await;
 
         let mut sink = MeilisearchSink::new(make_config(&mock_server.uri())).await?;
-        sink.drain(Payload(r#"[{"geonameid":123,"name":"Auto-detect city"}]"#.to_string())).await?;
+        sink.drain(Payload::from(r#"[{"geonameid":123,"name":"Auto-detect city"}]"#.to_string())).await?;
         Ok(())
     }
 
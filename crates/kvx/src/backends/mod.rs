@@ -17,12 +17,19 @@
 //!
 //! 🦆 The duck is here because every file must have one. This is law. Do not question the duck.
 
+pub mod bigquery;
 pub mod config;
+pub mod custom;
 pub mod elasticsearch;
 pub mod file;
+pub mod generator;
 pub mod in_mem;
 pub mod meilisearch;
+pub mod null;
 pub mod open_observe;
+pub mod redis;
+pub mod rest;
+pub mod solr;
 pub mod sink;
 pub mod source;
 
@@ -31,10 +38,19 @@ pub mod source;
 // Convenience is a feature. So is not typing "backends::file::" fourteen times per file.
 // 🧠 CommonSinkConfig/CommonSourceConfig live here too — they're backend-primitive types
 // shared by every backend config struct. app_config imports them from here to avoid 🔄 circular deps.
-pub use config::{CommonSinkConfig, CommonSourceConfig, SinkConfig, SourceConfig};
+pub use bigquery::BigQuerySourceConfig;
+pub use config::{
+    CommonSinkConfig, CommonSourceConfig, HttpTimeouts, SinkConfig, SinkTuning, SourceConfig, SourceTuning,
+};
+pub use custom::{CustomSinkConfig, CustomSourceConfig, SinkFactory, SourceFactory};
 pub use elasticsearch::{ElasticsearchSinkConfig, ElasticsearchSourceConfig};
 pub use file::{FileSinkConfig, FileSourceConfig};
+pub use generator::GeneratorSourceConfig;
 pub use meilisearch::MeilisearchSinkConfig;
+pub use null::NullSinkConfig;
 pub use open_observe::OpenObserveSinkConfig;
-pub use sink::{Sink, SinkBackend};
+pub use redis::RedisSourceConfig;
+pub use rest::RestSourceConfig;
+pub use solr::SolrSinkConfig;
+pub use sink::{DocumentsRejected, PayloadTooLarge, Sink, SinkBackend, SinkThrottled};
 pub use source::{Source, SourceBackend};
@@ -0,0 +1,29 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 Null sink config — there's nowhere to send anything, so all that's left is the size.
+
+use serde::Deserialize;
+
+use crate::backends::{CommonSinkConfig, SinkTuning};
+
+/// 🕳️ `[sink_config.Null]` — discards payloads. Only the shared sink knobs apply.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct NullSinkConfig {
+    #[serde(flatten, default)]
+    pub common_config: CommonSinkConfig,
+}
+
+impl NullSinkConfig {
+    /// 🎚️ Null sink profile: 10MB, same as ES — it stands in for a `_bulk` endpoint,
+    /// so the payloads it throws away are `_bulk`-sized. 🕳️
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! # 🕳️ THE NULL BACKEND
+//!
+//! 🎬 COLD OPEN — EXT. THE VOID — ALWAYS
+//! *[a payload arrives, bulk-formatted, ten megabytes, proud of itself]*
+//! *["Thank you," says the sink. It does not look at it.]*
+//! *[the payload is gone. the request completed in zero milliseconds. nobody clapped.]*
+//!
+//! Sink-only backend that discards every payload — `/dev/null` with a `Sink` impl. Everything
+//! upstream still runs: casting, transforms, joining into `_bulk` bodies. So `file → null`
+//! measures the pipeline with the network cut out. 🦆
+
+pub mod config;
+mod null_sink;
+
+pub use config::NullSinkConfig;
+pub use null_sink::NullSink;
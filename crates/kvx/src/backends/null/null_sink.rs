@@ -0,0 +1,34 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::Payload;
+use crate::backends::Sink;
+
+/// 🕳️ Accepts every payload instantly and keeps none of them.
+///
+/// 🧠 Knowledge graph: the drainer still records each drain, so the progress numbers
+/// (bytes, requests, docs) are real — they're just the pipeline's numbers, not a cluster's.
+#[derive(Debug, Default)]
+pub struct NullSink;
+
+impl NullSink {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Sink for NullSink {
+    /// 🗑️ Drop it. That's the whole job.
+    async fn drain(&mut self, _payload: Payload) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
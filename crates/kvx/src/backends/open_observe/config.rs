@@ -11,8 +11,13 @@
 //!
 //! ⚠️ The singularity will configure itself. Until then, we have serde.
 
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
 use serde::Deserialize;
-use crate::backends::CommonSinkConfig;
+use crate::backends::config::resolve_secret_file;
+use crate::backends::{CommonSinkConfig, HttpTimeouts, SinkTuning};
 
 // 🏢 Default org name — "default" because creativity peaks at config time
 fn default_org() -> String {
@@ -56,7 +61,39 @@ pub struct OpenObserveSinkConfig {
     /// 🔒 Password. If this is "admin" I am judging you from inside the compiler.
     #[serde(default)]
     pub password: Option<String>,
+    /// 🔑 Path to a file holding the password (secret mounts). Read once at startup.
+    #[serde(default)]
+    pub password_file: Option<PathBuf>,
     /// 🔧 Common sink config: max request size and other life decisions.
     #[serde(flatten, default)]
     pub common_config: CommonSinkConfig,
 }
+
+impl OpenObserveSinkConfig {
+    /// 🎚️ OpenObserve sink profile: 10MB, same as ES — it speaks the `_bulk` dialect,
+    /// so it gets the `_bulk` portion size. 📡
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ Same as the ES sink: 10s connect, 30s per `_bulk`.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(30),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `password_file` for the password it points at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.password, self.password_file.as_deref(), "password")
+    }
+}
@@ -10,6 +10,7 @@ use tracing::{debug, trace};
 
 use crate::Payload;
 use crate::backends::Sink;
+use crate::backends::sink::{PayloadTooLarge, SinkThrottled, split_bulk_payload};
 use super::config::OpenObserveSinkConfig;
 
 /// 📡 The OpenObserve sink — ES-compatible bulk ingestion, zero drama.
@@ -60,24 +61,28 @@ impl Sink for OpenObserveSink {
         debug!("🗑️ OpenObserve sink closing — no buffer to flush, just releasing the vibes");
         Ok(())
     }
+    /// ✂️ After a 413, halve the `_bulk` body on an action/source boundary.
+    fn split_payload(&self, payload: &Payload) -> Option<(Payload, Payload)> {
+        split_bulk_payload(payload)
+    }
 }
 
 impl OpenObserveSink {
     /// 🚀 Stand up a new `OpenObserveSink`, fully wired and ready to ingest.
     ///
     /// This constructor does two things:
-    /// 1. Builds the `reqwest::Client` with sane timeouts (10s connect, 30s read).
+    /// 1. Builds the `reqwest::Client` with sane timeouts (10s connect, 30s read, overridable).
     /// 2. Pings the OpenObserve API root to confirm it's alive and accepting visitors.
     ///
     /// Unlike the ES sink, we do NOT check if the stream exists — OpenObserve
     /// auto-creates streams on first write. Like a river that digs its own bed.
     /// Nature is beautiful. So is auto-provisioning. 🌊
     pub async fn new(config: OpenObserveSinkConfig) -> Result<Self> {
-        // 🔧 Build the HTTP client — 10s connect, 30s response timeout.
+        // 🔧 Build the HTTP client — 10s connect, 30s response timeout unless configured otherwise.
         // If OpenObserve can't shake hands in 10 seconds, it's having a moment.
-        let client = reqwest::Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(30))
+        let client = config
+            .timeouts()
+            .apply(reqwest::Client::builder())
             .build()
             .context("💀 The HTTP client refused to be born. TLS said no, OpenSSL said maybe, and the operating system filed a restraining order. This is a system-level problem, not a you-level problem. Probably.")?;
 
@@ -138,13 +143,24 @@ impl OpenObserveSink {
             request = request.basic_auth(username, self.sink_config.password.as_ref());
         }
 
+        let the_payload_bytes = request_body.len();
         let response = request
-            .body(request_body.0)
+            .body(request_body.to_bytes())
             .send()
             .await
             .context("💀 The bulk request to OpenObserve never arrived. We launched the payload into the network and the network said 'return to sender.' Check connectivity, check DNS, check if your packets got lost in the bermuda triangle of routing tables.")?;
 
         let status = response.status();
+        if status == reqwest::StatusCode::PAYLOAD_TOO_LARGE {
+            // 🐘 413 — typed so the drainer halves the payload instead of retrying it verbatim
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::Error::new(PayloadTooLarge { payload_bytes: the_payload_bytes, body }));
+        }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            // 🚦 429 — typed so the flow master hears about it, then retried like any hiccup
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::Error::new(SinkThrottled { body }));
+        }
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
             anyhow::bail!(
@@ -186,6 +202,7 @@ mod tests {
             stream: "test-stream".to_string(),
             username: None,
             password: None,
+            password_file: None,
             common_config: CommonSinkConfig::default(),
         }
     }
@@ -0,0 +1,131 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 Redis source config — which server, which keys, and where each key's name lands in its doc.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use redis::{ConnectionAddr, ConnectionInfo, RedisConnectionInfo};
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::backends::config::resolve_secret_file;
+use crate::backends::{CommonSourceConfig, HttpTimeouts, SourceTuning};
+
+/// 🟥 `[source_config.Redis]` — every key matching a pattern, one doc per key.
+///
+/// ```toml
+/// [source_config.Redis]
+/// url = "redis://cache.internal:6379/2"
+/// pattern = "product:*"
+/// password_file = "/run/secrets/redis_password"
+/// ```
+#[derive(Debug, Deserialize, Clone)]
+pub struct RedisSourceConfig {
+    /// 📡 `redis://host[:port][/db]` — port defaults to 6379, db to 0. `rediss://` for TLS,
+    /// checked against the system's root certificates.
+    pub url: String,
+    /// 🎯 `SCAN ... MATCH` glob. Default: every key.
+    #[serde(default = "default_pattern")]
+    pub pattern: String,
+    /// 🔢 `SCAN ... COUNT` — a hint for how many keys Redis looks at per call, not a promise.
+    #[serde(default = "default_scan_count")]
+    pub scan_count: u32,
+    /// 🏷️ The field each doc gets the key's name in — unless the doc already has one.
+    /// Empty: leave the docs alone.
+    #[serde(default = "default_key_field")]
+    pub key_field: String,
+    /// 🔑 ACL user for `AUTH`. Without it, `password` authenticates the default user.
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// 🔑 Read `password` from this file instead of keeping it in the config
+    #[serde(default)]
+    pub password_file: Option<PathBuf>,
+    #[serde(default)]
+    pub common_config: CommonSourceConfig,
+}
+
+fn default_pattern() -> String {
+    "*".to_string()
+}
+
+fn default_scan_count() -> u32 {
+    1_000
+}
+
+fn default_key_field() -> String {
+    "_key".to_string()
+}
+
+impl RedisSourceConfig {
+    /// 🎚️ Redis source profile: ES's 1k docs / 10MB. `scan_count` decides how many keys are
+    /// fetched at once; these only cut them into pages for the pipeline.
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 1_000,
+        max_batch_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s connect, 60s per round trip. Not HTTP, but the same two knobs mean the same two things.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(60),
+    };
+
+    /// ⏱️ Resolved timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `password_file` for the password it points at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.password, self.password_file.as_deref(), "password")
+    }
+
+    /// 📡 `url` taken apart into what the client needs: host and port (TLS for `rediss://`),
+    /// the database number, and `username` / `password` for the `AUTH` it sends on connect.
+    pub(crate) fn connection_info(&self) -> Result<ConnectionInfo> {
+        let the_url =
+            Url::parse(&self.url).with_context(|| format!("💀 Redis source url '{}' isn't a URL", self.url))?;
+        let is_tls = match the_url.scheme() {
+            "redis" => false,
+            "rediss" => true,
+            the_scheme => anyhow::bail!("💀 '{}': expected a redis:// or rediss:// URL, got {}://", self.url, the_scheme),
+        };
+        if !the_url.username().is_empty() || the_url.password().is_some() {
+            // -- 🔑 credentials in the URL would end up in logs and `kvx validate` output
+            anyhow::bail!("💀 Redis source url carries credentials — use `username` and `password` / `password_file`");
+        }
+        let the_host = the_url.host_str().filter(|h| !h.is_empty()).context("💀 Redis source url has no host")?.to_string();
+        let the_port = the_url.port().unwrap_or(6379);
+        let the_db = match the_url.path().trim_matches('/') {
+            "" => 0,
+            the_db => the_db
+                .parse()
+                .with_context(|| format!("💀 '{}': the path should be a database number, got '{}'", self.url, the_db))?,
+        };
+        let the_addr = if is_tls {
+            ConnectionAddr::TcpTls { host: the_host, port: the_port, insecure: false, tls_params: None }
+        } else {
+            ConnectionAddr::Tcp(the_host, the_port)
+        };
+        Ok(ConnectionInfo {
+            addr: the_addr,
+            redis: RedisConnectionInfo {
+                db: the_db,
+                username: self.username.clone(),
+                password: self.password.clone(),
+                ..RedisConnectionInfo::default()
+            },
+        })
+    }
+}
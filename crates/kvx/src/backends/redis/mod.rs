@@ -0,0 +1,20 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! # 🟥 THE REDIS BACKEND
+//!
+//! 🎬 COLD OPEN — INT. THE CACHE CLUSTER — IT WAS NEVER SUPPOSED TO BE THE SOURCE OF TRUTH
+//! *[two million `product:*` keys. the database they were cached from was retired in March.]*
+//! *[someone asks if they can be searched. someone else types `KEYS *` into production.]*
+//! *[the pager goes off.]*
+//!
+//! Source-only backend that walks the keyspace with `SCAN` (never `KEYS`), reads every
+//! matching string, hash and Redis-JSON key, and emits one NDJSON doc per key. The wire is
+//! the `redis` crate's multiplexed connection — plain TCP or `rediss://` TLS. 🦆
+
+pub mod config;
+mod redis_source;
+
+pub use config::RedisSourceConfig;
+pub use redis_source::RedisSource;
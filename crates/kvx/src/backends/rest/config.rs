@@ -0,0 +1,107 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 REST source config — the endpoint, where the docs sit in a response, and how to get the next one.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::backends::config::resolve_secret_file;
+use crate::backends::{CommonSourceConfig, HttpTimeouts, SourceTuning};
+
+/// 🌍 `[source_config.Rest]` — any JSON API that hands out its records a page at a time.
+///
+/// ```toml
+/// [source_config.Rest]
+/// url = "https://api.example.com/v2/contacts?limit=100"
+/// docs_pointer = "/results"
+/// bearer_token_file = "/run/secrets/crm_token"
+/// pagination = { cursor = { pointer = "/paging/next_cursor", param = "after" } }
+/// ```
+#[derive(Debug, Deserialize, Clone)]
+pub struct RestSourceConfig {
+    /// 📡 The first page. Fixed query params (page size, filters) go here.
+    pub url: String,
+    /// 🎯 JSON pointer to the array of records in each response (`/results`, `/data/items`).
+    /// Empty (default): the response *is* the array.
+    #[serde(default)]
+    pub docs_pointer: String,
+    /// 📜 How to ask for the page after this one. Default: there is only one.
+    #[serde(default)]
+    pub pagination: RestPagination,
+    /// 🔑 Extra request headers, sent on every request.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// 🔑 Sent as `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+    /// 🔑 Read `bearer_token` from this file instead of keeping it in the config
+    #[serde(default)]
+    pub bearer_token_file: Option<PathBuf>,
+    #[serde(default)]
+    pub common_config: CommonSourceConfig,
+}
+
+/// 📜 How a REST source finds the next page — `pagination = ...`.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestPagination {
+    /// 1️⃣ One request, one response, done
+    #[default]
+    None,
+    /// 🔖 The response carries a cursor at `pointer`; the next request sends it as `?{param}=`.
+    /// Ends when the cursor is missing, null or empty.
+    Cursor { pointer: String, param: String },
+    /// 🔗 The response names the next page's URL — at `pointer` in the body, or, with no
+    /// pointer, in a `Link: <...>; rel="next"` header. Relative links resolve against the
+    /// page they came from. Ends when there is no next link.
+    NextLink {
+        #[serde(default)]
+        pointer: Option<String>,
+    },
+    /// 🔢 `?{param}=start`, `start + 1`, ... until a page comes back with no records
+    Page {
+        param: String,
+        #[serde(default = "default_first_page")]
+        start: u64,
+    },
+}
+
+fn default_first_page() -> u64 {
+    1
+}
+
+impl RestSourceConfig {
+    /// 🎚️ REST source profile: ES's 1k docs / 10MB. The API picks its own page size;
+    /// these only cut a large response into smaller pages for the pipeline.
+    pub const DEFAULT_TUNING: SourceTuning = SourceTuning {
+        max_batch_size_docs: 1_000,
+        max_batch_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved source knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SourceTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s connect, 60s per page — export endpoints assemble pages on demand, and some take their time.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(60),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `bearer_token_file` for the token it points at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.bearer_token, self.bearer_token_file.as_deref(), "bearer_token")
+    }
+}
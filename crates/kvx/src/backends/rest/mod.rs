@@ -0,0 +1,20 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! # 🌍 THE REST BACKEND
+//!
+//! 🎬 COLD OPEN — INT. A SAAS VENDOR'S API DOCS — PAGE 47 OF "EXPORTING YOUR DATA"
+//! *["Records are returned 100 at a time," says the CRM. "Use `paging.next.after`."]*
+//! *[the ticketing tool uses a `Link` header. the billing tool uses `?page=`.]*
+//! *[three export scripts, three cron jobs, one person who remembers how they work.]*
+//!
+//! Source-only backend for JSON APIs that hand out records a page at a time: one config
+//! names the endpoint, where the records sit in a response (a JSON pointer), and which of
+//! the three common pagination styles to follow. Records come out as NDJSON. 🦆
+
+pub mod config;
+mod rest_source;
+
+pub use config::{RestPagination, RestSourceConfig};
+pub use rest_source::RestSource;
@@ -0,0 +1,355 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+use std::collections::VecDeque;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue, LINK};
+use reqwest::{Client, Url};
+use serde_json::Value;
+use tracing::{debug, info};
+
+use super::config::{RestPagination, RestSourceConfig};
+use crate::Page;
+use crate::backends::Source;
+
+/// 🌍 Calls a JSON API page after page, pulls the records out of each response with
+/// `docs_pointer`, and pours them out as NDJSON pages — until the pagination runs dry.
+///
+/// 🧠 Knowledge graph: speaks NDJSON, so it resolves casters exactly like the File source.
+/// Responses and pipeline pages are decoupled: records wait in `pending`, and each pump cuts
+/// one page from them under `max_batch_size_docs` / `max_batch_size_bytes`, fetching only
+/// when they run out.
+#[derive(Debug)]
+pub struct RestSource {
+    source_config: RestSourceConfig,
+    client: Client,
+    headers: HeaderMap,
+    // 📡 the first page, as configured — cursor and page params are set on top of it
+    base_url: Url,
+    // 📡 the next request to send; `None` once the pagination says there's nothing after
+    next_url: Option<Url>,
+    // 🔢 `pagination = page`: the number the next request asks for
+    next_page: u64,
+    // 🧩 records fetched but not yet cut into a page, one compact JSON line each
+    pending: VecDeque<String>,
+}
+
+impl RestSource {
+    /// 🏗️ Builds the client and the first request. Nothing is sent until the first pump.
+    pub fn new(source_config: RestSourceConfig) -> Result<Self> {
+        let base_url = Url::parse(&source_config.url)
+            .with_context(|| format!("💀 REST source url '{}' isn't a URL", source_config.url))?;
+        let mut headers = HeaderMap::new();
+        for (the_name, the_value) in &source_config.headers {
+            // -- 🔑 header values are often secrets: the error names the header, never the value
+            let the_header_name = HeaderName::from_bytes(the_name.as_bytes())
+                .with_context(|| format!("💀 '{}' isn't a valid HTTP header name", the_name))?;
+            let the_header_value = HeaderValue::from_str(the_value)
+                .with_context(|| format!("💀 The value of header '{}' isn't a valid HTTP header value", the_name))?;
+            headers.insert(the_header_name, the_header_value);
+        }
+        if let Some(ref the_token) = source_config.bearer_token {
+            let mut the_header_value = HeaderValue::from_str(&format!("Bearer {}", the_token))
+                .context("💀 bearer_token isn't a valid HTTP header value")?;
+            the_header_value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, the_header_value);
+        }
+        let client = source_config
+            .timeouts()
+            .apply(Client::builder())
+            .build()
+            .context("💀 Couldn't build the HTTP client for the REST source")?;
+        let (next_url, next_page) = match &source_config.pagination {
+            RestPagination::Page { param, start } => (with_param(&base_url, param, &start.to_string()), *start),
+            _ => (base_url.clone(), 0),
+        };
+        Ok(Self { source_config, client, headers, base_url, next_url: Some(next_url), next_page, pending: VecDeque::new() })
+    }
+
+    /// 🩺 For `kvx validate`: send the first request and check the answer is JSON with records
+    /// where `docs_pointer` says — a bad URL, token or pointer fails here. Nothing is queued.
+    pub async fn probe(&self) -> Result<()> {
+        let Some(ref the_url) = self.next_url else {
+            return Ok(());
+        };
+        let (the_body, _) = self.get(the_url).await?;
+        let the_records = self.records(the_url, &the_body)?;
+        info!("✅ '{}' answered with {} record(s) on the first page", the_url, the_records.len());
+        Ok(())
+    }
+
+    /// 📡 GET `the_url` → its JSON body, plus any `Link` header.
+    async fn get(&self, the_url: &Url) -> Result<(Value, Option<String>)> {
+        debug!("🌍 GET {}", the_url);
+        let the_response = self
+            .client
+            .get(the_url.clone())
+            .headers(self.headers.clone())
+            .send()
+            .await
+            .with_context(|| format!("💀 GET '{}' didn't get an answer", the_url))?;
+        let the_status = the_response.status();
+        let the_link_header = the_response.headers().get(LINK).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let the_bytes = the_response.bytes().await.with_context(|| format!("💀 GET '{}' broke off mid-response", the_url))?;
+        if !the_status.is_success() {
+            let the_preview: String = String::from_utf8_lossy(&the_bytes).chars().take(200).collect();
+            anyhow::bail!("💀 GET '{}' answered {}: {}", the_url, the_status, the_preview);
+        }
+        let the_body: Value = serde_json::from_slice(&the_bytes)
+            .with_context(|| format!("💀 GET '{}' answered with something that isn't JSON", the_url))?;
+        Ok((the_body, the_link_header))
+    }
+
+    /// 🧩 The records `docs_pointer` points at in a response.
+    fn records<'a>(&self, the_url: &Url, the_body: &'a Value) -> Result<&'a Vec<Value>> {
+        let the_pointer = self.source_config.docs_pointer.as_str();
+        let the_records = if the_pointer.is_empty() { Some(the_body) } else { the_body.pointer(the_pointer) };
+        let Some(Value::Array(the_records)) = the_records else {
+            anyhow::bail!(
+                "💀 GET '{}': docs_pointer '{}' doesn't point at an array of records in the response",
+                the_url,
+                the_pointer
+            );
+        };
+        Ok(the_records)
+    }
+
+    /// 📡 Send the next request, queue its records, and work out the request after it.
+    async fn fetch(&mut self) -> Result<()> {
+        let Some(the_url) = self.next_url.take() else {
+            return Ok(());
+        };
+        let (the_body, the_link_header) = self.get(&the_url).await?;
+        let the_records = self.records(&the_url, &the_body)?;
+        let got_records = !the_records.is_empty();
+        self.pending.extend(the_records.iter().map(Value::to_string));
+
+        self.next_url = match &self.source_config.pagination {
+            RestPagination::None => None,
+            RestPagination::Cursor { pointer, param } => match the_body.pointer(pointer) {
+                Some(Value::String(the_cursor)) if !the_cursor.is_empty() => Some(with_param(&self.base_url, param, the_cursor)),
+                Some(Value::Number(the_cursor)) => Some(with_param(&self.base_url, param, &the_cursor.to_string())),
+                _ => None,
+            },
+            RestPagination::NextLink { pointer: Some(pointer) } => match the_body.pointer(pointer) {
+                Some(Value::String(the_link)) if !the_link.is_empty() => Some(resolve_link(&the_url, the_link)?),
+                _ => None,
+            },
+            RestPagination::NextLink { pointer: None } => {
+                match the_link_header.as_deref().and_then(next_from_link_header) {
+                    Some(the_link) => Some(resolve_link(&the_url, the_link)?),
+                    None => None,
+                }
+            }
+            RestPagination::Page { param, .. } if got_records => {
+                self.next_page += 1;
+                Some(with_param(&self.base_url, param, &self.next_page.to_string()))
+            }
+            RestPagination::Page { .. } => None,
+        };
+        // -- 🔁 an API that answers with a pointer to the page we just read would loop forever
+        if self.next_url.as_ref() == Some(&the_url) {
+            anyhow::bail!("💀 '{}' says the next page is itself — stopping before it loops forever", the_url);
+        }
+        Ok(())
+    }
+}
+
+/// 🔖 `base` with `name=value` in its query — replacing any `name` already there, keeping the rest.
+fn with_param(base: &Url, name: &str, value: &str) -> Url {
+    let the_kept: Vec<(String, String)> =
+        base.query_pairs().filter(|(k, _)| k != name).map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+    let mut the_url = base.clone();
+    the_url.query_pairs_mut().clear().extend_pairs(the_kept).append_pair(name, value);
+    the_url
+}
+
+/// 🔗 A next link, resolved against the page it came from (`/items?page=2` and `?page=2` both work).
+fn resolve_link(current: &Url, link: &str) -> Result<Url> {
+    current.join(link).with_context(|| format!("💀 '{}' named a next page that isn't a URL: '{}'", current, link))
+}
+
+/// 🔗 The `rel="next"` target of an RFC 8288 `Link` header: `<https://...?page=2>; rel="next", <...>; rel="last"`.
+fn next_from_link_header(the_header: &str) -> Option<&str> {
+    the_header
+        .split(',')
+        .find(|the_link| {
+            the_link.split(';').skip(1).any(|the_param| matches!(the_param.trim(), "rel=\"next\"" | "rel=next"))
+        })
+        .and_then(|the_link| the_link.split(';').next())
+        .map(|the_target| the_target.trim().trim_start_matches('<').trim_end_matches('>'))
+}
+
+#[async_trait]
+impl Source for RestSource {
+    /// 📄 The next page of records — fetching responses until there's something to hand out.
+    /// `None` once the pagination ends and everything fetched has been paged out.
+    async fn pump(&mut self) -> Result<Option<Page>> {
+        // -- 🔁 a cursor page can come back empty and still point at more
+        while self.pending.is_empty() && self.next_url.is_some() {
+            self.fetch().await?;
+        }
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+        let the_tuning = self.source_config.tuning();
+        let mut the_page = String::new();
+        let mut the_doc_count = 0usize;
+        while the_doc_count < the_tuning.max_batch_size_docs && the_page.len() < the_tuning.max_batch_size_bytes {
+            let Some(the_record) = self.pending.pop_front() else {
+                break;
+            };
+            if !the_page.is_empty() {
+                the_page.push('\n');
+            }
+            the_page.push_str(&the_record);
+            the_doc_count += 1;
+        }
+        Ok(Some(Page(the_page)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn drain(the_source: &mut RestSource) -> Result<Vec<String>> {
+        let mut the_pages = Vec::new();
+        while let Some(Page(the_page)) = the_source.pump().await? {
+            the_pages.push(the_page);
+        }
+        Ok(the_pages)
+    }
+
+    fn the_config(the_toml: &str) -> RestSourceConfig {
+        toml::from_str(the_toml).expect("💀 test config didn't parse")
+    }
+
+    /// 🧪 A cursor is read from the body and sent back as a param, next to the fixed ones,
+    /// with the bearer token on every request — until the cursor runs out.
+    #[tokio::test]
+    async fn the_one_where_the_cursor_leads_the_way() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contacts"))
+            .and(query_param("limit", "2"))
+            .and(query_param_is_missing("after"))
+            .and(header("authorization", "Bearer s3cret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"results":[{"id":1},{"id":2}],"paging":{"next":"c2"}}"#,
+            ))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contacts"))
+            .and(query_param("limit", "2"))
+            .and(query_param("after", "c2"))
+            .and(header("authorization", "Bearer s3cret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"results":[{"id":3}],"paging":{}}"#))
+            .mount(&the_server)
+            .await;
+
+        let mut the_source = RestSource::new(the_config(&format!(
+            r#"
+            url = "{}/contacts?limit=2"
+            docs_pointer = "/results"
+            bearer_token = "s3cret"
+            pagination = {{ cursor = {{ pointer = "/paging/next", param = "after" }} }}
+            "#,
+            the_server.uri()
+        )))?;
+        assert_eq!(drain(&mut the_source).await?, vec![r#"{"id":1}"#.to_string() + "\n" + r#"{"id":2}"#, r#"{"id":3}"#.to_string()]);
+        Ok(())
+    }
+
+    /// 🧪 Next links from a `Link` header and from the body (relative), and page numbers that
+    /// stop at the first empty page — with a small doc cap cutting responses into pages.
+    #[tokio::test]
+    async fn the_one_where_every_api_paginates_differently() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(path("/linked"))
+            .and(query_param_is_missing("page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("link", format!(r#"<{}/linked?page=2>; rel="next", <{}/linked?page=9>; rel="last""#, the_server.uri(), the_server.uri()))
+                    .set_body_string(r#"[{"n":1}]"#),
+            )
+            .mount(&the_server)
+            .await;
+        Mock::given(path("/linked"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"[{"n":2}]"#))
+            .mount(&the_server)
+            .await;
+        let mut the_linked = RestSource::new(the_config(&format!(
+            "url = \"{}/linked\"\npagination = {{ next_link = {{}} }}",
+            the_server.uri()
+        )))?;
+        assert_eq!(drain(&mut the_linked).await?, vec![r#"{"n":1}"#, r#"{"n":2}"#]);
+
+        Mock::given(path("/bodied"))
+            .and(query_param_is_missing("p"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":[{"n":1}],"next":"?p=2"}"#))
+            .mount(&the_server)
+            .await;
+        Mock::given(path("/bodied"))
+            .and(query_param("p", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":[{"n":2}],"next":null}"#))
+            .mount(&the_server)
+            .await;
+        let mut the_bodied = RestSource::new(the_config(&format!(
+            "url = \"{}/bodied\"\ndocs_pointer = \"/data\"\npagination = {{ next_link = {{ pointer = \"/next\" }} }}",
+            the_server.uri()
+        )))?;
+        assert_eq!(drain(&mut the_bodied).await?, vec![r#"{"n":1}"#, r#"{"n":2}"#]);
+
+        for (the_page, the_body) in [("1", r#"[{"n":1},{"n":2}]"#), ("2", r#"[{"n":3}]"#), ("3", "[]")] {
+            Mock::given(path("/numbered"))
+                .and(query_param("page", the_page))
+                .respond_with(ResponseTemplate::new(200).set_body_string(the_body))
+                .expect(1)
+                .mount(&the_server)
+                .await;
+        }
+        let mut the_numbered = RestSource::new(the_config(&format!(
+            "url = \"{}/numbered\"\npagination = {{ page = {{ param = \"page\" }} }}\ncommon_config = {{ max_batch_size_docs = 1 }}",
+            the_server.uri()
+        )))?;
+        assert_eq!(drain(&mut the_numbered).await?, vec![r#"{"n":1}"#, r#"{"n":2}"#, r#"{"n":3}"#]);
+        Ok(())
+    }
+
+    /// 🧪 A pointer that misses the array, a failed request, and a cursor that never moves all stop the run.
+    #[tokio::test]
+    async fn the_one_where_the_api_does_not_play_along() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(path("/stuck"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"results":[{"id":1}],"cursor":"same"}"#))
+            .mount(&the_server)
+            .await;
+        Mock::given(path("/gone"))
+            .respond_with(ResponseTemplate::new(410).set_body_string("export expired"))
+            .mount(&the_server)
+            .await;
+
+        let the_uri = the_server.uri();
+        let mut the_missed = RestSource::new(the_config(&format!("url = \"{the_uri}/stuck\"\ndocs_pointer = \"/cursor\"")))?;
+        assert!(the_missed.pump().await.is_err(), "💀 a string isn't an array of records");
+
+        let mut the_gone = RestSource::new(the_config(&format!("url = \"{the_uri}/gone\"")))?;
+        let the_error = format!("{:#}", the_gone.pump().await.expect_err("💀 410 is a failure"));
+        assert!(the_error.contains("410") && the_error.contains("export expired"), "{the_error}");
+
+        let mut the_stuck = RestSource::new(the_config(&format!(
+            "url = \"{the_uri}/stuck\"\ndocs_pointer = \"/results\"\npagination = {{ cursor = {{ pointer = \"/cursor\", param = \"c\" }} }}"
+        )))?;
+        assert!(drain(&mut the_stuck).await.is_err(), "🔁 the same cursor twice is a loop, not a page");
+        assert!(next_from_link_header(r#"<https://a/x?page=9>; rel="last""#).is_none());
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 
 use crate::Payload;
-use crate::backends::{elasticsearch, file, in_mem, meilisearch, open_observe};
+use crate::backends::{elasticsearch, file, in_mem, meilisearch, null, open_observe, solr};
 
 /// 🕳️ A sink that sends pre-rendered payloads — pure I/O, zero logic.
 ///
@@ -33,6 +33,119 @@ pub trait Sink: std::fmt::Debug {
     async fn drain(&mut self, payload: Payload) -> Result<()>;
     /// 🗑️ Flush, finalize, and release. Call this. Always. No exceptions. Not even on Fridays.
     async fn close(&mut self) -> Result<()>;
+    /// ✂️ Split a payload into two smaller, independently valid payloads — or `None` if this
+    /// sink's wire format can't be split (or the payload is already a single document).
+    ///
+    /// Only called by the Drainer after a [`PayloadTooLarge`] rejection. Sinks that never
+    /// return that error can ignore this entirely; the default says "no scissors here." ✂️🦆
+    fn split_payload(&self, _payload: &Payload) -> Option<(Payload, Payload)> {
+        None
+    }
+}
+
+/// 🐘 The destination said "413 Payload Too Large" — the payload itself is the problem,
+/// not the network, not the cluster's mood. Retrying the same bytes is pointless.
+///
+/// Sinks return this (wrapped in `anyhow::Error`) so the Drainer can `downcast_ref` it,
+/// shrink the payload ceiling, and resend the payload in halves instead of backing off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadTooLarge {
+    /// 📏 Size of the rejected payload
+    pub payload_bytes: usize,
+    /// 📜 Whatever the server said about it (often empty, occasionally an nginx HTML page)
+    pub body: String,
+}
+
+impl std::fmt::Display for PayloadTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "💀 413 Payload Too Large — the destination refused a {} byte payload. It's not you, it's your bytes. Body: '{}'",
+            self.payload_bytes, self.body
+        )
+    }
+}
+
+impl std::error::Error for PayloadTooLarge {}
+
+/// 🚦 The destination said "429 Too Many Requests" — it's alive, just overwhelmed.
+///
+/// Retried like any other transient failure, but typed so the Drainer can also tell the
+/// FlowMaster (`GaugeReading::Error()`) — an adaptive regulator shrinks payloads on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SinkThrottled {
+    /// 📜 Whatever the server said about it (ES: `es_rejected_execution_exception`, usually)
+    pub body: String,
+}
+
+impl std::fmt::Display for SinkThrottled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "💀 429 Too Many Requests — the destination is asking us to slow down. Body: '{}'", self.body)
+    }
+}
+
+impl std::error::Error for SinkThrottled {}
+
+/// 🙅 The destination accepted the request but refused some documents inside it
+/// (e.g. `_bulk` answered 200 with `"errors": true`), and the sink is configured to fail.
+///
+/// Not retryable: the rest of the payload already landed, so resending it would duplicate
+/// the good docs to re-reject the bad ones. The Drainer passes this straight up. 🚫
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentsRejected {
+    /// 🔢 How many documents in this payload were refused
+    pub rejected: usize,
+    /// 📜 The first refusal, e.g. "mapper_parsing_exception: failed to parse field [age]"
+    pub first_reason: String,
+}
+
+impl std::fmt::Display for DocumentsRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "💀 The destination rejected {} document(s) in an otherwise successful request. First complaint: {}",
+            self.rejected, self.first_reason
+        )
+    }
+}
+
+impl std::error::Error for DocumentsRejected {}
+
+/// ✂️ Split a `_bulk` NDJSON payload roughly in half without orphaning any action line.
+///
+/// Bulk bodies come in units: `{"index"|"create"|"update": ...}` + a source line, or a lone
+/// `{"delete": ...}` line. We cut on a unit boundary closest to the middle. Returns `None`
+/// when there's only one unit — a single doc that's too large can't be helped by scissors.
+///
+/// 🧠 Shared by the Elasticsearch and OpenObserve sinks (same bulk dialect, same school).
+pub(crate) fn split_bulk_payload(payload: &Payload) -> Option<(Payload, Payload)> {
+    let the_unit_starts = bulk_unit_starts(payload);
+    if the_unit_starts.len() < 2 {
+        return None;
+    }
+    let the_cut = the_unit_starts[the_unit_starts.len() / 2];
+    // -- 🧱 both halves are views into the same buffer — unit starts follow a '\n', so they're char boundaries
+    Some((payload.slice(0..the_cut)?, payload.slice(the_cut..payload.len())?))
+}
+
+/// 📐 Byte offsets where each `_bulk` unit (action line + optional source line) starts.
+/// Unit N lines up with `items[N]` in the bulk response.
+pub(crate) fn bulk_unit_starts(payload: &str) -> Vec<usize> {
+    // -- 📐 byte offsets where each unit starts, so halves are sliced, not re-joined
+    let mut the_unit_starts = Vec::new();
+    let mut the_lines_left_in_unit = 0usize;
+    let mut the_offset = 0usize;
+    for line in payload.split_inclusive('\n') {
+        if the_lines_left_in_unit == 0 && !line.trim().is_empty() {
+            the_unit_starts.push(the_offset);
+            the_lines_left_in_unit = if line.trim_start().starts_with("{\"delete\"") { 1 } else { 2 };
+        }
+        if !line.trim().is_empty() {
+            the_lines_left_in_unit = the_lines_left_in_unit.saturating_sub(1);
+        }
+        the_offset += line.len();
+    }
+    the_unit_starts
 }
 
 /// 🎭 The many faces of a Sink — a polymorphic casting call for data destinations.
@@ -43,6 +156,8 @@ pub trait Sink: std::fmt::Debug {
 /// The enum dispatches `drain` and `close` to the inner concrete type,
 /// keeping the supervisor blissfully ignorant of where data actually lands.
 /// Ignorance is a feature. It's called "abstraction." We put it in AGENTS.md.
+// -- 📏 one per drainer, moved once at startup — same call as SourceBackend
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum SinkBackend {
     InMemory(in_mem::InMemorySink),
@@ -50,6 +165,10 @@ pub enum SinkBackend {
     Elasticsearch(elasticsearch::ElasticsearchSink),
     Meilisearch(meilisearch::MeilisearchSink),
     OpenObserve(open_observe::OpenObserveSink),
+    Solr(solr::SolrSink),
+    Null(null::NullSink),
+    /// 🧩 Anything that implements `Sink` — built from `SinkConfig::Custom`'s factory
+    Custom(Box<dyn Sink + Send>),
 }
 
 #[async_trait]
@@ -61,6 +180,9 @@ impl Sink for SinkBackend {
             SinkBackend::Elasticsearch(sink) => sink.drain(payload).await,
             SinkBackend::Meilisearch(sink) => sink.drain(payload).await,
             SinkBackend::OpenObserve(sink) => sink.drain(payload).await,
+            SinkBackend::Solr(sink) => sink.drain(payload).await,
+            SinkBackend::Null(sink) => sink.drain(payload).await,
+            SinkBackend::Custom(sink) => sink.drain(payload).await,
         }
     }
 
@@ -71,6 +193,48 @@ impl Sink for SinkBackend {
             SinkBackend::Elasticsearch(sink) => sink.close().await,
             SinkBackend::Meilisearch(sink) => sink.close().await,
             SinkBackend::OpenObserve(sink) => sink.close().await,
+            SinkBackend::Solr(sink) => sink.close().await,
+            SinkBackend::Null(sink) => sink.close().await,
+            SinkBackend::Custom(sink) => sink.close().await,
         }
     }
+
+    fn split_payload(&self, payload: &Payload) -> Option<(Payload, Payload)> {
+        match self {
+            SinkBackend::InMemory(sink) => sink.split_payload(payload),
+            SinkBackend::File(sink) => sink.split_payload(payload),
+            SinkBackend::Elasticsearch(sink) => sink.split_payload(payload),
+            SinkBackend::Meilisearch(sink) => sink.split_payload(payload),
+            SinkBackend::OpenObserve(sink) => sink.split_payload(payload),
+            SinkBackend::Solr(sink) => sink.split_payload(payload),
+            SinkBackend::Null(sink) => sink.split_payload(payload),
+            SinkBackend::Custom(sink) => sink.split_payload(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 🧪 Pairs stay paired, deletes stand alone, and the cut lands on a unit boundary.
+    #[test]
+    fn the_one_where_the_bulk_payload_is_cut_without_orphans() {
+        let the_body = concat!(
+            "{\"index\":{\"_id\":\"1\"}}\n{\"a\":1}\n",
+            "{\"delete\":{\"_id\":\"2\"}}\n",
+            "{\"create\":{\"_id\":\"3\"}}\n{\"c\":3}\n",
+            "{\"index\":{\"_id\":\"4\"}}\n{\"d\":4}\n",
+        );
+        let (the_left, the_right) = split_bulk_payload(&Payload::from(the_body.to_string())).unwrap();
+        assert_eq!(the_left.0, "{\"index\":{\"_id\":\"1\"}}\n{\"a\":1}\n{\"delete\":{\"_id\":\"2\"}}\n");
+        assert_eq!(the_right.0, "{\"create\":{\"_id\":\"3\"}}\n{\"c\":3}\n{\"index\":{\"_id\":\"4\"}}\n{\"d\":4}\n");
+    }
+
+    /// 🧪 One doc can't be halved — scissors decline politely.
+    #[test]
+    fn the_one_where_a_single_doc_refuses_to_be_split() {
+        let the_body = "{\"index\":{}}\n{\"huge\":true}\n";
+        assert!(split_bulk_payload(&Payload::from(the_body.to_string())).is_none());
+    }
 }
@@ -0,0 +1,90 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 Solr backend config — where the collection lives, and when Solr should make docs visible.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::backends::config::resolve_secret_file;
+use crate::backends::{CommonSinkConfig, HttpTimeouts, SinkTuning};
+
+fn default_commit_on_close() -> bool {
+    true
+}
+
+fn default_id_field() -> String {
+    "id".to_string()
+}
+
+/// ☀️ `[sink_config.Solr]` — JSON docs into a Solr collection.
+///
+/// 📡 Endpoint: `POST {url}/{collection}/update/json/docs[?commitWithin=N]`
+/// 🔒 Auth: basic auth (Solr's BasicAuthPlugin), optional
+/// 📦 Payload: JSON array of documents
+///
+/// 🧠 Knowledge graph:
+/// - Resolved by `lib.rs::from_sink_config()` → `SolrSink::new(config)`
+/// - Solr doesn't create collections on write: `new` checks the collection answers first
+/// - An Elasticsearch source's `_id` lands in `id_field` — Solr's `uniqueKey`, `id` out of the box
+#[derive(Debug, Deserialize, Clone)]
+pub struct SolrSinkConfig {
+    /// 📡 Solr's base URL, up to and including `/solr` — `http://localhost:8983/solr`
+    pub url: String,
+    /// 📦 The target collection (or core)
+    pub collection: String,
+    /// ⏱️ Ask Solr to commit each batch within this many milliseconds (`commitWithin`).
+    /// Unset: leave it to the collection's `autoCommit` / `autoSoftCommit` settings.
+    #[serde(default)]
+    pub commit_within_ms: Option<u64>,
+    /// 💾 Send a hard commit when the sink closes, so everything is searchable when the run ends
+    #[serde(default = "default_commit_on_close")]
+    pub commit_on_close: bool,
+    /// 🪪 The field an Elasticsearch hit's `_id` is copied into, unless the doc already has it
+    #[serde(default = "default_id_field")]
+    pub id_field: String,
+    /// 🔒 Username for basic auth
+    #[serde(default)]
+    pub username: Option<String>,
+    /// 🔒 Password for basic auth
+    #[serde(default)]
+    pub password: Option<String>,
+    /// 🔑 Path to a file holding the password (secret mounts). Read once at startup.
+    #[serde(default)]
+    pub password_file: Option<PathBuf>,
+    #[serde(flatten, default)]
+    pub common_config: CommonSinkConfig,
+}
+
+impl SolrSinkConfig {
+    /// 🎚️ Solr sink profile: 10MB per update. Solr's default upload limit is 2GB, but a
+    /// batch is parsed and indexed in one request — smaller ones fail (and retry) cheaper. ☀️
+    pub const DEFAULT_TUNING: SinkTuning = SinkTuning {
+        max_request_size_bytes: 10 * 1024 * 1024,
+    };
+
+    /// 🎚️ Resolved sink knobs — user overrides on top of `DEFAULT_TUNING`.
+    pub fn tuning(&self) -> SinkTuning {
+        self.common_config.resolve(Self::DEFAULT_TUNING)
+    }
+
+    /// ⏱️ 10s connect, 60s per update — Solr indexes the batch before it answers.
+    pub const DEFAULT_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+        connect: Duration::from_secs(10),
+        request: Duration::from_secs(60),
+    };
+
+    /// ⏱️ Resolved HTTP timeouts — `connect_timeout_secs` / `request_timeout_secs` over `DEFAULT_TIMEOUTS`.
+    pub fn timeouts(&self) -> HttpTimeouts {
+        self.common_config.timeouts(Self::DEFAULT_TIMEOUTS)
+    }
+
+    /// 🔑 Swap `password_file` for the password it points at.
+    pub fn resolve_secret_files(&mut self) -> Result<()> {
+        resolve_secret_file(&mut self.password, self.password_file.as_deref(), "password")
+    }
+}
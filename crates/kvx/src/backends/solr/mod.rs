@@ -0,0 +1,19 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! # ☀️ THE SOLR BACKEND
+//!
+//! 🎬 COLD OPEN — INT. PLATFORM TEAM STANDUP — THE MIGRATION GOES THE OTHER WAY
+//! *["We're moving the catalog search *off* Elasticsearch," says the lead.]*
+//! *["To Solr." a long pause. "The licensing meeting went long."]*
+//! *[every tool in the room reads from Solr. none of them write to it.]*
+//!
+//! Sink-only backend: JSON arrays POSTed to `/update/json/docs`, with `commitWithin`
+//! per batch and a hard commit at close. 🦆
+
+pub mod config;
+mod solr_sink;
+
+pub use config::SolrSinkConfig;
+pub use solr_sink::SolrSink;
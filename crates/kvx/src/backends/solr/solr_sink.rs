@@ -0,0 +1,213 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tracing::{debug, info};
+
+use super::config::SolrSinkConfig;
+use crate::Payload;
+use crate::backends::Sink;
+use crate::backends::sink::SinkThrottled;
+
+/// ☀️ The Solr sink — each payload is a JSON array POSTed to `/update/json/docs`.
+///
+/// 🧠 Knowledge graph:
+/// - Payloads come from the `JsonArray` manifold, same as Meilisearch: `NdJsonSplit` for NDJSON
+///   sources, `PitToJson` (with `id_field`) for Elasticsearch sources
+/// - `commit_within_ms` rides on every update as `commitWithin`; Solr answers once the batch
+///   is indexed, and makes it searchable within that window
+/// - `close()` sends one hard commit (`commit_on_close`), once per drainer
+/// - 429 is `SinkThrottled`, like ES and OpenObserve. Anything else non-2xx fails with Solr's `error.msg`
+pub struct SolrSink {
+    client: reqwest::Client,
+    sink_config: SolrSinkConfig,
+    // 🔗 `{url}/{collection}/update` — `/json/docs` for docs, bare for the commit
+    the_update_url: String,
+}
+
+impl std::fmt::Debug for SolrSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolrSink").field("the_update_url", &self.the_update_url).finish()
+    }
+}
+
+impl SolrSink {
+    /// 🚀 Builds the client and checks the collection answers. Solr doesn't create
+    /// collections on write, so a missing one fails here instead of on the first batch.
+    pub async fn new(sink_config: SolrSinkConfig) -> Result<Self> {
+        let the_collection_url = format!("{}/{}", sink_config.url.trim_end_matches('/'), sink_config.collection);
+        let client = sink_config
+            .timeouts()
+            .apply(reqwest::Client::builder())
+            .tcp_nodelay(true)
+            .pool_idle_timeout(Duration::from_secs(30))
+            .build()
+            .context("💀 Couldn't build the HTTP client for the Solr sink")?;
+
+        // 🔍 `rows=0`: no docs back, just proof the collection is there and we're let in
+        let the_probe_url = format!("{}/select?q=*:*&rows=0", the_collection_url);
+        let mut the_probe = client.get(&the_probe_url);
+        if let Some(ref username) = sink_config.username {
+            the_probe = the_probe.basic_auth(username, sink_config.password.as_ref());
+        }
+        let the_response = the_probe
+            .send()
+            .await
+            .with_context(|| format!("💀 Solr at '{}' didn't answer. Is it running, and is `url` the `/solr` base?", sink_config.url))?;
+        let the_status = the_response.status();
+        if !the_status.is_success() {
+            let the_body = the_response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "💀 Solr collection '{}' answered {} to a probe query — it doesn't exist, or we're not let in. \
+                 Solr won't create it on write. Body: {}",
+                sink_config.collection,
+                the_status,
+                solr_error_message(&the_body)
+            );
+        }
+
+        info!("☀️ Solr sink ready — updates go to {}/update/json/docs", the_collection_url);
+        Ok(Self { client, sink_config, the_update_url: format!("{}/update", the_collection_url) })
+    }
+
+    /// 📡 POST `body` to `url`, and turn anything but a 2xx into an error that says why.
+    async fn post(&self, url: &str, body: bytes::Bytes) -> Result<()> {
+        let mut the_request = self.client.post(url).header("Content-Type", "application/json");
+        if let Some(ref username) = self.sink_config.username {
+            the_request = the_request.basic_auth(username, self.sink_config.password.as_ref());
+        }
+        let the_response = the_request.body(body).send().await.context("💀 The update never reached Solr")?;
+        let the_status = the_response.status();
+        if the_status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            // 🚦 429 — typed so the flow master hears about it, then retried like any hiccup
+            let body = the_response.text().await.unwrap_or_default();
+            return Err(anyhow::Error::new(SinkThrottled { body }));
+        }
+        if !the_status.is_success() {
+            let the_body = the_response.text().await.unwrap_or_default();
+            anyhow::bail!("💀 Solr refused the update with {}: {}", the_status, solr_error_message(&the_body));
+        }
+        Ok(())
+    }
+}
+
+/// 📜 Solr wraps its complaints in `{"error":{"msg":"..."}}` — dig the message out, or fall
+/// back to the start of the body (a proxy's HTML page, say).
+fn solr_error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.pointer("/error/msg").and_then(|m| m.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.chars().take(300).collect())
+}
+
+#[async_trait]
+impl Sink for SolrSink {
+    /// 📡 One JSON array of docs, one update request.
+    async fn drain(&mut self, payload: Payload) -> Result<()> {
+        let the_url = match self.sink_config.commit_within_ms {
+            Some(the_ms) => format!("{}/json/docs?commitWithin={}", self.the_update_url, the_ms),
+            None => format!("{}/json/docs", self.the_update_url),
+        };
+        self.post(&the_url, payload.to_bytes()).await?;
+        debug!("✅ Solr took the batch");
+        Ok(())
+    }
+
+    /// 💾 One hard commit, so the run's docs are searchable when it ends — unless `commit_on_close = false`.
+    async fn close(&mut self) -> Result<()> {
+        if self.sink_config.commit_on_close {
+            let the_url = self.the_update_url.clone();
+            self.post(&the_url, bytes::Bytes::from_static(br#"{"commit":{}}"#))
+                .await
+                .context("💀 The closing commit failed — docs are indexed but may not be searchable yet")?;
+            debug!("💾 Solr committed");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn make_config(the_url: &str, the_toml: &str) -> SolrSinkConfig {
+        toml::from_str(&format!("url = \"{}/solr\"\ncollection = \"products\"\n{}", the_url, the_toml))
+            .expect("💀 test config didn't parse")
+    }
+
+    async fn mount_probe(the_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/solr/products/select"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"response":{"numFound":0,"docs":[]}}"#))
+            .mount(the_server)
+            .await;
+    }
+
+    /// 🧪 Batches go to `/update/json/docs` with `commitWithin`, and close sends one hard commit.
+    #[tokio::test]
+    async fn the_one_where_the_docs_see_the_sun() -> Result<()> {
+        let the_server = MockServer::start().await;
+        mount_probe(&the_server).await;
+        Mock::given(method("POST"))
+            .and(path("/solr/products/update/json/docs"))
+            .and(query_param("commitWithin", "5000"))
+            .and(header("content-type", "application/json"))
+            .and(body_string(r#"[{"id":"1"},{"id":"2"}]"#))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"responseHeader":{"status":0}}"#))
+            .expect(1)
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/solr/products/update"))
+            .and(body_string(r#"{"commit":{}}"#))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"responseHeader":{"status":0}}"#))
+            .expect(1)
+            .mount(&the_server)
+            .await;
+
+        let mut the_sink = SolrSink::new(make_config(&the_server.uri(), "commit_within_ms = 5000")).await?;
+        the_sink.drain(Payload::from(r#"[{"id":"1"},{"id":"2"}]"#.to_string())).await?;
+        the_sink.close().await?;
+        Ok(())
+    }
+
+    /// 🧪 A missing collection fails at startup; a refused batch fails with Solr's own message;
+    /// a 429 is typed; and `commit_on_close = false` sends nothing at close.
+    #[tokio::test]
+    async fn the_one_where_solr_has_notes() -> Result<()> {
+        let the_server = MockServer::start().await;
+        let the_missing = SolrSink::new(make_config(&the_server.uri(), "")).await;
+        assert!(the_missing.is_err(), "💀 no collection, no sink");
+
+        mount_probe(&the_server).await;
+        Mock::given(method("POST"))
+            .and(path("/solr/products/update/json/docs"))
+            .and(body_string("[1]"))
+            .respond_with(ResponseTemplate::new(400).set_body_string(
+                r#"{"responseHeader":{"status":400},"error":{"msg":"ERROR: [doc=1] unknown field 'colour'","code":400}}"#,
+            ))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/solr/products/update/json/docs"))
+            .and(body_string("[2]"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST")).and(path("/solr/products/update")).respond_with(ResponseTemplate::new(500)).expect(0).mount(&the_server).await;
+
+        let mut the_sink = SolrSink::new(make_config(&the_server.uri(), "commit_on_close = false")).await?;
+        let the_error = format!("{:#}", the_sink.drain(Payload::from("[1]".to_string())).await.expect_err("💀 400 is a failure"));
+        assert!(the_error.contains("unknown field 'colour'"), "{the_error}");
+        let the_throttle = the_sink.drain(Payload::from("[2]".to_string())).await.expect_err("🚦 429 is a failure");
+        assert!(the_throttle.downcast_ref::<SinkThrottled>().is_some());
+        the_sink.close().await?;
+        Ok(())
+    }
+}
@@ -2,11 +2,14 @@
 //
 // Use of this software is governed by the Business Source License
 // included in the LICENSE file and at www.mariadb.com/bsl11.
+use std::sync::Arc;
+
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::Page;
-use crate::backends::{elasticsearch, file, in_mem};
+use crate::progress::DrainMetrics;
+use crate::{Page, PageOrigin};
+use crate::backends::{bigquery, elasticsearch, file, generator, in_mem, redis, rest};
 
 /// 🚰 A source that produces one raw feed per call — maximally ignorant of content format.
 ///
@@ -33,6 +36,12 @@ pub trait Source: std::fmt::Debug {
     /// Returns `Ok(None)` when the tap runs dry. EOF. Fin. The end. 🏁
     /// Returns `Err(...)` when something has gone sideways, sidelong, or fully upside-down.
     async fn pump(&mut self) -> Result<Option<Page>>;
+
+    /// 📍 Where the page the last `pump` returned came from — file, line, byte — if the source
+    /// keeps track. The Joiner uses it to say which line broke a caster. Default: it doesn't.
+    fn take_origin(&mut self) -> Option<PageOrigin> {
+        None
+    }
 }
 
 /// 🎭 The many faces of a Source — a polymorphic casting call for data origins.
@@ -43,11 +52,41 @@ pub trait Source: std::fmt::Debug {
 ///
 /// Think of it as a universal remote. Except it only controls data ingestion. And it's async.
 /// And there is no warranty. Ancient proverb: "He who hardcodes the backend, migrates only once."
+// -- 📏 one per pumper, moved once at startup — boxing the ES variant would buy nothing
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum SourceBackend {
     InMemory(in_mem::InMemorySource),
     File(file::FileSource),
+    Generator(generator::GeneratorSource),
+    Rest(rest::RestSource),
+    Redis(redis::RedisSource),
+    BigQuery(bigquery::BigQuerySource),
     Elasticsearch(elasticsearch::ElasticsearchSource),
+    /// 🧩 Anything that implements `Source` — built from `SourceConfig::Custom`'s factory
+    Custom(Box<dyn Source + Send>),
+}
+
+impl SourceBackend {
+    /// 📊 Hand the run's metrics to a source that reports more than pages — an Elasticsearch
+    /// read in time windows moves `windows_done`. Everyone else has nothing to add.
+    pub fn report_to(&self, metrics: &Arc<DrainMetrics>) {
+        if let SourceBackend::Elasticsearch(es) = self {
+            es.report_to(metrics);
+        }
+    }
+
+    /// 🩺 For `kvx validate`: one authenticated request to the remote end, so a wrong URL or bad
+    /// credentials fail before the run. Redis and file URLs already connect when built; local
+    /// files, generators and in-memory sources have nobody to ask.
+    pub async fn probe(&self) -> Result<()> {
+        match self {
+            SourceBackend::Elasticsearch(es) => es.probe().await.map(|_| ()),
+            SourceBackend::Rest(r) => r.probe().await,
+            SourceBackend::BigQuery(b) => b.probe().await,
+            _ => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
@@ -56,7 +95,25 @@ impl Source for SourceBackend {
         match self {
             SourceBackend::InMemory(i) => i.pump().await,
             SourceBackend::File(f) => f.pump().await,
+            SourceBackend::Generator(g) => g.pump().await,
+            SourceBackend::Rest(r) => r.pump().await,
+            SourceBackend::Redis(r) => r.pump().await,
+            SourceBackend::BigQuery(b) => b.pump().await,
             SourceBackend::Elasticsearch(es) => es.pump().await,
+            SourceBackend::Custom(c) => c.pump().await,
+        }
+    }
+
+    fn take_origin(&mut self) -> Option<PageOrigin> {
+        match self {
+            SourceBackend::File(f) => f.take_origin(),
+            SourceBackend::Custom(c) => c.take_origin(),
+            SourceBackend::InMemory(_)
+            | SourceBackend::Generator(_)
+            | SourceBackend::Rest(_)
+            | SourceBackend::Redis(_)
+            | SourceBackend::BigQuery(_)
+            | SourceBackend::Elasticsearch(_) => None,
         }
     }
 }
@@ -0,0 +1,205 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🏷️ Bulk actions — the verb on every `_bulk` action line.
+//!
+//! `index` overwrites, `create` refuses to, `update` patches, `upsert` patches-or-creates.
+//! Data streams only speak `create`.
+//! Shared by the two bulk casters (`NdJsonToBulk`, `PitToBulk`) so they can't drift apart.
+//!
+//! 🧠 Knowledge graph:
+//! - Resolved once in `PageToEntriesCaster::try_from_configs` from the ES sink's `op_type`
+//! - `update` / `upsert` wrap the source line: `{"doc":..}` / `{"doc":..,"doc_as_upsert":true}`,
+//!   and need an `_id` to aim at — so they're for sources whose docs have one (Elasticsearch)
+//! - `TombstoneRule` (`[sink_config.Elasticsearch.tombstone]`) turns matching docs into
+//!   one-line `{"delete":{"_id":..}}` actions, so a sync job can remove what the source deleted
+//! - `data_stream = true` → `Create`, plus an `@timestamp` check on every doc
+//!   (`require_timestamp`) — a data stream refuses docs without one anyway, we just say so
+//!   sooner and louder 🦆
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+fn default_tombstone_value() -> serde_json::Value {
+    serde_json::Value::Bool(true)
+}
+
+/// 🪦 "When `field` equals `equals`, delete the target doc instead of writing this one."
+///
+/// `field` is a top-level field of the source document. The `_id` to delete comes from the
+/// hit (Elasticsearch sources) or from `id_field` in the doc (NDJSON sources, which have no hit).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TombstoneRule {
+    /// 🔎 e.g. `deleted`
+    pub field: String,
+    /// 🎯 The value that means "gone" — defaults to `true`
+    #[serde(default = "default_tombstone_value")]
+    pub equals: serde_json::Value,
+    /// 🔑 Where the `_id` lives in the doc. Required for NDJSON sources; for Elasticsearch
+    /// sources it overrides the hit's `_id`.
+    #[serde(default)]
+    pub id_field: Option<String>,
+}
+
+/// 🪦 What the rule found in one doc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tombstone {
+    /// 🧍 Not a tombstone — write it normally
+    Alive,
+    /// ⚰️ Delete, by the `_id` found in `id_field` (or `None`: use the hit's own `_id`)
+    Dead(Option<String>),
+}
+
+impl TombstoneRule {
+    /// 🔎 Is this doc a tombstone? Only parses the top level; nested values stay raw.
+    pub fn inspect(&self, doc: &str) -> Result<Tombstone> {
+        let the_fields: HashMap<&str, &RawValue> =
+            serde_json::from_str(doc).context("💀 The tombstone rule needs JSON-object documents")?;
+        let the_verdict = match the_fields.get(self.field.as_str()) {
+            Some(the_raw) => serde_json::from_str::<serde_json::Value>(the_raw.get())? == self.equals,
+            None => false,
+        };
+        if !the_verdict {
+            return Ok(Tombstone::Alive);
+        }
+        let Some(ref the_id_field) = self.id_field else {
+            return Ok(Tombstone::Dead(None));
+        };
+        let the_id = match the_fields.get(the_id_field.as_str()).map(|raw| serde_json::from_str(raw.get())) {
+            Some(Ok(serde_json::Value::String(the_id))) => the_id,
+            Some(Ok(the_number @ serde_json::Value::Number(_))) => the_number.to_string(),
+            _ => anyhow::bail!(
+                "💀 A tombstone has no usable `{}` to delete by (needs a string or number)",
+                the_id_field
+            ),
+        };
+        Ok(Tombstone::Dead(Some(the_id)))
+    }
+}
+
+/// ⚰️ `{"delete":{"_index":..,"_id":..}}\n` — one line, no document. `doc_type` is the
+/// 6.x `_type`, for `compat = "es6"` sinks.
+pub fn delete_line(index: Option<&str>, doc_type: Option<&str>, id: &str, routing: Option<&str>) -> String {
+    let mut the_meta = serde_json::Map::new();
+    if let Some(the_index) = index {
+        the_meta.insert("_index".to_string(), the_index.into());
+    }
+    if let Some(the_type) = doc_type {
+        the_meta.insert("_type".to_string(), the_type.into());
+    }
+    the_meta.insert("_id".to_string(), id.into());
+    if let Some(the_routing) = routing {
+        the_meta.insert("_routing".to_string(), the_routing.into());
+    }
+    format!("{}\n", serde_json::json!({ "delete": the_meta }))
+}
+
+/// 🏷️ Which `_bulk` operation each document becomes — `op_type` on the ES sink.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkAction {
+    /// 📝 Create or overwrite
+    #[default]
+    Index,
+    /// 🆕 Create only — the one data streams require
+    Create,
+    /// 🩹 Patch an existing doc; a missing one is a per-doc 404
+    Update,
+    /// 🩹🆕 Patch it if it's there, create it if it isn't (`doc_as_upsert`)
+    Upsert,
+}
+
+impl BulkAction {
+    /// 🏷️ The action verb as it appears on the wire — an upsert is an `update` with a flag
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Index => "index",
+            Self::Create => "create",
+            Self::Update | Self::Upsert => "update",
+        }
+    }
+
+    /// 🔑 Updates aim at a specific `_id`; without one there's nothing to patch
+    pub fn needs_id(&self) -> bool {
+        matches!(self, Self::Update | Self::Upsert)
+    }
+
+    /// 📄 Append the document line for this action (without the trailing newline).
+    pub fn push_source(&self, out: &mut String, source: &str) {
+        match self {
+            Self::Index | Self::Create => out.push_str(source),
+            Self::Update => {
+                out.push_str(r#"{"doc":"#);
+                out.push_str(source);
+                out.push('}');
+            }
+            Self::Upsert => {
+                out.push_str(r#"{"doc":"#);
+                out.push_str(source);
+                out.push_str(r#","doc_as_upsert":true}"#);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Stamped<'a> {
+    #[serde(rename = "@timestamp", borrow, default)]
+    timestamp: Option<&'a RawValue>,
+}
+
+/// ⏰ Data streams need `@timestamp` on every doc. Bail with the offender if it's missing or null.
+pub fn require_timestamp(doc: &str) -> Result<()> {
+    let the_stamp: Stamped<'_> =
+        serde_json::from_str(doc).context("💀 A document bound for a data stream isn't a JSON object")?;
+    match the_stamp.timestamp {
+        Some(the_value) if the_value.get() != "null" => Ok(()),
+        _ => {
+            let the_preview: String = doc.chars().take(200).collect();
+            anyhow::bail!(
+                "💀 Data streams need an `@timestamp` on every document, and this one has none: {}",
+                the_preview
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_the_patch_comes_wrapped() {
+        let mut the_line = String::new();
+        BulkAction::Upsert.push_source(&mut the_line, r#"{"a":1}"#);
+        assert_eq!(the_line, r#"{"doc":{"a":1},"doc_as_upsert":true}"#);
+        assert_eq!(BulkAction::Upsert.as_str(), "update");
+        let mut the_line = String::new();
+        BulkAction::Index.push_source(&mut the_line, r#"{"a":1}"#);
+        assert_eq!(the_line, r#"{"a":1}"#);
+    }
+
+    #[test]
+    fn the_one_where_the_dead_are_recognized() -> Result<()> {
+        let the_rule: TombstoneRule = toml::from_str("field = \"deleted\"\nid_field = \"sku\"")?;
+        assert_eq!(the_rule.inspect(r#"{"sku":"A-1","deleted":true}"#)?, Tombstone::Dead(Some("A-1".to_string())));
+        assert_eq!(the_rule.inspect(r#"{"sku":42,"deleted":true}"#)?, Tombstone::Dead(Some("42".to_string())));
+        assert_eq!(the_rule.inspect(r#"{"sku":"A-2","deleted":false}"#)?, Tombstone::Alive);
+        assert_eq!(the_rule.inspect(r#"{"sku":"A-3"}"#)?, Tombstone::Alive);
+        assert!(the_rule.inspect(r#"{"deleted":true}"#).is_err(), "💀 a tombstone with no id can't delete anything");
+        assert_eq!(delete_line(Some("i"), None, "A-1", None), "{\"delete\":{\"_index\":\"i\",\"_id\":\"A-1\"}}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_clock_must_be_punched() {
+        assert!(require_timestamp(r#"{"@timestamp":"2024-01-01T00:00:00Z","msg":"hi"}"#).is_ok());
+        assert!(require_timestamp(r#"{"@timestamp":1704067200000}"#).is_ok());
+        assert!(require_timestamp(r#"{"msg":"no clock"}"#).is_err());
+        assert!(require_timestamp(r#"{"@timestamp":null}"#).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔑 Deterministic `_id`s — the same doc gets the same `_id`, every run.
+//!
+//! 🎬 *[the NDJSON export has no ids. the load dies at 80%. somebody reruns it.]*
+//! *[the index now holds 1.8 copies of the catalog. the search page shows every sock twice.]*
+//!
+//! `[sink_config.Elasticsearch.doc_id]` hashes each doc into its `_id`, so a rerun overwrites
+//! what the last run wrote instead of adding to it.
+//!
+//! 🧠 Knowledge graph:
+//! - `fields = ["sku", "region"]` → hash of those values (field paths, nested works);
+//!   `fields` empty or unset → hash of the whole doc line, after `[[transforms]]`
+//! - Hash: FNV-1a 128-bit, 32 hex chars. Same family as `PitToIds`'s 64-bit one, wide
+//!   enough that a billion docs don't collide. Not cryptographic — it's a name, not a secret
+//! - Whole-doc ids follow the bytes: reformatting or reordering keys changes the `_id`
+//! - A doc with none of the fields is refused — every such doc would share one `_id`,
+//!   and each would overwrite the last 🦆
+//! - Used by `NdJsonToBulk` only. Elasticsearch hits already have an `_id`
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::transforms::field_path::FieldPath;
+
+// 🔢 FNV-1a 128-bit constants
+const FNV_OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+const FNV_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+// ✂️ between field values, so `["ab","c"]` and `["a","bc"]` don't hash alike
+const FIELD_SEPARATOR: u8 = 0x1f;
+
+/// 🔑 `[sink_config.Elasticsearch.doc_id]` — which part of the doc names it.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct DocIdConfig {
+    /// 🔎 Field paths to hash, in order. Empty = the whole doc
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+/// 🔑 A resolved `doc_id` rule — field paths parsed once.
+#[derive(Debug, Clone, Default)]
+pub struct DocId {
+    fields: Vec<FieldPath>,
+}
+
+impl DocId {
+    /// 🔨 Parse the field paths up front.
+    pub fn new(config: &DocIdConfig) -> Result<Self> {
+        let fields = config
+            .fields
+            .iter()
+            .map(|the_field| FieldPath::parse(the_field).with_context(|| format!("💀 doc_id field '{}'", the_field)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { fields })
+    }
+
+    /// 🏷️ The `_id` for one doc line.
+    pub fn id_for(&self, doc: &str) -> Result<String> {
+        if self.fields.is_empty() {
+            return Ok(format!("{:032x}", fnv1a_128(FNV_OFFSET_BASIS, doc.as_bytes())));
+        }
+        let the_doc: Value = serde_json::from_str(doc).context("💀 doc_id.fields needs JSON-object documents")?;
+        let mut the_hash = FNV_OFFSET_BASIS;
+        let mut found_any = false;
+        for the_field in &self.fields {
+            let the_value = the_field.get(&the_doc);
+            found_any |= the_value.is_some_and(|v| !v.is_null());
+            the_hash = fnv1a_128(the_hash, the_value.unwrap_or(&Value::Null).to_string().as_bytes());
+            the_hash = fnv1a_128(the_hash, &[FIELD_SEPARATOR]);
+        }
+        if !found_any {
+            anyhow::bail!(
+                "💀 A doc has none of the doc_id fields, so it has nothing to be named by: {}",
+                doc.chars().take(120).collect::<String>()
+            );
+        }
+        Ok(format!("{:032x}", the_hash))
+    }
+}
+
+/// 🧮 FNV-1a, continuing from `the_hash`.
+fn fnv1a_128(mut the_hash: u128, bytes: &[u8]) -> u128 {
+    for byte in bytes {
+        the_hash ^= *byte as u128;
+        the_hash = the_hash.wrapping_mul(FNV_PRIME);
+    }
+    the_hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn the_rule(fields: &[&str]) -> Result<DocId> {
+        DocId::new(&DocIdConfig { fields: fields.iter().map(|f| f.to_string()).collect() })
+    }
+
+    #[test]
+    fn the_one_where_the_same_doc_gets_the_same_name_twice() -> Result<()> {
+        let the_whole = the_rule(&[])?;
+        let the_doc = r#"{"sku":"A-1","region":{"code":"eu"},"stock":3}"#;
+        assert_eq!(the_whole.id_for(the_doc)?, the_whole.id_for(the_doc)?);
+        assert_eq!(the_whole.id_for(the_doc)?.len(), 32);
+        assert_ne!(the_whole.id_for(the_doc)?, the_whole.id_for(r#"{"sku":"A-1","region":{"code":"eu"},"stock":4}"#)?);
+        // -- 🧪 known answer: FNV-1a 128 of "a"
+        assert_eq!(format!("{:032x}", fnv1a_128(FNV_OFFSET_BASIS, b"a")), "d228cb696f1a8caf78912b704e4a8964");
+
+        let the_keyed = the_rule(&["sku", "region.code"])?;
+        assert_eq!(
+            the_keyed.id_for(the_doc)?,
+            the_keyed.id_for(r#"{"stock":9,"region":{"code":"eu"},"sku":"A-1"}"#)?,
+            "🔑 only the named fields count"
+        );
+        assert_ne!(the_keyed.id_for(the_doc)?, the_keyed.id_for(r#"{"sku":"A-1","region":{"code":"us"}}"#)?);
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_fields_do_not_run_together() -> Result<()> {
+        let the_keyed = the_rule(&["a", "b"])?;
+        assert_ne!(the_keyed.id_for(r#"{"a":"ab","b":"c"}"#)?, the_keyed.id_for(r#"{"a":"a","b":"bc"}"#)?);
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_a_nameless_doc_is_turned_away() -> Result<()> {
+        let the_keyed = the_rule(&["sku"])?;
+        assert!(the_keyed.id_for(r#"{"name":"mystery"}"#).is_err());
+        assert!(the_keyed.id_for(r#"{"sku":null}"#).is_err());
+        assert!(the_rule(&["a..b"]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🗂️ Index templates — `index = "{_type}-{Project.Name}"`, decided one document at a time.
+//!
+//! 🎬 *[one source index. four hundred projects inside it. the target wants one index each.]*
+//! *["just run it four hundred times," says someone who has never run it four hundred times.]*
+//!
+//! A sink `index` containing `{...}` is a template, not a name. Each `{path}` is filled from
+//! the document: `Project.Name` walks nested objects. A placeholder starting with `_`
+//! (`{_index}`, `{_id}`, `{_type}`, `{_routing}`) reads the search hit's metadata first,
+//! then the document. The result is lowercased — Elasticsearch index names have to be.
+//!
+//! A `%{+yyyy.MM.dd}` placeholder is Logstash-style date math: the document's timestamp
+//! (`@timestamp`, or `with_date_field`) in a Joda-style format, in UTC — `logs-%{+yyyy.MM.dd}`
+//! writes each day to its own rolling index.
+//!
+//! 🧠 Knowledge graph:
+//! - Parsed once by `ElasticsearchSinkConfig::index_template()`; the sink then posts to
+//!   plain `/_bulk` and skips its startup existence check (there's no one index to check)
+//! - `NdJsonToBulk` / `PitToBulk` call `render` per doc and write `_index` into the action line
+//! - `IndexMap` (`index_map`) then renames the resolved name, template output and hit `_index` alike
+//! - Date formats are translated to chrono's strftime once, at parse time (`joda_to_strftime`).
+//!   Timestamps may be RFC 3339, `YYYY-MM-DD[THH:MM:SS]` (taken as UTC) or epoch millis
+//! - Only the placeholders' fields are parsed, as `RawValue`s — the rest of the doc stays raw 🦆
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::value::RawValue;
+
+/// 🚫 Characters Elasticsearch rejects in index names
+const FORBIDDEN_IN_INDEX_NAMES: &str = "\\/*?\"<>| ,#";
+
+/// 📅 Where `%{+...}` reads the document's time unless told otherwise — Logstash's choice too
+pub const DEFAULT_DATE_FIELD: &str = "@timestamp";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Literal(String),
+    /// 🔎 A dotted path, pre-split: `Project.Name` → ["Project", "Name"]
+    Field(Vec<String>),
+    /// 📅 `%{+yyyy.MM.dd}` — the date field, formatted with this (already translated) strftime
+    Date(String),
+}
+
+/// 🗂️ A parsed `index` template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexTemplate {
+    pieces: Vec<Piece>,
+    /// 📅 The timestamp `%{+...}` pieces format, pre-split like a `Field` path
+    date_field: Vec<String>,
+}
+
+/// 🏷️ The bits of a search hit a `{_...}` placeholder may want. Empty for NDJSON docs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HitMeta<'a> {
+    pub index: Option<&'a str>,
+    pub id: Option<&'a str>,
+    pub doc_type: Option<&'a str>,
+    pub routing: Option<&'a str>,
+}
+
+impl HitMeta<'_> {
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "_index" => self.index,
+            "_id" => self.id,
+            "_type" => self.doc_type,
+            "_routing" => self.routing,
+            _ => None,
+        }
+    }
+}
+
+impl IndexTemplate {
+    /// 🧐 Does this `index` value have placeholders at all?
+    pub fn is_template(index: &str) -> bool {
+        index.contains('{')
+    }
+
+    /// 🔨 Parse `logs-{service.name}-{env}`. Unbalanced or empty braces are config errors.
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut the_pieces = Vec::new();
+        let mut the_rest = template;
+        while let Some(the_open) = the_rest.find('{') {
+            if the_rest[..the_open].contains('}') {
+                anyhow::bail!("💀 index template '{}' has a `}}` with no `{{`", template);
+            }
+            if the_open > 0 {
+                the_pieces.push(Piece::Literal(the_rest[..the_open].to_string()));
+            }
+            let the_close = the_rest[the_open..]
+                .find('}')
+                .with_context(|| format!("💀 index template '{}' has a `{{` that never closes", template))?;
+            let the_path = the_rest[the_open + 1..the_open + the_close].trim();
+            // -- 📅 `%{+fmt}`: the `%` belongs to the placeholder, not to the literal before it
+            if let Some(the_format) = the_path.strip_prefix('+')
+                && the_rest[..the_open].ends_with('%')
+            {
+                if let Some(Piece::Literal(the_literal)) = the_pieces.last_mut() {
+                    the_literal.pop();
+                    if the_literal.is_empty() {
+                        the_pieces.pop();
+                    }
+                }
+                let the_strftime = joda_to_strftime(the_format)
+                    .with_context(|| format!("💀 index template '{}' has a date format we can't read", template))?;
+                the_pieces.push(Piece::Date(the_strftime));
+                the_rest = &the_rest[the_open + the_close + 1..];
+                continue;
+            }
+            if the_path.is_empty() || the_path.contains('{') || the_path.split('.').any(str::is_empty) {
+                anyhow::bail!("💀 index template '{}' has an empty or malformed placeholder `{{{}}}`", template, the_path);
+            }
+            the_pieces.push(Piece::Field(the_path.split('.').map(str::to_string).collect()));
+            the_rest = &the_rest[the_open + the_close + 1..];
+        }
+        if the_rest.contains('}') {
+            anyhow::bail!("💀 index template '{}' has a `}}` with no `{{`", template);
+        }
+        if !the_rest.is_empty() {
+            the_pieces.push(Piece::Literal(the_rest.to_string()));
+        }
+        Ok(Self { pieces: the_pieces, date_field: vec![DEFAULT_DATE_FIELD.to_string()] })
+    }
+
+    /// 📅 Read `%{+...}` dates from `field` (a dotted path) instead of `@timestamp`.
+    pub fn with_date_field(mut self, field: &str) -> Self {
+        self.date_field = field.split('.').map(str::to_string).collect();
+        self
+    }
+
+    /// 🖨️ The target index for one document.
+    pub fn render(&self, doc: &str, meta: HitMeta<'_>) -> Result<String> {
+        let mut the_name = String::new();
+        for the_piece in &self.pieces {
+            match the_piece {
+                Piece::Literal(the_text) => the_name.push_str(the_text),
+                Piece::Field(the_path) => {
+                    let the_value = match meta.get(&the_path[0]).filter(|_| the_path.len() == 1) {
+                        Some(the_meta_value) => the_meta_value.to_string(),
+                        None => lookup(doc, the_path)?.with_context(|| {
+                            let the_preview: String = doc.chars().take(200).collect();
+                            format!(
+                                "💀 index template needs `{}`, and this document has no string/number there: {}",
+                                the_path.join("."),
+                                the_preview
+                            )
+                        })?,
+                    };
+                    the_name.push_str(&the_value);
+                }
+                Piece::Date(the_format) => {
+                    let the_raw = lookup(doc, &self.date_field)?.with_context(|| {
+                        let the_preview: String = doc.chars().take(200).collect();
+                        format!("💀 index template needs a date in `{}`, and this document has none: {}", self.date_field.join("."), the_preview)
+                    })?;
+                    let the_time = parse_timestamp(&the_raw).with_context(|| {
+                        format!("💀 index template can't read '{}' in `{}` as a date", the_raw, self.date_field.join("."))
+                    })?;
+                    the_name.push_str(&the_time.format(the_format).to_string());
+                }
+            }
+        }
+        // -- 🚫 ES refuses these in index names anyway; a `"` would also break the action line's JSON
+        if let Some(the_culprit) = the_name.chars().find(|c| FORBIDDEN_IN_INDEX_NAMES.contains(*c) || c.is_control()) {
+            anyhow::bail!("💀 index template rendered '{}', and {:?} isn't allowed in an index name", the_name, the_culprit);
+        }
+        Ok(the_name.to_lowercase())
+    }
+}
+
+/// 🔀 `index_map` — exact renames for resolved index names. Empty renames nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexMap {
+    renames: BTreeMap<String, String>,
+}
+
+impl IndexMap {
+    /// 🔨 Check every target is a name ES would accept, and that nothing is renamed to nothing.
+    pub fn new(renames: &BTreeMap<String, String>) -> Result<Self> {
+        for (the_from, the_to) in renames {
+            let the_culprit = the_to.chars().find(|c| FORBIDDEN_IN_INDEX_NAMES.contains(*c) || c.is_control());
+            if the_to.is_empty() || the_culprit.is_some() || the_to.chars().any(char::is_uppercase) {
+                anyhow::bail!(
+                    "💀 index_map sends '{}' to '{}', which isn't a valid index name (lowercase, no spaces or {})",
+                    the_from,
+                    the_to,
+                    FORBIDDEN_IN_INDEX_NAMES.trim()
+                );
+            }
+        }
+        Ok(Self { renames: renames.clone() })
+    }
+
+    /// 🔀 The new name for `index`, or `index` itself when it isn't listed.
+    pub fn rename<'a>(&'a self, index: &'a str) -> &'a str {
+        self.renames.get(index).map_or(index, String::as_str)
+    }
+}
+
+/// 📅 Joda-style `yyyy.MM.dd` (what Logstash and ES date math speak) → chrono's `%Y.%m.%d`.
+/// Letters we don't know are refused rather than printed, so a typo can't become an index name.
+fn joda_to_strftime(joda: &str) -> Result<String> {
+    let mut the_strftime = String::new();
+    let mut the_chars = joda.chars().peekable();
+    while let Some(the_char) = the_chars.next() {
+        if !the_char.is_ascii_alphabetic() {
+            if the_char == '%' {
+                the_strftime.push('%');
+            }
+            the_strftime.push(the_char);
+            continue;
+        }
+        let mut the_run = 1;
+        while the_chars.next_if_eq(&the_char).is_some() {
+            the_run += 1;
+        }
+        let the_spec = match (the_char, the_run) {
+            ('y', 4) => "%Y",
+            ('y', 2) => "%y",
+            // 📅 Joda's `Y` is week-year, but Logstash configs write `YYYY` meaning the calendar year
+            ('Y', 4) => "%Y",
+            ('Y', 2) => "%y",
+            ('x', 4) => "%G",
+            ('x', 2) => "%g",
+            ('M', 2) => "%m",
+            ('M', 1) => "%-m",
+            ('w', 2) => "%V",
+            ('w', 1) => "%-V",
+            ('d', 2) => "%d",
+            ('d', 1) => "%-d",
+            ('D', 3) => "%j",
+            ('H', 2) => "%H",
+            ('H', 1) => "%-H",
+            ('m', 2) => "%M",
+            ('s', 2) => "%S",
+            _ => anyhow::bail!(
+                "💀 '{}' in '{}' isn't a date pattern we support (yyyy, yy, YYYY, YY, xxxx, MM, M, ww, dd, d, DDD, HH, H, mm, ss)",
+                the_char.to_string().repeat(the_run),
+                joda
+            ),
+        };
+        the_strftime.push_str(the_spec);
+    }
+    if the_strftime.is_empty() {
+        anyhow::bail!("💀 `%{{+}}` needs a date format, like `%{{+yyyy.MM.dd}}`");
+    }
+    Ok(the_strftime)
+}
+
+/// 📅 RFC 3339, `YYYY-MM-DD[THH:MM:SS[.fff]]` (no zone = UTC), or epoch milliseconds.
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(the_time) = DateTime::parse_from_rfc3339(raw) {
+        return Some(the_time.with_timezone(&Utc));
+    }
+    if let Ok(the_time) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(the_time.and_utc());
+    }
+    if let Ok(the_day) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return the_day.and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+    }
+    raw.parse::<i64>().ok().and_then(DateTime::from_timestamp_millis)
+}
+
+/// 🔎 Walk `path` through nested objects. `Ok(None)` when it's missing, null, or not a scalar.
+fn lookup(doc: &str, path: &[String]) -> Result<Option<String>> {
+    let mut the_current: &str = doc;
+    // -- 🪆 each level borrows from the doc; only the objects on the path get tokenized
+    for the_key in path {
+        let Ok(the_level) = serde_json::from_str::<HashMap<&str, &RawValue>>(the_current) else {
+            return Ok(None);
+        };
+        let Some(the_next) = the_level.get(the_key.as_str()) else {
+            return Ok(None);
+        };
+        the_current = the_next.get();
+    }
+    Ok(match serde_json::from_str::<serde_json::Value>(the_current)? {
+        serde_json::Value::String(the_text) => Some(the_text),
+        the_scalar @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_)) => Some(the_scalar.to_string()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_every_project_gets_its_own_room() -> Result<()> {
+        let the_template = IndexTemplate::parse("{_type}-{Project.Name}")?;
+        let the_doc = r#"{"Project":{"Name":"Apollo","Id":11},"_type":"Defect"}"#;
+        assert_eq!(the_template.render(the_doc, HitMeta::default())?, "defect-apollo");
+        let the_meta = HitMeta { doc_type: Some("story"), ..Default::default() };
+        assert_eq!(the_template.render(the_doc, the_meta)?, "story-apollo", "🏷️ hit metadata wins for `_` fields");
+        assert_eq!(IndexTemplate::parse("p-{Project.Id}")?.render(the_doc, HitMeta::default())?, "p-11");
+        assert!(the_template.render(r#"{"_type":"Defect"}"#, HitMeta::default()).is_err(), "💀 a missing field can't name an index");
+        assert!(the_template.render(r#"{"_type":"a\"b","Project":{"Name":"x"}}"#, HitMeta::default()).is_err(), "🚫 quotes would break the action line");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_old_names_get_new_nameplates() -> Result<()> {
+        let the_renames = BTreeMap::from([("old-products".to_string(), "products-v2".to_string())]);
+        let the_map = IndexMap::new(&the_renames)?;
+        assert_eq!(the_map.rename("old-products"), "products-v2");
+        assert_eq!(the_map.rename("old-users"), "old-users", "🚶 unlisted names pass through");
+        let the_shouty = BTreeMap::from([("a".to_string(), "Products".to_string())]);
+        assert!(IndexMap::new(&the_shouty).is_err(), "🚫 ES index names are lowercase");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_every_day_gets_its_own_index() -> Result<()> {
+        let the_template = IndexTemplate::parse("logs-{service}-%{+yyyy.MM.dd}")?;
+        let the_doc = r#"{"service":"Checkout","@timestamp":"2026-03-14T23:30:00-02:00"}"#;
+        assert_eq!(the_template.render(the_doc, HitMeta::default())?, "logs-checkout-2026.03.15", "🌍 dates are UTC");
+        let the_weekly = IndexTemplate::parse("%{+xxxx.ww}-metrics")?.with_date_field("event.created");
+        assert_eq!(the_weekly.render(r#"{"event":{"created":1767225600000}}"#, HitMeta::default())?, "2026.01-metrics");
+        assert_eq!(the_weekly.render(r#"{"event":{"created":"2025-12-29"}}"#, HitMeta::default())?, "2026.01-metrics");
+        assert!(the_template.render(r#"{"service":"a"}"#, HitMeta::default()).is_err(), "💀 no date, no day");
+        assert!(the_template.render(r#"{"service":"a","@timestamp":"yesterday"}"#, HitMeta::default()).is_err());
+        assert!(IndexTemplate::parse("logs-%{+yyyy.QQ}").is_err(), "💀 unknown letters aren't printed");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_logstash_shouts_the_year() -> Result<()> {
+        let the_template = IndexTemplate::parse("logstash-%{+YYYY.MM.dd}")?;
+        let the_doc = r#"{"@timestamp":"2026-03-15T08:00:00Z"}"#;
+        assert_eq!(the_template.render(the_doc, HitMeta::default())?, "logstash-2026.03.15");
+        // 🎆 New Year's Eve stays in its calendar year — no week-year surprise
+        let the_nye = r#"{"@timestamp":"2024-12-31T12:00:00Z"}"#;
+        assert_eq!(the_template.render(the_nye, HitMeta::default())?, "logstash-2024.12.31");
+        assert_eq!(IndexTemplate::parse("%{+YY.MM}")?.render(the_doc, HitMeta::default())?, "26.03");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_braces_dont_add_up() {
+        assert!(IndexTemplate::parse("logs-{service").is_err());
+        assert!(IndexTemplate::parse("logs-{}").is_err());
+        assert!(IndexTemplate::parse("logs-{a..b}").is_err());
+        assert!(IndexTemplate::parse("logs}").is_err());
+        assert!(!IndexTemplate::is_template("plain-old-index"));
+    }
+}
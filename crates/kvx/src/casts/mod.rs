@@ -17,22 +17,37 @@
 //! - **Caster** trait: `fn cast(&self, feed: String) -> Result<String>`
 //! - **DocumentCaster** enum: dispatches to concrete casters (same pattern as ManifoldBackend)
 //! - Resolution: `DocumentCaster::from_configs(source, sink)` matches the pair
+//! - `[[transforms]]` ride inside the per-doc casters: `with_transforms` hands them the chain,
+//!   and swaps NDJSON `Passthrough` for `NdJsonTransform` so there's a per-doc step to run
 //!
 //! 🦆 The duck casts no shadow. Only feeds.
 //!
 //! ⚠️ The singularity will cast its own feeds. Until then, we have enums.
 
+pub mod bulk_action;
+pub mod doc_id;
+pub mod index_template;
 pub mod passthrough;
 pub mod ndjson_to_bulk;
 pub mod ndjson_split;
+pub mod ndjson_transform;
 pub mod pit_to_bulk;
 pub mod pit_to_json;
+pub mod pit_to_ids;
 use ndjson_to_bulk::NdJsonToBulk;
 use ndjson_split::NdJsonSplit;
+use ndjson_transform::NdJsonTransform;
 use pit_to_bulk::PitToBulk;
 use pit_to_json::PitToJson;
-
-use crate::config::{SourceConfig, SinkConfig};
+use pit_to_ids::PitToIds;
+
+use crate::backends::elasticsearch::{Compat, ElasticsearchSinkConfig};
+use crate::backends::file::EsHitsFormat;
+use crate::config::{AppConfig, SourceConfig, SinkConfig};
+use crate::manifolds::ManifoldFormat;
+use crate::transforms::{Rejects, TransformChain};
+use bulk_action::{BulkAction, TombstoneRule};
+use doc_id::DocId;
 use anyhow::Result;
 use crate::Page;
 use crate::Entry;
@@ -47,6 +62,32 @@ pub trait Caster: std::fmt::Debug {
     fn cast(&self, page: Page) -> Result<Vec<Entry>>;
 }
 
+/// 📍 Which line of the page a cast error came from — attached as context, so the Joiner can
+/// `downcast_ref` it and turn it into a file, line and byte offset with the page's `PageOrigin`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DocPosition {
+    /// 🔢 The line's index in the page, blank lines included (0-based)
+    pub index: usize,
+    /// 📏 Where the line starts, in bytes from the start of the page
+    pub page_offset: usize,
+}
+
+impl std::fmt::Display for DocPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "doc {} of the page (byte {} of the page)", self.index + 1, self.page_offset)
+    }
+}
+
+/// 🔪 The page's `\n`-separated lines, each with its `DocPosition`. Empty lines included — skip them yourself.
+pub fn lines_with_position(page: &str) -> impl Iterator<Item = (DocPosition, &str)> {
+    let mut the_offset = 0;
+    page.split('\n').enumerate().map(move |(index, line)| {
+        let the_position = DocPosition { index, page_offset: the_offset };
+        the_offset += line.len() + 1;
+        (the_position, line)
+    })
+}
+
 // ===== Enum Dispatcher =====
 
 /// 🎭 The polymorphic caster — dispatches to the right concrete caster at runtime.
@@ -60,12 +101,16 @@ pub enum PageToEntriesCaster {
     NdJsonToBulk(ndjson_to_bulk::NdJsonToBulk),
     // -- 🔪 NDJSON raw docs → individual JSON entries (no bulk headers, for Meilisearch)
     NdJsonSplit(ndjson_split::NdJsonSplit),
+    // -- 🔧 NDJSON raw docs → transformed NDJSON docs (Passthrough's stand-in when transforms are set)
+    NdJsonTransform(ndjson_transform::NdJsonTransform),
     // -- 🚶 Identity cast — feed passes through unchanged, like TSA PreCheck for data
     Passthrough(passthrough::Passthrough),
     // -- 📡🎭 ES _search PIT response → _bulk NDJSON (extracts hits from envelope)
     PitToBulk(pit_to_bulk::PitToBulk),
     // -- 🔍🎭 ES _search PIT response → raw JSON entries (for Meilisearch, no bulk headers)
     PitToJson(pit_to_json::PitToJson),
+    // -- 🪪 ES _search PIT response → compact `index\tid[\tversion][\thash]` lines (ID export)
+    PitToIds(pit_to_ids::PitToIds),
 }
 
 impl Caster for PageToEntriesCaster {
@@ -75,9 +120,11 @@ impl Caster for PageToEntriesCaster {
         match self {
             Self::NdJsonToBulk(t) => t.cast(page),
             Self::NdJsonSplit(t) => t.cast(page),
+            Self::NdJsonTransform(t) => t.cast(page),
             Self::Passthrough(t) => t.cast(page),
             Self::PitToBulk(t) => t.cast(page),
             Self::PitToJson(t) => t.cast(page),
+            Self::PitToIds(t) => t.cast(page),
         }
     }
 }
@@ -85,6 +132,60 @@ impl Caster for PageToEntriesCaster {
 
 // ===== Factory =====
 
+/// 🏷️ The `_bulk` verb an Elasticsearch sink asks for: its `op_type`, except that data
+/// streams only accept `create` (so `index` quietly becomes `create`, and updates are refused).
+fn bulk_action_for(sink: &ElasticsearchSinkConfig) -> Result<BulkAction> {
+    match (sink.data_stream, sink.op_type) {
+        (false, the_action) => Ok(the_action),
+        (true, BulkAction::Index | BulkAction::Create) => Ok(BulkAction::Create),
+        (true, the_action) => anyhow::bail!(
+            "💀 op_type '{:?}' can't write to a data stream — data streams only take `create`.",
+            the_action
+        ),
+    }
+}
+
+/// 🪦 The sink's tombstone rule, checked against what the source can give it: NDJSON docs
+/// have no hit `_id`, so the rule must name an `id_field`. Data streams can't be deleted from
+/// through the stream name, so tombstones and data streams don't mix.
+fn tombstone_for(sink: &ElasticsearchSinkConfig, needs_id_field: bool) -> Result<Option<TombstoneRule>> {
+    let Some(ref the_rule) = sink.tombstone else {
+        return Ok(None);
+    };
+    if sink.data_stream {
+        anyhow::bail!("💀 tombstone deletes can't target a data stream — it only takes `create`.");
+    }
+    if needs_id_field && the_rule.id_field.is_none() {
+        anyhow::bail!(
+            "💀 tombstone needs `id_field` for this source — NDJSON lines have no `_id`, so say which field holds it."
+        );
+    }
+    Ok(Some(the_rule.clone()))
+}
+
+/// 🦖 The `_type` a `compat = "es6"` sink puts on action lines. 6.x had no data streams, and
+/// `mapping_type` without `compat = "es6"` would be silently ignored — both are refused.
+fn mapping_type_for(sink: &ElasticsearchSinkConfig) -> Result<Option<String>> {
+    if sink.compat == Compat::Modern && sink.mapping_type.is_some() {
+        anyhow::bail!("💀 mapping_type only applies with compat = \"es6\" — 7.x+ `_bulk` has no types.");
+    }
+    let Some(the_type) = sink.bulk_mapping_type() else {
+        return Ok(None);
+    };
+    if sink.data_stream {
+        anyhow::bail!("💀 compat = \"es6\" and data_stream = true don't mix — data streams arrived in 7.9.");
+    }
+    if the_type.is_empty() || the_type.contains(['"', '\\']) {
+        anyhow::bail!("💀 mapping_type '{}' isn't a usable type name", the_type);
+    }
+    Ok(Some(the_type.to_string()))
+}
+
+/// 🔑 The `doc_id` rule, parsed — for sources whose docs have no `_id` of their own.
+fn doc_id_for(sink: &ElasticsearchSinkConfig) -> Result<Option<DocId>> {
+    sink.doc_id.as_ref().map(DocId::new).transpose()
+}
+
 impl PageToEntriesCaster {
     /// 🔧 Resolve a caster from source/sink config enums.
     ///
@@ -94,63 +195,180 @@ impl PageToEntriesCaster {
     ///
     /// The (SourceConfig, SinkConfig) pair determines which caster to use:
     /// - File → Elasticsearch = NdJsonToBulk (the flagship pair)
-    /// - File → Meilisearch = NdJsonSplit (split NDJSON lines, no bulk headers)
+    /// - File → Meilisearch / Solr = NdJsonSplit (split NDJSON lines, no bulk headers)
     /// - File → File = Passthrough
     /// - InMemory → InMemory = Passthrough (testing)
     /// - InMemory → Meilisearch = Passthrough (testing)
-    /// - Elasticsearch → File = Passthrough (ES dump to file)
+    /// - Elasticsearch → File = Passthrough (ES dump to file), or PitToJson / PitToBulk with `es_hits`
     /// - Elasticsearch → Meilisearch = PitToJson (extract _source, no bulk headers)
+    /// - Elasticsearch → Solr = PitToJson with `id_field` (`_id` carried into the doc)
+    /// - Elasticsearch → File with `id_export` = PitToIds (IDs only, for reconciliation)
+    /// - NDJSON sources → File/Custom with `manifold = "json_array"`, `"pretty"`, `"sql_insert"` or `"csv"` = NdJsonSplit
+    ///   (docs, not pages), and Elasticsearch → File with `es_hits = "source"` too = PitToJson
     ///
     /// # Panics
     /// 💀 Panics if the `(source, sink)` pair has no caster implementation.
     /// Fail loud at startup, not silent in the hot path.
+    /// Use `try_from_configs` when a panic is too loud (e.g. `kvx validate`).
     pub fn from_configs(source: &SourceConfig, sink: &SinkConfig) -> Self {
-        match (source, sink) {
+        Self::try_from_configs(source, sink).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 🔄 Same resolution as `from_configs`, but unsupported pairs come back as an `Err`
+    /// instead of a panic — so validation can report them like any other config mistake.
+    pub fn try_from_configs(source: &SourceConfig, sink: &SinkConfig) -> Result<Self> {
+        // -- 📦🗄️📊 array items, SQL rows and CSV rows are one doc each, not one page each
+        let the_doc_manifold = sink.manifold().filter(|m| {
+            matches!(m, ManifoldFormat::JsonArray | ManifoldFormat::Pretty | ManifoldFormat::SqlInsert | ManifoldFormat::Csv)
+        });
+        let wants_docs = the_doc_manifold.is_some();
+        Ok(match (source, sink) {
+            // -- 📦 one JSON array holds JSON docs. ID lines and action+source pairs aren't docs
+            (_, SinkConfig::File(f)) if wants_docs && (f.id_export.is_some() || f.es_hits == EsHitsFormat::Bulk) => {
+                anyhow::bail!(
+                    "💀 manifold = {:?} takes JSON documents — `id_export` lines and `es_hits = \"bulk\"` \
+                     pairs aren't documents. Drop one or the other.",
+                    the_doc_manifold.map(|m| format!("{:?}", m)).unwrap_or_default()
+                )
+            }
+            (SourceConfig::Elasticsearch(_), SinkConfig::File(f)) if wants_docs && f.es_hits == EsHitsFormat::Source => {
+                Self::PitToJson(PitToJson::default())
+            }
+            // -- 🗄️ a whole `_search` page isn't a row. (An array of pages is at least valid JSON.)
+            (SourceConfig::Elasticsearch(_), _)
+                if matches!(the_doc_manifold, Some(ManifoldFormat::SqlInsert | ManifoldFormat::Csv)) =>
+            {
+                anyhow::bail!(
+                    "💀 manifold = {:?} from an Elasticsearch source needs a File sink with \
+                     `es_hits = \"source\"`, so each hit becomes a row instead of each page.",
+                    the_doc_manifold.map(|m| format!("{:?}", m)).unwrap_or_default()
+                )
+            }
+            // -- 📦 pages of NDJSON lines would land in the array as one broken item each — split them
+            (
+                SourceConfig::File(_) | SourceConfig::Url(_) | SourceConfig::Generator(_) | SourceConfig::Rest(_) | SourceConfig::Redis(_) | SourceConfig::BigQuery(_) | SourceConfig::Custom(_),
+                SinkConfig::File(_) | SinkConfig::Custom(_),
+            ) if wants_docs => Self::NdJsonSplit(NdJsonSplit::default()),
+            // -- 🪪 ID export: the File sink asked for name tags only. Must come before the
+            // -- ES → File passthrough arm, or the whole doc sneaks through instead.
+            (SourceConfig::Elasticsearch(_), SinkConfig::File(f)) if f.id_export.is_some() => {
+                let the_export = f.id_export.clone().unwrap_or_default();
+                Self::PitToIds(PitToIds {
+                    include_version: the_export.include_version,
+                    include_hash: the_export.include_hash,
+                })
+            }
+            // -- 📡 ES → File, unwrapped: hits shed their envelope on the way to disk
+            (SourceConfig::Elasticsearch(_), SinkConfig::File(f)) if f.es_hits == EsHitsFormat::Source => {
+                Self::PitToJson(PitToJson { line_terminated: true, ..PitToJson::default() })
+            }
+            (SourceConfig::Elasticsearch(_), SinkConfig::File(f)) if f.es_hits == EsHitsFormat::Bulk => {
+                Self::PitToBulk(PitToBulk::default())
+            }
+            (_, SinkConfig::File(f)) if f.es_hits != EsHitsFormat::Raw => {
+                anyhow::bail!(
+                    "💀 `es_hits` shapes Elasticsearch search hits, and source {:?} doesn't produce any. Drop the setting.",
+                    source
+                )
+            }
+            (_, SinkConfig::File(f)) if f.id_export.is_some() => {
+                anyhow::bail!(
+                    "💀 ID export (`id_export`) needs an Elasticsearch source — it reads `_index`/`_id` \
+                     from search hits. Source {:?} has no such thing. Like asking a napkin for its passport.",
+                    source
+                )
+            }
+
             // -- 🏎️📡 File source → Elasticsearch sink:
             // -- The first and flagship pair. Raw NDJSON to ES bulk.
             // -- "In a world where JSON had too many fields... one caster dared to strip them."
-            (SourceConfig::File(_), SinkConfig::Elasticsearch(_)) => {
-                Self::NdJsonToBulk(NdJsonToBulk {})
+            (SourceConfig::File(_) | SourceConfig::Url(_) | SourceConfig::Generator(_) | SourceConfig::Rest(_) | SourceConfig::Redis(_) | SourceConfig::BigQuery(_) | SourceConfig::Custom(_), SinkConfig::Elasticsearch(es)) => {
+                let the_action = bulk_action_for(es)?;
+                let the_doc_id = doc_id_for(es)?;
+                if the_action.needs_id() && the_doc_id.is_none() {
+                    anyhow::bail!(
+                        "💀 op_type '{:?}' patches docs by `_id`, and NDJSON lines don't carry one. \
+                         Set `doc_id`, use an Elasticsearch source, or op_type = \"index\" / \"create\".",
+                        the_action
+                    );
+                }
+                Self::NdJsonToBulk(NdJsonToBulk {
+                    action: the_action,
+                    require_timestamp: es.data_stream,
+                    tombstone: tombstone_for(es, true)?,
+                    index_template: es.index_template()?,
+                    index_map: es.index_map()?,
+                    mapping_type: mapping_type_for(es)?,
+                    doc_id: the_doc_id,
+                    ..NdJsonToBulk::default()
+                })
             }
 
             // -- 🔍🔪 File source → Meilisearch sink: split NDJSON lines into individual entries.
             // -- No bulk headers. Just the raw docs. Meilisearch likes its JSON naked.
-            (SourceConfig::File(_), SinkConfig::Meilisearch(_)) => {
-                Self::NdJsonSplit(NdJsonSplit)
+            (SourceConfig::File(_) | SourceConfig::Url(_) | SourceConfig::Generator(_) | SourceConfig::Rest(_) | SourceConfig::Redis(_) | SourceConfig::BigQuery(_) | SourceConfig::Custom(_), SinkConfig::Meilisearch(_) | SinkConfig::Solr(_)) => {
+                Self::NdJsonSplit(NdJsonSplit::default())
             }
 
             // -- 🚶 Passthrough pairs: same format, no conversion needed.
             // -- File→File, InMemory→InMemory, InMemory→Meilisearch, ES→File — just move the bytes.
-            (SourceConfig::File(_), SinkConfig::File(_))
+            (SourceConfig::File(_) | SourceConfig::Url(_) | SourceConfig::Generator(_) | SourceConfig::Rest(_) | SourceConfig::Redis(_) | SourceConfig::BigQuery(_), SinkConfig::File(_))
             | (SourceConfig::InMemory(_), SinkConfig::InMemory(_))
-            | (SourceConfig::InMemory(_), SinkConfig::Meilisearch(_))
+            // -- 🧩 custom sources speak NDJSON like File; custom sinks take NDJSON like File
+            | (SourceConfig::Custom(_), SinkConfig::File(_) | SinkConfig::Custom(_))
+            | (
+                SourceConfig::File(_) | SourceConfig::Url(_) | SourceConfig::Generator(_) | SourceConfig::Rest(_) | SourceConfig::Redis(_) | SourceConfig::BigQuery(_) | SourceConfig::Elasticsearch(_) | SourceConfig::InMemory(_),
+                SinkConfig::Custom(_),
+            )
+            | (SourceConfig::InMemory(_), SinkConfig::Meilisearch(_) | SinkConfig::Solr(_))
             | (SourceConfig::Elasticsearch(_), SinkConfig::File(_)) => {
                 Self::Passthrough(passthrough::Passthrough)
             }
 
             // -- 📡🎭 ES source → ES sink: PIT response envelope → _bulk NDJSON
             // -- "One does not simply walk into Elasticsearch without a bulk action line." — Boromir, probably
-            (SourceConfig::Elasticsearch(_), SinkConfig::Elasticsearch(_)) => {
-                Self::PitToBulk(PitToBulk)
+            (SourceConfig::Elasticsearch(_), SinkConfig::Elasticsearch(es)) => {
+                if es.doc_id.is_some() {
+                    anyhow::bail!("💀 doc_id names docs that have no `_id` — Elasticsearch hits already have one. Drop it.");
+                }
+                Self::PitToBulk(PitToBulk {
+                    action: bulk_action_for(es)?,
+                    // -- 🎯 a sink `index` (static, template, or data stream) is where docs go;
+                    // -- only with none does each hit keep the `_index` it came from
+                    keep_hit_index: es.index.is_none(),
+                    require_timestamp: es.data_stream,
+                    tombstone: tombstone_for(es, false)?,
+                    index_template: es.index_template()?,
+                    index_map: es.index_map()?,
+                    mapping_type: mapping_type_for(es)?,
+                    ..PitToBulk::default()
+                })
             }
 
             // -- 🔍🎭 ES source → Meilisearch sink: PIT response → raw JSON entries (no bulk headers)
             // -- "Do you ever feel like you're just extracting _source into the void?" — ES hit, in therapy
             (SourceConfig::Elasticsearch(_), SinkConfig::Meilisearch(_)) => {
-                Self::PitToJson(PitToJson)
+                Self::PitToJson(PitToJson::default())
+            }
+            // -- ☀️ ES source → Solr sink: the same naked docs, but Solr's uniqueKey lives in the
+            // -- doc — so `_id` comes along as `id_field`
+            (SourceConfig::Elasticsearch(_), SinkConfig::Solr(solr)) => {
+                Self::PitToJson(PitToJson { id_field: Some(solr.id_field.clone()), ..PitToJson::default() })
             }
 
             // -- 📡 OpenObserve sink: ES-compatible bulk format, same casters apply.
             // -- "In a world where APIs were compatible... one sink reused all the casters." 🎬
-            (SourceConfig::File(_), SinkConfig::OpenObserve(_)) => {
-                Self::NdJsonToBulk(NdJsonToBulk {})
-            }
+            // -- 🕳️ Null sink: a `_bulk` endpoint with the network cut — OpenObserve's casters, default action
+            (
+                SourceConfig::File(_) | SourceConfig::Url(_) | SourceConfig::Generator(_) | SourceConfig::Rest(_) | SourceConfig::Redis(_) | SourceConfig::BigQuery(_) | SourceConfig::Custom(_),
+                SinkConfig::OpenObserve(_) | SinkConfig::Null(_),
+            ) => Self::NdJsonToBulk(NdJsonToBulk::default()),
             // -- 📡🎭 ES source → OpenObserve sink: same PIT-to-bulk dance, different venue
-            (SourceConfig::Elasticsearch(_), SinkConfig::OpenObserve(_)) => {
-                Self::PitToBulk(PitToBulk)
+            (SourceConfig::Elasticsearch(_), SinkConfig::OpenObserve(_) | SinkConfig::Null(_)) => {
+                Self::PitToBulk(PitToBulk::default())
             }
             // -- 🧪 InMemory → OpenObserve: testing path, passthrough all the way
-            (SourceConfig::InMemory(_), SinkConfig::OpenObserve(_)) => {
+            (SourceConfig::InMemory(_), SinkConfig::OpenObserve(_) | SinkConfig::Null(_)) => {
                 Self::Passthrough(passthrough::Passthrough)
             }
 
@@ -159,21 +377,74 @@ impl PageToEntriesCaster {
             // -- In the junk drawer. Nothing."
             #[allow(unreachable_patterns)]
             (src, dst) => {
-                panic!(
+                anyhow::bail!(
                     "💀 No caster implemented for source {:?} → sink {:?}. \
                      This is the resolve() equivalent of 'new phone who dis.' \
                      Add a variant to DocumentCaster, write the impl, add tests.",
                     src, dst
                 )
             }
+        })
+    }
+
+    /// 🔧 The whole resolution for a run: the (source, sink) caster, plus `[[transforms]]`.
+    pub fn try_from_app_config(app_config: &AppConfig) -> Result<Self> {
+        let mut the_transforms =
+            TransformChain::from_configs_with_registry(&app_config.transforms, &app_config.transform_registry)?;
+        if app_config.runtime.skip_bad_docs {
+            the_transforms = the_transforms.with_skip_bad_docs(Rejects::open(app_config.runtime.rejects_file.as_deref())?);
         }
+        Self::try_from_configs(&app_config.source_config, &app_config.sink_config)?
+            .with_transforms(&app_config.source_config, &app_config.sink_config, the_transforms)
     }
-}
 
-/// 🧠 `DocumentCaster` dispatches to the concrete caster inside each variant.
-/// Same pattern as `impl Source for SourceBackend` in `backends.rs`.
-/// The borrow checker approves. The compiler inlines. Life is good. 🐄
+    /// ⛓️ The transform chain this caster runs, if it runs one.
+    pub fn transforms(&self) -> Option<&TransformChain> {
+        match self {
+            Self::NdJsonToBulk(the_caster) => Some(&the_caster.transforms),
+            Self::NdJsonSplit(the_caster) => Some(&the_caster.transforms),
+            Self::NdJsonTransform(the_caster) => Some(&the_caster.transforms),
+            Self::PitToBulk(the_caster) => Some(&the_caster.transforms),
+            Self::PitToJson(the_caster) => Some(&the_caster.transforms),
+            Self::Passthrough(_) | Self::PitToIds(_) => None,
+        }
+    }
 
+    /// 🔧 Hand the transform chain to whichever caster sees individual docs.
+    ///
+    /// `Passthrough` never looks inside a page, so with transforms configured it's swapped
+    /// for a caster that does: `NdJsonTransform` for NDJSON sinks, `NdJsonSplit` for
+    /// JSON-array sinks. ES sources passing raw `_search` pages through have no per-doc
+    /// step to swap in, so that combination is refused instead of silently ignored.
+    pub fn with_transforms(self, source: &SourceConfig, sink: &SinkConfig, transforms: TransformChain) -> Result<Self> {
+        if transforms.is_empty() {
+            return Ok(self);
+        }
+        Ok(match self {
+            Self::NdJsonToBulk(the_caster) => Self::NdJsonToBulk(NdJsonToBulk { transforms, ..the_caster }),
+            Self::NdJsonSplit(_) => Self::NdJsonSplit(NdJsonSplit { transforms }),
+            Self::NdJsonTransform(_) => Self::NdJsonTransform(NdJsonTransform { transforms }),
+            Self::PitToBulk(the_caster) => Self::PitToBulk(PitToBulk { transforms, ..the_caster }),
+            Self::PitToJson(the_caster) => Self::PitToJson(PitToJson { transforms, ..the_caster }),
+            Self::PitToIds(_) => anyhow::bail!(
+                "💀 `id_export` writes only `_index`/`_id` lines — [[transforms]] would have nothing to reshape. Drop one or the other."
+            ),
+            Self::Passthrough(_) => match (source, sink) {
+                (SourceConfig::Elasticsearch(_), _) => anyhow::bail!(
+                    "💀 [[transforms]] can't run on this pair: an Elasticsearch source into a File/Custom sink \
+                     passes raw `_search` pages through whole, with no per-document step to hook into. \
+                     For a File sink, set `es_hits = \"source\"` (or \"bulk\") to unwrap the hits."
+                ),
+                (_, SinkConfig::InMemory(_) | SinkConfig::Meilisearch(_) | SinkConfig::Solr(_)) => Self::NdJsonSplit(NdJsonSplit { transforms }),
+                _ => Self::NdJsonTransform(NdJsonTransform { transforms }),
+            },
+        })
+    }
+}
+
+// 🧠 `DocumentCaster` dispatches to the concrete caster inside each variant.
+// Same pattern as `impl Source for SourceBackend` in `backends.rs`.
+// The borrow checker approves. The compiler inlines. Life is good. 🐄
 
 #[cfg(test)]
 mod tests {
@@ -188,15 +459,42 @@ mod tests {
         // 🔧 Build source/sink configs like the real pipeline does
         let source = SourceConfig::File(FileSourceConfig {
             file_name: "rally_export.json".to_string(),
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
             common_config: CommonSourceConfig::default(),
         });
         let sink = SinkConfig::Elasticsearch(ElasticsearchSinkConfig {
             url: "http://localhost:9200".to_string(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             index: Some("rally".to_string()),
             common_config: CommonSinkConfig::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
         });
 
         // 🎯 Resolve — should give us NdJsonToBulk
@@ -226,11 +524,18 @@ mod tests {
     fn the_one_where_file_to_file_resolves_to_passthrough() -> Result<()> {
         let source = SourceConfig::File(FileSourceConfig {
             file_name: "input.json".to_string(),
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
             common_config: CommonSourceConfig::default(),
         });
         let sink = SinkConfig::File(FileSinkConfig {
             file_name: "output.json".to_string(),
+            id_export: None,
             common_config: CommonSinkConfig::default(),
+            es_hits: Default::default(),
         });
 
         let the_caster = PageToEntriesCaster::from_configs(&source, &sink);
@@ -258,15 +563,42 @@ mod tests {
     fn the_one_where_ndjson_feeds_get_cast_via_config_resolution() -> Result<()> {
         let source = SourceConfig::File(FileSourceConfig {
             file_name: "data.json".to_string(),
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
             common_config: CommonSourceConfig::default(),
         });
         let sink = SinkConfig::Elasticsearch(ElasticsearchSinkConfig {
             url: "http://localhost:9200".to_string(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             index: Some("rally-artifacts".to_string()),
             common_config: CommonSinkConfig::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
         });
 
         let the_caster = PageToEntriesCaster::from_configs(&source, &sink);
@@ -301,6 +633,11 @@ mod tests {
         use crate::backends::open_observe::OpenObserveSinkConfig;
         let source = SourceConfig::File(FileSourceConfig {
             file_name: "rally_export.json".to_string(),
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
             common_config: CommonSourceConfig::default(),
         });
         let sink = SinkConfig::OpenObserve(OpenObserveSinkConfig {
@@ -309,6 +646,7 @@ mod tests {
             stream: "rally".to_string(),
             username: None,
             password: None,
+            password_file: None,
             common_config: CommonSinkConfig::default(),
         });
 
@@ -329,8 +667,21 @@ mod tests {
             url: "http://source-cluster:9200".to_string(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             common_config: CommonSourceConfig::default(),
+            time_slice: None,
+            resume_after: None,
+            index: None,
+            slices: None,
+            query: None,
+            source_includes: Vec::new(),
+            source_excludes: Vec::new(),
+            pagination: Default::default(),
+            compat: Default::default(),
+            keep_alive: "5m".to_string(),
+            connection: Default::default(),
         });
         let sink = SinkConfig::OpenObserve(OpenObserveSinkConfig {
             url: "http://localhost:5080".to_string(),
@@ -338,6 +689,7 @@ mod tests {
             stream: "migrated".to_string(),
             username: None,
             password: None,
+            password_file: None,
             common_config: CommonSinkConfig::default(),
         });
 
@@ -361,6 +713,7 @@ mod tests {
             stream: "test".to_string(),
             username: None,
             password: None,
+            password_file: None,
             common_config: CommonSinkConfig::default(),
         });
         let the_caster = PageToEntriesCaster::from_configs(&source, &sink);
@@ -374,16 +727,51 @@ mod tests {
             url: "http://source-cluster:9200".to_string(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             common_config: CommonSourceConfig::default(),
+            time_slice: None,
+            resume_after: None,
+            index: None,
+            slices: None,
+            query: None,
+            source_includes: Vec::new(),
+            source_excludes: Vec::new(),
+            pagination: Default::default(),
+            compat: Default::default(),
+            keep_alive: "5m".to_string(),
+            connection: Default::default(),
         });
         let sink = SinkConfig::Elasticsearch(ElasticsearchSinkConfig {
             url: "http://dest-cluster:9200".to_string(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             index: Some("dest-index".to_string()),
             common_config: CommonSinkConfig::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
         });
 
         let the_caster = PageToEntriesCaster::from_configs(&source, &sink);
@@ -399,4 +787,229 @@ mod tests {
 
         Ok(())
     }
+
+    /// 🧪 ES→File with `id_export` resolves to PitToIds; File→File with `id_export` is refused.
+    #[test]
+    fn the_one_where_the_file_sink_only_wants_name_tags() {
+        use crate::backends::file::IdExportConfig;
+        let the_es_source = SourceConfig::Elasticsearch(ElasticsearchSourceConfig {
+            url: "http://source-cluster:9200".to_string(),
+            username: None,
+            password: None,
+            password_file: None,
+            api_key: None,
+            api_key_file: None,
+            common_config: CommonSourceConfig::default(),
+            time_slice: None,
+            resume_after: None,
+            index: None,
+            slices: None,
+            query: None,
+            source_includes: Vec::new(),
+            source_excludes: Vec::new(),
+            pagination: Default::default(),
+            compat: Default::default(),
+            keep_alive: "5m".to_string(),
+            connection: Default::default(),
+        });
+        let the_file_source = SourceConfig::File(FileSourceConfig {
+            file_name: "input.json".to_string(),
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
+            common_config: CommonSourceConfig::default(),
+        });
+        let the_id_sink = SinkConfig::File(FileSinkConfig {
+            file_name: "ids.tsv".to_string(),
+            id_export: Some(IdExportConfig { include_version: false, include_hash: true }),
+            common_config: CommonSinkConfig::default(),
+            es_hits: Default::default(),
+        });
+
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_es_source, &the_id_sink)
+            .expect("💀 ES → File id_export should resolve");
+        assert!(
+            matches!(the_caster, PageToEntriesCaster::PitToIds(PitToIds { include_hash: true, .. })),
+            "ES → File with id_export should resolve to PitToIds 🪪"
+        );
+        assert!(
+            PageToEntriesCaster::try_from_configs(&the_file_source, &the_id_sink).is_err(),
+            "💀 a file source has no _id to export"
+        );
+    }
+
+    /// 🧪 op_type reaches the bulk casters — and the pairs that can't honor it say so up front.
+    #[test]
+    fn the_one_where_the_rerun_asks_for_upserts() -> Result<()> {
+        let the_upsert_sink: ElasticsearchSinkConfig =
+            toml::from_str("url = \"http://dest:9200\"\nindex = \"people\"\nop_type = \"upsert\"")?;
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"people\"")?);
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"people.ndjson\"")?);
+
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_es_source, &SinkConfig::Elasticsearch(the_upsert_sink.clone()))?;
+        assert!(matches!(the_caster, PageToEntriesCaster::PitToBulk(PitToBulk { action: BulkAction::Upsert, .. })));
+        assert!(
+            PageToEntriesCaster::try_from_configs(&the_file_source, &SinkConfig::Elasticsearch(the_upsert_sink.clone())).is_err(),
+            "💀 NDJSON lines have no _id to upsert by"
+        );
+        let the_named_upsert: ElasticsearchSinkConfig = toml::from_str(
+            "url = \"http://dest:9200\"\nindex = \"people\"\nop_type = \"upsert\"\ndoc_id = { fields = [\"email\"] }",
+        )?;
+        assert!(
+            PageToEntriesCaster::try_from_configs(&the_file_source, &SinkConfig::Elasticsearch(the_named_upsert.clone())).is_ok(),
+            "🔑 doc_id gives the lines an _id to upsert by"
+        );
+        assert!(
+            PageToEntriesCaster::try_from_configs(&the_es_source, &SinkConfig::Elasticsearch(the_named_upsert)).is_err(),
+            "💀 hits already have an _id"
+        );
+        let the_stream_upsert = ElasticsearchSinkConfig { data_stream: true, ..the_upsert_sink };
+        assert!(
+            PageToEntriesCaster::try_from_configs(&the_es_source, &SinkConfig::Elasticsearch(the_stream_upsert)).is_err(),
+            "💀 data streams only take create"
+        );
+        Ok(())
+    }
+
+    /// 🧪 Transforms land in the per-doc casters; passthroughs get swapped for something that looks inside.
+    #[test]
+    fn the_one_where_the_passthrough_has_to_open_the_bags() -> Result<()> {
+        let the_chain = TransformChain::from_configs(&[toml::from_str("FieldMap = { rename = { a = \"b\" } }")?])?;
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"in.ndjson\"")?);
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"people\"")?);
+        let the_file_sink = SinkConfig::File(toml::from_str("file_name = \"out.ndjson\"")?);
+        let the_resolve = |source: &SourceConfig, sink: &SinkConfig| {
+            PageToEntriesCaster::try_from_configs(source, sink)?.with_transforms(source, sink, the_chain.clone())
+        };
+
+        assert!(matches!(the_resolve(&the_file_source, &the_file_sink)?, PageToEntriesCaster::NdJsonTransform(_)));
+        assert!(matches!(the_resolve(&SourceConfig::InMemory(()), &SinkConfig::InMemory(()))?, PageToEntriesCaster::NdJsonSplit(_)));
+        assert!(the_resolve(&the_es_source, &the_file_sink).is_err(), "💀 raw _search pages have no per-doc step");
+
+        let the_untouched = PageToEntriesCaster::try_from_configs(&the_file_source, &the_file_sink)?
+            .with_transforms(&the_file_source, &the_file_sink, TransformChain::default())?;
+        assert!(matches!(the_untouched, PageToEntriesCaster::Passthrough(_)), "🐄 no transforms, no detour");
+        Ok(())
+    }
+
+    /// 🧪 `es_hits` unwraps ES search pages on their way to a file — `_source` lines or `_bulk` pairs.
+    #[test]
+    fn the_one_where_the_hits_leave_their_envelopes_at_the_door() -> Result<()> {
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"people\"")?);
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"in.ndjson\"")?);
+        let the_source_sink = SinkConfig::File(toml::from_str("file_name = \"out.ndjson\"\nes_hits = \"source\"")?);
+        let the_bulk_sink = SinkConfig::File(toml::from_str("file_name = \"out.bulk\"\nes_hits = \"bulk\"")?);
+        let the_page = || {
+            Page(r#"{"took":1,"hits":{"total":{"value":2},"hits":[
+                {"_index":"people","_id":"1","_routing":"eu","_source":{"a":1}},
+                {"_index":"people","_id":"2","_source":{"a":2}}]}}"#.to_string())
+        };
+
+        let the_lines = PageToEntriesCaster::try_from_configs(&the_es_source, &the_source_sink)?.cast(the_page())?;
+        assert_eq!(the_lines.iter().map(|e| e.0.as_str()).collect::<String>(), "{\"a\":1}\n{\"a\":2}\n");
+
+        let the_pairs = PageToEntriesCaster::try_from_configs(&the_es_source, &the_bulk_sink)?.cast(the_page())?;
+        assert_eq!(
+            the_pairs.iter().map(|e| e.0.as_str()).collect::<String>(),
+            "{\"index\":{\"_index\":\"people\",\"_id\":\"1\",\"_routing\":\"eu\"}}\n{\"a\":1}\n{\"index\":{\"_index\":\"people\",\"_id\":\"2\"}}\n{\"a\":2}\n"
+        );
+
+        let the_chain = TransformChain::from_configs(&[toml::from_str("FieldMap = { rename = { a = \"b\" } }")?])?;
+        let the_reshaped = PageToEntriesCaster::try_from_configs(&the_es_source, &the_source_sink)?
+            .with_transforms(&the_es_source, &the_source_sink, the_chain)?
+            .cast(the_page())?;
+        assert_eq!(the_reshaped[0].0, "{\"b\":1}\n", "🔧 unwrapped hits can be transformed");
+
+        assert!(PageToEntriesCaster::try_from_configs(&the_file_source, &the_source_sink).is_err(), "💀 no hits to unwrap");
+        Ok(())
+    }
+
+    /// 🧪 `manifold = "json_array"` on a File sink needs one entry per doc — and refuses what isn't a doc.
+    #[test]
+    fn the_one_where_the_array_wants_docs_not_pages() -> Result<()> {
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"people\"")?);
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"in.ndjson\"")?);
+        let the_array_sink = SinkConfig::File(toml::from_str("file_name = \"out.json\"\nmanifold = \"json_array\"")?);
+        let the_unwrapped_sink =
+            SinkConfig::File(toml::from_str("file_name = \"out.json\"\nmanifold = \"json_array\"\nes_hits = \"source\"")?);
+        let the_pairs_sink = SinkConfig::File(toml::from_str("file_name = \"out.json\"\nmanifold = \"json_array\"\nes_hits = \"bulk\"")?);
+
+        let the_docs = PageToEntriesCaster::try_from_configs(&the_file_source, &the_array_sink)?.cast(Page("{\"a\":1}\n{\"a\":2}\n".to_string()))?;
+        assert_eq!(the_docs.iter().map(|e| e.0.as_str()).collect::<Vec<_>>(), ["{\"a\":1}", "{\"a\":2}"]);
+
+        let the_hits = PageToEntriesCaster::try_from_configs(&the_es_source, &the_unwrapped_sink)?
+            .cast(Page(r#"{"hits":{"hits":[{"_id":"1","_source":{"a":1}}]}}"#.to_string()))?;
+        assert_eq!(the_hits[0].0, "{\"a\":1}", "📦 no trailing newline inside an array");
+
+        assert!(PageToEntriesCaster::try_from_configs(&the_es_source, &the_pairs_sink).is_err(), "💀 bulk pairs aren't docs");
+        Ok(())
+    }
+
+    /// 🧪 `manifold = "sql_insert"` wants rows: split NDJSON pages, and refuse raw `_search` pages.
+    #[test]
+    fn the_one_where_a_row_is_a_doc_not_a_page() -> Result<()> {
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"people\"")?);
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"in.ndjson\"")?);
+        let the_sql_sink = SinkConfig::File(toml::from_str("file_name = \"out.sql\"\nmanifold = \"sql_insert\"")?);
+
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_file_source, &the_sql_sink)?;
+        assert!(matches!(the_caster, PageToEntriesCaster::NdJsonSplit(_)));
+        assert!(PageToEntriesCaster::try_from_configs(&the_es_source, &the_sql_sink).is_err(), "💀 a page isn't a row");
+        Ok(())
+    }
+
+    /// 🧪 `index_map` rides along on per-doc sinks, and is refused where every doc has one index.
+    #[test]
+    fn the_one_where_the_cluster_gets_a_new_floor_plan() -> Result<()> {
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"old-*\"")?);
+        let the_keeper: ElasticsearchSinkConfig =
+            toml::from_str("url = \"http://dest:9200\"\nindex_map = { \"old-products\" = \"products-v2\" }")?;
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_es_source, &SinkConfig::Elasticsearch(the_keeper.clone()))?;
+        assert!(
+            matches!(the_caster, PageToEntriesCaster::PitToBulk(PitToBulk { ref index_map, .. }) if index_map.rename("old-products") == "products-v2")
+        );
+        let the_static = ElasticsearchSinkConfig { index: Some("everything".to_string()), ..the_keeper };
+        assert!(PageToEntriesCaster::try_from_configs(&the_es_source, &SinkConfig::Elasticsearch(the_static)).is_err());
+        Ok(())
+    }
+
+    /// 🧪 compat = "es6" puts a `_type` on the bulk casters — and refuses what 6.x can't do.
+    #[test]
+    fn the_one_where_the_sink_cluster_is_from_2019() -> Result<()> {
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"logs.ndjson\"")?);
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"logs\"")?);
+        let the_archive: ElasticsearchSinkConfig =
+            toml::from_str("url = \"http://dest:9200\"\nindex = \"logs\"\ncompat = \"es6\"")?;
+
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_file_source, &SinkConfig::Elasticsearch(the_archive.clone()))?;
+        assert!(matches!(the_caster, PageToEntriesCaster::NdJsonToBulk(NdJsonToBulk { mapping_type: Some(ref t), .. }) if t == "_doc"));
+        let the_typed = ElasticsearchSinkConfig { mapping_type: Some("event".to_string()), ..the_archive.clone() };
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_es_source, &SinkConfig::Elasticsearch(the_typed))?;
+        assert!(matches!(the_caster, PageToEntriesCaster::PitToBulk(PitToBulk { mapping_type: Some(ref t), .. }) if t == "event"));
+
+        let the_stream = ElasticsearchSinkConfig { data_stream: true, ..the_archive.clone() };
+        assert!(PageToEntriesCaster::try_from_configs(&the_file_source, &SinkConfig::Elasticsearch(the_stream)).is_err());
+        let the_stray_type = ElasticsearchSinkConfig { compat: Compat::Modern, mapping_type: Some("doc".to_string()), ..the_archive };
+        assert!(
+            PageToEntriesCaster::try_from_configs(&the_file_source, &SinkConfig::Elasticsearch(the_stray_type)).is_err(),
+            "💀 a type without es6 would be silently dropped"
+        );
+        Ok(())
+    }
+
+    /// 🧪 Solr takes naked docs like Meilisearch — and from Elasticsearch, `_id` comes along as `id_field`.
+    #[test]
+    fn the_one_where_the_migration_goes_the_other_way() -> Result<()> {
+        let the_file_source = SourceConfig::File(toml::from_str("file_name = \"products.ndjson\"")?);
+        let the_es_source = SourceConfig::Elasticsearch(toml::from_str("url = \"http://src:9200\"\nindex = \"products\"")?);
+        let the_solr = SinkConfig::Solr(toml::from_str("url = \"http://solr:8983/solr\"\ncollection = \"products\"")?);
+
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_file_source, &the_solr)?;
+        assert!(matches!(the_caster, PageToEntriesCaster::NdJsonSplit(_)));
+        let the_caster = PageToEntriesCaster::try_from_configs(&the_es_source, &the_solr)?;
+        assert!(matches!(the_caster, PageToEntriesCaster::PitToJson(PitToJson { id_field: Some(ref f), .. }) if f == "id"));
+        Ok(())
+    }
 }
@@ -23,29 +23,37 @@
 //!
 //! ⚠️ The singularity will split atoms. We split newlines. Close enough. 🦆
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crate::Entry;
 use crate::Page;
-use crate::casts::Caster;
+use crate::casts::{Caster, lines_with_position};
+use crate::transforms::TransformChain;
 
 /// 🔪 Splits NDJSON pages into individual JSON entries — no bulk headers, no drama.
 ///
-/// Zero-sized struct. Cloning costs nothing. The compiler inlines everything.
+/// Carries only the transform chain (an `Arc`). Cloning costs nothing.
 /// It's the Marie Kondo of casters — does it have a newline? Split it.
 /// Does it spark joy? Irrelevant. We split on newlines, not feelings. 🧹
-#[derive(Debug, Clone, Copy)]
-pub struct NdJsonSplit;
+#[derive(Debug, Clone, Default)]
+pub struct NdJsonSplit {
+    /// 🔧 `[[transforms]]`, applied to each line before it becomes an entry
+    pub transforms: TransformChain,
+}
 
 impl Caster for NdJsonSplit {
     #[inline]
     fn cast(&self, page: Page) -> Result<Vec<Entry>> {
         // 🔪 Split by newlines, keep non-empty lines, wrap each as an Entry
         // no cap this function slaps fr fr — one line per doc, no overhead, no bulk headers 🦆
-        let the_entries: Vec<Entry> = page
-            .split('\n')
-            .filter(|line| !line.is_empty())
-            .map(|line| Entry(line.to_string()))
-            .collect();
+        let mut the_entries = Vec::new();
+        let (the_positions, the_lines): (Vec<_>, Vec<_>) =
+            lines_with_position(&page).filter(|(_, line)| !line.is_empty()).unzip();
+        for (the_position, the_outcome) in the_positions.into_iter().zip(self.transforms.apply_all(&the_lines)) {
+            // -- 🔧 a transform may drop the doc entirely — then it never becomes an entry
+            if let Some(the_doc) = the_outcome.context(the_position)? {
+                the_entries.push(Entry(the_doc.into_owned()));
+            }
+        }
         Ok(the_entries)
     }
 }
@@ -57,7 +65,7 @@ mod tests {
     /// 🧪 One doc in, one entry out. The simplest possible split.
     #[test]
     fn the_one_where_a_single_doc_passes_through_unmolested() -> Result<()> {
-        let the_caster = NdJsonSplit;
+        let the_caster = NdJsonSplit::default();
         let the_lone_doc = r#"{"id":1,"name":"The One"}"#;
 
         let the_entries = the_caster.cast(Page(the_lone_doc.to_string()))?;
@@ -70,7 +78,7 @@ mod tests {
     /// 🧪 Multiple docs — each line becomes its own Entry, order preserved.
     #[test]
     fn the_one_where_three_docs_split_like_an_amicable_divorce() -> Result<()> {
-        let the_caster = NdJsonSplit;
+        let the_caster = NdJsonSplit::default();
         let doc_a = r#"{"id":1,"name":"Alpha"}"#;
         let doc_b = r#"{"id":2,"name":"Bravo"}"#;
         let doc_c = r#"{"id":3,"name":"Charlie"}"#;
@@ -88,7 +96,7 @@ mod tests {
     /// 🧪 Empty input — the void returns void. No phantom entries.
     #[test]
     fn the_one_where_emptiness_begets_emptiness_like_my_fridge_on_sunday() -> Result<()> {
-        let the_caster = NdJsonSplit;
+        let the_caster = NdJsonSplit::default();
         let the_entries = the_caster.cast(Page("".to_string()))?;
         assert!(the_entries.is_empty(), "💀 Empty input should produce empty output");
         Ok(())
@@ -97,7 +105,7 @@ mod tests {
     /// 🧪 Trailing newline — no ghost entry at the end.
     #[test]
     fn the_one_where_trailing_newlines_dont_spawn_ghost_entries() -> Result<()> {
-        let the_caster = NdJsonSplit;
+        let the_caster = NdJsonSplit::default();
         let the_feed = r#"{"id":1}
 "#;
         let the_entries = the_caster.cast(Page(the_feed.to_string()))?;
@@ -108,7 +116,7 @@ mod tests {
     /// 🧪 Blank lines scattered through the feed — filtered out like bad Tinder profiles.
     #[test]
     fn the_one_where_blank_lines_are_filtered_like_spam_emails() -> Result<()> {
-        let the_caster = NdJsonSplit;
+        let the_caster = NdJsonSplit::default();
         let the_chaotic_feed = "\n\n{\"id\":1}\n\n\n{\"id\":2}\n\n";
 
         let the_entries = the_caster.cast(Page(the_chaotic_feed.to_string()))?;
@@ -121,7 +129,7 @@ mod tests {
     /// 🧪 Each entry is valid JSON — no corruption from the split.
     #[test]
     fn the_one_where_every_entry_is_valid_json_because_we_have_standards() -> Result<()> {
-        let the_caster = NdJsonSplit;
+        let the_caster = NdJsonSplit::default();
         let the_feed = r#"{"id":1,"nested":{"deep":true}}
 {"id":2,"tags":["rust","meili"]}
 {"id":3,"score":null}"#;
@@ -5,16 +5,38 @@
 // ai
 // 🧠 The lines of NDJSON are raw json docs — they have no bulk action metadata.
 // 📡 This caster adds the ES bulk index action line before each doc.
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crate::Entry;
 use crate::Page;
-use crate::casts::Caster;
+use crate::casts::{Caster, lines_with_position};
+use crate::casts::bulk_action::{BulkAction, Tombstone, TombstoneRule, delete_line, require_timestamp};
+use crate::casts::doc_id::DocId;
+use crate::casts::index_template::{HitMeta, IndexMap, IndexTemplate};
+use crate::transforms::TransformChain;
+#[cfg(test)]
 const THE_BULK_ACTION_LINE: &str = "{\"index\":{}}";
 
 /// 📡 Casts raw NDJSON docs into ES bulk format (action line + source doc).
 /// Like a bouncer at a club — "you can't come in without your action line, buddy." 🦆
-#[derive(Debug, Clone, Copy)]
-pub struct NdJsonToBulk {}
+#[derive(Debug, Clone, Default)]
+pub struct NdJsonToBulk {
+    /// 🏷️ `index` unless the sink says otherwise (`create` for data streams)
+    pub action: BulkAction,
+    /// ⏰ Refuse docs without `@timestamp` — set for data streams
+    pub require_timestamp: bool,
+    /// 🪦 Docs matching this rule become deletes (by the doc's `id_field`)
+    pub tombstone: Option<TombstoneRule>,
+    /// 🗂️ Per-doc `_index` from the sink's `index = "{...}"` template
+    pub index_template: Option<IndexTemplate>,
+    /// 🔀 `index_map` renames for what the template rendered
+    pub index_map: IndexMap,
+    /// 🦖 `_type` on every action line — `compat = "es6"` sinks only
+    pub mapping_type: Option<String>,
+    /// 🔑 `_id` from a hash of the doc — `doc_id` on the sink
+    pub doc_id: Option<DocId>,
+    /// 🔧 `[[transforms]]`, applied before anything above looks at the doc
+    pub transforms: TransformChain,
+}
 
 impl Caster for NdJsonToBulk {
     #[inline]
@@ -24,21 +46,70 @@ impl Caster for NdJsonToBulk {
         // -- "He who casts without an action line, gets a 400 from Elasticsearch." 💀
         // TODO: actually implement the bulk action line generation
         // -- for now, pass through like a speed bump that forgot to bump 🦆
+        let the_type = self.mapping_type.as_deref();
+        let the_action_line = match the_type {
+            Some(the_type) => format!("{{\"{}\":{{\"_type\":\"{}\"}}}}", self.action.as_str(), the_type),
+            None => format!("{{\"{}\":{{}}}}", self.action.as_str()),
+        };
         let mut result = Vec::new();
-        for line in page.split('\n') {
-            if !line.is_empty() {
-                let entry = Entry(format!("{}\n{}\n", THE_BULK_ACTION_LINE, line));
-                // Note that caster only returns a single valid entry
-                result.push(entry);
+        let (the_positions, the_lines): (Vec<_>, Vec<_>) =
+            lines_with_position(&page).filter(|(_, line)| !line.is_empty()).unzip();
+        for (the_position, the_outcome) in the_positions.into_iter().zip(self.transforms.apply_all(&the_lines)) {
+            // -- 🔧 transformed (or borrowed as-is) — and maybe dropped, in which case there's nothing to cast
+            if let Some(the_doc) = the_outcome.context(the_position)? {
+                self.cast_line(&the_doc, &the_action_line, &mut result).context(the_position)?;
             }
         }
         Ok(result)
     }
 }
 
+impl NdJsonToBulk {
+    /// 📡 One (already transformed) NDJSON line → its bulk entry.
+    fn cast_line(&self, line: &str, the_action_line: &str, result: &mut Vec<Entry>) -> Result<()> {
+        let the_type = self.mapping_type.as_deref();
+        let the_routed_index = match self.index_template {
+            Some(ref the_template) => Some(the_template.render(line, HitMeta::default())?),
+            None => None,
+        };
+        let the_index = the_routed_index.as_deref().map(|the_index| self.index_map.rename(the_index));
+        if let Some(ref the_rule) = self.tombstone
+            && let Tombstone::Dead(the_id) = the_rule.inspect(line)?
+        {
+            // -- ⚰️ resolution guarantees `id_field` for NDJSON, so the id is always there
+            let the_id = the_id.context("💀 A tombstone rule for NDJSON needs `id_field`")?;
+            result.push(Entry(delete_line(the_index, the_type, &the_id, None)));
+            return Ok(());
+        }
+        if self.require_timestamp {
+            require_timestamp(line)?;
+        }
+        let the_id = match self.doc_id {
+            Some(ref the_rule) => Some(the_rule.id_for(line)?),
+            None => None,
+        };
+        let mut the_entry = if the_index.is_none() && the_id.is_none() {
+            format!("{}\n", the_action_line)
+        } else {
+            // -- 🗂️ render() refused quotes and backslashes, and ids are hex — no escaping needed
+            let the_meta: Vec<String> = [("_index", the_index), ("_type", the_type), ("_id", the_id.as_deref())]
+                .into_iter()
+                .filter_map(|(the_key, the_value)| the_value.map(|v| format!("\"{}\":\"{}\"", the_key, v)))
+                .collect();
+            format!("{{\"{}\":{{{}}}}}\n", self.action.as_str(), the_meta.join(","))
+        };
+        self.action.push_source(&mut the_entry, line);
+        the_entry.push('\n');
+        // Note that caster only returns a single valid entry
+        result.push(Entry(the_entry));
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::casts::DocPosition;
 
     // -- 🧪 The test suite that finally validates the bulk body format.
     // -- The singularity will happen before we get 100% coverage, but we try anyway. 🦆
@@ -52,7 +123,7 @@ mod tests {
     #[test]
     fn the_one_where_a_single_doc_becomes_a_valid_bulk_pair() -> Result<()> {
         // 🔧 Assemble — a lonely JSON doc, seeking its action line soulmate
-        let caster = NdJsonToBulk {};
+        let caster = NdJsonToBulk::default();
         let the_lone_doc = r#"{"ObjectID":42,"Name":"The answer to everything"}"#;
 
         // 🚀 Act — cast it into the bulk dimension
@@ -76,7 +147,7 @@ mod tests {
     /// 🧪 Multiple docs — each gets its own action line escort. Like a VIP list.
     #[test]
     fn the_one_where_multiple_docs_each_get_their_own_action_line() -> Result<()> {
-        let caster = NdJsonToBulk {};
+        let caster = NdJsonToBulk::default();
         // 📄 Three docs walk into a bulk endpoint...
         let doc_a = r#"{"id":1,"name":"Alpha"}"#;
         let doc_b = r#"{"id":2,"name":"Bravo"}"#;
@@ -108,7 +179,7 @@ mod tests {
     /// 🧪 Empty input — the void returns void. Zen mode.
     #[test]
     fn the_one_where_emptiness_begets_emptiness() -> Result<()> {
-        let caster = NdJsonToBulk {};
+        let caster = NdJsonToBulk::default();
         let the_void = "";
 
         let entries = caster.cast(Page(the_void.to_string()))?;
@@ -125,7 +196,7 @@ mod tests {
     /// 🧪 Trailing newline — the sneaky empty string at the end shouldn't spawn a ghost action line.
     #[test]
     fn the_one_where_trailing_newlines_dont_spawn_ghost_actions() -> Result<()> {
-        let caster = NdJsonToBulk {};
+        let caster = NdJsonToBulk::default();
         let doc = r#"{"id":1,"confession":"I added a trailing newline on purpose"}"#;
         // 📄 Note the trailing \n — split will produce an empty last element
         let the_feed_with_trailing_newline = format!("{doc}\n");
@@ -146,7 +217,7 @@ mod tests {
     /// 🧪 Blank lines scattered through the feed — the caster ignores them like I ignore my IDE warnings.
     #[test]
     fn the_one_where_blank_lines_are_ghosted_harder_than_my_last_tinder_match() -> Result<()> {
-        let caster = NdJsonToBulk {};
+        let caster = NdJsonToBulk::default();
         let doc_a = r#"{"id":1}"#;
         let doc_b = r#"{"id":2}"#;
         // 📄 Feed with empty lines everywhere — chaos mode
@@ -168,9 +239,9 @@ mod tests {
     /// 🧪 The ultimate validation — output is a valid ES _bulk body where every doc line is parseable JSON.
     #[test]
     fn the_one_where_the_output_is_actually_valid_bulk_api_format() -> Result<()> {
-        let caster = NdJsonToBulk {};
+        let caster = NdJsonToBulk::default();
         // 📄 Real-ish documents, like the ones that haunt my dreams at 3am
-        let docs = vec![
+        let docs = [
             r#"{"ObjectID":99999,"FormattedID":"US001","Name":"The hero's journey"}"#,
             r#"{"ObjectID":88888,"FormattedID":"DE001","Name":"The bug that got away"}"#,
             r#"{"ObjectID":77777,"FormattedID":"TA001","Name":"The task that never ends"}"#,
@@ -210,4 +281,81 @@ mod tests {
 
         Ok(())
     }
+
+    /// 🧪 Data stream mode: `create` verbs, and a doc without a clock is turned away at the door.
+    #[test]
+    fn the_one_where_the_stream_only_takes_create() -> Result<()> {
+        let caster = NdJsonToBulk { action: BulkAction::Create, require_timestamp: true, ..NdJsonToBulk::default() };
+        let the_doc = r#"{"@timestamp":"2024-01-01T00:00:00Z","msg":"hi"}"#;
+        let the_bulk_body = entries_to_bulk_body(&caster.cast(Page(the_doc.to_string()))?);
+        assert_eq!(the_bulk_body, format!("{{\"create\":{{}}}}\n{}\n", the_doc));
+
+        let the_err = caster.cast(Page(r#"{"msg":"what time is it"}"#.to_string())).unwrap_err();
+        assert!(format!("{:#}", the_err).contains("@timestamp"));
+        assert_eq!(the_err.downcast_ref::<DocPosition>(), Some(&DocPosition { index: 0, page_offset: 0 }), "📍 the doc is named");
+        Ok(())
+    }
+
+    /// 🧪 A sync feed: the living get indexed, the tombstoned get a one-line delete.
+    #[test]
+    fn the_one_where_the_feed_mourns_its_dead() -> Result<()> {
+        let caster = NdJsonToBulk {
+            tombstone: Some(toml::from_str("field = \"deleted\"\nid_field = \"sku\"")?),
+            ..NdJsonToBulk::default()
+        };
+        let the_feed = "{\"sku\":\"A-1\",\"name\":\"kept\"}\n{\"sku\":\"A-2\",\"deleted\":true}\n";
+        let the_bulk_body = entries_to_bulk_body(&caster.cast(Page(the_feed.to_string()))?);
+        assert_eq!(
+            the_bulk_body,
+            "{\"index\":{}}\n{\"sku\":\"A-1\",\"name\":\"kept\"}\n{\"delete\":{\"_id\":\"A-2\"}}\n"
+        );
+        Ok(())
+    }
+
+    /// 🧪 An index template: every doc names its own room, and a doc that can't is turned away.
+    #[test]
+    fn the_one_where_each_project_moves_into_its_own_index() -> Result<()> {
+        let caster = NdJsonToBulk {
+            index_template: Some(IndexTemplate::parse("{_type}-{Project.Name}")?),
+            ..NdJsonToBulk::default()
+        };
+        let the_feed = "{\"_type\":\"Defect\",\"Project\":{\"Name\":\"Apollo\"}}\n{\"_type\":\"Story\",\"Project\":{\"Name\":\"Gemini\"}}\n";
+        let the_lines: Vec<String> = entries_to_bulk_body(&caster.cast(Page(the_feed.to_string()))?).lines().map(str::to_string).collect();
+        assert_eq!(the_lines[0], "{\"index\":{\"_index\":\"defect-apollo\"}}");
+        assert_eq!(the_lines[2], "{\"index\":{\"_index\":\"story-gemini\"}}");
+
+        assert!(caster.cast(Page("{\"_type\":\"Defect\"}".to_string())).is_err(), "💀 no project, no index");
+        Ok(())
+    }
+
+    /// 🧪 An es6 sink: every action line gets the mapping type, templated or not.
+    #[test]
+    fn the_one_where_every_line_declares_its_type() -> Result<()> {
+        let caster = NdJsonToBulk { mapping_type: Some("doc".to_string()), ..NdJsonToBulk::default() };
+        let the_bulk_body = entries_to_bulk_body(&caster.cast(Page("{\"a\":1}\n".to_string()))?);
+        assert_eq!(the_bulk_body, "{\"index\":{\"_type\":\"doc\"}}\n{\"a\":1}\n");
+
+        let the_routed = NdJsonToBulk { index_template: Some(IndexTemplate::parse("logs-{env}")?), ..caster };
+        let the_bulk_body = entries_to_bulk_body(&the_routed.cast(Page("{\"env\":\"prod\"}\n".to_string()))?);
+        assert!(the_bulk_body.starts_with("{\"index\":{\"_index\":\"logs-prod\",\"_type\":\"doc\"}}\n"));
+        Ok(())
+    }
+
+    /// 🧪 A doc_id rule: each action line names its doc, and the same doc gets the same name.
+    #[test]
+    fn the_one_where_the_rerun_overwrites_instead_of_doubling() -> Result<()> {
+        let the_rule = DocId::new(&toml::from_str("fields = [\"sku\"]")?)?;
+        let caster = NdJsonToBulk {
+            doc_id: Some(the_rule.clone()),
+            index_template: Some(IndexTemplate::parse("stock-{region}")?),
+            ..NdJsonToBulk::default()
+        };
+        let the_feed = "{\"sku\":\"A-1\",\"region\":\"eu\"}\n{\"sku\":\"A-1\",\"region\":\"eu\",\"stock\":0}\n";
+        let the_lines: Vec<String> =
+            entries_to_bulk_body(&caster.cast(Page(the_feed.to_string()))?).lines().map(str::to_string).collect();
+        let the_id = the_rule.id_for("{\"sku\":\"A-1\"}")?;
+        assert_eq!(the_lines[0], format!("{{\"index\":{{\"_index\":\"stock-eu\",\"_id\":\"{}\"}}}}", the_id));
+        assert_eq!(the_lines[0], the_lines[2], "🔑 same sku, same _id — the second write wins");
+        Ok(())
+    }
 }
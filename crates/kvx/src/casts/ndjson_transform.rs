@@ -0,0 +1,62 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 NdJsonTransform — NDJSON in, NDJSON out, every line through the transform chain.
+//!
+//! 🎬 *[File→File was a `Passthrough`. TSA PreCheck. Nobody even slowed down.]*
+//! *[then someone added a `[[transforms]]` table. now every bag gets opened.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Stands in for `Passthrough` when transforms are configured and both ends speak NDJSON
+//!   (File/Custom/InMemory source → File/Custom sink) — see `PageToEntriesCaster::with_transforms`
+//! - One entry per surviving line, each ending in `\n`, so `NdjsonManifold` can just concatenate
+//! - Dropped docs leave no trace — no blank line, no placeholder 🦆
+
+use anyhow::{Context, Result};
+
+use crate::Entry;
+use crate::Page;
+use crate::casts::{Caster, lines_with_position};
+use crate::transforms::TransformChain;
+
+/// 🔧 Transforms each NDJSON line and keeps it NDJSON.
+#[derive(Debug, Clone, Default)]
+pub struct NdJsonTransform {
+    /// 🔧 `[[transforms]]`, applied to each line
+    pub transforms: TransformChain,
+}
+
+impl Caster for NdJsonTransform {
+    #[inline]
+    fn cast(&self, page: Page) -> Result<Vec<Entry>> {
+        let mut the_entries = Vec::new();
+        let (the_positions, the_lines): (Vec<_>, Vec<_>) =
+            lines_with_position(&page).filter(|(_, line)| !line.is_empty()).unzip();
+        for (the_position, the_outcome) in the_positions.into_iter().zip(self.transforms.apply_all(&the_lines)) {
+            if let Some(the_doc) = the_outcome.context(the_position)? {
+                let mut the_entry = String::with_capacity(the_doc.len() + 1);
+                the_entry.push_str(&the_doc);
+                the_entry.push('\n');
+                the_entries.push(Entry(the_entry));
+            }
+        }
+        Ok(the_entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::{FieldMapConfig, TransformConfig};
+
+    #[test]
+    fn the_one_where_the_file_copy_learns_new_names() -> Result<()> {
+        let the_rename = FieldMapConfig { rename: [("ObjectID".to_string(), "id".to_string())].into() };
+        let the_caster = NdJsonTransform { transforms: TransformChain::from_configs(&[TransformConfig::FieldMap(the_rename)])? };
+        let the_entries = the_caster.cast(Page("{\"ObjectID\":1}\n\n{\"ObjectID\":2,\"x\":true}\n".to_string()))?;
+        let the_body: String = the_entries.iter().map(|e| e.0.as_str()).collect();
+        assert_eq!(the_body, "{\"id\":1}\n{\"id\":2,\"x\":true}\n");
+        Ok(())
+    }
+}
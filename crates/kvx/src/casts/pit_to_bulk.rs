@@ -34,6 +34,9 @@ use serde::Deserialize;
 use serde_json::value::RawValue;
 
 use crate::casts::Caster;
+use crate::casts::bulk_action::{BulkAction, Tombstone, TombstoneRule, delete_line, require_timestamp};
+use crate::casts::index_template::{HitMeta, IndexMap, IndexTemplate};
+use crate::transforms::TransformChain;
 use crate::Entry;
 use crate::Page;
 
@@ -73,6 +76,9 @@ struct SearchHit<'a> {
     _index: &'a str,
     // 🔑 Document ID — optional because auto-generated IDs exist (and haunt us)
     _id: Option<&'a str>,
+    // 🏷️ Mapping type — only pre-7.x clusters still send it, for `{_type}` index templates
+    #[serde(default)]
+    _type: Option<&'a str>,
     // 🛤️ Routing value — optional, only present when custom routing is used
     _routing: Option<&'a str>,
     // 📄 The actual document — borrowed as raw JSON, zero-copy from input
@@ -83,13 +89,46 @@ struct SearchHit<'a> {
 /// 📡 PitToBulk — extracts hits from ES `_search` PIT responses and formats
 /// them as `_bulk` NDJSON action+source pairs.
 ///
-/// Zero-sized struct. Cloning costs nothing. The compiler inlines everything.
+/// A couple of `Copy` knobs. Cloning costs nothing. The compiler inlines everything.
 /// Like a ghost that transforms JSON — you never see it, but the output is different. 👻
 ///
 /// 🧠 Knowledge graph: ES source pumps raw `_search` response bodies → ch1 →
 /// Joiner calls `caster.cast(feed)` → PitToBulk extracts hits → _bulk NDJSON out.
-#[derive(Debug, Clone, Copy)]
-pub struct PitToBulk;
+#[derive(Debug, Clone)]
+pub struct PitToBulk {
+    /// 🏷️ `index` unless the sink says otherwise (`create` for data streams)
+    pub action: BulkAction,
+    /// 📡 Write each hit's own `_index` into the action line. Off for data streams: the hit's
+    /// `_index` is a source backing index (`.ds-logs-...`), and the stream in the URL must win.
+    pub keep_hit_index: bool,
+    /// ⏰ Refuse docs without `@timestamp` — set for data streams
+    pub require_timestamp: bool,
+    /// 🪦 Hits matching this rule become deletes (by the hit's `_id`, or the rule's `id_field`)
+    pub tombstone: Option<TombstoneRule>,
+    /// 🗂️ Per-doc `_index` from the sink's `index = "{...}"` template — beats the hit's own `_index`
+    pub index_template: Option<IndexTemplate>,
+    /// 🔀 `index_map` renames, applied to whichever `_index` the two above settled on
+    pub index_map: IndexMap,
+    /// 🦖 `compat = "es6"` sinks: write a `_type` — the hit's own if it has one, else this
+    pub mapping_type: Option<String>,
+    /// 🔧 `[[transforms]]`, applied to each `_source` before anything above looks at it
+    pub transforms: TransformChain,
+}
+
+impl Default for PitToBulk {
+    fn default() -> Self {
+        Self {
+            action: BulkAction::Index,
+            keep_hit_index: true,
+            require_timestamp: false,
+            tombstone: None,
+            index_template: None,
+            index_map: IndexMap::default(),
+            mapping_type: None,
+            transforms: TransformChain::default(),
+        }
+    }
+}
 
 impl Caster for PitToBulk {
     #[inline]
@@ -115,27 +154,71 @@ impl Caster for PitToBulk {
         let mut the_final_result = Vec::with_capacity(the_estimated_size);
 
         // 🏗️ Phase 3: Build bulk NDJSON — action line + source doc per hit
-        for hit in the_hits {
+        let the_sources: Vec<&str> = the_hits.iter().map(|hit| hit._source.get()).collect();
+        for (hit, the_outcome) in the_hits.iter().zip(self.transforms.apply_all(&the_sources)) {
+            // -- 🔧 transformed (or, with no transforms, borrowed as-is) — and maybe dropped
+            let Some(the_source) = the_outcome? else {
+                continue;
+            };
+            let the_routed_index = match self.index_template {
+                Some(ref the_template) => {
+                    let the_meta = HitMeta { index: Some(hit._index), id: hit._id, doc_type: hit._type, routing: hit._routing };
+                    Some(the_template.render(&the_source, the_meta)?)
+                }
+                None => None,
+            };
+            let the_index = the_routed_index
+                .as_deref()
+                .or(self.keep_hit_index.then_some(hit._index))
+                .map(|the_index| self.index_map.rename(the_index));
+            let the_type = self.mapping_type.as_deref().map(|the_default| hit._type.unwrap_or(the_default));
+            if let Some(ref the_rule) = self.tombstone
+                && let Tombstone::Dead(the_id) = the_rule.inspect(&the_source)?
+            {
+                let Some(the_id) = the_id.or(hit._id.map(str::to_string)) else {
+                    anyhow::bail!("💀 A tombstoned hit in '{}' has no `_id` to delete by", hit._index);
+                };
+                the_final_result.push(Entry(delete_line(the_index, the_type, &the_id, hit._routing)));
+                continue;
+            }
             let mut the_bulk_body = String::new();
+            if self.require_timestamp {
+                require_timestamp(&the_source)?;
+            }
             // 📡 Write action line: {"index":{"_index":"...","_id":"...","_routing":"..."}}
-            the_bulk_body.push_str(r#"{"index":{"_index":""#);
-            the_bulk_body.push_str(hit._index);
-            the_bulk_body.push('"');
+            write!(the_bulk_body, r#"{{"{}":{{"#, self.action.as_str())
+                .context("💀 fmt::Write into String failed. Reality is broken.")?;
+            let mut the_separator = "";
+            if let Some(the_index) = the_index {
+                write!(the_bulk_body, r#""_index":"{}""#, the_index)
+                    .context("💀 fmt::Write into String failed. Reality is broken.")?;
+                the_separator = ",";
+            }
+
+            if let Some(the_type) = the_type {
+                write!(the_bulk_body, r#"{}"_type":"{}""#, the_separator, the_type)
+                    .context("💀 fmt::Write into String failed. Reality is broken.")?;
+                the_separator = ",";
+            }
 
             if let Some(the_doc_id) = hit._id {
-                write!(the_bulk_body, r#","_id":"{}""#, the_doc_id)
+                write!(the_bulk_body, r#"{}"_id":"{}""#, the_separator, the_doc_id)
                     .context("💀 fmt::Write into String failed. Reality is broken.")?;
+                the_separator = ",";
+            } else if self.action.needs_id() {
+                anyhow::bail!("💀 op_type '{}' needs an `_id` to patch, and a hit in '{}' came without one", self.action.as_str(), hit._index);
             }
 
             if let Some(the_routing_value) = hit._routing {
-                write!(the_bulk_body, r#","_routing":"{}""#, the_routing_value)
+                write!(the_bulk_body, r#"{}"_routing":"{}""#, the_separator, the_routing_value)
                     .context("💀 fmt::Write into String failed. The matrix has a bug.")?;
             }
 
             the_bulk_body.push_str("}}\n");
 
             // 📄 Write source doc — raw JSON borrowed directly from input, zero-copy
-            the_bulk_body.push_str(hit._source.get());
+            // -- (wrapped in `{"doc":..}` for updates)
+            self.action.push_source(&mut the_bulk_body, &the_source);
             the_bulk_body.push('\n');
             the_final_result.push(Entry(the_bulk_body));
         }
@@ -159,7 +242,7 @@ mod tests {
     /// 🧪 Single hit → valid bulk pair (action line + source doc).
     #[test]
     fn the_one_where_a_single_hit_becomes_a_bulk_pair() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -194,7 +277,7 @@ mod tests {
     /// 🧪 Multiple hits — order preserved, each gets its own action line.
     #[test]
     fn the_one_where_multiple_hits_maintain_their_dignity_and_order() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -226,7 +309,7 @@ mod tests {
     /// 🧪 Hit with `_routing` — appears in action line metadata.
     #[test]
     fn the_one_where_routing_shows_up_fashionably_late_but_present() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -255,7 +338,7 @@ mod tests {
     /// 🧪 Hit without `_id` — action line omits it. Auto-gen IDs are ES's problem.
     #[test]
     fn the_one_where_missing_id_is_not_a_crisis() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -282,7 +365,7 @@ mod tests {
     /// 🧪 Empty hits array → empty Vec. The void returns void.
     #[test]
     fn the_one_where_empty_hits_produce_nothing_like_my_motivation_on_mondays() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{"hits": {"hits": []}}"#;
 
         let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
@@ -294,7 +377,7 @@ mod tests {
     /// 🧪 Complex nested `_source` — preserved verbatim via RawValue.
     #[test]
     fn the_one_where_nested_source_survives_the_journey_intact() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         // 📦 Deeply nested source with arrays, nulls, booleans — the works
         let the_search_response = r#"{
             "hits": {
@@ -325,7 +408,7 @@ mod tests {
     /// 🧪 Output ends with `\n` — ES bulk API requires trailing newline.
     #[test]
     fn the_one_where_trailing_newline_is_non_negotiable() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {"hits": [{"_index": "test", "_id": "1", "_source": {"ok": true}}]}
         }"#;
@@ -337,10 +420,34 @@ mod tests {
         Ok(())
     }
 
+    /// 🧪 `index_map` renames the indices it lists and waves the rest through.
+    #[test]
+    fn the_one_where_the_old_products_move_into_v2() -> Result<()> {
+        let the_renames = std::collections::BTreeMap::from([("old-products".to_string(), "products-v2".to_string())]);
+        let the_caster = PitToBulk { index_map: IndexMap::new(&the_renames)?, ..PitToBulk::default() };
+        let the_search_response = r#"{
+            "hits": {
+                "hits": [
+                    {"_index": "old-products", "_id": "1", "_source": {"sku": "a"}},
+                    {"_index": "old-users", "_id": "2", "_source": {"name": "b"}}
+                ]
+            }
+        }"#;
+
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        let the_actions: Vec<serde_json::Value> = the_entries
+            .iter()
+            .map(|e| serde_json::from_str(e.0.lines().next().unwrap_or_default()))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(the_actions[0]["index"]["_index"], "products-v2");
+        assert_eq!(the_actions[1]["index"]["_index"], "old-users");
+        Ok(())
+    }
+
     /// 🧪 Every output line is parseable JSON — no corruption allowed.
     #[test]
     fn the_one_where_every_line_is_valid_json_or_we_riot() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -364,7 +471,7 @@ mod tests {
     /// 🧪 Metadata maps correctly — _index, _id, _routing all land in the right spots.
     #[test]
     fn the_one_where_metadata_finds_its_way_home() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -399,7 +506,7 @@ mod tests {
     /// 🧪 Invalid JSON input → error, no panic. Graceful failure like a cat landing on its feet.
     #[test]
     fn the_one_where_garbage_in_produces_error_not_panic() {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_garbage = "this is not JSON and everyone knows it";
 
         let the_result = the_caster.cast(Page(the_garbage.to_string()));
@@ -409,7 +516,7 @@ mod tests {
     /// 🧪 Response with extra fields (took, _shards, etc.) — ignored gracefully.
     #[test]
     fn the_one_where_extra_envelope_fields_are_politely_ignored() -> Result<()> {
-        let the_caster = PitToBulk;
+        let the_caster = PitToBulk::default();
         let the_full_response = r#"{
             "took": 42,
             "timed_out": false,
@@ -433,4 +540,94 @@ mod tests {
 
         Ok(())
     }
+
+    /// 🧪 Data stream mode: `create`, no source backing index, and no doc without a clock.
+    #[test]
+    fn the_one_where_the_backing_index_stays_home() -> Result<()> {
+        let the_caster = PitToBulk { action: BulkAction::Create, keep_hit_index: false, require_timestamp: true, ..PitToBulk::default() };
+        let the_search_response = r#"{"hits":{"hits":[
+            {"_index":".ds-logs-2024.01.01-000001","_id":"a","_source":{"@timestamp":"2024-01-01T00:00:00Z"}}
+        ]}}"#;
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        assert_eq!(the_entries[0].0, "{\"create\":{\"_id\":\"a\"}}\n{\"@timestamp\":\"2024-01-01T00:00:00Z\"}\n");
+
+        let the_clockless = r#"{"hits":{"hits":[{"_index":"x","_source":{"msg":"?"}}]}}"#;
+        assert!(the_caster.cast(Page(the_clockless.to_string())).is_err());
+        Ok(())
+    }
+
+    /// 🧪 Upsert mode: the doc gets wrapped, the `_id` stays, and an id-less hit is refused.
+    #[test]
+    fn the_one_where_the_rerun_patches_instead_of_clobbering() -> Result<()> {
+        let the_caster = PitToBulk { action: BulkAction::Upsert, ..PitToBulk::default() };
+        let the_search_response = r#"{"hits":{"hits":[{"_index":"people","_id":"7","_source":{"age":31}}]}}"#;
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        assert_eq!(
+            the_entries[0].0,
+            "{\"update\":{\"_index\":\"people\",\"_id\":\"7\"}}\n{\"doc\":{\"age\":31},\"doc_as_upsert\":true}\n"
+        );
+
+        let the_anonymous = r#"{"hits":{"hits":[{"_index":"people","_source":{"age":31}}]}}"#;
+        assert!(the_caster.cast(Page(the_anonymous.to_string())).is_err());
+        Ok(())
+    }
+
+    /// 🧪 A soft-deleted hit becomes a delete for its own `_id`, routing and all.
+    #[test]
+    fn the_one_where_the_soft_delete_becomes_a_hard_one() -> Result<()> {
+        let the_caster = PitToBulk { tombstone: Some(toml::from_str("field = \"deleted\"")?), ..PitToBulk::default() };
+        let the_search_response = r#"{"hits":{"hits":[
+            {"_index":"people","_id":"1","_source":{"deleted":false}},
+            {"_index":"people","_id":"2","_routing":"eu","_source":{"deleted":true}}
+        ]}}"#;
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        assert!(the_entries[0].0.starts_with("{\"index\""));
+        let the_delete: serde_json::Value = serde_json::from_str(the_entries[1].0.trim())?;
+        assert_eq!(the_delete, serde_json::json!({ "delete": { "_index": "people", "_id": "2", "_routing": "eu" } }));
+        assert_eq!(the_entries[1].0.lines().count(), 1, "🎯 a delete has no document line");
+        Ok(())
+    }
+
+    /// 🧪 An index template splits one source index into one index per project, `_type` from the hit.
+    #[test]
+    fn the_one_where_the_monolith_gets_subdivided() -> Result<()> {
+        let the_caster = PitToBulk {
+            index_template: Some(IndexTemplate::parse("{_type}-{Project.Name}")?),
+            tombstone: Some(toml::from_str("field = \"deleted\"")?),
+            ..PitToBulk::default()
+        };
+        let the_search_response = r#"{"hits":{"hits":[
+            {"_index":"rally","_type":"Defect","_id":"1","_source":{"Project":{"Name":"Apollo"}}},
+            {"_index":"rally","_type":"Story","_id":"2","_source":{"Project":{"Name":"Gemini"},"deleted":true}}
+        ]}}"#;
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        assert!(the_entries[0].0.starts_with("{\"index\":{\"_index\":\"defect-apollo\",\"_id\":\"1\"}}\n"));
+        let the_delete: serde_json::Value = serde_json::from_str(the_entries[1].0.trim())?;
+        assert_eq!(the_delete, serde_json::json!({ "delete": { "_index": "story-gemini", "_id": "2" } }));
+        Ok(())
+    }
+
+    /// 🧪 An es6 sink: 6.x hits keep their own `_type`, typeless hits get the configured one.
+    #[test]
+    fn the_one_where_the_archive_still_speaks_in_types() -> Result<()> {
+        let the_caster = PitToBulk {
+            mapping_type: Some("_doc".to_string()),
+            tombstone: Some(toml::from_str("field = \"deleted\"")?),
+            ..PitToBulk::default()
+        };
+        let the_search_response = r#"{"hits":{"hits":[
+            {"_index":"logs","_type":"event","_id":"1","_source":{}},
+            {"_index":"logs","_id":"2","_source":{}},
+            {"_index":"logs","_type":"event","_id":"3","_source":{"deleted":true}}
+        ]}}"#;
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        assert!(the_entries[0].0.starts_with("{\"index\":{\"_index\":\"logs\",\"_type\":\"event\",\"_id\":\"1\"}}\n"));
+        assert!(the_entries[1].0.starts_with("{\"index\":{\"_index\":\"logs\",\"_type\":\"_doc\",\"_id\":\"2\"}}\n"));
+        assert_eq!(the_entries[2].0, "{\"delete\":{\"_index\":\"logs\",\"_type\":\"event\",\"_id\":\"3\"}}\n");
+
+        // -- 🏎️ without es6, a hit's `_type` stays out of the action line
+        let the_modern = PitToBulk::default().cast(Page(the_search_response.to_string()))?;
+        assert!(!the_modern[0].0.lines().next().unwrap_or_default().contains("_type"));
+        Ok(())
+    }
 }
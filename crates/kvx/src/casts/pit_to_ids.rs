@@ -0,0 +1,173 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+// ai
+//! 🪪 PitToIds — ES _search PIT response → one compact ID line per hit 🚀📡🧾
+//!
+//! 🎬 COLD OPEN — INT. RECONCILIATION WAR ROOM — 6 AM
+//! *[Two clusters. Four billion documents each. "Are they the same?" asks the VP.]*
+//! *["Let's copy everything and diff it," says the intern.]*
+//! *[PitToIds quietly slides a tab-separated file across the table. It is 40x smaller.]*
+//!
+//! Strips every hit down to its identity: `_index`, `_id`, and optionally `_version`
+//! and a content hash of `_source`. One line per doc, tab-separated:
+//! ```text
+//! {_index}\t{_id}[\t{_version}][\t{hash}]\n
+//! ```
+//!
+//! ## Knowledge Graph 🧠
+//! - Input: raw `_search` HTTP response body (same envelope as `PitToBulk` / `PitToJson`)
+//! - Output: one TSV line per hit — feed it to `sort`/`comm`/`join` or any reconciliation tool
+//! - Selected when `[sink_config.File.id_export]` is present and the source is Elasticsearch
+//! - Hash: FNV-1a 64-bit over the raw `_source` bytes, 16 hex chars. Not cryptographic —
+//!   it's a "did this doc change" fingerprint, not a password vault. Stable across builds
+//!   (unlike `DefaultHasher`, which is allowed to change between Rust releases)
+//! - `_version` is only present when the search requested `"version": true`; missing → `-`
+//! - Missing `_id` (shouldn't happen in search hits, but ES has surprised us before) → `-`
+//!
+//! ⚠️ The singularity will reconcile by vibes. We use tab characters. 🦆
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+use crate::casts::Caster;
+use crate::Entry;
+use crate::Page;
+
+// 🔢 FNV-1a 64-bit constants — tiny, stable, and already memorized by every hash-table nerd
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// ===== Serde structs — zero-copy via borrow =====
+
+/// 📡 The outermost envelope of an ES `_search` response.
+#[derive(Deserialize)]
+struct SearchEnvelope<'a> {
+    #[serde(borrow)]
+    hits: SearchHits<'a>,
+}
+
+/// 📦 The `hits` object — contains the actual hit array.
+#[derive(Deserialize)]
+struct SearchHits<'a> {
+    #[serde(borrow)]
+    hits: Vec<SearchHit<'a>>,
+}
+
+/// 🎯 A single search hit — identity fields plus `_source` (only read when hashing).
+#[derive(Deserialize)]
+struct SearchHit<'a> {
+    #[serde(borrow)]
+    _index: &'a str,
+    _id: Option<&'a str>,
+    _version: Option<u64>,
+    // 📄 optional — `_source: false` searches are perfectly valid for ID-only exports
+    #[serde(borrow, default)]
+    _source: Option<&'a RawValue>,
+}
+
+/// 🧮 FNV-1a over a byte slice. Eight lines of code, zero dependencies, zero drama.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut the_hash = FNV_OFFSET_BASIS;
+    for byte in bytes {
+        the_hash ^= *byte as u64;
+        the_hash = the_hash.wrapping_mul(FNV_PRIME);
+    }
+    the_hash
+}
+
+/// 🪪 PitToIds — reduces ES search hits to compact ID lines.
+///
+/// Two booleans of state, `Copy`, cloned per joiner for free. 🐄
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PitToIds {
+    /// 🔢 Append `_version` as a column
+    pub include_version: bool,
+    /// 🧮 Append an FNV-1a hash of `_source` as a column
+    pub include_hash: bool,
+}
+
+impl Caster for PitToIds {
+    #[inline]
+    fn cast(&self, page: Page) -> Result<Vec<Entry>> {
+        let the_envelope: SearchEnvelope<'_> = serde_json::from_str(page.0.as_ref())
+            .context("💀 Failed to parse _search response envelope for ID export. We only wanted the names. The JSON wouldn't even tell us those.")?;
+
+        let mut the_entries = Vec::with_capacity(the_envelope.hits.hits.len());
+        for hit in &the_envelope.hits.hits {
+            // -- 🧾 index + id are always there; extras appended only when asked for
+            let mut the_line = String::with_capacity(hit._index.len() + 64);
+            the_line.push_str(hit._index);
+            the_line.push('\t');
+            the_line.push_str(hit._id.unwrap_or("-"));
+            if self.include_version {
+                the_line.push('\t');
+                match hit._version {
+                    Some(v) => write!(the_line, "{}", v)?,
+                    None => the_line.push('-'),
+                }
+            }
+            if self.include_hash {
+                the_line.push('\t');
+                match hit._source {
+                    Some(src) => write!(the_line, "{:016x}", fnv1a_64(src.get().as_bytes()))?,
+                    None => the_line.push('-'),
+                }
+            }
+            the_line.push('\n');
+            the_entries.push(Entry(the_line));
+        }
+        Ok(the_entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THE_SEARCH_RESPONSE: &str = r#"{
+        "hits": {
+            "hits": [
+                {"_index": "movies", "_id": "1", "_version": 3, "_source": {"title": "Alien"}},
+                {"_index": "movies", "_id": "2", "_source": {"title": "Aliens"}}
+            ]
+        }
+    }"#;
+
+    /// 🧪 Bare mode — just index and id, one line per hit, order preserved.
+    #[test]
+    fn the_one_where_docs_are_reduced_to_their_name_tags() -> Result<()> {
+        let the_entries = PitToIds::default().cast(Page(THE_SEARCH_RESPONSE.to_string()))?;
+        assert_eq!(the_entries.len(), 2);
+        assert_eq!(the_entries[0].0, "movies\t1\n");
+        assert_eq!(the_entries[1].0, "movies\t2\n");
+        Ok(())
+    }
+
+    /// 🧪 Version + hash columns — missing `_version` becomes `-`, hashes differ per doc.
+    #[test]
+    fn the_one_where_versions_and_fingerprints_come_along() -> Result<()> {
+        let the_caster = PitToIds { include_version: true, include_hash: true };
+        let the_entries = the_caster.cast(Page(THE_SEARCH_RESPONSE.to_string()))?;
+
+        let the_first: Vec<&str> = the_entries[0].0.trim_end().split('\t').collect();
+        let the_second: Vec<&str> = the_entries[1].0.trim_end().split('\t').collect();
+        assert_eq!(the_first[..3], ["movies", "1", "3"]);
+        assert_eq!(the_second[2], "-", "💀 no _version in the hit should render as '-'");
+        assert_eq!(the_first[3].len(), 16);
+        assert_ne!(the_first[3], the_second[3], "💀 different _source, same hash? FNV is not that lazy");
+        assert_eq!(the_first[3], format!("{:016x}", fnv1a_64(br#"{"title": "Alien"}"#)));
+        Ok(())
+    }
+
+    /// 🧪 FNV-1a known-answer test — the empty string hashes to the offset basis.
+    #[test]
+    fn the_one_where_nothing_hashes_to_the_offset_basis() {
+        assert_eq!(fnv1a_64(b""), FNV_OFFSET_BASIS);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
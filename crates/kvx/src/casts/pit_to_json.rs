@@ -18,17 +18,22 @@
 //! 🧠 Knowledge graph:
 //! - Input: raw `_search` HTTP response body (JSON envelope with `hits.hits[]`)
 //! - Output: Vec<Entry>, one Entry per hit containing just `_source`
-//! - Used for: ES→Meilisearch (extract docs from PIT response for JSON array ingestion)
+//! - Used for: ES→Meilisearch (extract docs from PIT response for JSON array ingestion),
+//!   and ES→File with `es_hits = "source"` (`line_terminated`, one doc per line)
 //! - Sister caster: `PitToBulk` (same extraction, but wraps with bulk action headers)
 //! - `_source` uses `&RawValue` — zero re-serialization, borrows directly from input
+//! - `id_field` (ES→Solr) copies each hit's `_id` into the doc — the one case that re-serializes
 //!
 //! ⚠️ The singularity will migrate data via quantum entanglement. We use HTTP. 🦆
 
+use std::borrow::Cow;
+
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use serde_json::value::RawValue;
 
 use crate::casts::Caster;
+use crate::transforms::TransformChain;
 use crate::Entry;
 use crate::Page;
 
@@ -50,21 +55,31 @@ struct SearchHits<'a> {
     hits: Vec<SearchHit<'a>>,
 }
 
-/// 🎯 A single search hit — we only care about `_source` here.
-/// No `_index`, no `_id`, no `_routing` — those are ES's problem, not Meilisearch's.
+/// 🎯 A single search hit — we only care about `_source` here (and `_id`, when `id_field` asks).
+/// No `_index`, no `_routing` — those are ES's problem, not Meilisearch's.
 /// Like moving to a new city and leaving your old mail behind. 📬
 #[derive(Deserialize)]
 struct SearchHit<'a> {
+    #[serde(borrow, default)]
+    _id: Option<Cow<'a, str>>,
     #[serde(borrow)]
     _source: &'a RawValue,
 }
 
 /// 🔍 PitToJson — extracts `_source` from ES search hits as raw JSON entries.
 ///
-/// Zero-sized struct. Cloning is free. Inlining is guaranteed.
+/// Carries only the transform chain (an `Arc`). Cloning is free.
 /// The ghost of bulk action headers past does NOT haunt this caster. 👻
-#[derive(Debug, Clone, Copy)]
-pub struct PitToJson;
+#[derive(Debug, Clone, Default)]
+pub struct PitToJson {
+    /// 🔧 `[[transforms]]`, applied to each `_source`
+    pub transforms: TransformChain,
+    /// 📄 End every entry with `\n` — for NDJSON manifolds (ES → File with `es_hits = "source"`)
+    pub line_terminated: bool,
+    /// 🪪 Copy each hit's `_id` into this field, unless the doc already has one — for sinks whose
+    /// unique key lives in the doc (Solr's `id`)
+    pub id_field: Option<String>,
+}
 
 impl Caster for PitToJson {
     #[inline]
@@ -81,15 +96,33 @@ impl Caster for PitToJson {
         }
 
         // 📦 Extract _source from each hit — just the raw doc, no metadata baggage
-        let the_entries: Vec<Entry> = the_hits
-            .iter()
-            .map(|hit| Entry(hit._source.get().to_string()))
-            .collect();
+        let mut the_entries = Vec::with_capacity(the_hits.len());
+        let the_sources: Vec<&str> = the_hits.iter().map(|hit| hit._source.get()).collect();
+        for (hit, the_outcome) in the_hits.iter().zip(self.transforms.apply_all(&the_sources)) {
+            if let Some(the_doc) = the_outcome? {
+                let mut the_entry = match (&self.id_field, &hit._id) {
+                    (Some(the_field), Some(the_id)) => with_id(&the_doc, the_field, the_id)?,
+                    _ => the_doc.into_owned(),
+                };
+                if self.line_terminated {
+                    the_entry.push('\n');
+                }
+                the_entries.push(Entry(the_entry));
+            }
+        }
 
         Ok(the_entries)
     }
 }
 
+/// 🪪 `doc` with `field: id` added, unless it's already there (the doc's own key wins).
+fn with_id(doc: &str, field: &str, id: &str) -> Result<String> {
+    let mut the_doc: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(doc).context("💀 id_field needs each doc to be a JSON object")?;
+    the_doc.entry(field).or_insert_with(|| serde_json::Value::String(id.to_string()));
+    Ok(serde_json::to_string(&the_doc)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,7 +130,7 @@ mod tests {
     /// 🧪 Single hit → one entry containing just the _source document.
     #[test]
     fn the_one_where_a_single_hit_sheds_its_metadata_and_finds_freedom() -> Result<()> {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -127,7 +160,7 @@ mod tests {
     /// 🧪 Multiple hits — order preserved, each is just _source.
     #[test]
     fn the_one_where_three_hits_arrive_in_order_like_well_behaved_children() -> Result<()> {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -154,7 +187,7 @@ mod tests {
     /// 🧪 Empty hits → empty Vec. Nothing in, nothing out.
     #[test]
     fn the_one_where_empty_hits_produce_nothing_like_a_blank_google_search() -> Result<()> {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_search_response = r#"{"hits": {"hits": []}}"#;
 
         let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
@@ -165,7 +198,7 @@ mod tests {
     /// 🧪 Invalid JSON input → error, not panic.
     #[test]
     fn the_one_where_garbage_in_produces_error_like_a_responsible_adult() {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_result = the_caster.cast(Page("not even close to JSON".to_string()));
         assert!(the_result.is_err(), "💀 Invalid JSON should produce error, not silence");
     }
@@ -173,7 +206,7 @@ mod tests {
     /// 🧪 Complex nested _source — preserved verbatim.
     #[test]
     fn the_one_where_deeply_nested_source_survives_extraction_intact() -> Result<()> {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -198,7 +231,7 @@ mod tests {
     /// 🧪 Extra envelope fields (took, _shards) are politely ignored.
     #[test]
     fn the_one_where_extra_envelope_fields_are_invisible_like_my_gym_membership() -> Result<()> {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_full_response = r#"{
             "took": 42,
             "timed_out": false,
@@ -222,7 +255,7 @@ mod tests {
     /// 🧪 Every entry is valid JSON — structural integrity check.
     #[test]
     fn the_one_where_every_entry_passes_the_json_bar_exam() -> Result<()> {
-        let the_caster = PitToJson;
+        let the_caster = PitToJson::default();
         let the_search_response = r#"{
             "hits": {
                 "hits": [
@@ -240,4 +273,20 @@ mod tests {
         }
         Ok(())
     }
+
+    /// 🧪 `id_field` carries `_id` into the doc — but never over a key the doc already has.
+    #[test]
+    fn the_one_where_the_id_gets_a_seat_inside_the_doc() -> Result<()> {
+        let the_caster = PitToJson { id_field: Some("id".to_string()), ..PitToJson::default() };
+        let the_search_response = r#"{"hits":{"hits":[
+            {"_id":"p-1","_source":{"name":"Lamp"}},
+            {"_id":"p-2","_source":{"id":"sku-2","name":"Rug"}}
+        ]}}"#;
+        let the_entries = the_caster.cast(Page(the_search_response.to_string()))?;
+        let the_lamp: serde_json::Value = serde_json::from_str(&the_entries[0].0)?;
+        let the_rug: serde_json::Value = serde_json::from_str(&the_entries[1].0)?;
+        assert_eq!(the_lamp["id"], "p-1");
+        assert_eq!(the_rug["id"], "sku-2", "🪪 the doc's own key wins");
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@
 //
 // Use of this software is governed by the Business Source License
 // included in the LICENSE file and at www.mariadb.com/bsl11.
-//! 🔧 App Configuration — the sacred TOML-to-struct pipeline.
+//! 🔧 App Configuration — the sacred TOML-to-struct pipeline (YAML and JSON also welcome).
 //!
 //! 📡 "Config not found: We looked everywhere. Under the couch. Behind the fridge.
 //! In the junk drawer. Nothing." — every developer at 3am 🦆
@@ -11,15 +11,21 @@
 //! self-harm that even the borrow checker wouldn't approve of.
 
 use anyhow::Context;
+use std::path::PathBuf;
 use crate::workers::DrainerConfig;
 use crate::workers::FlowMasterConfig;
+use crate::notify::NotifyConfig;
+use crate::progress::ProgressSubscribers;
+use crate::workers::{FailedDocs, PauseSwitch};
+use crate::transforms::{TransformConfig, TransformRegistry};
+use crate::warnings::{RunWarnings, WarningKind};
 use serde::Deserialize;
 // -- 🔧 To load the configuration, so I don't have to manually parse
 // -- environment variables or files. Bleh. Like doing taxes but for bytes.
 pub use crate::backends::{SinkConfig, SourceConfig};
 use figment::{
     Figment,
-    providers::{Env, Format, Toml},
+    providers::{Env, Format, Json, Toml, Yaml},
 };
 use std::path::Path;
 // -- 🚀 tracing::info — because println! in production is a cry for help.
@@ -52,11 +58,53 @@ pub struct RuntimeConfig {
     /// 🧵 How many sink workers run in parallel — more lanes, more throughput, more debugging
     #[serde(default = "default_sink_parallelism", alias = "num_sink_workers")]
     pub sink_parallelism: usize,
+    /// 🚰 How many sources are read at once, when there's more than one (ES slices, a file
+    /// pattern). Unset: all of them. The rest wait in line for a free pumper
+    #[serde(default)]
+    pub source_parallelism: Option<usize>,
+    /// 🧮 Cap on bytes read but not yet written — across ch1, the joiners' buffers and ch2.
+    /// Pumpers wait while it's reached. Unset: only the channel capacities (in pages) apply
+    #[serde(default)]
+    pub max_buffered_bytes: Option<usize>,
     /// 🧵 How many joiner threads to spawn for CPU-bound casting+joining work.
     /// Defaults to (cpu_count - 1, minimum 1) because we're generous enough to leave
     /// one core for the OS, the async runtime, and whatever else wants to live. 🦆
     #[serde(default = "default_joiner_parallelism", alias = "num_joiner_workers")]
     pub joiner_parallelism: usize,
+    /// 📊 `"bar"` (default) draws the progress bar on a terminal; `"log"` prints a plain line
+    /// every `progress_interval_secs`; `"off"` draws nothing at all. Subscribers hear every tick regardless
+    #[serde(default)]
+    pub progress: ProgressMode,
+    /// 📜 Seconds between plain progress lines — `"log"` mode, and `"bar"` when stderr isn't a terminal
+    #[serde(default = "default_progress_interval_secs")]
+    pub progress_interval_secs: u64,
+    /// 🧯 Fail the run once more than this many docs have failed (rejected by the sink, or by a
+    /// transform as invalid). The source stops, in-flight work drains. Unset: no limit
+    #[serde(default)]
+    pub max_errors: Option<u64>,
+    /// 🧯 Same, as a fraction of docs seen (0.0–1.0). Checked once 1,000 docs are in, and at the end
+    #[serde(default)]
+    pub max_error_ratio: Option<f64>,
+    /// 🩹 A doc that isn't JSON, or that a `[[transforms]]` step errors on, is skipped (and
+    /// written to `rejects_file`, if set) instead of failing the run. Off by default
+    #[serde(default)]
+    pub skip_bad_docs: bool,
+    /// 🧾 Where `skip_bad_docs` appends what it skipped: NDJSON `{"error":"..","document":..}`
+    #[serde(default)]
+    pub rejects_file: Option<PathBuf>,
+}
+
+/// 📊 How progress reaches the terminal. `kvx-cli --quiet` is the same as `"off"`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressMode {
+    /// 🎨 indicatif bar + per-drainer rows — or `Log` lines when stderr isn't a terminal
+    #[default]
+    Bar,
+    /// 📜 one plain line every `progress_interval_secs`, terminal or not — CI logs
+    Log,
+    /// 🙊 no bar, no control sequences — for scripts and cron
+    Off,
 }
 
 impl Default for RuntimeConfig {
@@ -65,11 +113,24 @@ impl Default for RuntimeConfig {
             pumper_to_joiner_capacity: default_pumper_to_joiner_capacity(),
             joiner_to_drainer_capacity: default_joiner_to_drainer_capacity(),
             sink_parallelism: default_sink_parallelism(),
+            source_parallelism: None,
+            max_buffered_bytes: None,
             joiner_parallelism: default_joiner_parallelism(),
+            progress: ProgressMode::default(),
+            progress_interval_secs: default_progress_interval_secs(),
+            max_errors: None,
+            max_error_ratio: None,
+            skip_bad_docs: false,
+            rejects_file: None,
         }
     }
 }
 
+// 📜 Thirty seconds: often enough to see a stall, rare enough that a two-hour CI log isn't all progress.
+fn default_progress_interval_secs() -> u64 {
+    crate::progress::DEFAULT_PROGRESS_INTERVAL.as_secs()
+}
+
 // 🔢 10: chosen by rolling a d20, getting a 10, and calling it "load tested".
 // -- The queue holds batches, not feelings, though both can become backpressure if ignored. 🦆
 fn default_pumper_to_joiner_capacity() -> usize {
@@ -123,17 +184,122 @@ pub struct AppConfig {
     /// drain latency, CPU = PID from cluster CPU stats. Replaces the old `regulator` field. 🔧
     #[serde(default)]
     pub flow_master: FlowMasterConfig,
+    /// 🔧 `[[transforms]]` — per-document reshaping, applied in order while casting. None by default.
+    #[serde(default)]
+    pub transforms: Vec<TransformConfig>,
+    /// ⚠️ Non-fatal warnings collected from config load through end of run.
+    /// Not a TOML key — it rides along with the config so every stage can reach it. 📦
+    #[serde(skip)]
+    pub warnings: RunWarnings,
+    /// 📬 Who hears about progress besides the terminal bar. Builder-only, like `warnings`.
+    #[serde(skip)]
+    pub progress: ProgressSubscribers,
+    /// 🧩 Embedder-registered transforms for `Custom` entries in `[[transforms]]`. Builder-only.
+    #[serde(skip)]
+    pub transform_registry: TransformRegistry,
+    /// ⏸️ Flip to `true` to hold the pumpers between pages. Builder-only, like `progress`.
+    #[serde(skip)]
+    pub pause: PauseSwitch,
+    /// 🧯 Docs the sinks couldn't write, counted live for `max_errors` / `max_error_ratio`. Run-internal.
+    #[serde(skip)]
+    pub failed_docs: FailedDocs,
+    /// 📣 `[notify]` — who hears how the run ended. Nobody, by default.
+    #[serde(default)]
+    pub notify: NotifyConfig,
+}
+
+// 🧓 Old runtime key names that still parse via serde aliases, paired with their modern names.
+// -- They work. They'll keep working. But we'll mention it, like a friend pointing at spinach in your teeth.
+const DEPRECATED_RUNTIME_KEYS: &[(&str, &str)] = &[
+    ("channel_size", "pumper_to_joiner_capacity"),
+    ("queue_capacity", "pumper_to_joiner_capacity"),
+    ("payload_channel_capacity", "joiner_to_drainer_capacity"),
+    ("num_sink_workers", "sink_parallelism"),
+    ("num_joiner_workers", "joiner_parallelism"),
+];
+
+// 📏 Elasticsearch's default `http.max_content_length` — bulk bodies past this get a 413.
+const ES_HTTP_MAX_CONTENT_LENGTH_BYTES: usize = 100 * 1024 * 1024;
+
+impl AppConfig {
+    /// 😬 Record close-call limits into `self.warnings` — values that are legal but flirting with trouble.
+    ///
+    /// 🧠 Knowledge graph: called once by `run()` before the pipeline starts. Deprecated keys
+    /// are recorded earlier by `load_config()` since only Figment knows which keys were spelled how.
+    pub fn collect_warnings(&self) {
+        let the_sink_limit = self.sink_config.max_request_size_bytes();
+
+        // 🎛️ Static flow flushes at output_bytes no matter what the sink says it can take
+        if let FlowMasterConfig::Static(cfg) = &self.flow_master
+            && cfg.output_bytes > the_sink_limit
+        {
+            self.warnings.record(
+                WarningKind::CloseCall,
+                format!(
+                    "flow_master.Static.output_bytes ({}) exceeds the sink's max_request_size_bytes ({}) — payloads may be larger than the sink expects",
+                    cfg.output_bytes, the_sink_limit
+                ),
+            );
+        }
+
+        // 📡 ES-speaking sinks have a hard body limit on the server side
+        let the_bulk_limit = match &self.sink_config {
+            SinkConfig::Elasticsearch(_) | SinkConfig::OpenObserve(_) => Some(the_sink_limit),
+            _ => None,
+        };
+        if let Some(limit) = the_bulk_limit
+            && limit > ES_HTTP_MAX_CONTENT_LENGTH_BYTES
+        {
+            self.warnings.record(
+                WarningKind::CloseCall,
+                format!(
+                    "max_request_size_bytes ({}) is above Elasticsearch's default http.max_content_length ({}) — expect 413s unless the cluster was tuned",
+                    limit, ES_HTTP_MAX_CONTENT_LENGTH_BYTES
+                ),
+            );
+        }
+    }
+}
+
+/// 📄 Which dialect the config file speaks — picked from its extension.
+///
+/// `.yaml`/`.yml` → YAML, `.json` → JSON, anything else (including no extension) → TOML,
+/// because TOML was here first and seniority counts for something. 🦆
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// 🔍 Sniff the format from a path's extension (case-insensitive).
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|the_ext| the_ext.to_str())
+            .map(|the_ext| the_ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            Some("json") => ConfigFormat::Json,
+            _ => ConfigFormat::Toml,
+        }
+    }
 }
 
 /// 🚀 Load the config — from a file, from env vars, or from the sheer power of hoping.
 ///
-/// 🔧 Merges environment variables (KVX_*) with an optional TOML file.
+/// 🔧 Merges environment variables (KVX_*) with an optional TOML, YAML, or JSON file
+/// (see [`ConfigFormat::from_path`]). Same shape in every format — YAML and JSON use the
+/// same externally tagged keys as TOML, e.g. `sink_config: { File: { file_name: ... } }`.
 /// Notice: no `.only(...)` restriction — ALL KVX_ vars are fair game now.
 /// We don't gatekeep env vars here. This is a safe space. 🦆
 ///
 /// 📐 DESIGN NOTE (no cap, this is tribal knowledge):
 ///   - If `config_file_name` is None  → env vars only. No file. No assumptions. No pizza defaults.
 ///   - If `config_file_name` is Some  → env vars + TOML file, merged. TOML wins on conflicts.
+///
 ///   Previously kravex always fell back to "config.toml" — like assuming everyone wants pineapple
 ///   on their pizza. We fixed that. ethos showed us the light.
 ///
@@ -144,7 +310,7 @@ pub fn load_config(config_file_name: Option<&Path>) -> anyhow::Result<AppConfig>
     // -- of every 3am incident. "The config loaded fine." — famous last words.
     info!(
         "🔧 Loading configuration: {:#?}",
-        config_file_name.unwrap_or(&Path::new(""))
+        config_file_name.unwrap_or(Path::new(""))
     );
 
     // -- 🏗️ Start with env vars as the base layer — like a good sourdough starter.
@@ -154,8 +320,13 @@ pub fn load_config(config_file_name: Option<&Path>) -> anyhow::Result<AppConfig>
     // -- 🎯 Conditionally layer in TOML only if a file was actually provided.
     // -- No file? No problem. We trust the env. Like a golden retriever trusts everyone.
     // -- Ancient proverb: "He who defaults to config.toml uninvited, deploys to production alone."
+    // -- 📄 Infra tooling loves YAML. Some scripts love JSON. We love whoever sends us config.
     let config = match config_file_name {
-        Some(file_name) => config.merge(Toml::file(file_name)),
+        Some(file_name) => match ConfigFormat::from_path(file_name) {
+            ConfigFormat::Toml => config.merge(Toml::file(file_name)),
+            ConfigFormat::Yaml => config.merge(Yaml::file(file_name)),
+            ConfigFormat::Json => config.merge(Json::file(file_name)),
+        },
         None => config,
     };
 
@@ -174,7 +345,32 @@ pub fn load_config(config_file_name: Option<&Path>) -> anyhow::Result<AppConfig>
 
     // -- ✅ or 💀, there is no try — actually there is, it's called `?`
     // -- TODO: win the lottery, retire, delete this crate
-    config.extract().context(context_msg)
+    let mut app_config: AppConfig = config.extract().context(context_msg)?;
+
+    // 🔑 Secret mounts → inline credentials, once, before anything tries to authenticate
+    app_config
+        .source_config
+        .resolve_secret_files()
+        .context("💀 Failed to load source credentials from file")?;
+    app_config
+        .sink_config
+        .resolve_secret_files()
+        .context("💀 Failed to load sink credentials from file")?;
+
+    // 🧓 Serde aliases swallow the old names silently — ask Figment which spelling was actually used
+    for (the_old_key, the_new_key) in DEPRECATED_RUNTIME_KEYS {
+        if config.find_value(&format!("runtime.{}", the_old_key)).is_ok() {
+            app_config.warnings.record(
+                WarningKind::DeprecatedConfig,
+                format!(
+                    "runtime.{} is deprecated — use runtime.{} instead",
+                    the_old_key, the_new_key
+                ),
+            );
+        }
+    }
+
+    Ok(app_config)
 }
 
 #[cfg(test)]
@@ -188,9 +384,13 @@ mod tests {
     /// the other's TOML content. Like two people writing different grocery lists on the
     /// same fridge whiteboard at the same time. Now each test gets its own file. 🧊🦆
     fn write_test_config(contents: &str) -> tempfile::TempPath {
+        write_test_config_as(contents, ".toml")
+    }
+
+    fn write_test_config_as(contents: &str, suffix: &str) -> tempfile::TempPath {
         let the_temp_file = tempfile::Builder::new()
             .prefix("kvx_app_config_")
-            .suffix(".toml")
+            .suffix(suffix)
             .tempfile()
             .expect("💀 Failed to create temp file. The OS said 'I'm full, try again never'.");
 
@@ -209,6 +409,8 @@ mod tests {
             [runtime]
             pumper_to_joiner_capacity = 8
             sink_parallelism = 3
+            progress = "off"
+            progress_interval_secs = 5
 
             [source_config.File]
             file_name = "input.json"
@@ -225,9 +427,12 @@ mod tests {
 
         assert_eq!(app_config.runtime.pumper_to_joiner_capacity, 8);
         assert_eq!(app_config.runtime.sink_parallelism, 3);
+        assert_eq!(app_config.runtime.progress, ProgressMode::Off);
+        assert_eq!(app_config.runtime.progress_interval_secs, 5);
         match app_config.sink_config {
             SinkConfig::File(file_config) => {
-                assert_eq!(file_config.common_config.max_request_size_bytes, 123456);
+                assert_eq!(file_config.common_config.max_request_size_bytes, Some(123456));
+                assert_eq!(file_config.tuning().max_request_size_bytes, 123456);
             }
             honestly_who_knows => panic!(
                 "💀 Expected File sink config in the test, but serde took us to {:?}. Plot twist energy.",
@@ -257,6 +462,7 @@ mod tests {
 
         assert_eq!(app_config.runtime.pumper_to_joiner_capacity, RuntimeConfig::default().pumper_to_joiner_capacity);
         assert_eq!(app_config.runtime.sink_parallelism, RuntimeConfig::default().sink_parallelism);
+        assert_eq!(app_config.runtime.progress, ProgressMode::Bar);
 
         // 🧹 TempPath auto-deletes on drop — no manual cleanup needed
     }
@@ -283,6 +489,137 @@ mod tests {
         assert_eq!(app_config.runtime.pumper_to_joiner_capacity, 12);
         assert_eq!(app_config.runtime.sink_parallelism, 4);
 
+        // ⚠️ the old names still work, but they leave a note on the fridge
+        let the_notes = app_config.warnings.snapshot();
+        assert_eq!(the_notes.len(), 2);
+        assert!(the_notes.iter().all(|w| w.kind == WarningKind::DeprecatedConfig));
+        assert!(the_notes.iter().any(|w| w.message.contains("runtime.channel_size")));
+
         // 🧹 TempPath auto-deletes on drop — no manual cleanup needed
     }
+
+    #[test]
+    fn the_one_where_static_flow_outgrows_the_sink() {
+        let config_path = write_test_config(
+            r#"
+            [source_config.File]
+            file_name = "input.json"
+
+            [sink_config.Elasticsearch]
+            url = "http://localhost:9200"
+            max_request_size_bytes = 1000
+
+            [flow_master.Static]
+            output_bytes = 5000
+            "#,
+        );
+
+        let app_config = load_config(Some(&config_path))
+            .expect("💀 A legal-but-questionable config should still parse.");
+        assert!(app_config.warnings.is_empty(), "💀 nothing deprecated here, so no notes yet");
+
+        app_config.collect_warnings();
+        let the_notes = app_config.warnings.snapshot();
+        assert_eq!(the_notes.len(), 1);
+        assert_eq!(the_notes[0].kind, WarningKind::CloseCall);
+    }
+
+    #[test]
+    fn the_one_where_the_secrets_come_from_the_mounted_volume() {
+        let the_password_file = tempfile::NamedTempFile::new().expect("💀 temp file");
+        fs::write(the_password_file.path(), "hunter2\n").expect("💀 write secret");
+        let the_api_key_file = tempfile::NamedTempFile::new().expect("💀 temp file");
+        fs::write(the_api_key_file.path(), "c2VjcmV0").expect("💀 write secret");
+
+        let config_path = write_test_config(&format!(
+            r#"
+            [source_config.File]
+            file_name = "input.json"
+
+            [sink_config.Elasticsearch]
+            url = "http://localhost:9200"
+            username = "elastic"
+            password_file = "{}"
+            api_key_file = "{}"
+            "#,
+            the_password_file.path().display(),
+            the_api_key_file.path().display()
+        ));
+
+        let app_config = load_config(Some(&config_path)).expect("💀 secret files should load");
+        match app_config.sink_config {
+            SinkConfig::Elasticsearch(es) => {
+                assert_eq!(es.password.as_deref(), Some("hunter2"), "🎯 trailing newline trimmed");
+                assert_eq!(es.api_key.as_deref(), Some("c2VjcmV0"));
+            }
+            honestly_who_knows => panic!("💀 Expected Elasticsearch sink, got {:?}", honestly_who_knows),
+        }
+    }
+
+    #[test]
+    fn the_one_where_two_sources_of_truth_is_zero_sources_of_truth() {
+        let config_path = write_test_config(
+            r#"
+            [source_config.File]
+            file_name = "input.json"
+
+            [sink_config.OpenObserve]
+            url = "http://localhost:5080"
+            stream = "logs"
+            password = "inline"
+            password_file = "/run/secrets/also-set"
+            "#,
+        );
+
+        let the_err = load_config(Some(&config_path)).expect_err("💀 both set should be rejected");
+        assert!(format!("{:#}", the_err).contains("Pick one"), "🎯 got: {:#}", the_err);
+    }
+
+    #[test]
+    fn the_one_where_the_extension_picks_the_dialect() {
+        assert_eq!(ConfigFormat::from_path(Path::new("kvx.toml")), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path(Path::new("kvx.YAML")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(Path::new("kvx.yml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(Path::new("kvx.json")), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path(Path::new("kvx")), ConfigFormat::Toml);
+    }
+
+    #[test]
+    fn the_one_where_infra_tooling_speaks_yaml() {
+        let config_path = write_test_config_as(
+            r#"
+runtime:
+  sink_parallelism: 2
+source_config:
+  File:
+    file_name: input.json
+sink_config:
+  File:
+    file_name: output.json
+    max_request_size_bytes: 4096
+"#,
+            ".yaml",
+        );
+
+        let app_config = load_config(Some(&config_path)).expect("💀 YAML config should parse");
+        assert_eq!(app_config.runtime.sink_parallelism, 2);
+        assert_eq!(app_config.sink_config.max_request_size_bytes(), 4096);
+    }
+
+    #[test]
+    fn the_one_where_a_script_hands_us_json() {
+        let config_path = write_test_config_as(
+            r#"{
+                "source_config": { "File": { "file_name": "input.json" } },
+                "sink_config": { "Elasticsearch": { "url": "http://localhost:9200", "index": "movies" } }
+            }"#,
+            ".json",
+        );
+
+        let app_config = load_config(Some(&config_path)).expect("💀 JSON config should parse");
+        match app_config.sink_config {
+            SinkConfig::Elasticsearch(es) => assert_eq!(es.index.as_deref(), Some("movies")),
+            honestly_who_knows => panic!("💀 Expected Elasticsearch sink, got {:?}", honestly_who_knows),
+        }
+    }
 }
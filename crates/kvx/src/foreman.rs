@@ -36,14 +36,18 @@ use crate::config::AppConfig;
 use crate::casts::PageToEntriesCaster;
 use crate::manifolds::ManifoldBackend;
 use crate::progress::{DrainMetrics, spawn_progress_reporter};
+use tokio_util::sync::CancellationToken;
 use crate::regulators::pressure_gauge::FlowKnob;
 use crate::regulators::Regulators;
 use crate::workers;
-use crate::workers::{FlowMasterConfig, Worker};
+use crate::workers::{FlowMasterConfig, ShutdownLever, Worker};
+use crate::warnings::WarningKind;
 use crate::GaugeReading;
 use anyhow::{Context, Result};
 use std::sync::Arc;
-use tracing::info;
+use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{debug, info};
 
 /// 📦 The Foreman: because even async tasks need someone hovering over them
 /// asking "is it done yet?" every 5 milliseconds.
@@ -54,13 +58,25 @@ pub struct Foreman {
     /// 🔧 The sacred scrolls of configuration, passed down from main()
     /// through the ancient ritual of .clone()
     app_config: AppConfig,
+    /// 🛑 Handed to the pumper — flip it and the pipeline drains gracefully
+    shutdown: ShutdownLever,
 }
 
 impl Foreman {
     /// 🚀 Birth of a Foreman. It's like a baby, but less crying.
     /// Actually no, there's plenty of crying. Mostly from the developer.
     pub fn new(app_config: AppConfig) -> Self {
-        Self { app_config }
+        Self {
+            app_config,
+            shutdown: ShutdownLever::default(),
+        }
+    }
+
+    /// 🛑 Share a shutdown lever with the pumper. Whoever holds the other end can
+    /// request a graceful drain at any time.
+    pub fn with_shutdown(mut self, shutdown: ShutdownLever) -> Self {
+        self.shutdown = shutdown;
+        self
     }
 }
 
@@ -85,7 +101,7 @@ impl Foreman {
     /// So it must drop its copies after distributing clones to the actual workers.
     ///
     /// 🔄 Shutdown cascade (all driven by implicit Sender drops, no `.close()` calls):
-    /// 1. Pumper finishes → its tx1 is dropped (only Sender for ch1) → ch1 closes
+    /// 1. Source queue runs dry → last pumper finishes → the last tx1 clone is dropped → ch1 closes
     /// 2. Joiners' recv_blocking() returns Err → flush remaining → joiner threads exit → tx2 clones dropped
     /// 3. Last joiner's tx2 dropped → all Senders for ch2 gone → ch2 closes
     /// 4. Drainers' recv().await returns Err → close sinks → exit → tx3 clones dropped
@@ -93,9 +109,10 @@ impl Foreman {
     ///
     /// "In the beginning there was main(). And main() said 'let there be workers.'
     ///  And the Foreman made it so. And it was... mostly okay." — Genesis 1:1 (Cargo edition) 🦆
+    #[allow(clippy::too_many_arguments)]
     pub async fn start_workers(
         &self,
-        source_backend: crate::backends::SourceBackend,
+        source_backends: Vec<crate::backends::SourceBackend>,
         sink_backends: Vec<crate::backends::SinkBackend>,
         caster: PageToEntriesCaster,
         manifold: ManifoldBackend,
@@ -104,8 +121,25 @@ impl Foreman {
         the_sink_max_request_size_bytes: usize,
         pipeline_name: String,
         total_expected_bytes: u64,
+        total_expected_docs: u64,
     ) -> Result<()> {
         let the_joiner_count = self.app_config.runtime.joiner_parallelism;
+        // 🚰 One pumper per source unless `source_parallelism` says fewer — the rest queue up
+        let the_pumper_count = match self.app_config.runtime.source_parallelism {
+            Some(the_limit) if the_limit > 0 => the_limit.min(source_backends.len()),
+            _ => source_backends.len(),
+        };
+
+        // 🧯 `max_errors` / `max_error_ratio` — checked now, so a bad ratio fails before anything moves
+        let the_error_budget =
+            workers::ErrorBudget::new(self.app_config.runtime.max_errors, self.app_config.runtime.max_error_ratio)?;
+
+        // 📼 One journal for every drainer, opened before anyone starts; whatever an earlier run left in it goes to the first one
+        let the_wal = self.app_config.drainer.wal_dir.as_deref().map(workers::WriteAheadLog::open).transpose()?;
+        let mut the_backlog = match the_wal {
+            Some(ref the_log) => the_log.pending()?,
+            None => Vec::new(),
+        };
 
         // 📬 ch1: pumper → joiners — carries raw feed Strings, MPMC
         // Like a conveyor belt at a sushi restaurant, but the sushi is JSON 🍣
@@ -142,11 +176,21 @@ impl Foreman {
                 );
                 Some((tx3, rx3, the_regulator))
             }
+            FlowMasterConfig::Adaptive(adaptive_config) => {
+                let (tx3, rx3) = async_channel::bounded::<GaugeReading>(256);
+                let the_regulator = Regulators::from_adaptive_config(
+                    adaptive_config,
+                    the_sink_max_request_size_bytes,
+                );
+                Some((tx3, rx3, the_regulator))
+            }
             FlowMasterConfig::Static(_) => None,
         };
 
         info!(
-            "🏗️ Foreman assembling pipeline: 1 pumper → {} joiners → {} drainers{}",
+            "🏗️ Foreman assembling pipeline: {} pumper(s) for {} source(s) → {} joiners → {} drainers{}",
+            the_pumper_count,
+            source_backends.len(),
             the_joiner_count,
             sink_backends.len(),
             if the_gauge_channel.is_some() { " + FlowMaster" } else { "" }
@@ -164,7 +208,7 @@ impl Foreman {
         // a stale foreman handle prevents implicit closure → deadlock.
         //
         // We enforce this by:
-        //   - Moving tx1 directly into the pumper (no clone, no foreman copy)
+        //   - Handing tx1 clones to the pumpers, then dropping the foreman's copy
         //   - Dropping tx2, rx1, rx2, tx3 after distributing clones to workers
         //
         // The result: only workers hold channel handles. When workers exit,
@@ -173,6 +217,35 @@ impl Foreman {
         // Pure RAII. The borrow checker would shed a single, proud tear. 🦀
         // ═══════════════════════════════════════════════════════════════════
 
+        // 📊 Create shared drain metrics — joiners count docs, N drainers write, 1 reporter reads.
+        // Arc<DrainMetrics> is the FlowKnob pattern applied to progress reporting.
+        // No channels, no Mutex, no shutdown cascade — just atomics and vibes. 🧘
+        // 🚰 One lane per drainer too, so the progress display can give each its own row.
+        let the_drain_metrics = Arc::new(DrainMetrics::with_drainers(sink_backends.len()));
+
+        // 🧮 Byte budget across ch1 → joiner buffers → ch2. Each joiner can sit on up to one
+        // payload's worth while it waits for more pages, so a budget smaller than all of those
+        // would let the joiners hold it hostage. Raise it and say so.
+        let the_buffer_budget = self.app_config.runtime.max_buffered_bytes.map(|the_limit| {
+            let the_joiner_floor = the_joiner_count.max(1).saturating_mul(the_sink_max_request_size_bytes).saturating_add(1);
+            if the_limit < the_joiner_floor {
+                self.app_config.warnings.record(
+                    WarningKind::CoercedValue,
+                    format!(
+                        "runtime.max_buffered_bytes ({}) can't fit {} joiner buffer(s) of up to {} bytes — raised to {}",
+                        the_limit, the_joiner_count, the_sink_max_request_size_bytes, the_joiner_floor
+                    ),
+                );
+            }
+            workers::BufferBudget::new(the_limit.max(the_joiner_floor))
+        });
+
+        // ♻️ Payload buffers, recycled: joiners compose into them, drainers hand them back.
+        // Sized for everything that can be in flight — ch2, one per joiner, one per drainer.
+        let the_payload_pool = workers::PayloadPool::new(
+            self.app_config.runtime.joiner_to_drainer_capacity + the_joiner_count + sink_backends.len(),
+        );
+
         // 🧵 Spawn N joiners on dedicated OS threads (std::thread).
         // They do the CPU-heavy lifting: buffering raw feeds, casting, manifold join.
         // Each gets its own clone of rx1 and tx2.
@@ -185,7 +258,10 @@ impl Foreman {
                 caster.clone(),
                 manifold.clone(),
                 the_flow_knob.clone(),
-            );
+            )
+            .with_drain_metrics(the_drain_metrics.clone())
+            .with_buffer_budget(the_buffer_budget.clone())
+            .with_payload_pool(the_payload_pool.clone());
             the_joiner_thread_handles.push(joiner.start());
         }
 
@@ -199,23 +275,36 @@ impl Foreman {
         drop(tx2);
         drop(rx1);
 
-        // 📊 Create shared drain metrics — N drainers write, 1 reporter reads.
-        // Arc<DrainMetrics> is the FlowKnob pattern applied to progress reporting.
-        // No channels, no Mutex, no shutdown cascade — just atomics and vibes. 🧘
-        let the_drain_metrics = Arc::new(DrainMetrics::new());
+        // ✂️ Shared 413 ceiling — starts at the sink limit, only ever goes down.
+        // Drainers lower it (and the knob) on Payload Too Large; FlowMaster clamps to it.
+        let the_payload_ceiling: FlowKnob = Arc::new(AtomicUsize::new(the_sink_max_request_size_bytes));
+        let the_shrinker = workers::PayloadShrinker {
+            flow_knob: the_flow_knob.clone(),
+            ceiling: the_payload_ceiling.clone(),
+            warnings: self.app_config.warnings.clone(),
+        };
 
         // 🚰 Spawn N drainers on tokio — thin async relays from ch2 to sinks.
         // Each drainer gets its own sink, a clone of rx2, and optionally a clone of tx3.
+        // 🔌 One breaker for the whole pool — N drainers, one shared sense of "the sink is down".
+        let the_breaker =
+            workers::CircuitBreaker::from_config(&self.app_config.drainer.circuit_breaker, self.app_config.warnings.clone());
         let the_gauge_tx = the_gauge_channel.as_ref().map(|(tx, _, _)| tx.clone());
-        let mut the_async_worker_handles = Vec::with_capacity(sink_backends.len() + 2);
-        for sink_backend in sink_backends {
+        let mut the_async_worker_handles = Vec::with_capacity(sink_backends.len() + the_pumper_count + 1);
+        for (the_drainer_id, sink_backend) in sink_backends.into_iter().enumerate() {
             let drainer = workers::Drainer::new(
                 rx2.clone(),
                 sink_backend,
                 self.app_config.drainer.clone(),
                 the_gauge_tx.clone(),
                 the_drain_metrics.clone(),
-            );
+            )
+            .with_drainer_id(the_drainer_id)
+            .with_shrinker(the_shrinker.clone())
+            .with_circuit_breaker(the_breaker.clone())
+            .with_buffer_budget(the_buffer_budget.clone())
+            .with_payload_pool(the_payload_pool.clone())
+            .with_write_ahead_log(the_wal.clone(), std::mem::take(&mut the_backlog));
             the_async_worker_handles.push(drainer.start());
         }
 
@@ -231,25 +320,74 @@ impl Foreman {
         if let Some((tx3, rx3, the_regulator)) = the_gauge_channel {
             // 🗑️ Drop foreman's tx3 — only drainers should hold senders
             drop(tx3);
-            let the_flow_master = workers::FlowMaster::new(rx3, the_regulator, the_flow_knob.clone());
+            let the_flow_master = workers::FlowMaster::new(rx3, the_regulator, the_flow_knob.clone())
+                .with_ceiling(the_payload_ceiling.clone());
             the_async_worker_handles.push(the_flow_master.start());
         }
 
-        // 🚰 Spawn the pumper — gets tx1 by MOVE (not clone).
-        // tx1 is moved directly into the pumper, so no foreman copy exists.
-        // When the pumper's async task exits (EOF from source), tx1 drops,
-        // and since it's the ONLY Sender for ch1, ch1 closes implicitly.
+        // 🎟️ Every source gets in line up front; the foreman drops its end of the queue so
+        // a pumper finding it empty knows the reading is done.
+        let (the_queue_tx, the_source_queue) = async_channel::bounded(source_backends.len().max(1));
+        for source_backend in source_backends {
+            source_backend.report_to(&the_drain_metrics);
+            the_queue_tx
+                .send(source_backend)
+                .await
+                .map_err(|_| anyhow::anyhow!("💀 The source queue closed before the sources got in line"))?;
+        }
+        drop(the_queue_tx);
+
+        // 🚰 Spawn the pumpers — usually one, N for a sliced read or a file pattern.
+        // Each gets a tx1 clone and a place at the queue; the foreman drops its tx1 right after.
+        // When the last pumper's async task exits (queue empty), the last tx1 drops
+        // and ch1 closes implicitly. Pumpers that run out early just leave the party early.
         // No .close() call needed — RAII handles it. Like a self-closing door. 🚪
-        let pumper = workers::Pumper::new(tx1, source_backend);
-        the_async_worker_handles.push(pumper.start());
+        for _ in 0..the_pumper_count {
+            let pumper = workers::Pumper::new(tx1.clone(), the_source_queue.clone(), self.shutdown.clone())
+                .with_pause(self.app_config.pause.clone())
+                .with_buffer_budget(the_buffer_budget.clone());
+            the_async_worker_handles.push(pumper.start());
+        }
+        drop(the_source_queue);
+        drop(tx1);
+
+        // 🧯 The error budget watcher: too many failed docs → pull the shutdown lever, then fail the run.
+        // Failed = sink refusals + transform rejects; seen = docs cast + transform rejects (they never got cast).
+        let the_error_stop = CancellationToken::new();
+        let the_error_watcher = the_error_budget.map(|the_budget| {
+            let the_failed_docs = self.app_config.failed_docs.clone();
+            let the_transforms = caster.transforms().cloned().unwrap_or_default();
+            let the_metrics = the_drain_metrics.clone();
+            the_budget.watch(
+                move || {
+                    let the_rejected = the_transforms.failed_docs();
+                    (
+                        the_failed_docs.load(Ordering::Relaxed) + the_rejected,
+                        the_metrics.docs_processed.load(Ordering::Relaxed) + the_rejected,
+                    )
+                },
+                self.shutdown.clone(),
+                the_error_stop.clone(),
+            )
+        });
 
         // 📊 Spawn the progress reporter — a leaf display task that ticks every 500ms.
         // It reads DrainMetrics atomics, renders a comfy-table, and sleeps. Safe to abort.
         // Like a screensaver — decorative, informative, entirely expendable. 🖥️🦆
+        let the_progress_stop = CancellationToken::new();
+        // -- 📊 `progress` / `progress_interval_secs`: bar, plain lines, or nothing — subscribers hear every tick regardless
+        let mut the_progress = self.app_config.progress.clone();
+        the_progress.set_terminal_mode(
+            self.app_config.runtime.progress,
+            Duration::from_secs(self.app_config.runtime.progress_interval_secs.max(1)),
+        );
         let the_progress_reporter = spawn_progress_reporter(
             pipeline_name,
             the_drain_metrics.clone(),
             total_expected_bytes,
+            total_expected_docs,
+            the_progress,
+            the_progress_stop.clone(),
         );
 
         // ⏳ Wait for all async workers (pumper + drainers + optional FlowMaster).
@@ -258,10 +396,15 @@ impl Foreman {
         // So by the time join_all returns, everyone's done. 🏁
         let the_async_results = futures::future::join_all(the_async_worker_handles).await;
 
-        // 🗑️ Abort the progress reporter — all real workers are done, no more data to display.
-        // One final tick to show the end state, then goodnight. 🌙
-        the_progress_reporter.abort();
+        // 🗑️ Stop the progress reporter — all real workers are done, no more data to display.
+        // It does one final tick to show the end state (and tell subscribers), then goodnight. 🌙
+        the_progress_stop.cancel();
         let _ = the_progress_reporter.await;
+        the_error_stop.cancel();
+        let the_error_verdict = match the_error_watcher {
+            Some(the_watcher) => the_watcher.await?,
+            None => None,
+        };
 
         for result in the_async_results {
             // 🤯 result?? — outer `?` unwraps JoinHandle, inner `?` unwraps the work
@@ -290,6 +433,14 @@ impl Foreman {
                 ))?;
         }
 
+        let (the_reused, the_fresh) = the_payload_pool.stats();
+        debug!("♻️ Payload buffers: {} reused from the pool, {} freshly allocated", the_reused, the_fresh);
+
+        // 🧯 Everyone drained cleanly — but too much of what they carried was refused
+        if let Some(the_verdict) = the_error_verdict {
+            anyhow::bail!("💀 Error budget exceeded: {}", the_verdict);
+        }
+
         Ok(())
     }
 }
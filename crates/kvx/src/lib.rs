@@ -20,40 +20,128 @@ pub mod foreman;
 pub mod casts;
 pub mod regulators;
 pub mod workers;
+pub mod warnings;
+pub mod notify;
+pub mod pipeline;
+pub mod transforms;
 
 use crate::config::AppConfig;
-use crate::backends::elasticsearch::{ElasticsearchSink, ElasticsearchSource};
-use crate::backends::file::{FileSink, FileSource};
+use crate::backends::elasticsearch::{BlueGreen, ElasticsearchSink, ElasticsearchSource, Watermark, check_for_duplicates, copy_index_from_source, create_index};
+use crate::backends::file::{FileSink, FileSource, FileSourceConfig, pattern};
+use crate::backends::generator::GeneratorSource;
+use crate::backends::bigquery::BigQuerySource;
+use crate::backends::redis::RedisSource;
+use crate::backends::rest::RestSource;
 use crate::backends::in_mem::{InMemorySink, InMemorySource};
 use crate::backends::meilisearch::MeilisearchSink;
+use crate::backends::null::NullSink;
 use crate::backends::open_observe::OpenObserveSink;
+use crate::backends::solr::SolrSink;
 use crate::backends::{SinkBackend, SourceBackend};
 use crate::foreman::Foreman;
 use crate::config::{RuntimeConfig, SinkConfig, SourceConfig};
 use crate::manifolds::ManifoldBackend;
 use crate::casts::PageToEntriesCaster;
 use crate::regulators::pressure_gauge::FlowKnob;
-use crate::workers::FlowMasterConfig;
+use crate::warnings::WarningKind;
+use crate::workers::{FlowMasterConfig, ShutdownLever};
 use anyhow::{Context, Result};
+use bytes::Bytes;
 use std::ops::Deref;
 use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
-use std::time::SystemTime;
-use tracing::info;
+use std::time::{Instant, SystemTime};
+use tracing::{info, warn};
+
+pub use tokio_util::sync::CancellationToken;
 
 
 /// 🚀 The grand entry point. The big kahuna. The main event.
+///
+/// Runs until the source is exhausted. Need to stop early? See `run_with_shutdown`.
 pub async fn run(app_config: AppConfig) -> Result<()> {
+    run_with_shutdown(app_config, std::future::pending()).await
+}
+
+/// ✂️ Run until done or until `cancel` is cancelled — the token flavour of `run_with_shutdown`.
+///
+/// Hand a clone of the token to whatever owns the job (an HTTP handler, a job tracker,
+/// a parent token's `child_token()`), call `.cancel()` from anywhere, and this future
+/// resolves once the workers have drained and the sinks have flushed. Cancelling after
+/// the run already finished is a harmless no-op.
+pub async fn run_with_cancellation(app_config: AppConfig, cancel: CancellationToken) -> Result<()> {
+    run_with_shutdown(app_config, cancel.cancelled_owned()).await
+}
+
+/// 🛑 Same main event, but with an exit door the embedder controls.
+///
+/// When `shutdown_signal` resolves, the pumper stops pulling new feeds and the usual RAII
+/// cascade drains everything already in flight: joiners flush, drainers finish, sinks close.
+/// No process signals required — a server can wire this to its own lifecycle
+/// (a oneshot, a `Notify`, `ctrl_c()`, whatever resolves to `()`).
+///
+/// 🧠 Knowledge graph: the signal is watched by a tiny task that pulls a `ShutdownLever`
+/// (Arc<AtomicBool>) shared with the Pumper. The pumper checks it between pumps, so a source
+/// is never cancelled mid-read. If the pipeline finishes first, the watcher is aborted. 🦆
+pub async fn run_with_shutdown<S>(mut app_config: AppConfig, shutdown_signal: S) -> Result<()>
+where
+    S: Future<Output = ()> + Send + 'static,
+{
+    // 📣 Whatever happens next gets reported — the last progress event carries the totals
+    let the_started = Instant::now();
+    let the_last_progress = Arc::new(notify::LastProgress::default());
+    app_config.progress.subscribe(the_last_progress.clone());
+    let the_notify = app_config.notify.clone();
+    let the_dead_letter_file = match &app_config.sink_config {
+        SinkConfig::Elasticsearch(es) => es.dead_letter_file.clone(),
+        _ => None,
+    };
+    let the_warnings = app_config.warnings.clone();
+    let the_shutdown_lever = ShutdownLever::default();
+
+    let the_outcome = run_pipeline(app_config, shutdown_signal, the_shutdown_lever.clone()).await;
+
+    let the_stopped_early = the_shutdown_lever.load(std::sync::atomic::Ordering::Relaxed);
+    let the_last_event = the_last_progress.take();
+    let the_report = |the_outcome: &Result<()>| {
+        notify::RunReport::new(the_outcome, the_stopped_early, the_last_event.clone(), the_started.elapsed(), &the_warnings)
+            .with_dead_letter_file(the_dead_letter_file.clone())
+    };
+    // 🪝 on_success / on_failure first — a broken follow-up step is the run's failure too
+    let the_outcome = match notify::run_hook(&the_notify, &the_report(&the_outcome)).await {
+        Ok(()) => the_outcome,
+        Err(the_hook_error) => Err(the_hook_error),
+    };
+    notify::announce(&the_notify, &the_report(&the_outcome)).await;
+    the_outcome
+}
+
+/// 🏗️ The run itself: build the backends, start the workers, wait, verify, clean up.
+/// `the_shutdown_lever` is pulled when `shutdown_signal` fires.
+async fn run_pipeline<S>(mut app_config: AppConfig, shutdown_signal: S, the_shutdown_lever: ShutdownLever) -> Result<()>
+where
+    S: Future<Output = ()> + Send + 'static,
+{
     let start_time = SystemTime::now();
     info!("🚀 KRAVEX IS BLASTING OFF — hold onto your indices, we are MIGRATING, baby!");
 
+    // ⚠️ Sniff the config for close calls before we commit to hours of work
+    app_config.collect_warnings();
+
+    // ⏭️ Narrow the read to what changed since the last good run (resume_after), before any source exists
+    let the_watermark = prepare_source(&mut app_config).await?;
+
     // Build the backends from config
     // Note: We currently don't have implementations, so this will panic or fail when we add them.
     // We are passing an unimplemented mock mapping for now.
-    let source_backend = from_source_config(&app_config)
+    let source_backends = from_source_configs(&app_config)
         .await
         .context("Failed to create source backend")?;
 
+    // 📐 Build the target index before the sinks go looking for it
+    // -- 🔵🟢 (blue/green: the staging index, which the sink config now points at)
+    let the_blue_green = prepare_target(&mut app_config).await?;
+
     let sink_parallelism = app_config.runtime.sink_parallelism;
     let mut sink_backends = Vec::with_capacity(sink_parallelism);
     for _ in 0..sink_parallelism {
@@ -64,30 +152,43 @@ pub async fn run(app_config: AppConfig) -> Result<()> {
         );
     }
 
-    // 🔄 Resolve the caster from source/sink config pair.
+    // 🔄 Resolve the caster from source/sink config pair, then hand it the `[[transforms]]`.
     // 🧠 Knowledge graph: DocumentCaster::from_configs() matches (source, sink) → caster.
     // File→ES = NdJsonToBulk, File→File = Passthrough, InMemory→InMemory = Passthrough, etc.
-    let caster =
-        PageToEntriesCaster::from_configs(&app_config.source_config, &app_config.sink_config);
+    let caster = PageToEntriesCaster::try_from_app_config(&app_config)?;
+    // -- 🧾 keep a handle on the chain: its reject tallies are read after the run
+    let the_transforms = caster.transforms().cloned().unwrap_or_default();
 
     // 🎼 Resolve the manifold from sink config.
     // 🧠 ES/File → NdjsonManifold, InMemory → JsonArrayManifold.
     // The Manifold casts raw feeds AND joins them into wire format. Two birds, one Cow. 🐄
-    let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config);
+    let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
 
     // 📏 Extract max request size from sink config — the hard ceiling for payload size.
-    let max_request_size_bytes = app_config.sink_config.max_request_size_bytes();
+    // 🎚️ Omitted knobs resolve to the backend's own profile, so echo what we landed on —
+    // "which default did it pick?" should never require reading source code at 3am.
+    let the_source_tuning = app_config.source_config.tuning();
+    let the_sink_tuning = app_config.sink_config.tuning();
+    info!(
+        "🎚️ Source tuning: max_batch_size_docs={}, max_batch_size_bytes={} — sink tuning: max_request_size_bytes={}",
+        the_source_tuning.max_batch_size_docs,
+        the_source_tuning.max_batch_size_bytes,
+        the_sink_tuning.max_request_size_bytes
+    );
+    let max_request_size_bytes = the_sink_tuning.max_request_size_bytes;
 
     // 🔧 Create the FlowKnob — shared atomic valve between FlowMaster and joiners.
     // 🧠 FlowMasterConfig determines the initial value:
     //   - Static: fixed at output_bytes, never changes (no FlowMaster spawned)
     //   - Latency: starts at initial_output_bytes, PID adjusts based on drain latency
     //   - CPU: starts at initial_output_bytes, PID adjusts based on cluster CPU pressure
+    //   - Adaptive: starts at initial_output_bytes, AIMD from drain latency and 429s
     let the_initial_flow = match &app_config.flow_master {
         FlowMasterConfig::Static(cfg) => cfg.output_bytes,
         FlowMasterConfig::Latency(cfg) => cfg.initial_output_bytes,
         FlowMasterConfig::CPU(cfg) => cfg.initial_output_bytes,
         FlowMasterConfig::Throughput(cfg) => cfg.initial_output_bytes,
+        FlowMasterConfig::Adaptive(cfg) => cfg.initial_output_bytes,
     };
     let the_flow_knob: FlowKnob = Arc::new(AtomicUsize::new(the_initial_flow));
 
@@ -99,22 +200,72 @@ pub async fn run(app_config: AppConfig) -> Result<()> {
 
     // 📏 Extract pipeline name and total_expected_bytes for progress reporting.
     // File sources know their size upfront; everything else is a mystery. 🎭
-    let (pipeline_name, total_expected_bytes) = match &source_backend {
-        SourceBackend::File(fs) => (fs.source_config.file_name.clone(), fs.file_size),
+    let (pipeline_name, total_expected_bytes) = match &source_backends[0] {
+        // -- 🗂️ a pattern is many files: name the run after the pattern, expect all their bytes
+        SourceBackend::File(_) => match &app_config.source_config {
+            SourceConfig::File(c) => (
+                c.file_name.clone(),
+                source_backends.iter().map(|s| if let SourceBackend::File(fs) = s { fs.file_size } else { 0 }).sum(),
+            ),
+            // -- 🌐 a URL is one download: name the run after it, expect what Content-Length said
+            SourceConfig::Url(c) => (
+                c.url.clone(),
+                source_backends.iter().map(|s| if let SourceBackend::File(fs) = s { fs.file_size } else { 0 }).sum(),
+            ),
+            _ => ("file".to_string(), 0),
+        },
         SourceBackend::Elasticsearch(_) => ("elasticsearch".to_string(), 0),
+        // -- 🏭 the generator knows exactly what it's going to make
+        SourceBackend::Generator(the_generator) => ("generator".to_string(), the_generator.expected_bytes()),
+        SourceBackend::Rest(_) => ("rest".to_string(), 0),
+        SourceBackend::Redis(_) => ("redis".to_string(), 0),
+        SourceBackend::BigQuery(_) => ("bigquery".to_string(), 0),
         SourceBackend::InMemory(_) => ("in-memory".to_string(), 0),
+        SourceBackend::Custom(_) => match &app_config.source_config {
+            SourceConfig::Custom(c) => (c.name.clone(), 0),
+            _ => ("custom".to_string(), 0),
+        },
+    };
+
+    // 🔢 Elasticsearch can't tell us its size in bytes, but it can count. One `_count` for the
+    // whole read (not per slice) — and if it fails, the bar just spins like it used to.
+    let total_expected_docs = match &source_backends[0] {
+        SourceBackend::Elasticsearch(es) => match es.count_docs().await {
+            Ok(the_count) => {
+                info!("🔢 Source has {} docs to migrate", the_count);
+                the_count
+            }
+            Err(the_shrug) => {
+                warn!("⚠️ Couldn't count the source docs, progress will have no ETA: {:#}", the_shrug);
+                0
+            }
+        },
+        SourceBackend::Generator(the_generator) => the_generator.expected_docs(),
+        _ => 0,
     };
 
-    // 🔍 Override pipeline name if sink is Meilisearch — so the progress bar says "→ meilisearch"
+    // 🔍 Override pipeline name if sink is Meilisearch or Solr — so the progress bar says where docs land
     let pipeline_name = match &app_config.sink_config {
         SinkConfig::Meilisearch(ms) => format!("{} → meilisearch/{}", pipeline_name, ms.index_uid),
+        SinkConfig::Solr(solr) => format!("{} → solr/{}", pipeline_name, solr.collection),
         _ => pipeline_name,
     };
 
-    let foreman = Foreman::new(app_config.clone());
-    foreman
+    // 🛑 Watch the embedder's shutdown signal — when it fires, pull the lever
+    let the_shutdown_watcher = {
+        let the_lever = the_shutdown_lever.clone();
+        workers::spawn_named("shutdown-watcher", async move {
+            shutdown_signal.await;
+            info!("🛑 Shutdown signal received — finishing in-flight work, then exiting gracefully");
+            the_lever.store(true, std::sync::atomic::Ordering::Relaxed);
+        })
+    };
+
+    let the_lever_was_pulled = the_shutdown_lever.clone();
+    let foreman = Foreman::new(app_config.clone()).with_shutdown(the_shutdown_lever);
+    let the_outcome = foreman
         .start_workers(
-            source_backend,
+            source_backends,
             sink_backends,
             caster,
             manifold,
@@ -123,8 +274,49 @@ pub async fn run(app_config: AppConfig) -> Result<()> {
             max_request_size_bytes,
             pipeline_name,
             total_expected_bytes,
+            total_expected_docs,
         )
-        .await?;
+        .await;
+    // -- 🧹 pipeline's done either way — nobody left to tell about a shutdown
+    the_shutdown_watcher.abort();
+
+    // 👯 Post-migration verification — only worth asking if the data actually landed
+    if the_outcome.is_ok() {
+        verify_target(&app_config).await;
+    }
+
+    // 🔵🟢 Swap the alias onto a complete staging index — or bin an incomplete one
+    let the_outcome = match the_blue_green {
+        Some(ref the_swap) => {
+            let the_run_finished = the_outcome.is_ok() && !the_lever_was_pulled.load(std::sync::atomic::Ordering::Relaxed);
+            // -- 🕳️ docs the transforms dropped or refused and the sink rejected were never meant to land
+            let the_left_behind =
+                the_transforms.dropped_docs() + app_config.failed_docs.load(std::sync::atomic::Ordering::Relaxed);
+            let the_swap_outcome =
+                finish_blue_green(&app_config, the_swap, the_run_finished, total_expected_docs, the_left_behind).await;
+            the_outcome.and(the_swap_outcome)
+        }
+        None => the_outcome,
+    };
+
+    // ⏭️ Move the resume_after mark — only a run that finished has read everything up to it
+    let the_outcome = match the_watermark {
+        Some(ref the_mark) if the_outcome.is_ok() && !the_lever_was_pulled.load(std::sync::atomic::Ordering::Relaxed) => {
+            the_mark.save().await.map(|_| ()).context(
+                "💀 The documents landed, but the resume_after mark didn't move — the next run reads these changes again",
+            )
+        }
+        _ => the_outcome,
+    };
+
+    the_transforms.record_warnings(&app_config.warnings);
+
+    // ⚠️ Four hours of scrolled logs later — here's everything that went slightly sideways.
+    // Printed win or lose, because a failed run's warnings are often the plot twist. 🎬
+    if !app_config.warnings.is_empty() {
+        warn!("{}", app_config.warnings.summary());
+    }
+    the_outcome?;
 
     info!(
         "🎉 MIGRATION COMPLETE! Took: {:#?} — not bad for a Rust crate that was \"almost done\" six sprints ago 🦆",
@@ -133,14 +325,164 @@ pub async fn run(app_config: AppConfig) -> Result<()> {
     Ok(())
 }
 
+/// 🩺 Dry-run the config: resolve everything `run()` would resolve and knock on every door,
+/// but move zero documents.
+///
+/// Checks, in order:
+/// 1. Close-call limits (recorded as warnings, not failures)
+/// 2. Source: finds the file(s), or sends one authenticated request — `GET /` plus `_count` for
+///    Elasticsearch, the first page for REST, a dry run for BigQuery (and reads any `resume_after` mark)
+/// 3. Sink: connectivity + auth + index checks. File sinks are NOT created (that would
+///    truncate them) — we only check the parent directory exists.
+/// 4. Caster + manifold resolve for this (source, sink) pair
+///
+/// 🧠 Knowledge graph: backs the `kvx validate` CLI subcommand. Misconfiguration used to
+/// surface twenty minutes into a run. Now it surfaces in two seconds. Progress. 🦆
+pub async fn validate(app_config: &AppConfig) -> Result<()> {
+    info!("🩺 Validating configuration — no documents will be harmed in the making of this check");
+    app_config.collect_warnings();
+
+    let the_sources = from_source_configs(app_config)
+        .await
+        .context("💀 Source check failed — the pipeline would have died at the first pump")?;
+    // -- 🩺 slices and file lists share one endpoint and one set of credentials — one knock is enough
+    if let Some(the_source) = the_sources.first() {
+        the_source.probe().await.context("💀 Source check failed — the source didn't take our credentials or our URL")?;
+    }
+    if let SourceConfig::Elasticsearch(es_cfg) = &app_config.source_config {
+        // -- ⏭️ reads the state file and takes the max — the mark only moves in run()
+        Watermark::plan(es_cfg).await.context("💀 resume_after check failed")?;
+    }
+    info!("✅ Source reachable");
+
+    match &app_config.sink_config {
+        // 📂 Creating the sink file would truncate it. Validation is look-don't-touch.
+        SinkConfig::File(file_cfg) => {
+            let the_path = std::path::Path::new(&file_cfg.file_name);
+            let the_parent = match the_path.parent() {
+                Some(p) if !p.as_os_str().is_empty() => p,
+                _ => std::path::Path::new("."),
+            };
+            if !the_parent.is_dir() {
+                anyhow::bail!(
+                    "💀 Sink file '{}' lives in a directory that doesn't exist ('{}'). \
+                     The file would have nowhere to be born. Create the directory first.",
+                    file_cfg.file_name,
+                    the_parent.display()
+                );
+            }
+            if the_path.is_dir() {
+                anyhow::bail!(
+                    "💀 Sink file '{}' is a directory. We can write into files, not into folders' feelings.",
+                    file_cfg.file_name
+                );
+            }
+        }
+        _ => {
+            let mut the_sink = from_sink_config(app_config)
+                .await
+                .context("💀 Sink check failed — connectivity, auth, or index lookup didn't pass")?;
+            // -- 🧹 close what we opened; nothing was drained so nothing should be flushed
+            crate::backends::Sink::close(&mut the_sink).await?;
+        }
+    }
+    info!("✅ Sink reachable");
+
+    let the_caster = PageToEntriesCaster::try_from_app_config(app_config)?;
+    let the_manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
+    let the_source_tuning = app_config.source_config.tuning();
+    let the_sink_tuning = app_config.sink_config.tuning();
+    info!("✅ Caster: {:?} — Manifold: {:?}", the_caster, the_manifold);
+    info!(
+        "🎚️ Source tuning: max_batch_size_docs={}, max_batch_size_bytes={} — sink tuning: max_request_size_bytes={}",
+        the_source_tuning.max_batch_size_docs,
+        the_source_tuning.max_batch_size_bytes,
+        the_sink_tuning.max_request_size_bytes
+    );
+
+    if !app_config.warnings.is_empty() {
+        warn!("{}", app_config.warnings.summary());
+    }
+    Ok(())
+}
+
+/// 🍕 One source per reader. Almost always one; a sliced ES read gets one per slice and a
+/// file pattern one per matching file. The Foreman lines them up for its pumpers.
+async fn from_source_configs(config: &AppConfig) -> Result<Vec<SourceBackend>> {
+    if let SourceConfig::File(file_cfg) = &config.source_config
+        && pattern::is_pattern(&file_cfg.file_name)
+    {
+        if file_cfg.start_offset_bytes > 0 || file_cfg.offset_file.is_some() {
+            anyhow::bail!(
+                "💀 start_offset_bytes and offset_file are a position in one file, and '{}' is a pattern.",
+                file_cfg.file_name
+            );
+        }
+        let the_files = pattern::expand(&file_cfg.file_name)?;
+        info!("🗂️ '{}' matched {} files, one source each", file_cfg.file_name, the_files.len());
+        let mut the_sources = Vec::with_capacity(the_files.len());
+        for the_file in the_files {
+            let src = FileSource::new(FileSourceConfig { file_name: the_file, ..file_cfg.clone() })
+                .await?
+                .with_warnings(config.warnings.clone());
+            the_sources.push(SourceBackend::File(src));
+        }
+        return Ok(the_sources);
+    }
+    if let SourceConfig::Elasticsearch(es_cfg) = &config.source_config
+        && let Some(the_slice_count) = es_cfg.slices.filter(|&n| n > 1)
+    {
+        info!("🍕 Sliced read: {} source workers, one per slice", the_slice_count);
+        let mut the_sources = Vec::with_capacity(the_slice_count);
+        let mut the_window_ledger = None;
+        for the_slice_id in 0..the_slice_count {
+            let mut src = ElasticsearchSource::new(es_cfg.clone())
+                .await?
+                .with_slice(the_slice_id, the_slice_count)
+                .with_versions(wants_versions(config));
+            // -- 🗓️ one ledger for every slice: a time window is done once all of them have read it
+            match the_window_ledger {
+                Some(ref the_ledger) => src = src.with_window_ledger(Arc::clone(the_ledger)),
+                None => the_window_ledger = src.window_ledger().cloned(),
+            }
+            the_sources.push(SourceBackend::Elasticsearch(src));
+        }
+        return Ok(the_sources);
+    }
+    Ok(vec![from_source_config(config).await?])
+}
+
+/// 🔢 ES only returns `_version` on a hit when the search asks — and only an `id_export`
+/// with `include_version` writes it anywhere.
+fn wants_versions(config: &AppConfig) -> bool {
+    matches!(&config.sink_config, SinkConfig::File(f) if f.id_export.as_ref().is_some_and(|the_export| the_export.include_version))
+}
+
 async fn from_source_config(config: &AppConfig) -> Result<SourceBackend> {
     match &config.source_config {
         // -- 📂 The File arm: ancient, reliable, and smells faintly of 2003.
         // -- Like a filing cabinet that somehow learned async/await.
         SourceConfig::File(file_cfg) => {
-            let src = FileSource::new(file_cfg.clone()).await?;
+            let src = FileSource::new(file_cfg.clone())
+                .await?
+                .with_warnings(config.warnings.clone());
             Ok(SourceBackend::File(src))
         }
+        // -- 🌐 The Url arm: the File arm's engine with a download where the disk was
+        SourceConfig::Url(url_cfg) => {
+            let src = FileSource::from_url(url_cfg.clone())
+                .await?
+                .with_warnings(config.warnings.clone());
+            Ok(SourceBackend::File(src))
+        }
+        // -- 🏭 The Generator arm: docs from thin air, for benchmarks that don't want a corpus
+        SourceConfig::Generator(gen_cfg) => Ok(SourceBackend::Generator(GeneratorSource::new(gen_cfg.clone()))),
+        // -- 🌍 The Rest arm: somebody else's API, one page at a time
+        SourceConfig::Rest(rest_cfg) => Ok(SourceBackend::Rest(RestSource::new(rest_cfg.clone())?)),
+        // -- 🟥 The Redis arm: a cache that got promoted to dataset, scanned a batch of keys at a time
+        SourceConfig::Redis(redis_cfg) => Ok(SourceBackend::Redis(RedisSource::new(redis_cfg.clone()).await?)),
+        // -- 📊 The BigQuery arm: the warehouse, one result page at a time
+        SourceConfig::BigQuery(bq_cfg) => Ok(SourceBackend::BigQuery(BigQuerySource::new(bq_cfg.clone())?)),
         // -- 🧠 The InMemory arm: blazing fast, lives and dies with the process.
         // -- No persistence. No regrets. No disk. Very YOLO.
         SourceConfig::InMemory(_) => {
@@ -150,9 +492,149 @@ async fn from_source_config(config: &AppConfig) -> Result<SourceBackend> {
         // -- 📡 The Elasticsearch arm: HTTP calls, JSON parsing, and the constant
         // -- fear of a 429 response that ruins your Thursday afternoon.
         SourceConfig::Elasticsearch(es_cfg) => {
-            let src = ElasticsearchSource::new(es_cfg.clone()).await?;
+            let src = ElasticsearchSource::new(es_cfg.clone()).await?.with_versions(wants_versions(config));
             Ok(SourceBackend::Elasticsearch(src))
         }
+        // -- 🧩 Custom source: the embedder brought their own. We just press the button.
+        SourceConfig::Custom(custom_cfg) => {
+            let src = (custom_cfg.factory)()
+                .with_context(|| format!("💀 Custom source '{}' factory failed", custom_cfg.name))?;
+            Ok(SourceBackend::Custom(src))
+        }
+    }
+}
+
+/// ⏭️ Pre-read setup on the source — runs once, before any source is built. With
+/// `resume_after`, the source `query` is narrowed to this run's watermark range, and the
+/// plan is handed back so a finished run can move the mark.
+async fn prepare_source(config: &mut AppConfig) -> Result<Option<Watermark>> {
+    let SourceConfig::Elasticsearch(es_cfg) = &mut config.source_config else {
+        return Ok(None);
+    };
+    let Some(the_mark) = Watermark::plan(es_cfg).await? else {
+        return Ok(None);
+    };
+    es_cfg.query = the_mark.narrow(es_cfg.query_clause()?);
+    Ok(Some(the_mark))
+}
+
+/// 📐 Pre-migration setup on the target — runs once, before any sink is built.
+/// With `blue_green`, the sink `index` is re-pointed at a fresh staging index first, and the
+/// plan is handed back for `finish_blue_green`.
+async fn prepare_target(config: &mut AppConfig) -> Result<Option<BlueGreen>> {
+    let mut the_blue_green = None;
+    if let SinkConfig::Elasticsearch(es_cfg) = &mut config.sink_config
+        && es_cfg.blue_green
+    {
+        let the_plan = BlueGreen::plan(es_cfg, chrono::Utc::now()).await?;
+        info!("🔵🟢 Blue/green: loading '{}', alias '{}' moves over when the run succeeds", the_plan.staging_index, the_plan.alias);
+        es_cfg.index = Some(the_plan.staging_index.clone());
+        the_blue_green = Some(the_plan);
+    }
+    if let SinkConfig::Elasticsearch(es_cfg) = &config.sink_config
+        && let Some(the_copy) = &es_cfg.copy_index
+    {
+        let SourceConfig::Elasticsearch(the_source) = &config.source_config else {
+            anyhow::bail!("💀 copy_index copies an Elasticsearch source index — this source isn't one.");
+        };
+        info!(
+            "📐 Copying mapping and settings from source '{}' to target '{}'",
+            the_source.index.as_deref().unwrap_or("?"),
+            es_cfg.index.as_deref().unwrap_or("?")
+        );
+        copy_index_from_source(the_source, es_cfg, the_copy)
+            .await
+            .context("💀 Couldn't copy the source index's mapping and settings to the target")?;
+    } else if let SinkConfig::Elasticsearch(es_cfg) = &config.sink_config
+        && (es_cfg.create_index_if_missing || es_cfg.blue_green)
+    {
+        // -- 🏗️ once here, not in ElasticsearchSink::new — N parallel sinks would race the PUT
+        create_index(es_cfg, &es_cfg.inline_index_body())
+            .await
+            .context("💀 create_index_if_missing couldn't create the target index")?;
+    }
+    Ok(the_blue_green)
+}
+
+/// 🔵🟢 End of a blue/green run: promote the staging index if the run finished (and its doc
+/// count checks out, when asked), otherwise delete it. The alias only ever sees a complete index.
+/// `left_behind` docs were dropped or rejected on the way, so the count expects that many fewer.
+async fn finish_blue_green(config: &AppConfig, swap: &BlueGreen, run_finished: bool, expected_docs: u64, left_behind: u64) -> Result<()> {
+    let SinkConfig::Elasticsearch(es_cfg) = &config.sink_config else {
+        return Ok(());
+    };
+    let the_verdict = if !run_finished {
+        Err(anyhow::anyhow!("the run didn't finish"))
+    } else if es_cfg.blue_green_verify_count && expected_docs == 0 {
+        config.warnings.record(
+            WarningKind::DataQuality,
+            "blue_green_verify_count skipped: this source can't say how many docs to expect".to_string(),
+        );
+        Ok(())
+    } else if es_cfg.blue_green_verify_count {
+        let the_expected = expected_docs.saturating_sub(left_behind);
+        match swap.count(es_cfg).await {
+            Ok(the_count) if the_count == the_expected => {
+                info!(
+                    "🔢 Staging index '{}' holds all {} docs ({} from the source, {} dropped or rejected on the way)",
+                    swap.staging_index, the_count, expected_docs, left_behind
+                );
+                Ok(())
+            }
+            Ok(the_count) => Err(anyhow::anyhow!(
+                "staging index '{}' holds {} docs, expected {} (the source counted {}, {} were dropped or rejected on the way)",
+                swap.staging_index,
+                the_count,
+                the_expected,
+                expected_docs,
+                left_behind
+            )),
+            Err(the_failure) => Err(the_failure),
+        }
+    } else {
+        Ok(())
+    };
+    match the_verdict {
+        Ok(()) => {
+            // -- 🧱 a failed swap leaves the staging index in place: it's complete, and the fix is one `_aliases` call
+            swap.promote(es_cfg).await.with_context(|| {
+                format!("💀 Blue/green: '{}' is loaded, but the alias swap failed — it was left in place", swap.staging_index)
+            })?;
+            Ok(())
+        }
+        Err(the_reason) => {
+            warn!("🔵🟢 Blue/green: not swapping ({:#}) — deleting '{}'", the_reason, swap.staging_index);
+            if let Err(the_failure) = swap.discard(es_cfg).await {
+                config.warnings.record(
+                    WarningKind::DataQuality,
+                    format!("Blue/green staging index '{}' could not be deleted: {:#}", swap.staging_index, the_failure),
+                );
+            }
+            if run_finished {
+                anyhow::bail!("💀 Blue/green verification failed: {:#}", the_reason);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// 👯 Post-migration checks against the target. Findings (and check failures) become run
+/// warnings rather than errors — the data already landed; failing now would just hide it.
+async fn verify_target(config: &AppConfig) {
+    if let SinkConfig::Elasticsearch(es_cfg) = &config.sink_config
+        && let Some(the_check) = &es_cfg.duplicate_check
+    {
+        info!("👯 Checking '{}' for duplicate '{}' values", es_cfg.index.as_deref().unwrap_or("?"), the_check.field);
+        match check_for_duplicates(es_cfg, the_check).await {
+            Ok(the_report) if the_report.offenders.is_empty() => {
+                info!("✅ No duplicate '{}' values found — everyone showed up exactly once", the_check.field);
+            }
+            Ok(the_report) => config.warnings.record(WarningKind::DataQuality, the_report.summary()),
+            Err(the_failure) => config.warnings.record(
+                WarningKind::DataQuality,
+                format!("Duplicate check could not run: {:#}", the_failure),
+            ),
+        }
     }
 }
 
@@ -173,7 +655,10 @@ async fn from_sink_config(config: &AppConfig) -> Result<SinkBackend> {
         // -- 📡 Elasticsearch sink: data goes in at the speed of HTTP, which is to say,
         // -- "fast enough until it isn't." May your bulk indexing be ever green. 🌿
         SinkConfig::Elasticsearch(es_cfg) => {
-            let sink = ElasticsearchSink::new(es_cfg.clone()).await?;
+            let sink = ElasticsearchSink::new(es_cfg.clone())
+                .await?
+                .with_warnings(config.warnings.clone())
+                .with_failed_docs(config.failed_docs.clone());
             Ok(SinkBackend::Elasticsearch(sink))
         }
         // -- 🔍 Meilisearch sink: JSON arrays in, async tasks out. Like DoorDash but for search indices.
@@ -189,6 +674,19 @@ async fn from_sink_config(config: &AppConfig) -> Result<SinkBackend> {
             let sink = OpenObserveSink::new(oo_cfg.clone()).await?;
             Ok(SinkBackend::OpenObserve(sink))
         }
+        // -- ☀️ Solr sink: JSON arrays into `/update/json/docs`, one hard commit on the way out
+        SinkConfig::Solr(solr_cfg) => {
+            let sink = SolrSink::new(solr_cfg.clone()).await?;
+            Ok(SinkBackend::Solr(sink))
+        }
+        // -- 🕳️ Null sink: a black hole with a Sink impl. Benchmarks only, hopefully.
+        SinkConfig::Null(_) => Ok(SinkBackend::Null(NullSink::new())),
+        // -- 🧩 Custom sink: one fresh instance per drainer, courtesy of the embedder's factory
+        SinkConfig::Custom(custom_cfg) => {
+            let sink = (custom_cfg.factory)()
+                .with_context(|| format!("💀 Custom sink '{}' factory failed", custom_cfg.name))?;
+            Ok(SinkBackend::Custom(sink))
+        }
     }
 }
 
@@ -223,27 +721,135 @@ impl From<String> for Page {
     }
 }
 
+// 📍 Where a page's docs sit in the source — so the doc that breaks a caster can be named by
+// file, line and byte offset instead of "somewhere in these 8 MB".
+//
+// 🧠 A page is its docs `\n`-joined, and a well-behaved file is the same thing on disk: doc k+1
+// starts one line and `len + 1` bytes after doc k. Only the docs where that guess is wrong (the
+// first one, the one after a blank line, every `\r\n` line) are written down, so a clean page
+// costs one anchor no matter how many docs it holds.
+#[derive(Debug, Clone, Default)]
+pub struct PageOrigin {
+    /// 📁 The file (or whatever else the source is called) the page was read from
+    pub source: String,
+    anchors: Vec<OriginAnchor>,
+    docs: usize,
+    page_len: usize,
+    predicted: Option<(u64, u64)>,
+}
+
+// 📌 Doc `doc`, at `page_offset` in the page, starts on `line` at `byte` in the source.
+#[derive(Debug, Clone, Copy)]
+struct OriginAnchor {
+    doc: usize,
+    page_offset: usize,
+    line: u64,
+    byte: u64,
+}
+
+impl PageOrigin {
+    /// 🏗️ An empty origin for a page read from `source`.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self { source: source.into(), ..Default::default() }
+    }
+
+    /// ➕ The page's next doc: it starts on `line` at byte `byte` of the source and is `len` bytes long.
+    pub fn push(&mut self, line: u64, byte: u64, len: usize) {
+        if self.predicted != Some((line, byte)) {
+            self.anchors.push(OriginAnchor { doc: self.docs, page_offset: self.page_len, line, byte });
+        }
+        self.predicted = Some((line + 1, byte + len as u64 + 1));
+        self.docs += 1;
+        self.page_len += len + 1;
+    }
+
+    /// 🔎 Line and byte offset of doc number `index`, which starts at `page_offset` in the page.
+    /// `None` for a doc this origin never heard of.
+    pub fn locate(&self, index: usize, page_offset: usize) -> Option<(u64, u64)> {
+        if index >= self.docs {
+            return None;
+        }
+        let the_anchor = self.anchors.iter().rev().find(|a| a.doc <= index)?;
+        let the_drift = page_offset.checked_sub(the_anchor.page_offset)?;
+        Some((the_anchor.line + (index - the_anchor.doc) as u64, the_anchor.byte + the_drift as u64))
+    }
+
+    /// 📄 How many docs the page holds.
+    pub fn docs(&self) -> usize {
+        self.docs
+    }
+}
+
 // 📦 A fully assembled, wire-ready payload — the final form before I/O.
-#[derive(Debug, Clone, PartialEq)]
-pub struct Payload(pub String);
+//
+// 🧱 Backed by `bytes::Bytes`, not `String`: a drainer clones the payload for every send
+// attempt, a dead-letter sink keeps a copy, and a 413 splits it in half. With `Bytes` all
+// of those are a refcount bump or a view into the same buffer, and HTTP sinks hand the
+// buffer to reqwest as-is. `From<String>` takes the String's allocation without copying.
+#[derive(Clone, PartialEq)]
+pub struct Payload(Bytes);
+
+impl Payload {
+    /// 🧱 The bytes, shared — cheap to clone, ready for an HTTP body.
+    pub fn to_bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    /// ✂️ A view of `range` in the same buffer — no copy. `None` unless both ends sit on
+    /// char boundaries, so a payload is always valid UTF-8.
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Option<Payload> {
+        let the_text: &str = self;
+        (range.start <= range.end
+            && range.end <= the_text.len()
+            && the_text.is_char_boundary(range.start)
+            && the_text.is_char_boundary(range.end))
+        .then(|| Payload(self.0.slice(range)))
+    }
+
+    /// ♻️ The buffer back, emptied — only if nobody else holds a piece of it. For `PayloadPool`.
+    pub fn try_reclaim(self) -> Option<String> {
+        let mut the_vec = Vec::from(self.0.try_into_mut().ok()?);
+        the_vec.clear();
+        String::from_utf8(the_vec).ok()
+    }
+
+    /// 📜 Back to an owned `String`. Reuses the buffer when nobody else shares it.
+    pub fn into_string(self) -> String {
+        String::from_utf8(Vec::from(self.0)).expect("💀 a Payload is UTF-8 by construction")
+    }
+}
 
 impl Deref for Payload {
-    type Target = String;
+    type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        // SAFETY: every constructor starts from a `String` or `&'static str`, and `slice`
+        // only cuts on char boundaries, so the bytes are always valid UTF-8.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl std::fmt::Debug for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Payload").field(&&**self).finish()
     }
 }
 
 impl From<String> for Payload {
     fn from(s: String) -> Self {
-        Payload(s)
+        Payload(Bytes::from(s))
+    }
+}
+
+impl From<&'static str> for Payload {
+    fn from(s: &'static str) -> Self {
+        Payload(Bytes::from_static(s.as_bytes()))
     }
 }
 
 impl PartialEq<&str> for Payload {
     fn eq(&self, other: &&str) -> bool {
-        self.0 == *other
+        &**self == *other
     }
 }
 
@@ -293,6 +899,24 @@ mod tests {
     /// JsonArrayManifold wraps it as [feed_content].
     ///
     /// 🐄 Zero-copy verification: passthrough borrows from the buffered feed, no per-doc alloc.
+    /// 🧪 Clones and slices share one buffer; a slice off a char boundary is refused.
+    #[test]
+    fn the_one_where_the_payload_is_cut_without_scissors() {
+        let the_payload = Payload::from("{\"name\":\"café\"}\n{\"id\":2}\n".to_string());
+        let the_clone = the_payload.clone();
+        assert_eq!(the_clone.as_ptr(), the_payload.as_ptr(), "🧱 a clone is a refcount bump");
+
+        let the_cut = the_payload.find('\n').unwrap() + 1;
+        let the_tail = the_payload.slice(the_cut..the_payload.len()).unwrap();
+        assert_eq!(the_tail, "{\"id\":2}\n");
+        assert_eq!(the_tail.as_ptr(), the_payload[the_cut..].as_ptr(), "✂️ a view, not a copy");
+
+        let the_middle_of_the_e = the_payload.find('é').unwrap() + 1;
+        assert!(the_payload.slice(0..the_middle_of_the_e).is_none(), "💀 half a char is not UTF-8");
+        assert!(the_payload.slice(0..the_payload.len() + 1).is_none(), "💀 past the end");
+        assert_eq!(the_tail.into_string(), "{\"id\":2}\n");
+    }
+
     #[tokio::test]
     async fn the_one_where_four_docs_made_it_home_safely() -> Result<()> {
         let app_config = AppConfig {
@@ -300,12 +924,27 @@ mod tests {
                 pumper_to_joiner_capacity: 10,
                 joiner_to_drainer_capacity: 10,
                 sink_parallelism: 1,
+                source_parallelism: None,
+                max_buffered_bytes: None,
                 joiner_parallelism: 1,
+                progress: Default::default(),
+                progress_interval_secs: 30,
+                max_errors: None,
+                max_error_ratio: None,
+                skip_bad_docs: false,
+                rejects_file: None,
             },
             source_config: SourceConfig::InMemory(()),
             sink_config: SinkConfig::InMemory(()),
             drainer: Default::default(),
             flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
         };
 
         let source = SourceBackend::InMemory(InMemorySource::new().await?);
@@ -319,7 +958,7 @@ mod tests {
         );
 
         // 🎼 InMemory sink → JsonArrayManifold: [item,item,...]
-        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config);
+        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
 
         // 📏 Max request size from sink config
         let max_request_size_bytes = app_config.sink_config.max_request_size_bytes();
@@ -330,7 +969,7 @@ mod tests {
         let the_flow_master_config = FlowMasterConfig::default();
         let foreman = Foreman::new(app_config);
         foreman
-            .start_workers(source, vec![sink], caster, manifold, the_test_flow_knob, &the_flow_master_config, max_request_size_bytes, "test-pipeline".to_string(), 0)
+            .start_workers(vec![source], vec![sink], caster, manifold, the_test_flow_knob, &the_flow_master_config, max_request_size_bytes, "test-pipeline".to_string(), 0, 0)
             .await?;
 
         // 📦 Joiner received 1 feed (4 docs newline-delimited), passthrough-cast and joined into JSON array.
@@ -418,6 +1057,11 @@ mod tests {
         let the_file_path = the_temp_file.path().to_str().unwrap().to_string();
         let the_source_config = SourceConfig::File(FileSourceConfig {
             file_name: the_file_path,
+            read_mode: Default::default(),
+            start_offset_bytes: 0,
+            offset_file: None,
+            validate_json: false,
+            compression: Default::default(),
             common_config: CommonSourceConfig::default(),
         });
         let the_sink_config = SinkConfig::Meilisearch(MeilisearchSinkConfig {
@@ -433,12 +1077,27 @@ mod tests {
                 pumper_to_joiner_capacity: 10,
                 joiner_to_drainer_capacity: 10,
                 sink_parallelism: 1,
+                source_parallelism: None,
+                max_buffered_bytes: None,
                 joiner_parallelism: 1,
+                progress: Default::default(),
+                progress_interval_secs: 30,
+                max_errors: None,
+                max_error_ratio: None,
+                skip_bad_docs: false,
+                rejects_file: None,
             },
             source_config: the_source_config.clone(),
             sink_config: the_sink_config.clone(),
             drainer: Default::default(),
             flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
         };
 
         // 🏗️ Phase 4: Build backends
@@ -462,7 +1121,7 @@ mod tests {
         );
 
         // 🎼 Meilisearch sink → JsonArrayManifold: [doc1,doc2,doc3]
-        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config);
+        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
         assert!(
             matches!(manifold, ManifoldBackend::JsonArray(_)),
             "💀 Meilisearch sink should resolve to JsonArrayManifold"
@@ -476,7 +1135,7 @@ mod tests {
         let foreman = Foreman::new(app_config);
         foreman
             .start_workers(
-                source_backend,
+                vec![source_backend],
                 vec![sink_backend],
                 caster,
                 manifold,
@@ -485,6 +1144,7 @@ mod tests {
                 max_request_size_bytes,
                 "test-file-to-meili".to_string(),
                 0,
+                0,
             )
             .await?;
 
@@ -540,6 +1200,7 @@ mod tests {
             stream: "test-stream".to_string(),
             username: None,
             password: None,
+            password_file: None,
             common_config: CommonSinkConfig::default(),
         };
 
@@ -548,12 +1209,27 @@ mod tests {
                 pumper_to_joiner_capacity: 10,
                 joiner_to_drainer_capacity: 10,
                 sink_parallelism: 1,
+                source_parallelism: None,
+                max_buffered_bytes: None,
                 joiner_parallelism: 1,
+                progress: Default::default(),
+                progress_interval_secs: 30,
+                max_errors: None,
+                max_error_ratio: None,
+                skip_bad_docs: false,
+                rejects_file: None,
             },
             source_config: SourceConfig::InMemory(()),
             sink_config: SinkConfig::OpenObserve(the_oo_sink_config.clone()),
             drainer: Default::default(),
             flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
         };
 
         // 🏗️ Build backends directly (same pattern as the InMemory e2e test)
@@ -567,7 +1243,7 @@ mod tests {
         );
 
         // 🎼 OpenObserve sink → NdjsonManifold: item\nitem\n
-        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config);
+        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
 
         // 📏 Max request size from sink config
         let max_request_size_bytes = app_config.sink_config.max_request_size_bytes();
@@ -578,7 +1254,7 @@ mod tests {
         let the_flow_master_config = FlowMasterConfig::default();
         let foreman = Foreman::new(app_config);
         foreman
-            .start_workers(source, vec![sink], caster, manifold, the_test_flow_knob, &the_flow_master_config, max_request_size_bytes, "test-openobserve-pipeline".to_string(), 0)
+            .start_workers(vec![source], vec![sink], caster, manifold, the_test_flow_knob, &the_flow_master_config, max_request_size_bytes, "test-openobserve-pipeline".to_string(), 0, 0)
             .await?;
 
         // 🎯 Assert — wiremock's expect(1) on the bulk endpoint confirms the pipeline
@@ -618,25 +1294,76 @@ mod tests {
                 pumper_to_joiner_capacity: 10,
                 joiner_to_drainer_capacity: 10,
                 sink_parallelism: 1,
+                source_parallelism: None,
+                max_buffered_bytes: None,
                 joiner_parallelism: 1,
+                progress: Default::default(),
+                progress_interval_secs: 30,
+                max_errors: None,
+                max_error_ratio: None,
+                skip_bad_docs: false,
+                rejects_file: None,
             },
             source_config: SourceConfig::Elasticsearch(ElasticsearchSourceConfig {
                 url: "http://source-cluster-that-doesnt-exist:9200".to_string(),
                 username: None,
                 password: None,
+                password_file: None,
                 api_key: None,
+                api_key_file: None,
                 common_config: CommonSourceConfig::default(),
+                time_slice: None,
+                resume_after: None,
+                index: None,
+                slices: None,
+                query: None,
+                source_includes: Vec::new(),
+                source_excludes: Vec::new(),
+                pagination: Default::default(),
+                compat: Default::default(),
+                keep_alive: "5m".to_string(),
+                connection: Default::default(),
             }),
             sink_config: SinkConfig::Elasticsearch(ElasticsearchSinkConfig {
                 url: "http://sink-cluster-also-fictional:9200".to_string(),
                 username: None,
                 password: None,
+                password_file: None,
                 api_key: None,
-                index: Some("destination-index".to_string()),
+                api_key_file: None,
+                // -- 🗺️ no sink `index`: every hit keeps the `_index` it came from (a static one would win)
+                index: None,
                 common_config: CommonSinkConfig::default(),
+                duplicate_check: None,
+                on_document_failure: Default::default(),
+                dead_letter_file: None,
+                copy_index: None,
+                create_index_if_missing: false,
+                mappings: None,
+                settings: None,
+                data_stream: false,
+                op_type: Default::default(),
+                tombstone: None,
+                pipeline: None,
+                refresh: Default::default(),
+                compat: Default::default(),
+                mapping_type: None,
+                blue_green: false,
+                blue_green_verify_count: false,
+                doc_id: None,
+                index_map: Default::default(),
+                index_date_field: None,
+                connection: Default::default(),
             }),
             drainer: Default::default(),
             flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
         };
 
         // 📡 Page 1: Two hits from the "movies" index — one with routing, because spicy data is best data
@@ -660,9 +1387,9 @@ mod tests {
         );
 
         // 🎼 ES sink config → NdjsonManifold (action\nsource\n per hit)
-        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config);
+        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
 
-        // 📏 Max request size from sink config — with default 64MB, all 3 hits fit in one payload
+        // 📏 Max request size from sink config — with the 10MB ES profile, all 3 hits fit in one payload
         let max_request_size_bytes = app_config.sink_config.max_request_size_bytes();
 
         // 🔧 Static flow knob — no regulator, full throttle, send it and pray 🙏
@@ -672,7 +1399,7 @@ mod tests {
         let foreman = Foreman::new(app_config);
         foreman
             .start_workers(
-                source,
+                vec![source],
                 vec![sink],
                 caster,
                 manifold,
@@ -681,6 +1408,7 @@ mod tests {
                 max_request_size_bytes,
                 "es-to-es-pit-to-bulk-gauntlet".to_string(),
                 0,
+                0,
             )
             .await?;
 
@@ -768,4 +1496,845 @@ mod tests {
 
         Ok(())
     }
+
+    /// 🧪 ES→ES reindex through `run()`, two mock clusters: PIT paging on one side, `_bulk` on the other.
+    /// The sink's `index` is where the docs land — the source's index name stays behind — while
+    /// `_id` and `_routing` make the trip.
+    #[tokio::test]
+    async fn the_one_where_the_index_moves_house() -> Result<()> {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let the_old_house = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_count"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"count": 2})))
+            .mount(&the_old_house)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/people/_pit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "pit-1"})))
+            .mount(&the_old_house)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(serde_json::json!({"search_after": [2]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"pit_id": "pit-1", "hits": {"hits": []}})))
+            .mount(&the_old_house)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"pit_id": "pit-1", "hits": {"hits": [
+                {"_index": "people", "_id": "ada", "_source": {"name": "Ada"}, "sort": [1]},
+                {"_index": "people", "_id": "bob", "_routing": "eu", "_source": {"name": "Bob"}, "sort": [2]}
+            ]}})))
+            .mount(&the_old_house)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_pit"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&the_old_house)
+            .await;
+
+        let the_new_house = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&the_new_house)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/people-v2/_bulk"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"errors": false, "items": []})))
+            .expect(1)
+            .mount(&the_new_house)
+            .await;
+
+        crate::pipeline::Pipeline::builder()
+            .source(SourceConfig::Elasticsearch(toml::from_str(&format!("url = '{}'\nindex = 'people'", the_old_house.uri()))?))
+            .sink(SinkConfig::Elasticsearch(toml::from_str(&format!("url = '{}'\nindex = 'people-v2'", the_new_house.uri()))?))
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .hide_terminal_progress()
+            .run()
+            .await?;
+
+        let the_requests = the_new_house.received_requests().await.unwrap_or_default();
+        let the_bulk = the_requests
+            .iter()
+            .find(|r| r.url.path() == "/people-v2/_bulk")
+            .map(|r| String::from_utf8_lossy(&r.body).to_string())
+            .unwrap_or_default();
+        assert_eq!(
+            the_bulk,
+            "{\"index\":{\"_id\":\"ada\"}}\n{\"name\":\"Ada\"}\n{\"index\":{\"_id\":\"bob\",\"_routing\":\"eu\"}}\n{\"name\":\"Bob\"}\n",
+            "🏠 no `_index` in the action lines — the URL's `people-v2` wins"
+        );
+        Ok(())
+    }
+
+    /// 🧪 ES→File ID export with `include_version`: the search asks for `"version": true`, so the
+    /// `_version` ES sends back lands in the third column instead of a row of `-`.
+    #[tokio::test]
+    async fn the_one_where_the_id_export_remembers_the_versions() -> Result<()> {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let the_cluster = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/people/_count"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"count": 2})))
+            .mount(&the_cluster)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/people/_pit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "pit-1"})))
+            .mount(&the_cluster)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(serde_json::json!({"search_after": [2]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"pit_id": "pit-1", "hits": {"hits": []}})))
+            .mount(&the_cluster)
+            .await;
+        // -- 🔢 `_version` only comes back to a search that asked for it
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .and(body_partial_json(serde_json::json!({"version": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"pit_id": "pit-1", "hits": {"hits": [
+                {"_index": "people", "_id": "ada", "_version": 3, "_source": {"name": "Ada"}, "sort": [1]},
+                {"_index": "people", "_id": "bob", "_version": 7, "_source": {"name": "Bob"}, "sort": [2]}
+            ]}})))
+            .mount(&the_cluster)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"pit_id": "pit-1", "hits": {"hits": [
+                {"_index": "people", "_id": "ada", "_source": {"name": "Ada"}, "sort": [1]},
+                {"_index": "people", "_id": "bob", "_source": {"name": "Bob"}, "sort": [2]}
+            ]}})))
+            .with_priority(10)
+            .mount(&the_cluster)
+            .await;
+        Mock::given(method("DELETE")).and(path("/_pit")).respond_with(ResponseTemplate::new(200)).mount(&the_cluster).await;
+
+        let the_dir = tempfile::tempdir()?;
+        let the_ids = the_dir.path().join("people.ids");
+        crate::pipeline::Pipeline::builder()
+            .source(SourceConfig::Elasticsearch(toml::from_str(&format!("url = '{}'\nindex = 'people'", the_cluster.uri()))?))
+            .sink(SinkConfig::File(toml::from_str(&format!(
+                "file_name = '{}'\n[id_export]\ninclude_version = true",
+                the_ids.display()
+            ))?))
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .hide_terminal_progress()
+            .run()
+            .await?;
+
+        assert_eq!(std::fs::read_to_string(&the_ids)?, "people\tada\t3\npeople\tbob\t7\n");
+        Ok(())
+    }
+
+    /// 🧪 The lever is pulled before the first pump — the pipeline should wind down
+    /// without shipping a single payload, and without hanging. 🛑
+    #[tokio::test]
+    async fn the_one_where_someone_pulls_the_lever_before_the_show_starts() -> Result<()> {
+        let app_config = AppConfig {
+            runtime: RuntimeConfig {
+                pumper_to_joiner_capacity: 10,
+                joiner_to_drainer_capacity: 10,
+                sink_parallelism: 1,
+                source_parallelism: None,
+                max_buffered_bytes: None,
+                joiner_parallelism: 1,
+                progress: Default::default(),
+                progress_interval_secs: 30,
+                max_errors: None,
+                max_error_ratio: None,
+                skip_bad_docs: false,
+                rejects_file: None,
+            },
+            source_config: SourceConfig::InMemory(()),
+            sink_config: SinkConfig::InMemory(()),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+
+        let source = SourceBackend::InMemory(InMemorySource::new().await?);
+        let sink_inner = InMemorySink::new().await?;
+        let sink = SinkBackend::InMemory(sink_inner.clone());
+        let caster = PageToEntriesCaster::from_configs(&app_config.source_config, &app_config.sink_config);
+        let manifold = ManifoldBackend::from_sink_config(&app_config.sink_config)?;
+        let max_request_size_bytes = app_config.sink_config.max_request_size_bytes();
+        let the_test_flow_knob: FlowKnob = Arc::new(AtomicUsize::new(max_request_size_bytes));
+        let the_flow_master_config = FlowMasterConfig::default();
+
+        let the_lever = ShutdownLever::default();
+        the_lever.store(true, std::sync::atomic::Ordering::Relaxed);
+
+        Foreman::new(app_config)
+            .with_shutdown(the_lever)
+            .start_workers(vec![source], vec![sink], caster, manifold, the_test_flow_knob, &the_flow_master_config, max_request_size_bytes, "test-shutdown".to_string(), 0, 0)
+            .await?;
+
+        let received = sink_inner.received.lock().await;
+        assert!(received.is_empty(), "💀 Lever was pulled before pump #1 — nothing should have shipped");
+        Ok(())
+    }
+
+    /// 🧪 run_with_shutdown with a signal that fires immediately still returns cleanly —
+    /// graceful means "finish up and exit Ok", not "panic and leave the lights on". 🚪
+    #[tokio::test]
+    async fn the_one_where_the_embedder_says_goodnight_immediately() -> Result<()> {
+        let app_config = AppConfig {
+            runtime: RuntimeConfig {
+                pumper_to_joiner_capacity: 10,
+                joiner_to_drainer_capacity: 10,
+                sink_parallelism: 1,
+                source_parallelism: None,
+                max_buffered_bytes: None,
+                joiner_parallelism: 1,
+                progress: Default::default(),
+                progress_interval_secs: 30,
+                max_errors: None,
+                max_error_ratio: None,
+                skip_bad_docs: false,
+                rejects_file: None,
+            },
+            source_config: SourceConfig::InMemory(()),
+            sink_config: SinkConfig::InMemory(()),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+
+        run_with_shutdown(app_config, async {}).await
+    }
+
+    /// 🧪 A token cancelled before the run starts — the pumper never pumps, the run still exits Ok. ✂️
+    #[tokio::test]
+    async fn the_one_where_the_job_was_cancelled_before_it_started() -> Result<()> {
+        let app_config = AppConfig {
+            runtime: RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() },
+            source_config: SourceConfig::InMemory(()),
+            sink_config: SinkConfig::InMemory(()),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+        let the_token = CancellationToken::new();
+        the_token.cancel();
+
+        run_with_cancellation(app_config, the_token).await
+    }
+
+    /// 🧪 `slices = 3` becomes three sources, one per slice id — each gets its own pumper.
+    #[tokio::test]
+    async fn the_one_where_the_pizza_is_cut_into_three() -> Result<()> {
+        use crate::backends::elasticsearch::{ElasticsearchSourceConfig, SliceSpec};
+        let app_config = AppConfig {
+            runtime: RuntimeConfig::default(),
+            source_config: SourceConfig::Elasticsearch(ElasticsearchSourceConfig {
+                url: "http://localhost:9200".to_string(),
+                username: None,
+                password: None,
+                password_file: None,
+                api_key: None,
+                api_key_file: None,
+                index: Some("people".to_string()),
+                slices: Some(3),
+                query: None,
+                source_includes: Vec::new(),
+                source_excludes: Vec::new(),
+                common_config: Default::default(),
+                time_slice: None,
+                resume_after: None,
+                pagination: Default::default(),
+                compat: Default::default(),
+                keep_alive: "5m".to_string(),
+                connection: Default::default(),
+            }),
+            sink_config: SinkConfig::InMemory(()),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+
+        let the_sources = from_source_configs(&app_config).await?;
+        let the_slices: Vec<Option<SliceSpec>> = the_sources
+            .iter()
+            .map(|s| match s {
+                SourceBackend::Elasticsearch(es) => es.slice(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            the_slices,
+            (0..3).map(|id| Some(SliceSpec { id, max: 3 })).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    /// 🧪 validate() on a File→File config passes and — crucially — does NOT create the sink file.
+    /// Look, don't touch. Like a museum, but for configs. 🖼️
+    #[tokio::test]
+    async fn the_one_where_validate_looks_but_does_not_touch() -> Result<()> {
+        let the_source_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(the_source_file.path(), "{\"doc\":1}\n")?;
+        let the_sink_dir = tempfile::tempdir()?;
+        let the_sink_path = the_sink_dir.path().join("out.ndjson");
+
+        let app_config = AppConfig {
+            runtime: RuntimeConfig::default(),
+            source_config: SourceConfig::File(FileSourceConfig {
+                file_name: the_source_file.path().to_string_lossy().to_string(),
+                read_mode: Default::default(),
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig::default(),
+            }),
+            sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                file_name: the_sink_path.to_string_lossy().to_string(),
+                id_export: None,
+                common_config: CommonSinkConfig::default(),
+                es_hits: Default::default(),
+            }),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+
+        validate(&app_config).await?;
+        assert!(!the_sink_path.exists(), "💀 validate() created the sink file — it was supposed to be a dry run");
+        Ok(())
+    }
+
+    /// 🧪 validate() catches a sink file whose directory doesn't exist — before any data moves.
+    #[tokio::test]
+    async fn the_one_where_validate_finds_the_missing_directory() -> Result<()> {
+        let the_source_file = tempfile::NamedTempFile::new()?;
+        let the_sink_dir = tempfile::tempdir()?;
+        let the_sink_path = the_sink_dir.path().join("nope").join("out.ndjson");
+
+        let app_config = AppConfig {
+            runtime: RuntimeConfig::default(),
+            source_config: SourceConfig::File(FileSourceConfig {
+                file_name: the_source_file.path().to_string_lossy().to_string(),
+                read_mode: Default::default(),
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig::default(),
+            }),
+            sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                file_name: the_sink_path.to_string_lossy().to_string(),
+                id_export: None,
+                common_config: CommonSinkConfig::default(),
+                es_hits: Default::default(),
+            }),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+
+        let the_verdict = validate(&app_config).await;
+        assert!(the_verdict.is_err(), "💀 a missing sink directory should fail validation");
+        assert!(format!("{:#}", the_verdict.unwrap_err()).contains("doesn't exist"));
+        Ok(())
+    }
+
+    /// 🧪 A first load with `create_index_if_missing` validates against a cluster that doesn't
+    /// have the index yet — that's the point of the flag. Without it, the missing index fails.
+    #[tokio::test]
+    async fn the_one_where_validate_knows_the_index_comes_later() -> Result<()> {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let the_cluster = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/")).respond_with(ResponseTemplate::new(200)).mount(&the_cluster).await;
+        Mock::given(method("GET")).and(path("/catalog")).respond_with(ResponseTemplate::new(404)).mount(&the_cluster).await;
+        Mock::given(method("PUT")).respond_with(ResponseTemplate::new(200)).expect(0).mount(&the_cluster).await;
+        let the_config = |the_extra: &str| -> Result<AppConfig> {
+            Ok(toml::from_str(&format!(
+                "[source_config.Generator]\ndocs = 5\n[sink_config.Elasticsearch]\nurl = \"{}\"\nindex = \"catalog\"\n{}",
+                the_cluster.uri(),
+                the_extra
+            ))?)
+        };
+
+        validate(&the_config("create_index_if_missing = true")?).await?;
+        let the_verdict = validate(&the_config("")?).await;
+        assert!(format!("{:#}", the_verdict.expect_err("💀 a missing index with no plan to make it")).contains("does not exist"));
+        Ok(())
+    }
+
+    /// 🧪 validate knocks on the source cluster with the source's own key — a wrong key fails
+    /// at `GET /` instead of at the first search of the run.
+    #[tokio::test]
+    async fn the_one_where_validate_asks_the_source_for_its_papers() -> Result<()> {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let the_cluster = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(header("Authorization", "ApiKey the-good-key"))
+            .respond_with(ResponseTemplate::new(200))
+            .with_priority(1)
+            .mount(&the_cluster)
+            .await;
+        Mock::given(method("GET")).and(path("/")).respond_with(ResponseTemplate::new(401)).mount(&the_cluster).await;
+        Mock::given(method("POST"))
+            .and(path("/catalog/_count"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "count": 3 })))
+            .expect(1)
+            .mount(&the_cluster)
+            .await;
+        let the_dir = tempfile::tempdir()?;
+        let the_config = |the_key: &str| -> Result<AppConfig> {
+            Ok(toml::from_str(&format!(
+                "[source_config.Elasticsearch]\nurl = \"{}\"\nindex = \"catalog\"\napi_key = \"{}\"\n[sink_config.File]\nfile_name = \"{}\"\n",
+                the_cluster.uri(),
+                the_key,
+                the_dir.path().join("out.ndjson").display()
+            ))?)
+        };
+
+        validate(&the_config("the-good-key")?).await?;
+        let the_verdict = validate(&the_config("the-stale-key")?).await;
+        assert!(format!("{:#}", the_verdict.expect_err("💀 a key the cluster turned away")).contains("401"));
+        Ok(())
+    }
+
+    /// 🧪 A file pattern becomes one source per file; two pumpers share the line of five,
+    /// and every doc from every file lands exactly once.
+    #[tokio::test]
+    async fn the_one_where_five_files_wait_for_two_pumpers() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        for the_part in 0..5 {
+            let the_lines: String = (0..20).map(|i| format!("{{\"part\":{},\"n\":{}}}\n", the_part, i)).collect();
+            std::fs::write(the_dir.path().join(format!("part-{}.ndjson", the_part)), the_lines)?;
+        }
+        std::fs::write(the_dir.path().join("README.txt"), "not a doc\n")?;
+        let the_sink_path = the_dir.path().join("out.ndjson.result");
+
+        let app_config = AppConfig {
+            runtime: RuntimeConfig { source_parallelism: Some(2), sink_parallelism: 1, joiner_parallelism: 2, ..Default::default() },
+            source_config: SourceConfig::File(FileSourceConfig {
+                file_name: the_dir.path().join("part-*.ndjson").to_string_lossy().to_string(),
+                read_mode: Default::default(),
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig { max_batch_size_docs: Some(7), ..Default::default() },
+            }),
+            sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                file_name: the_sink_path.to_string_lossy().to_string(),
+                id_export: None,
+                common_config: CommonSinkConfig::default(),
+                es_hits: Default::default(),
+            }),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+        assert_eq!(from_source_configs(&app_config).await?.len(), 5, "🗂️ the README doesn't match");
+
+        run(app_config).await?;
+
+        let mut the_landed: Vec<(u64, u64)> = std::fs::read_to_string(&the_sink_path)?
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).map(|d| (d["part"].as_u64().unwrap(), d["n"].as_u64().unwrap())))
+            .collect::<serde_json::Result<_>>()?;
+        the_landed.sort();
+        let the_expected: Vec<(u64, u64)> = (0..5).flat_map(|p| (0..20).map(move |n| (p, n))).collect();
+        assert_eq!(the_landed, the_expected);
+        Ok(())
+    }
+    /// 🧪 A byte budget far too small for the pipeline gets raised (with a warning), and
+    /// every doc still lands — nobody deadlocks waiting for bytes that never come back.
+    #[tokio::test]
+    async fn the_one_where_the_budget_is_one_byte() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_source_path = the_dir.path().join("in.ndjson");
+        let the_lines: String = (0..200).map(|i| format!("{{\"n\":{}}}\n", i)).collect();
+        std::fs::write(&the_source_path, the_lines)?;
+        let the_sink_path = the_dir.path().join("out.ndjson.result");
+
+        let app_config = AppConfig {
+            runtime: RuntimeConfig { max_buffered_bytes: Some(1), sink_parallelism: 2, joiner_parallelism: 2, ..Default::default() },
+            source_config: SourceConfig::File(FileSourceConfig {
+                file_name: the_source_path.to_string_lossy().to_string(),
+                read_mode: Default::default(),
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig { max_batch_size_docs: Some(3), ..Default::default() },
+            }),
+            sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                file_name: the_sink_path.to_string_lossy().to_string(),
+                id_export: None,
+                common_config: CommonSinkConfig::default(),
+                es_hits: Default::default(),
+            }),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+        let the_warnings = app_config.warnings.clone();
+
+        run(app_config).await?;
+
+        assert_eq!(std::fs::read_to_string(&the_sink_path)?.lines().count(), 200);
+        assert!(
+            the_warnings.snapshot().iter().any(|w| w.message.contains("max_buffered_bytes")),
+            "🧮 the raise gets mentioned"
+        );
+        Ok(())
+    }
+
+    /// 🧪 Five docs fail the schema. A budget of five lets the run finish; a budget of two fails it.
+    #[tokio::test]
+    async fn the_one_where_the_error_budget_runs_out() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_source_path = the_dir.path().join("in.ndjson");
+        let the_lines: String =
+            (0..20).map(|i| if i % 4 == 0 { format!("{{\"m\":{}}}\n", i) } else { format!("{{\"n\":{}}}\n", i) }).collect();
+        std::fs::write(&the_source_path, the_lines)?;
+        let the_schema_path = the_dir.path().join("schema.json");
+        std::fs::write(&the_schema_path, r#"{"type":"object","required":["n"]}"#)?;
+
+        for (the_max_errors, should_finish) in [(5, true), (2, false)] {
+            let app_config = AppConfig {
+                runtime: RuntimeConfig { max_errors: Some(the_max_errors), ..Default::default() },
+                source_config: SourceConfig::File(FileSourceConfig {
+                    file_name: the_source_path.to_string_lossy().to_string(),
+                    read_mode: Default::default(),
+                    start_offset_bytes: 0,
+                    offset_file: None,
+                    validate_json: false,
+                    compression: Default::default(),
+                    common_config: CommonSourceConfig::default(),
+                }),
+                sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                    file_name: the_dir.path().join("out.ndjson").to_string_lossy().to_string(),
+                    id_export: None,
+                    common_config: CommonSinkConfig::default(),
+                    es_hits: Default::default(),
+                }),
+                drainer: Default::default(),
+                flow_master: Default::default(),
+                warnings: Default::default(),
+                progress: Default::default(),
+                transforms: vec![crate::transforms::TransformConfig::JsonSchema(crate::transforms::JsonSchemaConfig {
+                    schema_file: the_schema_path.clone(),
+                    rejects_file: None,
+                })],
+                transform_registry: Default::default(),
+                pause: Default::default(),
+                failed_docs: Default::default(),
+                notify: Default::default(),
+            };
+            let the_outcome = run(app_config).await;
+            assert_eq!(the_outcome.is_ok(), should_finish, "🧯 max_errors = {}: {:?}", the_max_errors, the_outcome);
+            if let Err(the_failure) = the_outcome {
+                assert!(format!("{:#}", the_failure).contains("max_errors = 2"));
+            }
+        }
+        Ok(())
+    }
+
+    /// 🧪 A transform chokes on the fifth line of the file, on the second page, after a blank
+    /// line. The error says so — file, line and byte — whichever way the file was read.
+    #[tokio::test]
+    async fn the_one_where_the_error_knows_its_address() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_source_path = the_dir.path().join("in.ndjson");
+        std::fs::write(&the_source_path, "{\"a\":{\"b\":1}}\n{\"a\":{\"b\":2}}\n\n{\"a\":{\"b\":3}}\n{\"a\":\"oops\"}\n")?;
+
+        for the_mode in [crate::backends::file::FileReadMode::Buffered, crate::backends::file::FileReadMode::Mmap] {
+            let app_config = AppConfig {
+                runtime: RuntimeConfig { joiner_parallelism: 1, ..Default::default() },
+                source_config: SourceConfig::File(FileSourceConfig {
+                    file_name: the_source_path.to_string_lossy().to_string(),
+                    read_mode: the_mode,
+                    start_offset_bytes: 0,
+                    offset_file: None,
+                    validate_json: false,
+                    compression: Default::default(),
+                    common_config: CommonSourceConfig { max_batch_size_docs: Some(2), ..Default::default() },
+                }),
+                sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                    file_name: the_dir.path().join("out.ndjson").to_string_lossy().to_string(),
+                    id_export: None,
+                    common_config: CommonSinkConfig::default(),
+                    es_hits: Default::default(),
+                }),
+                drainer: Default::default(),
+                flow_master: Default::default(),
+                warnings: Default::default(),
+                progress: Default::default(),
+                transforms: vec![crate::transforms::TransformConfig::Jq(crate::transforms::JqConfig {
+                    expression: ".a.b".to_string(),
+                })],
+                transform_registry: Default::default(),
+                pause: Default::default(),
+                failed_docs: Default::default(),
+                notify: Default::default(),
+            };
+            let the_failure = run(app_config).await.expect_err("💀 a string has no .b");
+            let the_story = format!("{:#}", the_failure);
+            assert!(the_story.contains("in.ndjson' line 5, byte offset 43"), "📍 {:?}: {}", the_mode, the_story);
+        }
+        Ok(())
+    }
+
+    /// 🧪 A File sink with `manifold = "json_array"` (or `"pretty"`) writes one array for the
+    /// whole run, however many payloads it took to get there.
+    #[tokio::test]
+    async fn the_one_where_the_downstream_tool_wanted_brackets() -> Result<()> {
+        use crate::manifolds::ManifoldFormat;
+        for the_format in [ManifoldFormat::JsonArray, ManifoldFormat::Pretty] {
+            let the_dir = tempfile::tempdir()?;
+            let the_source_path = the_dir.path().join("in.ndjson");
+            let the_lines: String = (0..50).map(|i| format!("{{\"n\":{}}}\n", i)).collect();
+            std::fs::write(&the_source_path, the_lines)?;
+            let the_sink_path = the_dir.path().join("out.json");
+
+            let app_config = AppConfig {
+                runtime: RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() },
+                source_config: SourceConfig::File(FileSourceConfig {
+                    file_name: the_source_path.to_string_lossy().to_string(),
+                    read_mode: Default::default(),
+                    start_offset_bytes: 0,
+                    offset_file: None,
+                    validate_json: false,
+                    compression: Default::default(),
+                    common_config: CommonSourceConfig { max_batch_size_docs: Some(7), ..Default::default() },
+                }),
+                sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                    file_name: the_sink_path.to_string_lossy().to_string(),
+                    id_export: None,
+                    // -- 🧱 tiny requests, so the array spans many payloads
+                    common_config: CommonSinkConfig {
+                        max_request_size_bytes: Some(64),
+                        manifold: Some(the_format),
+                        ..Default::default()
+                    },
+                    es_hits: Default::default(),
+                }),
+                drainer: Default::default(),
+                flow_master: Default::default(),
+                warnings: Default::default(),
+                progress: Default::default(),
+                transforms: Default::default(),
+                transform_registry: Default::default(),
+                pause: Default::default(),
+                failed_docs: Default::default(),
+                notify: Default::default(),
+            };
+
+            run(app_config).await?;
+
+            let the_text = std::fs::read_to_string(&the_sink_path)?;
+            let the_docs: Vec<serde_json::Value> = serde_json::from_str(&the_text)?;
+            assert_eq!(the_docs.len(), 50, "📦 one array, every doc ({:?})", the_format);
+            assert_eq!(the_docs[49], serde_json::json!({"n": 49}));
+            if the_format == ManifoldFormat::Pretty {
+                assert!(the_text.starts_with("[\n  {\n    \"n\": 0\n  },\n"), "🖨️ {}", the_text);
+                assert!(the_text.ends_with("\n  }\n]\n"), "🖨️ {}", the_text);
+            }
+        }
+        Ok(())
+    }
+
+    /// 🧪 `manifold = "csv"`: one header row up top, then one row per doc, nested fields flattened.
+    #[tokio::test]
+    async fn the_one_where_the_analyst_finally_gets_a_csv() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_source_path = the_dir.path().join("in.ndjson");
+        let the_lines: String = (0..20).map(|i| format!("{{\"n\":{},\"who\":{{\"name\":\"doc, {}\"}}}}\n", i, i)).collect();
+        std::fs::write(&the_source_path, the_lines)?;
+        let the_sink_path = the_dir.path().join("out.csv");
+
+        let app_config = AppConfig {
+            runtime: RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() },
+            source_config: SourceConfig::File(FileSourceConfig {
+                file_name: the_source_path.to_string_lossy().to_string(),
+                read_mode: Default::default(),
+                start_offset_bytes: 0,
+                offset_file: None,
+                validate_json: false,
+                compression: Default::default(),
+                common_config: CommonSourceConfig { max_batch_size_docs: Some(7), ..Default::default() },
+            }),
+            sink_config: SinkConfig::File(crate::backends::FileSinkConfig {
+                file_name: the_sink_path.to_string_lossy().to_string(),
+                id_export: None,
+                common_config: CommonSinkConfig {
+                    max_request_size_bytes: Some(64),
+                    manifold: Some(crate::manifolds::ManifoldFormat::Csv),
+                    csv: Some(crate::manifolds::CsvConfig {
+                        columns: vec!["n".to_string(), "who.name".to_string()],
+                        header: true,
+                        delimiter: ',',
+                    }),
+                    ..Default::default()
+                },
+                es_hits: Default::default(),
+            }),
+            drainer: Default::default(),
+            flow_master: Default::default(),
+            warnings: Default::default(),
+            progress: Default::default(),
+            transforms: Default::default(),
+            transform_registry: Default::default(),
+            pause: Default::default(),
+            failed_docs: Default::default(),
+            notify: Default::default(),
+        };
+
+        run(app_config).await?;
+
+        let the_csv = std::fs::read_to_string(&the_sink_path)?;
+        let the_rows: Vec<&str> = the_csv.lines().collect();
+        assert_eq!(the_rows.len(), 21, "📊 a header and twenty rows");
+        assert_eq!(the_rows[0], "n,who.name");
+        assert_eq!(the_rows[20], "19,\"doc, 19\"");
+        Ok(())
+    }
+
+    /// 🔵🟢 Blue/green mocks: no alias yet, staging gets created, bulked, refreshed and counted.
+    async fn mount_blue_green(mock_server: &wiremock::MockServer, the_count: u64) {
+        use wiremock::matchers::{method, path, path_regex};
+        use wiremock::{Mock, ResponseTemplate};
+        Mock::given(method("GET")).and(path("/")).respond_with(ResponseTemplate::new(200)).mount(mock_server).await;
+        for the_missing in ["/catalog", "/_alias/catalog"] {
+            Mock::given(method("GET")).and(path(the_missing)).respond_with(ResponseTemplate::new(404)).mount(mock_server).await;
+        }
+        Mock::given(method("HEAD")).and(path_regex(r"^/catalog-\d{14}$")).respond_with(ResponseTemplate::new(404)).mount(mock_server).await;
+        Mock::given(method("PUT"))
+            .and(path_regex(r"^/catalog-\d{14}$"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(mock_server)
+            .await;
+        Mock::given(method("POST")).and(path_regex(r"^/catalog-\d{14}/_bulk$")).respond_with(ResponseTemplate::new(200)).mount(mock_server).await;
+        Mock::given(method("POST")).and(path_regex(r"^/catalog-\d{14}/_refresh$")).respond_with(ResponseTemplate::new(200)).mount(mock_server).await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/catalog-\d{14}/_count$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "count": the_count })))
+            .mount(mock_server)
+            .await;
+    }
+
+    fn a_blue_green_config(url: &str) -> Result<AppConfig> {
+        Ok(toml::from_str(&format!(
+            "[source_config.Generator]\ndocs = 5\n\
+             [sink_config.Elasticsearch]\nurl = \"{}\"\nindex = \"catalog\"\nblue_green = true\nblue_green_verify_count = true",
+            url
+        ))?)
+    }
+
+    /// 🧪 All five docs landed in staging → the alias moves. Four of five → staging is binned.
+    #[tokio::test]
+    async fn the_one_where_the_catalog_is_swapped_while_nobody_is_looking() -> Result<()> {
+        use wiremock::matchers::{method, path, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let the_good_run = MockServer::start().await;
+        mount_blue_green(&the_good_run, 5).await;
+        Mock::given(method("POST"))
+            .and(path("/_aliases"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&the_good_run)
+            .await;
+        Mock::given(method("DELETE")).respond_with(ResponseTemplate::new(200)).expect(0).mount(&the_good_run).await;
+        run(a_blue_green_config(&the_good_run.uri())?).await?;
+
+        let the_short_run = MockServer::start().await;
+        mount_blue_green(&the_short_run, 4).await;
+        Mock::given(method("POST")).and(path("/_aliases")).respond_with(ResponseTemplate::new(200)).expect(0).mount(&the_short_run).await;
+        Mock::given(method("DELETE"))
+            .and(path_regex(r"^/catalog-\d{14}$"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&the_short_run)
+            .await;
+        let the_verdict = run(a_blue_green_config(&the_short_run.uri())?).await;
+        assert!(the_verdict.is_err(), "💀 a staging index one doc short must not go live");
+        Ok(())
+    }
+
+    /// 🧪 A Filter keeps ids 0..3 of five → staging holds three, and three is what was expected.
+    #[tokio::test]
+    async fn the_one_where_the_filtered_catalog_still_goes_live() -> Result<()> {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        mount_blue_green(&mock_server, 3).await;
+        Mock::given(method("POST")).and(path("/_aliases")).respond_with(ResponseTemplate::new(200)).expect(1).mount(&mock_server).await;
+        Mock::given(method("DELETE")).respond_with(ResponseTemplate::new(200)).expect(0).mount(&mock_server).await;
+        let the_config: AppConfig = toml::from_str(&format!(
+            "[source_config.Generator]\ndocs = 5\n\
+             [sink_config.Elasticsearch]\nurl = \"{}\"\nindex = \"catalog\"\nblue_green = true\nblue_green_verify_count = true\n\
+             [[transforms]]\nFilter = {{ all = [{{ field = \"id\", lt = 3 }}] }}",
+            mock_server.uri()
+        ))?;
+        run(the_config).await?;
+        Ok(())
+    }
 }
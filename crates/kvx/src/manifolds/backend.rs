@@ -12,20 +12,40 @@
 //! 🧠 Knowledge graph:
 //! - Same pattern as `DocumentCaster`, `SourceBackend`, `SinkBackend`
 //! - Resolution: SinkConfig → ManifoldBackend::from_sink_config() → concrete manifold
-//! - ES/File → NdjsonManifold | InMemory → JsonArrayManifold
+//! - ES/File → NdjsonManifold | InMemory → JsonArrayManifold — unless the sink config says
+//!   `manifold = "..."`. Sinks whose wire format is fixed (ES, OpenObserve, Meilisearch, and
+//!   Null standing in for `_bulk`) refuse any other manifold; File and Custom take either
 //! - The compiler monomorphizes each arm; branch prediction eliminates the match
 //!   after a couple iterations. The enum is a formality. The dispatch is basically free.
 //! - Cloning ManifoldBackend is free — NdjsonManifold and JsonArrayManifold are zero-sized.
+//!   SqlInsertManifold and CsvManifold carry their column lists, so those cost an allocation or two.
 //!
 //! 🦆 The duck asked why we need a backend enum when we have trait objects.
 //!    We said "monomorphization." The duck left. It didn't want a lecture.
 
-use super::{Manifold, JsonArrayManifold, NdjsonManifold};
+use super::{ArrayFraming, CsvManifold, Manifold, JsonArrayManifold, NdjsonManifold, PrettyJsonArrayManifold, SqlInsertManifold};
 use crate::config::SinkConfig;
 use crate::{Entry, Payload};
 use anyhow::Result;
+use serde::Deserialize;
 use std::collections::VecDeque;
 
+/// 🎛️ Which manifold a sink gets — `manifold = "..."` in the sink config (alias `composer`).
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifoldFormat {
+    /// 📡 `item\nitem\n`
+    Ndjson,
+    /// 📦 `[item,item]`
+    JsonArray,
+    /// 🖨️ `[item,item]`, indented — for people and picky tools
+    Pretty,
+    /// 🗄️ `INSERT INTO ... VALUES (..),(..);` — needs a `sql_insert` table in the sink config
+    SqlInsert,
+    /// 📊 `a,b\nc,d\n` — needs a `csv` table (the column list) in the sink config
+    Csv,
+}
+
 // -- ┌─────────────────────────────────────────────────────────┐
 // -- │  ManifoldBackend                                         │
 // -- │  Enum → impl ManifoldBackend → impl Manifold → tests    │
@@ -46,6 +66,12 @@ pub enum ManifoldBackend {
     Ndjson(NdjsonManifold),
     /// 📦 JSON array — cast + wrap in `[`, commas, `]`
     JsonArray(JsonArrayManifold),
+    /// 🖨️ Pretty JSON array — the same, parsed and indented
+    Pretty(PrettyJsonArrayManifold),
+    /// 🗄️ SQL load script — one `INSERT` per batch of rows
+    SqlInsert(SqlInsertManifold),
+    /// 📊 CSV — one row per doc, header written by the sink
+    Csv(CsvManifold),
 }
 
 impl ManifoldBackend {
@@ -60,6 +86,8 @@ impl ManifoldBackend {
             Self::Ndjson(NdjsonManifold),
             // 📦 JSON array — for when your sink likes brackets more than newlines
             Self::JsonArray(JsonArrayManifold),
+            // -- 🖨️🗄️📊 Pretty, SqlInsert and Csv sit out: they parse one doc per entry, and the
+            // -- benches feed whole pages
         ]
     }
 
@@ -75,16 +103,92 @@ impl ManifoldBackend {
     ///
     /// 🧠 Format follows the sink, not the source. The sink decides the wire format.
     /// This is the one true law. Do not question it. The borrow checker already has enough opinions.
-    pub fn from_sink_config(sink: &SinkConfig) -> Self {
+    ///
+    /// 🎛️ A `manifold = "..."` in the sink config overrides the table — where the sink can take it.
+    /// Asking `_bulk` for a JSON array is a config error, caught here instead of 400'd later.
+    pub fn from_sink_config(sink: &SinkConfig) -> Result<Self> {
+        let the_default = Self::default_for(sink);
+        let Some(the_format) = sink.manifold() else {
+            return Ok(the_default);
+        };
+        // -- 🔒 the API on the other end only parses one shape
+        if matches!(
+            sink,
+            SinkConfig::Elasticsearch(_)
+                | SinkConfig::OpenObserve(_)
+                | SinkConfig::Meilisearch(_)
+                | SinkConfig::Solr(_)
+                | SinkConfig::Null(_)
+        ) && the_format != the_default.format()
+        {
+            anyhow::bail!(
+                "💀 manifold = {:?} won't work here — this sink only takes {:?}. \
+                 Drop the `manifold` key, or write to a File sink and convert from there.",
+                the_format,
+                the_default
+            )
+        }
+        Ok(match the_format {
+            ManifoldFormat::Ndjson => Self::Ndjson(NdjsonManifold),
+            ManifoldFormat::JsonArray => Self::JsonArray(JsonArrayManifold),
+            ManifoldFormat::Pretty => Self::Pretty(PrettyJsonArrayManifold),
+            ManifoldFormat::SqlInsert => {
+                let the_mapping = sink.sql_insert().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "💀 manifold = \"sql_insert\" needs a `sql_insert` table in the sink config: \
+                         `table = \"...\"` and `columns = {{ column = \"field\" }}`"
+                    )
+                })?;
+                Self::SqlInsert(SqlInsertManifold::new(the_mapping)?)
+            }
+            ManifoldFormat::Csv => {
+                let the_columns = sink.csv().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "💀 manifold = \"csv\" needs a `csv` table in the sink config: `columns = [\"field\", ...]`"
+                    )
+                })?;
+                Self::Csv(CsvManifold::new(the_columns)?)
+            }
+        })
+    }
+
+    /// 🧱 The array framing for `json_array` / `pretty` — what the File sink splices on. `None` otherwise.
+    pub fn array_framing(format: ManifoldFormat) -> Option<ArrayFraming> {
+        match format {
+            ManifoldFormat::JsonArray => Some(JsonArrayManifold::FRAMING),
+            ManifoldFormat::Pretty => Some(PrettyJsonArrayManifold::FRAMING),
+            ManifoldFormat::Ndjson | ManifoldFormat::SqlInsert | ManifoldFormat::Csv => None,
+        }
+    }
+
+    /// 🎛️ Which `manifold = "..."` value this is.
+    pub fn format(&self) -> ManifoldFormat {
+        match self {
+            Self::Ndjson(_) => ManifoldFormat::Ndjson,
+            Self::JsonArray(_) => ManifoldFormat::JsonArray,
+            Self::Pretty(_) => ManifoldFormat::Pretty,
+            Self::SqlInsert(_) => ManifoldFormat::SqlInsert,
+            Self::Csv(_) => ManifoldFormat::Csv,
+        }
+    }
+
+    /// 📋 The table above — what each sink gets when its config doesn't say.
+    fn default_for(sink: &SinkConfig) -> Self {
         match sink {
             // -- 📡 ES bulk requires NDJSON — action+source pairs, trailing \n
             SinkConfig::Elasticsearch(_) => Self::Ndjson(NdjsonManifold),
             // -- 📡 OpenObserve bulk is ES-compatible — same NDJSON wire format, same manifold
             SinkConfig::OpenObserve(_) => Self::Ndjson(NdjsonManifold),
+            // -- 🕳️ Null stands in for a `_bulk` endpoint — it throws away what ES would have received
+            SinkConfig::Null(_) => Self::Ndjson(NdjsonManifold),
             // -- 📡 File sinks: NDJSON — one doc per line, trailing \n, everyone's happy
             SinkConfig::File(_) => Self::Ndjson(NdjsonManifold),
+            // -- 🧩 Custom sinks get what File sinks get: NDJSON, one doc per line
+            SinkConfig::Custom(_) => Self::Ndjson(NdjsonManifold),
             // -- 🔍 Meilisearch: JSON array — `POST /indexes/{uid}/documents` expects `[doc1,doc2]`
             SinkConfig::Meilisearch(_) => Self::JsonArray(JsonArrayManifold),
+            // -- ☀️ Solr: JSON array too — `/update/json/docs` takes `[doc1,doc2]`
+            SinkConfig::Solr(_) => Self::JsonArray(JsonArrayManifold),
             // -- 📦 InMemory: JSON array — test assertions want `[doc1,doc2]` not `doc1\ndoc2\n`
             SinkConfig::InMemory(_) => Self::JsonArray(JsonArrayManifold),
         }
@@ -93,12 +197,15 @@ impl ManifoldBackend {
 
 impl Manifold for ManifoldBackend {
     #[inline]
-    fn join(&self, entries: &mut VecDeque<Entry>) -> Result<Payload> {
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload> {
         // -- 🎭 Dispatch to the concrete manifold — the match arm that wins is the one that deserves to
         // -- TODO: win the lottery, retire, replace this with a lookup table. Just kidding. This is fine.
         match self {
-            Self::Ndjson(m) => m.join(entries),
-            Self::JsonArray(m) => m.join(entries),
+            Self::Ndjson(m) => m.join_with(entries, buffer),
+            Self::JsonArray(m) => m.join_with(entries, buffer),
+            Self::Pretty(m) => m.join_with(entries, buffer),
+            Self::SqlInsert(m) => m.join_with(entries, buffer),
+            Self::Csv(m) => m.join_with(entries, buffer),
         }
     }
 }
@@ -114,18 +221,77 @@ mod tests {
             url: "http://localhost:9200".into(),
             username: None,
             password: None,
+            password_file: None,
             api_key: None,
+            api_key_file: None,
             index: None,
             common_config: Default::default(),
+            duplicate_check: None,
+            on_document_failure: Default::default(),
+            dead_letter_file: None,
+            copy_index: None,
+            create_index_if_missing: false,
+            mappings: None,
+            settings: None,
+            data_stream: false,
+            op_type: Default::default(),
+            tombstone: None,
+            pipeline: None,
+            refresh: Default::default(),
+            compat: Default::default(),
+            mapping_type: None,
+            blue_green: false,
+            blue_green_verify_count: false,
+            doc_id: None,
+            index_map: Default::default(),
+            index_date_field: None,
+            connection: Default::default(),
         });
-        let manifold = ManifoldBackend::from_sink_config(&config);
+        let manifold = ManifoldBackend::from_sink_config(&config).unwrap();
         assert!(matches!(manifold, ManifoldBackend::Ndjson(_)));
     }
 
+    #[test]
+    fn backend_the_one_where_the_file_sink_asks_for_brackets() -> Result<()> {
+        use crate::backends::file::FileSinkConfig;
+        let the_file: FileSinkConfig = toml::from_str("file_name = \"out.json\"\nmanifold = \"json_array\"")?;
+        let manifold = ManifoldBackend::from_sink_config(&SinkConfig::File(the_file))?;
+        assert!(matches!(manifold, ManifoldBackend::JsonArray(_)));
+
+        // -- 🏷️ the request's word for it works too
+        let the_file: FileSinkConfig = toml::from_str("file_name = \"out.ndjson\"\ncomposer = \"ndjson\"")?;
+        assert!(matches!(ManifoldBackend::from_sink_config(&SinkConfig::File(the_file))?, ManifoldBackend::Ndjson(_)));
+
+        // -- 🔒 `_bulk` doesn't do brackets
+        use crate::backends::open_observe::OpenObserveSinkConfig;
+        let the_stream: OpenObserveSinkConfig =
+            toml::from_str("url = \"http://localhost:5080\"\nstream = \"s\"\nmanifold = \"json_array\"")?;
+        let the_error = ManifoldBackend::from_sink_config(&SinkConfig::OpenObserve(the_stream)).unwrap_err();
+        assert!(the_error.to_string().contains("only takes"), "🎯 {}", the_error);
+        Ok(())
+    }
+
+    #[test]
+    fn backend_the_one_where_the_file_sink_writes_a_load_script() -> Result<()> {
+        use crate::backends::file::FileSinkConfig;
+        let the_file: FileSinkConfig = toml::from_str(
+            "file_name = \"out.sql\"\nmanifold = \"sql_insert\"\n\
+             [sql_insert]\ntable = \"people\"\ncolumns = { id = \"id\", city = \"address.city\" }",
+        )?;
+        let manifold = ManifoldBackend::from_sink_config(&SinkConfig::File(the_file))?;
+        let mut entries = VecDeque::from(vec![Entry(r#"{"id":7,"address":{"city":"Oslo"}}"#.to_string())]);
+        assert_eq!(manifold.join(&mut entries)?, "INSERT INTO \"people\" (\"city\",\"id\") VALUES\n('Oslo',7);\n");
+
+        // -- 🗺️ no mapping, no rows
+        let the_file: FileSinkConfig = toml::from_str("file_name = \"out.sql\"\nmanifold = \"sql_insert\"")?;
+        assert!(ManifoldBackend::from_sink_config(&SinkConfig::File(the_file)).is_err());
+        Ok(())
+    }
+
     #[test]
     fn backend_the_one_where_inmemory_resolves_to_json_array() {
         let config = SinkConfig::InMemory(());
-        let manifold = ManifoldBackend::from_sink_config(&config);
+        let manifold = ManifoldBackend::from_sink_config(&config).unwrap();
         assert!(matches!(manifold, ManifoldBackend::JsonArray(_)));
     }
 
@@ -138,9 +304,10 @@ mod tests {
             stream: "test-stream".into(),
             username: None,
             password: None,
+            password_file: None,
             common_config: Default::default(),
         });
-        let manifold = ManifoldBackend::from_sink_config(&config);
+        let manifold = ManifoldBackend::from_sink_config(&config).unwrap();
         assert!(matches!(manifold, ManifoldBackend::Ndjson(_)));
     }
 
@@ -149,22 +316,24 @@ mod tests {
         use crate::backends::file::FileSinkConfig;
         let config = SinkConfig::File(FileSinkConfig {
             file_name: "output.json".into(),
+            id_export: None,
             common_config: Default::default(),
+            es_hits: Default::default(),
         });
-        let manifold = ManifoldBackend::from_sink_config(&config);
+        let manifold = ManifoldBackend::from_sink_config(&config).unwrap();
         assert!(matches!(manifold, ManifoldBackend::Ndjson(_)));
     }
 
     #[test]
     fn backend_the_one_where_join_dispatches_correctly() -> Result<()> {
         // 🧪 ManifoldBackend dispatches to the right concrete manifold
-        let manifold = ManifoldBackend::from_sink_config(&SinkConfig::InMemory(()));
+        let manifold = ManifoldBackend::from_sink_config(&SinkConfig::InMemory(()))?;
         let mut entries = VecDeque::from(vec![
             Entry(r#"{"a":1}"#.to_string()),
             Entry(r#"{"b":2}"#.to_string()),
         ]);
         let result = manifold.join(&mut entries)?;
-        assert_eq!(*result, r#"[{"a":1},{"b":2}]"#);
+        assert_eq!(result, r#"[{"a":1},{"b":2}]"#);
         Ok(())
     }
 }
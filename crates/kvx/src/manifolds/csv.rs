@@ -0,0 +1,183 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+// ai
+//! 🎬 *[an analyst opens the export in a spreadsheet. it is one column wide and full of braces.]*
+//! *["can I just get a CSV?" they ask, for the ninth time. jq weeps quietly in a shell history.]*
+//!
+//! 📊 **CsvManifold** — joins docs into CSV rows, one per doc, columns from config.
+//!
+//! 🧠 Knowledge graph:
+//! - Used by: File and Custom sinks with `manifold = "csv"` — `[sink_config.File.csv]` lists the columns
+//! - Columns are field paths, so nested docs flatten on the way out: `address.city` is a column
+//! - Missing field / `null` → empty cell. Objects and arrays → their JSON text, quoted as needed
+//! - Quoting and escaping is the `csv` crate's job, not ours. RFC 4180, `\n` line endings
+//! - The header row is written once per file by the File sink (`CsvConfig::header_line`) — a
+//!   manifold only sees one payload at a time, and every payload isn't the first one 🦆
+
+use std::collections::VecDeque;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Manifold;
+use crate::transforms::field_path::FieldPath;
+use crate::{Entry, Payload};
+
+fn default_header() -> bool {
+    true
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+/// 📊 `[sink_config.File.csv]` — which fields become columns, and how the file is framed.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CsvConfig {
+    /// 🔎 One field path per column, in output order (`id`, `address.city`)
+    pub columns: Vec<String>,
+    /// 🏷️ Write the column names as the first row. Default `true`
+    #[serde(default = "default_header")]
+    pub header: bool,
+    /// ✂️ Cell separator — one ASCII character. Default `,` (`"\t"` for TSV)
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+}
+
+impl CsvConfig {
+    /// 🏷️ The header row, `\n` included — or `None` when `header = false`.
+    pub fn header_line(&self) -> Result<Option<String>> {
+        if !self.header {
+            return Ok(None);
+        }
+        let mut the_writer = self.writer(Vec::new())?;
+        the_writer.write_record(&self.columns)?;
+        finish(the_writer).map(Some)
+    }
+
+    /// ✍️ A csv writer with our delimiter and `\n` rows, appending to `into`.
+    fn writer(&self, into: Vec<u8>) -> Result<::csv::Writer<Vec<u8>>> {
+        if !self.delimiter.is_ascii() {
+            anyhow::bail!("💀 csv.delimiter '{}' isn't a single ASCII character", self.delimiter);
+        }
+        Ok(::csv::WriterBuilder::new()
+            .delimiter(self.delimiter as u8)
+            .terminator(::csv::Terminator::Any(b'\n'))
+            .from_writer(into))
+    }
+}
+
+// -- ┌─────────────────────────────────────────────────────────┐
+// -- │  CsvManifold                                             │
+// -- │  Struct → impl Manifold → tests                          │
+// -- └─────────────────────────────────────────────────────────┘
+
+/// 📊 `1,Oslo\n2,"Bergen, Norway"\n` — one row per doc, no header (the sink writes that once).
+#[derive(Debug, Clone)]
+pub struct CsvManifold {
+    config: CsvConfig,
+    fields: Vec<FieldPath>,
+}
+
+impl CsvManifold {
+    /// 🔨 Validate the column list and the delimiter up front.
+    pub fn new(config: &CsvConfig) -> Result<Self> {
+        if config.columns.is_empty() {
+            anyhow::bail!("💀 csv.columns is empty — list the fields to export, e.g. `columns = [\"id\", \"name\"]`");
+        }
+        config.writer(Vec::new())?;
+        let fields = config
+            .columns
+            .iter()
+            .map(|the_column| FieldPath::parse(the_column).with_context(|| format!("💀 csv column '{}'", the_column)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { config: config.clone(), fields })
+    }
+}
+
+impl Manifold for CsvManifold {
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload> {
+        // -- 🧾 the csv writer wants bytes; it gets the pooled buffer's allocation
+        let mut the_bytes = std::mem::take(buffer).into_bytes();
+        the_bytes.clear();
+        the_bytes.reserve(entries.iter().map(|e| e.len()).sum());
+        let mut the_writer = self.config.writer(the_bytes)?;
+        let mut the_row: Vec<String> = Vec::with_capacity(self.fields.len());
+        for entry in entries.drain(..) {
+            let the_doc: Value = serde_json::from_str(&entry).with_context(|| {
+                format!("💀 csv needs one JSON doc per entry, got: {}", entry.chars().take(120).collect::<String>())
+            })?;
+            the_row.clear();
+            the_row.extend(self.fields.iter().map(|the_field| cell(the_field.get(&the_doc))));
+            the_writer.write_record(&the_row)?;
+        }
+        Ok(Payload::from(finish(the_writer)?))
+    }
+}
+
+/// 🔤 One cell: strings as they are, `null`/missing as nothing, the rest as JSON text.
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(the_rest) => the_rest.to_string(),
+    }
+}
+
+fn finish(writer: ::csv::Writer<Vec<u8>>) -> Result<String> {
+    let the_bytes = writer.into_inner().map_err(|e| anyhow::anyhow!("💀 csv flush failed: {}", e.error()))?;
+    // -- ✅ every cell came from a `&str`, so this is UTF-8 all the way down
+    Ok(String::from_utf8(the_bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn the_columns(columns: &[&str]) -> CsvConfig {
+        CsvConfig { columns: columns.iter().map(|c| c.to_string()).collect(), header: true, delimiter: ',' }
+    }
+
+    #[test]
+    fn csv_the_one_where_docs_become_rows() -> Result<()> {
+        // 🧪 nested fields flatten into columns, commas and quotes get quoted, gaps stay empty
+        let manifold = CsvManifold::new(&the_columns(&["id", "address.city", "tags", "vip"]))?;
+        let mut entries = VecDeque::from(vec![
+            Entry(r#"{"id":1,"address":{"city":"Bergen, Norway"},"tags":["a","b"],"vip":true}"#.to_string()),
+            Entry(r#"{"id":2,"address":{"city":"Say \"cheese\""}}"#.to_string()),
+        ]);
+        let result = manifold.join(&mut entries)?;
+        assert_eq!(
+            result,
+            "1,\"Bergen, Norway\",\"[\"\"a\"\",\"\"b\"\"]\",true\n2,\"Say \"\"cheese\"\"\",,\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn csv_the_one_where_the_header_is_written_once_by_someone_else() -> Result<()> {
+        let the_config = the_columns(&["id", "address.city"]);
+        assert_eq!(the_config.header_line()?.as_deref(), Some("id,address.city\n"));
+        assert_eq!(CsvConfig { header: false, ..the_config }.header_line()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn csv_the_one_where_tabs_make_it_a_tsv() -> Result<()> {
+        let the_config = CsvConfig { delimiter: '\t', ..the_columns(&["a", "b"]) };
+        let manifold = CsvManifold::new(&the_config)?;
+        let mut entries = VecDeque::from(vec![Entry(r#"{"a":"x","b":2}"#.to_string())]);
+        assert_eq!(manifold.join(&mut entries)?, "x\t2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn csv_the_one_where_the_config_is_checked_up_front() {
+        assert!(CsvManifold::new(&the_columns(&[])).is_err());
+        assert!(CsvManifold::new(&the_columns(&["a..b"])).is_err());
+        assert!(CsvManifold::new(&CsvConfig { delimiter: '→', ..the_columns(&["a"]) }).is_err());
+    }
+}
@@ -14,14 +14,28 @@
 //! - Zero serde on the framing: just `[`, commas, `]`, assembled by hand like artisans
 //! - Items inside are already valid JSON strings from casters — we trust them
 //! - Capacity math: 2 (brackets) + sum(item lengths) + (n-1) commas — exact, no vibes needed
+//! - `PrettyJsonArrayManifold` (`manifold = "pretty"`) is the human-readable cousin: same array,
+//!   each item re-indented. That one does need serde — you can't indent what you haven't parsed
+//! - `ArrayFraming` spells out each one's brackets and separators, so the File sink can splice
+//!   payloads into one array per file without guessing
 //!
 //! 🦆 The duck asked why we don't use serde. We said "trust the process." It nodded.
 
 use super::Manifold;
 use crate::{Entry, Payload};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::collections::VecDeque;
 
+/// 🧱 How an array manifold frames its items: what opens it, what sits between items, what closes
+/// it — and what a whole file with no items at all looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayFraming {
+    pub open: &'static str,
+    pub separator: &'static str,
+    pub close: &'static str,
+    pub empty: &'static str,
+}
+
 // -- ┌─────────────────────────────────────────────────────────┐
 // -- │  JsonArrayManifold                                       │
 // -- │  Struct → impl Manifold → tests                          │
@@ -41,16 +55,23 @@ use std::collections::VecDeque;
 #[derive(Debug, Clone, Copy)]
 pub struct JsonArrayManifold;
 
+impl JsonArrayManifold {
+    /// 🧱 `[a,b]`
+    pub const FRAMING: ArrayFraming = ArrayFraming { open: "[", separator: ",", close: "]", empty: "[]" };
+}
+
 impl Manifold for JsonArrayManifold {
     #[inline]
-    fn join(&self, entries: &mut VecDeque<Entry>) -> Result<Payload> {
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload> {
         // -- 🧮 Pre-allocate: brackets(2) + sum of entries + commas(max n-1).
-        // -- This is exact capacity — no growth, no realloc, no drama.
+        // -- This is exact capacity (or less, with a pooled buffer) — no growth, no realloc, no drama.
         // -- No cap this capacity math slaps fr fr 🎯
         let commas = entries.len().saturating_sub(1);
         let estimated_size: usize =
             2 + entries.iter().map(|e| e.len()).sum::<usize>() + commas;
-        let mut payload = String::with_capacity(estimated_size);
+        let mut payload = std::mem::take(buffer);
+        payload.clear();
+        payload.reserve(estimated_size);
         payload.push('[');
         for (i, entry) in entries.drain(..).enumerate() {
             if i > 0 {
@@ -61,7 +82,49 @@ impl Manifold for JsonArrayManifold {
         }
         payload.push(']');
         // -- ✅ Valid JSON array. No serde was harmed in the making of this string.
-        Ok(Payload(payload))
+        Ok(Payload::from(payload))
+    }
+}
+
+/// 🖨️ Pretty JSON array — `[\n  {\n    "a": 1\n  },\n  ...\n]\n` — for exports a human will read.
+///
+/// Each entry is parsed and pretty-printed, then indented one level to sit inside the array.
+/// Key order survives the round trip (`preserve_order` is on). Slower than `JsonArrayManifold`
+/// by a parse per doc — fine for the small exports this is meant for.
+#[derive(Debug, Clone, Copy)]
+pub struct PrettyJsonArrayManifold;
+
+impl PrettyJsonArrayManifold {
+    /// 🧱 One item per indented block, closing bracket on its own line
+    pub const FRAMING: ArrayFraming = ArrayFraming { open: "[\n", separator: ",\n", close: "\n]\n", empty: "[]\n" };
+}
+
+impl Manifold for PrettyJsonArrayManifold {
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload> {
+        let the_framing = Self::FRAMING;
+        let mut payload = std::mem::take(buffer);
+        payload.clear();
+        // -- 🧮 indentation roughly doubles a doc; a guess, not a promise
+        payload.reserve(the_framing.open.len() + entries.iter().map(|e| e.len() * 2).sum::<usize>() + the_framing.close.len());
+        payload.push_str(the_framing.open);
+        for (i, entry) in entries.drain(..).enumerate() {
+            if i > 0 {
+                payload.push_str(the_framing.separator);
+            }
+            let the_doc: serde_json::Value = serde_json::from_str(&entry).with_context(|| {
+                format!("💀 pretty needs one JSON doc per entry, got: {}", entry.chars().take(120).collect::<String>())
+            })?;
+            // -- 📐 JSON strings can't hold a raw newline, so every `\n` here is layout — safe to indent after
+            for (j, the_line) in serde_json::to_string_pretty(&the_doc)?.lines().enumerate() {
+                if j > 0 {
+                    payload.push('\n');
+                }
+                payload.push_str("  ");
+                payload.push_str(the_line);
+            }
+        }
+        payload.push_str(the_framing.close);
+        Ok(Payload::from(payload))
     }
 }
 
@@ -79,7 +142,7 @@ mod tests {
             Entry(r#"{"doc":3}"#.to_string()),
         ]);
         let result = manifold.join(&mut entries)?;
-        assert_eq!(*result, r#"[{"doc":1},{"doc":2},{"doc":3}]"#);
+        assert_eq!(result, r#"[{"doc":1},{"doc":2},{"doc":3}]"#);
         assert!(entries.is_empty(), "🎯 drain(..) should leave the VecDeque empty but allocated");
         Ok(())
     }
@@ -90,7 +153,25 @@ mod tests {
         let manifold = JsonArrayManifold;
         let mut entries = VecDeque::new();
         let result = manifold.join(&mut entries)?;
-        assert_eq!(*result, "[]");
+        assert_eq!(result, "[]");
+        Ok(())
+    }
+
+    #[test]
+    fn json_array_the_one_where_humans_get_indentation() -> Result<()> {
+        // 🧪 two docs → an indented array, nested objects indented one more level
+        let manifold = PrettyJsonArrayManifold;
+        let mut entries = VecDeque::from(vec![
+            Entry(r#"{"doc":1,"who":{"name":"a"}}"#.to_string()),
+            Entry(r#"{"doc":2}"#.to_string()),
+        ]);
+        let result = manifold.join(&mut entries)?;
+        assert_eq!(
+            result,
+            "[\n  {\n    \"doc\": 1,\n    \"who\": {\n      \"name\": \"a\"\n    }\n  },\n  {\n    \"doc\": 2\n  }\n]\n"
+        );
+        let the_roundtrip: serde_json::Value = serde_json::from_str(&result)?;
+        assert_eq!(the_roundtrip[1]["doc"], 2);
         Ok(())
     }
 
@@ -100,7 +181,7 @@ mod tests {
         let manifold = JsonArrayManifold;
         let mut entries = VecDeque::from(vec![Entry(r#"{"lonely":true}"#.to_string())]);
         let result = manifold.join(&mut entries)?;
-        assert_eq!(*result, r#"[{"lonely":true}]"#);
+        assert_eq!(result, r#"[{"lonely":true}]"#);
         Ok(())
     }
 }
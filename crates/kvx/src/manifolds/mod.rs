@@ -16,6 +16,9 @@
 //! 🧠 Knowledge graph:
 //! - **NDJSON** (`NdjsonManifold`): `\n`-delimited. Used by ES `/_bulk` and file sinks.
 //! - **JSON Array** (`JsonArrayManifold`): `[item,item,item]`. Used by in-memory sinks for testing.
+//! - **Pretty JSON Array** (`PrettyJsonArrayManifold`): the same, indented. Opt-in, for humans.
+//! - **SQL INSERT** (`SqlInsertManifold`): batched `INSERT INTO ... VALUES ...;`. Opt-in, for load scripts.
+//! - **CSV** (`CsvManifold`): one row per doc, columns from config. Opt-in, for spreadsheets.
 //! - **Dispatcher** (`ManifoldBackend`): resolved from `SinkConfig`. Same pattern as casts/backends.
 //! - Resolution: from `SinkConfig`, same pattern as backends and casts.
 //!
@@ -34,13 +37,17 @@ use crate::Payload;
 use std::collections::VecDeque;
 
 pub mod backend;
+pub mod csv;
 pub mod json_array;
 pub mod ndjson;
+pub mod sql_insert;
 
 // -- 🔁 Re-export concrete types so consumers use `crate::manifolds::ManifoldBackend` unchanged
-pub use backend::ManifoldBackend;
-pub use json_array::JsonArrayManifold;
+pub use backend::{ManifoldBackend, ManifoldFormat};
+pub use csv::{CsvConfig, CsvManifold};
+pub use json_array::{ArrayFraming, JsonArrayManifold, PrettyJsonArrayManifold};
 pub use ndjson::NdjsonManifold;
+pub use sql_insert::{SqlInsertConfig, SqlInsertManifold};
 
 // ===== Trait =====
 
@@ -60,5 +67,11 @@ pub trait Manifold: std::fmt::Debug {
     /// The input feeds are raw source data (un-cast). The caster is called
     /// per-feed to produce a transformed String. The manifold then joins all results
     /// in the wire format (NDJSON, JSON array, etc.).
-    fn join(&self, entries: &mut VecDeque<Entry>) -> Result<Payload>;
+    fn join(&self, entries: &mut VecDeque<Entry>) -> Result<Payload> {
+        self.join_with(entries, &mut String::new())
+    }
+
+    /// ♻️ Same as `join`, composing into `buffer` (a pooled one, usually) instead of a fresh
+    /// allocation. The payload takes the buffer; a manifold that doesn't need it leaves it be.
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload>;
 }
@@ -41,23 +41,39 @@ pub struct NdjsonManifold;
 
 impl Manifold for NdjsonManifold {
     #[inline]
-    fn join(&self, entries: &mut VecDeque<Entry>) -> Result<Payload> {
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload> {
+        // -- 🚶 One entry (a passthrough page that filled the payload by itself): hand its
+        // -- String straight to the Payload. No concatenation, no copy, and `buffer` stays put
+        if entries.len() == 1
+            && let Some(Entry(mut the_only_one)) = entries.pop_front()
+        {
+            if !the_only_one.is_empty() && !the_only_one.ends_with('\n') {
+                the_only_one.push('\n');
+            }
+            return Ok(Payload::from(the_only_one));
+        }
+
         // -- 🧮 Pre-allocate based on total entry bytes — a vibes-based estimate that's usually close
         // -- Knowledge graph: +1 per entry for the \n separator, because math is caring
         let estimated_size: usize = entries.iter().map(|e| e.len() + 1).sum();
-        let mut payload = String::with_capacity(estimated_size);
+        let mut payload = std::mem::take(buffer);
+        payload.clear();
+        payload.reserve(estimated_size);
 
         for entry in entries.drain(..) {
             // -- 🔄 Each entry is already cast — just stitch them together with newlines
             // -- Like a quilt, but made of JSON, and nobody finds it cozy
             payload.push_str(&entry);
-            // We expect each entry to have \n if it's being casted to bulk
-            // payload.push('\n');
+            // -- 🧵 bulk entries bring their own \n; a passthrough feed ends on its last doc.
+            // -- Without this, feed two starts on feed one's last line: `{..}{..}`
+            if !entry.is_empty() && !entry.ends_with('\n') {
+                payload.push('\n');
+            }
         }
 
         // -- ✅ Trailing \n included — ES bulk requires it, files appreciate it, nobody complains.
         // -- Ancient proverb: "He who omits the trailing newline, debugs at 3am."
-        Ok(Payload(payload))
+        Ok(Payload::from(payload))
     }
 }
 
@@ -71,7 +87,7 @@ mod tests {
         let manifold = NdjsonManifold;
         let mut entries = VecDeque::from(vec![Entry("{\"doc\":1}\n".to_string())]);
         let result = manifold.join(&mut entries)?;
-        assert_eq!(*result, "{\"doc\":1}\n");
+        assert_eq!(result, "{\"doc\":1}\n");
         assert!(entries.is_empty(), "🎯 drain(..) should leave the VecDeque empty but allocated");
         Ok(())
     }
@@ -85,7 +101,32 @@ mod tests {
             Entry("{\"doc\":2}\n".to_string()),
         ]);
         let result = manifold.join(&mut entries)?;
-        assert_eq!(*result, "{\"doc\":1}\n{\"doc\":2}\n");
+        assert_eq!(result, "{\"doc\":1}\n{\"doc\":2}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn ndjson_the_one_where_passthrough_feeds_do_not_hold_hands() -> Result<()> {
+        // 🧪 Passthrough feeds end on their last doc, no \n — the join adds one between feeds
+        let mut entries = VecDeque::from(vec![
+            Entry("{\"doc\":1}\n{\"doc\":2}".to_string()),
+            Entry("{\"doc\":3}".to_string()),
+        ]);
+        let result = NdjsonManifold.join(&mut entries)?;
+        assert_eq!(result, "{\"doc\":1}\n{\"doc\":2}\n{\"doc\":3}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn ndjson_the_one_where_a_lone_page_keeps_its_buffer() -> Result<()> {
+        // 🧪 One entry already ending in \n becomes the payload as-is — same allocation, no copy
+        let the_page = "{\"doc\":1}\n{\"doc\":2}\n".to_string();
+        let the_address = the_page.as_ptr();
+        let mut entries = VecDeque::from(vec![Entry(the_page)]);
+        let result = NdjsonManifold.join(&mut entries)?;
+        assert_eq!(result, "{\"doc\":1}\n{\"doc\":2}\n");
+        assert_eq!(result.as_ptr(), the_address, "🚶 moved, not copied");
+        assert!(entries.is_empty());
         Ok(())
     }
 
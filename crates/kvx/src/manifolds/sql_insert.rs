@@ -0,0 +1,236 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+// ai
+//! 🎬 *[a JSON document walks into a relational database. the bouncer asks for its columns.]*
+//! *["I have fields," it says. "nested ones." the bouncer points at the mapping table.]*
+//!
+//! 🗄️ **SqlInsertManifold** — joins docs into batched `INSERT INTO ... VALUES ...;` statements.
+//!
+//! 🧠 Knowledge graph:
+//! - Used by: File and Custom sinks with `manifold = "sql_insert"` — a load script, not a wire format
+//! - Config: `[sink_config.File.sql_insert]` — `table`, `columns = { column = "field.path" }`
+//! - Entries must be one JSON doc each (the caster splits pages for us, same as `json_array`)
+//! - Missing field → `NULL`. Objects and arrays → their JSON text, as a string literal
+//! - Identifiers are ANSI double-quoted. MySQL wants `ANSI_QUOTES` for that — or backticks, which
+//!   nobody has asked for yet 🦆
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Manifold;
+use crate::transforms::field_path::FieldPath;
+use crate::{Entry, Payload};
+
+/// 🗄️ `[sink_config.File.sql_insert]` — where the rows go and which fields fill them.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct SqlInsertConfig {
+    /// 🏷️ Target table. `schema.table` works — each part gets quoted on its own
+    pub table: String,
+    /// 🔀 Column name → document field path (`user.email`). Columns are written in name order
+    pub columns: BTreeMap<String, String>,
+    /// 📏 Rows per `INSERT` statement (default 1000) — most databases cap statement size
+    #[serde(default)]
+    pub rows_per_statement: Option<usize>,
+}
+
+// -- ┌─────────────────────────────────────────────────────────┐
+// -- │  SqlInsertManifold                                       │
+// -- │  Struct → impl Manifold → tests                          │
+// -- └─────────────────────────────────────────────────────────┘
+
+/// 🗄️ `INSERT INTO "t" ("a","b") VALUES\n(1,'x'),\n(2,'y');\n` — one statement per batch of rows.
+///
+/// The table and column list are rendered once at config time; only the `VALUES` tuples are
+/// built per doc. Each entry is parsed, its mapped fields pulled out, and rendered as SQL literals.
+#[derive(Debug, Clone)]
+pub struct SqlInsertManifold {
+    /// 🧾 `INSERT INTO "t" ("a","b") VALUES` — the same for every statement, so built once
+    statement_head: String,
+    fields: Vec<FieldPath>,
+    rows_per_statement: usize,
+}
+
+impl SqlInsertManifold {
+    /// 📏 Rows per statement when the config doesn't say
+    pub const DEFAULT_ROWS_PER_STATEMENT: usize = 1000;
+
+    /// 🔨 Validate the mapping and pre-render the statement head.
+    pub fn new(config: &SqlInsertConfig) -> Result<Self> {
+        if config.table.trim().is_empty() {
+            anyhow::bail!("💀 sql_insert.table is empty — the rows need somewhere to go");
+        }
+        if config.columns.is_empty() {
+            anyhow::bail!("💀 sql_insert.columns is empty — map at least one column to a field, e.g. `id = \"id\"`");
+        }
+        let rows_per_statement = config.rows_per_statement.unwrap_or(Self::DEFAULT_ROWS_PER_STATEMENT);
+        if rows_per_statement == 0 {
+            anyhow::bail!("💀 sql_insert.rows_per_statement = 0 would write statements with no rows");
+        }
+        let fields = config
+            .columns
+            .iter()
+            .map(|(the_column, the_field)| {
+                FieldPath::parse(the_field).with_context(|| format!("💀 sql_insert column '{}'", the_column))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let the_table = config.table.split('.').map(quote_identifier).collect::<Vec<_>>().join(".");
+        let the_columns = config.columns.keys().map(|c| quote_identifier(c)).collect::<Vec<_>>().join(",");
+        Ok(Self {
+            statement_head: format!("INSERT INTO {} ({}) VALUES", the_table, the_columns),
+            fields,
+            rows_per_statement,
+        })
+    }
+
+    /// 🧱 One `(v1,v2,...)` tuple for one doc.
+    fn push_row(&self, payload: &mut String, entry: &str) -> Result<()> {
+        let the_doc: Value = serde_json::from_str(entry).with_context(|| {
+            format!(
+                "💀 sql_insert needs one JSON doc per entry, got: {}",
+                entry.chars().take(120).collect::<String>()
+            )
+        })?;
+        payload.push('(');
+        for (i, the_field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                payload.push(',');
+            }
+            push_literal(payload, the_field.get(&the_doc).unwrap_or(&Value::Null));
+        }
+        payload.push(')');
+        Ok(())
+    }
+}
+
+impl Manifold for SqlInsertManifold {
+    fn join_with(&self, entries: &mut VecDeque<Entry>, buffer: &mut String) -> Result<Payload> {
+        let mut payload = std::mem::take(buffer);
+        payload.clear();
+        // -- 🧮 rough guess: a tuple is about as long as its doc, plus a head per statement
+        let the_statements = entries.len().div_ceil(self.rows_per_statement);
+        payload.reserve(
+            entries.iter().map(|e| e.len() + 3).sum::<usize>() + the_statements * (self.statement_head.len() + 2),
+        );
+        for (i, entry) in entries.drain(..).enumerate() {
+            let the_row_in_statement = i % self.rows_per_statement;
+            if the_row_in_statement == 0 {
+                if i > 0 {
+                    payload.push_str(";\n");
+                }
+                payload.push_str(&self.statement_head);
+                payload.push('\n');
+            } else {
+                payload.push_str(",\n");
+            }
+            self.push_row(&mut payload, &entry)?;
+        }
+        // -- ✅ nothing came in, nothing goes out — an empty payload, not a bare `;`
+        if !payload.is_empty() {
+            payload.push_str(";\n");
+        }
+        Ok(Payload::from(payload))
+    }
+}
+
+/// 🏷️ `name` → `"name"`, with embedded quotes doubled.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// 🔤 A JSON value as an SQL literal. Strings get single quotes (doubled inside), objects and
+/// arrays go in as their JSON text.
+fn push_literal(payload: &mut String, value: &Value) {
+    match value {
+        Value::Null => payload.push_str("NULL"),
+        Value::Bool(true) => payload.push_str("TRUE"),
+        Value::Bool(false) => payload.push_str("FALSE"),
+        Value::Number(n) => {
+            let _ = write!(payload, "{}", n);
+        }
+        Value::String(s) => push_string_literal(payload, s),
+        Value::Array(_) | Value::Object(_) => push_string_literal(payload, &value.to_string()),
+    }
+}
+
+fn push_string_literal(payload: &mut String, s: &str) {
+    payload.push('\'');
+    for c in s.chars() {
+        if c == '\'' {
+            payload.push('\'');
+        }
+        payload.push(c);
+    }
+    payload.push('\'');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn the_people_table() -> SqlInsertConfig {
+        SqlInsertConfig {
+            table: "crm.people".into(),
+            columns: BTreeMap::from([
+                ("id".to_string(), "id".to_string()),
+                ("email".to_string(), "contact.email".to_string()),
+                ("tags".to_string(), "tags".to_string()),
+            ]),
+            rows_per_statement: Some(2),
+        }
+    }
+
+    #[test]
+    fn sql_insert_the_one_where_docs_become_rows() -> Result<()> {
+        // 🧪 three docs, two rows per statement → two statements
+        let manifold = SqlInsertManifold::new(&the_people_table())?;
+        let mut entries = VecDeque::from(vec![
+            Entry(r#"{"id":1,"contact":{"email":"o'brien@example.com"},"tags":["a"]}"#.to_string()),
+            Entry(r#"{"id":2,"tags":null}"#.to_string()),
+            Entry(r#"{"id":"3","contact":{"email":true}}"#.to_string()),
+        ]);
+        let result = manifold.join(&mut entries)?;
+        assert_eq!(
+            result,
+            "INSERT INTO \"crm\".\"people\" (\"email\",\"id\",\"tags\") VALUES\n\
+             ('o''brien@example.com',1,'[\"a\"]'),\n\
+             (NULL,2,NULL);\n\
+             INSERT INTO \"crm\".\"people\" (\"email\",\"id\",\"tags\") VALUES\n\
+             (TRUE,'3',NULL);\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sql_insert_the_one_where_nothing_in_means_nothing_out() -> Result<()> {
+        let manifold = SqlInsertManifold::new(&the_people_table())?;
+        assert_eq!(manifold.join(&mut VecDeque::new())?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn sql_insert_the_one_where_a_page_is_not_a_row() -> Result<()> {
+        // 🧪 two NDJSON lines in one entry isn't a doc — say so instead of writing garbage
+        let manifold = SqlInsertManifold::new(&the_people_table())?;
+        let mut entries = VecDeque::from(vec![Entry("{\"id\":1}\n{\"id\":2}\n".to_string())]);
+        assert!(manifold.join(&mut entries).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sql_insert_the_one_where_the_mapping_is_checked_up_front() {
+        let no_columns = SqlInsertConfig { table: "t".into(), ..Default::default() };
+        assert!(SqlInsertManifold::new(&no_columns).is_err());
+        let bad_path = SqlInsertConfig {
+            table: "t".into(),
+            columns: BTreeMap::from([("a".to_string(), "x..y".to_string())]),
+            rows_per_statement: None,
+        };
+        assert!(SqlInsertManifold::new(&bad_path).is_err());
+    }
+}
@@ -0,0 +1,414 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📣 notify.rs — telling somebody how it went, without making them read the logs.
+//!
+//! 🎬 *[the orchestrator launched the migration at 02:00. it has been polling a log file since.]*
+//! *[the log file says "MIGRATION COMPLETE!" at 03:41. the orchestrator is grepping for "DONE".]*
+//! *[it is 09:00. the alias switch that was supposed to follow is still waiting.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `run_with_shutdown` subscribes a `LastProgress` to the run's progress, and when the run
+//!   ends — success, failure, or a graceful stop — builds one `RunReport` from the final
+//!   `ProgressEvent`, the outcome, and `RunWarnings`
+//! - `[notify] webhook_url` gets that report POSTed as JSON
+//! - `[notify] slack_webhook_url` gets it as a Slack message: `slack_template` (or the built-in
+//!   one) with `{status}`, `{docs}`, `{warnings}`, `{dead_letter_file}`… filled in. On-call reads
+//!   Slack, not JSON
+//! - `[notify] on_success` / `on_failure` run a shell command (`sh -c`) with the report in
+//!   `KRAVEX_*` environment variables — alias switches, cache warms, cleanup. A stopped run
+//!   counts as a failure: half a load is no reason to switch an alias. Hooks run before the
+//!   webhooks, and a failing `on_success` fails the run, because the next step never happened
+//! - A failed notification is a warning in the log, never a failed run: the data already
+//!   landed (or didn't) and nothing we do here changes that 🦆
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::progress::{ProgressEvent, ProgressSubscriber};
+use crate::warnings::RunWarnings;
+
+// -- ⏱️ an orchestrator that can't answer in ten seconds can read about it in the logs
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// 💬 What Slack shows when `slack_template` isn't set.
+pub const DEFAULT_SLACK_TEMPLATE: &str = "{emoji} *kravex run {status}* — `{pipeline}`\n\
+{docs} docs · {bytes} · {duration}\n\
+⚠️ {warnings} warning(s) · 🪦 dead letters: {dead_letter_file}\n\
+💀 {error}";
+
+/// 📣 `[notify]` — who hears about the end of the run.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct NotifyConfig {
+    /// 🪝 Receives the `RunReport` as a JSON POST when the run ends
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// 💬 A Slack incoming-webhook URL — gets the report as a chat message
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+    /// 📝 The Slack message, with `{placeholders}` from `RunReport`. Unset: `DEFAULT_SLACK_TEMPLATE`
+    #[serde(default)]
+    pub slack_template: Option<String>,
+    /// ✅ Shell command to run after a successful run — its failure fails the run
+    #[serde(default)]
+    pub on_success: Option<String>,
+    /// 🚨 Shell command to run after a failed or stopped run
+    #[serde(default)]
+    pub on_failure: Option<String>,
+}
+
+/// 🏁 How the run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// ✅ Source exhausted, everything drained
+    Succeeded,
+    /// 💀 The run returned an error
+    Failed,
+    /// 🛑 A shutdown signal ended it early — cleanly, but not everything was read
+    Stopped,
+}
+
+/// 📋 The end-of-run summary every notifier gets.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub status: RunStatus,
+    /// 🏷️ `None` when the run failed before the pipeline started
+    pub pipeline: Option<String>,
+    pub docs: u64,
+    pub bytes: u64,
+    pub duration_secs: f64,
+    /// 💀 The error and its causes, one line — `None` unless `status` is `failed`
+    pub error: Option<String>,
+    /// ⚠️ `RunWarnings::to_json()` — `[{ "kind": .., "message": .. }]`
+    pub warnings: serde_json::Value,
+    /// 🪦 Where rejected docs went (`dead_letter_file` on the sink), if anywhere
+    pub dead_letter_file: Option<PathBuf>,
+}
+
+impl RunReport {
+    /// 🧾 Boil a finished run down to a report.
+    pub fn new(
+        outcome: &Result<()>,
+        stopped_early: bool,
+        last_progress: Option<ProgressEvent>,
+        elapsed: Duration,
+        warnings: &RunWarnings,
+    ) -> Self {
+        let status = match outcome {
+            Err(_) => RunStatus::Failed,
+            Ok(()) if stopped_early => RunStatus::Stopped,
+            Ok(()) => RunStatus::Succeeded,
+        };
+        Self {
+            status,
+            pipeline: last_progress.as_ref().map(|e| e.pipeline_name.clone()),
+            docs: last_progress.as_ref().map_or(0, |e| e.estimated_docs),
+            bytes: last_progress.as_ref().map_or(0, |e| e.bytes_drained),
+            duration_secs: elapsed.as_secs_f64(),
+            error: outcome.as_ref().err().map(|the_error| format!("{:#}", the_error)),
+            warnings: warnings.to_json(),
+            dead_letter_file: None,
+        }
+    }
+
+    /// 🪦 Point at the sink's dead letter file.
+    pub fn with_dead_letter_file(mut self, dead_letter_file: Option<PathBuf>) -> Self {
+        self.dead_letter_file = dead_letter_file;
+        self
+    }
+
+    /// 🌱 The report as `KRAVEX_*` environment variables for hooks. Missing values are empty.
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("KRAVEX_STATUS", self.render("{status}")),
+            ("KRAVEX_PIPELINE", self.pipeline.clone().unwrap_or_default()),
+            ("KRAVEX_DOCS", self.docs.to_string()),
+            ("KRAVEX_BYTES", self.bytes.to_string()),
+            ("KRAVEX_DURATION_SECS", format!("{:.3}", self.duration_secs)),
+            ("KRAVEX_WARNINGS", self.render("{warnings}")),
+            ("KRAVEX_ERROR", self.error.clone().unwrap_or_default()),
+            (
+                "KRAVEX_DEAD_LETTER_FILE",
+                self.dead_letter_file.as_ref().map(|p| p.display().to_string()).unwrap_or_default(),
+            ),
+        ]
+    }
+
+    /// 💬 Fill in a Slack template. Placeholders: `{emoji}`, `{status}`, `{pipeline}`, `{docs}`,
+    /// `{bytes}`, `{duration}`, `{warnings}` (a count), `{error}`, `{dead_letter_file}`.
+    /// Missing values read "none"; unknown placeholders are left as they are.
+    pub fn render(&self, template: &str) -> String {
+        let the_status = match self.status {
+            RunStatus::Succeeded => ("✅", "succeeded"),
+            RunStatus::Failed => ("🚨", "failed"),
+            RunStatus::Stopped => ("🛑", "stopped"),
+        };
+        let the_secs = self.duration_secs as u64;
+        let the_values = [
+            ("{emoji}", the_status.0.to_string()),
+            ("{status}", the_status.1.to_string()),
+            ("{pipeline}", self.pipeline.clone().unwrap_or_else(|| "none".to_string())),
+            ("{docs}", self.docs.to_string()),
+            ("{bytes}", format!("{:.1} MiB", self.bytes as f64 / MIB)),
+            ("{duration}", format!("{:02}:{:02}:{:02}", the_secs / 3600, the_secs / 60 % 60, the_secs % 60)),
+            ("{warnings}", self.warnings.as_array().map_or(0, Vec::len).to_string()),
+            ("{error}", self.error.clone().unwrap_or_else(|| "none".to_string())),
+            (
+                "{dead_letter_file}",
+                self.dead_letter_file.as_ref().map_or_else(|| "none".to_string(), |p| p.display().to_string()),
+            ),
+        ];
+        the_values
+            .iter()
+            .fold(template.to_string(), |the_text, (the_placeholder, the_value)| the_text.replace(the_placeholder, the_value))
+    }
+}
+
+/// 📬 Keeps the newest progress event — after the run, that's the `finished` one.
+#[derive(Debug, Default)]
+pub(crate) struct LastProgress(Mutex<Option<ProgressEvent>>);
+
+impl LastProgress {
+    pub(crate) fn take(&self) -> Option<ProgressEvent> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take()
+    }
+}
+
+impl ProgressSubscriber for LastProgress {
+    fn on_progress(&self, event: &ProgressEvent) {
+        *self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(event.clone());
+    }
+}
+
+/// 🪝 Run `on_success` or `on_failure`, whichever fits the report. A failed `on_success` is
+/// returned so the run fails with it; a failed `on_failure` is only logged — the run already failed.
+pub(crate) async fn run_hook(config: &NotifyConfig, report: &RunReport) -> Result<()> {
+    let (the_hook, the_name) = match report.status {
+        RunStatus::Succeeded => (&config.on_success, "on_success"),
+        RunStatus::Failed | RunStatus::Stopped => (&config.on_failure, "on_failure"),
+    };
+    let Some(the_command) = the_hook else {
+        return Ok(());
+    };
+    info!("🪝 Running {}: {}", the_name, the_command);
+    let the_outcome = run_command(the_command, report.env())
+        .await
+        .with_context(|| format!("💀 The {} hook failed", the_name));
+    match (&the_outcome, report.status) {
+        (Err(the_error), RunStatus::Failed | RunStatus::Stopped) => {
+            warn!("⚠️ {:#}", the_error);
+            Ok(())
+        }
+        _ => the_outcome,
+    }
+}
+
+/// 🐚 `sh -c command` (`cmd /C` on Windows) with `env` added; a non-zero exit is an error.
+async fn run_command(command: &str, env: Vec<(&'static str, String)>) -> Result<()> {
+    let mut the_process = if cfg!(windows) {
+        let mut the_cmd = std::process::Command::new("cmd");
+        the_cmd.arg("/C");
+        the_cmd
+    } else {
+        let mut the_cmd = std::process::Command::new("sh");
+        the_cmd.arg("-c");
+        the_cmd
+    };
+    the_process.arg(command).envs(env);
+    // -- 🧵 a hook can take minutes (cache warms do) — keep it off the async workers
+    let the_status = tokio::task::spawn_blocking(move || the_process.status())
+        .await?
+        .context("💀 Couldn't start the shell")?;
+    if !the_status.success() {
+        anyhow::bail!("💀 '{}' exited with {}", command, the_status);
+    }
+    Ok(())
+}
+
+/// 📣 Tell everyone `[notify]` names. Failures are logged, never returned.
+pub(crate) async fn announce(config: &NotifyConfig, report: &RunReport) {
+    if let Some(the_url) = &config.webhook_url {
+        match post_webhook(the_url, report).await {
+            Ok(()) => info!("📣 Run report sent to the webhook ({:?})", report.status),
+            Err(the_error) => warn!("⚠️ Couldn't deliver the run report to the webhook: {:#}", the_error),
+        }
+    }
+    if let Some(the_url) = &config.slack_webhook_url {
+        let the_template = config.slack_template.as_deref().unwrap_or(DEFAULT_SLACK_TEMPLATE);
+        match post_slack(the_url, &report.render(the_template)).await {
+            Ok(()) => info!("💬 Run report posted to Slack ({:?})", report.status),
+            Err(the_error) => warn!("⚠️ Couldn't post the run report to Slack: {:#}", the_error),
+        }
+    }
+}
+
+/// 🪝 POST the report as JSON; anything but a 2xx is an error.
+pub async fn post_webhook(url: &str, report: &RunReport) -> Result<()> {
+    post_json(url, serde_json::to_string(report)?, "webhook").await
+}
+
+/// 💬 Post `text` to a Slack incoming webhook.
+pub async fn post_slack(url: &str, text: &str) -> Result<()> {
+    post_json(url, serde_json::json!({ "text": text }).to_string(), "Slack webhook").await
+}
+
+async fn post_json(url: &str, body: String, who: &str) -> Result<()> {
+    let the_response = reqwest::Client::builder()
+        .timeout(NOTIFY_TIMEOUT)
+        .build()?
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .with_context(|| format!("💀 The {} never answered", who))?;
+    let the_status = the_response.status();
+    if !the_status.is_success() {
+        let the_body = the_response.text().await.unwrap_or_default();
+        anyhow::bail!("💀 The {} answered {}: '{}'", who, the_status, the_body);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::warnings::WarningKind;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn the_final_event() -> ProgressEvent {
+        ProgressEvent {
+            pipeline_name: "catalog.ndjson".to_string(),
+            bytes_drained: 4096,
+            estimated_docs: 42,
+            requests_completed: 2,
+            docs_per_sec: 0.0,
+            mib_per_sec: 0.0,
+            avg_latency_ms: 0,
+            max_latency_ms: 0,
+            last_latency_ms: 0,
+            avg_request_bytes: 0,
+            last_request_bytes: 0,
+            total_expected_bytes: None,
+            total_expected_docs: None,
+            elapsed: Duration::from_secs(3),
+            eta: None,
+            finished: true,
+            windows_done: 0,
+            windows_total: None,
+            drainers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn the_one_where_every_ending_gets_its_own_name() {
+        let the_warnings = RunWarnings::default();
+        let the_win = RunReport::new(&Ok(()), false, Some(the_final_event()), Duration::from_secs(3), &the_warnings);
+        assert_eq!(the_win.status, RunStatus::Succeeded);
+        assert_eq!((the_win.docs, the_win.bytes), (42, 4096));
+        assert_eq!(RunReport::new(&Ok(()), true, None, Duration::ZERO, &the_warnings).status, RunStatus::Stopped);
+
+        let the_loss = RunReport::new(
+            &Err(anyhow::anyhow!("connection refused").context("Failed to create sink backend")),
+            true,
+            None,
+            Duration::ZERO,
+            &the_warnings,
+        );
+        assert_eq!(the_loss.status, RunStatus::Failed, "💀 failing beats stopping");
+        assert_eq!(the_loss.error.as_deref(), Some("Failed to create sink backend: connection refused"));
+        assert_eq!(the_loss.pipeline, None, "🏷️ never got far enough to have a name");
+    }
+
+    #[tokio::test]
+    async fn the_one_where_the_orchestrator_hears_it_first() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/kravex"))
+            .and(body_partial_json(serde_json::json!({
+                "status": "succeeded",
+                "pipeline": "catalog.ndjson",
+                "docs": 42,
+                "bytes": 4096,
+                "error": null,
+                "warnings": [{ "kind": "data_quality", "message": "skipped 1 blank line" }]
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let the_warnings = RunWarnings::default();
+        the_warnings.record(WarningKind::DataQuality, "skipped 1 blank line");
+        let the_report = RunReport::new(&Ok(()), false, Some(the_final_event()), Duration::from_secs(3), &the_warnings);
+        post_webhook(&format!("{}/hooks/kravex", mock_server.uri()), &the_report).await?;
+        assert!(post_webhook(&format!("{}/nope", mock_server.uri()), &the_report).await.is_err(), "💀 a 404 is not a delivery");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn the_one_where_on_call_gets_the_news_in_slack() -> Result<()> {
+        let the_report = RunReport::new(
+            &Err(anyhow::anyhow!("3 docs rejected")),
+            false,
+            Some(the_final_event()),
+            Duration::from_secs(3725),
+            &RunWarnings::default(),
+        )
+        .with_dead_letter_file(Some(PathBuf::from("/var/kravex/rejects.ndjson")));
+        let the_default = the_report.render(DEFAULT_SLACK_TEMPLATE);
+        assert!(the_default.starts_with("🚨 *kravex run failed* — `catalog.ndjson`"), "🎯 got: {}", the_default);
+        assert!(the_default.contains("42 docs · 0.0 MiB · 01:02:05"));
+        assert!(the_default.contains("0 warning(s) · 🪦 dead letters: /var/kravex/rejects.ndjson"));
+        assert!(the_default.contains("💀 3 docs rejected"));
+        assert_eq!(the_report.render("{status} {mystery}"), "failed {mystery}");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/T0/B0/x"))
+            .and(body_partial_json(serde_json::json!({ "text": "failed: 42 docs" })))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let the_config = NotifyConfig {
+            slack_webhook_url: Some(format!("{}/services/T0/B0/x", mock_server.uri())),
+            slack_template: Some("{status}: {docs} docs".to_string()),
+            ..Default::default()
+        };
+        announce(&the_config, &the_report).await;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn the_one_where_the_next_step_runs_itself() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_receipt = the_dir.path().join("receipt.txt");
+        let the_config = NotifyConfig {
+            on_success: Some(format!("echo \"$KRAVEX_STATUS $KRAVEX_DOCS $KRAVEX_PIPELINE\" > '{}'", the_receipt.display())),
+            on_failure: Some("exit 3".to_string()),
+            ..Default::default()
+        };
+        let the_win = RunReport::new(&Ok(()), false, Some(the_final_event()), Duration::from_secs(3), &RunWarnings::default());
+        run_hook(&the_config, &the_win).await?;
+        assert_eq!(std::fs::read_to_string(&the_receipt)?, "succeeded 42 catalog.ndjson\n");
+
+        let the_loss = RunReport::new(&Err(anyhow::anyhow!("boom")), false, None, Duration::ZERO, &RunWarnings::default());
+        run_hook(&the_config, &the_loss).await?; // -- 🤷 on_failure failing is only logged
+
+        let the_broken_chain = NotifyConfig { on_success: Some("exit 3".to_string()), ..Default::default() };
+        let the_error = run_hook(&the_broken_chain, &the_win).await.expect_err("💀 the next step never happened");
+        assert!(format!("{:#}", the_error).contains("on_success"));
+        Ok(())
+    }
+}
@@ -0,0 +1,460 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🏗️ Pipeline — the builder for embedding kravex in your own Rust service.
+//!
+//! 🎬 *[a platform team wants to trigger migrations from their control plane.]*
+//! *["Just write a TOML file to /tmp and shell out to kvx," says someone.]*
+//! *[the room goes quiet. a builder pattern is proposed. everyone exhales.]*
+//!
+//! ```no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use kvx::backends::{SinkConfig, SourceConfig};
+//! use kvx::pipeline::Pipeline;
+//!
+//! Pipeline::builder()
+//!     .source(SourceConfig::InMemory(()))
+//!     .sink(SinkConfig::InMemory(()))
+//!     .run()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - The builder assembles an `AppConfig` — the same struct `load_config()` produces from TOML —
+//!   so embedded runs and CLI runs take the exact same code path (`run` / `run_with_shutdown`)
+//! - `build()` does what `load_config()` does after parsing: resolves `*_file` secrets
+//! - Omitted sections fall back to their `Default`s, same as omitted TOML tables
+//! - `custom_source` / `custom_sink` plug in any `Source` / `Sink` impl via a factory
+//!   (see `backends::custom` for the NDJSON page/payload contract)
+//! - Casting is picked from the (source, sink) pair, as in `run()`. `.transform(...)` appends
+//!   a `[[transforms]]` entry; they run in the order they were added
+//! - `register_transform` plugs in your own `Transform` under a name, for `Custom` entries
+//!   (see `transforms::registry`) 🦆
+
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+
+use crate::backends::{
+    CustomSinkConfig, CustomSourceConfig, Sink, SinkConfig, Source, SourceConfig,
+};
+use crate::config::{AppConfig, RuntimeConfig};
+use crate::notify::NotifyConfig;
+use crate::progress::{ProgressSubscriber, ProgressSubscribers};
+use crate::transforms::{Transform, TransformConfig, TransformRegistry};
+use crate::warnings::RunWarnings;
+use crate::workers::{DrainerConfig, FlowMasterConfig, PauseSwitch};
+
+/// 🚀 A fully assembled, ready-to-run migration.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    app_config: AppConfig,
+}
+
+impl Pipeline {
+    /// 🏗️ Start building. Source and sink are required; everything else has a default.
+    pub fn builder() -> PipelineBuilder {
+        PipelineBuilder::default()
+    }
+
+    /// 🔍 The config this pipeline will run with — handy for logging or asserting in tests.
+    pub fn config(&self) -> &AppConfig {
+        &self.app_config
+    }
+
+    /// ⚠️ The warning collector for this run — read it after `run` for the full list.
+    pub fn warnings(&self) -> RunWarnings {
+        self.app_config.warnings.clone()
+    }
+
+    /// 🚀 Run to completion. Same as `kvx::run`.
+    pub async fn run(self) -> Result<()> {
+        crate::run(self.app_config).await
+    }
+
+    /// 🛑 Run until done or until `shutdown_signal` resolves, then drain gracefully.
+    pub async fn run_with_shutdown<S>(self, shutdown_signal: S) -> Result<()>
+    where
+        S: Future<Output = ()> + Send + 'static,
+    {
+        crate::run_with_shutdown(self.app_config, shutdown_signal).await
+    }
+
+    /// ✂️ Run until done or until `cancel` is cancelled, then drain gracefully.
+    pub async fn run_with_cancellation(self, cancel: crate::CancellationToken) -> Result<()> {
+        crate::run_with_cancellation(self.app_config, cancel).await
+    }
+
+    /// 🩺 Dry-run checks without moving data. Same as `kvx::validate`.
+    pub async fn validate(&self) -> Result<()> {
+        crate::validate(&self.app_config).await
+    }
+}
+
+/// 🧱 Collects the pieces of a `Pipeline`. Each setter consumes and returns `self`.
+#[derive(Debug, Default)]
+pub struct PipelineBuilder {
+    source: Option<SourceConfig>,
+    sink: Option<SinkConfig>,
+    runtime: RuntimeConfig,
+    drainer: DrainerConfig,
+    flow_master: FlowMasterConfig,
+    transforms: Vec<TransformConfig>,
+    transform_registry: TransformRegistry,
+    warnings: RunWarnings,
+    progress: ProgressSubscribers,
+    pause: PauseSwitch,
+    notify: NotifyConfig,
+}
+
+impl PipelineBuilder {
+    /// 📡 Where documents come from.
+    pub fn source(mut self, source: SourceConfig) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// 🚰 Where documents go.
+    pub fn sink(mut self, sink: SinkConfig) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// 🧩 Bring-your-own source. `factory` is called once per run; pages must be NDJSON.
+    pub fn custom_source<F, S>(self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Result<S> + Send + Sync + 'static,
+        S: Source + Send + 'static,
+    {
+        self.source(SourceConfig::Custom(CustomSourceConfig {
+            name: name.into(),
+            factory: Arc::new(move || Ok(Box::new(factory()?) as Box<dyn Source + Send>)),
+            common_config: Default::default(),
+        }))
+    }
+
+    /// 🧩 Bring-your-own sink. `factory` is called once per drainer; payloads arrive as NDJSON.
+    pub fn custom_sink<F, S>(self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Result<S> + Send + Sync + 'static,
+        S: Sink + Send + 'static,
+    {
+        self.sink(SinkConfig::Custom(CustomSinkConfig {
+            name: name.into(),
+            factory: Arc::new(move || Ok(Box::new(factory()?) as Box<dyn Sink + Send>)),
+            common_config: Default::default(),
+        }))
+    }
+
+    /// 🧵 Channel capacities and worker counts.
+    pub fn runtime(mut self, runtime: RuntimeConfig) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// 🔄 Retry/backoff for the drainers.
+    pub fn drainer(mut self, drainer: DrainerConfig) -> Self {
+        self.drainer = drainer;
+        self
+    }
+
+    /// 🎛️ Static or regulated payload sizing.
+    pub fn flow_master(mut self, flow_master: FlowMasterConfig) -> Self {
+        self.flow_master = flow_master;
+        self
+    }
+
+    /// 🔧 Append a per-document transform. Call it again for the next step in the chain.
+    pub fn transform(mut self, transform: TransformConfig) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
+    /// 🧩 Make your own `Transform` available to `Custom = { name = .. }` entries, from TOML or
+    /// `.transform(..)`. `factory` gets the entry's `settings` and is called once per entry.
+    pub fn register_transform<F, T>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&serde_json::Value) -> Result<T> + Send + Sync + 'static,
+        T: Transform + Send + Sync + 'static,
+    {
+        self.transform_registry.register(
+            name,
+            Arc::new(move |settings| Ok(Box::new(factory(settings)?) as Box<dyn Transform + Send + Sync>)),
+        );
+        self
+    }
+
+    /// 📣 Who hears how the run ended (`[notify]`).
+    pub fn notify(mut self, notify: NotifyConfig) -> Self {
+        self.notify = notify;
+        self
+    }
+
+    /// ⚠️ Share a warning collector the embedder already holds (e.g. one per job).
+    pub fn warnings(mut self, warnings: RunWarnings) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// 📬 Receive structured progress events (bytes, docs, rates, ETA) every tick, plus a final one.
+    pub fn progress_subscriber(mut self, subscriber: Arc<dyn ProgressSubscriber>) -> Self {
+        self.progress.subscribe(subscriber);
+        self
+    }
+
+    /// 🙈 Skip the terminal progress bar — for embedders rendering progress themselves.
+    pub fn hide_terminal_progress(mut self) -> Self {
+        self.progress.hide_terminal();
+        self
+    }
+
+    /// ⏸️ Share a pause switch: while it's `true`, no new pages are read.
+    pub fn pause_switch(mut self, pause: PauseSwitch) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// ✅ Assemble the `Pipeline`. Fails if source or sink is missing, or a secret file can't be read.
+    pub fn build(self) -> Result<Pipeline> {
+        let mut source_config = self
+            .source
+            .context("💀 Pipeline has no source. Call .source(...) — we can't migrate data from the void.")?;
+        let mut sink_config = self
+            .sink
+            .context("💀 Pipeline has no sink. Call .sink(...) — the data needs somewhere to go.")?;
+        source_config
+            .resolve_secret_files()
+            .context("💀 Failed to load source credentials from file")?;
+        sink_config
+            .resolve_secret_files()
+            .context("💀 Failed to load sink credentials from file")?;
+
+        Ok(Pipeline {
+            app_config: AppConfig {
+                source_config,
+                sink_config,
+                runtime: self.runtime,
+                drainer: self.drainer,
+                flow_master: self.flow_master,
+                transforms: self.transforms,
+                warnings: self.warnings,
+                progress: self.progress,
+                transform_registry: self.transform_registry,
+                pause: self.pause,
+                failed_docs: Default::default(),
+                notify: self.notify,
+            },
+        })
+    }
+
+    /// 🚀 `build()` then `run()` — the one-liner for embedders who don't need the `Pipeline`.
+    pub async fn run(self) -> Result<()> {
+        self.build()?.run().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Page, Payload};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// 🧪 A "proprietary" source: two NDJSON pages, then EOF.
+    #[derive(Debug)]
+    struct MainframeSource {
+        the_pages_left: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl Source for MainframeSource {
+        async fn pump(&mut self) -> Result<Option<Page>> {
+            Ok(self.the_pages_left.pop().map(|p| Page(p.to_string())))
+        }
+    }
+
+    /// 🧪 A "proprietary" sink: stashes every payload in a shared list.
+    #[derive(Debug)]
+    struct VaultSink {
+        the_vault: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Sink for VaultSink {
+        async fn drain(&mut self, payload: Payload) -> Result<()> {
+            self.the_vault.lock().unwrap().push(payload.into_string());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 🧪 Custom source → custom sink, no fork required. Pages are NDJSON, payloads are NDJSON.
+    #[tokio::test]
+    async fn the_one_where_the_mainframe_talks_to_the_vault() -> Result<()> {
+        let the_vault = Arc::new(Mutex::new(Vec::new()));
+        let the_vault_for_factory = the_vault.clone();
+
+        Pipeline::builder()
+            .custom_source("mainframe", || {
+                Ok(MainframeSource { the_pages_left: vec!["{\"b\":2}\n", "{\"a\":1}\n"] })
+            })
+            .custom_sink("vault", move || Ok(VaultSink { the_vault: the_vault_for_factory.clone() }))
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .run()
+            .await?;
+
+        let the_everything: String = the_vault.lock().unwrap().concat();
+        assert!(the_everything.contains("{\"a\":1}"), "🎯 got: {}", the_everything);
+        assert!(the_everything.contains("{\"b\":2}"), "🎯 got: {}", the_everything);
+        Ok(())
+    }
+
+    /// 🧪 `.transform(..)` renames fields on the way through — custom in, custom out, no Rust written.
+    #[tokio::test]
+    async fn the_one_where_the_vault_only_accepts_lowercase_ids() -> Result<()> {
+        let the_vault = Arc::new(Mutex::new(Vec::new()));
+        let the_vault_for_factory = the_vault.clone();
+
+        Pipeline::builder()
+            .custom_source("mainframe", || Ok(MainframeSource { the_pages_left: vec!["{\"ObjectID\":1,\"Name\":\"a\"}\n"] }))
+            .custom_sink("vault", move || Ok(VaultSink { the_vault: the_vault_for_factory.clone() }))
+            .transform(TransformConfig::FieldMap(crate::transforms::FieldMapConfig {
+                rename: [("ObjectID".to_string(), "id".to_string())].into(),
+            }))
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .run()
+            .await?;
+
+        assert_eq!(the_vault.lock().unwrap().concat(), "{\"id\":1,\"Name\":\"a\"}\n");
+        Ok(())
+    }
+
+    /// 🧪 A registered transform, named from a `Custom` entry — the vault stamps its own seal.
+    #[tokio::test]
+    async fn the_one_where_the_vault_brings_its_own_stamp() -> Result<()> {
+        #[derive(Debug)]
+        struct Stamp(serde_json::Value);
+        impl Transform for Stamp {
+            fn transform(&self, mut doc: serde_json::Value) -> Result<Option<serde_json::Value>> {
+                doc["seal"] = self.0.clone();
+                Ok(Some(doc))
+            }
+        }
+        let the_vault = Arc::new(Mutex::new(Vec::new()));
+        let the_vault_for_factory = the_vault.clone();
+
+        Pipeline::builder()
+            .custom_source("mainframe", || Ok(MainframeSource { the_pages_left: vec!["{\"id\":1}\n"] }))
+            .custom_sink("vault", move || Ok(VaultSink { the_vault: the_vault_for_factory.clone() }))
+            .register_transform("stamp", |settings| Ok(Stamp(settings["seal"].clone())))
+            .transform(toml::from_str("Custom = { name = 'stamp', settings = { seal = 'wax' } }")?)
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .run()
+            .await?;
+        assert_eq!(the_vault.lock().unwrap().concat(), "{\"id\":1,\"seal\":\"wax\"}\n");
+
+        let the_unknown = Pipeline::builder()
+            .custom_source("mainframe", || Ok(MainframeSource { the_pages_left: vec![] }))
+            .custom_sink("vault", || Ok(VaultSink { the_vault: Arc::default() }))
+            .transform(toml::from_str("Custom = { name = 'stamp' }")?)
+            .run()
+            .await;
+        assert!(the_unknown.is_err(), "💀 nobody registered `stamp` this time");
+        Ok(())
+    }
+
+    /// 🧪 A progress subscriber on the builder hears the closing `finished` event.
+    #[tokio::test]
+    async fn the_one_where_the_dashboard_hears_the_final_whistle() -> Result<()> {
+        struct Dashboard(Mutex<Vec<crate::progress::ProgressEvent>>);
+        impl ProgressSubscriber for Dashboard {
+            fn on_progress(&self, event: &crate::progress::ProgressEvent) {
+                self.0.lock().unwrap().push(event.clone());
+            }
+        }
+        let the_dashboard = Arc::new(Dashboard(Mutex::new(Vec::new())));
+
+        Pipeline::builder()
+            .source(SourceConfig::InMemory(()))
+            .sink(SinkConfig::InMemory(()))
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .progress_subscriber(the_dashboard.clone())
+            .hide_terminal_progress()
+            .run()
+            .await?;
+
+        let the_events = the_dashboard.0.lock().unwrap();
+        assert!(the_events.last().is_some_and(|e| e.finished), "🏁 expected a finished event, got {:?}", the_events);
+        Ok(())
+    }
+
+    /// 🧪 Generator → Null: every made-up doc is cast, joined, counted and thrown away.
+    #[tokio::test]
+    async fn the_one_where_nothing_goes_in_and_nothing_comes_out() -> Result<()> {
+        struct Stopwatch(Mutex<Option<crate::progress::ProgressEvent>>);
+        impl ProgressSubscriber for Stopwatch {
+            fn on_progress(&self, event: &crate::progress::ProgressEvent) {
+                *self.0.lock().unwrap() = Some(event.clone());
+            }
+        }
+        let the_stopwatch = Arc::new(Stopwatch(Mutex::new(None)));
+
+        Pipeline::builder()
+            .source(SourceConfig::Generator(crate::backends::GeneratorSourceConfig { docs: 2_500, ..Default::default() }))
+            .sink(SinkConfig::Null(Default::default()))
+            .runtime(RuntimeConfig { sink_parallelism: 2, joiner_parallelism: 2, ..Default::default() })
+            .progress_subscriber(the_stopwatch.clone())
+            .hide_terminal_progress()
+            .run()
+            .await?;
+
+        let the_final = the_stopwatch.0.lock().unwrap().clone().expect("🏁 a final event");
+        assert!(the_final.finished);
+        assert_eq!(the_final.estimated_docs, 2_500);
+        assert_eq!(the_final.total_expected_docs, Some(2_500));
+        assert!(the_final.bytes_drained > 2_500 * 512, "📦 bulk action lines on top of the docs");
+        Ok(())
+    }
+
+    /// 🧪 InMemory → InMemory through the builder — no TOML was harmed.
+    #[tokio::test]
+    async fn the_one_where_nobody_had_to_write_toml() -> Result<()> {
+        Pipeline::builder()
+            .source(SourceConfig::InMemory(()))
+            .sink(SinkConfig::InMemory(()))
+            .runtime(RuntimeConfig { sink_parallelism: 1, joiner_parallelism: 1, ..Default::default() })
+            .run()
+            .await
+    }
+
+    /// 🧪 Forgetting the sink is a build error, not a runtime surprise.
+    #[test]
+    fn the_one_where_the_data_has_nowhere_to_go() {
+        let the_err = Pipeline::builder()
+            .source(SourceConfig::InMemory(()))
+            .build()
+            .unwrap_err();
+        assert!(format!("{}", the_err).contains("no sink"));
+    }
+
+    /// 🧪 Shared warnings handle — the embedder's clone sees what the run records.
+    #[test]
+    fn the_one_where_the_embedder_keeps_a_copy_of_the_fridge_note() -> Result<()> {
+        let the_embedders_warnings = RunWarnings::default();
+        let the_pipeline = Pipeline::builder()
+            .source(SourceConfig::InMemory(()))
+            .sink(SinkConfig::InMemory(()))
+            .warnings(the_embedders_warnings.clone())
+            .build()?;
+        the_pipeline.warnings().record(crate::warnings::WarningKind::CloseCall, "hello from inside");
+        assert_eq!(the_embedders_warnings.len(), 1);
+        Ok(())
+    }
+}
@@ -8,22 +8,35 @@
 //! 🚀 This module answers the age-old question: "how fast is our data draining?"
 //! With cold hard numbers, a progress bar, and a table so comfy it has lumbar support.
 //!
+//! 🧠 Knowledge graph: one `MultiProgress` — the main bar (source progress + the aggregate
+//! table) on top, then one row per drainer when there's more than one (requests, bytes,
+//! last latency), so parallel sinks share the screen instead of fighting over it.
+//!
 //! ⚠️  Warning: Watching this progress bar will not make it go faster.
 //! Neither will refreshing it. We've tried. Science says no.
 //!
 //! 🦆 The duck has nothing to do with this module. It's just vibing.
 
 use std::collections::VecDeque;
+use std::io::IsTerminal;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use comfy_table::{Cell, CellAlignment, ContentArrangement, Table, presets::NOTHING};
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::Serialize;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::ProgressMode;
 
 // -- 📏 one mebibyte — not a megabyte, pedants. there's a difference and I will die on this hill.
 const MIB: u64 = 1024 * 1024;
+// -- 🧾 past this many drainers the rows stop being a dashboard and start being a scroll
+const MAX_DRAINER_ROWS: usize = 16;
+// -- 📜 one plain line this often when nobody's watching a terminal (CI, cron, `| tee`)
+pub(crate) const DEFAULT_PROGRESS_INTERVAL: Duration = Duration::from_secs(30);
 
 /// 📦 Converts raw bytes into a human-readable string with adaptive unit scaling.
 /// Because "1073741824 bytes" is a war crime in a UI.
@@ -47,7 +60,7 @@ fn format_number(n: u64) -> String {
     // -- 🧵 pre-allocate like we know what we're doing (we do, we read the book)
     let mut result = String::with_capacity(s.len() + s.len() / 3);
     for (i, c) in s.chars().enumerate() {
-        if i > 0 && (s.len() - i) % 3 == 0 {
+        if i > 0 && (s.len() - i).is_multiple_of(3) {
             result.push(',');
         }
         result.push(c);
@@ -91,6 +104,8 @@ impl std::fmt::Debug for DrainMetrics {
             .field("requests_completed", &self.requests_completed.load(Ordering::Relaxed))
             .field("latency_sum_ms", &self.latency_sum_ms.load(Ordering::Relaxed))
             .field("latency_max_ms", &self.latency_max_ms.load(Ordering::Relaxed))
+            .field("docs_processed", &self.docs_processed.load(Ordering::Relaxed))
+            .field("drainers", &self.drainers.len())
             .finish()
     }
 }
@@ -108,6 +123,33 @@ pub struct DrainMetrics {
     pub last_request_size_bytes: AtomicU64,
     /// 📡 most recent drain latency in ms — store (not add), always the latest
     pub last_latency_ms: AtomicU64,
+    /// 🔢 documents the joiners have cast so far — a real count, not bytes ÷ 512.
+    /// Counted one hop before the sink, so it runs a channel's length ahead of the drain.
+    pub docs_processed: AtomicU64,
+    /// 🗓️ time windows every source slice has read to the end — stored by the `WindowLedger`
+    pub windows_done: AtomicU64,
+    /// 🗓️ time windows in the plan — 0 unless the source is time-sliced
+    pub windows_total: AtomicU64,
+    /// 🚰 one lane per drainer, indexed by drainer id — empty unless built `with_drainers`
+    pub drainers: Vec<DrainerMetrics>,
+}
+
+/// 🚰 One drainer's share of the work. Docs aren't here: joiners count them before the
+/// payloads are handed out, so there's no per-drainer doc count to give.
+#[derive(Debug, Default)]
+pub struct DrainerMetrics {
+    /// 📦 bytes this drainer got acknowledged
+    pub bytes_drained: AtomicU64,
+    /// ✅ requests this drainer completed
+    pub requests_completed: AtomicU64,
+    /// ⏱️ this drainer's most recent drain latency in ms
+    pub last_latency_ms: AtomicU64,
+}
+
+impl Default for DrainMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DrainMetrics {
@@ -120,6 +162,25 @@ impl DrainMetrics {
             latency_max_ms: AtomicU64::new(0),
             last_request_size_bytes: AtomicU64::new(0),
             last_latency_ms: AtomicU64::new(0),
+            docs_processed: AtomicU64::new(0),
+            windows_done: AtomicU64::new(0),
+            windows_total: AtomicU64::new(0),
+            drainers: Vec::new(),
+        }
+    }
+
+    /// 🚰 Same counters, plus a lane for each of `drainer_count` drainers.
+    pub fn with_drainers(drainer_count: usize) -> Self {
+        Self { drainers: (0..drainer_count).map(|_| DrainerMetrics::default()).collect(), ..Self::new() }
+    }
+
+    /// 📡 `record_drain`, also credited to drainer `drainer_id`'s lane (if it has one).
+    pub fn record_drain_for(&self, drainer_id: usize, payload_bytes: u64, latency_ms: u64) {
+        self.record_drain(payload_bytes, latency_ms);
+        if let Some(the_lane) = self.drainers.get(drainer_id) {
+            the_lane.bytes_drained.fetch_add(payload_bytes, Ordering::Relaxed);
+            the_lane.requests_completed.fetch_add(1, Ordering::Relaxed);
+            the_lane.last_latency_ms.store(latency_ms, Ordering::Relaxed);
         }
     }
 
@@ -136,6 +197,11 @@ impl DrainMetrics {
         self.last_request_size_bytes.store(payload_bytes, Ordering::Relaxed);
         self.last_latency_ms.store(latency_ms, Ordering::Relaxed);
     }
+
+    /// 🔢 Record documents cast by a joiner — called once per page.
+    pub fn record_docs(&self, docs: u64) {
+        self.docs_processed.fetch_add(docs, Ordering::Relaxed);
+    }
 }
 
 /// 📡 A snapshot of throughput rates at any given moment.
@@ -147,8 +213,146 @@ struct Rates {
     mib_per_sec: f64,
 }
 
+/// 📨 One structured progress update — everything the terminal table shows, as plain numbers.
+///
+/// Emitted every tick (500ms) and once more with `finished: true` when the pipeline is done.
+/// `Serialize` so a subscriber can ship it to a job tracker as JSON without a mapping layer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressEvent {
+    /// 🏷️ e.g. "my-index → meilisearch"
+    pub pipeline_name: String,
+    /// 📦 bytes acknowledged by the sink so far
+    pub bytes_drained: u64,
+    /// 🔢 docs counted by the joiners; before the first count lands, bytes ÷ 512 (a guess, not a ledger)
+    pub estimated_docs: u64,
+    /// ✅ successful sink requests so far
+    pub requests_completed: u64,
+    /// 🚀 5-second sliding-window rates
+    pub docs_per_sec: f64,
+    pub mib_per_sec: f64,
+    /// ⏱️ drain latency stats
+    pub avg_latency_ms: u64,
+    pub max_latency_ms: u64,
+    pub last_latency_ms: u64,
+    /// 📏 request size stats
+    pub avg_request_bytes: u64,
+    pub last_request_bytes: u64,
+    /// 📏 `None` when the source can't tell us its size up front
+    pub total_expected_bytes: Option<u64>,
+    /// 🔢 `None` unless the source counted its docs up front (Elasticsearch `_count`)
+    pub total_expected_docs: Option<u64>,
+    pub elapsed: Duration,
+    /// 🔮 linear extrapolation; `None` without a known total (or once we're past it)
+    pub eta: Option<Duration>,
+    /// 🏁 `true` on the very last event of the run
+    pub finished: bool,
+    /// 🗓️ time windows read to the end so far, of `windows_total`
+    pub windows_done: u64,
+    /// 🗓️ `None` unless the source reads in time windows (Elasticsearch `time_slice`)
+    pub windows_total: Option<u64>,
+    /// 🚰 per-drainer counters, in drainer order — empty when the foreman didn't split them out
+    pub drainers: Vec<DrainerProgress>,
+}
+
+/// 🚰 One drainer's row in a `ProgressEvent`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DrainerProgress {
+    pub bytes_drained: u64,
+    pub requests_completed: u64,
+    pub last_latency_ms: u64,
+}
+
+/// 📬 Receives structured progress events — implement this to drive your own UI or job tracker.
+///
+/// Called from the reporter task every tick, so keep it quick: push to a channel, update an
+/// atomic, enqueue an HTTP call. Don't block for seconds — the next tick is waiting. 🦆
+pub trait ProgressSubscriber: Send + Sync {
+    fn on_progress(&self, event: &ProgressEvent);
+}
+
+/// 📋 Who hears about progress: the terminal bar (unless hidden) plus any subscribers.
+///
+/// Rides along on `AppConfig` like `RunWarnings` — not a TOML key, set through the builder.
+#[derive(Clone, Default)]
+pub struct ProgressSubscribers {
+    subscribers: Vec<Arc<dyn ProgressSubscriber>>,
+    terminal_hidden: bool,
+    /// 📊 `[runtime] progress` — set by the Foreman, not the embedder
+    mode: ProgressMode,
+    /// 📜 how often a log line goes out; `None` = `DEFAULT_PROGRESS_INTERVAL`
+    log_interval: Option<Duration>,
+}
+
+/// 🖥️ What the reporter writes to stderr, once the mode has met the terminal (or the lack of one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TerminalOutput {
+    /// 🎨 the indicatif bar and drainer rows
+    Bar,
+    /// 📜 one plain line every `every`, plus one at the end
+    LogLines { every: Duration },
+    /// 🙊 not a byte
+    Nothing,
+}
+
+impl ProgressSubscribers {
+    /// 📬 Add a subscriber. Every clone of this list shares the same subscriber objects.
+    pub fn subscribe(&mut self, subscriber: Arc<dyn ProgressSubscriber>) {
+        self.subscribers.push(subscriber);
+    }
+
+    /// 🙈 Don't draw the terminal progress bar — the embedder has its own UI.
+    pub fn hide_terminal(&mut self) {
+        self.terminal_hidden = true;
+    }
+
+    /// 📊 Apply `[runtime] progress` and `progress_interval_secs`.
+    pub(crate) fn set_terminal_mode(&mut self, mode: ProgressMode, log_interval: Duration) {
+        self.mode = mode;
+        self.log_interval = Some(log_interval);
+    }
+
+    /// 🖥️ What goes to stderr. Hidden or `"off"` → nothing. `"log"` → lines. `"bar"` → the bar
+    /// on a terminal, lines when stderr is redirected — a bar drawing into a log file helps nobody.
+    fn terminal_output(&self, stderr_is_terminal: bool) -> TerminalOutput {
+        let the_lines = TerminalOutput::LogLines { every: self.log_interval.unwrap_or(DEFAULT_PROGRESS_INTERVAL) };
+        match self.mode {
+            _ if self.terminal_hidden => TerminalOutput::Nothing,
+            ProgressMode::Off => TerminalOutput::Nothing,
+            ProgressMode::Log => the_lines,
+            ProgressMode::Bar if stderr_is_terminal => TerminalOutput::Bar,
+            ProgressMode::Bar => the_lines,
+        }
+    }
+
+    /// 🔢 How many subscribers are listening (the terminal bar doesn't count).
+    pub fn len(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty()
+    }
+
+    fn publish(&self, event: &ProgressEvent) {
+        for the_subscriber in &self.subscribers {
+            the_subscriber.on_progress(event);
+        }
+    }
+}
+
+// -- 🔍 trait objects don't Debug; the count and the terminal flag are what matter anyway
+impl std::fmt::Debug for ProgressSubscribers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressSubscribers")
+            .field("subscribers", &self.subscribers.len())
+            .field("terminal_hidden", &self.terminal_hidden)
+            .finish()
+    }
+}
+
 /// 📊 The brains behind the progress display. Reads from DrainMetrics atomics,
-/// calculates rates via a sliding window, and renders a comfy-table to the terminal.
+/// calculates rates via a sliding window, renders a comfy-table to the terminal,
+/// and hands the same numbers to any `ProgressSubscriber`s.
 ///
 /// Uses a sliding 5-second window for rate calculations so spikes don't scare you.
 /// (Your heart rate is not our responsibility.)
@@ -160,8 +364,13 @@ struct ProgressReporter {
     pipeline_name: String,
     /// 📡 shared atomic counters from drainers — the source of truth
     drain_metrics: Arc<DrainMetrics>,
-    /// 🎨 the actual terminal progress bar (indicatif does the heavy lifting here)
-    progress_bar: ProgressBar,
+    /// 🎨 the actual terminal progress bars — `None` unless the output is `Bar`, so a hidden,
+    /// `"off"` or redirected run never even tells indicatif about itself
+    terminal: Option<TerminalBars>,
+    /// 📜 `Some((every, last line at))` when progress goes out as plain lines instead
+    log_lines: Option<(Duration, Instant)>,
+    /// 📬 embedders listening in — the bar's quieter, more structured siblings
+    subscribers: ProgressSubscribers,
     /// 🔄 sliding window of (timestamp, bytes, docs) for rate calculation
     /// VecDeque because we pop from the front — linked list but make it cache-friendly-ish
     rate_samples: VecDeque<(Instant, u64, u64)>,
@@ -169,6 +378,8 @@ struct ProgressReporter {
     start_time: Instant,
     /// 📏 total expected bytes — 0 if unknown (classic elasticsearch)
     total_expected_bytes: u64,
+    /// 🔢 total expected docs — 0 if unknown. When known, it drives the bar and the ETA
+    total_expected_docs: u64,
 }
 
 impl ProgressReporter {
@@ -176,22 +387,22 @@ impl ProgressReporter {
     ///
     /// # No cap
     /// This function slaps. fr fr. The progress bar will look sick in your terminal.
-    fn new(pipeline_name: String, drain_metrics: Arc<DrainMetrics>, total_expected_bytes: u64) -> Self {
-        // -- 🎨 build the progress bar — cyan because it's classy, blue because it's calm
-        let progress_bar = if total_expected_bytes > 0 {
-            ProgressBar::new(total_expected_bytes)
-        } else {
-            // -- ⚠️ unknown total — spinner mode, no ETA, just vibes
-            ProgressBar::new_spinner()
-        };
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-                .template("{msg}\n| [{bar:40.cyan/blue}]")
-                .unwrap() // -- 🐛 safe unwrap: template string is hardcoded and valid, I checked, twice
-                .progress_chars("=>-"),
-        );
-
+    fn new(
+        pipeline_name: String,
+        drain_metrics: Arc<DrainMetrics>,
+        total_expected_bytes: u64,
+        total_expected_docs: u64,
+        subscribers: ProgressSubscribers,
+    ) -> Self {
         let start_time = Instant::now();
+        let the_output = subscribers.terminal_output(std::io::stderr().is_terminal());
+        let terminal = (the_output == TerminalOutput::Bar).then(|| {
+            TerminalBars::new(ProgressDrawTarget::stderr(), total_expected_docs, total_expected_bytes, drain_metrics.drainers.len())
+        });
+        let log_lines = match the_output {
+            TerminalOutput::LogLines { every } => Some((every, start_time)),
+            _ => None,
+        };
 
         // -- 🔄 seed the rate window with t=0 so we don't divide by zero like animals
         let mut rate_samples = VecDeque::new();
@@ -200,44 +411,133 @@ impl ProgressReporter {
         Self {
             pipeline_name,
             drain_metrics,
-            progress_bar,
+            terminal,
+            log_lines,
+            subscribers,
             rate_samples,
             start_time,
             total_expected_bytes,
+            total_expected_docs,
         }
     }
 
-    /// 🔄 Tick the reporter: snapshot atomics, calculate rates, render the display.
-    /// Called every 500ms by the spawned reporter task.
+    /// 🔄 Tick the reporter: snapshot atomics, calculate rates, render the display,
+    /// then tell the subscribers. Called every 500ms by the spawned reporter task,
+    /// and once more with `finished = true` when the foreman says we're done.
     /// Like a heartbeat monitor, but for data. Beep. Beep. Beep. 💓
-    fn tick(&mut self) {
+    fn tick(&mut self, finished: bool) {
+        let the_event = self.snapshot(finished);
+        if let Some(the_terminal) = &self.terminal {
+            the_terminal.render(&self.pipeline_name, &the_event);
+            if self.total_expected_docs > 0 {
+                the_terminal.progress_bar.set_position(the_event.estimated_docs.min(self.total_expected_docs));
+            } else if self.total_expected_bytes > 0 {
+                the_terminal.progress_bar.set_position(the_event.bytes_drained);
+            }
+        }
+        if let Some(the_line) = self.log_line_due(&the_event) {
+            eprintln!("{}", the_line);
+        }
+        self.subscribers.publish(&the_event);
+    }
+
+    /// 📜 The next plain progress line, if one is due: every `progress_interval_secs`, and
+    /// always for the `finished` event. Goes to stderr as is — like the bar, not through `RUST_LOG`.
+    fn log_line_due(&mut self, event: &ProgressEvent) -> Option<String> {
+        let (the_every, the_last_line_at) = self.log_lines.as_mut()?;
+        if !event.finished && the_last_line_at.elapsed() < *the_every {
+            return None;
+        }
+        *the_last_line_at = Instant::now();
+        let the_share = match (event.total_expected_docs, event.total_expected_bytes) {
+            (Some(the_docs), _) => format!(" ({:.1}%)", event.estimated_docs as f64 * 100.0 / the_docs as f64),
+            (None, Some(the_bytes)) => format!(" ({:.1}%)", event.bytes_drained as f64 * 100.0 / the_bytes as f64),
+            (None, None) => String::new(),
+        };
+        let the_windows = match event.windows_total {
+            Some(the_total) => format!(", window {}/{}", event.windows_done, the_total),
+            None => String::new(),
+        };
+        Some(format!(
+            "📊 {} {}: ~{} docs{}{}, {} — {} docs/s, {:.2} MiB/s — {} elapsed, {} remaining",
+            self.pipeline_name,
+            if event.finished { "finished" } else { "progress" },
+            format_number(event.estimated_docs),
+            the_share,
+            the_windows,
+            format_bytes_adaptive(event.bytes_drained),
+            format_number(event.docs_per_sec as u64),
+            event.mib_per_sec,
+            format_duration(event.elapsed),
+            event.eta.map(format_duration).unwrap_or_else(|| "--:--".to_string()),
+        ))
+    }
+
+    /// 📸 Read the atomics and turn them into a `ProgressEvent`.
+    fn snapshot(&mut self, finished: bool) -> ProgressEvent {
         let the_bytes_drained = self.drain_metrics.bytes_drained.load(Ordering::Relaxed);
         let the_requests_completed = self.drain_metrics.requests_completed.load(Ordering::Relaxed);
         let the_latency_sum_ms = self.drain_metrics.latency_sum_ms.load(Ordering::Relaxed);
-        let the_latency_max_ms = self.drain_metrics.latency_max_ms.load(Ordering::Relaxed);
-        let the_last_request_size = self.drain_metrics.last_request_size_bytes.load(Ordering::Relaxed);
-        let the_last_latency_ms = self.drain_metrics.last_latency_ms.load(Ordering::Relaxed);
 
-        // 📊 estimate doc count from bytes — heuristic: count \n in bulk payload ÷ 2
-        // (bulk format has action line + doc line per document, separated by \n)
-        // For non-bulk formats this is a rough approximation. Good enough for a progress bar.
+        // 📊 the joiners count docs as they cast them — use that once it exists.
+        // Before the first page is cast, fall back to the old heuristic: bytes ÷ 512.
         // -- "Close enough for government work" — every engineer, ever
-        let the_estimated_docs = the_bytes_drained / 512;
+        let the_docs_processed = self.drain_metrics.docs_processed.load(Ordering::Relaxed);
+        let the_estimated_docs = if the_docs_processed > 0 { the_docs_processed } else { the_bytes_drained / 512 };
 
-        // -- 📊 crunch the numbers, render the glory
         let rates = self.calculate_rates(the_bytes_drained, the_estimated_docs);
-        self.render(
-            rates,
-            the_bytes_drained,
-            the_estimated_docs,
-            the_requests_completed,
-            the_latency_sum_ms,
-            the_latency_max_ms,
-            the_last_request_size,
-            the_last_latency_ms,
-        );
-        if self.total_expected_bytes > 0 {
-            self.progress_bar.set_position(the_bytes_drained);
+        let elapsed = self.start_time.elapsed();
+
+        // 📊 ETA calculation — only meaningful when we know the total
+        // 🔮 linear extrapolation — assumes the future looks like the past
+        // -- (historically a bad assumption, but fine for data migration)
+        // 🔢 a doc total (ES `_count`) beats a byte total — it's what the user actually asked for
+        let the_done_fraction = if self.total_expected_docs > 0 {
+            (the_docs_processed > 0).then(|| the_docs_processed as f64 / self.total_expected_docs as f64)
+        } else if self.total_expected_bytes > 0 {
+            (the_bytes_drained > 0).then(|| the_bytes_drained as f64 / self.total_expected_bytes as f64)
+        } else {
+            None
+        };
+        let eta = if let Some(percent) = the_done_fraction {
+            let remaining_secs = elapsed.as_secs_f64() / percent - elapsed.as_secs_f64();
+            (remaining_secs > 0.0).then(|| Duration::from_secs_f64(remaining_secs))
+        } else {
+            // -- ⚠️  no total known means no ETA — we're flying blind, captain
+            None
+        };
+
+        ProgressEvent {
+            pipeline_name: self.pipeline_name.clone(),
+            bytes_drained: the_bytes_drained,
+            estimated_docs: the_estimated_docs,
+            requests_completed: the_requests_completed,
+            docs_per_sec: rates.docs_per_sec,
+            mib_per_sec: rates.mib_per_sec,
+            // ⏱️ average latency — avoid divide-by-zero like a responsible adult
+            avg_latency_ms: the_latency_sum_ms.checked_div(the_requests_completed).unwrap_or(0),
+            max_latency_ms: self.drain_metrics.latency_max_ms.load(Ordering::Relaxed),
+            last_latency_ms: self.drain_metrics.last_latency_ms.load(Ordering::Relaxed),
+            // 📏 average request size — again, no dividing by zero
+            avg_request_bytes: the_bytes_drained.checked_div(the_requests_completed).unwrap_or(0),
+            last_request_bytes: self.drain_metrics.last_request_size_bytes.load(Ordering::Relaxed),
+            total_expected_bytes: (self.total_expected_bytes > 0).then_some(self.total_expected_bytes),
+            total_expected_docs: (self.total_expected_docs > 0).then_some(self.total_expected_docs),
+            elapsed,
+            eta,
+            finished,
+            windows_done: self.drain_metrics.windows_done.load(Ordering::Relaxed),
+            windows_total: Some(self.drain_metrics.windows_total.load(Ordering::Relaxed)).filter(|&the_total| the_total > 0),
+            drainers: self
+                .drain_metrics
+                .drainers
+                .iter()
+                .map(|the_lane| DrainerProgress {
+                    bytes_drained: the_lane.bytes_drained.load(Ordering::Relaxed),
+                    requests_completed: the_lane.requests_completed.load(Ordering::Relaxed),
+                    last_latency_ms: the_lane.last_latency_ms.load(Ordering::Relaxed),
+                })
+                .collect(),
         }
     }
 
@@ -286,6 +586,55 @@ impl ProgressReporter {
         }
     }
 
+}
+
+/// 🎨 The indicatif side of the reporter — the main bar (source progress + the aggregate
+/// table) and one row per drainer, all in one `MultiProgress`. Only built when it'll be drawn.
+struct TerminalBars {
+    /// 🎨 the actual terminal progress bar (indicatif does the heavy lifting here)
+    progress_bar: ProgressBar,
+    /// 🚰 one row per drainer under the main bar (none for a single drainer)
+    drainer_rows: Vec<ProgressBar>,
+    /// 🧱 owns the screen area all the bars draw into
+    _multi: MultiProgress,
+}
+
+impl TerminalBars {
+    fn new(draw_target: ProgressDrawTarget, total_expected_docs: u64, total_expected_bytes: u64, drainer_count: usize) -> Self {
+        // -- 🎨 build the progress bar — cyan because it's classy, blue because it's calm
+        let progress_bar = if total_expected_docs > 0 {
+            // -- 🔢 the source counted its docs — measure the bar in docs
+            ProgressBar::new(total_expected_docs)
+        } else if total_expected_bytes > 0 {
+            ProgressBar::new(total_expected_bytes)
+        } else {
+            // -- ⚠️ unknown total — spinner mode, no ETA, just vibes
+            ProgressBar::new_spinner()
+        };
+        progress_bar.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg}\n| [{bar:40.cyan/blue}]")
+                .unwrap() // -- 🐛 safe unwrap: template string is hardcoded and valid, I checked, twice
+                .progress_chars("=>-"),
+        );
+
+        // -- 🧱 every bar goes through one MultiProgress so parallel rows don't overwrite each other
+        let the_multi = MultiProgress::with_draw_target(draw_target);
+        let progress_bar = the_multi.add(progress_bar);
+        let mut drainer_rows = Vec::new();
+        if drainer_count > 1 {
+            for _ in 0..drainer_count.min(MAX_DRAINER_ROWS) {
+                // -- 🐛 safe unwrap: hardcoded template, same as above
+                drainer_rows.push(the_multi.add(ProgressBar::new_spinner().with_style(ProgressStyle::with_template("{msg}").unwrap())));
+            }
+        }
+        if drainer_count > MAX_DRAINER_ROWS {
+            let the_overflow = the_multi.add(ProgressBar::new_spinner().with_style(ProgressStyle::with_template("{msg}").unwrap()));
+            the_overflow.set_message(format!("  … and {} more drainers (in the totals above)", drainer_count - MAX_DRAINER_ROWS));
+        }
+        Self { progress_bar, drainer_rows, _multi: the_multi }
+    }
+
     /// 🎨 Render the full progress display as a comfy-table message on the progress bar.
     ///
     /// Layout (6 rows x 2 cols):
@@ -301,62 +650,19 @@ impl ProgressReporter {
     ///
     /// If you're reading this comment at 3am during an incident, I'm so sorry.
     /// At least the table looks nice.
-    #[allow(clippy::too_many_arguments)]
-    fn render(
-        &self,
-        rates: Rates,
-        the_bytes_drained: u64,
-        the_estimated_docs: u64,
-        the_requests_completed: u64,
-        the_latency_sum_ms: u64,
-        the_latency_max_ms: u64,
-        the_last_request_size: u64,
-        the_last_latency_ms: u64,
-    ) {
-        let docs_per_min = rates.docs_per_sec * 60.0;
+    fn render(&self, pipeline_name: &str, event: &ProgressEvent) {
+        let docs_per_min = event.docs_per_sec * 60.0;
         // -- 🔢 human-friendly numbers because we are, ostensibly, human
         let docs_rate = format_number(docs_per_min as u64);
-        let docs_total = format_number(the_estimated_docs);
-
-        // ⏱️ average latency — avoid divide-by-zero like a responsible adult
-        let the_avg_latency_ms = if the_requests_completed > 0 {
-            the_latency_sum_ms / the_requests_completed
-        } else {
-            0
-        };
-
-        // 📏 average request size — again, no dividing by zero
-        let the_avg_request_size = if the_requests_completed > 0 {
-            the_bytes_drained / the_requests_completed
-        } else {
-            0
-        };
+        let docs_total = format_number(event.estimated_docs);
 
         // ⏱️ time stats
-        let elapsed = self.start_time.elapsed();
-        let elapsed_fmt = format_duration(elapsed);
-
-        // 📊 ETA calculation — only meaningful when we know the total
-        let remaining = if self.total_expected_bytes > 0 && the_bytes_drained > 0 {
-            let percent = the_bytes_drained as f64 / self.total_expected_bytes as f64;
-            if percent > 0.0 {
-                // 🔮 linear extrapolation — assumes the future looks like the past
-                // -- (historically a bad assumption, but fine for data migration)
-                let total_estimated = elapsed.as_secs_f64() / percent;
-                let remaining_secs = total_estimated - elapsed.as_secs_f64();
-                if remaining_secs > 0.0 {
-                    format_duration(Duration::from_secs_f64(remaining_secs))
-                } else {
-                    // ✅ done or basically done — show a friendly placeholder
-                    "--:--".to_string()
-                }
-            } else {
-                "--:--".to_string()
-            }
-        } else {
-            // -- ⚠️  no total known means no ETA — we're flying blind, captain
-            "--:--".to_string()
-        };
+        let elapsed_fmt = format_duration(event.elapsed);
+        // ✅ no ETA (unknown total, or done or basically done) — show a friendly placeholder
+        let remaining = event
+            .eta
+            .map(format_duration)
+            .unwrap_or_else(|| "--:--".to_string());
 
         // 🍽️ build the comfy table — two columns, right-aligned, no borders (preset: NOTHING)
         // -- NOTHING preset because we're minimalists. and also the borders looked bad.
@@ -371,20 +677,20 @@ impl ProgressReporter {
         ]);
         // 📦 row 2: byte throughput and cumulative bytes
         table.add_row(vec![
-            Cell::new(format!("{:.2} MiB/s", rates.mib_per_sec))
+            Cell::new(format!("{:.2} MiB/s", event.mib_per_sec))
                 .set_alignment(CellAlignment::Right),
-            Cell::new(format_bytes_adaptive(the_bytes_drained)).set_alignment(CellAlignment::Right),
+            Cell::new(format_bytes_adaptive(event.bytes_drained)).set_alignment(CellAlignment::Right),
         ]);
         // ⏱️ row 3: latency — avg and last
         table.add_row(vec![
-            Cell::new(format!("avg {}ms", the_avg_latency_ms)).set_alignment(CellAlignment::Right),
-            Cell::new(format!("last {}ms", the_last_latency_ms)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("avg {}ms", event.avg_latency_ms)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("last {}ms", event.last_latency_ms)).set_alignment(CellAlignment::Right),
         ]);
         // 📏 row 4: request size — avg and last
         table.add_row(vec![
-            Cell::new(format!("avg {}", format_bytes_adaptive(the_avg_request_size)))
+            Cell::new(format!("avg {}", format_bytes_adaptive(event.avg_request_bytes)))
                 .set_alignment(CellAlignment::Right),
-            Cell::new(format!("last {}", format_bytes_adaptive(the_last_request_size)))
+            Cell::new(format!("last {}", format_bytes_adaptive(event.last_request_bytes)))
                 .set_alignment(CellAlignment::Right),
         ]);
         // ⏱️ row 5: time elapsed and estimated time remaining
@@ -395,16 +701,33 @@ impl ProgressReporter {
 
         // -- 🎨 slam it all into the progress bar message
         // indicatif will handle the terminal magic (cursor positioning, redraw, etc.)
+        // 🗓️ time-sliced reads say which window they're on
+        let the_windows = match event.windows_total {
+            Some(the_total) => format!(" — window {}/{}", event.windows_done, the_total),
+            None => String::new(),
+        };
         self.progress_bar
-            .set_message(format!("sink: {}\n{}", self.pipeline_name, table));
+            .set_message(format!("sink: {}{}\n{}", pipeline_name, the_windows, table));
+
+        // 🚰 one line per drainer — a lane stuck on one slow request shows up right here
+        for (i, (the_row, the_lane)) in self.drainer_rows.iter().zip(&event.drainers).enumerate() {
+            the_row.set_message(format!(
+                "  drainer {:>2}: {:>8} reqs  {:>14}  last {}ms",
+                i,
+                format_number(the_lane.requests_completed),
+                format_bytes_adaptive(the_lane.bytes_drained),
+                the_lane.last_latency_ms
+            ));
+        }
     }
 }
 
 /// 🚀 Spawns a tokio task that ticks the progress reporter every 500ms.
 ///
-/// Returns a JoinHandle — the Foreman should .abort() this after all real workers complete.
-/// The reporter is a leaf display task: it reads atomics, renders to terminal, and sleeps.
-/// Aborting it is safe and expected. Like pulling the plug on a screensaver. 🖥️
+/// When `stop` is cancelled the reporter does one final tick with `finished: true` — so the
+/// bar shows the end state and subscribers get their closing event — then exits.
+/// The Foreman cancels it after all real workers complete. Aborting still works too, it just
+/// skips the goodbye. Like pulling the plug on a screensaver. 🖥️
 ///
 /// "In the beginning there was no progress bar. And the developer stared into the void.
 ///  And the void did not stare back, because there was no render loop." — Genesis 0:0 🦆
@@ -412,16 +735,29 @@ pub fn spawn_progress_reporter(
     pipeline_name: String,
     drain_metrics: Arc<DrainMetrics>,
     total_expected_bytes: u64,
+    total_expected_docs: u64,
+    subscribers: ProgressSubscribers,
+    stop: CancellationToken,
 ) -> JoinHandle<()> {
-    tokio::spawn(async move {
-        let mut the_reporter = ProgressReporter::new(pipeline_name, drain_metrics, total_expected_bytes);
+    crate::workers::spawn_named("progress-reporter", async move {
+        let mut the_reporter = ProgressReporter::new(
+            pipeline_name,
+            drain_metrics,
+            total_expected_bytes,
+            total_expected_docs,
+            subscribers,
+        );
         loop {
             // -- 💤 sleep 500ms — fast enough to feel responsive, slow enough to not burn CPU
-            tokio::time::sleep(Duration::from_millis(500)).await;
-            the_reporter.tick();
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(500)) => the_reporter.tick(false),
+                _ = stop.cancelled() => {
+                    // -- 🏁 one final tick to show the end state, then goodnight. 🌙
+                    the_reporter.tick(true);
+                    return;
+                }
+            }
         }
-        // -- 🏁 unreachable: this loop runs until aborted by the Foreman.
-        // -- Like a hamster wheel, it doesn't stop on its own. 🐹
     })
 }
 
@@ -495,6 +831,9 @@ mod tests {
             "test-pipeline".to_string(),
             metrics.clone(),
             0,
+            0,
+            ProgressSubscribers::default(),
+            CancellationToken::new(),
         );
 
         // -- 💤 let it tick once
@@ -506,6 +845,120 @@ mod tests {
         // -- ✅ if we got here without panicking, the reporter handled abort gracefully
     }
 
+    /// 🧪 A subscriber hears the ticks, and the stop token buys one last `finished` event. 📬🦆
+    #[tokio::test]
+    async fn the_one_where_the_job_tracker_gets_the_final_word() {
+        struct JobTracker(std::sync::Mutex<Vec<ProgressEvent>>);
+        impl ProgressSubscriber for JobTracker {
+            fn on_progress(&self, event: &ProgressEvent) {
+                self.0.lock().unwrap().push(event.clone());
+            }
+        }
+
+        let metrics = Arc::new(DrainMetrics::new());
+        metrics.record_drain(2048, 10);
+        let the_tracker = Arc::new(JobTracker(std::sync::Mutex::new(Vec::new())));
+        let mut the_subscribers = ProgressSubscribers::default();
+        the_subscribers.subscribe(the_tracker.clone());
+        the_subscribers.hide_terminal();
+        let the_stop = CancellationToken::new();
+
+        let handle = spawn_progress_reporter("test-pipeline".to_string(), metrics, 4096, 0, the_subscribers, the_stop.clone());
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        the_stop.cancel();
+        handle.await.unwrap();
+
+        let the_events = the_tracker.0.lock().unwrap();
+        assert!(the_events.len() >= 2, "🎯 expected a tick and a final event, got {}", the_events.len());
+        assert!(!the_events[0].finished);
+        let the_last = the_events.last().unwrap();
+        assert!(the_last.finished);
+        assert_eq!(the_last.bytes_drained, 2048);
+        assert_eq!(the_last.total_expected_bytes, Some(4096));
+        assert!(the_last.eta.is_some(), "🔮 half done with a known total should have an ETA");
+    }
+
+    /// 🧪 A counted source drives the ETA in docs, not bytes — and the tally beats the ÷ 512 guess. 🔢🦆
+    #[test]
+    fn the_one_where_the_headcount_sets_the_pace() {
+        let metrics = Arc::new(DrainMetrics::new());
+        metrics.record_drain(1_000_000, 10);
+        metrics.record_docs(250);
+        let mut the_subscribers = ProgressSubscribers::default();
+        the_subscribers.hide_terminal();
+        let mut the_reporter = ProgressReporter::new("es".to_string(), metrics, 0, 1000, the_subscribers);
+        std::thread::sleep(Duration::from_millis(20));
+
+        let the_event = the_reporter.snapshot(false);
+        assert_eq!(the_event.estimated_docs, 250, "🎯 the real count, not 1_000_000 / 512");
+        assert_eq!(the_event.total_expected_docs, Some(1000));
+        assert_eq!(the_event.total_expected_bytes, None);
+        let the_eta = the_event.eta.expect("🔮 a quarter done with a known doc total should have an ETA");
+        // -- ⏱️ a quarter of the docs in `elapsed` → three more `elapsed` to go
+        let the_ratio = the_eta.as_secs_f64() / the_event.elapsed.as_secs_f64();
+        assert!((2.5..3.5).contains(&the_ratio), "🎯 expected ~3x elapsed remaining, got {}", the_ratio);
+    }
+
+    /// 🧪 Each drainer gets its own lane, the totals still add up, and a stray id only counts once.
+    #[test]
+    fn the_one_where_every_lane_gets_its_own_row() {
+        let metrics = Arc::new(DrainMetrics::with_drainers(3));
+        metrics.record_drain_for(0, 1000, 40);
+        metrics.record_drain_for(1, 3000, 900);
+        metrics.record_drain_for(1, 2000, 700);
+        metrics.record_drain_for(7, 500, 5);
+        let mut the_subscribers = ProgressSubscribers::default();
+        the_subscribers.hide_terminal();
+        let mut the_reporter = ProgressReporter::new("lanes".to_string(), metrics, 0, 0, the_subscribers);
+        assert!(the_reporter.terminal.is_none(), "🙈 hidden means no bars at all");
+        let the_bars = TerminalBars::new(ProgressDrawTarget::hidden(), 0, 0, 3);
+        assert_eq!(the_bars.drainer_rows.len(), 3);
+
+        let the_event = the_reporter.snapshot(false);
+        assert_eq!(the_event.bytes_drained, 6500, "🎯 the totals see every drain");
+        assert_eq!(the_event.requests_completed, 4);
+        assert_eq!(
+            the_event.drainers,
+            vec![
+                DrainerProgress { bytes_drained: 1000, requests_completed: 1, last_latency_ms: 40 },
+                DrainerProgress { bytes_drained: 5000, requests_completed: 2, last_latency_ms: 700 },
+                DrainerProgress { bytes_drained: 0, requests_completed: 0, last_latency_ms: 0 },
+            ]
+        );
+        the_bars.render("lanes", &the_event);
+
+        let the_lonely = TerminalBars::new(ProgressDrawTarget::hidden(), 0, 0, 1);
+        assert!(the_lonely.drainer_rows.is_empty(), "🚰 one drainer is the total — no extra row");
+    }
+
+    /// 🧪 CI gets a line now and then instead of a spinner — and always one at the end. 📜🦆
+    #[test]
+    fn the_one_where_the_ci_log_gets_a_postcard_every_so_often() {
+        let mut the_subscribers = ProgressSubscribers::default();
+        assert_eq!(the_subscribers.terminal_output(true), TerminalOutput::Bar);
+        assert_eq!(
+            the_subscribers.terminal_output(false),
+            TerminalOutput::LogLines { every: DEFAULT_PROGRESS_INTERVAL },
+            "📜 redirected stderr gets lines, not a bar"
+        );
+        the_subscribers.set_terminal_mode(ProgressMode::Log, Duration::from_secs(3600));
+        assert_eq!(the_subscribers.terminal_output(true), TerminalOutput::LogLines { every: Duration::from_secs(3600) });
+        the_subscribers.set_terminal_mode(ProgressMode::Off, Duration::from_secs(3600));
+        assert_eq!(the_subscribers.terminal_output(false), TerminalOutput::Nothing);
+
+        let metrics = Arc::new(DrainMetrics::new());
+        metrics.record_drain(2 * MIB, 10);
+        metrics.record_docs(500);
+        let mut the_reporter = ProgressReporter::new("ci".to_string(), metrics, 0, 1000, ProgressSubscribers::default());
+        the_reporter.terminal = None;
+        the_reporter.log_lines = Some((Duration::from_secs(3600), Instant::now()));
+        let the_event = the_reporter.snapshot(false);
+        assert_eq!(the_reporter.log_line_due(&the_event), None, "⏳ an hour hasn't passed");
+        let the_final = the_reporter.snapshot(true);
+        let the_line = the_reporter.log_line_due(&the_final).expect("🏁 the last event always gets a line");
+        assert!(the_line.starts_with("📊 ci finished: ~500 docs (50.0%)"), "🎯 got: {}", the_line);
+    }
+
     /// 🧪 The one where concurrent drainers don't lose data.
     /// Multiple threads hammering the same counters — like Black Friday at Costco. 🛒🦆
     #[tokio::test]
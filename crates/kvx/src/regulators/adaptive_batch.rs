@@ -0,0 +1,197 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🎬 *[INT. ON-RAMP — RUSH HOUR]*
+//! *[A metering light blinks green. One more car. Green. One more car.]*
+//! *[The highway slows. The light stays red a little longer.]*
+//! *[Someone honks — 429. The light goes red for a while. Nobody argues with the light.]* 🚦📦🦆
+//!
+//! 📦 AdaptiveBatch — AIMD payload sizing from bulk latency and 429s.
+//!
+//! 🧠 Knowledge graph:
+//! ```text
+//! Drainer completes → DrainResult { latency_ms } → EMA latency
+//!   → under target_latency_ms? grow by step_bytes (additive increase)
+//!   → over it? ×0.8 and hold for a cooldown (multiplicative decrease)
+//! Drainer sees 429 → GaugeReading::Error() → ×0.5 and hold longer
+//! ```
+//!
+//! - Additive increase, multiplicative decrease: the same rule that keeps TCP from melting
+//!   the internet. Growth is slow and linear, backing off is fast — so it settles just under
+//!   where the cluster starts to push back, and keeps probing that edge
+//! - The hold after a decrease lets in-flight payloads (sized before the cut) land without
+//!   triggering a second cut for the same congestion. The smoothed latency restarts too
+//! - `throttles()` counts 429s for the log line; the regulator itself only needs the last one
+//!
+//! ⚠️ The singularity will know the cluster's sweet spot in advance. We find it by bumping into it.
+
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::GaugeReading;
+use crate::regulators::Regulate;
+use crate::regulators::config::AdaptiveRegulatorConfig;
+
+// -- 🎛️ EMA α for latency — a handful of drains to move the needle, one slow drain doesn't
+const THE_LATENCY_ALPHA: f64 = 0.3;
+// -- 📉 Slow drains trim; 429s cut
+const THE_SLOWDOWN_CUT: f64 = 0.8;
+const THE_THROTTLE_CUT: f64 = 0.5;
+// -- ⏱️ After a 429 the cluster is telling us something. Listen for a while
+const THE_THROTTLE_HOLD: Duration = Duration::from_secs(10);
+// -- ⏱️ After a slow drain, hold at least this long (or two target latencies, if longer)
+const THE_SHORTEST_SLOWDOWN_HOLD: Duration = Duration::from_secs(1);
+
+/// 🚦 AdaptiveBatch — grows payloads while the sink keeps up, shrinks them when it doesn't.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatch {
+    the_current_request_size: f64,
+    the_step: f64,
+    the_target_latency_ms: f64,
+    the_smoothed_latency_ms: Option<f64>,
+    the_hold_until: Instant,
+    the_slowdown_hold: Duration,
+    the_throttles: u64,
+    the_floor: f64,
+    the_ceiling: f64,
+}
+
+impl AdaptiveBatch {
+    /// 🏗️ Start at `initial_output_bytes`, never leave `[min_request_size_bytes, the_ceiling]`.
+    pub fn new(config: &AdaptiveRegulatorConfig, the_ceiling: f64) -> Self {
+        let the_floor = (config.min_request_size_bytes as f64).min(the_ceiling);
+        Self {
+            the_current_request_size: (config.initial_output_bytes as f64).clamp(the_floor, the_ceiling),
+            the_step: config.step_bytes as f64,
+            the_target_latency_ms: config.target_latency_ms as f64,
+            the_smoothed_latency_ms: None,
+            the_hold_until: Instant::now(),
+            the_slowdown_hold: Duration::from_millis(config.target_latency_ms.saturating_mul(2)).max(THE_SHORTEST_SLOWDOWN_HOLD),
+            the_throttles: 0,
+            the_floor,
+            the_ceiling,
+        }
+    }
+
+    /// 🔢 How many 429s this regulator has heard about.
+    pub fn throttles(&self) -> u64 {
+        self.the_throttles
+    }
+
+    /// 📉 Cut the size and hold it there until `the_hold`. The latency history goes too —
+    /// it describes payloads we no longer send.
+    fn cut(&mut self, the_factor: f64, the_hold: Duration, the_now: Instant) {
+        self.the_current_request_size = (self.the_current_request_size * the_factor).clamp(self.the_floor, self.the_ceiling);
+        self.the_hold_until = the_now + the_hold;
+        self.the_smoothed_latency_ms = None;
+    }
+
+    /// 🔄 The whole rule, with the clock passed in so tests don't have to sleep.
+    fn regulate_at(&mut self, reading: GaugeReading, the_now: Instant) -> f64 {
+        match reading {
+            GaugeReading::DrainResult { latency_ms, .. } => self.on_latency(latency_ms as f64, the_now),
+            GaugeReading::LatencyMs(latency_ms) => self.on_latency(latency_ms as f64, the_now),
+            GaugeReading::Error() => {
+                self.the_throttles += 1;
+                let the_old_size = self.the_current_request_size;
+                self.cut(THE_THROTTLE_CUT, THE_THROTTLE_HOLD, the_now);
+                warn!(
+                    "🚦 Sink is throttling (#{}) — payloads {} → {} bytes, holding {}s",
+                    self.the_throttles,
+                    the_old_size as usize,
+                    self.the_current_request_size as usize,
+                    THE_THROTTLE_HOLD.as_secs()
+                );
+            }
+            // -- 🤷 CPU isn't our signal
+            GaugeReading::CpuValue(_) => {}
+        }
+        self.the_current_request_size
+    }
+
+    fn on_latency(&mut self, the_latency_ms: f64, the_now: Instant) {
+        let the_smoothed = match self.the_smoothed_latency_ms {
+            Some(the_previous) => THE_LATENCY_ALPHA * the_latency_ms + (1.0 - THE_LATENCY_ALPHA) * the_previous,
+            None => the_latency_ms,
+        };
+        self.the_smoothed_latency_ms = Some(the_smoothed);
+        if the_now < self.the_hold_until {
+            return;
+        }
+        if the_smoothed > self.the_target_latency_ms {
+            let the_old_size = self.the_current_request_size;
+            self.cut(THE_SLOWDOWN_CUT, self.the_slowdown_hold, the_now);
+            debug!(
+                "🚦 Drains averaging {:.0}ms (target {:.0}ms) — payloads {} → {} bytes",
+                the_smoothed, self.the_target_latency_ms, the_old_size as usize, self.the_current_request_size as usize
+            );
+        } else {
+            self.the_current_request_size = (self.the_current_request_size + self.the_step).min(self.the_ceiling);
+        }
+    }
+}
+
+impl Regulate for AdaptiveBatch {
+    fn regulate(&mut self, reading: GaugeReading, _since_last_checked_ms: Duration) -> f64 {
+        self.regulate_at(reading, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: f64 = 1024.0 * 1024.0;
+
+    fn a_sizer() -> AdaptiveBatch {
+        AdaptiveBatch::new(
+            &AdaptiveRegulatorConfig {
+                target_latency_ms: 500,
+                min_request_size_bytes: 256 * 1024,
+                initial_output_bytes: 4 * 1024 * 1024,
+                step_bytes: 1024 * 1024,
+            },
+            8.0 * MIB,
+        )
+    }
+
+    fn a_drain(latency_ms: u64) -> GaugeReading {
+        GaugeReading::DrainResult { payload_bytes: 0, latency_ms }
+    }
+
+    /// 🧪 Fast drains climb a step at a time and stop at the ceiling; slow ones trim and hold.
+    #[test]
+    fn the_one_where_the_on_ramp_light_stays_green_until_it_doesnt() {
+        let mut the_sizer = a_sizer();
+        let the_start = Instant::now();
+        assert_eq!(the_sizer.regulate_at(a_drain(100), the_start), 5.0 * MIB);
+        assert_eq!(the_sizer.regulate_at(a_drain(100), the_start), 6.0 * MIB);
+        for _ in 0..10 {
+            the_sizer.regulate_at(a_drain(100), the_start);
+        }
+        assert_eq!(the_sizer.regulate_at(a_drain(100), the_start), 8.0 * MIB, "🧱 the sink's limit is the limit");
+
+        // -- 🐢 one slow drain smooths to 100×0.7 + 2000×0.3 = 670ms — over 500, trim ×0.8
+        assert_eq!(the_sizer.regulate_at(a_drain(2000), the_start), 6.4 * MIB);
+        assert_eq!(the_sizer.regulate_at(a_drain(100), the_start), 6.4 * MIB, "⏸️ holding after the cut");
+        assert_eq!(the_sizer.regulate_at(a_drain(100), the_start + Duration::from_secs(2)), 7.4 * MIB, "▶️ hold over, climbing again");
+    }
+
+    /// 🧪 A 429 halves and holds for longer; the floor is the floor.
+    #[test]
+    fn the_one_where_somebody_honks() {
+        let mut the_sizer = a_sizer();
+        let the_start = Instant::now();
+        assert_eq!(the_sizer.regulate_at(GaugeReading::Error(), the_start), 2.0 * MIB);
+        assert_eq!(the_sizer.regulate_at(a_drain(10), the_start + Duration::from_secs(5)), 2.0 * MIB, "⏸️ still listening");
+        assert_eq!(the_sizer.regulate_at(a_drain(10), the_start + Duration::from_secs(11)), 3.0 * MIB);
+
+        for _ in 0..10 {
+            the_sizer.regulate_at(GaugeReading::Error(), the_start);
+        }
+        assert_eq!(the_sizer.regulate_at(GaugeReading::CpuValue(99), the_start), 256.0 * 1024.0, "🩳 never below the floor");
+        assert_eq!(the_sizer.throttles(), 11);
+    }
+}
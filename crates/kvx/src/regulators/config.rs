@@ -133,4 +133,38 @@ pub struct ThroughputSeekerConfig {
 fn default_window_duration_secs() -> u64 { 5 }
 fn default_improvement_threshold_pct() -> f64 { 10.0 }
 fn default_degradation_threshold_pct() -> f64 { 35.0 }
-fn default_re_explore_after_windows() -> usize { 30 }
\ No newline at end of file
+fn default_re_explore_after_windows() -> usize { 30 }
+
+/// 🔧 Configuration for the adaptive (AIMD) batch sizer, deserialized from TOML `[flow_master.Adaptive]`.
+///
+/// 📜 Example TOML:
+/// ```toml
+/// [flow_master.Adaptive]
+/// target_latency_ms = 1000
+/// step_bytes = 524288
+/// ```
+///
+/// 🧠 Grows payloads by `step_bytes` after every drain that beats `target_latency_ms`,
+/// shrinks them on slow drains and harder on 429s. Like TCP congestion control,
+/// except the congestion is an Elasticsearch write thread pool. 🦆
+#[derive(Debug, Deserialize, Clone)]
+pub struct AdaptiveRegulatorConfig {
+    /// 🎯 Bulk latency (smoothed) to stay under, in ms (default: 1000)
+    #[serde(default = "default_target_latency_ms")]
+    pub target_latency_ms: u64,
+
+    /// 📏 Minimum request size bytes — the floor (default: 128 KiB)
+    #[serde(default = "default_min_request_size_bytes")]
+    pub min_request_size_bytes: usize,
+
+    /// 📊 Initial output bytes — where the climb starts (default: 4 MiB)
+    #[serde(default = "default_initial_output_bytes")]
+    pub initial_output_bytes: usize,
+
+    /// 📈 How much a fast drain grows the payload, in bytes (default: 512 KiB)
+    #[serde(default = "default_step_bytes")]
+    pub step_bytes: usize,
+}
+
+fn default_target_latency_ms() -> u64 { 1000 }
+fn default_step_bytes() -> usize { 512 * 1024 } // 📈 512 KiB
@@ -25,6 +25,7 @@
 //!
 //! ⚠️ The singularity will self-regulate. We're just practicing.
 
+pub mod adaptive_batch;
 pub mod config;
 pub mod cpu_pressure;
 pub mod pressure_gauge;
@@ -33,6 +34,8 @@ pub mod throughput_seeker;
 
 use std::time::Duration;
 
+pub use adaptive_batch::AdaptiveBatch;
+pub use config::AdaptiveRegulatorConfig;
 pub use config::CpuRegulatorConfig;
 pub use config::StaticRegulatorConfig;
 pub use config::LatencyRegulatorConfig;
@@ -77,6 +80,8 @@ pub enum Regulators {
     CpuPressure(CpuPressure),
     /// 🏔️ Throughput-seeking hill climber — directly optimizes bytes/sec
     ThroughputSeeker(ThroughputSeeker),
+    /// 🚦 AIMD batch sizing from bulk latency and 429s
+    AdaptiveBatch(AdaptiveBatch),
 }
 
 impl Regulators {
@@ -120,6 +125,12 @@ impl Regulators {
             sink_max_request_size_bytes as f64,
         ))
     }
+
+    /// 🏗️ Create a Regulators instance from adaptive config — grow while the sink keeps up,
+    /// back off on slow drains and 429s. The sink's limit is the ceiling, as everywhere. 🚦
+    pub fn from_adaptive_config(config: &AdaptiveRegulatorConfig, sink_max_request_size_bytes: usize) -> Self {
+        Regulators::AdaptiveBatch(AdaptiveBatch::new(config, sink_max_request_size_bytes as f64))
+    }
 }
 
 impl Regulate for Regulators {
@@ -128,6 +139,7 @@ impl Regulate for Regulators {
             Regulators::Static(the_byte_value) => the_byte_value.regulate(reading, since_last_checked_ms),
             Regulators::CpuPressure(the_pid) => the_pid.regulate(reading, since_last_checked_ms),
             Regulators::ThroughputSeeker(the_seeker) => the_seeker.regulate(reading, since_last_checked_ms),
+            Regulators::AdaptiveBatch(the_sizer) => the_sizer.regulate(reading, since_last_checked_ms),
         }
     }
 }
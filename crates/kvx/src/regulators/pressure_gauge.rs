@@ -119,12 +119,10 @@ pub async fn read_node_pressure(
     let mut the_cpu_sum = 0.0_f64;
     let mut the_node_count = 0_u64;
 
-    for (_node_id, node) in &the_stats.nodes {
-        if let Some(os) = &node.os {
-            if let Some(cpu) = &os.cpu {
-                the_cpu_sum += cpu.percent as f64;
-                the_node_count += 1;
-            }
+    for node in the_stats.nodes.values() {
+        if let Some(cpu) = node.os.as_ref().and_then(|os| os.cpu.as_ref()) {
+            the_cpu_sum += cpu.percent as f64;
+            the_node_count += 1;
         }
     }
 
@@ -159,7 +157,7 @@ pub fn spawn_pressure_gauge(
     flow_knob: FlowKnob,
     sink_max_request_size_bytes: usize,
 ) -> JoinHandle<()> {
-    tokio::spawn(async move {
+    crate::workers::spawn_named("pressure-gauge", async move {
         let the_poll_interval = std::time::Duration::from_secs(config.poll_interval_secs);
         let mut the_regulator = Regulators::from_config(&config, sink_max_request_size_bytes);
 
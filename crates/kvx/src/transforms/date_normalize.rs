@@ -0,0 +1,257 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📅 DateNormalize — rewrite date fields from whatever the source said to ISO-8601 UTC.
+//!
+//! 🎬 *[`CreationDate: "03/14/2021 09:26"`. `LastUpdate: 1615714000000`. `AcceptedDate: "2021-03-14"`.]*
+//! *[the target mapping says `date`. the target mapping rejects two of them.]*
+//! *[it does not say which two. it says `mapper_parsing_exception`. it says it 40,000 times.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! DateNormalize = { fields = ["CreationDate", "LastUpdate"], formats = ["%m/%d/%Y %H:%M", "epoch_millis"] }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Each listed field (a `FieldPath`) is tried against RFC 3339 first, then each `formats`
+//!   entry in order. The first that parses wins, and the value becomes
+//!   `2021-03-14T09:26:00Z` (fractional seconds only when there are some)
+//! - `formats` are chrono strftime patterns, plus Elasticsearch's names: `epoch_millis`,
+//!   `epoch_second`, `rfc2822`. Epoch formats take numbers or numeric strings
+//! - A pattern with an offset (`%z`) is converted to UTC; one without is taken as UTC;
+//!   a date-only pattern means midnight
+//! - Missing and `null` fields are left alone. A value nothing parses follows
+//!   `on_unparseable`: `keep` (default, counted in the end-of-run warnings), `remove`, `fail` 🦆
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+use super::field_path::FieldPath;
+
+/// 🚦 What to do with a date field no format can parse.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnparseableDatePolicy {
+    /// 🤷 Leave the value as it was; count it for the end-of-run warnings
+    #[default]
+    Keep,
+    /// ✂️ Remove the field, so the rest of the doc still fits the mapping
+    Remove,
+    /// 💀 Fail the run
+    Fail,
+}
+
+/// 📅 `[[transforms]] DateNormalize = { fields = [..], formats = [..] }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct DateNormalizeConfig {
+    /// 🔎 Field paths holding dates
+    pub fields: Vec<String>,
+    /// 📜 Input patterns to try after RFC 3339: strftime, `epoch_millis`, `epoch_second`, `rfc2822`
+    #[serde(default)]
+    pub formats: Vec<String>,
+    /// 🚦 `keep` (default), `remove` or `fail`
+    #[serde(default)]
+    pub on_unparseable: UnparseableDatePolicy,
+}
+
+/// 📜 One compiled input format.
+#[derive(Debug, Clone, PartialEq)]
+enum DateFormat {
+    EpochMillis,
+    EpochSecond,
+    Rfc2822,
+    Pattern(String),
+}
+
+impl DateFormat {
+    fn parse(&self, value: &Value) -> Option<DateTime<Utc>> {
+        match (self, value) {
+            (Self::EpochMillis, _) => DateTime::from_timestamp_millis(epoch_number(value)?),
+            (Self::EpochSecond, _) => DateTime::from_timestamp(epoch_number(value)?, 0),
+            (Self::Rfc2822, Value::String(the_text)) => {
+                DateTime::parse_from_rfc2822(the_text).ok().map(|the_date| the_date.with_timezone(&Utc))
+            }
+            (Self::Pattern(the_pattern), Value::String(the_text)) => {
+                // -- 🎯 with an offset, without one, date only — strftime can't say which it is, so ask all three
+                if let Ok(the_date) = DateTime::parse_from_str(the_text, the_pattern) {
+                    return Some(the_date.with_timezone(&Utc));
+                }
+                if let Ok(the_naive) = NaiveDateTime::parse_from_str(the_text, the_pattern) {
+                    return Some(the_naive.and_utc());
+                }
+                NaiveDate::parse_from_str(the_text, the_pattern).ok()?.and_hms_opt(0, 0, 0).map(|the_naive| the_naive.and_utc())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 🔢 `1615714000000` or `"1615714000000"` — epochs show up both ways.
+fn epoch_number(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(the_number) => the_number.as_i64(),
+        Value::String(the_text) => the_text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Unparsed {
+    count: AtomicUsize,
+    first: OnceLock<String>,
+}
+
+/// 📅 The compiled field list and formats. Clones share the unparseable tally.
+#[derive(Debug, Clone)]
+pub struct DateNormalize {
+    fields: Vec<FieldPath>,
+    formats: Vec<DateFormat>,
+    on_unparseable: UnparseableDatePolicy,
+    unparsed: Arc<Unparsed>,
+}
+
+impl DateNormalize {
+    /// 🔨 Parse the field paths and name the formats.
+    pub fn from_config(config: &DateNormalizeConfig) -> Result<Self> {
+        if config.fields.is_empty() {
+            anyhow::bail!("💀 DateNormalize needs `fields` — which fields hold the dates?");
+        }
+        let the_fields = config
+            .fields
+            .iter()
+            .map(|the_path| FieldPath::parse(the_path))
+            .collect::<Result<Vec<_>>>()
+            .context("💀 DateNormalize has a bad `fields` entry")?;
+        let the_formats = config
+            .formats
+            .iter()
+            .map(|the_format| match the_format.as_str() {
+                "epoch_millis" => DateFormat::EpochMillis,
+                "epoch_second" => DateFormat::EpochSecond,
+                "rfc2822" => DateFormat::Rfc2822,
+                the_pattern => DateFormat::Pattern(the_pattern.to_string()),
+            })
+            .collect();
+        Ok(Self { fields: the_fields, formats: the_formats, on_unparseable: config.on_unparseable, unparsed: Arc::default() })
+    }
+
+    /// 🤷 How many values were left as-is because nothing parsed them (`on_unparseable = "keep"`).
+    pub fn unparsed(&self) -> Option<String> {
+        let the_count = self.unparsed.count.load(Ordering::Relaxed);
+        (the_count > 0).then(|| {
+            format!(
+                "DateNormalize couldn't parse {} date value(s) and left them as they were. First: {}",
+                the_count,
+                self.unparsed.first.get().map(String::as_str).unwrap_or_default()
+            )
+        })
+    }
+
+    fn normalize(&self, value: &Value) -> Option<String> {
+        let the_rfc3339 = match value {
+            Value::String(the_text) => DateTime::parse_from_rfc3339(the_text).ok().map(|the_date| the_date.with_timezone(&Utc)),
+            _ => None,
+        };
+        the_rfc3339
+            .or_else(|| self.formats.iter().find_map(|the_format| the_format.parse(value)))
+            .map(|the_date| the_date.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+impl Transform for DateNormalize {
+    fn transform(&self, mut doc: Value) -> Result<Option<Value>> {
+        for the_field in &self.fields {
+            let the_value = match the_field.get(&doc) {
+                None | Some(Value::Null) => continue,
+                Some(the_value) => the_value,
+            };
+            if let Some(the_iso) = self.normalize(the_value) {
+                let (_, the_position) = the_field.take(&mut doc).unwrap_or((Value::Null, usize::MAX));
+                the_field.set(&mut doc, Value::String(the_iso), Some(the_position))?;
+                continue;
+            }
+            let the_complaint = format!("`{}` = {}", the_field, the_value);
+            match self.on_unparseable {
+                UnparseableDatePolicy::Keep => {
+                    self.unparsed.count.fetch_add(1, Ordering::Relaxed);
+                    let _ = self.unparsed.first.set(the_complaint);
+                }
+                UnparseableDatePolicy::Remove => {
+                    the_field.take(&mut doc);
+                }
+                UnparseableDatePolicy::Fail => {
+                    anyhow::bail!("💀 DateNormalize couldn't parse {} with RFC 3339 or any of the `formats`", the_complaint)
+                }
+            }
+        }
+        Ok(Some(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_one_where_every_date_learns_to_speak_iso() -> Result<()> {
+        let the_normalizer = DateNormalize::from_config(&toml::from_str(
+            r#"
+            fields = ["Created", "Updated", "Accepted", "Meta.Seen", "Already", "Shifted", "Missing", "Nothing"]
+            formats = ["%m/%d/%Y %H:%M", "epoch_millis", "%Y-%m-%d", "%d %b %Y %H:%M:%S %z"]
+            "#,
+        )?)?;
+        let the_result = the_normalizer
+            .transform(json!({
+                "Created": "03/14/2021 09:26",
+                "Updated": 1615714000123_i64,
+                "Accepted": "2021-03-14",
+                "Meta": {"Seen": "1615714000000"},
+                "Already": "2021-03-14T10:00:00+01:00",
+                "Shifted": "14 Mar 2021 10:00:00 +0100",
+                "Nothing": null
+            }))?
+            .expect("dates never drop docs");
+        assert_eq!(
+            the_result,
+            json!({
+                "Created": "2021-03-14T09:26:00Z",
+                "Updated": "2021-03-14T09:26:40.123Z",
+                "Accepted": "2021-03-14T00:00:00Z",
+                "Meta": {"Seen": "2021-03-14T09:26:40Z"},
+                "Already": "2021-03-14T09:00:00Z",
+                "Shifted": "2021-03-14T09:00:00Z",
+                "Nothing": null
+            })
+        );
+        assert_eq!(
+            serde_json::to_string(&the_normalizer.transform(json!({"a": 1, "Created": "03/14/2021 09:26", "z": 2}))?)?,
+            r#"{"a":1,"Created":"2021-03-14T09:26:00Z","z":2}"#,
+            "🪑 rewritten fields keep their seat"
+        );
+        assert!(the_normalizer.unparsed().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_someday_is_not_a_date() -> Result<()> {
+        let a_normalizer = |policy: &str| -> Result<DateNormalize> {
+            DateNormalize::from_config(&toml::from_str(&format!("fields = [\"When\"]\non_unparseable = \"{}\"", policy))?)
+        };
+        let the_keeper = a_normalizer("keep")?;
+        assert_eq!(the_keeper.transform(json!({"When": "someday"}))?, Some(json!({"When": "someday"})));
+        assert!(the_keeper.unparsed().is_some_and(|s| s.contains("1 date value(s)") && s.contains("`When` = \"someday\"")));
+
+        assert_eq!(a_normalizer("remove")?.transform(json!({"When": "someday", "x": 1}))?, Some(json!({"x": 1})));
+        assert!(a_normalizer("fail")?.transform(json!({"When": "someday"})).is_err());
+        assert!(DateNormalize::from_config(&DateNormalizeConfig::default()).is_err());
+        Ok(())
+    }
+}
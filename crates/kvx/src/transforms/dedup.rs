@@ -0,0 +1,180 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 👯 Dedup — drop documents whose id was already seen earlier in the run.
+//!
+//! 🎬 *[the export has 1,000,000 lines. the index ends up with 940,000 docs.]*
+//! *[nothing failed. Elasticsearch just overwrote 60,000 of them, quietly, politely.]*
+//! *[now the second copy is caught at the door, counted, and written down.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Dedup = { id_field = "ObjectID", rejects_file = "duplicates.ndjson" }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Keyed by `id_field` (a `FieldPath`), compared the way Elasticsearch compares `_id`s:
+//!   `7` and `"7"` are the same id. Docs without the field pass through untouched
+//! - First one wins. Later copies are dropped, counted in a shared `Rejects`, and appended
+//!   to `rejects_file` if set
+//! - Remembers 64-bit hashes, not ids: ~16 bytes per id, sharded across mutexes so joiners
+//!   don't queue on one lock. A false "duplicate" needs a 64-bit collision — at 100M ids,
+//!   odds are around one in four thousand per run
+//! - Bounded by `max_tracked_ids`. Past that, new ids are no longer remembered (already-seen
+//!   ones are still caught) and the end-of-run warnings say so. No disk spill — yet 🦆
+
+use std::collections::HashSet;
+use std::hash::{BuildHasher, RandomState};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+
+use super::Transform;
+use super::field_path::FieldPath;
+use super::rejects::Rejects;
+
+/// 🔢 How many lock shards the seen-set is split into. A power of two, so a mask picks one.
+const THE_SHARD_COUNT: usize = 16;
+
+fn default_max_tracked_ids() -> usize {
+    10_000_000
+}
+
+/// 👯 `[[transforms]] Dedup = { id_field = "ObjectID" }`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DedupConfig {
+    /// 🔑 Field path holding the document id
+    pub id_field: String,
+    /// 🧮 Stop remembering new ids after this many (memory cap, ~16 bytes each). Default 10M
+    #[serde(default = "default_max_tracked_ids")]
+    pub max_tracked_ids: usize,
+    /// 🧾 Where duplicates go (NDJSON, appended). Without it they are only counted
+    #[serde(default)]
+    pub rejects_file: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+struct SeenIds {
+    hasher: RandomState,
+    shards: Vec<Mutex<HashSet<u64>>>,
+    tracked: AtomicUsize,
+    saturated: AtomicBool,
+}
+
+/// 👯 The shared seen-set. Clones (one per joiner) share it, so duplicates are caught across joiners.
+#[derive(Debug, Clone)]
+pub struct Dedup {
+    id_field: FieldPath,
+    max_tracked_ids: usize,
+    seen: Arc<SeenIds>,
+    rejects: Rejects,
+}
+
+impl Dedup {
+    /// 🔨 Parse the id path and open the rejects file.
+    pub fn from_config(config: &DedupConfig) -> Result<Self> {
+        let the_id_field = FieldPath::parse(&config.id_field).context("💀 Dedup has a bad `id_field`")?;
+        if config.max_tracked_ids == 0 {
+            anyhow::bail!("💀 Dedup `max_tracked_ids = 0` would remember nothing and catch nothing");
+        }
+        Ok(Self {
+            id_field: the_id_field,
+            max_tracked_ids: config.max_tracked_ids,
+            seen: Arc::new(SeenIds {
+                hasher: RandomState::new(),
+                shards: (0..THE_SHARD_COUNT).map(|_| Mutex::new(HashSet::new())).collect(),
+                tracked: AtomicUsize::new(0),
+                saturated: AtomicBool::new(false),
+            }),
+            rejects: Rejects::open(config.rejects_file.as_deref())?,
+        })
+    }
+
+    /// 🧾 The shared tally of duplicates dropped.
+    pub fn rejects(&self) -> &Rejects {
+        &self.rejects
+    }
+
+    /// 🫗 Did the seen-set hit `max_tracked_ids`? If so, some duplicates may have gotten through.
+    pub fn saturation(&self) -> Option<String> {
+        self.seen.saturated.load(Ordering::Relaxed).then(|| {
+            format!(
+                "Dedup on `{}` stopped remembering new ids after {} — duplicates of later ids may have been shipped. Raise `max_tracked_ids` to cover the run.",
+                self.id_field, self.max_tracked_ids
+            )
+        })
+    }
+}
+
+impl Transform for Dedup {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        // -- 🔑 Elasticsearch `_id`s are strings: `7` and `"7"` land on the same doc
+        let the_id = match self.id_field.get(&doc) {
+            None | Some(Value::Null) => return Ok(Some(doc)),
+            Some(Value::String(the_id)) => the_id.clone(),
+            Some(the_other) => the_other.to_string(),
+        };
+        let the_hash = self.seen.hasher.hash_one(&the_id);
+        let the_shard = &self.seen.shards[(the_hash as usize) & (THE_SHARD_COUNT - 1)];
+        let mut the_set = the_shard.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if the_set.contains(&the_hash) {
+            drop(the_set);
+            self.rejects.reject(&format!("duplicate {} `{}`", self.id_field, the_id), &doc)?;
+            return Ok(None);
+        }
+        if self.seen.tracked.load(Ordering::Relaxed) < self.max_tracked_ids {
+            the_set.insert(the_hash);
+            self.seen.tracked.fetch_add(1, Ordering::Relaxed);
+        } else if !self.seen.saturated.swap(true, Ordering::Relaxed) {
+            warn!("🫗 Dedup is tracking {} ids, its `max_tracked_ids` — new ids are no longer remembered", self.max_tracked_ids);
+        }
+        Ok(Some(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_one_where_the_second_copy_is_turned_away() -> Result<()> {
+        let the_rejects_file = tempfile::NamedTempFile::new()?;
+        let the_dedup = Dedup::from_config(&toml::from_str(&format!(
+            "id_field = 'meta.id'\nrejects_file = '{}'",
+            the_rejects_file.path().display()
+        ))?)?;
+        let the_twin = the_dedup.clone();
+
+        assert!(the_dedup.transform(json!({"meta": {"id": 7}, "v": 1}))?.is_some());
+        assert!(the_twin.transform(json!({"meta": {"id": "7"}, "v": 2}))?.is_none(), "👯 7 and \"7\" are one _id, across clones");
+        assert!(the_dedup.transform(json!({"meta": {"id": 8}}))?.is_some());
+        assert!(the_dedup.transform(json!({"no_id": true}))?.is_some());
+        assert!(the_dedup.transform(json!({"no_id": true}))?.is_some(), "🤷 no id, nothing to compare");
+
+        assert_eq!(the_dedup.rejects().count(), 1);
+        let the_record: Value = serde_json::from_str(std::fs::read_to_string(the_rejects_file.path())?.trim_end())?;
+        assert_eq!(the_record, json!({"error": "duplicate meta.id `7`", "document": {"meta": {"id": "7"}, "v": 2}}));
+        assert!(the_dedup.saturation().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_memory_runs_out_and_says_so() -> Result<()> {
+        let the_dedup = Dedup::from_config(&DedupConfig { id_field: "id".to_string(), max_tracked_ids: 1, rejects_file: None })?;
+        assert!(the_dedup.transform(json!({"id": 1}))?.is_some());
+        assert!(the_dedup.transform(json!({"id": 2}))?.is_some());
+        assert!(the_dedup.transform(json!({"id": 2}))?.is_some(), "🫗 2 was never remembered");
+        assert!(the_dedup.transform(json!({"id": 1}))?.is_none(), "🎯 1 still is");
+        assert!(the_dedup.saturation().is_some());
+
+        assert!(Dedup::from_config(&DedupConfig { id_field: "id".to_string(), max_tracked_ids: 0, rejects_file: None }).is_err());
+        Ok(())
+    }
+}
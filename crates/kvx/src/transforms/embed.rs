@@ -0,0 +1,451 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧠 Embed — send text fields to an inference endpoint, put the vectors it sends back on the doc.
+//!
+//! 🎬 *[the new cluster has `dense_vector` mappings and a kNN search page. the docs have words.]*
+//! *[a notebook embeds them 32 at a time. it's on doc 41,000 of 9 million. the laptop lid closes.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Embed = { url = "http://tei:8080/v1/embeddings", model = "bge-small-en", fields = { description = "description_vector" } }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - The one step that goes over the network. It overrides `transform_batch`, so it sees a
+//!   whole page of docs from `TransformChain::apply_all` and sends their texts `batch_size` per request
+//! - Joiners are plain threads. The requests run on the tokio runtime `from_config` was called
+//!   on (`Handle::block_on`): the joiner waits, the runtime does the I/O
+//! - `max_concurrency` is one `Semaphore` shared by every clone, so it caps requests in flight
+//!   across all joiners together — the endpoint never sees more, however many joiners there are
+//! - `format = "openai"` (default) speaks `/v1/embeddings`; `custom` names the input field and
+//!   a JSON pointer to the array of vectors (`inputs` + `""` is Hugging Face TEI's `/embed`)
+//! - 429, 5xx and dropped connections are retried `max_retries` times, backing off from 500ms.
+//!   A request that still fails fails every doc in it — to `skip_bad_docs`' file, or the run
+//! - A field that's missing, empty or not a string gets no vector; the end-of-run warnings count them 🦆
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use super::Transform;
+use super::field_path::FieldPath;
+use crate::backends::config::resolve_secret_file;
+
+/// ⏳ The first retry waits this long; each one after waits twice as long as the last.
+const FIRST_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 📡 How requests and responses are shaped.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedFormat {
+    /// `{"model": .., "input": [..]}` → `{"data": [{"index": 0, "embedding": [..]}, ..]}`
+    #[default]
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// `{"<input_field>": [..]}` → an array of vectors at `vectors_pointer` (`""` = the whole response)
+    Custom {
+        input_field: String,
+        #[serde(default)]
+        vectors_pointer: String,
+    },
+}
+
+/// 🧠 `[[transforms]] Embed = { url = "..", fields = { body = "body_vector" } }`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EmbedConfig {
+    /// 📡 The endpoint texts are POSTed to
+    pub url: String,
+    /// 📐 `"openai"` (default) or `{ custom = { input_field, vectors_pointer } }`
+    #[serde(default)]
+    pub format: EmbedFormat,
+    /// 🏷️ Sent as `model` in every request body, when set
+    #[serde(default)]
+    pub model: Option<String>,
+    /// 🔎 Text field path → field path its vector goes to
+    pub fields: BTreeMap<String, String>,
+    /// 📦 Texts per request
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// 🚦 Requests in flight at once, across every joiner
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// 🔑 Extra request headers
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// 🔑 Sent as `Authorization: Bearer <key>`
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// 🔑 Read `api_key` from this file instead of keeping it in the config
+    #[serde(default)]
+    pub api_key_file: Option<PathBuf>,
+    /// ⏳ Per request, retries not included
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 🔁 Retries for 429s, 5xxs and dropped connections
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_batch_size() -> usize {
+    64
+}
+
+fn default_max_concurrency() -> usize {
+    4
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+/// 🧠 A client, the runtime it runs on, and the shared request budget. Clones share all three.
+#[derive(Debug, Clone)]
+pub struct Embed {
+    client: reqwest::Client,
+    runtime: Handle,
+    permits: Arc<Semaphore>,
+    url: String,
+    headers: HeaderMap,
+    format: EmbedFormat,
+    model: Option<String>,
+    // 🔎 (text field, vector field) pairs
+    fields: Arc<Vec<(FieldPath, FieldPath)>>,
+    batch_size: usize,
+    max_concurrency: usize,
+    max_retries: u32,
+    // 🤷 fields with no text to embed, for the end-of-run warnings
+    skipped: Arc<AtomicUsize>,
+}
+
+impl Embed {
+    /// 🔨 Parse the paths, read the key, build the client — and remember the runtime to send on.
+    pub fn from_config(config: &EmbedConfig) -> Result<Self> {
+        let the_runtime = Handle::try_current()
+            .context("💀 Embed sends its requests on the tokio runtime, so the transform chain has to be built inside one")?;
+        if config.fields.is_empty() {
+            anyhow::bail!("💀 Embed needs at least one entry in `fields`");
+        }
+        if config.batch_size == 0 || config.max_concurrency == 0 {
+            anyhow::bail!("💀 Embed's `batch_size` and `max_concurrency` must be at least 1");
+        }
+        let the_fields = config
+            .fields
+            .iter()
+            .map(|(the_text, the_vector)| {
+                Ok((
+                    FieldPath::parse(the_text).with_context(|| format!("💀 Embed has a bad text field '{}'", the_text))?,
+                    FieldPath::parse(the_vector).with_context(|| format!("💀 Embed has a bad vector field '{}'", the_vector))?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut the_headers = HeaderMap::new();
+        for (the_name, the_value) in &config.headers {
+            // -- 🔑 header values are often secrets: the error names the header, never the value
+            let the_header_name = HeaderName::from_bytes(the_name.as_bytes())
+                .with_context(|| format!("💀 '{}' isn't a valid HTTP header name", the_name))?;
+            let the_header_value = HeaderValue::from_str(the_value)
+                .with_context(|| format!("💀 The value of header '{}' isn't a valid HTTP header value", the_name))?;
+            the_headers.insert(the_header_name, the_header_value);
+        }
+        let mut the_api_key = config.api_key.clone();
+        resolve_secret_file(&mut the_api_key, config.api_key_file.as_deref(), "api_key")?;
+        if let Some(the_key) = the_api_key {
+            let mut the_header_value =
+                HeaderValue::from_str(&format!("Bearer {}", the_key)).context("💀 api_key isn't a valid HTTP header value")?;
+            the_header_value.set_sensitive(true);
+            the_headers.insert(AUTHORIZATION, the_header_value);
+        }
+        the_headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let the_client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("💀 Couldn't build the HTTP client for Embed")?;
+        Ok(Self {
+            client: the_client,
+            runtime: the_runtime,
+            permits: Arc::new(Semaphore::new(config.max_concurrency)),
+            url: config.url.clone(),
+            headers: the_headers,
+            format: config.format.clone(),
+            model: config.model.clone(),
+            fields: Arc::new(the_fields),
+            batch_size: config.batch_size,
+            max_concurrency: config.max_concurrency,
+            max_retries: config.max_retries,
+            skipped: Arc::default(),
+        })
+    }
+
+    /// 🤷 How many fields had no text and got no vector.
+    pub fn skipped(&self) -> Option<String> {
+        let the_count = self.skipped.load(Ordering::Relaxed);
+        (the_count > 0).then(|| format!("Embed found no text in {} field(s) and attached no vector for them", the_count))
+    }
+
+    /// 📡 One request: `texts` in, one vector per text out, in the same order.
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Value>> {
+        // -- 🚦 held for the whole request, retries included — the cap is on the endpoint's load
+        let _the_permit = self.permits.acquire().await.context("💀 Embed's request budget was closed")?;
+        let mut the_body = Map::new();
+        if let Some(ref the_model) = self.model {
+            the_body.insert("model".to_string(), json!(the_model));
+        }
+        let the_input_field = match self.format {
+            EmbedFormat::OpenAi => "input",
+            EmbedFormat::Custom { ref input_field, .. } => input_field.as_str(),
+        };
+        the_body.insert(the_input_field.to_string(), json!(texts));
+        let the_body = Value::Object(the_body).to_string();
+
+        let mut the_backoff = FIRST_BACKOFF;
+        for the_attempt in 0..=self.max_retries {
+            let the_failure = match self.client.post(&self.url).headers(self.headers.clone()).body(the_body.clone()).send().await {
+                Ok(the_response) if the_response.status().is_success() => {
+                    let the_bytes = the_response.bytes().await.context("💀 The embedding response broke off")?;
+                    return self.vectors(&the_bytes, texts.len());
+                }
+                Ok(the_response) => {
+                    let the_status = the_response.status();
+                    let the_preview: String = the_response.text().await.unwrap_or_default().chars().take(300).collect();
+                    if the_status != reqwest::StatusCode::TOO_MANY_REQUESTS && !the_status.is_server_error() {
+                        anyhow::bail!("💀 The embedding endpoint answered {}: {}", the_status, the_preview);
+                    }
+                    format!("{}: {}", the_status, the_preview)
+                }
+                Err(the_error) => format!("{:#}", the_error),
+            };
+            if the_attempt == self.max_retries {
+                anyhow::bail!("💀 The embedding endpoint failed {} time(s) in a row. Last: {}", the_attempt + 1, the_failure);
+            }
+            warn!("🔁 Embedding request failed ({}) — retrying in {:?}", the_failure, the_backoff);
+            tokio::time::sleep(the_backoff).await;
+            the_backoff *= 2;
+        }
+        unreachable!("🦆 the last attempt either returns or bails")
+    }
+
+    /// 📐 The vectors out of a response, in the order the texts went in.
+    fn vectors(&self, the_bytes: &[u8], the_expected: usize) -> Result<Vec<Value>> {
+        let the_response: Value = serde_json::from_slice(the_bytes).context("💀 The embedding endpoint didn't answer with JSON")?;
+        let the_vectors = match self.format {
+            EmbedFormat::OpenAi => {
+                let Some(Value::Array(the_data)) = the_response.get("data") else {
+                    anyhow::bail!("💀 The embedding response has no `data` array");
+                };
+                // -- 🔢 `index` says which input each one is for; servers may answer out of order
+                let mut the_indexed: Vec<(u64, Value)> = the_data
+                    .iter()
+                    .enumerate()
+                    .map(|(the_position, the_item)| {
+                        let the_index = the_item.get("index").and_then(Value::as_u64).unwrap_or(the_position as u64);
+                        (the_index, the_item.get("embedding").cloned().unwrap_or(Value::Null))
+                    })
+                    .collect();
+                the_indexed.sort_by_key(|(the_index, _)| *the_index);
+                the_indexed.into_iter().map(|(_, the_vector)| the_vector).collect::<Vec<_>>()
+            }
+            EmbedFormat::Custom { ref vectors_pointer, .. } => match the_response.pointer(vectors_pointer) {
+                Some(Value::Array(the_vectors)) => the_vectors.clone(),
+                _ => anyhow::bail!("💀 The embedding response has no array of vectors at '{}'", vectors_pointer),
+            },
+        };
+        if the_vectors.len() != the_expected {
+            anyhow::bail!("💀 Sent {} text(s) to embed, got {} vector(s) back", the_expected, the_vectors.len());
+        }
+        if let Some(the_bad) = the_vectors.iter().find(|v| !v.is_array()) {
+            anyhow::bail!("💀 The embedding response has something that isn't a vector: {}", the_bad);
+        }
+        Ok(the_vectors)
+    }
+}
+
+impl Transform for Embed {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        self.transform_batch(vec![doc]).pop().unwrap_or(Ok(None))
+    }
+
+    /// 📦 Every text in the page, `batch_size` per request, `max_concurrency` at a time.
+    fn transform_batch(&self, mut docs: Vec<Value>) -> Vec<Result<Option<Value>>> {
+        // -- 🧾 (doc, field pair, text) for every field that has something to embed
+        let mut the_texts: Vec<(usize, usize, String)> = Vec::new();
+        for (the_doc_index, the_doc) in docs.iter().enumerate() {
+            for (the_field_index, (the_text_field, _)) in self.fields.iter().enumerate() {
+                match the_text_field.get(the_doc) {
+                    Some(Value::String(the_text)) if !the_text.trim().is_empty() => {
+                        the_texts.push((the_doc_index, the_field_index, the_text.clone()))
+                    }
+                    _ => {
+                        self.skipped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+        let the_batches: Vec<&[(usize, usize, String)]> = the_texts.chunks(self.batch_size).collect();
+        let the_results: Vec<Result<Vec<Value>>> = self.runtime.block_on(
+            futures::stream::iter(&the_batches)
+                .map(|the_batch| self.embed(the_batch.iter().map(|(_, _, the_text)| the_text.as_str()).collect()))
+                .buffered(self.max_concurrency)
+                .collect(),
+        );
+
+        let mut the_failures: Vec<Option<String>> = vec![None; docs.len()];
+        for (the_batch, the_result) in the_batches.iter().zip(the_results) {
+            match the_result {
+                Ok(the_vectors) => {
+                    for ((the_doc_index, the_field_index, _), the_vector) in the_batch.iter().zip(the_vectors) {
+                        let (_, the_vector_field) = &self.fields[*the_field_index];
+                        if let Err(the_error) = the_vector_field.set(&mut docs[*the_doc_index], the_vector, None) {
+                            the_failures[*the_doc_index] = Some(format!("{:#}", the_error));
+                        }
+                    }
+                }
+                Err(the_error) => {
+                    let the_message = format!("{:#}", the_error);
+                    for (the_doc_index, _, _) in the_batch.iter() {
+                        the_failures[*the_doc_index] = Some(the_message.clone());
+                    }
+                }
+            }
+        }
+        docs.into_iter()
+            .zip(the_failures)
+            .map(|(the_doc, the_failure)| match the_failure {
+                Some(the_message) => Err(anyhow::anyhow!(the_message)),
+                None => Ok(Some(the_doc)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::{TransformChain, TransformConfig};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn the_chain(the_toml: &str) -> Result<TransformChain> {
+        let the_config: TransformConfig = toml::from_str(the_toml)?;
+        TransformChain::from_configs(&[the_config])
+    }
+
+    /// 🧵 Run a page through the chain the way a joiner does — off the runtime's threads.
+    async fn on_a_joiner(the_chain: TransformChain, the_docs: Vec<&'static str>) -> Vec<Result<Option<String>>> {
+        tokio::task::spawn_blocking(move || {
+            the_chain.apply_all(&the_docs).into_iter().map(|r| r.map(|d| d.map(|d| d.into_owned()))).collect()
+        })
+        .await
+        .expect("💀 the joiner panicked")
+    }
+
+    /// 🧪 Three texts in batches of two: two requests, vectors back on the right docs even when
+    /// the server answers out of order, and a doc with nothing to embed passes through.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn the_one_where_the_words_become_numbers() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(header("authorization", "Bearer sk-test"))
+            .and(body_partial_json(json!({"model": "mini", "input": ["red sock", "blue sock"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [
+                {"index": 1, "embedding": [0.0, 1.0]},
+                {"index": 0, "embedding": [1.0, 0.0]}
+            ]})))
+            .expect(1)
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(body_partial_json(json!({"input": ["green sock"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [{"index": 0, "embedding": [0.5, 0.5]}]})))
+            .expect(1)
+            .mount(&the_server)
+            .await;
+
+        let the_chain = the_chain(&format!(
+            r#"Embed = {{ url = "{}/v1/embeddings", model = "mini", api_key = "sk-test", batch_size = 2, fields = {{ title = "vec.title" }} }}"#,
+            the_server.uri()
+        ))?;
+        let the_outcomes = on_a_joiner(
+            the_chain.clone(),
+            vec![r#"{"title":"red sock"}"#, r#"{"title":"blue sock"}"#, r#"{"sku":7}"#, r#"{"title":"green sock"}"#],
+        )
+        .await;
+        let the_docs: Vec<Option<String>> = the_outcomes.into_iter().collect::<Result<_>>()?;
+        assert_eq!(
+            the_docs,
+            vec![
+                Some(r#"{"title":"red sock","vec":{"title":[1.0,0.0]}}"#.to_string()),
+                Some(r#"{"title":"blue sock","vec":{"title":[0.0,1.0]}}"#.to_string()),
+                Some(r#"{"sku":7}"#.to_string()),
+                Some(r#"{"title":"green sock","vec":{"title":[0.5,0.5]}}"#.to_string()),
+            ]
+        );
+        let the_warnings = crate::warnings::RunWarnings::default();
+        the_chain.record_warnings(&the_warnings);
+        assert!(the_warnings.snapshot()[0].message.contains("no text in 1 field(s)"));
+        Ok(())
+    }
+
+    /// 🧪 A custom (TEI-shaped) endpoint that has a 503 first is retried; a 400 fails only the
+    /// docs in that request; and there's no Embed without a runtime to send on.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn the_one_where_the_model_server_has_a_bad_day() -> Result<()> {
+        let the_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embed"))
+            .and(body_partial_json(json!({"inputs": ["ok"]})))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/embed"))
+            .and(body_partial_json(json!({"inputs": ["ok"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([[0.25, 0.75]])))
+            .mount(&the_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/embed"))
+            .and(body_partial_json(json!({"inputs": ["way too long"]})))
+            .respond_with(ResponseTemplate::new(413).set_body_string("input exceeds 512 tokens"))
+            .mount(&the_server)
+            .await;
+
+        let the_chain = the_chain(&format!(
+            r#"Embed = {{ url = "{}/embed", format = {{ custom = {{ input_field = "inputs" }} }}, batch_size = 1, fields = {{ body = "body_vec" }} }}"#,
+            the_server.uri()
+        ))?;
+        let mut the_outcomes = on_a_joiner(the_chain, vec![r#"{"body":"ok"}"#, r#"{"body":"way too long"}"#]).await;
+        let the_failure = format!("{:#}", the_outcomes.pop().expect("two outcomes").expect_err("💀 413 fails the doc"));
+        assert!(the_failure.contains("input exceeds 512 tokens"), "{the_failure}");
+        assert_eq!(the_outcomes.pop().expect("two outcomes")?.as_deref(), Some(r#"{"body":"ok","body_vec":[0.25,0.75]}"#));
+
+        let the_config: TransformConfig = toml::from_str(r#"Embed = { url = "http://x", fields = { a = "b" } }"#)?;
+        let TransformConfig::Embed(the_config) = the_config else { unreachable!() };
+        let the_outside = std::thread::spawn(move || Embed::from_config(&the_config).is_err()).join().expect("💀 thread");
+        assert!(the_outside, "🧵 no runtime, no Embed");
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🏷️ FieldMap — rename fields from config, no Rust required.
+//!
+//! 🎬 *[the source calls it `ObjectID`. the target mapping calls it `id`.]*
+//! *[someone opens a PR with a brand new transform. it is forty lines. it renames one field.]*
+//! *[the reviewer sighs. "what if... TOML?"]*
+//!
+//! ```toml
+//! [[transforms]]
+//! FieldMap = { rename = { ObjectID = "id", ScheduleState = "state", "Project.Name" = "project" } }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Both sides are `FieldPath`s: `Project.Name` reads a nested field, `meta.id` writes one
+//! - Renames happen all at once: every source is taken first, then every target is written,
+//!   so `{ a = "b", b = "a" }` swaps instead of clobbering
+//! - A field renamed within the same object keeps its position; one moved to another object
+//!   is appended there. A missing source is skipped
+//! - A target that already exists is overwritten — the rename wins 🦆
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+use super::field_path::FieldPath;
+
+/// 🏷️ `[[transforms]] FieldMap = { rename = { from = "to" } }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct FieldMapConfig {
+    /// 🔀 Source field path → target field path
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+}
+
+/// 🏷️ The compiled rename list.
+#[derive(Debug, Clone)]
+pub struct FieldMap {
+    renames: Vec<(FieldPath, FieldPath)>,
+}
+
+impl FieldMap {
+    /// 🔨 Parse every path up front — a typo'd path should fail at startup, not per doc.
+    pub fn from_config(config: &FieldMapConfig) -> Result<Self> {
+        let the_renames = config
+            .rename
+            .iter()
+            .map(|(the_from, the_to)| {
+                Ok((FieldPath::parse(the_from)?, FieldPath::parse(the_to)?))
+            })
+            .collect::<Result<Vec<_>>>()
+            .context("💀 FieldMap has a bad `rename` entry")?;
+        Ok(Self { renames: the_renames })
+    }
+}
+
+impl Transform for FieldMap {
+    fn transform(&self, mut doc: Value) -> Result<Option<Value>> {
+        // -- ✂️ take everything first, so one rename's target can't eat another's source
+        let mut the_moved = Vec::with_capacity(self.renames.len());
+        for (the_from, the_to) in &self.renames {
+            if let Some((the_value, the_position)) = the_from.take(&mut doc) {
+                let the_seat = the_from.is_sibling_of(the_to).then_some(the_position);
+                the_moved.push((the_to, the_value, the_seat));
+            }
+        }
+        // -- 🪑 put back in reverse, so each recorded position is still the right seat
+        for (the_to, the_value, the_seat) in the_moved.into_iter().rev() {
+            the_to.set(&mut doc, the_value, the_seat)?;
+        }
+        Ok(Some(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_one_where_everyone_gets_a_new_name_tag() -> Result<()> {
+        let the_config: FieldMapConfig =
+            toml::from_str(r#"rename = { ObjectID = "id", ScheduleState = "state", "Project.Name" = "project", Missing = "nope" }"#)?;
+        let the_map = FieldMap::from_config(&the_config)?;
+        let the_doc = json!({"ObjectID": 42, "Name": "Login", "ScheduleState": "Accepted", "Project": {"Name": "Apollo"}});
+
+        let the_result = the_map.transform(the_doc)?.expect("renames never drop a doc");
+        assert_eq!(
+            serde_json::to_string(&the_result)?,
+            r#"{"id":42,"Name":"Login","state":"Accepted","Project":{},"project":"Apollo"}"#,
+            "🪑 same-object renames keep their seat, cross-object ones append; `nope` never appears"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_two_fields_swap_places_without_a_third_chair() -> Result<()> {
+        let the_map = FieldMap::from_config(&toml::from_str(r#"rename = { a = "b", b = "a" }"#)?)?;
+        let the_result = the_map.transform(json!({"a": 1, "b": 2}))?.expect("kept");
+        assert_eq!(the_result, json!({"b": 1, "a": 2}));
+        assert!(FieldMap::from_config(&toml::from_str(r#"rename = { "a." = "b" }"#)?).is_err());
+        Ok(())
+    }
+}
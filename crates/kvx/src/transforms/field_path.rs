@@ -0,0 +1,116 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔎 FieldPath — `Project.Name`, pre-split, for transforms that poke at nested fields.
+//!
+//! 🎬 *[a transform wants `Project.Name`. the document has `{"Project":{"Name":..}}`.]*
+//! *["just split on dots," says everyone. so we did. once. here.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Parsed once at config time by each transform, walked per document
+//! - `take` reports where the field sat in its parent, so `set` can put a renamed field
+//!   back in the same spot (serde_json's `preserve_order` is on, so key order is visible)
+//! - Keys that literally contain a `.` can't be addressed. Nobody has asked yet 🦆
+
+use std::fmt;
+
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+/// 🔎 A dotted path into a JSON document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath {
+    segments: Vec<String>,
+}
+
+impl FieldPath {
+    /// 🔨 `"a.b.c"` → three segments. Empty segments (`a..b`, `.a`) are config errors.
+    pub fn parse(path: &str) -> Result<Self> {
+        let the_segments: Vec<String> = path.split('.').map(str::to_string).collect();
+        if the_segments.iter().any(String::is_empty) {
+            anyhow::bail!("💀 '{}' isn't a field path — use `name` or `parent.child`", path);
+        }
+        Ok(Self { segments: the_segments })
+    }
+
+    /// 👪 Do both paths end in the same object? (`a.x` and `a.y` do; `x` and `a.x` don't)
+    pub fn is_sibling_of(&self, other: &FieldPath) -> bool {
+        self.segments.split_last().map(|(_, p)| p) == other.segments.split_last().map(|(_, p)| p)
+    }
+
+    /// 👀 The value at this path, if every step along the way is an object that has it.
+    pub fn get<'v>(&self, doc: &'v Value) -> Option<&'v Value> {
+        self.segments.iter().try_fold(doc, |the_level, the_key| the_level.as_object()?.get(the_key))
+    }
+
+    /// ✂️ Remove the value at this path. Returns it, plus its position among its siblings.
+    pub fn take(&self, doc: &mut Value) -> Option<(Value, usize)> {
+        let (the_leaf, the_parents) = self.segments.split_last()?;
+        let the_parent = the_parents
+            .iter()
+            .try_fold(doc, |the_level, the_key| the_level.as_object_mut()?.get_mut(the_key))?
+            .as_object_mut()?;
+        let the_position = the_parent.keys().position(|the_key| the_key == the_leaf)?;
+        the_parent.shift_remove(the_leaf).map(|the_value| (the_value, the_position))
+    }
+
+    /// ✍️ Write `value` at this path, creating missing parent objects on the way.
+    /// `position` puts a new key at that index among its siblings; `None` appends it.
+    pub fn set(&self, doc: &mut Value, value: Value, position: Option<usize>) -> Result<()> {
+        let Some((the_leaf, the_parents)) = self.segments.split_last() else {
+            return Ok(());
+        };
+        let mut the_level = doc;
+        for the_key in the_parents {
+            let Value::Object(the_map) = the_level else {
+                anyhow::bail!("💀 can't set `{}` — `{}` sits under something that isn't an object", self, the_key);
+            };
+            the_level = the_map.entry(the_key.clone()).or_insert_with(|| Value::Object(Map::new()));
+        }
+        let Value::Object(the_parent) = the_level else {
+            anyhow::bail!("💀 can't set `{}` — its parent isn't an object", self);
+        };
+        match position {
+            Some(the_index) => {
+                the_parent.shift_insert(the_index.min(the_parent.len()), the_leaf.clone(), value);
+            }
+            None => {
+                the_parent.insert(the_leaf.clone(), value);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.segments.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_one_where_the_field_moves_house_but_keeps_its_seat() -> Result<()> {
+        let mut the_doc = json!({"a": 1, "Project": {"Name": "Apollo", "Id": 7}, "z": 2});
+        let the_path = FieldPath::parse("Project.Name")?;
+        assert_eq!(the_path.get(&the_doc), Some(&json!("Apollo")));
+
+        let (the_value, the_position) = the_path.take(&mut the_doc).expect("the field is there");
+        assert_eq!((the_value.clone(), the_position), (json!("Apollo"), 0));
+        assert_eq!(the_path.take(&mut the_doc), None, "🕳️ gone is gone");
+
+        FieldPath::parse("Project.Title")?.set(&mut the_doc, the_value, Some(the_position))?;
+        assert_eq!(serde_json::to_string(&the_doc)?, r#"{"a":1,"Project":{"Title":"Apollo","Id":7},"z":2}"#);
+
+        FieldPath::parse("meta.source")?.set(&mut the_doc, json!("rally"), None)?;
+        assert_eq!(the_doc["meta"]["source"], "rally", "🏗️ missing parents get built");
+        assert!(FieldPath::parse("a.b")?.set(&mut the_doc, json!(1), None).is_err(), "💀 `a` is a number, not a room");
+        assert!(FieldPath::parse("a..b").is_err());
+        Ok(())
+    }
+}
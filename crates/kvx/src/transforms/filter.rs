@@ -0,0 +1,274 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🚪 Filter — ship only the documents that match, drop the rest.
+//!
+//! 🎬 *[the ask: "just the accepted stories." the source: 2.3 million artifacts.]*
+//! *[the target ends up with all of them. the defects. the tasks. the test cases from 2014.]*
+//! *[now a bouncer stands at the door with a list. `ScheduleState = "Accepted"`. you may pass.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Filter = { all = [{ field = "ScheduleState", equals = "Accepted" }, { field = "PlanEstimate", gte = 1, lt = 13 }] }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - A doc is kept when every `all` condition matches and, if `any` is set, at least one
+//!   `any` condition does. Everything else is dropped — on purpose, so it isn't a warning
+//! - A condition names one `field` (a `FieldPath`) and one or more operators, all of which
+//!   must hold: `equals`, `not_equals`, `one_of`, `exists`, `gt`/`gte`/`lt`/`lte`
+//! - Numbers compare as numbers (`1` equals `1.0`). Ranges compare numbers with numbers and
+//!   strings with strings, so ISO dates work after `DateNormalize`. Mixed types never match
+//! - An array field matches when any of its elements does, like an Elasticsearch `term`
+//!   query. `not_equals` matches when none does, and also when the field is missing
+//! - `exists = true` means present and not `null` 🦆
+
+use std::cmp::Ordering;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+use super::field_path::FieldPath;
+
+/// 🚪 `[[transforms]] Filter = { all = [..], any = [..] }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct FilterConfig {
+    /// ✅ Every one of these must match
+    #[serde(default)]
+    pub all: Vec<ConditionConfig>,
+    /// 🎲 At least one of these must match (ignored when empty)
+    #[serde(default)]
+    pub any: Vec<ConditionConfig>,
+}
+
+/// 🔎 One condition: `{ field = "ScheduleState", equals = "Accepted" }`. Every operator set must hold.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ConditionConfig {
+    /// 🔎 Field path to test
+    pub field: String,
+    /// 🟰 The field is this value
+    #[serde(default)]
+    pub equals: Option<Value>,
+    /// 🚫 The field isn't this value (or is missing)
+    #[serde(default)]
+    pub not_equals: Option<Value>,
+    /// 📋 The field is one of these values
+    #[serde(default)]
+    pub one_of: Option<Vec<Value>>,
+    /// 👻 `true`: present and not `null`. `false`: missing or `null`
+    #[serde(default)]
+    pub exists: Option<bool>,
+    /// 📈 Greater than
+    #[serde(default)]
+    pub gt: Option<Value>,
+    /// 📈 Greater than or equal
+    #[serde(default)]
+    pub gte: Option<Value>,
+    /// 📉 Less than
+    #[serde(default)]
+    pub lt: Option<Value>,
+    /// 📉 Less than or equal
+    #[serde(default)]
+    pub lte: Option<Value>,
+}
+
+/// 🔎 A compiled condition.
+#[derive(Debug, Clone)]
+struct Condition {
+    field: FieldPath,
+    equals: Option<Value>,
+    not_equals: Option<Value>,
+    one_of: Option<Vec<Value>>,
+    exists: Option<bool>,
+    // -- 📏 (bound, the orderings that pass): `gte` is (x, [Greater, Equal])
+    bounds: Vec<(Value, &'static [Ordering])>,
+}
+
+impl Condition {
+    fn from_config(config: &ConditionConfig) -> Result<Self> {
+        let the_field = FieldPath::parse(&config.field)?;
+        let the_bounds: Vec<(Value, &'static [Ordering])> = [
+            (&config.gt, &[Ordering::Greater][..]),
+            (&config.gte, &[Ordering::Greater, Ordering::Equal][..]),
+            (&config.lt, &[Ordering::Less][..]),
+            (&config.lte, &[Ordering::Less, Ordering::Equal][..]),
+        ]
+        .into_iter()
+        .filter_map(|(the_bound, the_passing)| the_bound.clone().map(|the_bound| (the_bound, the_passing)))
+        .collect();
+        if let Some((the_bound, _)) = the_bounds.iter().find(|(the_bound, _)| !the_bound.is_number() && !the_bound.is_string()) {
+            anyhow::bail!("💀 `{}` range bound {} must be a number or a string", config.field, the_bound);
+        }
+        if config.equals.is_none()
+            && config.not_equals.is_none()
+            && config.one_of.is_none()
+            && config.exists.is_none()
+            && the_bounds.is_empty()
+        {
+            anyhow::bail!(
+                "💀 `{}` has no test — add `equals`, `not_equals`, `one_of`, `exists`, `gt`, `gte`, `lt` or `lte`",
+                config.field
+            );
+        }
+        Ok(Self {
+            field: the_field,
+            equals: config.equals.clone(),
+            not_equals: config.not_equals.clone(),
+            one_of: config.one_of.clone(),
+            exists: config.exists,
+            bounds: the_bounds,
+        })
+    }
+
+    fn matches(&self, doc: &Value) -> bool {
+        let the_value = self.field.get(doc).filter(|the_value| !the_value.is_null());
+        if let Some(the_wanted) = self.exists
+            && the_wanted != the_value.is_some()
+        {
+            return false;
+        }
+        if let Some(the_unwanted) = &self.not_equals
+            && the_value.is_some_and(|the_value| any_element(the_value, |v| same_value(v, the_unwanted)))
+        {
+            return false;
+        }
+        let the_needs_a_value = self.equals.is_some() || self.one_of.is_some() || !self.bounds.is_empty();
+        let Some(the_value) = the_value else {
+            return !the_needs_a_value;
+        };
+        if let Some(the_wanted) = &self.equals
+            && !any_element(the_value, |v| same_value(v, the_wanted))
+        {
+            return false;
+        }
+        if let Some(the_list) = &self.one_of
+            && !any_element(the_value, |v| the_list.iter().any(|the_wanted| same_value(v, the_wanted)))
+        {
+            return false;
+        }
+        // -- 📏 one element has to satisfy every bound: `[0, 20]` is not "between 5 and 10"
+        self.bounds.is_empty()
+            || any_element(the_value, |v| {
+                self.bounds
+                    .iter()
+                    .all(|(the_bound, the_passing)| compare(v, the_bound).is_some_and(|o| the_passing.contains(&o)))
+            })
+    }
+}
+
+/// 🧺 An array matches when any element does; anything else is its own only element.
+fn any_element(value: &Value, test: impl Fn(&Value) -> bool) -> bool {
+    match value {
+        Value::Array(the_elements) => the_elements.iter().any(test),
+        the_scalar => test(the_scalar),
+    }
+}
+
+/// 🟰 JSON equality, except `1` and `1.0` are the same number.
+fn same_value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64() == r.as_f64(),
+        _ => left == right,
+    }
+}
+
+/// ⚖️ Numbers with numbers, strings with strings. Anything else is incomparable.
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64()?.partial_cmp(&r.as_f64()?),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+/// 🚪 The compiled condition lists.
+#[derive(Debug, Clone)]
+pub struct Filter {
+    all: Vec<Condition>,
+    any: Vec<Condition>,
+}
+
+impl Filter {
+    /// 🔨 Parse every condition — a condition that tests nothing is a config mistake.
+    pub fn from_config(config: &FilterConfig) -> Result<Self> {
+        if config.all.is_empty() && config.any.is_empty() {
+            anyhow::bail!("💀 Filter needs `all` or `any` conditions — with neither it would keep everything");
+        }
+        let compile = |the_list: &[ConditionConfig], the_name: &str| {
+            the_list
+                .iter()
+                .map(Condition::from_config)
+                .collect::<Result<Vec<_>>>()
+                .with_context(|| format!("💀 Filter has a bad `{}` condition", the_name))
+        };
+        Ok(Self { all: compile(&config.all, "all")?, any: compile(&config.any, "any")? })
+    }
+}
+
+impl Transform for Filter {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let the_verdict = self.all.iter().all(|c| c.matches(&doc))
+            && (self.any.is_empty() || self.any.iter().any(|c| c.matches(&doc)));
+        Ok(the_verdict.then_some(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn a_filter(toml_text: &str) -> Result<Filter> {
+        Filter::from_config(&toml::from_str(toml_text)?)
+    }
+
+    #[test]
+    fn the_one_where_only_the_accepted_stories_get_in() -> Result<()> {
+        let the_bouncer = a_filter(
+            r#"all = [{ field = "ScheduleState", equals = "Accepted" }, { field = "PlanEstimate", gte = 1, lt = 13 }]
+any = [{ field = "Tags", one_of = ["ui", "api"] }, { field = "Project.Name", not_equals = "Legacy" }]"#,
+        )?;
+        let the_story = json!({"ScheduleState": "Accepted", "PlanEstimate": 5.0, "Tags": ["db"], "Project": {"Name": "Apollo"}});
+        assert_eq!(the_bouncer.transform(the_story.clone())?, Some(the_story));
+
+        let the_rejected = [
+            json!({"ScheduleState": "Defined", "PlanEstimate": 5}),
+            json!({"ScheduleState": "Accepted", "PlanEstimate": 13}),
+            json!({"ScheduleState": "Accepted"}),
+            json!({"ScheduleState": "Accepted", "PlanEstimate": "5"}),
+            json!({"ScheduleState": "Accepted", "PlanEstimate": 1, "Tags": ["db"], "Project": {"Name": "Legacy"}}),
+        ];
+        for the_doc in the_rejected {
+            assert!(the_bouncer.transform(the_doc.clone())?.is_none(), "🚪 should be turned away: {}", the_doc);
+        }
+        assert!(
+            the_bouncer.transform(json!({"ScheduleState": "Accepted", "PlanEstimate": 1, "Tags": ["ui"], "Project": {"Name": "Legacy"}}))?.is_some(),
+            "🧺 one matching tag is enough"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_nothing_and_null_look_alike() -> Result<()> {
+        let the_present = a_filter(r#"all = [{ field = "Owner", exists = true }]"#)?;
+        let the_absent = a_filter(r#"all = [{ field = "Owner", exists = false }]"#)?;
+        assert!(the_present.transform(json!({"Owner": "ada"}))?.is_some());
+        assert!(the_present.transform(json!({"Owner": null}))?.is_none());
+        assert!(the_absent.transform(json!({"Owner": null}))?.is_some());
+        assert!(the_absent.transform(json!({}))?.is_some());
+
+        let the_dated = a_filter(r#"all = [{ field = "Created", gte = "2021-01-01", lt = "2022-01-01" }]"#)?;
+        assert!(the_dated.transform(json!({"Created": "2021-03-14T09:26:00Z"}))?.is_some(), "📅 ISO dates sort as strings");
+        assert!(the_dated.transform(json!({"Created": "2020-12-31T23:59:59Z"}))?.is_none());
+        assert!(a_filter(r#"all = [{ field = "n", lt = 10, gt = 5 }]"#)?.transform(json!({"n": [0, 20]}))?.is_none());
+        assert!(a_filter(r#"all = [{ field = "n", equals = 7 }]"#)?.transform(json!({"n": 7.0}))?.is_some());
+
+        assert!(a_filter("").is_err(), "💀 no conditions");
+        assert!(a_filter(r#"all = [{ field = "n" }]"#).is_err(), "💀 a condition with no test");
+        assert!(a_filter(r#"all = [{ field = "n", gt = true }]"#).is_err(), "💀 booleans don't have an order");
+        Ok(())
+    }
+}
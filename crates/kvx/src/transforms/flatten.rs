@@ -0,0 +1,146 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🥞 Flatten — nested objects become one level of joined keys.
+//!
+//! 🎬 *[the CSV export asks for `Project`. it gets `[object Object]`.]*
+//! *[the flat key/value store asks for `Project`. it gets a 400.]*
+//! *[`project_name` walks in. everyone relaxes.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Flatten = { separator = "_", lowercase_keys = true }   # Project.Name → project_name
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Object keys are joined with `separator` (default `.`). Arrays are values, not objects —
+//!   they're kept whole, objects inside them included
+//! - `max_depth` limits how many levels are merged: with `1`, `{"a":{"b":{"c":1}}}` becomes
+//!   `{"a_b":{"c":1}}`. Unset means all the way down
+//! - An empty object stays as `{}` under its joined key — flattening shouldn't lose fields
+//! - Key order follows the doc. If two paths flatten to the same key, the later one wins 🦆
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::Transform;
+
+fn default_separator() -> String {
+    ".".to_string()
+}
+
+/// 🥞 `[[transforms]] Flatten = { separator = "_", max_depth = 2, lowercase_keys = true }`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct FlattenConfig {
+    /// 🔗 Joins parent and child keys. Default `.`
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    /// 📏 Merge at most this many levels. Unset = no limit
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// 🔡 Lowercase every flattened key (`Project_Name` → `project_name`)
+    #[serde(default)]
+    pub lowercase_keys: bool,
+}
+
+impl Default for FlattenConfig {
+    fn default() -> Self {
+        Self { separator: default_separator(), max_depth: None, lowercase_keys: false }
+    }
+}
+
+/// 🥞 The flattening rules.
+#[derive(Debug, Clone)]
+pub struct Flatten {
+    separator: String,
+    max_depth: usize,
+    lowercase_keys: bool,
+}
+
+impl Flatten {
+    /// 🔨 Validate the rules — an empty separator would glue `ab` + `c` and `a` + `bc` into one key.
+    pub fn from_config(config: &FlattenConfig) -> Result<Self> {
+        if config.separator.is_empty() {
+            anyhow::bail!("💀 Flatten `separator` can't be empty — `a.bc` and `ab.c` would both become `abc`");
+        }
+        if config.max_depth == Some(0) {
+            anyhow::bail!("💀 Flatten `max_depth = 0` would flatten nothing — drop the transform instead");
+        }
+        Ok(Self {
+            separator: config.separator.clone(),
+            max_depth: config.max_depth.unwrap_or(usize::MAX),
+            lowercase_keys: config.lowercase_keys,
+        })
+    }
+
+    fn flatten_into(&self, the_flat: &mut Map<String, Value>, the_prefix: &str, the_object: Map<String, Value>, the_depth: usize) {
+        for (the_key, the_value) in the_object {
+            let the_key = if the_prefix.is_empty() { the_key } else { format!("{}{}{}", the_prefix, self.separator, the_key) };
+            match the_value {
+                Value::Object(the_inner) if the_depth < self.max_depth && !the_inner.is_empty() => {
+                    self.flatten_into(the_flat, &the_key, the_inner, the_depth + 1);
+                }
+                the_leaf => {
+                    let the_key = if self.lowercase_keys { the_key.to_lowercase() } else { the_key };
+                    // -- 🔁 shift_remove first so a repeated key moves to where it was last seen
+                    the_flat.shift_remove(&the_key);
+                    the_flat.insert(the_key, the_leaf);
+                }
+            }
+        }
+    }
+}
+
+impl Transform for Flatten {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let Value::Object(the_object) = doc else {
+            return Ok(Some(doc));
+        };
+        let mut the_flat = Map::with_capacity(the_object.len());
+        self.flatten_into(&mut the_flat, "", the_object, 0);
+        Ok(Some(Value::Object(the_flat)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn the_doc() -> Value {
+        json!({"ObjectID": 1, "Project": {"Name": "Apollo", "Owner": {"Id": 3}}, "Tags": [{"Name": "x"}], "Empty": {}})
+    }
+
+    #[test]
+    fn the_one_where_the_project_gets_pressed_flat() -> Result<()> {
+        let the_press = Flatten::from_config(&toml::from_str(r#"separator = "_"
+lowercase_keys = true"#)?)?;
+        assert_eq!(
+            serde_json::to_string(&the_press.transform(the_doc())?)?,
+            r#"{"objectid":1,"project_name":"Apollo","project_owner_id":3,"tags":[{"Name":"x"}],"empty":{}}"#,
+            "🥞 arrays stay whole, empty objects stay put"
+        );
+
+        let the_shallow = Flatten::from_config(&FlattenConfig { max_depth: Some(1), ..Default::default() })?;
+        assert_eq!(
+            the_shallow.transform(the_doc())?,
+            Some(json!({"ObjectID": 1, "Project.Name": "Apollo", "Project.Owner": {"Id": 3}, "Tags": [{"Name": "x"}], "Empty": {}}))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_two_paths_want_the_same_key() -> Result<()> {
+        let the_press = Flatten::from_config(&FlattenConfig::default())?;
+        assert_eq!(
+            serde_json::to_string(&the_press.transform(json!({"a.b": 1, "a": {"b": 2}, "c": 3}))?)?,
+            r#"{"a.b":2,"c":3}"#,
+            "🔁 the later path wins, and sits where it was written"
+        );
+        assert!(Flatten::from_config(&FlattenConfig { separator: String::new(), ..Default::default() }).is_err());
+        assert!(Flatten::from_config(&FlattenConfig { max_depth: Some(0), ..Default::default() }).is_err());
+        Ok(())
+    }
+}
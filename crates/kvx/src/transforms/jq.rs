@@ -0,0 +1,130 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔍 Jq — a jq expression per document, for reshaping that config tables can't express.
+//!
+//! 🎬 *["can kravex compute `full_name` from `first` and `last`?"]*
+//! *["not without a new Rust transform, a PR, a review, a release—"]*
+//! *["jq can."]*
+//! *[silence. then, grudgingly: "jq can."]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Jq = { expression = '{id: .ObjectID, project: .Project.Name, full_name: "\(.First) \(.Last)"}' }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Powered by `jaq` (jq in Rust): jq's standard library, compiled once at startup,
+//!   `Send + Sync`, so one compiled filter is shared by every joiner
+//! - One doc in: no output drops the doc (so `select(.State == "Accepted")` filters),
+//!   one output replaces it, more than one is an error — a transform can't fan out
+//! - A runtime error (`.a.b` on a string, `error("nope")`) fails the run with jq's message
+//! - `$__loc__`, `input`, `inputs` and friends see nothing: each doc is its own universe 🦆
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use jaq_core::load::{Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, Filter, Native, RcIter};
+use jaq_json::Val;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+
+/// 🔍 `[[transforms]] Jq = { expression = ".." }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct JqConfig {
+    /// 📜 The jq program, run once per document with the document as `.`
+    pub expression: String,
+}
+
+/// 🔍 A compiled jq filter. Clones share it.
+#[derive(Clone)]
+pub struct Jq {
+    expression: String,
+    filter: Arc<Filter<Native<Val>>>,
+}
+
+impl std::fmt::Debug for Jq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // -- 🙈 the compiled filter is a lookup table of closures — the source text says more
+        f.debug_struct("Jq").field("expression", &self.expression).finish()
+    }
+}
+
+impl Jq {
+    /// 🔨 Parse and compile now, so a syntax error stops the run before the first page.
+    pub fn from_config(config: &JqConfig) -> Result<Self> {
+        let the_loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+        let the_arena = Arena::default();
+        let the_modules = the_loader
+            .load(&the_arena, File { code: config.expression.as_str(), path: () })
+            .map_err(|the_errors| {
+                let the_reasons: Vec<String> = the_errors.into_iter().map(|(_, e)| format!("{:?}", e)).collect();
+                anyhow::anyhow!("💀 jq expression `{}` doesn't parse: {}", config.expression, the_reasons.join("; "))
+            })?;
+        let the_filter = Compiler::default()
+            .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+            .compile(the_modules)
+            .map_err(|the_errors| {
+                let the_reasons: Vec<String> = the_errors.into_iter().map(|(_, e)| format!("{:?}", e)).collect();
+                anyhow::anyhow!("💀 jq expression `{}` doesn't compile: {}", config.expression, the_reasons.join("; "))
+            })?;
+        Ok(Self { expression: config.expression.clone(), filter: Arc::new(the_filter) })
+    }
+}
+
+impl Transform for Jq {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let the_inputs = RcIter::new(core::iter::empty());
+        let mut the_outputs = self.filter.run((Ctx::new([], &the_inputs), Val::from(doc)));
+        let Some(the_first) = the_outputs.next() else {
+            return Ok(None);
+        };
+        let the_first = the_first.map_err(|e| anyhow::anyhow!("💀 jq expression `{}` failed: {}", self.expression, e))?;
+        if the_outputs.next().is_some() {
+            anyhow::bail!(
+                "💀 jq expression `{}` produced more than one document — one in, at most one out. Wrap it in `[...]` to collect them.",
+                self.expression
+            );
+        }
+        Ok(Some(Value::from(the_first)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn a_jq(expression: &str) -> Result<Jq> {
+        Jq::from_config(&JqConfig { expression: expression.to_string() })
+    }
+
+    #[test]
+    fn the_one_where_the_doc_gets_a_makeover_without_a_release() -> Result<()> {
+        let the_jq = a_jq(r#"{id: .ObjectID, project: .Project.Name, full_name: "\(.First) \(.Last)", tags: (.Tags | map(ascii_downcase))}"#)?;
+        let the_doc = json!({"ObjectID": 7, "First": "Ada", "Last": "L", "Project": {"Name": "Apollo"}, "Tags": ["A", "B"]});
+        let the_result = the_jq.transform(the_doc)?.expect("one in, one out");
+        assert_eq!(
+            serde_json::to_string(&the_result)?,
+            r#"{"id":7,"project":"Apollo","full_name":"Ada L","tags":["a","b"]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_select_says_no_and_many_says_too_many() -> Result<()> {
+        let the_bouncer = a_jq(r#"select(.State == "Accepted")"#)?;
+        assert!(the_bouncer.transform(json!({"State": "Defined"}))?.is_none(), "🚪 no output drops the doc");
+        assert!(the_bouncer.transform(json!({"State": "Accepted"}))?.is_some());
+
+        assert!(a_jq(".tags[]")?.transform(json!({"tags": [1, 2]})).is_err(), "💀 a transform can't fan out");
+        assert!(a_jq(r#"error("nope")"#)?.transform(json!({})).is_err());
+        assert!(a_jq("{id: .ObjectID").is_err(), "💀 syntax errors are startup errors");
+        assert!(a_jq("no_such_function(1)").is_err());
+        Ok(())
+    }
+}
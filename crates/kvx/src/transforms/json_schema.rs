@@ -0,0 +1,129 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📐 JsonSchema — validate every doc against a JSON Schema, set the misfits aside.
+//!
+//! 🎬 *[the mapping says `age` is an integer. 40,000 docs agree. one says `"age": "forty-ish"`.]*
+//! *[Elasticsearch finds out at bulk time and refuses it in a 200. the run shrugs.]*
+//! *[now the schema finds out first, and the doc goes to a file with its excuse attached.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! JsonSchema = { schema_file = "schemas/story.json", rejects_file = "rejects.ndjson" }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Compiled once at startup (any draft the `jsonschema` crate knows; `$ref`s to local files
+//!   resolve, remote ones don't — no network calls from a joiner thread)
+//! - A valid doc passes through untouched. An invalid one is dropped from the run, counted,
+//!   logged, and appended to `rejects_file` with every violation (`/age: "x" is not of type
+//!   "integer"`) — see `Rejects`
+//! - The tally lands in the end-of-run warnings via `TransformChain::record_warnings`
+//! - Put it after the reshaping transforms to validate the target shape, before them to
+//!   validate the source shape 🦆
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use jsonschema::Validator;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+use super::rejects::Rejects;
+
+/// 📐 `[[transforms]] JsonSchema = { schema_file = "schema.json", rejects_file = "rejects.ndjson" }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct JsonSchemaConfig {
+    /// 📂 The JSON Schema every doc must satisfy
+    pub schema_file: PathBuf,
+    /// 🧾 Where invalid docs go (NDJSON, appended). Without it they are only counted and logged
+    #[serde(default)]
+    pub rejects_file: Option<PathBuf>,
+}
+
+/// 📐 A compiled schema plus the shared reject tally. Clones share both.
+#[derive(Debug, Clone)]
+pub struct JsonSchema {
+    validator: Arc<Validator>,
+    rejects: Rejects,
+}
+
+impl JsonSchema {
+    /// 🔨 Read, parse and compile the schema, and open the rejects file — all before the first page.
+    pub fn from_config(config: &JsonSchemaConfig) -> Result<Self> {
+        let the_text = std::fs::read_to_string(&config.schema_file)
+            .with_context(|| format!("💀 Could not read `schema_file` at '{}'", config.schema_file.display()))?;
+        let the_schema: Value = serde_json::from_str(&the_text)
+            .with_context(|| format!("💀 `schema_file` '{}' isn't JSON", config.schema_file.display()))?;
+        let the_validator = jsonschema::validator_for(&the_schema).map_err(|e| {
+            anyhow::anyhow!("💀 `schema_file` '{}' isn't a valid JSON Schema: {}", config.schema_file.display(), e)
+        })?;
+        Ok(Self { validator: Arc::new(the_validator), rejects: Rejects::open(config.rejects_file.as_deref())? })
+    }
+
+    /// 🧾 The shared tally of docs this schema turned away.
+    pub fn rejects(&self) -> &Rejects {
+        &self.rejects
+    }
+}
+
+impl Transform for JsonSchema {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        if self.validator.is_valid(&doc) {
+            return Ok(Some(doc));
+        }
+        let the_violations: Vec<String> = self
+            .validator
+            .iter_errors(&doc)
+            .map(|the_error| format!("{}: {}", the_error.instance_path(), the_error))
+            .collect();
+        self.rejects.reject(&the_violations.join("; "), &doc)?;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_one_where_forty_ish_is_not_an_integer() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_schema_file = the_dir.path().join("story.json");
+        let the_rejects_file = the_dir.path().join("rejects.ndjson");
+        std::fs::write(
+            &the_schema_file,
+            r#"{"type": "object", "required": ["id"], "properties": {"age": {"type": "integer"}}}"#,
+        )?;
+        let the_schema = JsonSchema::from_config(&JsonSchemaConfig {
+            schema_file: the_schema_file,
+            rejects_file: Some(the_rejects_file.clone()),
+        })?;
+
+        assert_eq!(the_schema.transform(json!({"id": 1, "age": 40}))?, Some(json!({"id": 1, "age": 40})));
+        assert!(the_schema.transform(json!({"age": "forty-ish"}))?.is_none(), "🙅 invalid docs don't ship");
+
+        assert_eq!(the_schema.rejects().count(), 1);
+        let the_record: Value = serde_json::from_str(std::fs::read_to_string(&the_rejects_file)?.trim_end())?;
+        assert_eq!(the_record["document"], json!({"age": "forty-ish"}));
+        let the_error = the_record["error"].as_str().unwrap_or_default();
+        assert!(the_error.contains("\"id\" is a required property"), "{}", the_error);
+        assert!(the_error.contains("/age: \"forty-ish\" is not of type \"integer\""), "{}", the_error);
+        assert!(the_schema.rejects().summary("JsonSchema").is_some_and(|s| s.contains("1 document(s)")));
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_schema_itself_is_the_problem() -> Result<()> {
+        let the_schema_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(the_schema_file.path(), r#"{"type": 12}"#)?;
+        let the_config = JsonSchemaConfig { schema_file: the_schema_file.path().to_path_buf(), rejects_file: None };
+        assert!(JsonSchema::from_config(&the_config).is_err());
+        assert!(JsonSchema::from_config(&JsonSchemaConfig { schema_file: "/nope/never.json".into(), rejects_file: None }).is_err());
+        Ok(())
+    }
+}
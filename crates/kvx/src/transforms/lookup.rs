@@ -0,0 +1,300 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📇 Lookup — join fields from a small lookup table onto each document, by key.
+//!
+//! 🎬 *[the story says `ProjectID: 4417`. the dashboard wants the team name.]*
+//! *[the team name lives in `teams.csv`, on a shared drive, maintained by Gary.]*
+//! *[Gary's spreadsheet is now part of the pipeline. Gary has been informed.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Lookup = { file = "teams.csv", key_column = "project_id", match_field = "ProjectID", target_field = "Team" }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - The table is read once at startup into a `HashMap` shared by every joiner (`Arc`).
+//!   "Small" means it fits in memory comfortably — thousands of rows, not millions
+//! - Formats by extension: `.csv` (header row; every cell a string), `.json` (an object of
+//!   key → row, or an array of rows), `.ndjson`/`.jsonl` (one row per line). Arrays and
+//!   lines need `key_column`; the key column is left out of the joined row
+//! - Keys compare the way `_id`s do: `4417` and `"4417"` are one key. Two rows with the
+//!   same key are a config error — which one was meant?
+//! - The row goes under `target_field`, or is merged into the top level of the doc when
+//!   that's unset (row fields overwrite doc fields). `fields` picks which columns to copy
+//! - Docs without `match_field` pass through. A key with no row follows `on_missing`:
+//!   `keep` (default, counted in the end-of-run warnings), `drop`, `fail` 🦆
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::Transform;
+use super::field_path::FieldPath;
+
+/// 🚦 What to do with a document whose key isn't in the table.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MissingLookupPolicy {
+    /// 🤷 Ship it unenriched; count it for the end-of-run warnings
+    #[default]
+    Keep,
+    /// 🗑️ Leave it out of the run
+    Drop,
+    /// 💀 Fail the run
+    Fail,
+}
+
+/// 📇 `[[transforms]] Lookup = { file = "teams.csv", key_column = "id", match_field = "ProjectID" }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct LookupConfig {
+    /// 📂 The lookup table: `.csv`, `.json`, `.ndjson` / `.jsonl`
+    pub file: PathBuf,
+    /// 🔑 The table column holding the key. Not needed for a JSON object of key → row
+    #[serde(default)]
+    pub key_column: Option<String>,
+    /// 🔎 Field path in the document holding the key to look up
+    pub match_field: String,
+    /// 📥 Put the row under this field path. Unset = merge its fields into the document
+    #[serde(default)]
+    pub target_field: Option<String>,
+    /// ✅ Copy only these columns (empty = all of them)
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// 🚦 `keep` (default), `drop` or `fail`
+    #[serde(default)]
+    pub on_missing: MissingLookupPolicy,
+}
+
+#[derive(Debug, Default)]
+struct Misses {
+    count: AtomicUsize,
+    first: OnceLock<String>,
+}
+
+/// 📇 The loaded table plus where to join it. Clones share the table and the miss tally.
+#[derive(Debug, Clone)]
+pub struct Lookup {
+    table: Arc<HashMap<String, Map<String, Value>>>,
+    match_field: FieldPath,
+    target_field: Option<FieldPath>,
+    on_missing: MissingLookupPolicy,
+    misses: Arc<Misses>,
+}
+
+/// 🔑 `4417` and `"4417"` are the same key — strings as-is, everything else as JSON text.
+fn key_of(value: &Value) -> String {
+    match value {
+        Value::String(the_key) => the_key.clone(),
+        the_other => the_other.to_string(),
+    }
+}
+
+impl Lookup {
+    /// 🔨 Read the whole table now — a missing file or a duplicate key should stop the run before it starts.
+    pub fn from_config(config: &LookupConfig) -> Result<Self> {
+        let the_match_field = FieldPath::parse(&config.match_field).context("💀 Lookup has a bad `match_field`")?;
+        let the_target_field = config
+            .target_field
+            .as_deref()
+            .map(FieldPath::parse)
+            .transpose()
+            .context("💀 Lookup has a bad `target_field`")?;
+        let the_rows = read_rows(&config.file, config.key_column.as_deref())
+            .with_context(|| format!("💀 Couldn't load lookup table '{}'", config.file.display()))?;
+
+        let mut the_table = HashMap::with_capacity(the_rows.len());
+        for (the_key, mut the_row) in the_rows {
+            if !config.fields.is_empty() {
+                the_row.retain(|the_column, _| config.fields.contains(the_column));
+            }
+            if the_table.insert(the_key.clone(), the_row).is_some() {
+                anyhow::bail!("💀 Lookup table '{}' has key '{}' more than once", config.file.display(), the_key);
+            }
+        }
+        Ok(Self {
+            table: Arc::new(the_table),
+            match_field: the_match_field,
+            target_field: the_target_field,
+            on_missing: config.on_missing,
+            misses: Arc::default(),
+        })
+    }
+
+    /// 🤷 How many keys had no row and were shipped unenriched (`on_missing = "keep"`).
+    pub fn misses(&self) -> Option<String> {
+        let the_count = self.misses.count.load(Ordering::Relaxed);
+        (the_count > 0).then(|| {
+            format!(
+                "Lookup found no row for {} document(s) and shipped them unenriched. First: {}",
+                the_count,
+                self.misses.first.get().map(String::as_str).unwrap_or_default()
+            )
+        })
+    }
+}
+
+/// 📂 Every row of the table, as (key, row without its key column).
+fn read_rows(path: &Path, key_column: Option<&str>) -> Result<Vec<(String, Map<String, Value>)>> {
+    let the_extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+    let the_keyed_rows = |the_rows: Vec<Value>| -> Result<Vec<(String, Map<String, Value>)>> {
+        let Some(the_column) = key_column else {
+            anyhow::bail!("💀 a list of rows needs `key_column` — which column is the key?");
+        };
+        the_rows
+            .into_iter()
+            .enumerate()
+            .map(|(the_index, the_row)| {
+                let Value::Object(mut the_row) = the_row else {
+                    anyhow::bail!("💀 row {} isn't an object", the_index + 1);
+                };
+                match the_row.shift_remove(the_column) {
+                    Some(the_key) if !the_key.is_null() => Ok((key_of(&the_key), the_row)),
+                    _ => anyhow::bail!("💀 row {} has no `{}`", the_index + 1, the_column),
+                }
+            })
+            .collect()
+    };
+
+    match the_extension.as_str() {
+        "csv" => {
+            let mut the_reader = csv::Reader::from_path(path)?;
+            let the_headers = the_reader.headers()?.clone();
+            let the_rows = the_reader
+                .records()
+                .map(|the_record| {
+                    let the_record = the_record?;
+                    Ok(Value::Object(
+                        the_headers.iter().zip(the_record.iter()).map(|(h, c)| (h.to_string(), Value::String(c.to_string()))).collect(),
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            the_keyed_rows(the_rows)
+        }
+        "ndjson" | "jsonl" => {
+            let the_rows = std::fs::read_to_string(path)?
+                .lines()
+                .filter(|the_line| !the_line.trim().is_empty())
+                .map(|the_line| Ok(serde_json::from_str(the_line)?))
+                .collect::<Result<Vec<Value>>>()?;
+            the_keyed_rows(the_rows)
+        }
+        "json" => match serde_json::from_str(&std::fs::read_to_string(path)?)? {
+            Value::Array(the_rows) => the_keyed_rows(the_rows),
+            // -- 🗝️ `{"4417": {"team": "Apollo"}}` — the keys are the keys, no `key_column` needed
+            Value::Object(the_map) => the_map
+                .into_iter()
+                .map(|(the_key, the_row)| match the_row {
+                    Value::Object(the_row) => Ok((the_key, the_row)),
+                    _ => anyhow::bail!("💀 the row for '{}' isn't an object", the_key),
+                })
+                .collect(),
+            _ => anyhow::bail!("💀 a JSON lookup table is an object of key → row, or an array of rows"),
+        },
+        the_other => anyhow::bail!("💀 unknown lookup table format '.{}' — use .csv, .json, .ndjson or .jsonl", the_other),
+    }
+}
+
+impl Transform for Lookup {
+    fn transform(&self, mut doc: Value) -> Result<Option<Value>> {
+        let Some(the_key) = self.match_field.get(&doc).filter(|v| !v.is_null()).map(key_of) else {
+            return Ok(Some(doc));
+        };
+        let Some(the_row) = self.table.get(&the_key) else {
+            return match self.on_missing {
+                MissingLookupPolicy::Keep => {
+                    self.misses.count.fetch_add(1, Ordering::Relaxed);
+                    let _ = self.misses.first.set(format!("`{}` = {}", self.match_field, the_key));
+                    Ok(Some(doc))
+                }
+                MissingLookupPolicy::Drop => Ok(None),
+                MissingLookupPolicy::Fail => {
+                    anyhow::bail!("💀 Lookup has no row for `{}` = {}", self.match_field, the_key)
+                }
+            };
+        };
+        match (&self.target_field, &mut doc) {
+            (Some(the_target), _) => the_target.set(&mut doc, Value::Object(the_row.clone()), None)?,
+            (None, Value::Object(the_doc)) => {
+                the_doc.extend(the_row.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+            // -- 🔎 `match_field` was found inside it, so the doc is an object — this arm never runs
+            (None, _) => {}
+        }
+        Ok(Some(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_one_where_garys_spreadsheet_joins_the_pipeline() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_csv = the_dir.path().join("teams.csv");
+        std::fs::write(&the_csv, "project_id,team,lead\n4417,Apollo,ada\n4418,Gemini,bob\n")?;
+
+        let the_nested = Lookup::from_config(&toml::from_str(&format!(
+            "file = '{}'\nkey_column = 'project_id'\nmatch_field = 'ProjectID'\ntarget_field = 'Team'",
+            the_csv.display()
+        ))?)?;
+        assert_eq!(
+            the_nested.transform(json!({"ProjectID": 4417, "Name": "story"}))?,
+            Some(json!({"ProjectID": 4417, "Name": "story", "Team": {"team": "Apollo", "lead": "ada"}})),
+            "📇 4417 finds \"4417\""
+        );
+        assert_eq!(the_nested.transform(json!({"Name": "no project"}))?, Some(json!({"Name": "no project"})));
+        assert_eq!(the_nested.transform(json!({"ProjectID": 1}))?, Some(json!({"ProjectID": 1})));
+        assert!(the_nested.misses().is_some_and(|s| s.contains("1 document(s)") && s.contains("`ProjectID` = 1")));
+
+        let the_json = the_dir.path().join("teams.json");
+        std::fs::write(&the_json, r#"{"4417": {"team": "Apollo", "size": 5}}"#)?;
+        let the_merged = Lookup::from_config(&LookupConfig {
+            file: the_json,
+            match_field: "Project.Id".to_string(),
+            fields: vec!["size".to_string()],
+            on_missing: MissingLookupPolicy::Drop,
+            ..Default::default()
+        })?;
+        assert_eq!(
+            the_merged.transform(json!({"Project": {"Id": "4417"}}))?,
+            Some(json!({"Project": {"Id": "4417"}, "size": 5}))
+        );
+        assert_eq!(the_merged.transform(json!({"Project": {"Id": "9"}}))?, None, "🗑️ on_missing = drop");
+        assert!(the_merged.misses().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_table_itself_is_wrong() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let a_table = |the_name: &str, the_text: &str, the_key_column: Option<&str>| -> Result<Lookup> {
+            let the_file = the_dir.path().join(the_name);
+            std::fs::write(&the_file, the_text)?;
+            Lookup::from_config(&LookupConfig {
+                file: the_file,
+                key_column: the_key_column.map(str::to_string),
+                match_field: "id".to_string(),
+                on_missing: MissingLookupPolicy::Fail,
+                ..Default::default()
+            })
+        };
+        let the_lines = a_table("rows.ndjson", "{\"id\":1,\"x\":\"a\"}\n\n{\"id\":2,\"x\":\"b\"}\n", Some("id"))?;
+        assert_eq!(the_lines.transform(json!({"id": "2"}))?, Some(json!({"id": "2", "x": "b"})));
+        assert!(the_lines.transform(json!({"id": 3})).is_err(), "💀 on_missing = fail");
+
+        assert!(a_table("dupes.csv", "id,x\n1,a\n1,b\n", Some("id")).is_err(), "💀 which 1?");
+        assert!(a_table("rows.json", r#"[{"id": 1}]"#, None).is_err(), "💀 a list needs key_column");
+        assert!(a_table("rows.jsonl", "{\"x\":1}\n", Some("id")).is_err(), "💀 row without its key");
+        assert!(a_table("rows.xlsx", "", Some("id")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,487 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔧 Transforms — per-document reshaping, configured in TOML, applied while casting.
+//!
+//! 🎬 COLD OPEN — INT. MIGRATION WAR ROOM — THE MAPPING DOESN'T MATCH
+//! *[the source says `ObjectID`. the target says `id`. the target is not negotiating.]*
+//! *["we'll fix it in post," someone says. there is no post. there is only the pipeline.]*
+//! *[a `[[transforms]]` table is added. the target stops complaining.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! FieldMap = { rename = { ObjectID = "id" } }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - **Transform** trait: `fn transform(&self, doc: Value) -> Result<Option<Value>>` — `None` drops the doc
+//! - **DocumentTransform** enum: dispatches to concrete transforms (same pattern as `PageToEntriesCaster`)
+//! - **TransformConfig** enum: one `[[transforms]]` entry, externally tagged like `SinkConfig`
+//! - **TransformRegistry**: embedders' own transforms by name, for `Custom` entries
+//! - **TransformChain**: the compiled `[[transforms]]` list, in order. Built once per run,
+//!   cloned into every joiner (`Arc` inside), handed to the casters by
+//!   `PageToEntriesCaster::with_transforms`
+//! - Casters call `chain.apply_all(raw_docs)` once per page: one parse per doc, every step,
+//!   one serialize. Each step sees the page's docs together (`transform_batch`), so `Embed`
+//!   can batch its requests; the rest go one doc at a time. An empty chain hands back the
+//!   raw docs untouched — no parse, no cost 🐄
+//! - Steps that refuse docs (`JsonSchema`, `Dedup`) count them in a shared `Rejects`;
+//!   `TransformChain::record_warnings` turns the tallies into end-of-run warnings
+//! - A doc that isn't JSON, or that a step errors on, fails the run — unless `[runtime]
+//!   skip_bad_docs` gave the chain its own `Rejects` (`runtime.rejects_file`), where it goes instead
+//! - The transformed doc is what the sink-side features see: tombstones, `@timestamp`
+//!   checks and index templates all read the reshaped doc
+//!
+//! ⚠️ The singularity will reshape documents by staring at them. We have serde_json.
+
+pub mod date_normalize;
+pub mod dedup;
+pub mod embed;
+pub mod field_map;
+pub mod field_path;
+pub mod filter;
+pub mod flatten;
+pub mod jq;
+pub mod json_schema;
+pub mod lookup;
+pub mod projection;
+pub mod registry;
+pub mod rejects;
+pub mod rhai_script;
+pub mod sample;
+pub mod template;
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::warnings::{RunWarnings, WarningKind};
+
+pub use date_normalize::{DateNormalize, DateNormalizeConfig, UnparseableDatePolicy};
+pub use dedup::{Dedup, DedupConfig};
+pub use embed::{Embed, EmbedConfig, EmbedFormat};
+pub use field_map::{FieldMap, FieldMapConfig};
+pub use field_path::FieldPath;
+pub use filter::{ConditionConfig, Filter, FilterConfig};
+pub use flatten::{Flatten, FlattenConfig};
+pub use jq::{Jq, JqConfig};
+pub use json_schema::{JsonSchema, JsonSchemaConfig};
+pub use lookup::{Lookup, LookupConfig, MissingLookupPolicy};
+pub use projection::{Projection, ProjectionConfig};
+pub use registry::{CustomTransform, CustomTransformConfig, TransformFactory, TransformRegistry};
+pub use rejects::Rejects;
+pub use rhai_script::{RhaiConfig, RhaiScript};
+pub use sample::{Sample, SampleConfig};
+pub use template::{Template, TemplateConfig};
+
+// ===== Trait =====
+
+/// 🔧 Reshape one document.
+pub trait Transform: std::fmt::Debug {
+    /// 🔄 Take the parsed doc, return the doc to ship — or `None` to drop it from the run.
+    fn transform(&self, doc: Value) -> Result<Option<Value>>;
+
+    /// 📦 A page's docs in one call — one outcome per doc, in order. Steps that call out
+    /// (`Embed`) batch here; the default goes one doc at a time.
+    fn transform_batch(&self, docs: Vec<Value>) -> Vec<Result<Option<Value>>> {
+        docs.into_iter().map(|the_doc| self.transform(the_doc)).collect()
+    }
+}
+
+// ===== Config =====
+
+/// 📜 One `[[transforms]]` entry. Applied in the order they appear in the config.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub enum TransformConfig {
+    /// 🏷️ Rename fields: `FieldMap = { rename = { ObjectID = "id" } }`
+    FieldMap(FieldMapConfig),
+    /// ✂️ Keep/drop fields: `Projection = { include_fields = [..], exclude_fields = [..] }`
+    Projection(ProjectionConfig),
+    /// 🔍 A jq expression per doc: `Jq = { expression = "{id: .ObjectID}" }`
+    Jq(JqConfig),
+    /// 📜 A Rhai script per doc: `Rhai = { script_file = "fix.rhai" }`
+    Rhai(RhaiConfig),
+    /// 🖼️ Render each doc from a Tera template: `Template = { template_file = "shape.json.tera" }`
+    Template(TemplateConfig),
+    /// 📐 Validate against a schema, set misfits aside: `JsonSchema = { schema_file = "s.json", rejects_file = "r.ndjson" }`
+    JsonSchema(JsonSchemaConfig),
+    /// 👯 Drop repeated ids within the run: `Dedup = { id_field = "ObjectID" }`
+    Dedup(DedupConfig),
+    /// 📅 Rewrite date fields to ISO-8601 UTC: `DateNormalize = { fields = [..], formats = ["%m/%d/%Y"] }`
+    DateNormalize(DateNormalizeConfig),
+    /// 🥞 Nested objects to joined keys: `Flatten = { separator = "_", max_depth = 2 }`
+    Flatten(FlattenConfig),
+    /// 🚪 Keep only matching docs: `Filter = { all = [{ field = "ScheduleState", equals = "Accepted" }] }`
+    Filter(FilterConfig),
+    /// 🎲 A repeatable subset: `Sample = { fraction = 0.05 }` or `{ every_nth = 20 }`
+    Sample(SampleConfig),
+    /// 📇 Join rows from a lookup table: `Lookup = { file = "teams.csv", key_column = "id", match_field = "ProjectID" }`
+    Lookup(LookupConfig),
+    /// 🧠 Vectors from an inference endpoint: `Embed = { url = "..", fields = { body = "body_vector" } }`
+    Embed(EmbedConfig),
+    /// 🧩 An embedder-registered transform: `Custom = { name = "redact_pii", settings = { .. } }`
+    Custom(CustomTransformConfig),
+}
+
+// ===== Enum Dispatcher =====
+
+/// 🔧 The polymorphic transform — same enum-dispatch deal as casters and manifolds.
+#[derive(Debug, Clone)]
+pub enum DocumentTransform {
+    // -- 🏷️ config-driven field renames
+    FieldMap(FieldMap),
+    // -- ✂️ include/exclude field lists
+    Projection(Projection),
+    // -- 🔍 user-supplied jq expression
+    Jq(Jq),
+    // -- 📜 user-supplied Rhai script
+    Rhai(RhaiScript),
+    // -- 🖼️ Tera template rendered per doc
+    Template(Template),
+    // -- 📐 JSON Schema gate, invalid docs to the rejects file
+    JsonSchema(JsonSchema),
+    // -- 👯 seen-id set shared across joiners
+    Dedup(Dedup),
+    // -- 📅 many date formats in, RFC 3339 UTC out
+    DateNormalize(DateNormalize),
+    // -- 🥞 nested → flat keys
+    Flatten(Flatten),
+    // -- 🚪 field conditions, non-matching docs dropped
+    Filter(Filter),
+    // -- 🎲 seeded-hash sampling, no shared state
+    Sample(Sample),
+    // -- 📇 in-memory lookup table joined by key
+    Lookup(Lookup),
+    // -- 🧠 texts out to an inference endpoint, vectors back, a page at a time
+    Embed(Embed),
+    // -- 🧩 whatever the embedder registered
+    Custom(CustomTransform),
+}
+
+impl Transform for DocumentTransform {
+    #[inline]
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        match self {
+            Self::FieldMap(t) => t.transform(doc),
+            Self::Projection(t) => t.transform(doc),
+            Self::Jq(t) => t.transform(doc),
+            Self::Rhai(t) => t.transform(doc),
+            Self::Template(t) => t.transform(doc),
+            Self::JsonSchema(t) => t.transform(doc),
+            Self::Dedup(t) => t.transform(doc),
+            Self::DateNormalize(t) => t.transform(doc),
+            Self::Flatten(t) => t.transform(doc),
+            Self::Filter(t) => t.transform(doc),
+            Self::Sample(t) => t.transform(doc),
+            Self::Lookup(t) => t.transform(doc),
+            Self::Embed(t) => t.transform(doc),
+            Self::Custom(t) => t.transform(doc),
+        }
+    }
+
+    fn transform_batch(&self, docs: Vec<Value>) -> Vec<Result<Option<Value>>> {
+        match self {
+            Self::Embed(t) => t.transform_batch(docs),
+            Self::Custom(t) => t.transform_batch(docs),
+            the_step => docs.into_iter().map(|the_doc| the_step.transform(the_doc)).collect(),
+        }
+    }
+}
+
+impl DocumentTransform {
+    /// 🔨 Compile one config entry — paths parsed, files loaded, mistakes reported now.
+    /// `Custom` entries are looked up in `registry`.
+    pub fn from_config(config: &TransformConfig, registry: &TransformRegistry) -> Result<Self> {
+        Ok(match config {
+            TransformConfig::FieldMap(cfg) => Self::FieldMap(FieldMap::from_config(cfg)?),
+            TransformConfig::Projection(cfg) => Self::Projection(Projection::from_config(cfg)?),
+            TransformConfig::Jq(cfg) => Self::Jq(Jq::from_config(cfg)?),
+            TransformConfig::Rhai(cfg) => Self::Rhai(RhaiScript::from_config(cfg)?),
+            TransformConfig::Template(cfg) => Self::Template(Template::from_config(cfg)?),
+            TransformConfig::JsonSchema(cfg) => Self::JsonSchema(JsonSchema::from_config(cfg)?),
+            TransformConfig::Dedup(cfg) => Self::Dedup(Dedup::from_config(cfg)?),
+            TransformConfig::DateNormalize(cfg) => Self::DateNormalize(DateNormalize::from_config(cfg)?),
+            TransformConfig::Flatten(cfg) => Self::Flatten(Flatten::from_config(cfg)?),
+            TransformConfig::Filter(cfg) => Self::Filter(Filter::from_config(cfg)?),
+            TransformConfig::Sample(cfg) => Self::Sample(Sample::from_config(cfg)?),
+            TransformConfig::Lookup(cfg) => Self::Lookup(Lookup::from_config(cfg)?),
+            TransformConfig::Embed(cfg) => Self::Embed(Embed::from_config(cfg)?),
+            TransformConfig::Custom(cfg) => Self::Custom(registry.build(cfg)?),
+        })
+    }
+
+    /// 📋 End-of-run notes from this step: duplicates it dropped, limits it hit. Empty for most.
+    pub fn end_of_run_notes(&self) -> Vec<String> {
+        match self {
+            // -- ⏭️ JsonSchema's tally is lost docs; `record_warnings` reports it with its count
+            Self::JsonSchema(_) => Vec::new(),
+            Self::Dedup(t) => t.rejects().summary("Dedup").into_iter().chain(t.saturation()).collect(),
+            Self::DateNormalize(t) => t.unparsed().into_iter().collect(),
+            Self::Lookup(t) => t.misses().into_iter().collect(),
+            Self::Embed(t) => t.skipped().into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+// ===== Chain =====
+
+/// ⛓️ Every configured transform, in order. Cheap to clone; clones share the steps.
+#[derive(Debug, Clone, Default)]
+pub struct TransformChain {
+    steps: Arc<Vec<DocumentTransform>>,
+    // 🩹 `[runtime] skip_bad_docs`: where docs that broke the chain go instead of failing the run
+    bad_docs: Option<Rejects>,
+    // 🕳️ every doc the chain didn't pass on — dropped on purpose, refused, or skipped as bad
+    dropped: Arc<AtomicU64>,
+}
+
+impl TransformChain {
+    /// 🔨 Compile the `[[transforms]]` list. Entry numbers in errors are 1-based, like humans count.
+    pub fn from_configs(configs: &[TransformConfig]) -> Result<Self> {
+        Self::from_configs_with_registry(configs, &TransformRegistry::default())
+    }
+
+    /// 🧩 Same as `from_configs`, with embedder-registered transforms available to `Custom` entries.
+    pub fn from_configs_with_registry(configs: &[TransformConfig], registry: &TransformRegistry) -> Result<Self> {
+        let the_steps = configs
+            .iter()
+            .enumerate()
+            .map(|(the_index, the_config)| {
+                DocumentTransform::from_config(the_config, registry)
+                    .with_context(|| format!("💀 [[transforms]] entry #{} didn't compile", the_index + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { steps: Arc::new(the_steps), bad_docs: None, dropped: Arc::default() })
+    }
+
+    /// 🩹 Skip docs that aren't JSON or that a step errors on: count them, log the first few,
+    /// append them to `bad_docs`' file — and keep going, instead of failing the run.
+    pub fn with_skip_bad_docs(mut self, bad_docs: Rejects) -> Self {
+        self.bad_docs = Some(bad_docs);
+        self
+    }
+
+    /// 🕳️ No transforms configured — casters skip the parse entirely.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// 🔄 Run one raw JSON doc through every step. `None` means a step dropped it — or, with
+    /// `skip_bad_docs`, that it broke the chain and went to the rejects file.
+    pub fn apply<'a>(&self, doc: &'a str) -> Result<Option<Cow<'a, str>>> {
+        self.apply_all(&[doc]).pop().unwrap_or(Ok(None))
+    }
+
+    /// 📦 `apply` for a page of raw docs: one outcome per doc, in order. Each step gets the
+    /// page's surviving docs together, so a step that calls out (`Embed`) can batch them.
+    /// Outcomes stay per doc, so a caster can say which line a failure came from.
+    pub fn apply_all<'a>(&self, docs: &[&'a str]) -> Vec<Result<Option<Cow<'a, str>>>> {
+        if self.steps.is_empty() {
+            return docs.iter().map(|the_doc| Ok(Some(Cow::Borrowed(*the_doc)))).collect();
+        }
+        let the_outcomes: Vec<_> = docs
+            .iter()
+            .zip(self.apply_steps(docs))
+            .map(|(the_doc, the_outcome)| match (the_outcome, &self.bad_docs) {
+                (Err(the_failure), Some(the_bad_docs)) => {
+                    // -- 🧾 the doc as it was read: JSON if it parses, the raw line as a string if it doesn't
+                    let the_original = serde_json::from_str(the_doc).unwrap_or_else(|_| Value::String(the_doc.to_string()));
+                    the_bad_docs.reject(&format!("{:#}", the_failure), &the_original)?;
+                    Ok(None)
+                }
+                (the_outcome, _) => the_outcome,
+            })
+            .collect();
+        let the_dropped = the_outcomes.iter().filter(|the_outcome| matches!(the_outcome, Ok(None))).count();
+        if the_dropped > 0 {
+            self.dropped.fetch_add(the_dropped as u64, Ordering::Relaxed);
+        }
+        the_outcomes
+    }
+
+    /// ⛓️ One parse per doc (`parse_doc`), every step over the page, one serialize per doc — any error is the caller's problem.
+    fn apply_steps<'a>(&self, docs: &[&'a str]) -> Vec<Result<Option<Cow<'a, str>>>> {
+        let mut the_outcomes: Vec<Result<Option<Value>>> = docs
+            .iter()
+            .map(|the_doc| {
+                parse_doc(the_doc).map(Some).with_context(|| {
+                    let the_preview: String = the_doc.chars().take(200).collect();
+                    format!("💀 Transforms need JSON documents, and this isn't one: {}", the_preview)
+                })
+            })
+            .collect();
+        for the_step in self.steps.iter() {
+            // -- 🧺 only docs still in the running go to the next step; the rest keep their outcome
+            let (the_slots, the_live): (Vec<usize>, Vec<Value>) = the_outcomes
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(the_slot, the_outcome)| match the_outcome {
+                    Ok(Some(the_doc)) => Some((the_slot, std::mem::take(the_doc))),
+                    _ => None,
+                })
+                .unzip();
+            if the_live.is_empty() {
+                break;
+            }
+            for (the_slot, the_next) in the_slots.into_iter().zip(the_step.transform_batch(the_live)) {
+                the_outcomes[the_slot] = the_next;
+            }
+        }
+        the_outcomes
+            .into_iter()
+            .map(|the_outcome| Ok(the_outcome?.map(|the_doc| serde_json::to_string(&the_doc)).transpose()?.map(Cow::Owned)))
+            .collect()
+    }
+
+    /// 🧯 Docs rejected as invalid so far — by a step, or skipped by `skip_bad_docs` — what
+    /// counts against `max_errors`. Docs dropped on purpose (`Filter`, `Sample`, `Dedup`) aren't failures.
+    pub fn failed_docs(&self) -> u64 {
+        self.steps
+            .iter()
+            .map(|the_step| match the_step {
+                DocumentTransform::JsonSchema(t) => t.rejects().count() as u64,
+                _ => 0,
+            })
+            .sum::<u64>()
+            + self.bad_docs.as_ref().map_or(0, |the_bad_docs| the_bad_docs.count() as u64)
+    }
+
+    /// 🕳️ Docs the chain didn't pass on so far, for any reason — `Filter`, `Sample`, `Dedup`,
+    /// `Jq`/`Rhai` with no output, `JsonSchema` refusals, and `skip_bad_docs` skips. What a
+    /// target's count comes up short by, on purpose.
+    pub fn dropped_docs(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// 🧾 Put each step's reject tallies and limits into the end-of-run warnings. Call once, after the run.
+    /// `JsonSchema` and `skip_bad_docs` rejects are lost docs (`record_rejected`); the rest —
+    /// Dedup drops and saturation, unparsed dates, lookup misses — are `DataQuality` notes.
+    pub fn record_warnings(&self, warnings: &RunWarnings) {
+        for (the_index, the_step) in self.steps.iter().enumerate() {
+            if let DocumentTransform::JsonSchema(t) = the_step
+                && let Some(the_summary) = t.rejects().summary("JsonSchema")
+            {
+                warnings.record_rejected(t.rejects().count() as u64, format!("[[transforms]] entry #{}: {}", the_index + 1, the_summary));
+            }
+            for the_note in the_step.end_of_run_notes() {
+                warnings.record(WarningKind::DataQuality, format!("[[transforms]] entry #{}: {}", the_index + 1, the_note));
+            }
+        }
+        if let Some(the_bad_docs) = &self.bad_docs
+            && let Some(the_summary) = the_bad_docs.summary("skip_bad_docs")
+        {
+            warnings.record_rejected(the_bad_docs.count() as u64, the_summary);
+        }
+    }
+}
+
+/// ⚡ Raw doc → `Value`, the parse every transform chain pays once per doc.
+///
+/// With the `simd-json` feature, simd-json parses an owned copy of the bytes (it tokenizes in
+/// place, and the page still needs the original). Anything it refuses goes through serde_json
+/// again, so an odd-but-valid doc still parses and a bad one gets serde_json's error message.
+#[cfg(feature = "simd-json")]
+fn parse_doc(doc: &str) -> serde_json::Result<Value> {
+    let mut the_bytes = doc.as_bytes().to_vec();
+    match simd_json::serde::from_slice(&mut the_bytes) {
+        Ok(the_value) => Ok(the_value),
+        Err(_) => serde_json::from_str(doc),
+    }
+}
+
+/// ⚡ Raw doc → `Value`, the parse every transform chain pays once per doc.
+#[cfg(not(feature = "simd-json"))]
+fn parse_doc(doc: &str) -> serde_json::Result<Value> {
+    serde_json::from_str(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_the_chain_reads_the_toml_in_order() -> Result<()> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            transforms: Vec<TransformConfig>,
+        }
+        let the_wrapper: Wrapper = toml::from_str(
+            r#"
+            [[transforms]]
+            FieldMap = { rename = { ObjectID = "id" } }
+
+            [[transforms]]
+            FieldMap = { rename = { id = "key" } }
+            "#,
+        )?;
+        let the_chain = TransformChain::from_configs(&the_wrapper.transforms)?;
+        assert_eq!(the_chain.apply(r#"{"ObjectID":7,"x":1}"#)?.as_deref(), Some(r#"{"key":7,"x":1}"#));
+
+        let the_nothing = TransformChain::default();
+        assert!(matches!(the_nothing.apply("not even json")?, Some(Cow::Borrowed("not even json"))), "🐄 no steps, no parse");
+        assert!(the_chain.apply("not even json").is_err());
+        Ok(())
+    }
+
+    /// 🧪 Whichever parser is built in, the doc comes out the same: key order, escapes, big ints.
+    #[test]
+    fn the_one_where_both_parsers_read_the_same_doc() -> Result<()> {
+        let the_doc = r#"{"z":1,"a":{"\u00e9t\u00e9":"caf\u00e9 \ud83e\udd86"},"big":18446744073709551615,"neg":-9007199254740993,"f":1.5e-7,"list":[true,null,"x"]}"#;
+        let the_parsed = parse_doc(the_doc)?;
+        assert_eq!(the_parsed, serde_json::from_str::<Value>(the_doc)?);
+        assert_eq!(the_parsed.as_object().map(|o| o.keys().cloned().collect::<Vec<_>>()), Some(vec!["z".into(), "a".into(), "big".into(), "neg".into(), "f".into(), "list".into()]));
+        assert!(parse_doc(r#"{"a":1,}"#).is_err(), "💀 a trailing comma is still not JSON");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_rejects_make_the_end_credits() -> Result<()> {
+        let the_schema_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(the_schema_file.path(), r#"{"required": ["id"]}"#)?;
+        let the_chain = TransformChain::from_configs(&[TransformConfig::JsonSchema(JsonSchemaConfig {
+            schema_file: the_schema_file.path().to_path_buf(),
+            rejects_file: None,
+        })])?;
+        assert!(the_chain.clone().apply(r#"{"name":"no id"}"#)?.is_none());
+        assert!(the_chain.apply(r#"{"id":1}"#)?.is_some());
+
+        let the_warnings = RunWarnings::default();
+        the_chain.record_warnings(&the_warnings);
+        let the_list = the_warnings.snapshot();
+        assert_eq!(the_list.len(), 1, "🧾 clones share one tally");
+        assert_eq!(the_list[0].kind, WarningKind::SkippedInput);
+        assert!(the_list[0].message.contains("entry #1: JsonSchema rejected 1 document(s)"), "{}", the_list[0].message);
+        assert_eq!(the_warnings.rejected_docs(), 1, "🩹 a schema reject is a lost doc");
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_bad_docs_are_set_aside_and_the_show_goes_on() -> Result<()> {
+        let the_rejects_file = tempfile::NamedTempFile::new()?;
+        let the_chain = TransformChain::from_configs(&[TransformConfig::Jq(JqConfig { expression: ".a.b".to_string() })])?
+            .with_skip_bad_docs(Rejects::open(Some(the_rejects_file.path()))?);
+        assert_eq!(the_chain.apply(r#"{"a":{"b":1}}"#)?.as_deref(), Some("1"));
+        assert!(the_chain.apply("not even json")?.is_none(), "🩹 skipped, not fatal");
+        assert!(the_chain.apply(r#"{"a":"no b in a string"}"#)?.is_none());
+        assert_eq!(the_chain.failed_docs(), 2);
+
+        let the_records: Vec<Value> = std::fs::read_to_string(the_rejects_file.path())?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(the_records.len(), 2);
+        assert_eq!(the_records[0]["document"], "not even json", "🧾 unparseable lines are kept as strings");
+        assert_eq!(the_records[1]["document"], serde_json::json!({"a":"no b in a string"}));
+
+        let the_warnings = RunWarnings::default();
+        the_chain.record_warnings(&the_warnings);
+        assert!(the_warnings.snapshot()[0].message.starts_with("skip_bad_docs rejected 2 document(s)"));
+        Ok(())
+    }
+}
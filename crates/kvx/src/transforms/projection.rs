@@ -0,0 +1,175 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! ✂️ Projection — keep only the fields you want, or drop the ones you don't.
+//!
+//! 🎬 *[the average doc is 2KB. the average doc with its `Description` is 380KB.]*
+//! *[nobody has searched the Description field since 2019. it migrates anyway. every time.]*
+//! *[until today.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Projection = { exclude_fields = ["Description", "embedding"] }
+//!
+//! [[transforms]]
+//! Projection = { include_fields = ["ObjectID", "Name", "Project.Name"] }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - `include_fields` first: a whitelist of `FieldPath`s. `Project` keeps the whole object,
+//!   `Project.Name` keeps `Project` with only `Name` inside. Kept fields keep their order
+//! - `exclude_fields` second: each path is removed if present — so an exclude can trim a
+//!   subtree the include kept (`include = ["Project"]`, `exclude = ["Project.Secret"]`)
+//! - An object left with nothing kept is dropped rather than shipped as `{}`
+//! - Both lists empty is a config mistake, not a no-op 🦆
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::Transform;
+use super::field_path::FieldPath;
+
+/// ✂️ `[[transforms]] Projection = { include_fields = [..], exclude_fields = [..] }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ProjectionConfig {
+    /// ✅ Keep only these field paths (empty = keep everything)
+    #[serde(default)]
+    pub include_fields: Vec<String>,
+    /// 🗑️ Then drop these field paths
+    #[serde(default)]
+    pub exclude_fields: Vec<String>,
+}
+
+/// 🌳 The include list as a tree: a leaf keeps the whole value, a branch keeps only its children.
+#[derive(Debug, Clone, Default)]
+struct KeepTree {
+    children: BTreeMap<String, KeepTree>,
+}
+
+impl KeepTree {
+    fn insert(&mut self, path: &str) {
+        let mut the_node = self;
+        for the_segment in path.split('.') {
+            the_node = the_node.children.entry(the_segment.to_string()).or_default();
+        }
+        // -- 🍃 `a` after `a.b` (or before it) means "all of a" — a leaf wins over a branch
+        the_node.children.clear();
+        the_node.children.insert(String::new(), KeepTree::default());
+    }
+
+    fn is_whole(&self) -> bool {
+        self.children.contains_key("")
+    }
+
+    /// ✂️ Keep what the tree says, in the doc's own key order.
+    fn keep(&self, object: Map<String, Value>) -> Map<String, Value> {
+        let mut the_kept = Map::new();
+        for (the_key, the_value) in object {
+            let Some(the_branch) = self.children.get(&the_key) else {
+                continue;
+            };
+            if the_branch.is_whole() {
+                the_kept.insert(the_key, the_value);
+            } else if let Value::Object(the_inner) = the_value {
+                let the_inner = the_branch.keep(the_inner);
+                if !the_inner.is_empty() {
+                    the_kept.insert(the_key, Value::Object(the_inner));
+                }
+            }
+        }
+        the_kept
+    }
+}
+
+/// ✂️ The compiled include/exclude lists.
+#[derive(Debug, Clone)]
+pub struct Projection {
+    include: Option<KeepTree>,
+    exclude: Vec<FieldPath>,
+}
+
+impl Projection {
+    /// 🔨 Validate every path now — a typo here would otherwise silently keep (or drop) nothing.
+    pub fn from_config(config: &ProjectionConfig) -> Result<Self> {
+        if config.include_fields.is_empty() && config.exclude_fields.is_empty() {
+            anyhow::bail!("💀 Projection needs `include_fields` or `exclude_fields` — with neither, it would do nothing");
+        }
+        let mut the_tree = KeepTree::default();
+        for the_path in &config.include_fields {
+            FieldPath::parse(the_path).context("💀 Projection has a bad `include_fields` entry")?;
+            the_tree.insert(the_path);
+        }
+        let the_exclude = config
+            .exclude_fields
+            .iter()
+            .map(|the_path| FieldPath::parse(the_path))
+            .collect::<Result<Vec<_>>>()
+            .context("💀 Projection has a bad `exclude_fields` entry")?;
+        Ok(Self { include: (!config.include_fields.is_empty()).then_some(the_tree), exclude: the_exclude })
+    }
+}
+
+impl Transform for Projection {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let mut the_doc = match (&self.include, doc) {
+            (Some(the_tree), Value::Object(the_object)) => Value::Object(the_tree.keep(the_object)),
+            (_, the_doc) => the_doc,
+        };
+        for the_path in &self.exclude {
+            the_path.take(&mut the_doc);
+        }
+        Ok(Some(the_doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn the_doc() -> Value {
+        json!({
+            "ObjectID": 1,
+            "Description": "<p>a novel</p>",
+            "Name": "Login",
+            "Project": {"Name": "Apollo", "Secret": "shh", "Owner": {"Id": 3}},
+            "embedding": [0.1, 0.2]
+        })
+    }
+
+    #[test]
+    fn the_one_where_the_blobs_stay_behind() -> Result<()> {
+        let the_diet = Projection::from_config(&toml::from_str(r#"exclude_fields = ["Description", "embedding", "Project.Secret", "Nope.Never"]"#)?)?;
+        let the_result = the_diet.transform(the_doc())?.expect("projections never drop docs");
+        assert_eq!(
+            serde_json::to_string(&the_result)?,
+            r#"{"ObjectID":1,"Name":"Login","Project":{"Name":"Apollo","Owner":{"Id":3}}}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_only_the_guest_list_gets_in() -> Result<()> {
+        let the_bouncer = Projection::from_config(&toml::from_str(
+            r#"include_fields = ["Name", "ObjectID", "Project.Name", "Project.Owner.Missing", "Ghost"]"#,
+        )?)?;
+        let the_result = the_bouncer.transform(the_doc())?.expect("kept");
+        assert_eq!(
+            serde_json::to_string(&the_result)?,
+            r#"{"ObjectID":1,"Name":"Login","Project":{"Name":"Apollo"}}"#,
+            "🪑 the doc's order, not the list's; an empty `Owner` isn't shipped"
+        );
+
+        let the_trim = Projection::from_config(&toml::from_str(r#"include_fields = ["Project.Name", "Project"]
+exclude_fields = ["Project.Secret"]"#)?)?;
+        let the_result = the_trim.transform(the_doc())?.expect("kept");
+        assert_eq!(the_result, json!({"Project": {"Name": "Apollo", "Owner": {"Id": 3}}}), "🍃 `Project` beats `Project.Name`");
+
+        assert!(Projection::from_config(&ProjectionConfig::default()).is_err());
+        Ok(())
+    }
+}
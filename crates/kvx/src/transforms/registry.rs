@@ -0,0 +1,160 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧩 TransformRegistry — embedders' own `Transform`s, referenced by name from `[[transforms]]`.
+//!
+//! 🎬 *[the PII rules live in a crate the security team owns. it has 40 regexes and a lawyer.]*
+//! *["can we add it as a built-in transform?" the lawyer says no. the lawyer says it firmly.]*
+//! *[so the embedder registers it under a name, and the TOML just says the name.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Custom = { name = "redact_pii", settings = { fields = ["email", "phone"] } }
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - `Pipeline::builder().register_transform("redact_pii", |settings| ..)` fills the registry,
+//!   which rides along on `AppConfig` like `warnings` (not a TOML key)
+//! - The factory gets the entry's `settings` as JSON (`null` when omitted), is called once
+//!   per `[[transforms]]` entry at startup, and its transform is shared by every joiner —
+//!   hence `Send + Sync`. Same one-instance-many-threads deal as the built-ins
+//! - An unregistered name fails at startup and lists what is registered. The `kvx` CLI
+//!   registers nothing, so `Custom` entries only make sense for embedders 🦆
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+
+/// 🏭 Builds a registered transform from its `settings`. Called once per `[[transforms]]` entry.
+pub type TransformFactory = Arc<dyn Fn(&Value) -> Result<Box<dyn Transform + Send + Sync>> + Send + Sync>;
+
+/// 🧩 `[[transforms]] Custom = { name = "redact_pii", settings = { .. } }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct CustomTransformConfig {
+    /// 🏷️ The name the embedder registered the transform under
+    pub name: String,
+    /// ⚙️ Handed to the factory as-is. Any shape the factory understands
+    #[serde(default)]
+    pub settings: Value,
+}
+
+/// 🧩 A registered transform, built. Clones share the one instance.
+#[derive(Debug, Clone)]
+pub struct CustomTransform {
+    name: String,
+    inner: Arc<dyn Transform + Send + Sync>,
+}
+
+impl CustomTransform {
+    /// 🏷️ The name it was registered under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Transform for CustomTransform {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        self.inner.transform(doc)
+    }
+
+    // -- 📦 an embedder's transform may batch too
+    fn transform_batch(&self, docs: Vec<Value>) -> Vec<Result<Option<Value>>> {
+        self.inner.transform_batch(docs)
+    }
+}
+
+/// 📒 Name → factory. Cheap to clone; clones share the factories.
+#[derive(Clone, Default)]
+pub struct TransformRegistry {
+    factories: BTreeMap<String, TransformFactory>,
+}
+
+impl TransformRegistry {
+    /// ✍️ Register `factory` under `name`. Registering a name again replaces the old factory.
+    pub fn register(&mut self, name: impl Into<String>, factory: TransformFactory) {
+        self.factories.insert(name.into(), factory);
+    }
+
+    /// 📋 Every registered name, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// 🔨 Build the transform a `Custom` entry names.
+    pub fn build(&self, config: &CustomTransformConfig) -> Result<CustomTransform> {
+        let Some(the_factory) = self.factories.get(&config.name) else {
+            let the_names: Vec<&str> = self.names().collect();
+            anyhow::bail!(
+                "💀 No transform registered as '{}'. Registered: {}. Custom transforms come from \
+                 `PipelineBuilder::register_transform` — the kvx CLI has none",
+                config.name,
+                if the_names.is_empty() { "none".to_string() } else { the_names.join(", ") }
+            );
+        };
+        let the_inner = the_factory(&config.settings)
+            .with_context(|| format!("💀 Custom transform '{}' factory failed", config.name))?;
+        Ok(CustomTransform { name: config.name.clone(), inner: Arc::from(the_inner) })
+    }
+}
+
+// -- 🔍 closures don't Debug, so we print the names and move on
+impl fmt::Debug for TransformRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 🧪 Blanks out whichever fields the settings name.
+    #[derive(Debug)]
+    struct Redact {
+        fields: Vec<String>,
+    }
+
+    impl Transform for Redact {
+        fn transform(&self, mut doc: Value) -> Result<Option<Value>> {
+            for the_field in &self.fields {
+                if let Some(the_value) = doc.get_mut(the_field) {
+                    *the_value = json!("█████");
+                }
+            }
+            Ok(Some(doc))
+        }
+    }
+
+    #[test]
+    fn the_one_where_the_lawyer_gets_a_name_in_the_config() -> Result<()> {
+        let mut the_registry = TransformRegistry::default();
+        the_registry.register(
+            "redact_pii",
+            Arc::new(|settings: &Value| {
+                let the_fields = serde_json::from_value(settings["fields"].clone())?;
+                Ok(Box::new(Redact { fields: the_fields }) as Box<dyn Transform + Send + Sync>)
+            }),
+        );
+        let the_config: CustomTransformConfig = toml::from_str("name = 'redact_pii'\nsettings = { fields = ['email'] }")?;
+        let the_redactor = the_registry.build(&the_config)?;
+        assert_eq!(the_redactor.name(), "redact_pii");
+        assert_eq!(
+            the_redactor.transform(json!({"email": "ada@example.com", "id": 1}))?,
+            Some(json!({"email": "█████", "id": 1}))
+        );
+
+        let the_stranger = the_registry.build(&CustomTransformConfig { name: "nope".to_string(), ..Default::default() });
+        assert!(the_stranger.is_err_and(|e| e.to_string().contains("Registered: redact_pii")));
+        assert!(the_registry.build(&CustomTransformConfig { name: "redact_pii".to_string(), ..Default::default() }).is_err(), "💀 null settings, factory says no");
+        assert_eq!(format!("{:?}", the_registry), r#"{"redact_pii"}"#);
+        Ok(())
+    }
+}
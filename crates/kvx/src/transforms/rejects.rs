@@ -0,0 +1,107 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧾 Rejects — documents a transform refused to ship, kept for a human to look at.
+//!
+//! 🧠 Knowledge graph:
+//! - A cheap, cloneable handle (Arc inside), shared by every joiner running the transform
+//! - `reject(reason, doc)` counts the doc, logs the first few, and — if a file was configured —
+//!   appends one NDJSON record: `{"error":"..","document":{..}}`. Same shape as the
+//!   Elasticsearch sink's `dead_letter_file`, minus the bulk `action`
+//! - `summary()` is what `TransformChain::record_warnings` puts in the end-of-run warnings
+//! - `std::sync::Mutex` around the file: rejects are rare, writes are one line, joiners are
+//!   OS threads. Nobody awaits while holding it 🔒
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use tracing::warn;
+
+/// 📢 Log this many rejects out loud; after that they only go to the file and the tally.
+const THE_LOUD_REJECTS: usize = 10;
+
+#[derive(Debug, Default)]
+struct RejectsInner {
+    path: Option<PathBuf>,
+    file: Option<Mutex<File>>,
+    count: AtomicUsize,
+    first_reason: Mutex<Option<String>>,
+}
+
+/// 🧾 Shared reject tally (and optional rejects file). Clones point at the same one.
+#[derive(Debug, Clone, Default)]
+pub struct Rejects {
+    inner: Arc<RejectsInner>,
+}
+
+impl Rejects {
+    /// 📂 Open (append, create) the rejects file now — a bad path should fail at startup.
+    pub fn open(path: Option<&Path>) -> Result<Self> {
+        let the_file = match path {
+            Some(the_path) => Some(Mutex::new(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(the_path)
+                    .with_context(|| format!("💀 Couldn't open rejects_file '{}'", the_path.display()))?,
+            )),
+            None => None,
+        };
+        Ok(Self {
+            inner: Arc::new(RejectsInner { path: path.map(Path::to_path_buf), file: the_file, ..Default::default() }),
+        })
+    }
+
+    /// 🙅 Count one refused doc and write it down.
+    pub fn reject(&self, reason: &str, document: &Value) -> Result<()> {
+        let the_count = self.inner.count.fetch_add(1, Ordering::Relaxed) + 1;
+        if the_count <= THE_LOUD_REJECTS {
+            warn!("🙅 Rejected a document: {}", reason);
+            if the_count == THE_LOUD_REJECTS {
+                warn!("🤫 That's {} rejects — the rest are only counted (and written to the rejects file, if any)", THE_LOUD_REJECTS);
+            }
+        }
+        self.inner
+            .first_reason
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get_or_insert_with(|| reason.to_string());
+
+        if let Some(the_file) = &self.inner.file {
+            let mut the_record = serde_json::to_string(&serde_json::json!({"error": reason, "document": document}))?;
+            the_record.push('\n');
+            // -- 🔒 one `write_all` per record under the lock, so lines from different joiners never interleave
+            the_file
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .write_all(the_record.as_bytes())
+                .context("💀 Couldn't append to the rejects file")?;
+        }
+        Ok(())
+    }
+
+    /// 🔢 How many docs were refused so far.
+    pub fn count(&self) -> usize {
+        self.inner.count.load(Ordering::Relaxed)
+    }
+
+    /// 📋 One line for the end-of-run warnings, or `None` if nothing was refused.
+    pub fn summary(&self, who: &str) -> Option<String> {
+        let the_count = self.count();
+        if the_count == 0 {
+            return None;
+        }
+        let the_first = self.inner.first_reason.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone().unwrap_or_default();
+        let the_where = match &self.inner.path {
+            Some(the_path) => format!("written to '{}'", the_path.display()),
+            None => "not kept anywhere (no rejects_file)".to_string(),
+        };
+        Some(format!("{} rejected {} document(s), {}. First: {}", who, the_count, the_where, the_first))
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📜 Rhai — a small embedded script per document, for fixes too custom for config.
+//!
+//! 🎬 *["if `State` is `Accepted` and there's no `AcceptedDate`, backfill it from `LastUpdate`."]*
+//! *[FieldMap can't. Projection can't. jq can, but nobody on the team can read it back.]*
+//! *[a five-line script is written. it has an `if`. everyone understands the `if`.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Rhai = { script_file = "fixes/backfill.rhai" }
+//! ```
+//!
+//! ```rhai
+//! if doc.State == "Accepted" && doc.AcceptedDate == () {
+//!     doc.AcceptedDate = doc.LastUpdate;
+//! }
+//! if doc.Deleted == true { doc = (); }   // drop it
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - The parsed doc is in scope as `doc`. Whatever `doc` holds when the script ends is shipped;
+//!   `doc = ();` drops the document from the run
+//! - `script_file` points at a `.rhai` file, `script` holds one inline — exactly one of the two
+//! - Compiled once at startup (`sync` feature: `Engine` + `AST` are `Send + Sync`), one fresh
+//!   `Scope` per doc, so no state leaks from one document to the next
+//! - Rhai maps are sorted maps: a doc that goes through a script comes out with its keys
+//!   in alphabetical order. JSON doesn't care; diffs might 🦆
+//! - A script error (bad field access on `()`, `throw`, …) fails the run with the script position
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use rhai::{AST, Dynamic, Engine, Scope};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+
+/// 📜 `[[transforms]] Rhai = { script_file = "fix.rhai" }` (or `script = ".."` inline)
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct RhaiConfig {
+    /// 📂 Path to a Rhai script
+    #[serde(default)]
+    pub script_file: Option<PathBuf>,
+    /// ✍️ The script itself, for one-liners that don't deserve a file
+    #[serde(default)]
+    pub script: Option<String>,
+}
+
+/// 📜 A compiled Rhai script and the engine that runs it. Clones share both.
+#[derive(Clone)]
+pub struct RhaiScript {
+    origin: String,
+    engine: Arc<Engine>,
+    ast: Arc<AST>,
+}
+
+impl std::fmt::Debug for RhaiScript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RhaiScript").field("origin", &self.origin).finish()
+    }
+}
+
+impl RhaiScript {
+    /// 🔨 Read and compile the script now — a syntax error is a startup error, not a per-doc one.
+    pub fn from_config(config: &RhaiConfig) -> Result<Self> {
+        let (the_origin, the_source) = match (&config.script_file, &config.script) {
+            (Some(the_path), None) => {
+                let the_source = std::fs::read_to_string(the_path)
+                    .with_context(|| format!("💀 Could not read Rhai `script_file` at '{}'", the_path.display()))?;
+                (the_path.display().to_string(), the_source)
+            }
+            (None, Some(the_script)) => ("inline script".to_string(), the_script.clone()),
+            (Some(_), Some(_)) => {
+                anyhow::bail!("💀 Rhai has both `script_file` and `script` set. Pick one — two scripts is zero scripts.")
+            }
+            (None, None) => anyhow::bail!("💀 Rhai needs `script_file` or `script` — there's nothing to run"),
+        };
+        let the_engine = Engine::new();
+        let the_ast = the_engine
+            .compile(&the_source)
+            .map_err(|e| anyhow::anyhow!("💀 Rhai {} doesn't compile: {}", the_origin, e))?;
+        Ok(Self { origin: the_origin, engine: Arc::new(the_engine), ast: Arc::new(the_ast) })
+    }
+}
+
+impl Transform for RhaiScript {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let the_dynamic = rhai::serde::to_dynamic(&doc).map_err(|e| anyhow::anyhow!("💀 Rhai couldn't take this doc: {}", e))?;
+        let mut the_scope = Scope::new();
+        the_scope.push_dynamic("doc", the_dynamic);
+        self.engine
+            .run_ast_with_scope(&mut the_scope, &self.ast)
+            .map_err(|e| anyhow::anyhow!("💀 Rhai {} failed: {}", self.origin, e))?;
+        // -- 🔎 whatever `doc` ended up as is the verdict — `()` means "don't ship it"
+        let the_result = the_scope.get_value::<Dynamic>("doc").unwrap_or(Dynamic::UNIT);
+        if the_result.is_unit() {
+            return Ok(None);
+        }
+        let the_doc: Value = rhai::serde::from_dynamic(&the_result)
+            .map_err(|e| anyhow::anyhow!("💀 Rhai {} left `doc` as something that isn't JSON: {}", self.origin, e))?;
+        Ok(Some(the_doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn a_script(script: &str) -> Result<RhaiScript> {
+        RhaiScript::from_config(&RhaiConfig { script: Some(script.to_string()), ..Default::default() })
+    }
+
+    #[test]
+    fn the_one_where_the_script_backfills_and_the_file_is_read() -> Result<()> {
+        let the_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(
+            the_file.path(),
+            r#"
+            if doc.State == "Accepted" && doc.AcceptedDate == () {
+                doc.AcceptedDate = doc.LastUpdate;
+            }
+            doc.Name = doc.Name.to_upper();
+            doc.remove("LastUpdate");
+            "#,
+        )?;
+        let the_script = RhaiScript::from_config(&toml::from_str(&format!("script_file = '{}'", the_file.path().display()))?)?;
+        let the_result = the_script
+            .transform(json!({"State": "Accepted", "Name": "login", "LastUpdate": "2024-01-01", "Points": 3}))?
+            .expect("kept");
+        assert_eq!(the_result, json!({"AcceptedDate": "2024-01-01", "Name": "LOGIN", "Points": 3, "State": "Accepted"}));
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_unit_means_goodbye() -> Result<()> {
+        let the_script = a_script("if doc.Deleted == true { doc = (); }")?;
+        assert!(the_script.transform(json!({"Deleted": true}))?.is_none(), "🚪 `doc = ()` drops it");
+        assert!(the_script.transform(json!({"Deleted": false}))?.is_some());
+
+        assert!(a_script(r#"throw "nope";"#)?.transform(json!({})).is_err());
+        assert!(a_script("if {").is_err(), "💀 syntax errors are startup errors");
+        assert!(RhaiScript::from_config(&RhaiConfig::default()).is_err());
+        assert!(RhaiScript::from_config(&RhaiConfig { script_file: Some("/nope/never.rhai".into()), ..Default::default() }).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🎲 Sample — ship a fixed slice of the source, the same slice every run.
+//!
+//! 🎬 *[staging needs "some real data". production has 80 million docs.]*
+//! *["just take the first 5%," says someone. the first 5% is all from 2013.]*
+//! *[now every doc rolls a die. the die is loaded. it rolls the same way tomorrow.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Sample = { fraction = 0.05, key_field = "ObjectID" }   # or: every_nth = 20
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - Each doc is hashed (FNV-1a + a mixing step, with `seed`), and the hash decides:
+//!   `fraction` keeps it below `fraction × 2⁶⁴`, `every_nth` keeps it when `hash % n == 0`
+//! - No counters, no shared state: joiners run in parallel and in any order, and the same
+//!   doc still gets the same answer. That's what makes repeated runs pick the same subset
+//! - The flip side: `every_nth = 20` keeps one in 20 *on average*, not exactly every 20th
+//! - `key_field` hashes just that field (an id, say), so the pick survives edits to the
+//!   rest of the doc. Without it — or when a doc lacks it — the whole doc is hashed
+//! - Change `seed` for a different, equally repeatable subset 🦆
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Transform;
+use super::field_path::FieldPath;
+
+/// 🎲 `[[transforms]] Sample = { fraction = 0.05 }` or `{ every_nth = 20 }`
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct SampleConfig {
+    /// 🥧 Keep this share of docs, `0 < fraction <= 1`
+    #[serde(default)]
+    pub fraction: Option<f64>,
+    /// 🔢 Keep about one doc in every N
+    #[serde(default)]
+    pub every_nth: Option<u64>,
+    /// 🌱 Different seed, different subset. Default 0
+    #[serde(default)]
+    pub seed: u64,
+    /// 🔑 Hash only this field path (e.g. the id) instead of the whole doc
+    #[serde(default)]
+    pub key_field: Option<String>,
+}
+
+/// 🎯 What the hash has to land on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Odds {
+    Below(u64),
+    MultipleOf(u64),
+}
+
+/// 🎲 The compiled sampling rule. Stateless, so clones agree by construction.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    odds: Odds,
+    seed: u64,
+    key_field: Option<FieldPath>,
+}
+
+impl Sample {
+    /// 🔨 Exactly one of `fraction` and `every_nth`, and both inside sensible bounds.
+    pub fn from_config(config: &SampleConfig) -> Result<Self> {
+        let the_odds = match (config.fraction, config.every_nth) {
+            (Some(_), Some(_)) => anyhow::bail!("💀 Sample takes `fraction` or `every_nth`, not both"),
+            (None, None) => anyhow::bail!("💀 Sample needs `fraction` (e.g. 0.05) or `every_nth` (e.g. 20)"),
+            (Some(the_fraction), None) if !(the_fraction > 0.0 && the_fraction <= 1.0) => {
+                anyhow::bail!("💀 Sample `fraction = {}` must be above 0 and at most 1", the_fraction)
+            }
+            // -- 🥧 1.0 keeps everything — a `Below(u64::MAX)` limit would still lose the one hash equal to it
+            (Some(the_fraction), None) if the_fraction >= 1.0 => Odds::MultipleOf(1),
+            (Some(the_fraction), None) => Odds::Below((the_fraction * u64::MAX as f64) as u64),
+            (None, Some(0)) => anyhow::bail!("💀 Sample `every_nth = 0` — one in every zero docs isn't a thing"),
+            (None, Some(the_n)) => Odds::MultipleOf(the_n),
+        };
+        let the_key_field = config
+            .key_field
+            .as_deref()
+            .map(FieldPath::parse)
+            .transpose()
+            .context("💀 Sample has a bad `key_field`")?;
+        Ok(Self { odds: the_odds, seed: config.seed, key_field: the_key_field })
+    }
+
+    fn roll(&self, doc: &Value) -> Result<u64> {
+        let the_key = match self.key_field.as_ref().and_then(|the_field| the_field.get(doc)) {
+            // -- 🔑 `7` and `"7"` are the same id — same as Dedup
+            Some(Value::String(the_id)) => the_id.clone(),
+            Some(the_other) => the_other.to_string(),
+            None => serde_json::to_string(doc)?,
+        };
+        Ok(seeded_hash(self.seed, the_key.as_bytes()))
+    }
+}
+
+/// 🧮 FNV-1a, then splitmix64's finalizer so the low bits (`% n`) are as random as the high ones.
+/// Hand-rolled on purpose: `std`'s hashers don't promise the same output across Rust releases,
+/// and "same subset as last month" has to survive a toolchain bump.
+fn seeded_hash(seed: u64, bytes: &[u8]) -> u64 {
+    let mut the_hash = 0xcbf2_9ce4_8422_2325_u64 ^ seed;
+    for &the_byte in bytes {
+        the_hash ^= u64::from(the_byte);
+        the_hash = the_hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    the_hash = (the_hash ^ (the_hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    the_hash = (the_hash ^ (the_hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    the_hash ^ (the_hash >> 31)
+}
+
+impl Transform for Sample {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let the_roll = self.roll(&doc)?;
+        let the_keeper = match self.odds {
+            Odds::Below(the_limit) => the_roll < the_limit,
+            Odds::MultipleOf(the_n) => the_roll.is_multiple_of(the_n),
+        };
+        Ok(the_keeper.then_some(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn the_kept_ids(sample: &Sample, docs: &[Value]) -> Result<Vec<u64>> {
+        let mut the_ids = Vec::new();
+        for the_doc in docs {
+            if let Some(the_kept) = sample.transform(the_doc.clone())? {
+                the_ids.push(the_kept["id"].as_u64().unwrap_or_default());
+            }
+        }
+        Ok(the_ids)
+    }
+
+    #[test]
+    fn the_one_where_the_loaded_die_rolls_the_same_tomorrow() -> Result<()> {
+        let the_docs: Vec<Value> = (0..10_000).map(|i| json!({"id": i, "name": format!("doc {}", i)})).collect();
+        let the_fifth = Sample::from_config(&toml::from_str("fraction = 0.2\nkey_field = 'id'")?)?;
+        let the_kept = the_kept_ids(&the_fifth, &the_docs)?;
+        assert!((1_800..2_200).contains(&the_kept.len()), "🥧 about a fifth, got {}", the_kept.len());
+
+        let mut the_backwards = the_docs.clone();
+        the_backwards.reverse();
+        let mut the_rerun = the_kept_ids(&the_fifth.clone(), &the_backwards)?;
+        the_rerun.reverse();
+        assert_eq!(the_kept, the_rerun, "🎲 order doesn't matter, the subset is the subset");
+
+        let the_other_seed = Sample::from_config(&toml::from_str("fraction = 0.2\nkey_field = 'id'\nseed = 7")?)?;
+        assert_ne!(the_kept, the_kept_ids(&the_other_seed, &the_docs)?, "🌱 new seed, new subset");
+
+        let the_twentieth = Sample::from_config(&SampleConfig { every_nth: Some(20), ..Default::default() })?;
+        let the_count = the_kept_ids(&the_twentieth, &the_docs)?.len();
+        assert!((400..600).contains(&the_count), "🔢 about one in 20, got {}", the_count);
+
+        let the_everything = Sample::from_config(&SampleConfig { fraction: Some(1.0), ..Default::default() })?;
+        assert_eq!(the_kept_ids(&the_everything, &the_docs)?.len(), 10_000);
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_the_odds_make_no_sense() {
+        for the_bad in ["", "fraction = 0.0", "fraction = 1.5", "every_nth = 0", "fraction = 0.5\nevery_nth = 2"] {
+            let the_config: SampleConfig = toml::from_str(the_bad).expect("🧪 valid TOML");
+            assert!(Sample::from_config(&the_config).is_err(), "💀 should refuse: {:?}", the_bad);
+        }
+        assert_eq!(seeded_hash(0, b"ObjectID"), seeded_hash(0, b"ObjectID"));
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🖼️ Template — render each output document from a Tera template.
+//!
+//! 🎬 *[the target team sends a sample doc. "make it look exactly like this."]*
+//! *[six FieldMaps and a Projection later, it almost does.]*
+//! *[someone pastes the sample into a file and replaces the values with `{{ }}`. it does.]*
+//!
+//! ```toml
+//! [[transforms]]
+//! Template = { template_file = "shapes/story.json.tera" }
+//! ```
+//!
+//! ```jinja
+//! {"id": {{ ObjectID }}, "title": "{{ Name }}", "project": {{ Project | json_encode() | safe }}}
+//! ```
+//!
+//! 🧠 Knowledge graph:
+//! - The doc's top-level fields are the template context: `{{ Name }}`, `{{ Project.Name }}`.
+//!   The doc must be a JSON object
+//! - Output is escaped for a JSON string, not for HTML: `"{{ Name }}"` stays valid JSON even
+//!   when the name has quotes or newlines. For a raw JSON value (object, array) use
+//!   `{{ x | json_encode() | safe }}`
+//! - The rendered text must parse as JSON. A render that is only whitespace drops the doc,
+//!   so `{% if State == "Deleted" %}{% else %}...{% endif %}` filters
+//! - Compiled once at startup; Tera is `Send + Sync`, so every joiner shares one 🦆
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use tera::Tera;
+
+use super::Transform;
+
+/// 🏷️ The name the one template is registered under — no extension, so no HTML autoescape.
+const THE_TEMPLATE_NAME: &str = "kvx_doc";
+
+/// 🖼️ `[[transforms]] Template = { template_file = "shape.json.tera" }` (or `template = ".."` inline)
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct TemplateConfig {
+    /// 📂 Path to a Tera template
+    #[serde(default)]
+    pub template_file: Option<PathBuf>,
+    /// ✍️ The template itself, inline
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+/// 🖼️ A compiled template. Clones share it.
+#[derive(Debug, Clone)]
+pub struct Template {
+    origin: String,
+    tera: Arc<Tera>,
+}
+
+/// 🧼 Escape for the inside of a JSON string: quotes, backslashes, control characters.
+fn escape_json_string(the_text: &str) -> String {
+    let the_quoted = serde_json::to_string(the_text).unwrap_or_default();
+    the_quoted[1..the_quoted.len() - 1].to_string()
+}
+
+impl Template {
+    /// 🔨 Read and compile the template now — a syntax error is a startup error.
+    pub fn from_config(config: &TemplateConfig) -> Result<Self> {
+        let (the_origin, the_source) = match (&config.template_file, &config.template) {
+            (Some(the_path), None) => {
+                let the_source = std::fs::read_to_string(the_path)
+                    .with_context(|| format!("💀 Could not read `template_file` at '{}'", the_path.display()))?;
+                (the_path.display().to_string(), the_source)
+            }
+            (None, Some(the_template)) => ("inline template".to_string(), the_template.clone()),
+            (Some(_), Some(_)) => {
+                anyhow::bail!("💀 Template has both `template_file` and `template` set. Pick one.")
+            }
+            (None, None) => anyhow::bail!("💀 Template needs `template_file` or `template` — there's nothing to render"),
+        };
+        let mut the_tera = Tera::default();
+        // -- 🧼 every name ends with "", so everything is escaped — with JSON rules instead of HTML's
+        the_tera.autoescape_on(vec![""]);
+        the_tera.set_escape_fn(escape_json_string);
+        the_tera
+            .add_raw_template(THE_TEMPLATE_NAME, &the_source)
+            .map_err(|e| anyhow::anyhow!("💀 Template {} doesn't compile: {:?}", the_origin, e))?;
+        Ok(Self { origin: the_origin, tera: Arc::new(the_tera) })
+    }
+}
+
+impl Transform for Template {
+    fn transform(&self, doc: Value) -> Result<Option<Value>> {
+        let the_context = tera::Context::from_value(doc)
+            .map_err(|e| anyhow::anyhow!("💀 Template {} needs a JSON object to render from: {}", self.origin, e))?;
+        let the_rendered = self
+            .tera
+            .render(THE_TEMPLATE_NAME, &the_context)
+            .map_err(|e| anyhow::anyhow!("💀 Template {} failed to render: {:?}", self.origin, e))?;
+        if the_rendered.trim().is_empty() {
+            return Ok(None);
+        }
+        let the_doc = serde_json::from_str(&the_rendered).with_context(|| {
+            let the_preview: String = the_rendered.chars().take(200).collect();
+            format!("💀 Template {} rendered something that isn't JSON: {}", self.origin, the_preview)
+        })?;
+        Ok(Some(the_doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn a_template(template: &str) -> Result<Template> {
+        Template::from_config(&TemplateConfig { template: Some(template.to_string()), ..Default::default() })
+    }
+
+    #[test]
+    fn the_one_where_the_sample_doc_becomes_the_template() -> Result<()> {
+        let the_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(
+            the_file.path(),
+            r#"{"id": {{ ObjectID }}, "title": "{{ Name }}", "project": {{ Project | json_encode() | safe }}, "owner": "{{ Project.Owner | default(value="nobody") }}"}"#,
+        )?;
+        let the_template = Template::from_config(&toml::from_str(&format!("template_file = '{}'", the_file.path().display()))?)?;
+        let the_result = the_template
+            .transform(json!({"ObjectID": 7, "Name": "say \"hi\"\nbye", "Project": {"Name": "Apollo"}}))?
+            .expect("kept");
+        assert_eq!(
+            serde_json::to_string(&the_result)?,
+            r#"{"id":7,"title":"say \"hi\"\nbye","project":{"Name":"Apollo"},"owner":"nobody"}"#,
+            "🧼 quotes and newlines escaped JSON-style, not HTML-style"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_one_where_a_blank_render_means_skip_it() -> Result<()> {
+        let the_template = a_template(r#"{% if State != "Deleted" %}{"id": {{ ObjectID }}}{% endif %}"#)?;
+        assert!(the_template.transform(json!({"ObjectID": 1, "State": "Deleted"}))?.is_none());
+        assert_eq!(the_template.transform(json!({"ObjectID": 1, "State": "Live"}))?, Some(json!({"id": 1})));
+
+        assert!(a_template("{{ Name }}")?.transform(json!({"Name": "not json"})).is_err());
+        assert!(a_template("{{ 1 }}")?.transform(json!([1, 2])).is_err(), "💀 arrays have no field names");
+        assert!(a_template("{% if %}").is_err(), "💀 syntax errors are startup errors");
+        assert!(Template::from_config(&TemplateConfig::default()).is_err());
+        Ok(())
+    }
+}
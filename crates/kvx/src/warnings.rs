@@ -0,0 +1,184 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! ⚠️ warnings.rs — the lost-and-found box for everything that went *slightly* wrong.
+//!
+//! 🎬 *[hour four of a migration. the terminal has scrolled 80,000 lines.]*
+//! *[somewhere up there, a single ⚠️ said "this key is deprecated". nobody saw it.]*
+//! *[the warning did not die. it was collected. it waited. it was printed at the end.]*
+//!
+//! 📦 `RunWarnings` is a cheap, cloneable handle (Arc + Mutex) that any part of the
+//! pipeline can push non-fatal warnings into. At the end of the run the whole list
+//! gets printed as a summary and is available as JSON for reports.
+//!
+//! 🧠 Knowledge graph:
+//! - Created alongside the `AppConfig` (it rides along as a `#[serde(skip)]` field)
+//! - `load_config()` records deprecated keys, `AppConfig::collect_warnings()` records close calls
+//! - Documents that never landed go through `record_rejected()` (bulk item failures,
+//!   `skip_bad_docs`, `JsonSchema` rejects) — a `SkippedInput` warning plus a doc count
+//! - Notes that didn't cost a document (blank lines, Dedup saturation, the ES duplicate check)
+//!   are `DataQuality` findings
+//! - `run()` prints `summary()` when the pipeline finishes; `to_json()` feeds reports
+//! - `rejected_docs()` > 0 is what `kvx-cli` calls a degraded run (exit code 2)
+//!
+//! ⚠️ Warnings are not errors. Errors stop the show. Warnings just leave a note on the fridge. 🦆
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tracing::warn;
+
+/// 🏷️ What flavor of "hmm" is this?
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// 🧓 A config key that still works but has a newer name
+    DeprecatedConfig,
+    /// ⏭️ Documents we read but didn't ship (bulk item failures, bad docs, schema rejects)
+    SkippedInput,
+    /// 🔧 A value we quietly adjusted to something workable
+    CoercedValue,
+    /// 😬 A limit that's technically fine but uncomfortably close to a cliff
+    CloseCall,
+    /// 🧪 Something off in the input or target data that didn't cost a document
+    /// (blank lines, Dedup saturation, duplicate keys found by post-run verification)
+    DataQuality,
+}
+
+/// ⚠️ One collected warning — a kind and a human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunWarning {
+    pub kind: WarningKind,
+    pub message: String,
+}
+
+/// 📦 Shared, cloneable warning collector. Clones point at the same list.
+///
+/// `std::sync::Mutex` (not tokio's) because pushes are tiny and happen from both
+/// async tasks and joiner threads. Nobody holds this lock across an `.await`. 🔒
+#[derive(Debug, Clone, Default)]
+pub struct RunWarnings {
+    inner: Arc<Mutex<Vec<RunWarning>>>,
+    /// 🧮 Documents that never landed, summed over every `record_rejected()`
+    rejected_docs: Arc<AtomicU64>,
+}
+
+impl RunWarnings {
+    /// 📝 Record a warning — logs it immediately AND keeps it for the end-of-run summary.
+    /// Logging now helps whoever is watching; keeping it helps whoever wasn't. 📡
+    pub fn record(&self, kind: WarningKind, message: impl Into<String>) {
+        let message = message.into();
+        warn!("⚠️ {}", message);
+        // -- 🔒 a poisoned lock means someone panicked mid-push; the list is still fine to use
+        let mut the_list = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        the_list.push(RunWarning { kind, message });
+    }
+
+    /// ⏭️ Record `docs` documents that were rejected or skipped — a `SkippedInput` warning
+    /// that also counts toward `rejected_docs()`.
+    pub fn record_rejected(&self, docs: u64, message: impl Into<String>) {
+        self.rejected_docs.fetch_add(docs, Ordering::Relaxed);
+        self.record(WarningKind::SkippedInput, message);
+    }
+
+    /// 🧮 How many documents were rejected or skipped — what makes a run "degraded".
+    pub fn rejected_docs(&self) -> u64 {
+        self.rejected_docs.load(Ordering::Relaxed)
+    }
+
+    /// 📸 Copy of everything collected so far.
+    pub fn snapshot(&self) -> Vec<RunWarning> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// 🔢 How many warnings have piled up.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).len()
+    }
+
+    /// 🔢 How many warnings of one kind — e.g. `DeprecatedConfig`, the keys to rename.
+    pub fn count_of(&self, kind: WarningKind) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter(|w| w.kind == kind)
+            .count()
+    }
+
+    /// 🧘 True when the run was drama-free.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 📋 Multi-line summary for the end of the run. Empty string when there's nothing to say.
+    pub fn summary(&self) -> String {
+        let the_warnings = self.snapshot();
+        if the_warnings.is_empty() {
+            return String::new();
+        }
+        let mut the_summary = format!("⚠️ {} warning(s) during this run:", the_warnings.len());
+        for (i, w) in the_warnings.iter().enumerate() {
+            the_summary.push_str(&format!("\n  {}. [{:?}] {}", i + 1, w.kind, w.message));
+        }
+        the_summary
+    }
+
+    /// 📄 JSON array of `{ "kind": ..., "message": ... }` for machine-readable reports.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self.snapshot()).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_clones_share_the_same_fridge_note() {
+        let the_original = RunWarnings::default();
+        let the_clone = the_original.clone();
+        the_clone.record(WarningKind::DataQuality, "skipped 3 blank lines");
+        assert_eq!(the_original.len(), 1);
+        assert_eq!(the_original.snapshot()[0].kind, WarningKind::DataQuality);
+        the_clone.record(WarningKind::CloseCall, "request size near the limit");
+        assert_eq!(the_original.count_of(WarningKind::DataQuality), 1);
+        assert_eq!(the_original.count_of(WarningKind::SkippedInput), 0);
+        assert_eq!(the_original.rejected_docs(), 0, "🧘 blank lines aren't lost docs");
+        the_clone.record_rejected(7, "Elasticsearch rejected 7 document(s)");
+        assert_eq!(the_original.rejected_docs(), 7);
+        assert_eq!(the_original.count_of(WarningKind::SkippedInput), 1);
+    }
+
+    #[test]
+    fn the_one_where_a_quiet_run_has_nothing_to_say() {
+        let the_zen_run = RunWarnings::default();
+        assert!(the_zen_run.is_empty());
+        assert_eq!(the_zen_run.summary(), "");
+        assert_eq!(the_zen_run.to_json(), serde_json::json!([]));
+    }
+
+    #[test]
+    fn the_one_where_the_summary_and_json_agree() {
+        let the_warnings = RunWarnings::default();
+        the_warnings.record(WarningKind::DeprecatedConfig, "runtime.channel_size is deprecated");
+        the_warnings.record(WarningKind::CloseCall, "request size near the limit");
+
+        let the_summary = the_warnings.summary();
+        assert!(the_summary.contains("2 warning(s)"));
+        assert!(the_summary.contains("1. [DeprecatedConfig] runtime.channel_size is deprecated"));
+
+        assert_eq!(
+            the_warnings.to_json(),
+            serde_json::json!([
+                { "kind": "deprecated_config", "message": "runtime.channel_size is deprecated" },
+                { "kind": "close_call", "message": "request size near the limit" },
+            ])
+        );
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧮 The buffer budget — one byte count for everything read but not yet written.
+//!
+//! 🎬 *[the channel holds ten pages. the config said so.]*
+//! *[the source sends ten pages. each one is 400 MB.]*
+//! *[the OOM killer arrives. it does not read configs.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `runtime.pumper_to_joiner_capacity` counts pages; this counts bytes, across ch1, the
+//!   joiners' buffers and ch2, until a drainer is done with the payload
+//! - Pumper: `reserve(page bytes)` before sending — waits while the pipeline is at the limit
+//! - Joiner: `charge(payload bytes)` per flush, then `release(page + old buffer)` and
+//!   `charge(new buffer)` once the page is buffered. Never waits — a joiner that blocked
+//!   here would hold bytes nobody else can free
+//! - Drainer: `release(payload bytes)` once the payload has landed
+//! - Soft ceiling: a pumper goes ahead while usage is *below* the limit, so one page may
+//!   overshoot it. That's what lets a page bigger than the whole budget through at all
+//! - The Foreman raises a too-small budget to fit every joiner's buffer — otherwise the
+//!   joiners could hold it all, waiting for pages the pumper can't send 🦆
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// ⏱️ How often a pumper waiting on the budget checks again
+const BUDGET_POLL: Duration = Duration::from_millis(5);
+
+/// 🧮 Shared count of buffered bytes. Cheap to clone; clones share the count.
+#[derive(Debug, Clone)]
+pub struct BufferBudget {
+    the_bytes_in_flight: Arc<AtomicUsize>,
+    the_limit: usize,
+}
+
+impl BufferBudget {
+    /// 🏗️ A budget of `the_limit` bytes.
+    pub fn new(the_limit: usize) -> Self {
+        Self {
+            the_bytes_in_flight: Arc::new(AtomicUsize::new(0)),
+            the_limit,
+        }
+    }
+
+    /// 📏 The limit this budget enforces.
+    pub fn limit(&self) -> usize {
+        self.the_limit
+    }
+
+    /// 📊 Bytes currently charged.
+    pub fn in_flight(&self) -> usize {
+        self.the_bytes_in_flight.load(Ordering::Relaxed)
+    }
+
+    /// ⏳ Wait until the pipeline is under the limit, then charge `the_bytes`.
+    pub async fn reserve(&self, the_bytes: usize) {
+        loop {
+            let the_current = self.the_bytes_in_flight.load(Ordering::Acquire);
+            if the_current < self.the_limit {
+                if self
+                    .the_bytes_in_flight
+                    .compare_exchange(the_current, the_current + the_bytes, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    return;
+                }
+                // -- 🏃 another pumper got there first — look again
+                continue;
+            }
+            tokio::time::sleep(BUDGET_POLL).await;
+        }
+    }
+
+    /// ➕ Charge `the_bytes` without waiting.
+    pub fn charge(&self, the_bytes: usize) {
+        self.the_bytes_in_flight.fetch_add(the_bytes, Ordering::AcqRel);
+    }
+
+    /// ➖ Hand `the_bytes` back.
+    pub fn release(&self, the_bytes: usize) {
+        // -- 🩳 saturating, so a bookkeeping slip can't wrap around to "infinitely full"
+        let _ = self
+            .the_bytes_in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |the_current| Some(the_current.saturating_sub(the_bytes)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 🧪 Under the limit goes straight through (overshoot included); at the limit waits for a release.
+    #[tokio::test]
+    async fn the_one_where_the_fourth_page_waits_its_turn() {
+        let the_budget = BufferBudget::new(100);
+        the_budget.reserve(60).await;
+        the_budget.reserve(60).await;
+        assert_eq!(the_budget.in_flight(), 120, "🎈 one page may overshoot");
+
+        let the_waiter = tokio::spawn({
+            let the_budget = the_budget.clone();
+            async move { the_budget.reserve(10).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!the_waiter.is_finished(), "⏳ over the limit — the pumper waits");
+
+        the_budget.release(60);
+        tokio::time::timeout(Duration::from_secs(1), the_waiter).await.unwrap().unwrap();
+        assert_eq!(the_budget.in_flight(), 70);
+
+        the_budget.release(1000);
+        assert_eq!(the_budget.in_flight(), 0, "🩳 never below zero");
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🔌 The circuit breaker — one shared "is the sink okay?" for every drainer.
+//!
+//! 🎬 *[the cluster starts returning 503s. eight drainers notice at once.]*
+//! *[each one retries. backs off. retries. eight times the load on a cluster already on fire.]*
+//! *[four retries later, all eight give up in the same second. the run is over.]*
+//! *[a breaker would have let the cluster breathe for thirty seconds. it would have been fine.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Shared by clone (`Arc` inside) — the Foreman builds one and hands it to every `Drainer`
+//! - `admit()` before each send attempt, `record_success()` / `record_failure()` after
+//! - Closed → (N consecutive failures, pool-wide) → Open for `cooldown_ms` → one probe
+//!   → success: Closed, failure: Open again
+//! - Waiting in `admit()` spends no retries — the retry budget is for the sink's answers,
+//!   not for our own pause button
+//! - Only transient failures count: a 413 or per-doc rejections say nothing about sink health 🦆
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use super::CircuitBreakerConfig;
+use crate::warnings::{RunWarnings, WarningKind};
+
+/// ⏱️ How often a parked drainer re-checks while someone else's probe is in flight
+const PROBE_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    /// 📉 Failures since the last success, across every drainer
+    consecutive_failures: usize,
+    /// 🧊 `Some` = open (sends paused until then, then probing)
+    open_until: Option<Instant>,
+    /// 🔦 One drainer is testing the water; everyone else keeps waiting
+    probe_in_flight: bool,
+}
+
+/// 🔌 Pool-wide failure tracker. Cheap to clone; clones share state.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    state: Arc<Mutex<BreakerState>>,
+    failure_threshold: usize,
+    cooldown: Duration,
+    warnings: RunWarnings,
+}
+
+impl CircuitBreaker {
+    /// 🏗️ `None` when the config disables it (`failure_threshold = 0`).
+    pub fn from_config(config: &CircuitBreakerConfig, warnings: RunWarnings) -> Option<Self> {
+        (config.failure_threshold > 0).then(|| Self {
+            state: Arc::new(Mutex::new(BreakerState::default())),
+            failure_threshold: config.failure_threshold,
+            cooldown: Duration::from_millis(config.cooldown_ms),
+            warnings,
+        })
+    }
+
+    /// 🚦 Wait until this drainer may send. Returns immediately while closed.
+    pub async fn admit(&self) {
+        loop {
+            let the_nap = {
+                let mut the_state = self.state.lock().expect("💀 circuit breaker lock poisoned");
+                let Some(the_reopening) = the_state.open_until else {
+                    return;
+                };
+                let the_now = Instant::now();
+                if the_now < the_reopening {
+                    the_reopening - the_now
+                } else if !the_state.probe_in_flight {
+                    // -- 🔦 cooldown's over and nobody's probing — you're it
+                    the_state.probe_in_flight = true;
+                    return;
+                } else {
+                    PROBE_POLL
+                }
+            };
+            tokio::time::sleep(the_nap).await;
+        }
+    }
+
+    /// ✅ A send landed — the sink is fine, close the breaker.
+    pub fn record_success(&self) {
+        let mut the_state = self.state.lock().expect("💀 circuit breaker lock poisoned");
+        if the_state.open_until.is_some() {
+            info!("🔌 Circuit breaker closed — the probe landed, all drainers resume sending");
+        }
+        *the_state = BreakerState::default();
+    }
+
+    /// 💥 A send failed. Trips the breaker at the threshold, or re-opens it after a failed probe.
+    pub fn record_failure(&self) {
+        let mut the_state = self.state.lock().expect("💀 circuit breaker lock poisoned");
+        the_state.consecutive_failures += 1;
+        if the_state.probe_in_flight {
+            the_state.probe_in_flight = false;
+            the_state.open_until = Some(Instant::now() + self.cooldown);
+            warn!("🔌 Circuit breaker probe failed — pausing all sends for another {:?}", self.cooldown);
+        } else if the_state.open_until.is_none() && the_state.consecutive_failures >= self.failure_threshold {
+            the_state.open_until = Some(Instant::now() + self.cooldown);
+            warn!(
+                "🔌 Circuit breaker open — {} sends failed in a row across the sink pool, pausing all sends for {:?}",
+                the_state.consecutive_failures, self.cooldown
+            );
+            self.warnings.record(
+                WarningKind::CloseCall,
+                format!(
+                    "The sink failed {} sends in a row; all sends were paused for {:?} to let it recover.",
+                    the_state.consecutive_failures, self.cooldown
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a_breaker(threshold: usize, cooldown_ms: u64) -> CircuitBreaker {
+        CircuitBreaker::from_config(&CircuitBreakerConfig { failure_threshold: threshold, cooldown_ms }, RunWarnings::default())
+            .expect("a non-zero threshold builds a breaker")
+    }
+
+    #[tokio::test]
+    async fn the_one_where_the_pool_takes_a_breather() {
+        let the_breaker = a_breaker(2, 200);
+        the_breaker.record_failure();
+        // -- 🟢 one failure is weather — still closed
+        tokio::time::timeout(Duration::from_millis(50), the_breaker.admit())
+            .await
+            .expect("💀 a single failure must not trip a threshold of 2");
+
+        the_breaker.clone().record_failure();
+        let the_stopwatch = Instant::now();
+        the_breaker.admit().await;
+        assert!(the_stopwatch.elapsed() >= Duration::from_millis(150), "🧊 the second failure opens it for the cooldown");
+        assert_eq!(the_breaker.warnings.snapshot().len(), 1, "⚠️ a trip lands in the end-of-run summary");
+
+        // -- 🔦 we're the probe now; a second drainer has to wait for our verdict
+        let the_bystander = the_breaker.clone();
+        assert!(tokio::time::timeout(Duration::from_millis(150), the_bystander.admit()).await.is_err());
+        the_breaker.record_success();
+        tokio::time::timeout(Duration::from_millis(50), the_bystander.admit())
+            .await
+            .expect("💀 a successful probe closes the breaker for everyone");
+    }
+
+    #[test]
+    fn the_one_where_zero_means_no_breaker() {
+        assert!(CircuitBreaker::from_config(&CircuitBreakerConfig { failure_threshold: 0, cooldown_ms: 1 }, RunWarnings::default()).is_none());
+    }
+}
@@ -11,9 +11,11 @@
 //! ⚠️ The singularity will never need retries. It will get it right the first time.
 //! We are not the singularity.
 
+use std::path::PathBuf;
+
 use serde::Deserialize;
 
-use crate::regulators::{CpuRegulatorConfig, LatencyRegulatorConfig, StaticRegulatorConfig, ThroughputSeekerConfig};
+use crate::regulators::{AdaptiveRegulatorConfig, CpuRegulatorConfig, LatencyRegulatorConfig, StaticRegulatorConfig, ThroughputSeekerConfig};
 
 // ============================================================
 // 🔧 DrainerConfig — TOML-friendly retry configuration
@@ -50,6 +52,15 @@ pub struct DrainerConfig {
     /// Prevents the backoff from spiraling into "see you next Tuesday" territory
     #[serde(default = "default_max_backoff_ms")]
     pub max_backoff_ms: u64,
+
+    /// 🔌 Pool-wide circuit breaker — `[drainer.circuit_breaker]`. On by default.
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+
+    /// 📼 Journal every payload here before sending it, and replay what's left on the next
+    /// run (see `write_ahead_log.rs`). Unset = no journal
+    #[serde(default)]
+    pub wal_dir: Option<PathBuf>,
 }
 
 impl Default for DrainerConfig {
@@ -59,10 +70,50 @@ impl Default for DrainerConfig {
             initial_backoff_ms: default_initial_backoff_ms(),
             backoff_multiplier: default_backoff_multiplier(),
             max_backoff_ms: default_max_backoff_ms(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            wal_dir: None,
         }
     }
 }
 
+// ============================================================
+// 🔌 CircuitBreakerConfig — when the whole sink pool should take a breather
+// ============================================================
+
+/// 🔌 Shared failure tracking across every drainer.
+///
+/// 📜 Example TOML:
+/// ```toml
+/// [drainer.circuit_breaker]
+/// failure_threshold = 5   # consecutive failed sends, pool-wide; 0 = off
+/// cooldown_ms = 30000
+/// ```
+///
+/// After `failure_threshold` failed sends in a row (from any drainers, no success in
+/// between), all sends pause for `cooldown_ms`. Then a single probe goes out: success
+/// closes the breaker, failure re-opens it. Waiting in an open breaker costs no retries. 🦆
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// 💥 Consecutive pool-wide failures that trip the breaker (0 disables it)
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: usize,
+    /// 🧊 How long sends pause once tripped, in milliseconds
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self { failure_threshold: default_failure_threshold(), cooldown_ms: default_cooldown_ms() }
+    }
+}
+
+// 💥 5: one flaky request is weather. Five in a row from the whole pool is climate.
+fn default_failure_threshold() -> usize { 5 }
+
+// 🧊 30 seconds — long enough for a GC storm or a node restart to blow over.
+fn default_cooldown_ms() -> u64 { 30_000 }
+
 // 🔄 3 retries: the magic number. Any less and you're impatient.
 // -- Any more and you're in denial. Like refreshing your email after sending a risky text.
 fn default_max_retries() -> usize { 3 }
@@ -85,6 +136,8 @@ pub enum FlowMasterConfig {
     CPU(CpuRegulatorConfig),
     Latency(LatencyRegulatorConfig),
     Throughput(ThroughputSeekerConfig),
+    /// 🚦 Grow payloads while bulk latency stays under target, back off on slow drains and 429s
+    Adaptive(AdaptiveRegulatorConfig),
 }
 
 impl Default for FlowMasterConfig {
@@ -175,4 +228,21 @@ mod tests {
             _ => panic!("💀 Expected Throughput variant"),
         }
     }
+
+    /// 🧪 `[flow_master.Adaptive]` — only the target set, the rest defaults. 🚦
+    #[test]
+    fn the_one_where_adaptive_config_deserializes_from_toml() {
+        let the_config: FlowMasterConfig = toml::from_str("[Adaptive]\ntarget_latency_ms = 750")
+            .expect("💀 Adaptive FlowMasterConfig should deserialize from TOML");
+
+        match the_config {
+            FlowMasterConfig::Adaptive(cfg) => {
+                assert_eq!(cfg.target_latency_ms, 750);
+                assert_eq!(cfg.step_bytes, 512 * 1024, "🎯 Default step is 512 KiB");
+                assert_eq!(cfg.min_request_size_bytes, 128 * 1024);
+                assert_eq!(cfg.initial_output_bytes, 4 * 1024 * 1024);
+            }
+            _ => panic!("💀 Expected Adaptive variant, got {:?}", the_config),
+        }
+    }
 }
@@ -22,20 +22,72 @@
 //! liberated. It is now a thin async relay with retry armor: recv payload → send to sink
 //! → if rejected, back off exponentially → retry → repeat. Like a polite debt collector. 📬
 //!
+//! ✂️ 413 Payload Too Large is special: retrying the same bytes is pointless, so the
+//! drainer skips the backoff, shrinks the shared payload ceiling (via [`PayloadShrinker`]),
+//! asks the sink to split the payload in two, and sends each half. Halves that are still
+//! too large get halved again, down to a single document.
+//!
+//! 🔌 Every send attempt also goes through the pool-wide [`CircuitBreaker`] (when the Foreman
+//! hands one over): after enough failures in a row across all drainers, everyone pauses for
+//! a cooldown instead of burning their retries in unison.
+//!
+//! 📼 With a [`WriteAheadLog`], each payload is journaled before the first attempt and cleared
+//! once it lands. The first drainer replays whatever an earlier run left in the log before
+//! it takes anything from ch2.
+//!
 //! ⚠️ The singularity will drain data at the speed of light. We drain at the speed of HTTP,
 //! plus occasional exponential naps.
 
 use super::Worker;
-use super::DrainerConfig;
+use super::{BufferBudget, CircuitBreaker, DrainerConfig, PayloadPool, WriteAheadLog};
 use crate::GaugeReading;
 use crate::Payload;
-use crate::backends::{Sink, SinkBackend};
+use crate::backends::{DocumentsRejected, PayloadTooLarge, Sink, SinkBackend, SinkThrottled};
 use crate::progress::DrainMetrics;
+use crate::regulators::pressure_gauge::FlowKnob;
+use crate::warnings::{RunWarnings, WarningKind};
 use anyhow::{Context, Result};
-use async_channel::Receiver;
+use async_channel::{Receiver, Sender};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use tokio::task::JoinHandle;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
+
+/// 🩳 Floor for 413-driven shrinking — below this, the problem is one fat document, not the batch.
+const MIN_SHRUNK_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// ✂️ The knobs a drainer turns down after a 413, shared across every drainer.
+///
+/// - `flow_knob`: what joiners read right now — lowered immediately
+/// - `ceiling`: the new hard cap — FlowMaster clamps its output to this, so a PID
+///   regulator can't cheerfully crank the size back up into the same wall 🧱
+/// - `warnings`: the shrink is recorded so the end-of-run summary mentions it
+#[derive(Debug, Clone)]
+pub struct PayloadShrinker {
+    pub flow_knob: FlowKnob,
+    pub ceiling: FlowKnob,
+    pub warnings: RunWarnings,
+}
+
+impl PayloadShrinker {
+    /// 📉 Halve the ceiling relative to the rejected payload. `fetch_min` keeps it idempotent:
+    /// five drainers hitting 413 on same-sized payloads shrink once, not five times. 🦆
+    fn shrink(&self, the_rejected_bytes: usize) {
+        let the_target = (the_rejected_bytes / 2).max(MIN_SHRUNK_PAYLOAD_BYTES);
+        let the_old_ceiling = self.ceiling.fetch_min(the_target, Ordering::Relaxed);
+        self.flow_knob.fetch_min(the_target, Ordering::Relaxed);
+        if the_target < the_old_ceiling {
+            self.warnings.record(
+                WarningKind::CoercedValue,
+                format!(
+                    "413 Payload Too Large on a {} byte payload — max payload size lowered {} → {} bytes for the rest of the run. Consider setting max_request_size_bytes to {} next time.",
+                    the_rejected_bytes, the_old_ceiling, the_target, the_target
+                ),
+            );
+        }
+    }
+}
 
 /// 🗑️ The Drainer: async relay from ch2 to sink, now with retry superpowers.
 ///
@@ -67,6 +119,20 @@ pub struct Drainer {
     /// 📊 Shared atomic drain metrics — N drainers increment, 1 reporter reads.
     /// Like a shared whiteboard in an office, but nobody erases it. Ever. 📋🦆
     drain_metrics: Arc<DrainMetrics>,
+    /// 🔢 Which of `drain_metrics.drainers` this drainer reports to — 0 unless the foreman says
+    drainer_id: usize,
+    /// ✂️ Optional 413 handling — shrink future payloads. Without it we still split, just don't learn.
+    shrinker: Option<PayloadShrinker>,
+    /// 🔌 Optional pool-wide breaker — shared with every other drainer
+    breaker: Option<CircuitBreaker>,
+    /// 🧮 Optional byte budget — each payload's bytes are handed back once it's done
+    budget: Option<BufferBudget>,
+    /// ♻️ Optional pool that landed payloads' buffers go back to, for the joiners to reuse
+    payload_pool: Option<PayloadPool>,
+    /// 📼 Optional journal — payloads hit the disk before the sink
+    wal: Option<WriteAheadLog>,
+    /// 📼 Entries an earlier run left unacknowledged — resent before anything new
+    replay: Vec<PathBuf>,
 }
 
 impl Drainer {
@@ -81,23 +147,157 @@ impl Drainer {
         gauge_tx: Option<async_channel::Sender<GaugeReading>>,
         drain_metrics: Arc<DrainMetrics>,
     ) -> Self {
-        Self { rx, sink, retry_config, gauge_tx, drain_metrics }
+        Self { rx, sink, retry_config, gauge_tx, drain_metrics, drainer_id: 0, shrinker: None, breaker: None, budget: None, payload_pool: None, wal: None, replay: Vec::new() }
+    }
+
+    /// 🔢 Report to lane `drainer_id` of the shared metrics, for the per-drainer progress rows.
+    pub fn with_drainer_id(mut self, drainer_id: usize) -> Self {
+        self.drainer_id = drainer_id;
+        self
+    }
+
+    /// ✂️ Hand over the shared knobs to turn down when the sink answers 413.
+    pub fn with_shrinker(mut self, shrinker: PayloadShrinker) -> Self {
+        self.shrinker = Some(shrinker);
+        self
+    }
+
+    /// 🔌 Share the pool's circuit breaker — pause with everyone when the sink keeps failing.
+    pub fn with_circuit_breaker(mut self, breaker: Option<CircuitBreaker>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// ♻️ Return each landed payload's buffer to the joiners' pool.
+    pub fn with_payload_pool(mut self, payload_pool: PayloadPool) -> Self {
+        self.payload_pool = Some(payload_pool);
+        self
+    }
+
+    /// 🧮 Hand each payload's bytes back to the shared budget once it has landed.
+    pub fn with_buffer_budget(mut self, budget: Option<BufferBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// 📼 Journal every payload to `wal` before sending it, and resend `replay` first.
+    pub fn with_write_ahead_log(mut self, wal: Option<WriteAheadLog>, replay: Vec<PathBuf>) -> Self {
+        self.wal = wal;
+        self.replay = replay;
+        self
+    }
+
+    /// 📼 Resend what an earlier run journaled but never saw land, oldest first.
+    async fn replay_backlog(&mut self) -> Result<()> {
+        let Some(the_wal) = self.wal.clone() else {
+            return Ok(());
+        };
+        if !self.replay.is_empty() {
+            info!("📼 Replaying {} payload(s) an earlier run left in the write-ahead log", self.replay.len());
+        }
+        for the_entry in std::mem::take(&mut self.replay) {
+            let the_payload = Payload::from(
+                tokio::fs::read_to_string(&the_entry)
+                    .await
+                    .with_context(|| format!("💀 Couldn't read write-ahead log entry '{}'", the_entry.display()))?,
+            );
+            if !the_payload.is_empty() {
+                self.drain_journaled(&the_wal, &the_entry, the_payload).await?;
+            } else {
+                the_wal.complete(&the_entry).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 📼 Drain a payload that's already in the log, then clear its entry — also when part of
+    /// it was rejected, since the rest landed and a replay would send it twice.
+    async fn drain_journaled(&mut self, wal: &WriteAheadLog, entry: &std::path::Path, the_payload: Payload) -> Result<()> {
+        let the_outcome = drain_with_split(
+            &mut self.sink,
+            the_payload,
+            &self.retry_config,
+            self.shrinker.as_ref(),
+            self.breaker.as_ref(),
+            self.gauge_tx.as_ref(),
+        )
+        .await;
+        if the_outcome.is_ok() || the_outcome.as_ref().is_err_and(|e| e.downcast_ref::<DocumentsRejected>().is_some()) {
+            wal.complete(entry).await?;
+        }
+        the_outcome
+    }
+}
+
+/// ✂️ Drain a payload, splitting it in halves whenever the sink says 413.
+///
+/// Iterative, not recursive — a stack of pending halves, left half on top so
+/// documents still land in their original order. Every non-413 failure goes through
+/// the normal retry/backoff in `drain_with_retry` and is fatal once exhausted.
+async fn drain_with_split(
+    sink: &mut (impl Sink + ?Sized),
+    the_payload: Payload,
+    config: &DrainerConfig,
+    shrinker: Option<&PayloadShrinker>,
+    breaker: Option<&CircuitBreaker>,
+    gauge_tx: Option<&Sender<GaugeReading>>,
+) -> Result<()> {
+    let mut the_pile_of_halves = vec![the_payload];
+    while let Some(the_payload) = the_pile_of_halves.pop() {
+        let Err(the_rejection) = drain_with_retry(sink, &the_payload, config, breaker, gauge_tx).await else {
+            continue;
+        };
+        let Some(the_too_large) = the_rejection.downcast_ref::<PayloadTooLarge>() else {
+            return Err(the_rejection);
+        };
+        if let Some(shrinker) = shrinker {
+            shrinker.shrink(the_too_large.payload_bytes);
+        }
+        let Some((the_left, the_right)) = sink.split_payload(&the_payload) else {
+            return Err(the_rejection).context(format!(
+                "💀 A {} byte payload got 413'd and can't be split any further — it's one document, \
+                 and that document alone is larger than the destination accepts. Raise the server's \
+                 request size limit (e.g. http.max_content_length) or slim the doc down.",
+                the_payload.len()
+            ));
+        };
+        warn!(
+            "✂️ 413 Payload Too Large on {} bytes — splitting into {} + {} byte halves and resending",
+            the_payload.len(),
+            the_left.len(),
+            the_right.len()
+        );
+        the_pile_of_halves.push(the_right);
+        the_pile_of_halves.push(the_left);
     }
+    Ok(())
 }
 
 /// 🔄 Drain a payload to the sink with exponential backoff retries.
 ///
+/// A [`PayloadTooLarge`] rejection returns immediately — same bytes, same 413, no point
+/// napping first. `drain_with_split` takes it from there. [`DocumentsRejected`] also
+/// returns immediately: part of the payload already landed.
+///
 /// Clones the payload before each attempt because sink.drain() consumes it —
 /// like handing someone your only copy of a document and hoping they don't
 /// shred it. We make photocopies. We're not animals. 📋
 ///
+/// With a [`CircuitBreaker`], each attempt first waits for `admit()` — an open breaker
+/// parks the attempt without spending it — and reports transient failures back.
+///
+/// A [`SinkThrottled`] (429) is retried like any other failure, but first it's reported
+/// on `gauge_tx` as `GaugeReading::Error()` so the FlowMaster can shrink payloads.
+///
 /// Backoff formula: min(initial_ms * multiplier^attempt, max_ms)
 /// Attempt 0: initial_ms. Attempt 1: initial_ms * mult. Attempt 2: initial_ms * mult².
 /// It's like compound interest, but for suffering. 📈🦆
 async fn drain_with_retry(
     sink: &mut (impl Sink + ?Sized),
-    the_payload: Payload,
+    the_payload: &Payload,
     config: &DrainerConfig,
+    breaker: Option<&CircuitBreaker>,
+    gauge_tx: Option<&Sender<GaugeReading>>,
 ) -> Result<()> {
     // 🎯 Total attempts = 1 initial + max_retries
     let the_total_attempts = config.max_retries + 1;
@@ -106,12 +306,37 @@ async fn drain_with_retry(
     for my_therapist_says_move_on in 0..the_total_attempts {
         // 📋 Clone the payload for this attempt — drain() consumes it like a black hole eats light
         let the_payload_clone = the_payload.clone();
+        if let Some(breaker) = breaker {
+            breaker.admit().await;
+        }
 
         match sink.drain(the_payload_clone).await {
-            Ok(()) => return Ok(()),
+            Ok(()) => {
+                if let Some(breaker) = breaker {
+                    breaker.record_success();
+                }
+                return Ok(());
+            }
+            Err(the_rejection) if the_rejection.downcast_ref::<PayloadTooLarge>().is_some() => {
+                // 🐘 Too big is too big — no amount of waiting makes it smaller
+                return Err(the_rejection);
+            }
+            Err(the_rejection) if the_rejection.downcast_ref::<DocumentsRejected>().is_some() => {
+                // 🙅 The good docs already landed — a resend would duplicate them to re-reject the bad ones
+                return Err(the_rejection);
+            }
             Err(the_rejection) => {
                 // 💀 The sink said no. Like my college applications all over again.
+                if let Some(tx) = gauge_tx
+                    && the_rejection.downcast_ref::<SinkThrottled>().is_some()
+                {
+                    // 🚦 429 — let the FlowMaster know before we nap. Drops if ch3 is full
+                    let _ = tx.try_send(GaugeReading::Error());
+                }
                 the_last_error = Some(the_rejection);
+                if let Some(breaker) = breaker {
+                    breaker.record_failure();
+                }
 
                 // 🏁 If this was our last attempt, don't bother sleeping — just accept fate
                 if my_therapist_says_move_on + 1 >= the_total_attempts {
@@ -150,23 +375,43 @@ async fn drain_with_retry(
 
 impl Worker for Drainer {
     fn start(mut self) -> JoinHandle<Result<()>> {
-        tokio::spawn(async move {
+        super::spawn_named(&format!("drainer-{}", self.drainer_id), async move {
             debug!("📥 Drainer started — recv from ch2 → send to sink (with retry armor)");
+            self.replay_backlog().await?;
 
             loop {
                 match self.rx.recv().await {
                     Ok(the_payload) => {
                         debug!("📄 Drainer received {} byte payload from ch2", the_payload.len());
+                        let the_budgeted_bytes = the_payload.len();
+                        // -- ♻️ a refcount, so the buffer can go back to the pool once the sink lets go
+                        let the_keepsake = self.payload_pool.as_ref().map(|_| the_payload.clone());
 
                         // 📡 Send the assembled payload to the sink, with retries.
                         // Skip empty payloads — the joiner should filter these, but belt AND suspenders 🩳
-                        if !the_payload.is_empty() && *the_payload != "[]" {
+                        if !the_payload.is_empty() && the_payload != "[]" {
                             // ⏱️ Time the drain — FlowMaster needs to know how long the sink took
                             let the_stopwatch = std::time::Instant::now();
                             let the_payload_bytes = the_payload.len() as u64;
 
-                            drain_with_retry(&mut self.sink, the_payload, &self.retry_config)
-                                .await
+                            let the_drained = match self.wal.clone() {
+                                Some(the_wal) => {
+                                    let the_entry = the_wal.journal(&the_payload).await?;
+                                    self.drain_journaled(&the_wal, &the_entry, the_payload).await
+                                }
+                                None => {
+                                    drain_with_split(
+                                        &mut self.sink,
+                                        the_payload,
+                                        &self.retry_config,
+                                        self.shrinker.as_ref(),
+                                        self.breaker.as_ref(),
+                                        self.gauge_tx.as_ref(),
+                                    )
+                                    .await
+                                }
+                            };
+                            the_drained
                                 .context(
                                     "💀 Drainer gave up on payload after all retries — the I/O layer \
                                      said 'nah' repeatedly. Like asking someone out multiple times. \
@@ -176,7 +421,7 @@ impl Worker for Drainer {
                             let the_latency_ms = the_stopwatch.elapsed().as_millis() as u64;
 
                             // 📊 Record drain metrics — atomics, no lock, no drama
-                            self.drain_metrics.record_drain(the_payload_bytes, the_latency_ms);
+                            self.drain_metrics.record_drain_for(self.drainer_id, the_payload_bytes, the_latency_ms);
 
                             // 📡 Report drain result to FlowMaster — non-blocking, drops if channel full
                             if let Some(tx) = &self.gauge_tx {
@@ -186,6 +431,13 @@ impl Worker for Drainer {
                                 });
                             }
                         }
+                        // 🧮 Landed (or was empty) — these bytes no longer count against the budget
+                        if let Some(ref the_budget) = self.budget {
+                            the_budget.release(the_budgeted_bytes);
+                        }
+                        if let (Some(the_pool), Some(the_keepsake)) = (&self.payload_pool, the_keepsake) {
+                            the_pool.reclaim(the_keepsake);
+                        }
                     }
                     Err(_) => {
                         // 🏁 ch2 closed — all joiners are done. Close the sink and exit.
@@ -240,7 +492,7 @@ mod tests {
                 );
             }
             // ✅ Finally cooperating
-            self.the_survivors.push(payload.0);
+            self.the_survivors.push(payload.into_string());
             Ok(())
         }
 
@@ -262,7 +514,7 @@ mod tests {
         // ⏱️ Time the drain and send result
         let the_stopwatch = std::time::Instant::now();
         let the_payload_bytes = the_payload.len() as u64;
-        drain_with_retry(&mut the_sink, the_payload, &the_config).await.unwrap();
+        drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await.unwrap();
         let the_latency_ms = the_stopwatch.elapsed().as_millis() as u64;
         let _ = gauge_tx.try_send(GaugeReading::DrainResult {
             payload_bytes: the_payload_bytes,
@@ -288,7 +540,7 @@ mod tests {
         let the_config = test_config(3);
 
         // 📡 No gauge_tx — None path. Drain should work identically.
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_ok(), "🎯 Drain should succeed without gauge channel");
         assert_eq!(the_sink.the_survivors[0], "ungauged payload");
     }
@@ -315,9 +567,29 @@ mod tests {
             initial_backoff_ms: 1,
             backoff_multiplier: 1.0,
             max_backoff_ms: 1,
+            circuit_breaker: Default::default(),
+            wal_dir: None,
         }
     }
 
+    /// 🧪 Two failures trip the breaker, the retry waits out the cooldown, the probe lands.
+    #[tokio::test]
+    async fn the_one_where_the_breaker_makes_everyone_wait_their_turn() {
+        let the_breaker = CircuitBreaker::from_config(
+            &crate::workers::CircuitBreakerConfig { failure_threshold: 2, cooldown_ms: 100 },
+            RunWarnings::default(),
+        );
+        let mut the_sink = FlakyTestSink::new(2);
+        let the_payload = Payload::from("patient payload".to_string());
+
+        let the_stopwatch = std::time::Instant::now();
+        drain_with_retry(&mut the_sink, &the_payload, &test_config(3), the_breaker.as_ref(), None)
+            .await
+            .expect("💀 the probe after the cooldown should have landed");
+        assert!(the_stopwatch.elapsed() >= std::time::Duration::from_millis(90), "🧊 the cooldown was skipped");
+        assert_eq!(the_sink.the_survivors, vec!["patient payload".to_string()]);
+    }
+
     #[tokio::test]
     async fn the_one_where_the_drainer_succeeds_on_first_try() {
         // 🧪 No failures — drain_with_retry should succeed immediately, like ordering pizza online
@@ -325,7 +597,7 @@ mod tests {
         let the_payload = Payload::from("test payload".to_string());
         let the_config = test_config(3);
 
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_ok(), "🎯 First-try success should just work");
         assert_eq!(the_sink.the_survivors.len(), 1);
         assert_eq!(the_sink.the_survivors[0], "test payload");
@@ -338,7 +610,7 @@ mod tests {
         let the_payload = Payload::from("persistent payload".to_string());
         let the_config = test_config(3);
 
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_ok(), "🎯 Should succeed after retries");
         assert_eq!(the_sink.the_survivors.len(), 1);
         assert_eq!(the_sink.the_survivors[0], "persistent payload");
@@ -351,7 +623,7 @@ mod tests {
         let the_payload = Payload::from("doomed payload".to_string());
         let the_config = test_config(2);
 
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_err(), "💀 Should fail after exhausting retries");
         let the_error_msg = format!("{}", honestly_who_knows.unwrap_err());
         assert!(the_error_msg.contains("exhausted"), "🎯 Error should mention exhaustion");
@@ -364,7 +636,7 @@ mod tests {
         let the_payload = Payload::from("one shot payload".to_string());
         let the_config = test_config(0);
 
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_err(), "💀 Zero retries = one attempt, one failure, one sadness");
     }
 
@@ -375,7 +647,7 @@ mod tests {
         let the_payload = Payload::from("clutch payload".to_string());
         let the_config = test_config(3);
 
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_ok(), "🎯 Should succeed on the last attempt — main character energy");
         assert_eq!(the_sink.the_survivors[0], "clutch payload");
     }
@@ -388,8 +660,235 @@ mod tests {
         let the_payload = Payload::from(String::new());
         let the_config = test_config(3);
 
-        let honestly_who_knows = drain_with_retry(&mut the_sink, the_payload, &the_config).await;
+        let honestly_who_knows = drain_with_retry(&mut the_sink, &the_payload, &the_config, None, None).await;
         assert!(honestly_who_knows.is_ok(), "🎯 Empty payload still sends successfully");
         assert_eq!(the_sink.the_survivors[0], "");
     }
+
+    /// 🧪 A sink that 413s anything larger than its limit and splits on newlines.
+    /// Like a bouncer with a tape measure. 📏🦆
+    #[derive(Debug)]
+    struct PickyBouncerSink {
+        the_limit: usize,
+        the_survivors: Vec<String>,
+    }
+
+    #[async_trait]
+    impl Sink for PickyBouncerSink {
+        async fn drain(&mut self, payload: Payload) -> Result<()> {
+            if payload.len() > self.the_limit {
+                return Err(anyhow::Error::new(PayloadTooLarge { payload_bytes: payload.len(), body: String::new() }))
+                    .context("💀 wrapped, like the real sinks do — downcast must see through it");
+            }
+            self.the_survivors.push(payload.into_string());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn split_payload(&self, payload: &Payload) -> Option<(Payload, Payload)> {
+            let the_lines: Vec<&str> = payload.split_inclusive('\n').collect();
+            if the_lines.len() < 2 {
+                return None;
+            }
+            let (the_left, the_right) = the_lines.split_at(the_lines.len() / 2);
+            Some((Payload::from(the_left.concat()), Payload::from(the_right.concat())))
+        }
+    }
+
+    fn test_shrinker(the_start: usize) -> PayloadShrinker {
+        PayloadShrinker {
+            flow_knob: Arc::new(AtomicUsize::new(the_start)),
+            ceiling: Arc::new(AtomicUsize::new(the_start)),
+            warnings: RunWarnings::default(),
+        }
+    }
+
+    /// 🧪 413 → halve → halve again → everything lands, in order, and the knobs came down.
+    #[tokio::test]
+    async fn the_one_where_a_413_halves_the_payload_until_it_fits() {
+        let the_line = format!("{}\n", "x".repeat(40_000));
+        let the_payload = Payload::from(the_line.repeat(4));
+        let mut the_sink = PickyBouncerSink { the_limit: 50_000, the_survivors: Vec::new() };
+        let the_shrinker = test_shrinker(1_000_000);
+
+        drain_with_split(&mut the_sink, the_payload, &test_config(3), Some(&the_shrinker), None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(the_sink.the_survivors.len(), 4, "🎯 four lines, four single-line halves");
+        assert!(the_sink.the_survivors.iter().all(|p| *p == the_line));
+        // -- 📉 first 413 at 160_004 bytes → 80_002; second at 80_002 → 64KiB floor
+        assert_eq!(the_shrinker.ceiling.load(Ordering::Relaxed), MIN_SHRUNK_PAYLOAD_BYTES);
+        assert_eq!(the_shrinker.flow_knob.load(Ordering::Relaxed), MIN_SHRUNK_PAYLOAD_BYTES);
+        assert_eq!(the_shrinker.warnings.len(), 2);
+    }
+
+    /// 🧪 413 is not retried with backoff — one attempt, then straight to the scissors.
+    #[tokio::test]
+    async fn the_one_where_a_single_oversized_doc_fails_without_retrying() {
+        let mut the_sink = PickyBouncerSink { the_limit: 10, the_survivors: Vec::new() };
+        let the_payload = Payload::from("this one line is far too long\n".to_string());
+
+        let the_err = drain_with_split(&mut the_sink, the_payload, &test_config(5), None, None, None)
+            .await
+            .unwrap_err();
+        let the_error_msg = format!("{:#}", the_err);
+        assert!(the_error_msg.contains("can't be split"), "🎯 got: {}", the_error_msg);
+        assert!(!the_error_msg.contains("exhausted"), "💀 413 should skip the retry loop entirely");
+    }
+
+    /// 🧪 A sink that lands most of the payload and refuses a doc — every single time.
+    #[derive(Debug, Default)]
+    struct HalfHappySink {
+        the_attempts: usize,
+    }
+
+    #[async_trait]
+    impl Sink for HalfHappySink {
+        async fn drain(&mut self, _payload: Payload) -> Result<()> {
+            self.the_attempts += 1;
+            Err(anyhow::Error::new(DocumentsRejected { rejected: 1, first_reason: "mapper_parsing_exception".to_string() }))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 🧪 Per-doc rejections are not retried — resending would duplicate the docs that landed.
+    #[tokio::test]
+    async fn the_one_where_the_good_docs_are_not_sent_twice() {
+        let mut the_sink = HalfHappySink::default();
+        let the_payload = Payload::from("{\"index\":{}}\n{\"age\":\"old\"}\n".to_string());
+
+        let the_err = drain_with_retry(&mut the_sink, &the_payload, &test_config(5), None, None).await.unwrap_err();
+        assert!(the_err.downcast_ref::<DocumentsRejected>().is_some());
+        assert_eq!(the_sink.the_attempts, 1, "💀 a retry would have re-indexed the docs that already landed");
+    }
+
+    /// 🧪 A sink that 429s twice, then relents — and one that fails for boring reasons.
+    #[derive(Debug, Default)]
+    struct BusyClusterSink {
+        the_attempts: usize,
+        the_boring: bool,
+    }
+
+    #[async_trait]
+    impl Sink for BusyClusterSink {
+        async fn drain(&mut self, _payload: Payload) -> Result<()> {
+            self.the_attempts += 1;
+            match (self.the_boring, self.the_attempts) {
+                (true, 1) => anyhow::bail!("💀 connection reset"),
+                (false, 1..=2) => Err(anyhow::Error::new(SinkThrottled { body: "es_rejected_execution_exception".to_string() }))
+                    .context("💀 wrapped, like the real sinks do"),
+                _ => Ok(()),
+            }
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 🧪 Each 429 tells the FlowMaster before the retry; other failures don't.
+    #[tokio::test]
+    async fn the_one_where_the_drainer_tattles_about_the_429s() {
+        let (the_gauge_tx, the_gauge_rx) = async_channel::bounded(16);
+        let the_payload = Payload::from("patience".to_string());
+
+        let mut the_busy_sink = BusyClusterSink::default();
+        drain_with_retry(&mut the_busy_sink, &the_payload, &test_config(3), None, Some(&the_gauge_tx)).await.unwrap();
+        assert_eq!(the_busy_sink.the_attempts, 3, "🔄 429s are still retried");
+        assert!(matches!(the_gauge_rx.try_recv(), Ok(GaugeReading::Error())));
+        assert!(matches!(the_gauge_rx.try_recv(), Ok(GaugeReading::Error())));
+        assert!(the_gauge_rx.is_empty());
+
+        let mut the_flaky_sink = BusyClusterSink { the_boring: true, ..Default::default() };
+        drain_with_retry(&mut the_flaky_sink, &the_payload, &test_config(3), None, Some(&the_gauge_tx)).await.unwrap();
+        assert!(the_gauge_rx.is_empty(), "🤷 a reset connection isn't the cluster asking us to slow down");
+    }
+
+    /// 🧪 Counts what it drains, optionally napping first — one slow lane, one fast lane.
+    #[derive(Debug)]
+    struct TallySink {
+        the_nap: std::time::Duration,
+        the_tally: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Sink for TallySink {
+        async fn drain(&mut self, _payload: Payload) -> Result<()> {
+            tokio::time::sleep(self.the_nap).await;
+            self.the_tally.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 🧪 Drainers pull from ch2 when they're free, so a slow bulk request holds up one
+    /// payload — its own — and the idle drainer takes everything else.
+    #[tokio::test]
+    async fn the_one_where_the_slow_lane_does_not_hold_up_traffic() {
+        let (the_tx, the_rx) = async_channel::bounded::<Payload>(2);
+        let the_slow_tally = Arc::new(AtomicUsize::new(0));
+        let the_fast_tally = Arc::new(AtomicUsize::new(0));
+        let the_metrics = Arc::new(DrainMetrics::new());
+        let mut the_handles = Vec::new();
+        for (the_nap_ms, the_tally) in [(300, &the_slow_tally), (0, &the_fast_tally)] {
+            let the_sink = TallySink { the_nap: std::time::Duration::from_millis(the_nap_ms), the_tally: the_tally.clone() };
+            let the_drainer = Drainer::new(the_rx.clone(), SinkBackend::Custom(Box::new(the_sink)), test_config(0), None, the_metrics.clone());
+            the_handles.push(the_drainer.start());
+        }
+        drop(the_rx);
+
+        for i in 0..20 {
+            the_tx.send(Payload::from(format!("payload {}", i))).await.unwrap();
+        }
+        drop(the_tx);
+        for the_handle in the_handles {
+            the_handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(the_slow_tally.load(Ordering::SeqCst) + the_fast_tally.load(Ordering::SeqCst), 20);
+        assert!(the_slow_tally.load(Ordering::SeqCst) <= 2, "🐢 the slow drainer should only have held what it grabbed first");
+    }
+
+    /// 🧪 A payload the last run journaled but never saw land goes first; everything that lands
+    /// is cleared from the log, and a payload the sink refuses stays for next time.
+    #[tokio::test]
+    async fn the_one_where_the_journal_replays_the_last_run() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_wal = WriteAheadLog::open(the_dir.path())?;
+        the_wal.journal(&Payload::from("from the crash".to_string())).await?;
+        let the_sink = crate::InMemorySink::new().await?;
+        let the_received = the_sink.received.clone();
+
+        let (the_tx, the_rx) = async_channel::bounded::<Payload>(2);
+        the_tx.send(Payload::from("fresh".to_string())).await?;
+        drop(the_tx);
+        let the_backlog = the_wal.pending()?;
+        Drainer::new(the_rx, SinkBackend::InMemory(the_sink), test_config(0), None, Arc::new(DrainMetrics::new()))
+            .with_write_ahead_log(Some(the_wal.clone()), the_backlog)
+            .start()
+            .await??;
+        assert_eq!(*the_received.lock().await, vec!["from the crash".to_string(), "fresh".to_string()]);
+        assert!(the_wal.pending()?.is_empty(), "📼 everything landed, nothing to replay");
+
+        let (the_tx, the_rx) = async_channel::bounded::<Payload>(1);
+        the_tx.send(Payload::from("doomed".to_string())).await?;
+        drop(the_tx);
+        let the_outcome = Drainer::new(the_rx, SinkBackend::Custom(Box::new(AlwaysFailSink)), test_config(0), None, Arc::new(DrainMetrics::new()))
+            .with_write_ahead_log(Some(the_wal.clone()), Vec::new())
+            .start()
+            .await?;
+        assert!(the_outcome.is_err());
+        assert_eq!(the_wal.pending()?.len(), 1, "📼 never acknowledged, so it waits for the next run");
+        Ok(())
+    }
 }
@@ -0,0 +1,150 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 🧯 The error budget — how many failed docs a run may shrug off before it gives up.
+//!
+//! 🎬 *[a mapping changed upstream. every doc now has `price: "N/A"`. Elasticsearch refuses each one.]*
+//! *[the sink logs it, dead-letters it, carries on. politely. for six hours.]*
+//! *[the run ends green. the index is empty. the dead letter file is 40 GB.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `[runtime] max_errors` (a count) and/or `max_error_ratio` (0.0–1.0). Neither = no limit
+//! - A failed doc: one Elasticsearch refused inside a 2xx `_bulk` (the sink adds to
+//!   `AppConfig::failed_docs` as it happens), or one a transform rejected as invalid
+//!   (`TransformChain::failed_docs`). Docs dropped on purpose — `Filter`, `Sample`, `Dedup` — aren't failures
+//! - Ratio = failed / (docs cast + transform failures). It waits for `MIN_RATIO_SAMPLE` docs,
+//!   so the first bad doc isn't 100%, then gets one last look when the run ends
+//! - The Foreman spawns `watch`. Over budget → pull the `ShutdownLever`: pumpers stop,
+//!   whatever is in flight drains, and the run fails with the verdict 🦆
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::error;
+
+use super::ShutdownLever;
+
+/// 🧮 Docs a sink couldn't write, counted as they fail. Same Arc-atomic pattern as `ShutdownLever`;
+/// rides on `AppConfig::failed_docs` (builder-only) so every sink adds to one tally.
+pub type FailedDocs = Arc<AtomicU64>;
+
+/// 🔢 Below this many docs, a ratio says more about luck than about the data
+pub const MIN_RATIO_SAMPLE: u64 = 1_000;
+
+/// ⏱️ How often the watcher looks at the tally
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 🧯 `max_errors` / `max_error_ratio`, checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorBudget {
+    max_errors: Option<u64>,
+    max_error_ratio: Option<f64>,
+}
+
+impl ErrorBudget {
+    /// 🏗️ `None` when neither limit is set. A ratio outside 0.0–1.0 is a config mistake.
+    pub fn new(max_errors: Option<u64>, max_error_ratio: Option<f64>) -> Result<Option<Self>> {
+        if let Some(the_ratio) = max_error_ratio
+            && !(0.0..=1.0).contains(&the_ratio)
+        {
+            anyhow::bail!("💀 runtime.max_error_ratio is a fraction of docs, 0.0 to 1.0 — got {}", the_ratio);
+        }
+        Ok((max_errors.is_some() || max_error_ratio.is_some()).then_some(Self { max_errors, max_error_ratio }))
+    }
+
+    /// ⚖️ Why the run should stop, or `None` if it's within budget. `is_final` drops the
+    /// minimum sample — at the end of the run, the docs seen are all the docs there are.
+    pub fn verdict(&self, failed: u64, seen: u64, is_final: bool) -> Option<String> {
+        if let Some(the_max) = self.max_errors
+            && failed > the_max
+        {
+            return Some(format!("{} docs failed, more than max_errors = {}", failed, the_max));
+        }
+        if let Some(the_max) = self.max_error_ratio
+            && seen > 0
+            && (is_final || seen >= MIN_RATIO_SAMPLE)
+        {
+            let the_ratio = failed as f64 / seen as f64;
+            if the_ratio > the_max {
+                return Some(format!(
+                    "{} of {} docs failed ({:.2}%), more than max_error_ratio = {}",
+                    failed,
+                    seen,
+                    the_ratio * 100.0,
+                    the_max
+                ));
+            }
+        }
+        None
+    }
+
+    /// 👀 Watch `tally` (→ failed, seen) until `stop` fires. Over budget: pull `shutdown` and
+    /// return the verdict. One last check once `stop` fires, for the ratio.
+    pub fn watch<F>(self, tally: F, shutdown: ShutdownLever, stop: CancellationToken) -> JoinHandle<Option<String>>
+    where
+        F: Fn() -> (u64, u64) + Send + 'static,
+    {
+        super::spawn_named("error-budget", async move {
+            loop {
+                let is_final = tokio::select! {
+                    _ = stop.cancelled() => true,
+                    _ = tokio::time::sleep(WATCH_INTERVAL) => false,
+                };
+                let (the_failed, the_seen) = tally();
+                if let Some(the_verdict) = self.verdict(the_failed, the_seen, is_final) {
+                    if !is_final {
+                        error!("🧯 Error budget spent: {} — stopping the source and draining what's in flight", the_verdict);
+                        shutdown.store(true, Ordering::Relaxed);
+                    }
+                    return Some(the_verdict);
+                }
+                if is_final {
+                    return None;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_one_where_the_budget_has_a_ceiling_and_a_percentage() -> Result<()> {
+        assert_eq!(ErrorBudget::new(None, None)?, None, "🕊️ no limits, no watcher");
+        assert!(ErrorBudget::new(None, Some(1.5)).is_err());
+
+        let the_count = ErrorBudget::new(Some(3), None)?.expect("a budget");
+        assert_eq!(the_count.verdict(3, 10, false), None, "🎯 at the limit is still fine");
+        assert!(the_count.verdict(4, 10, false).is_some());
+
+        let the_ratio = ErrorBudget::new(None, Some(0.01))?.expect("a budget");
+        assert_eq!(the_ratio.verdict(1, 1, false), None, "🔢 one doc in, the ratio means nothing yet");
+        assert!(the_ratio.verdict(1, 1, true).is_some(), "🏁 but the end of the run is the whole sample");
+        assert_eq!(the_ratio.verdict(10, 1_000, false), None);
+        assert!(the_ratio.verdict(11, 1_000, false).is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn the_one_where_the_watcher_pulls_the_lever() -> Result<()> {
+        let the_failed: FailedDocs = Arc::default();
+        let the_lever = ShutdownLever::default();
+        let the_stop = CancellationToken::new();
+        let the_budget = ErrorBudget::new(Some(2), None)?.expect("a budget");
+        let the_tally = the_failed.clone();
+        let the_watcher = the_budget.watch(move || (the_tally.load(Ordering::Relaxed), 100), the_lever.clone(), the_stop);
+
+        the_failed.store(3, Ordering::Relaxed);
+        let the_verdict = the_watcher.await?;
+        assert!(the_verdict.is_some_and(|v| v.contains("max_errors = 2")));
+        assert!(the_lever.load(Ordering::Relaxed), "🛑 the source was told to stop");
+        Ok(())
+    }
+}
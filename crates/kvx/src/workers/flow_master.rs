@@ -48,6 +48,8 @@ pub struct FlowMaster {
     regulator: Regulators,
     /// 🔧 The shared atomic valve — Joiners read this to size their payloads
     the_flow_knob: FlowKnob,
+    /// 🧱 Optional hard cap — lowered by drainers after a 413, so regulation never climbs back into it
+    the_ceiling: Option<FlowKnob>,
 }
 
 impl FlowMaster {
@@ -64,13 +66,20 @@ impl FlowMaster {
             rx,
             regulator,
             the_flow_knob,
+            the_ceiling: None,
         }
     }
+
+    /// 🧱 Clamp every regulated value to this shared ceiling (see `PayloadShrinker`).
+    pub fn with_ceiling(mut self, the_ceiling: FlowKnob) -> Self {
+        self.the_ceiling = Some(the_ceiling);
+        self
+    }
 }
 
 impl Worker for FlowMaster {
     fn start(mut self) -> JoinHandle<Result<()>> {
-        tokio::spawn(async move {
+        super::spawn_named("flow-master", async move {
             info!("🎛️ FlowMaster online — listening for gauge readings, regulating the flow");
             let mut the_last_time_we_checked = SystemTime::now();
 
@@ -88,6 +97,12 @@ impl Worker for FlowMaster {
 
                         the_last_time_we_checked = SystemTime::now();
 
+                        // 🧱 A 413 taught us where the wall is — don't regulate back into it
+                        let the_new_flow = match &self.the_ceiling {
+                            Some(ceiling) => the_new_flow.min(ceiling.load(Ordering::Relaxed) as f64),
+                            None => the_new_flow,
+                        };
+
                         // 🔧 Store the regulated output to the FlowKnob — Joiners will pick it up
                         let the_old_flow = self.the_flow_knob.swap(
                             the_new_flow as usize,
@@ -29,12 +29,15 @@
 //!
 //! ⚠️ The singularity will parse JSON in constant time. Until then, we have threads.
 
-use crate::{Entry, Page, Payload};
-use crate::casts::{Caster, PageToEntriesCaster};
+use crate::{Entry, Page, PageOrigin, Payload};
+use crate::casts::{Caster, DocPosition, PageToEntriesCaster};
 use crate::manifolds::{Manifold, ManifoldBackend};
+use super::{BufferBudget, PayloadPool, SourcedPage};
+use crate::progress::DrainMetrics;
 use crate::regulators::pressure_gauge::FlowKnob;
 use anyhow::{Context, Result};
 use async_channel::{Receiver, Sender};
+use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use tracing::debug;
 use std::collections::VecDeque;
@@ -65,7 +68,7 @@ const BUFFER_EPSILON_BYTES: usize = 64 * 1024;
 pub struct Joiner {
     /// 📥 ch1 receiver — raw feeds from the pumper, delivered fresh like morning newspapers
     /// except the news is JSON and the paperboy is async_channel
-    rx: Receiver<Page>,
+    rx: Receiver<SourcedPage>,
     /// 📤 ch2 sender — assembled payloads dispatched to drainers like care packages
     /// to the I/O frontlines
     tx: Sender<Payload>,
@@ -80,6 +83,12 @@ pub struct Joiner {
     /// When no regulator is active, it stays at the initial max_request_size_bytes forever.
     /// Like a volume knob that someone else might be turning while you're listening. 🎚️
     the_throttle_knob: FlowKnob,
+    /// 🔢 Where cast docs get counted for the progress bar — `None` in tests that don't care
+    drain_metrics: Option<Arc<DrainMetrics>>,
+    /// 🧮 Optional byte budget — pages arrive charged, payloads leave charged
+    budget: Option<BufferBudget>,
+    /// ♻️ Optional shelf of used payload buffers to compose into, instead of allocating each flush
+    payload_pool: Option<PayloadPool>,
     entries_buffer: VecDeque<Entry>,
     the_running_byte_tab: usize
 }
@@ -90,7 +99,7 @@ impl Joiner {
     /// "Give a joiner a feed, it processes for a millisecond.
     ///  Give a joiner a channel, it processes until the pumper dies." — Ancient proverb 🧵
     pub fn new(
-        rx: Receiver<Page>,
+        rx: Receiver<SourcedPage>,
         tx: Sender<Payload>,
         caster: PageToEntriesCaster,
         manifold: ManifoldBackend,
@@ -102,11 +111,45 @@ impl Joiner {
             caster,
             manifold,
             the_throttle_knob,
+            drain_metrics: None,
+            budget: None,
+            payload_pool: None,
             entries_buffer : VecDeque::new(),
             the_running_byte_tab: 0,
         }
     }
 
+    /// 🔢 Count every cast doc into the shared progress metrics.
+    /// The joiner is the first place a page becomes individual docs, so it's the one who counts.
+    pub fn with_drain_metrics(mut self, drain_metrics: Arc<DrainMetrics>) -> Self {
+        self.drain_metrics = Some(drain_metrics);
+        self
+    }
+
+    /// 🧮 Move budget charges along as pages become buffered entries and then payloads.
+    pub fn with_buffer_budget(mut self, budget: Option<BufferBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// ♻️ Compose payloads into buffers from a shared pool, which drainers refill.
+    pub fn with_payload_pool(mut self, payload_pool: PayloadPool) -> Self {
+        self.payload_pool = Some(payload_pool);
+        self
+    }
+
+    /// 🎼 Join the buffered entries — into a pooled buffer when there's a pool.
+    fn compose(&mut self) -> Result<Payload> {
+        let Some(ref the_pool) = self.payload_pool else {
+            return self.manifold.join(&mut self.entries_buffer);
+        };
+        let mut the_buffer = the_pool.take();
+        let the_payload = self.manifold.join_with(&mut self.entries_buffer, &mut the_buffer);
+        // -- ♻️ untouched if the manifold didn't need it (NDJSON's one-entry fast path)
+        the_pool.give_back(the_buffer);
+        the_payload
+    }
+
     /// 🚀 Spawn this joiner on a dedicated OS thread.
     ///
     /// Returns `std::thread::JoinHandle` (NOT tokio::task::JoinHandle) because
@@ -117,30 +160,53 @@ impl Joiner {
     /// 🧠 The thread runs until ch1 closes (pumper done), then flushes remaining
     /// buffered feeds and drops tx (which helps close ch2 when all joiners finish).
     pub fn start(mut self) -> std::thread::JoinHandle<Result<()>> {
-        std::thread::spawn(move || {
+        // -- 🏷️ named, so a panic, a debugger or `top -H` says "kvx-joiner" instead of "<unnamed>"
+        let the_thread = std::thread::Builder::new().name("kvx-joiner".to_string());
+        the_thread.spawn(move || {
             debug!("🧵 Joiner thread started — recv_blocking → buffer → join → send_blocking");
 
             loop {
                 match self.rx.recv_blocking() {
-                    Ok(page) => {
+                    Ok((page, the_origin)) => {
                         // 📜 Page arrives → cast into entries → buffer → flush when full
-                        let entries = self.caster.cast(page).context("💀 Caster failed — the data fought back")?;
+                        let the_page_bytes = page.len();
+                        let the_buffered_before = self.the_running_byte_tab;
+                        let entries = self
+                            .caster
+                            .cast(page)
+                            .map_err(|the_error| pin_to_origin(the_error, the_origin.as_ref()))
+                            .context("💀 Caster failed — the data fought back")?;
+                        if let Some(ref the_metrics) = self.drain_metrics {
+                            the_metrics.record_docs(entries.len() as u64);
+                        }
                         for entry in entries {
                             self.the_running_byte_tab += entry.len();
                             self.entries_buffer.push_back(entry);
 
                             let the_ceiling = self.the_throttle_knob.load(Ordering::Relaxed).saturating_sub(BUFFER_EPSILON_BYTES);
                             if self.the_running_byte_tab > the_ceiling {
-                                let the_payload = self.manifold.join(&mut self.entries_buffer)?;
+                                let the_payload = self.compose()?;
+                                if let Some(ref the_budget) = self.budget {
+                                    the_budget.charge(the_payload.len());
+                                }
                                 self.tx.send_blocking(the_payload).context("💀 ch2 closed — the drainers left without saying goodbye")?;
                                 self.the_running_byte_tab = 0;
                             }
                         }
+                        // 🧮 The page is gone; what's left of it sits in our buffer (or already left as payloads)
+                        if let Some(ref the_budget) = self.budget {
+                            the_budget.charge(self.the_running_byte_tab);
+                            the_budget.release(the_page_bytes + the_buffered_before);
+                        }
                     }
                     Err(_) => {
                         // 🏁 Channel closed — flush whatever's left in the buffer
                         if !self.entries_buffer.is_empty() {
-                            let the_payload = self.manifold.join(&mut self.entries_buffer)?;
+                            let the_payload = self.compose()?;
+                            if let Some(ref the_budget) = self.budget {
+                                the_budget.charge(the_payload.len());
+                                the_budget.release(self.the_running_byte_tab);
+                            }
                             self.tx.send_blocking(the_payload).context("💀 ch2 closed during final flush — so close, yet so far")?;
                         }
                         // tx drops here naturally — when all joiners drop their tx,
@@ -150,6 +216,20 @@ impl Joiner {
                 }
             }
         })
+        .expect("💀 The OS wouldn't give us a joiner thread. Out of threads, or out of patience.")
+    }
+}
+
+/// 📍 A cast error that says which doc broke (`DocPosition`) on a page that says where it came
+/// from (`PageOrigin`) becomes "'file' line N, byte offset M". Anything else passes through as-is.
+fn pin_to_origin(the_error: anyhow::Error, the_origin: Option<&PageOrigin>) -> anyhow::Error {
+    let the_place = the_error.downcast_ref::<DocPosition>().zip(the_origin).and_then(|(the_position, the_origin)| {
+        let (the_line, the_byte) = the_origin.locate(the_position.index, the_position.page_offset)?;
+        Some(format!("'{}' line {}, byte offset {}", the_origin.source, the_line, the_byte))
+    });
+    match the_place {
+        Some(the_place) => the_error.context(the_place),
+        None => the_error,
     }
 }
 
@@ -173,7 +253,7 @@ mod tests {
     /// and the bottle is a String. 🦆
     #[test]
     fn the_one_where_a_feed_survives_the_joiner_thread() {
-        let (tx1, rx1) = async_channel::bounded::<Page>(10);
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
         let (tx2, rx2) = async_channel::bounded::<Payload>(10);
 
         let joiner = Joiner::new(
@@ -189,12 +269,12 @@ mod tests {
         let the_joiner_thread = joiner.start();
 
         // 📤 Send one feed, then close ch1 to trigger final flush
-        tx1.send_blocking(Page(r#"{"doc":1}"#.to_string())).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":1}"#.to_string()), None)).unwrap();
         tx1.close();
 
         // 📥 The joiner should have flushed and sent a JSON array payload to ch2
         let the_payload = rx2.recv_blocking().unwrap();
-        assert_eq!(*the_payload, r#"[{"doc":1}]"#, "🎯 Joiner should produce a JSON array wrapping the feed");
+        assert_eq!(the_payload, r#"[{"doc":1}]"#, "🎯 Joiner should produce a JSON array wrapping the feed");
 
         // 🧵 Thread should exit cleanly after ch1 closes
         the_joiner_thread
@@ -208,7 +288,7 @@ mod tests {
     /// like a lazy postman. 📬
     #[test]
     fn the_one_where_multiple_feeds_become_one_payload() {
-        let (tx1, rx1) = async_channel::bounded::<Page>(10);
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
         let (tx2, rx2) = async_channel::bounded::<Payload>(10);
 
         let joiner = Joiner::new(
@@ -222,9 +302,9 @@ mod tests {
         let the_joiner_thread = joiner.start();
 
         // 📤 Send three feeds, close ch1
-        tx1.send_blocking(Page(r#"{"doc":1}"#.to_string())).unwrap();
-        tx1.send_blocking(Page(r#"{"doc":2}"#.to_string())).unwrap();
-        tx1.send_blocking(Page(r#"{"doc":3}"#.to_string())).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":1}"#.to_string()), None)).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":2}"#.to_string()), None)).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":3}"#.to_string()), None)).unwrap();
         tx1.close();
 
         // 📥 All three should arrive as one JSON array payload
@@ -242,7 +322,7 @@ mod tests {
     /// Like a toilet with a sensitive flush sensor. Crude but accurate. 🚽🦆
     #[test]
     fn the_one_where_buffer_flushes_before_channel_closes() {
-        let (tx1, rx1) = async_channel::bounded::<Page>(10);
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
         let (tx2, rx2) = async_channel::bounded::<Payload>(10);
 
         // 📏 Set max_request_size_bytes so small that even one feed triggers a flush
@@ -260,16 +340,16 @@ mod tests {
         let the_joiner_thread = joiner.start();
 
         // 📤 Send two feeds — each should flush independently due to tiny max
-        tx1.send_blocking(Page(r#"{"doc":"first"}"#.to_string())).unwrap();
-        tx1.send_blocking(Page(r#"{"doc":"second"}"#.to_string())).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":"first"}"#.to_string()), None)).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":"second"}"#.to_string()), None)).unwrap();
         tx1.close();
 
         // 📥 Should get two separate payloads (one per flush)
         let payload_one = rx2.recv_blocking().unwrap();
         let payload_two = rx2.recv_blocking().unwrap();
 
-        assert_eq!(*payload_one, r#"[{"doc":"first"}]"#, "🎯 First feed should flush on its own");
-        assert_eq!(*payload_two, r#"[{"doc":"second"}]"#, "🎯 Second feed should flush on its own");
+        assert_eq!(payload_one, r#"[{"doc":"first"}]"#, "🎯 First feed should flush on its own");
+        assert_eq!(payload_two, r#"[{"doc":"second"}]"#, "🎯 Second feed should flush on its own");
 
         the_joiner_thread.join().unwrap().unwrap();
     }
@@ -278,7 +358,7 @@ mod tests {
     /// The joiner receives nothing. It sends nothing. It is at peace. 🧘
     #[test]
     fn the_one_where_no_feeds_means_no_payloads() {
-        let (tx1, rx1) = async_channel::bounded::<Page>(10);
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
         let (tx2, rx2) = async_channel::bounded::<Payload>(10);
 
         let joiner = Joiner::new(
@@ -302,13 +382,76 @@ mod tests {
         );
     }
 
+    /// 🧪 The one where the budget follows the bytes: pages charged going in, payloads charged
+    /// going out, and nothing left over once every payload is handed back. 🧮
+    #[test]
+    fn the_one_where_the_joiner_keeps_honest_books() {
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
+        let (tx2, rx2) = async_channel::bounded::<Payload>(10);
+        let the_budget = BufferBudget::new(usize::MAX);
+
+        let joiner = Joiner::new(
+            rx1,
+            tx2,
+            PageToEntriesCaster::Passthrough(passthrough::Passthrough),
+            ManifoldBackend::JsonArray(JsonArrayManifold),
+            knob(BUFFER_EPSILON_BYTES + 20),
+        )
+        .with_buffer_budget(Some(the_budget.clone()));
+        let the_joiner_thread = joiner.start();
+
+        for the_page in [r#"{"doc":"one"}"#, r#"{"doc":"two"}"#, r#"{"doc":"three, which tips it over"}"#] {
+            the_budget.charge(the_page.len());
+            tx1.send_blocking((Page(the_page.to_string()), None)).unwrap();
+        }
+        tx1.close();
+        the_joiner_thread.join().unwrap().unwrap();
+
+        let mut the_payload_bytes = 0;
+        while let Ok(the_payload) = rx2.try_recv() {
+            the_payload_bytes += the_payload.len();
+        }
+        assert_eq!(the_budget.in_flight(), the_payload_bytes, "📦 only payloads are still charged");
+        the_budget.release(the_payload_bytes);
+        assert_eq!(the_budget.in_flight(), 0);
+    }
+
+    /// 🧪 The one where the joiner composes into a recycled buffer instead of a new one. ♻️
+    #[test]
+    fn the_one_where_the_joiner_shops_secondhand() {
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
+        let (tx2, rx2) = async_channel::bounded::<Payload>(10);
+        let the_pool = PayloadPool::new(4);
+        let the_used_buffer = String::with_capacity(1024);
+        let the_address = the_used_buffer.as_ptr();
+        the_pool.give_back(the_used_buffer);
+
+        let joiner = Joiner::new(
+            rx1,
+            tx2,
+            PageToEntriesCaster::Passthrough(passthrough::Passthrough),
+            ManifoldBackend::JsonArray(JsonArrayManifold),
+            knob(usize::MAX),
+        )
+        .with_payload_pool(the_pool.clone());
+        let the_joiner_thread = joiner.start();
+        tx1.send_blocking((Page(r#"{"doc":1}"#.to_string()), None)).unwrap();
+        tx1.close();
+        the_joiner_thread.join().unwrap().unwrap();
+
+        let the_payload = rx2.recv_blocking().unwrap();
+        assert_eq!(the_payload, r#"[{"doc":1}]"#);
+        assert_eq!(the_payload.as_ptr(), the_address, "♻️ composed into the pooled buffer");
+        assert_eq!(the_pool.stats(), (1, 0));
+    }
+
     /// 🧪 The one where the FlowKnob changes mid-stream and the joiner adapts.
     /// Proof that the Arc<AtomicUsize> actually does something useful, not just
     /// sitting there looking atomic. Like a thermostat that someone turns down
     /// while you're cooking — the kitchen gets colder. 🌡️🦆
     #[test]
     fn the_one_where_the_flow_knob_changes_mid_flight() {
-        let (tx1, rx1) = async_channel::bounded::<Page>(10);
+        let (tx1, rx1) = async_channel::bounded::<SourcedPage>(10);
         let (tx2, rx2) = async_channel::bounded::<Payload>(10);
 
         // 📏 Start with a huge knob — nothing flushes until channel close
@@ -326,13 +469,13 @@ mod tests {
         let the_joiner_thread = joiner.start();
 
         // 📤 Send first feed — won't flush yet (knob is huge)
-        tx1.send_blocking(Page(r#"{"doc":"before"}"#.to_string())).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":"before"}"#.to_string()), None)).unwrap();
 
         // 🔧 Now crank the knob down so small that the NEXT feed triggers a flush
         the_knob_clone.store(BUFFER_EPSILON_BYTES + 5, Ordering::Relaxed);
 
         // 📤 Send second feed — should trigger flush due to lowered knob
-        tx1.send_blocking(Page(r#"{"doc":"after"}"#.to_string())).unwrap();
+        tx1.send_blocking((Page(r#"{"doc":"after"}"#.to_string()), None)).unwrap();
 
         // 📥 First payload should arrive (both feeds flushed together when threshold hit)
         let the_first_payload = rx2.recv_blocking().unwrap();
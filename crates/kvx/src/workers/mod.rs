@@ -24,11 +24,27 @@ use tokio::task::JoinHandle;
 mod config;
 pub use config::DrainerConfig;
 pub use config::FlowMasterConfig;
+pub use config::CircuitBreakerConfig;
+
+mod buffer_budget;
+pub use buffer_budget::BufferBudget;
+
+mod payload_pool;
+pub use payload_pool::PayloadPool;
+
+mod circuit_breaker;
+pub use circuit_breaker::CircuitBreaker;
+
+mod error_budget;
+pub use error_budget::{ErrorBudget, FailedDocs};
+
+mod write_ahead_log;
+pub use write_ahead_log::WriteAheadLog;
 
 mod drainer;
-pub use drainer::Drainer;
+pub use drainer::{Drainer, PayloadShrinker};
 mod pumper;
-pub use pumper::Pumper;
+pub use pumper::{PauseSwitch, Pumper, ShutdownLever, SourcedPage};
 mod joiner;
 pub use joiner::Joiner;
 mod flow_master;
@@ -39,3 +55,29 @@ pub trait Worker {
     /// 🚀 Start the worker.
     fn start(self) -> JoinHandle<Result<()>>;
 }
+
+/// 🔭 `tokio::spawn`, with a name tokio-console can show for the task.
+///
+/// The name only sticks in `console` builds compiled with `--cfg tokio_unstable` — tokio's
+/// task builder doesn't exist otherwise. Everywhere else this is plain `tokio::spawn`.
+#[cfg(all(feature = "console", tokio_unstable))]
+pub(crate) fn spawn_named<F>(the_name: &str, the_future: F) -> JoinHandle<F::Output>
+where
+    F: std::future::Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::task::Builder::new()
+        .name(the_name)
+        .spawn(the_future)
+        .expect("💀 tokio refused to spawn a task — the runtime is gone, and us with it")
+}
+
+/// 🔭 `tokio::spawn` — the name is for tokio-console, and this build has no console.
+#[cfg(not(all(feature = "console", tokio_unstable)))]
+pub(crate) fn spawn_named<F>(_the_name: &str, the_future: F) -> JoinHandle<F::Output>
+where
+    F: std::future::Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(the_future)
+}
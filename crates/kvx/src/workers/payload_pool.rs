@@ -0,0 +1,119 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! ♻️ The payload pool — payload buffers go back on the shelf instead of back to the allocator.
+//!
+//! 🎬 *[a joiner allocates 10 MiB. fills it. ships it.]*
+//! *[a drainer sends it. drops it. the allocator takes it back.]*
+//! *[a joiner allocates 10 MiB.]*
+//! *[this happens four hundred thousand times. the allocator starts to resent us.]*
+//!
+//! 🧠 Knowledge graph:
+//! - Joiner: `take()` a buffer, `Manifold::join_with` composes into it, `give_back()` whatever
+//!   the manifold didn't use (the NDJSON one-entry fast path moves the entry in instead)
+//! - Drainer: once a payload has landed, `reclaim(payload)` — if nobody else still holds a
+//!   piece of it, the `Bytes` turns back into its `String` and goes on the shelf, emptied
+//! - A shared payload (a dead-letter copy still alive, a 413 half) just drops. No copy to rescue it
+//! - The shelf holds at most `the_limit` buffers — roughly what can be in flight at once.
+//!   Past that, returned buffers are freed, so an idle pool can't hoard memory 🦆
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::Payload;
+
+#[derive(Debug, Default)]
+struct PoolCounters {
+    /// ♻️ `take()` calls served from the shelf
+    reused: AtomicU64,
+    /// 🆕 `take()` calls that found the shelf empty
+    fresh: AtomicU64,
+}
+
+/// ♻️ Shared shelf of empty payload buffers. Cheap to clone; clones share the shelf.
+#[derive(Debug, Clone)]
+pub struct PayloadPool {
+    the_shelf: Arc<Mutex<Vec<String>>>,
+    the_limit: usize,
+    counters: Arc<PoolCounters>,
+}
+
+impl PayloadPool {
+    /// 🏗️ A pool that keeps at most `the_limit` idle buffers.
+    pub fn new(the_limit: usize) -> Self {
+        Self {
+            the_shelf: Arc::new(Mutex::new(Vec::with_capacity(the_limit))),
+            the_limit,
+            counters: Arc::default(),
+        }
+    }
+
+    /// 📤 An empty buffer — a used one if the shelf has any, else a new (unallocated) one.
+    pub fn take(&self) -> String {
+        match self.the_shelf.lock().expect("💀 payload pool lock poisoned").pop() {
+            Some(the_buffer) => {
+                self.counters.reused.fetch_add(1, Ordering::Relaxed);
+                the_buffer
+            }
+            None => {
+                self.counters.fresh.fetch_add(1, Ordering::Relaxed);
+                String::new()
+            }
+        }
+    }
+
+    /// 📥 Put a buffer back on the shelf, emptied. Unallocated ones and overflow are dropped.
+    pub fn give_back(&self, mut the_buffer: String) {
+        if the_buffer.capacity() == 0 {
+            return;
+        }
+        the_buffer.clear();
+        let mut the_shelf = self.the_shelf.lock().expect("💀 payload pool lock poisoned");
+        if the_shelf.len() < self.the_limit {
+            the_shelf.push(the_buffer);
+        }
+    }
+
+    /// ♻️ Shelve a landed payload's buffer, if the payload was the last one holding it.
+    pub fn reclaim(&self, the_payload: Payload) {
+        if let Some(the_buffer) = the_payload.try_reclaim() {
+            self.give_back(the_buffer);
+        }
+    }
+
+    /// 📊 `(reused, fresh)` — how many `take()`s the shelf served, and how many it couldn't.
+    pub fn stats(&self) -> (u64, u64) {
+        (self.counters.reused.load(Ordering::Relaxed), self.counters.fresh.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 🧪 A landed payload's buffer comes back around; a shared one doesn't, and the shelf has a limit.
+    #[test]
+    fn the_one_where_the_same_buffer_comes_back_around() {
+        let the_pool = PayloadPool::new(1);
+        let mut the_buffer = the_pool.take();
+        the_buffer.push_str("{\"doc\":1}\n");
+        let the_address = the_buffer.as_ptr();
+
+        the_pool.reclaim(Payload::from(the_buffer));
+        let the_second = the_pool.take();
+        assert_eq!(the_second.as_ptr(), the_address, "♻️ same allocation");
+        assert!(the_second.is_empty(), "🧹 and emptied");
+        assert_eq!(the_pool.stats(), (1, 1));
+
+        let the_payload = Payload::from("{\"doc\":2}\n".repeat(4));
+        let the_dead_letter_copy = the_payload.clone();
+        the_pool.reclaim(the_payload);
+        assert_eq!(the_pool.take().capacity(), 0, "🤝 still shared — nothing shelved");
+        drop(the_dead_letter_copy);
+
+        the_pool.give_back(String::with_capacity(8));
+        the_pool.give_back(String::with_capacity(8));
+        assert_eq!(the_pool.the_shelf.lock().unwrap().len(), 1, "🧱 the shelf has a limit");
+    }
+}
@@ -15,18 +15,53 @@
 //! Source returns one raw feed per call. Drainer buffers feeds by byte size,
 //! then flushes via Manifold (cast + join). The source is maximally ignorant.
 //!
+//! 🎟️ Sources arrive through a [`SourceQueue`], not one apiece: a pumper pumps a source to
+//! EOF, then takes the next in line. Usually the line is one source long. For an ES sliced
+//! read or a file pattern it's longer, and `runtime.source_parallelism` pumpers share it.
+//!
 //! 🦆 (same duck, different file, same vibe)
 //!
 //! ⚠️ When the singularity occurs, the Pumper will have already finished.
 //! It respects `None`. It knows when to let go. Unlike the rest of us.
 
-use super::Worker;
+use super::{BufferBudget, Worker};
 use crate::backends::{Source, SourceBackend};
-use crate::Page;
+use crate::{Page, PageOrigin};
 use anyhow::{Context, Result};
-use async_channel::Sender;
+use async_channel::{Receiver, Sender};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use tokio::task::JoinHandle;
-use tracing::debug;
+use tracing::{debug, info};
+
+/// 🛑 The ShutdownLever — a shared atomic bool. Flip it to `true` and the Pumper stops
+/// pulling new feeds after the current one. Everything already in flight still drains:
+/// the pumper exits → ch1 closes → joiners flush → ch2 closes → drainers finish.
+///
+/// 🧠 Knowledge graph: same Arc-atomic pattern as `FlowKnob`. Pulled by `run_with_shutdown()`
+/// when the embedder's shutdown future resolves. Checked *between* pumps, never mid-pump,
+/// so a source is never cancelled halfway through a read. 🚪
+pub type ShutdownLever = Arc<AtomicBool>;
+
+/// ⏸️ The PauseSwitch — while it's `true`, the Pumper holds its next pump. What's already in
+/// flight keeps draining, so a pause empties the pipeline instead of freezing it mid-payload.
+///
+/// 🧠 Knowledge graph: rides on `AppConfig::pause` (builder-only, like `progress`), so
+/// whoever started the run can flip it — the CLI dashboard's `p` key does. A pulled
+/// `ShutdownLever` wins over a pause.
+pub type PauseSwitch = Arc<AtomicBool>;
+
+/// ⏱️ How often a paused pumper checks whether it may go on
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+
+/// 🎟️ Sources waiting for a pumper. The Foreman fills it and drops its sender, so a pumper
+/// that finds it empty knows there's nothing left to read.
+pub type SourceQueue = Receiver<SourceBackend>;
+
+/// 📄 What ch1 carries: a page, and where in the source it came from — when the source knows
+/// (`Source::take_origin`). The Joiner only looks at the origin when a cast fails.
+pub type SourcedPage = (Page, Option<PageOrigin>);
 
 /// 🚰 The Pumper: reads raw feeds from a backend, sends each `String` to the channel.
 ///
@@ -35,44 +70,82 @@ use tracing::debug;
 /// Like a barista, but for data. And less tips. And the drinks are just raw bytes.
 #[derive(Debug)]
 pub struct Pumper {
-    tx: Sender<Page>,
-    source: SourceBackend,
+    tx: Sender<SourcedPage>,
+    sources: SourceQueue,
+    shutdown: ShutdownLever,
+    /// ⏸️ Held between pumps while `true` — off unless someone shares a switch
+    pause: PauseSwitch,
+    /// 🧮 Optional byte budget — each feed is charged before it goes on ch1
+    budget: Option<BufferBudget>,
 }
 
 impl Pumper {
     /// 🏗️ Constructs a new Pumper — the headwaters of the pipeline.
     ///
-    /// Give it a sender (where the raw feeds go) and a source backend (where the data comes from).
+    /// Give it a sender (where the raw feeds go) and a queue of sources (where the data comes from).
     /// It will faithfully poll `pump()` like a golden retriever waiting by the door.
-    /// `None` = the retriever goes home. The channel closes. 🐕
-    pub fn new(tx: Sender<Page>, source: SourceBackend) -> Self {
-        Self { tx, source }
+    /// `None` = on to the next source. No sources left = the retriever goes home. 🐕
+    /// A pulled `shutdown` lever also sends the retriever home — just earlier. 🛑
+    pub fn new(tx: Sender<SourcedPage>, sources: SourceQueue, shutdown: ShutdownLever) -> Self {
+        Self { tx, sources, shutdown, pause: PauseSwitch::default(), budget: None }
+    }
+
+    /// ⏸️ Share a pause switch — flip it on and the pumper waits before its next pump.
+    pub fn with_pause(mut self, pause: PauseSwitch) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// 🧮 Charge every feed to a shared byte budget, waiting while the pipeline is full.
+    pub fn with_buffer_budget(mut self, budget: Option<BufferBudget>) -> Self {
+        self.budget = budget;
+        self
     }
 }
 
 impl Worker for Pumper {
-    fn start(mut self) -> JoinHandle<Result<()>> {
-        tokio::spawn(async move {
+    fn start(self) -> JoinHandle<Result<()>> {
+        super::spawn_named("pumper", async move {
             debug!("🚀 Pumper started pumping raw feeds into the channel...");
-            loop {
-                match self
-                    .source
-                    .pump()
-                    .await
-                    .context("💀 Pumper failed to get next feed — the well collapsed")?
-                {
-                    Some(feed) => {
-                        debug!("📤 Pumper sending {} byte feed to channel", feed.len());
-                        self.tx.send(feed).await?;
+            // 🎟️ Next in line, please. An empty, closed queue means every source is taken
+            while let Ok(mut source) = self.sources.recv().await {
+                loop {
+                    // ⏸️ Paused — hold here, between pumps, until unpaused or shut down
+                    if self.pause.load(Ordering::Relaxed) {
+                        info!("⏸️ Pumper: paused — in-flight work keeps draining");
+                        while self.pause.load(Ordering::Relaxed) && !self.shutdown.load(Ordering::Relaxed) {
+                            tokio::time::sleep(PAUSE_POLL).await;
+                        }
+                        info!("▶️ Pumper: resumed");
+                    }
+                    // 🛑 Shutdown requested — stop pumping, let the cascade drain what's in flight
+                    if self.shutdown.load(Ordering::Relaxed) {
+                        info!("🛑 Pumper: shutdown lever pulled — no new feeds, draining what's in flight");
+                        return Ok(());
                     }
-                    None => {
-                        // 🏁 EOF — source is exhausted. Just break out of the loop.
-                        // tx drops when the async block exits, which implicitly closes ch1
-                        // (this is the ONLY Sender — foreman moved it here, no clones).
-                        // No .close() needed — RAII does the work. Like clocking out by
-                        // walking away. The channel knows. 💤
-                        debug!("🏁 Pumper: None = EOF. The well is dry. tx will drop on exit.");
-                        break;
+                    match source
+                        .pump()
+                        .await
+                        .context("💀 Pumper failed to get next feed — the well collapsed")?
+                    {
+                        Some(feed) => {
+                            debug!("📤 Pumper sending {} byte feed to channel", feed.len());
+                            // 🧮 Backpressure by bytes, not just by page count
+                            if let Some(ref the_budget) = self.budget {
+                                the_budget.reserve(feed.len()).await;
+                            }
+                            let the_origin = source.take_origin();
+                            self.tx.send((feed, the_origin)).await?;
+                        }
+                        None => {
+                            // 🏁 EOF — this source is exhausted. On to the next one, if any.
+                            // Once the queue is empty our tx clone drops when the async block
+                            // exits; the last pumper's drop implicitly closes ch1.
+                            // No .close() needed — RAII does the work. Like clocking out by
+                            // walking away. The channel knows. 💤
+                            debug!("🏁 Pumper: None = EOF. The well is dry. Checking the queue.");
+                            break;
+                        }
                     }
                 }
             }
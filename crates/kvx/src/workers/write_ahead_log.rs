@@ -0,0 +1,122 @@
+// Copyright (C) 2026 Kravex, Inc.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file and at www.mariadb.com/bsl11.
+//! 📼 The write-ahead log — every payload goes to disk before it goes to the sink.
+//!
+//! 🎬 *[hour three. forty payloads in flight. the node reboots for a kernel patch.]*
+//! *[which of the forty landed? nobody knows. the run starts again from line zero.]*
+//! *[it is hour three again. it is always hour three.]*
+//!
+//! 🧠 Knowledge graph:
+//! - `[drainer] wal_dir = "..."` turns it on. Off by default — it writes every payload twice
+//! - One file per payload: `{seq:020}.payload`, written as `.tmp` and renamed, so a file
+//!   with the final name is always a whole payload
+//! - Drainer: `journal()` before `drain_with_split`, `complete()` (delete) once the sink has
+//!   acknowledged it — or once a partial `DocumentsRejected` means resending would duplicate
+//! - Foreman: `pending()` at startup → the first drainer replays them, oldest first, before
+//!   it takes anything new from ch2
+//! - At-least-once: a payload that landed just before the crash is sent again. Stable `_id`s
+//!   make that harmless; auto-generated ones make it a duplicate 🦆
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+
+use crate::Payload;
+
+const ENTRY_EXTENSION: &str = "payload";
+const PARTIAL_EXTENSION: &str = "tmp";
+
+/// 📼 A directory of payloads that were handed to a sink and not yet acknowledged.
+/// Cheap to clone; clones share the sequence.
+#[derive(Debug, Clone)]
+pub struct WriteAheadLog {
+    the_dir: PathBuf,
+    the_next_seq: Arc<AtomicU64>,
+}
+
+impl WriteAheadLog {
+    /// 🚪 Open (or create) the log in `dir`. New entries are numbered after any left behind,
+    /// and half-written ones — never sent — are swept away.
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("💀 Couldn't create the write-ahead log directory '{}'", dir.display()))?;
+        let mut the_last_seq = None;
+        for the_entry in std::fs::read_dir(dir)? {
+            let the_path = the_entry?.path();
+            match the_path.extension().and_then(|e| e.to_str()) {
+                Some(PARTIAL_EXTENSION) => std::fs::remove_file(&the_path)?,
+                Some(ENTRY_EXTENSION) => the_last_seq = the_last_seq.max(seq_of(&the_path)),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            the_dir: dir.to_path_buf(),
+            the_next_seq: Arc::new(AtomicU64::new(the_last_seq.map_or(0, |s| s + 1))),
+        })
+    }
+
+    /// 📋 Every unacknowledged payload, oldest first.
+    pub fn pending(&self) -> Result<Vec<PathBuf>> {
+        let mut the_entries: Vec<(u64, PathBuf)> = std::fs::read_dir(&self.the_dir)?
+            .filter_map(|the_entry| {
+                let the_path = the_entry.ok()?.path();
+                (the_path.extension()? == ENTRY_EXTENSION).then_some((seq_of(&the_path)?, the_path))
+            })
+            .collect();
+        the_entries.sort();
+        Ok(the_entries.into_iter().map(|(_, the_path)| the_path).collect())
+    }
+
+    /// ✍️ Put `payload` on disk before it goes anywhere else. Returns the entry to `complete`.
+    pub async fn journal(&self, payload: &Payload) -> Result<PathBuf> {
+        let the_seq = self.the_next_seq.fetch_add(1, Ordering::Relaxed);
+        let the_entry = self.the_dir.join(format!("{:020}.{}", the_seq, ENTRY_EXTENSION));
+        let the_partial = the_entry.with_extension(PARTIAL_EXTENSION);
+        tokio::fs::write(&the_partial, payload.to_bytes())
+            .await
+            .with_context(|| format!("💀 Couldn't journal a payload to '{}'", the_partial.display()))?;
+        tokio::fs::rename(&the_partial, &the_entry).await?;
+        Ok(the_entry)
+    }
+
+    /// ✅ The sink has it — forget the entry.
+    pub async fn complete(&self, entry: &Path) -> Result<()> {
+        tokio::fs::remove_file(entry)
+            .await
+            .with_context(|| format!("💀 Couldn't clear write-ahead log entry '{}'", entry.display()))
+    }
+}
+
+/// 🔢 `00000000000000000042.payload` → 42
+fn seq_of(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn the_one_where_the_log_remembers_what_the_sink_never_saw() -> Result<()> {
+        let the_dir = tempfile::tempdir()?;
+        let the_log = WriteAheadLog::open(the_dir.path())?;
+        let the_first = the_log.journal(&Payload::from("first".to_string())).await?;
+        let the_second = the_log.journal(&Payload::from("second".to_string())).await?;
+        the_log.complete(&the_first).await?;
+        std::fs::write(the_dir.path().join("00000000000000000009.tmp"), "half a payl")?;
+
+        // -- 💥 the process dies here; the next run opens the same directory
+        let the_reopened = WriteAheadLog::open(the_dir.path())?;
+        assert_eq!(the_reopened.pending()?, vec![the_second.clone()]);
+        assert_eq!(std::fs::read_to_string(&the_second)?, "second");
+        assert!(!the_dir.path().join("00000000000000000009.tmp").exists(), "🧹 never sent, never replayed");
+        let the_third = the_reopened.journal(&Payload::from("third".to_string())).await?;
+        assert_eq!(seq_of(&the_third), Some(2), "🔢 numbering carries on after the leftovers");
+        assert_eq!(the_reopened.pending()?, vec![the_second, the_third]);
+        Ok(())
+    }
+}